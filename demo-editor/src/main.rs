@@ -41,5 +41,10 @@ fn main() -> eframe::Result<()> {
     let schema_set = editor.schema_set().clone();
     inspectors::register_inspectors(&schema_set, editor.inspector_registry_mut());
 
+    // Deletes all build data and builds everything from scratch
+    if std::env::args().any(|arg| arg == "--rebuild-all") {
+        editor.rebuild_all(true).unwrap();
+    }
+
     editor.run()
 }
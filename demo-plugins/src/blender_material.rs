@@ -53,7 +53,7 @@ impl Importer for BlenderMaterialImporter {
         &self,
         context: ScanContext,
     ) -> PipelineResult<()> {
-        let json_str = std::fs::read_to_string(context.path)?;
+        let json_str = context.read_source_file_to_string()?;
         let json_data: MaterialJsonFileFormat = {
             profiling::scope!("serde_json::from_str");
             serde_json::from_str(&json_str)?
@@ -87,10 +87,10 @@ impl Importer for BlenderMaterialImporter {
         //
         // Read the file
         //
-        let json_str = std::fs::read_to_string(context.path)?;
+        let json_str = context.source_file_string()?;
         let json_data: MaterialJsonFileFormat = {
             profiling::scope!("serde_json::from_str");
-            serde_json::from_str(&json_str)?
+            serde_json::from_str(json_str)?
         };
 
         //
@@ -1,5 +1,5 @@
 pub use super::*;
-use std::io::BufReader;
+use std::io::Cursor;
 use std::path::PathBuf;
 
 use crate::generated::{MeshAdvMeshAssetRecord, MeshAdvMeshImportedDataRecord};
@@ -7,7 +7,7 @@ use crate::push_buffer::PushBuffer;
 use hydrate_base::b3f::B3FReader;
 use hydrate_data::{ImportableName, Record};
 use hydrate_model::pipeline::Importer;
-use hydrate_model::pipeline::{AssetPlugin, ImportContext, ScanContext};
+use hydrate_model::pipeline::{AssetPlugin, ImportContext, ImportWarningSeverity, ScanContext};
 use hydrate_pipeline::{AssetPluginSetupContext, HashMap, PipelineResult};
 use serde::{Deserialize, Serialize};
 use type_uuid::TypeUuid;
@@ -68,11 +68,11 @@ impl Importer for BlenderMeshImporter {
         &self,
         context: ScanContext,
     ) -> PipelineResult<()> {
-        let file = std::fs::File::open(context.path)?;
-        let mut buf_reader = BufReader::new(file);
-        let b3f_reader = B3FReader::new(&mut buf_reader)?
+        let file_bytes = context.read_source_file()?;
+        let mut reader = Cursor::new(file_bytes);
+        let b3f_reader = B3FReader::new(&mut reader)?
             .ok_or("Blender Mesh Import error, mesh file format not recognized")?;
-        let json_block = b3f_reader.read_block(&mut buf_reader, 0)?;
+        let json_block = b3f_reader.read_block(&mut reader, 0)?;
         let mesh_as_json: MeshJson = {
             profiling::scope!("serde_json::from_slice");
             serde_json::from_slice(&json_block).map_err(|e| e.to_string())?
@@ -97,11 +97,10 @@ impl Importer for BlenderMeshImporter {
         //
         // Read the file
         //
-        let file = std::fs::File::open(context.path)?;
-        let mut buf_reader = BufReader::new(file);
-        let b3f_reader = B3FReader::new(&mut buf_reader)?
+        let mut reader = Cursor::new(context.source_file_bytes());
+        let b3f_reader = B3FReader::new(&mut reader)?
             .ok_or("Blender Mesh Import error, mesh file format not recognized")?;
-        let json_block = b3f_reader.read_block(&mut buf_reader, 0)?;
+        let json_block = b3f_reader.read_block(&mut reader, 0)?;
         let mesh_as_json: MeshJson = {
             profiling::scope!("serde_json::from_slice");
             serde_json::from_slice(&json_block).map_err(|e| e.to_string())?
@@ -123,23 +122,32 @@ impl Importer for BlenderMeshImporter {
         }
 
         for mesh_part in &mesh_as_json.mesh_parts {
+            if mesh_part.tangent.is_none() {
+                context.add_importable_warning(
+                    ImportableName::default(),
+                    ImportWarningSeverity::Info,
+                    Some("mesh_parts"),
+                    "Mesh part has no tangent data, tangents will be generated at build time",
+                );
+            }
+
             //
             // Get byte slices of all input data for this mesh part
             //
             let positions_bytes = b3f_reader.read_block(
-                &mut buf_reader,
+                &mut reader,
                 mesh_part.position.ok_or("No position data")? as usize,
             )?;
             let normals_bytes = b3f_reader.read_block(
-                &mut buf_reader,
+                &mut reader,
                 mesh_part.normal.ok_or("No normal data")? as usize,
             )?;
             let tex_coords_bytes = b3f_reader.read_block(
-                &mut buf_reader,
+                &mut reader,
                 *mesh_part.uv.get(0).ok_or("No texture coordinate data")? as usize,
             )?;
             let part_indices_bytes =
-                b3f_reader.read_block(&mut buf_reader, mesh_part.indices as usize)?;
+                b3f_reader.read_block(&mut reader, mesh_part.indices as usize)?;
 
             //
             // Get strongly typed slices of all input data for this mesh part
@@ -138,6 +138,7 @@ impl JobProcessor for ExampleBuildJobGather {
     ) -> PipelineResult<JobEnumeratedDependencies> {
         Ok(JobEnumeratedDependencies {
             upstream_jobs: context.input.scatter_tasks.clone(),
+            ..Default::default()
         })
     }
 
@@ -1054,8 +1054,12 @@ impl RecordAccessor for GpuImageAssetAccessor {
 }
 
 impl GpuImageAssetAccessor {
-    pub fn compress(&self) -> BooleanFieldAccessor {
-        BooleanFieldAccessor::new(self.0.push("compress"))
+    pub fn format(&self) -> EnumFieldAccessor::<GpuImageFormatEnum> {
+        EnumFieldAccessor::<GpuImageFormatEnum>::new(self.0.push("format"))
+    }
+
+    pub fn generate_mips(&self) -> BooleanFieldAccessor {
+        BooleanFieldAccessor::new(self.0.push("generate_mips"))
     }
 }
 pub struct GpuImageAssetRef<'a>(PropertyPath, DataContainerRef<'a>);
@@ -1073,8 +1077,12 @@ impl<'a> RecordRef for GpuImageAssetRef<'a> {
 }
 
 impl<'a> GpuImageAssetRef<'a> {
-    pub fn compress(&self) -> BooleanFieldRef {
-        BooleanFieldRef::new(self.0.push("compress"), self.1.clone())
+    pub fn format(&self) -> EnumFieldRef::<GpuImageFormatEnum> {
+        EnumFieldRef::<GpuImageFormatEnum>::new(self.0.push("format"), self.1.clone())
+    }
+
+    pub fn generate_mips(&self) -> BooleanFieldRef {
+        BooleanFieldRef::new(self.0.push("generate_mips"), self.1.clone())
     }
 }
 pub struct GpuImageAssetRefMut<'a>(PropertyPath, Rc<RefCell<DataContainerRefMut<'a>>>);
@@ -1092,8 +1100,12 @@ impl<'a> RecordRefMut for GpuImageAssetRefMut<'a> {
 }
 
 impl<'a> GpuImageAssetRefMut<'a> {
-    pub fn compress(self: &'a Self) -> BooleanFieldRefMut {
-        BooleanFieldRefMut::new(self.0.push("compress"), &self.1)
+    pub fn format(self: &'a Self) -> EnumFieldRefMut::<GpuImageFormatEnum> {
+        EnumFieldRefMut::<GpuImageFormatEnum>::new(self.0.push("format"), &self.1)
+    }
+
+    pub fn generate_mips(self: &'a Self) -> BooleanFieldRefMut {
+        BooleanFieldRefMut::new(self.0.push("generate_mips"), &self.1)
     }
 }
 pub struct GpuImageAssetRecord(PropertyPath, Rc<RefCell<Option<DataContainer>>>);
@@ -1115,8 +1127,42 @@ impl Record for GpuImageAssetRecord {
 }
 
 impl GpuImageAssetRecord {
-    pub fn compress(self: &Self) -> BooleanField {
-        BooleanField::new(self.0.push("compress"), &self.1)
+    pub fn format(self: &Self) -> EnumField::<GpuImageFormatEnum> {
+        EnumField::<GpuImageFormatEnum>::new(self.0.push("format"), &self.1)
+    }
+
+    pub fn generate_mips(self: &Self) -> BooleanField {
+        BooleanField::new(self.0.push("generate_mips"), &self.1)
+    }
+}
+#[derive(Copy, Clone)]
+pub enum GpuImageFormatEnum {
+    Rgba8,
+    Bc7,
+}
+
+impl Enum for GpuImageFormatEnum {
+    fn to_symbol_name(&self) -> &'static str {
+        match self {
+            GpuImageFormatEnum::Rgba8 => "Rgba8",
+            GpuImageFormatEnum::Bc7 => "Bc7",
+        }
+    }
+
+    fn from_symbol_name(str: &str) -> Option<GpuImageFormatEnum> {
+        match str {
+            "Rgba8" => Some(GpuImageFormatEnum::Rgba8),
+            "RGBA8" => Some(GpuImageFormatEnum::Rgba8),
+            "Bc7" => Some(GpuImageFormatEnum::Bc7),
+            "BC7" => Some(GpuImageFormatEnum::Bc7),
+            _ => None,
+        }
+    }
+}
+
+impl GpuImageFormatEnum {
+    pub fn schema_name() -> &'static str {
+        "GpuImageFormat"
     }
 }
 #[derive(Default)]
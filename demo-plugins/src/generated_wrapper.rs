@@ -1,3 +1,4 @@
+use demo_types::image::GpuImageFormat;
 use demo_types::mesh_adv::{MeshAdvBlendMethod, MeshAdvShadowMethod};
 use hydrate_data::*;
 use hydrate_model::{DataContainer, DataContainerRef, DataContainerRefMut, DataSetResult};
@@ -187,3 +188,12 @@ impl Into<MeshAdvShadowMethod> for MeshAdvShadowMethodEnum {
         }
     }
 }
+
+impl Into<GpuImageFormat> for GpuImageFormatEnum {
+    fn into(self) -> GpuImageFormat {
+        match self {
+            GpuImageFormatEnum::Rgba8 => GpuImageFormat::Rgba8,
+            GpuImageFormatEnum::Bc7 => GpuImageFormat::Bc7,
+        }
+    }
+}
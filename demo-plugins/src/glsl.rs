@@ -15,6 +15,7 @@ use hydrate_pipeline::{
 use serde::{Deserialize, Serialize};
 use shaderc::IncludeType;
 use type_uuid::TypeUuid;
+use uuid::Uuid;
 
 fn range_of_line_at_position(
     code: &[char],
@@ -481,7 +482,7 @@ impl Importer for GlslSourceFileImporter {
         context: ScanContext,
     ) -> PipelineResult<()> {
         log::debug!("GlslSourceFileImporter reading file {:?}", context.path);
-        let code = std::fs::read_to_string(context.path)?;
+        let code = context.read_source_file_to_string()?;
         let code_chars: Vec<_> = code.chars().collect();
 
         let importable = context.add_default_importable::<GlslSourceFileAssetRecord>()?;
@@ -500,7 +501,7 @@ impl Importer for GlslSourceFileImporter {
         //
         // Read the file
         //
-        let code = std::fs::read_to_string(context.path)?;
+        let code = context.source_file_string()?.to_string();
 
         //
         // Create import data
@@ -671,6 +672,10 @@ impl Builder for GlslBuildTargetBuilder {
         GlslBuildTargetAssetRecord::schema_name()
     }
 
+    fn artifact_types(&self) -> Vec<Uuid> {
+        vec![Uuid::from_bytes(GlslBuildTargetBuiltData::UUID)]
+    }
+
     fn start_jobs(
         &self,
         context: BuilderContext,
@@ -8,6 +8,7 @@ use hydrate_pipeline::{
 };
 use serde::{Deserialize, Serialize};
 use type_uuid::TypeUuid;
+use uuid::Uuid;
 
 #[derive(Hash, Serialize, Deserialize)]
 pub struct GpuBufferJobInput {
@@ -62,6 +63,10 @@ impl Builder for GpuBufferBuilder {
         GpuBufferAssetRecord::schema_name()
     }
 
+    fn artifact_types(&self) -> Vec<Uuid> {
+        vec![Uuid::from_bytes(GpuBufferBuiltData::UUID)]
+    }
+
     fn start_jobs(
         &self,
         context: BuilderContext,
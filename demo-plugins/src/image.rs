@@ -1,8 +1,7 @@
 pub use super::*;
 use ::image::GenericImageView;
-use std::sync::Arc;
 
-use super::generated::{GpuImageAssetRecord, GpuImageImportedDataRecord};
+use super::generated::{GpuImageAssetRecord, GpuImageFormatEnum, GpuImageImportedDataRecord};
 use ::image::Rgba;
 use demo_types::image::*;
 use hydrate_data::Record;
@@ -17,6 +16,7 @@ use hydrate_pipeline::{
 };
 use serde::{Deserialize, Serialize};
 use type_uuid::TypeUuid;
+use uuid::Uuid;
 
 #[derive(TypeUuid, Default)]
 #[uuid = "e7c83acb-f73b-4b3c-b14d-fe5cc17c0fa3"]
@@ -24,13 +24,22 @@ pub struct GpuImageImporter;
 
 impl Importer for GpuImageImporter {
     fn supported_file_extensions(&self) -> &[&'static str] {
-        &["png", "jpg", "tif"]
+        &["png", "jpg", "tga", "tif"]
     }
 
     fn scan_file(
         &self,
         context: ScanContext,
     ) -> PipelineResult<()> {
+        // Only the header needs to be decoded to get the dimensions
+        let source_file_bytes = context.read_source_file()?;
+        let (width, height) = ::image::io::Reader::new(std::io::Cursor::new(&source_file_bytes))
+            .with_guessed_format()
+            .map_err(|x| x.to_string())?
+            .into_dimensions()
+            .map_err(|x| x.to_string())?;
+        context.info(format!("Image is {}x{}", width, height));
+
         context.add_default_importable::<GpuImageAssetRecord>()?;
         Ok(())
     }
@@ -42,7 +51,8 @@ impl Importer for GpuImageImporter {
         //
         // Read the file
         //
-        let decoded_image = ::image::open(context.path).map_err(|x| x.to_string())?;
+        let decoded_image =
+            ::image::load_from_memory(context.source_file_bytes()).map_err(|x| x.to_string())?;
 
         let (width, height) = decoded_image.dimensions();
         let image_bytes = decoded_image.into_rgba8().to_vec();
@@ -59,7 +69,8 @@ impl Importer for GpuImageImporter {
         // Create the default asset
         //
         let default_asset = GpuImageAssetRecord::new_builder(context.schema_set);
-        default_asset.compress().set(false)?;
+        default_asset.format().set(GpuImageFormatEnum::Rgba8)?;
+        default_asset.generate_mips().set(false)?;
 
         //
         // Return the created assets
@@ -70,6 +81,152 @@ impl Importer for GpuImageImporter {
     }
 }
 
+// Number of mip levels in a full chain down to 1x1
+fn mip_level_count(
+    width: u32,
+    height: u32,
+) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
+
+// Produces the mip levels as RGBA8, each level is half the size of the previous one
+fn build_rgba8_mips(
+    image_bytes: &[u8],
+    width: u32,
+    height: u32,
+    generate_mips: bool,
+) -> PipelineResult<Vec<(u32, u32, Vec<u8>)>> {
+    let mut mip_image = ::image::RgbaImage::from_vec(width, height, image_bytes.to_vec())
+        .ok_or_else(|| {
+            format!(
+                "Image data is {} bytes, expected {}x{} RGBA8",
+                image_bytes.len(),
+                width,
+                height
+            )
+        })?;
+
+    let level_count = if generate_mips {
+        mip_level_count(width, height)
+    } else {
+        1
+    };
+
+    let mut levels = Vec::with_capacity(level_count as usize);
+    for _ in 1..level_count {
+        let next_width = (mip_image.width() / 2).max(1);
+        let next_height = (mip_image.height() / 2).max(1);
+        let next_image = ::image::imageops::resize(
+            &mip_image,
+            next_width,
+            next_height,
+            ::image::imageops::FilterType::Triangle,
+        );
+        levels.push((mip_image.width(), mip_image.height(), mip_image.into_raw()));
+        mip_image = next_image;
+    }
+    levels.push((mip_image.width(), mip_image.height(), mip_image.into_raw()));
+
+    Ok(levels)
+}
+
+// Compresses to UASTC with basis, then transcodes each mip level to BC7
+fn build_bc7_mips(
+    image_bytes: &[u8],
+    width: u32,
+    height: u32,
+    generate_mips: bool,
+) -> PipelineResult<Vec<(u32, u32, Vec<u8>)>> {
+    profiling::scope!("Compressing Image");
+    let mut compressor_params = basis_universal::CompressorParams::new();
+    compressor_params.set_basis_format(basis_universal::BasisTextureFormat::UASTC4x4);
+    compressor_params.set_generate_mipmaps(generate_mips);
+    compressor_params.set_color_space(basis_universal::ColorSpace::Srgb);
+    compressor_params.set_uastc_quality_level(basis_universal::UASTC_QUALITY_DEFAULT);
+
+    let mut source_image = compressor_params.source_image_mut(0);
+
+    source_image.init(image_bytes, width, height, 4);
+    let mut compressor = basis_universal::Compressor::new(4);
+    unsafe {
+        compressor.init(&compressor_params);
+        log::debug!("Compressing texture");
+        compressor
+            .process()
+            .map_err(|e| format!("Compressor process() failed {:?}", e))?;
+        log::debug!("Compressed texture");
+    }
+    let basis_file = compressor.basis_file();
+
+    basis_universal::transcoder_init();
+    let mut transcoder = basis_universal::Transcoder::new();
+    transcoder
+        .prepare_transcoding(basis_file)
+        .map_err(|_| "Could not prepare compressed image for transcoding".to_string())?;
+
+    let mut levels = Vec::default();
+    for level_index in 0..transcoder.image_level_count(basis_file, 0) {
+        let level_description = transcoder
+            .image_level_description(basis_file, 0, level_index)
+            .ok_or_else(|| format!("Compressed image is missing mip level {}", level_index))?;
+        let level_bytes = transcoder
+            .transcode_image_level(
+                basis_file,
+                basis_universal::TranscoderTextureFormat::BC7_RGBA,
+                basis_universal::TranscodeParameters {
+                    image_index: 0,
+                    level_index,
+                    ..Default::default()
+                },
+            )
+            .map_err(|e| format!("Transcoding mip level {} failed {:?}", level_index, e))?;
+        levels.push((
+            level_description.original_width,
+            level_description.original_height,
+            level_bytes,
+        ));
+    }
+    transcoder.end_transcoding();
+
+    Ok(levels)
+}
+
+// Converts RGBA8 pixels to the target format and packs all mip levels into one buffer, recording
+// where each level is
+pub fn build_gpu_image_data(
+    image_bytes: &[u8],
+    width: u32,
+    height: u32,
+    format: GpuImageFormat,
+    generate_mips: bool,
+) -> PipelineResult<GpuImageAssetData> {
+    let levels = match format {
+        GpuImageFormat::Rgba8 => build_rgba8_mips(image_bytes, width, height, generate_mips)?,
+        GpuImageFormat::Bc7 => build_bc7_mips(image_bytes, width, height, generate_mips)?,
+    };
+
+    let mut packed_bytes = Vec::with_capacity(levels.iter().map(|x| x.2.len()).sum());
+    let mut subresources = Vec::with_capacity(levels.len());
+    for (mip_level, (level_width, level_height, level_bytes)) in levels.into_iter().enumerate() {
+        subresources.push(GpuImageSubresource {
+            mip_level: mip_level as u32,
+            width: level_width,
+            height: level_height,
+            offset: packed_bytes.len() as u64,
+            size: level_bytes.len() as u64,
+        });
+        packed_bytes.extend_from_slice(&level_bytes);
+    }
+
+    Ok(GpuImageAssetData {
+        image_bytes: packed_bytes,
+        width,
+        height,
+        format,
+        subresources,
+    })
+}
+
 #[derive(Hash, Serialize, Deserialize)]
 pub struct GpuImageJobInput {
     pub asset_id: AssetId,
@@ -89,7 +246,7 @@ impl JobProcessor for GpuImageJobProcessor {
     type OutputT = GpuImageJobOutput;
 
     fn version(&self) -> u32 {
-        1
+        2
     }
 
     fn run<'a>(
@@ -100,7 +257,8 @@ impl JobProcessor for GpuImageJobProcessor {
         // Read asset properties
         //
         let asset = context.asset::<GpuImageAssetRecord>(context.input.asset_id)?;
-        let compressed = asset.compress().get()?;
+        let format: GpuImageFormat = asset.format().get()?.into();
+        let generate_mips = asset.generate_mips().get()?;
 
         //
         // Read imported data
@@ -113,43 +271,10 @@ impl JobProcessor for GpuImageJobProcessor {
         let height = imported_data.height().get()?;
 
         //
-        // Compress the image, or just return the raw image bytes
-        //
-        let image_bytes = if compressed {
-            profiling::scope!("Compressing Image");
-            let mut compressor_params = basis_universal::CompressorParams::new();
-            compressor_params.set_basis_format(basis_universal::BasisTextureFormat::UASTC4x4);
-            compressor_params.set_generate_mipmaps(true);
-            compressor_params.set_color_space(basis_universal::ColorSpace::Srgb);
-            compressor_params.set_uastc_quality_level(basis_universal::UASTC_QUALITY_DEFAULT);
-
-            let mut source_image = compressor_params.source_image_mut(0);
-
-            source_image.init(&image_bytes, width, height, 4);
-            let mut compressor = basis_universal::Compressor::new(4);
-            unsafe {
-                compressor.init(&compressor_params);
-                log::debug!("Compressing texture");
-                compressor
-                    .process()
-                    .map_err(|e| format!("Compressor process() failed {:?}", e))?;
-                log::debug!("Compressed texture");
-            }
-            let compressed_basis_data = Arc::new(compressor.basis_file().to_vec());
-            compressed_basis_data
-        } else {
-            //log::debug!("Not compressing texture");
-            (*image_bytes).clone()
-        };
-
-        //
-        // Create the processed data
+        // Convert to the target format and lay out the mip levels
         //
-        let processed_data = GpuImageAssetData {
-            image_bytes: (*image_bytes).clone(),
-            width,
-            height,
-        };
+        let processed_data =
+            build_gpu_image_data(&image_bytes, width, height, format, generate_mips)?;
 
         //
         // Serialize and return
@@ -169,6 +294,10 @@ impl Builder for GpuImageBuilder {
         GpuImageAssetRecord::schema_name()
     }
 
+    fn artifact_types(&self) -> Vec<Uuid> {
+        vec![Uuid::from_bytes(GpuImageAssetData::UUID)]
+    }
+
     fn start_jobs(
         &self,
         context: BuilderContext,
@@ -264,3 +393,128 @@ impl AssetPlugin for GpuImageAssetPlugin {
             .register_thumbnail_provider::<GpuImageThumbnailProvider>();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hydrate_base::hashing::HashMap;
+    use hydrate_data::{ImportableName, SchemaLinker, SchemaSetBuilder};
+    use hydrate_pipeline::{
+        HydrateProjectConfiguration, ImportSourceFile, ImporterRegistryBuilder, LogEventLevel,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn import_png_and_build_rgba8_with_mips() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .add_source_dir(
+                Path::new(env!("CARGO_MANIFEST_DIR")).join("../demo-editor/data/schema"),
+                "**.json",
+            )
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root_path).unwrap();
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+
+        // A 4x2 red image
+        let mut png_bytes = Vec::default();
+        ::image::DynamicImage::ImageRgba8(::image::RgbaImage::from_pixel(
+            4,
+            2,
+            Rgba([255, 0, 0, 255]),
+        ))
+        .write_to(&mut png_bytes, ::image::ImageOutputFormat::Png)
+        .unwrap();
+        let png_path = root_path.join("red.png");
+        std::fs::write(&png_path, &png_bytes).unwrap();
+
+        // Scanning reports the dimensions
+        let importer_registry = ImporterRegistryBuilder::default().build();
+        let mut scanned_importables = HashMap::default();
+        let mut log_events = Vec::default();
+        GpuImageImporter
+            .scan_file(ScanContext::new(
+                &png_path,
+                &schema_set,
+                &importer_registry,
+                &project_config,
+                &mut scanned_importables,
+                &mut log_events,
+            ))
+            .unwrap();
+        assert!(scanned_importables.contains_key(&ImportableName::default()));
+        assert!(log_events
+            .iter()
+            .any(|x| matches!(x.level, LogEventLevel::Info) && x.message == "Image is 4x2"));
+
+        // Importing stores the pixels as import data
+        let importable_assets = HashMap::default();
+        let mut imported_importables = HashMap::default();
+        let mut warnings = Vec::default();
+        GpuImageImporter
+            .import_file(ImportContext::new(
+                ImportSourceFile {
+                    path: &png_path,
+                    bytes: &png_bytes,
+                },
+                &importable_assets,
+                &schema_set,
+                &project_config,
+                &mut imported_importables,
+                &mut log_events,
+                &mut warnings,
+            ))
+            .unwrap();
+        std::fs::remove_dir_all(&root_path).unwrap();
+
+        let import_data = imported_importables[&ImportableName::default()]
+            .import_data
+            .as_ref()
+            .unwrap();
+        let width = import_data
+            .resolve_property(&schema_set, "width")
+            .unwrap()
+            .as_u32()
+            .unwrap();
+        let height = import_data
+            .resolve_property(&schema_set, "height")
+            .unwrap()
+            .as_u32()
+            .unwrap();
+        let image_bytes = import_data
+            .resolve_property(&schema_set, "image_bytes")
+            .unwrap()
+            .as_bytes()
+            .unwrap()
+            .clone();
+        assert_eq!((width, height), (4, 2));
+
+        // Building with mips packs every level down to 1x1
+        let built_data =
+            build_gpu_image_data(&image_bytes, width, height, GpuImageFormat::Rgba8, true).unwrap();
+        assert_eq!(built_data.format, GpuImageFormat::Rgba8);
+        let layout: Vec<_> = built_data
+            .subresources
+            .iter()
+            .map(|x| (x.mip_level, x.width, x.height, x.offset, x.size))
+            .collect();
+        assert_eq!(
+            layout,
+            vec![(0, 4, 2, 0, 32), (1, 2, 1, 32, 8), (2, 1, 1, 40, 4)]
+        );
+        assert_eq!(built_data.image_bytes.len(), 44);
+        assert_eq!(&built_data.image_bytes[40..44], &[255, 0, 0, 255]);
+
+        // Without mips there is only the full size image
+        let built_data =
+            build_gpu_image_data(&image_bytes, width, height, GpuImageFormat::Rgba8, false)
+                .unwrap();
+        assert_eq!(built_data.subresources.len(), 1);
+        assert_eq!(built_data.image_bytes, *image_bytes);
+    }
+}
@@ -15,6 +15,7 @@ use hydrate_pipeline::{
 };
 use serde::{Deserialize, Serialize};
 use type_uuid::TypeUuid;
+use uuid::Uuid;
 
 #[derive(Hash, Serialize, Deserialize)]
 pub struct MeshAdvMaterialJobInput {
@@ -104,6 +105,10 @@ impl Builder for MeshAdvMaterialBuilder {
         MeshAdvMaterialAssetRecord::schema_name()
     }
 
+    fn artifact_types(&self) -> Vec<Uuid> {
+        vec![Uuid::from_bytes(MeshAdvMaterialData::UUID)]
+    }
+
     fn start_jobs(
         &self,
         context: BuilderContext,
@@ -256,13 +261,15 @@ impl JobProcessor for MeshAdvMeshPreprocessJobProcessor {
         // Vertex Full Buffer
         //
         let vertex_buffer_full_artifact_id = if !all_vertices_full.is_empty() {
-            Some(context.produce_artifact(
+            Some(context.produce_artifact_streaming(
                 context.input.asset_id,
                 Some("full"),
-                MeshAdvBufferAssetData {
-                    resource_type: RafxResourceType::VERTEX_BUFFER,
-                    alignment: std::mem::size_of::<MeshVertexFull>() as u32,
-                    data: all_vertices_full.into_data(),
+                |_handle_factory| {
+                    Ok(MeshAdvBufferAssetData {
+                        resource_type: RafxResourceType::VERTEX_BUFFER,
+                        alignment: std::mem::size_of::<MeshVertexFull>() as u32,
+                        data: all_vertices_full.into_data(),
+                    })
                 },
             ))
         } else {
@@ -273,13 +280,15 @@ impl JobProcessor for MeshAdvMeshPreprocessJobProcessor {
         // Vertex Position Buffer
         //
         let vertex_buffer_position_artifact_id = if !all_vertices_position.is_empty() {
-            Some(context.produce_artifact(
+            Some(context.produce_artifact_streaming(
                 context.input.asset_id,
                 Some("position"),
-                MeshAdvBufferAssetData {
-                    resource_type: RafxResourceType::VERTEX_BUFFER,
-                    alignment: std::mem::size_of::<MeshVertexPosition>() as u32,
-                    data: all_vertices_position.into_data(),
+                |_handle_factory| {
+                    Ok(MeshAdvBufferAssetData {
+                        resource_type: RafxResourceType::VERTEX_BUFFER,
+                        alignment: std::mem::size_of::<MeshVertexPosition>() as u32,
+                        data: all_vertices_position.into_data(),
+                    })
                 },
             ))
         } else {
@@ -323,20 +332,23 @@ impl JobProcessor for MeshAdvMeshPreprocessJobProcessor {
                     })
                 }
 
-                let vertex_full_buffer = if let Some(vertex_buffer_full_artifact_id) =
-                    vertex_buffer_full_artifact_id
-                {
-                    Some(handle_factory.make_handle_to_artifact(vertex_buffer_full_artifact_id?))
-                } else {
-                    None
-                };
+                let vertex_full_buffer =
+                    if let Some(vertex_buffer_full_artifact_id) = vertex_buffer_full_artifact_id {
+                        Some(handle_factory.make_handle_to_artifact_raw(
+                            context.input.asset_id,
+                            vertex_buffer_full_artifact_id?,
+                        ))
+                    } else {
+                        None
+                    };
 
                 let vertex_position_buffer = if let Some(vertex_buffer_position_artifact_id) =
                     vertex_buffer_position_artifact_id
                 {
-                    Some(
-                        handle_factory.make_handle_to_artifact(vertex_buffer_position_artifact_id?),
-                    )
+                    Some(handle_factory.make_handle_to_artifact_raw(
+                        context.input.asset_id,
+                        vertex_buffer_position_artifact_id?,
+                    ))
                 } else {
                     None
                 };
@@ -362,6 +374,13 @@ impl Builder for MeshAdvMeshBuilder {
         MeshAdvMeshAssetRecord::schema_name()
     }
 
+    fn artifact_types(&self) -> Vec<Uuid> {
+        vec![
+            Uuid::from_bytes(MeshAdvMeshAssetData::UUID),
+            Uuid::from_bytes(MeshAdvBufferAssetData::UUID),
+        ]
+    }
+
     fn start_jobs(
         &self,
         context: BuilderContext,
@@ -401,3 +420,56 @@ impl AssetPlugin for MeshAdvAssetPlugin {
             .register_job_processor::<MeshAdvMeshPreprocessJobProcessor>();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hydrate_data::{SchemaLinker, SchemaSetBuilder};
+    use hydrate_pipeline::AssetPluginRegistryBuilders;
+    use std::path::Path;
+
+    #[test]
+    fn builders_report_produced_artifact_types() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .add_source_dir(
+                Path::new(env!("CARGO_MANIFEST_DIR")).join("../demo-editor/data/schema"),
+                "**.json",
+            )
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let registries = AssetPluginRegistryBuilders::new()
+            .register_plugin::<MeshAdvAssetPlugin>()
+            .finish(&schema_set);
+        let builder_registry = &registries.builder_registry;
+        let fingerprint_of = |schema_name: &str| {
+            schema_set
+                .find_named_type(schema_name)
+                .unwrap()
+                .fingerprint()
+        };
+
+        assert_eq!(
+            builder_registry.artifact_types_for_asset(fingerprint_of(
+                MeshAdvMaterialAssetRecord::schema_name()
+            )),
+            &[Uuid::from_bytes(MeshAdvMaterialData::UUID)]
+        );
+        assert_eq!(
+            builder_registry
+                .artifact_types_for_asset(fingerprint_of(MeshAdvMeshAssetRecord::schema_name())),
+            &[
+                Uuid::from_bytes(MeshAdvMeshAssetData::UUID),
+                Uuid::from_bytes(MeshAdvBufferAssetData::UUID)
+            ]
+        );
+
+        // Imported data is not built, so nothing is produced for it
+        assert!(builder_registry
+            .artifact_types_for_asset(fingerprint_of(MeshAdvMeshImportedDataRecord::schema_name()))
+            .is_empty());
+    }
+}
@@ -6,6 +6,7 @@ use hydrate_pipeline::{
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 use type_uuid::{Bytes, TypeUuid};
+use uuid::Uuid;
 
 use super::SimpleData;
 
@@ -95,6 +96,10 @@ impl<T: SimpleData + Sized + Serialize + for<'a> Deserialize<'a> + TypeUuid> Bui
         self.asset_type
     }
 
+    fn artifact_types(&self) -> Vec<Uuid> {
+        vec![Uuid::from_bytes(T::UUID)]
+    }
+
     fn start_jobs(
         &self,
         context: BuilderContext,
@@ -1,5 +1,6 @@
-use super::generated::{AllFieldsAccessor, TransformAccessor, TransformRefAccessor};
+use super::generated::TransformRefAccessor;
 use demo_types::simple_data::*;
+use hydrate_data::read_struct;
 use hydrate_model::pipeline::AssetPlugin;
 use hydrate_pipeline::{AssetPluginSetupContext, DataContainerRef, HandleFactory, PipelineResult};
 
@@ -29,16 +30,7 @@ impl SimpleData for Transform {
         data_container: DataContainerRef,
         _handle_context: HandleFactory,
     ) -> PipelineResult<Self> {
-        let x = TransformAccessor::default();
-        let position = x.position().get_vec3(data_container.clone())?;
-        let rotation = x.rotation().get_vec4(data_container.clone())?;
-        let scale = x.scale().get_vec3(data_container.clone())?;
-
-        Ok(Transform {
-            position,
-            rotation,
-            scale,
-        })
+        Ok(read_struct(data_container)?)
     }
 }
 
@@ -47,16 +39,7 @@ impl SimpleData for AllFields {
         data_container: DataContainerRef,
         _handle_context: HandleFactory,
     ) -> PipelineResult<Self> {
-        let x = AllFieldsAccessor::default();
-        let boolean = x.boolean().get(data_container.clone())?;
-        let int32 = x.i32().get(data_container.clone())?;
-        let int64 = x.i64().get(data_container.clone())?;
-
-        Ok(AllFields {
-            boolean,
-            int32,
-            int64,
-        })
+        Ok(read_struct(data_container)?)
     }
 }
 
@@ -1,10 +1,29 @@
 use serde::{Deserialize, Serialize};
 use type_uuid::TypeUuid;
 
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GpuImageFormat {
+    Rgba8,
+    Bc7,
+}
+
+// The location of a single mip level within image_bytes
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GpuImageSubresource {
+    pub mip_level: u32,
+    pub width: u32,
+    pub height: u32,
+    pub offset: u64,
+    pub size: u64,
+}
+
 #[derive(Serialize, Deserialize, TypeUuid)]
 #[uuid = "1a4dde10-5e60-483d-88fa-4f59752e4524"]
 pub struct GpuImageAssetData {
+    // All mip levels packed together, largest first
     pub image_bytes: Vec<u8>,
     pub width: u32,
     pub height: u32,
+    pub format: GpuImageFormat,
+    pub subresources: Vec<GpuImageSubresource>,
 }
@@ -1,4 +1,5 @@
 use hydrate_base::Handle;
+use hydrate_data::FromHydrateRecord;
 use serde::{Deserialize, Serialize};
 use type_uuid::TypeUuid;
 
@@ -12,7 +13,7 @@ pub struct TransformRef {
     pub transform: Handle<Transform>,
 }
 
-#[derive(Serialize, Deserialize, TypeUuid, Debug)]
+#[derive(Serialize, Deserialize, TypeUuid, Debug, FromHydrateRecord)]
 #[uuid = "da334afa-7af9-4894-8b7e-29defe202e90"]
 pub struct Transform {
     pub position: [f32; 3],
@@ -23,10 +24,12 @@ pub struct Transform {
 // What if we had a way to "bind" raw rust structs to fields? Needs to know how to read and write,
 // but we really just need to provide method of getting a ref and mutable ref to individual fields
 
-#[derive(Serialize, Deserialize, TypeUuid)]
+#[derive(Serialize, Deserialize, TypeUuid, FromHydrateRecord)]
 #[uuid = "df64f515-7e2f-47c2-b4d3-17ec7f2e63c7"]
 pub struct AllFields {
     pub boolean: bool,
+    #[hydrate(rename = "i32")]
     pub int32: i32,
+    #[hydrate(rename = "i64")]
     pub int64: i64,
 }
@@ -0,0 +1,36 @@
+// Measures the throughput of each content hash algorithm on a large buffer. Run with
+// `cargo bench -p hydrate-base --bench content_hash`.
+
+use hydrate_base::hashing::ContentHashAlgorithm;
+use std::hash::BuildHasher;
+use std::hint::black_box;
+use std::time::Instant;
+
+const BUFFER_SIZE: usize = 256 * 1024 * 1024;
+const ITERATIONS: u32 = 4;
+
+fn main() {
+    let buffer: Vec<u8> = (0..BUFFER_SIZE).map(|x| x as u8).collect();
+
+    for algorithm in [
+        ContentHashAlgorithm::SipHash,
+        ContentHashAlgorithm::Xxh3,
+        ContentHashAlgorithm::Blake3,
+    ] {
+        // Warm up caches before timing
+        black_box(algorithm.hash_one(&buffer));
+
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            black_box(algorithm.hash_one(black_box(&buffer)));
+        }
+        let elapsed = start.elapsed();
+
+        let bytes_hashed = BUFFER_SIZE as f64 * ITERATIONS as f64;
+        println!(
+            "{:?}: {:.2} GB/s",
+            algorithm,
+            bytes_hashed / elapsed.as_secs_f64() / 1_000_000_000.0
+        );
+    }
+}
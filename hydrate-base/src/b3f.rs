@@ -11,8 +11,8 @@
 //! [n*len(n)] data (format/encoding/semantics would be implied by file tag). Each block begins at
 //! [x] pad to 16 byte offset
 //!
-//! Endianness is undefined. Use the magic number to detect if endianness is different between
-//! writer/reader
+//! All numbers are little-endian regardless of the host, so files can be written and read on
+//! machines with different architectures
 //!
 //! This format can be encoded into a block, making this structure hierarchical. In this
 //! case, omit the magic number, and use the file tag to optionally indicate the contents
@@ -46,7 +46,7 @@ impl<'a> B3FWriter<'a> {
         version: u32,
     ) -> Self {
         B3FWriter {
-            file_tag: u32::from_ne_bytes(file_tag),
+            file_tag: u32::from_le_bytes(file_tag),
             version,
             blocks: Vec::default(),
         }
@@ -77,16 +77,16 @@ impl<'a> B3FWriter<'a> {
         //
         // 16 byte header
         //
-        writer.write(&0xBB33FF00u32.to_ne_bytes()).unwrap();
-        writer.write(&self.file_tag.to_ne_bytes()).unwrap();
-        writer.write(&self.version.to_ne_bytes()).unwrap();
+        writer.write(&0xBB33FF00u32.to_le_bytes()).unwrap();
+        writer.write(&self.file_tag.to_le_bytes()).unwrap();
+        writer.write(&self.version.to_le_bytes()).unwrap();
         let block_count = self.blocks.len() as u32;
-        writer.write(&block_count.to_ne_bytes()).unwrap();
+        writer.write(&block_count.to_le_bytes()).unwrap();
 
         //
         // A single u64 zero + N u64 block end positions
         //
-        writer.write(&0u64.to_ne_bytes()).unwrap();
+        writer.write(&0u64.to_le_bytes()).unwrap();
 
         let mut block_begin = 0;
         for block in &self.blocks {
@@ -94,7 +94,7 @@ impl<'a> B3FWriter<'a> {
             let block_end = block_begin + block.len();
 
             // Write the ending of the previous block (or 0 for first block)
-            writer.write(&(block_end as u64).to_ne_bytes()).unwrap();
+            writer.write(&(block_end as u64).to_le_bytes()).unwrap();
 
             // Realign to 16 bytes, this is where the next block begins
             block_begin = ((block_end + BLOCK_ALIGNMENT_IN_BYTES - 1) / BLOCK_ALIGNMENT_IN_BYTES)
@@ -107,7 +107,7 @@ impl<'a> B3FWriter<'a> {
         let data_offset =
             HEADER_SIZE_IN_BYTES + ((self.blocks.len() + 1) * BLOCK_LENGTH_SIZE_IN_BYTES);
         if data_offset % 16 == 8 {
-            writer.write(&0u64.to_ne_bytes()).unwrap();
+            writer.write(&0u64.to_le_bytes()).unwrap();
         } else {
             assert!(data_offset % 16 == 0);
         }
@@ -120,7 +120,7 @@ impl<'a> B3FWriter<'a> {
             if block.len() % 16 != 0 {
                 let required_padding = 16 - block.len() % 16;
                 for _ in 0..required_padding {
-                    writer.write(&0u8.to_ne_bytes()).unwrap();
+                    writer.write(&0u8.to_le_bytes()).unwrap();
                 }
             }
         }
@@ -135,7 +135,7 @@ pub struct B3FReader {
 
 impl B3FReader {
     pub fn file_tag_as_u32(&self) -> u32 {
-        u32::from_ne_bytes(self.file_tag.try_into().unwrap())
+        u32::from_le_bytes(self.file_tag.try_into().unwrap())
     }
 
     pub fn file_tag_as_u8(&self) -> &[u8] {
@@ -154,7 +154,7 @@ impl B3FReader {
         reader.seek(SeekFrom::Start(0))?;
         let mut bytes = [0u8; 4];
         reader.read(&mut bytes)?;
-        let magic_number = u32::from_ne_bytes(bytes);
+        let magic_number = u32::from_le_bytes(bytes);
         if magic_number != 0xBB33FF00 {
             return Ok(None);
         }
@@ -163,10 +163,10 @@ impl B3FReader {
         let file_tag = bytes;
 
         reader.read(&mut bytes)?;
-        let version = u32::from_ne_bytes(bytes);
+        let version = u32::from_le_bytes(bytes);
 
         reader.read(&mut bytes)?;
-        let block_count = u32::from_ne_bytes(bytes);
+        let block_count = u32::from_le_bytes(bytes);
 
         Ok(Some(B3FReader {
             file_tag,
@@ -187,9 +187,9 @@ impl B3FReader {
 
         let mut bytes = [0u8; 8];
         reader.read(&mut bytes)?;
-        let mut begin = u64::from_ne_bytes(bytes.try_into().unwrap()) as usize;
+        let mut begin = u64::from_le_bytes(bytes.try_into().unwrap()) as usize;
         reader.read(&mut bytes)?;
-        let end = u64::from_ne_bytes(bytes.try_into().unwrap()) as usize;
+        let end = u64::from_le_bytes(bytes.try_into().unwrap()) as usize;
 
         // Begin position needs to be rounded up to 16-byte offset
         begin = ((begin + BLOCK_ALIGNMENT_IN_BYTES - 1) / BLOCK_ALIGNMENT_IN_BYTES)
@@ -226,3 +226,54 @@ impl B3FReader {
         Ok(&data[block_location])
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn b3f_is_little_endian_on_any_host() {
+        let block_a = [1u8, 2, 3];
+        let block_b = [4u8; 17];
+        let mut writer = B3FWriter::new_from_u8_tag(*b"TEST", 0x01020304);
+        writer.add_block(&block_a);
+        writer.add_block(&block_b);
+        let mut written = Vec::default();
+        writer.write(&mut written);
+
+        // Built byte by byte so the expected layout does not depend on the host
+        let mut expected = Vec::default();
+        expected.extend_from_slice(&[0x00, 0xFF, 0x33, 0xBB]);
+        expected.extend_from_slice(b"TEST");
+        expected.extend_from_slice(&[0x04, 0x03, 0x02, 0x01]);
+        expected.extend_from_slice(&[2, 0, 0, 0]);
+        expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0]);
+        expected.extend_from_slice(&[3, 0, 0, 0, 0, 0, 0, 0]);
+        expected.extend_from_slice(&[33, 0, 0, 0, 0, 0, 0, 0]);
+        expected.extend_from_slice(&[0; 8]);
+        expected.extend_from_slice(&block_a);
+        expected.extend_from_slice(&[0; 13]);
+        expected.extend_from_slice(&block_b);
+        expected.extend_from_slice(&[0; 15]);
+        assert_eq!(written, expected);
+
+        let mut cursor = Cursor::new(expected.as_slice());
+        let reader = B3FReader::new(&mut cursor).unwrap().unwrap();
+        assert_eq!(reader.file_tag_as_u8(), b"TEST");
+        assert_eq!(reader.file_tag_as_u32(), u32::from_le_bytes(*b"TEST"));
+        assert_eq!(reader.version(), 0x01020304);
+        assert_eq!(reader.block_count(), 2);
+        assert_eq!(reader.read_block(&mut cursor, 0).unwrap(), block_a);
+        assert_eq!(
+            reader.read_block_from_slice(&expected, 1).unwrap(),
+            &block_b[..]
+        );
+
+        // A big-endian magic number is not mistaken for a B3F file
+        let mut swapped = expected.clone();
+        swapped[0..4].reverse();
+        assert!(B3FReader::new(&mut Cursor::new(swapped.as_slice()))
+            .unwrap()
+            .is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{ArtifactId, StringHash};
+use crate::{ArtifactId, AssetId, StringHash};
 use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
@@ -9,15 +9,22 @@ use uuid::Uuid;
 #[derive(Serialize, Deserialize)]
 pub struct DebugArtifactManifestDataJson {
     pub artifact_id: ArtifactId,
-    // stored as a string so we can encoded as hex
+    // stored as a string so it can be encoded as hex
     pub build_hash: String,
     pub combined_build_hash: String,
     pub symbol_name: String,
-    // stored as a string so we can encoded as hex. The hash isn't really needed but it's nice
+    // stored as a string so it can be encoded as hex. The hash isn't really needed but it's nice
     // to have in the file for looking up a hash while debugging
     pub symbol_hash: String,
     pub artifact_type: Uuid,
     pub debug_name: String,
+    // The asset the artifact was built for, and a hash of that asset's build inputs at the time,
+    // stored as a string so it can be encoded as hex. Used to estimate the size of the next build.
+    // Manifests written by older versions don't have them.
+    #[serde(default)]
+    pub asset_id: Option<AssetId>,
+    #[serde(default)]
+    pub asset_build_hash: Option<String>,
 }
 
 /// Used to store debug manifest data. It's not needed for the game to function but can be used in
@@ -86,7 +93,8 @@ impl BuiltArtifactHeaderData {
         let bytes = serialized.len();
         // Just
         assert!(bytes <= MAX_HEADER_SIZE);
-        writer.write(&bytes.to_le_bytes())?;
+        // Fixed to 8 bytes so that the length can be read on hosts with a different pointer width
+        writer.write(&(bytes as u64).to_le_bytes())?;
         writer.write(&serialized)?;
 
         Ok(())
@@ -97,7 +105,7 @@ impl BuiltArtifactHeaderData {
     ) -> std::io::Result<BuiltArtifactHeaderData> {
         let mut length_bytes = [0u8; 8];
         reader.read(&mut length_bytes)?;
-        let length = usize::from_le_bytes(length_bytes);
+        let length = u64::from_le_bytes(length_bytes) as usize;
         assert!(length <= MAX_HEADER_SIZE);
 
         let mut read_buffer = vec![0u8; length];
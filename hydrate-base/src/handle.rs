@@ -298,6 +298,24 @@ impl<T> Handle<T> {
         }
     }
 
+    /// Creates a handle that does not refer to any artifact. It never loads, and serializes as a
+    /// null artifact ID.
+    pub fn null() -> Self {
+        // Nothing is ref-counted for the null handle, so the sender is never read from
+        let (chan, _) = crossbeam_channel::unbounded();
+        Self {
+            handle_ref: HandleRef {
+                resolved_load_handle: ResolvedLoadHandle::new_null_handle(),
+                ref_type: HandleRefType::Weak(chan),
+            },
+            marker: PhantomData,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.load_handle() == LoadHandle(0)
+    }
+
     pub fn artifact<'a>(
         &self,
         storage: &'a impl TypedArtifactStorage<T>,
@@ -306,6 +324,12 @@ impl<T> Handle<T> {
     }
 }
 
+impl<T> Default for Handle<T> {
+    fn default() -> Self {
+        Handle::null()
+    }
+}
+
 impl<T> ArtifactHandle for Handle<T> {
     fn resolved_load_handle(&self) -> &Arc<ResolvedLoadHandle> {
         &self.handle_ref.resolved_load_handle
@@ -624,19 +648,21 @@ fn serialize_handle<S>(
 where
     S: ser::Serializer,
 {
-    SerdeContext::with_active(|loader, _| {
-        use ser::SerializeSeq;
-        let uuid_bytes: uuid::Bytes = *loader
-            .artifact_id(load)
-            .unwrap_or_default()
-            .as_uuid()
-            .as_bytes();
-        let mut seq = serializer.serialize_seq(Some(uuid_bytes.len()))?;
-        for element in &uuid_bytes {
-            seq.serialize_element(element)?;
-        }
-        seq.end()
-    })
+    use ser::SerializeSeq;
+
+    // Null handles don't need a loader to serialize
+    let artifact_id = if load == LoadHandle(0) {
+        ArtifactId::null()
+    } else {
+        SerdeContext::with_active(|loader, _| loader.artifact_id(load).unwrap_or_default())
+    };
+
+    let uuid_bytes: uuid::Bytes = *artifact_id.as_uuid().as_bytes();
+    let mut seq = serializer.serialize_seq(Some(uuid_bytes.len()))?;
+    for element in &uuid_bytes {
+        seq.serialize_element(element)?;
+    }
+    seq.end()
 }
 impl<T> Serialize for Handle<T> {
     fn serialize<S>(
@@ -806,6 +832,18 @@ pub trait TypedArtifactStorage<A> {
     ) -> Option<&A>;
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    // The artifact's metadata reported a different type than the one the caller asked for
+    TypeMismatch,
+    // The artifact's metadata lists a dependency that is not in the manifest. This usually means
+    // the build is incomplete or the manifest is stale
+    MissingDependency(ArtifactId),
+    // No storage was added to the loader for the artifact's type (by UUID), so there is nowhere to
+    // load it into
+    UnregisteredType(Uuid),
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum LoadState {
     // Not loaded, and we haven't started trying to load it. Ref count > 0 implies we want to start
@@ -821,6 +859,12 @@ pub enum LoadState {
     Loading,
     // The engine finished loading the artifact and it is available to the game.
     Loaded,
+    // The artifact could not be loaded and will not become available unless it is unloaded and
+    // requested again
+    Error(LoadError),
+    // The handle was requested with a null artifact ID. It doesn't refer to any artifact, so it will
+    // never load and the artifact is never available
+    Null,
 }
 
 // This allows a handle in hydrate_base to get information from the loader which may be in hydrate_loader
@@ -878,6 +922,11 @@ pub trait ArtifactHandle {
     where
         Self: Sized,
     {
+        // A null handle never refers to a loaded artifact
+        if self.load_handle() == LoadHandle(0) {
+            return None;
+        }
+
         storage.get(self)
     }
 
@@ -909,3 +958,43 @@ pub fn make_handle_within_serde_context<T>(uuid: ArtifactId) -> Handle<T> {
         Handle::<T>::new(ref_op_sender.clone(), load_handle)
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Hands out the same artifact for every handle so only the null check can return None
+    struct AlwaysLoadedStorage(u32);
+
+    impl TypedArtifactStorage<u32> for AlwaysLoadedStorage {
+        fn get<T: ArtifactHandle>(
+            &self,
+            _handle: &T,
+        ) -> Option<&u32> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn null_handle_round_trips_and_never_resolves() {
+        let handle = Handle::<u32>::default();
+        assert!(handle.is_null());
+        assert_eq!(handle, Handle::<u32>::null());
+
+        let storage = AlwaysLoadedStorage(5);
+        assert!(handle.artifact(&storage).is_none());
+        assert!(handle.clone().artifact(&storage).is_none());
+
+        // Serializes as a null ID without a loader being active
+        let bytes = bincode::serialize(&handle).unwrap();
+        let null_id_bytes =
+            bincode::serialize(&ArtifactId::null().as_uuid().as_bytes().to_vec()).unwrap();
+        assert_eq!(bytes, null_id_bytes);
+
+        let serde_context = DummySerdeContextHandle::default();
+        let deserialized: Handle<u32> =
+            serde_context.scope(|| bincode::deserialize(&bytes).unwrap());
+        assert!(deserialized.is_null());
+        assert!(deserialized.artifact(&storage).is_none());
+    }
+}
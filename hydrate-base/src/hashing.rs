@@ -2,3 +2,102 @@
 pub type HashMap<K, V> = std::collections::HashMap<K, V, ahash::RandomState>;
 /// Default hashset for hydrate. Opts-out of more expensive secure hash.
 pub type HashSet<T> = std::collections::HashSet<T, ahash::RandomState>;
+
+use serde::{Deserialize, Serialize};
+use std::hash::{BuildHasher, Hasher};
+
+/// Selects the hash function used for content hashes, i.e. hashes of potentially large buffers
+/// like import data and built artifacts. The selected algorithm is a [`BuildHasher`] so anything
+/// that implements `Hash` can be hashed with it.
+///
+/// Only content hashes are configurable. Schema fingerprints, asset property hashes, job input
+/// hashes and artifact IDs always use siphash so that they stay stable across projects and
+/// versions. Changing this setting changes the build hash of anything imported or built afterwards,
+/// so affected assets will be rebuilt.
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ContentHashAlgorithm {
+    /// Matches the hashes produced by older versions
+    #[default]
+    SipHash,
+    /// Non-cryptographic, much faster on large buffers
+    Xxh3,
+    /// Cryptographic, suitable for content addressing
+    Blake3,
+}
+
+impl BuildHasher for ContentHashAlgorithm {
+    type Hasher = ContentHasher;
+
+    fn build_hasher(&self) -> ContentHasher {
+        match self {
+            ContentHashAlgorithm::SipHash => {
+                ContentHasher::SipHash(siphasher::sip::SipHasher::default())
+            }
+            ContentHashAlgorithm::Xxh3 => {
+                ContentHasher::Xxh3(Box::new(xxhash_rust::xxh3::Xxh3::new()))
+            }
+            ContentHashAlgorithm::Blake3 => ContentHasher::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+}
+
+/// Hasher produced by [`ContentHashAlgorithm`]
+pub enum ContentHasher {
+    SipHash(siphasher::sip::SipHasher),
+    Xxh3(Box<xxhash_rust::xxh3::Xxh3>),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher for ContentHasher {
+    fn finish(&self) -> u64 {
+        match self {
+            ContentHasher::SipHash(hasher) => hasher.finish(),
+            ContentHasher::Xxh3(hasher) => hasher.digest(),
+            ContentHasher::Blake3(hasher) => {
+                let hash = hasher.finalize();
+                u64::from_le_bytes(hash.as_bytes()[0..8].try_into().unwrap())
+            }
+        }
+    }
+
+    fn write(
+        &mut self,
+        bytes: &[u8],
+    ) {
+        match self {
+            ContentHasher::SipHash(hasher) => hasher.write(bytes),
+            ContentHasher::Xxh3(hasher) => hasher.update(bytes),
+            ContentHasher::Blake3(hasher) => {
+                hasher.update(bytes);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::Hash;
+
+    #[test]
+    fn content_hash_algorithms() {
+        let data = vec![7u8; 1024];
+        let other_data = vec![8u8; 1024];
+        for algorithm in [
+            ContentHashAlgorithm::SipHash,
+            ContentHashAlgorithm::Xxh3,
+            ContentHashAlgorithm::Blake3,
+        ] {
+            assert_eq!(algorithm.hash_one(&data), algorithm.hash_one(&data));
+            assert_ne!(algorithm.hash_one(&data), algorithm.hash_one(&other_data));
+        }
+
+        // The default must keep producing the same hashes as before this was configurable
+        let mut hasher = siphasher::sip::SipHasher::default();
+        data.hash(&mut hasher);
+        assert_eq!(
+            ContentHashAlgorithm::default().hash_one(&data),
+            hasher.finish()
+        );
+    }
+}
@@ -2,6 +2,9 @@ pub mod hashing;
 
 pub mod uuid_path;
 
+mod path_canonicalization;
+pub use path_canonicalization::{canonicalize_path, PathCanonicalization};
+
 pub mod built_artifact_metadata;
 pub use built_artifact_metadata::{
     ArtifactManifestData, BuiltArtifactHeaderData, DebugArtifactManifestDataJson,
@@ -21,6 +24,9 @@ pub use handle::LoadHandle;
 mod string_hash;
 pub use string_hash::StringHash;
 
+mod symbol_table;
+pub use symbol_table::{SymbolTable, SymbolTableEntry};
+
 pub mod b3f;
 
 pub mod lru_cache;
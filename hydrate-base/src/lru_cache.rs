@@ -18,10 +18,36 @@ pub struct LruCache<K, V> {
 
     // Lookup for the index a key is stored at
     lookup: HashMap<K, u32>,
+
+    // Size in bytes declared for each slot when it was inserted, lines up with lru_list_pairs
+    lru_list_sizes: Vec<usize>,
+    total_bytes: usize,
+
+    // If set, least recently used entries are evicted until the total size is within this budget
+    max_bytes: Option<usize>,
+
+    // Called with entries that are evicted to make room, but not with entries that are removed
+    on_evict: Option<Box<dyn FnMut(K, V) + Send>>,
 }
 
 impl<K: Clone + PartialEq + Eq + Hash, V> LruCache<K, V> {
     pub fn new(size: u32) -> LruCache<K, V> {
+        Self::new_with_max_bytes(size, None)
+    }
+
+    // Evicts entries once either the entry count or the total size of entries declared with
+    // insert_with_size() would exceed the given limits
+    pub fn new_with_byte_budget(
+        size: u32,
+        max_bytes: usize,
+    ) -> LruCache<K, V> {
+        Self::new_with_max_bytes(size, Some(max_bytes))
+    }
+
+    fn new_with_max_bytes(
+        size: u32,
+        max_bytes: Option<usize>,
+    ) -> LruCache<K, V> {
         assert!(size > 2);
         let mut lru_list = vec![
             LruCacheNode {
@@ -52,9 +78,29 @@ impl<K: Clone + PartialEq + Eq + Hash, V> LruCache<K, V> {
             lru_list,
             lru_list_pairs,
             lookup,
+            lru_list_sizes: vec![0; size as usize],
+            total_bytes: 0,
+            max_bytes,
+            on_evict: None,
         }
     }
 
+    pub fn set_on_evict<F: FnMut(K, V) + Send + 'static>(
+        &mut self,
+        on_evict: F,
+    ) {
+        self.on_evict = Some(Box::new(on_evict));
+    }
+
+    // Sum of the sizes declared when inserting the entries currently in the cache
+    pub fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+
+    pub fn max_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
     // For debug, can throw in to try and find when state is invalid
     /*
     fn check_list(&self) {
@@ -201,23 +247,53 @@ impl<K: Clone + PartialEq + Eq + Hash, V> LruCache<K, V> {
         k: K,
         v: V,
     ) {
-        if let Some(key_to_remove) = self.lru_list_pairs[self.lru_list_tail as usize]
-            .as_ref()
-            .map(|(k, _)| k)
-            .cloned()
-        {
-            self.remove(&key_to_remove);
-        }
+        self.insert_with_size(k, v, 0);
+    }
 
-        // remove tail element if it exists
-        let node_index = self.lru_list_tail;
-        if let Some((k, _)) = &self.lru_list_pairs[node_index as usize] {
-            self.lookup.remove(k);
-        }
+    // The size is only used for byte budget accounting, it is not updated if the value is later
+    // modified through get_mut() or pairs_mut()
+    pub fn insert_with_size(
+        &mut self,
+        k: K,
+        v: V,
+        size_in_bytes: usize,
+    ) {
+        // Replacing a value is not an eviction
+        self.remove(&k);
 
-        self.move_to_front(self.lru_list_tail);
+        // Evict the tail element if the cache is full
+        self.evict(self.lru_list_tail);
+
+        let node_index = self.lru_list_tail;
+        self.move_to_front(node_index);
         self.lookup.insert(k.clone(), node_index);
         self.lru_list_pairs[node_index as usize] = Some((k, v));
+        self.lru_list_sizes[node_index as usize] = size_in_bytes;
+        self.total_bytes += size_in_bytes;
+
+        // Evict least recently used entries until we are within the byte budget. The entry that was
+        // just inserted is kept even if it alone is over budget.
+        if let Some(max_bytes) = self.max_bytes {
+            let mut iter = self.lru_list_tail;
+            while self.total_bytes > max_bytes && iter != self.lru_list_head {
+                let previous = self.lru_list[iter as usize].previous;
+                self.evict(iter);
+                iter = previous;
+            }
+        }
+    }
+
+    fn evict(
+        &mut self,
+        node_index: u32,
+    ) {
+        if let Some((k, _)) = &self.lru_list_pairs[node_index as usize] {
+            let k = k.clone();
+            let v = self.remove(&k).unwrap();
+            if let Some(on_evict) = &mut self.on_evict {
+                (on_evict)(k, v);
+            }
+        }
     }
 
     pub fn remove(
@@ -231,6 +307,7 @@ impl<K: Clone + PartialEq + Eq + Hash, V> LruCache<K, V> {
             let v = self.lru_list_pairs[node_index as usize]
                 .take()
                 .map(|(_, v)| v);
+            self.total_bytes -= std::mem::take(&mut self.lru_list_sizes[node_index as usize]);
             self.lookup.remove(k);
             v
         } else {
@@ -307,4 +384,62 @@ mod test {
         assert!(lru_cache.get(&2, false).is_none());
         assert!(lru_cache.get(&3, false).is_some());
     }
+
+    #[test]
+    fn check_count_eviction_calls_on_evict() {
+        let evicted = std::sync::Arc::new(std::sync::Mutex::new(Vec::default()));
+        let mut lru_cache = LruCache::new(3);
+        let evicted_clone = evicted.clone();
+        lru_cache.set_on_evict(move |k, v| evicted_clone.lock().unwrap().push((k, v)));
+
+        lru_cache.insert(0, 0);
+        lru_cache.insert(1, 1);
+        lru_cache.insert(2, 2);
+        assert!(evicted.lock().unwrap().is_empty());
+
+        // Replacing or removing an entry is not an eviction
+        lru_cache.insert(1, 10);
+        lru_cache.remove(&2);
+        assert!(evicted.lock().unwrap().is_empty());
+
+        lru_cache.insert(3, 3);
+        lru_cache.insert(4, 4);
+        assert_eq!(*evicted.lock().unwrap(), vec![(0, 0)]);
+        assert_eq!(lru_cache.get(&1, false), Some(&10));
+    }
+
+    #[test]
+    fn check_byte_budget_eviction() {
+        let evicted = std::sync::Arc::new(std::sync::Mutex::new(Vec::default()));
+        let mut lru_cache = LruCache::new_with_byte_budget(10, 100);
+        let evicted_clone = evicted.clone();
+        lru_cache.set_on_evict(move |k, _| evicted_clone.lock().unwrap().push(k));
+
+        lru_cache.insert_with_size(0, 0, 40);
+        lru_cache.insert_with_size(1, 1, 40);
+        assert_eq!(lru_cache.total_bytes(), 80);
+
+        // Touch the oldest, so the next one is evicted first
+        lru_cache.get(&0, true);
+
+        // Far fewer entries than the count limit, but over the byte budget
+        lru_cache.insert_with_size(2, 2, 40);
+        assert_eq!(*evicted.lock().unwrap(), vec![1]);
+        assert_eq!(lru_cache.total_bytes(), 80);
+
+        // Several entries may need to be evicted to make room for a large one
+        lru_cache.insert_with_size(3, 3, 90);
+        assert_eq!(*evicted.lock().unwrap(), vec![1, 0, 2]);
+        assert_eq!(lru_cache.total_bytes(), 90);
+        assert!(lru_cache.get(&3, false).is_some());
+
+        // An entry that is over budget on its own is still kept
+        lru_cache.insert_with_size(4, 4, 200);
+        assert_eq!(*evicted.lock().unwrap(), vec![1, 0, 2, 3]);
+        assert_eq!(lru_cache.total_bytes(), 200);
+        assert!(lru_cache.get(&4, false).is_some());
+
+        lru_cache.remove(&4);
+        assert_eq!(lru_cache.total_bytes(), 0);
+    }
 }
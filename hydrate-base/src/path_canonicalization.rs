@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+/// Controls how paths are canonicalized when they are stored or compared. Paths to source files
+/// and asset files are compared to find existing assets and resolve references, so every path
+/// involved in that must be canonicalized with the same settings.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PathCanonicalization {
+    /// Resolve symlinks to the file they point at. If false, symlinks are kept as-is and only "."
+    /// and ".." are removed. Turning this off can help with network drives and symlinked source
+    /// folders, where resolving the link produces a different path than the one that was stored.
+    #[serde(default = "default_follow_symlinks")]
+    pub follow_symlinks: bool,
+    /// Lowercase the canonicalized path. Only use this on case-insensitive file systems, it
+    /// makes paths that differ only by case compare as equal.
+    #[serde(default)]
+    pub case_fold: bool,
+}
+
+fn default_follow_symlinks() -> bool {
+    true
+}
+
+impl Default for PathCanonicalization {
+    fn default() -> Self {
+        PathCanonicalization {
+            follow_symlinks: default_follow_symlinks(),
+            case_fold: false,
+        }
+    }
+}
+
+/// Produces an absolute path with no "." or ".." components. The path must exist. With default
+/// settings this is equivalent to dunce::canonicalize.
+pub fn canonicalize_path(
+    path: &Path,
+    canonicalization: PathCanonicalization,
+) -> std::io::Result<PathBuf> {
+    let canonicalized = if canonicalization.follow_symlinks {
+        dunce::canonicalize(path)?
+    } else {
+        // Fail the same way dunce::canonicalize would if the path doesn't exist
+        std::fs::symlink_metadata(path)?;
+        normalize_lexically(&std::path::absolute(path)?)
+    };
+
+    if canonicalization.case_fold {
+        Ok(PathBuf::from(
+            canonicalized.to_string_lossy().to_lowercase(),
+        ))
+    } else {
+        Ok(canonicalized)
+    }
+}
+
+// Removes "." and ".." without touching the file system, so symlinks are not resolved
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::default();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_dir() -> PathBuf {
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root_path.join("Textures")).unwrap();
+        std::fs::write(root_path.join("Textures/Brick.PNG"), [0u8]).unwrap();
+        dunce::canonicalize(root_path).unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_paths() {
+        let root_path = create_test_dir();
+        let file_path = root_path.join("Textures/Brick.PNG");
+        let linked_dir_path = root_path.join("Linked");
+        std::os::unix::fs::symlink(root_path.join("Textures"), &linked_dir_path).unwrap();
+        let linked_file_path = linked_dir_path.join("Brick.PNG");
+
+        // Following symlinks, the link and the file it points at canonicalize the same
+        let follow = PathCanonicalization::default();
+        assert_eq!(
+            canonicalize_path(&linked_file_path, follow).unwrap(),
+            canonicalize_path(&file_path, follow).unwrap(),
+        );
+        assert_eq!(
+            canonicalize_path(&root_path.join("Linked/../Linked/./Brick.PNG"), follow).unwrap(),
+            file_path,
+        );
+
+        // Otherwise the link is kept, but "." and ".." are still removed
+        let no_follow = PathCanonicalization {
+            follow_symlinks: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            canonicalize_path(&root_path.join("Linked/../Linked/./Brick.PNG"), no_follow).unwrap(),
+            linked_file_path,
+        );
+        assert!(canonicalize_path(&root_path.join("Linked/Missing.PNG"), no_follow).is_err());
+
+        std::fs::remove_dir_all(root_path).unwrap();
+    }
+
+    #[test]
+    fn differently_cased_paths() {
+        let root_path = create_test_dir();
+        let file_path = root_path.join("Textures/Brick.PNG");
+        let lowercase_file_path = root_path.join("textures/brick.png");
+
+        // Case is preserved by default
+        assert_eq!(
+            canonicalize_path(&file_path, PathCanonicalization::default()).unwrap(),
+            file_path
+        );
+
+        let case_fold = PathCanonicalization {
+            case_fold: true,
+            ..Default::default()
+        };
+        let expected = PathBuf::from(file_path.to_string_lossy().to_lowercase());
+        assert_eq!(canonicalize_path(&file_path, case_fold).unwrap(), expected);
+
+        // The differently cased path only exists if the file system is case-insensitive
+        if lowercase_file_path.exists() {
+            assert_eq!(
+                canonicalize_path(&lowercase_file_path, case_fold).unwrap(),
+                expected
+            );
+        }
+
+        std::fs::remove_dir_all(root_path).unwrap();
+    }
+}
@@ -0,0 +1,262 @@
+use crate::hashing::HashMap;
+#[cfg(not(feature = "strip-stringhash-strings"))]
+use crate::string_hash::StringHashContents;
+use crate::{ArtifactId, StringHash};
+use std::io::{BufRead, Write};
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub struct SymbolTableEntry {
+    pub artifact_id: ArtifactId,
+    pub symbol_name: Arc<String>,
+}
+
+/// Maps symbol names to the artifacts they refer to. The build produces one of these alongside the
+/// manifest and the loader uses it to resolve loads by symbol. Symbols are looked up by hash, but
+/// the full name is kept so that different names with the same hash can be told apart.
+#[derive(Default, Debug)]
+pub struct SymbolTable {
+    symbols: HashMap<u128, Vec<SymbolTableEntry>>,
+}
+
+impl SymbolTable {
+    /// Returns the artifact previously registered with this exact name, if there was one
+    pub fn register_symbol(
+        &mut self,
+        symbol_name: &str,
+        artifact_id: ArtifactId,
+    ) -> Option<ArtifactId> {
+        let hash = StringHash::from_runtime_str(symbol_name).hash();
+        self.register_symbol_with_hash(hash, Arc::new(symbol_name.to_string()), artifact_id)
+    }
+
+    fn register_symbol_with_hash(
+        &mut self,
+        hash: u128,
+        symbol_name: Arc<String>,
+        artifact_id: ArtifactId,
+    ) -> Option<ArtifactId> {
+        let entries = self.symbols.entry(hash).or_default();
+        for entry in entries.iter_mut() {
+            if entry.symbol_name == symbol_name {
+                return Some(std::mem::replace(&mut entry.artifact_id, artifact_id));
+            }
+        }
+
+        entries.push(SymbolTableEntry {
+            artifact_id,
+            symbol_name,
+        });
+        None
+    }
+
+    /// Finds the artifact for a symbol. If the symbol's string is available it must match the
+    /// registered name exactly. If it isn't (i.e. the string was stripped), the hash must refer to
+    /// a single symbol.
+    pub fn resolve(
+        &self,
+        symbol: &StringHash,
+    ) -> Option<ArtifactId> {
+        #[cfg(not(feature = "strip-stringhash-strings"))]
+        let symbol_name = match symbol.contents() {
+            StringHashContents::Static(s) => Some(*s),
+            StringHashContents::Runtime(s) => Some(s.as_str()),
+            StringHashContents::Unknown => None,
+        };
+
+        #[cfg(feature = "strip-stringhash-strings")]
+        let symbol_name = None;
+
+        self.resolve_with_hash(symbol.hash(), symbol_name)
+    }
+
+    fn resolve_with_hash(
+        &self,
+        hash: u128,
+        symbol_name: Option<&str>,
+    ) -> Option<ArtifactId> {
+        let entries = self.symbols.get(&hash)?;
+        if let Some(symbol_name) = symbol_name {
+            entries
+                .iter()
+                .find(|entry| entry.symbol_name.as_str() == symbol_name)
+                .map(|entry| entry.artifact_id)
+        } else if entries.len() == 1 {
+            Some(entries[0].artifact_id)
+        } else {
+            // Ambiguous, the hash collided and there is no name to tell the symbols apart
+            None
+        }
+    }
+
+    /// All symbols with the given hash. More than one means the hash collided.
+    pub fn symbols_with_hash(
+        &self,
+        hash: u128,
+    ) -> &[SymbolTableEntry] {
+        self.symbols
+            .get(&hash)
+            .map(|x| x.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u128, &SymbolTableEntry)> {
+        self.symbols
+            .iter()
+            .flat_map(|(&hash, entries)| entries.iter().map(move |entry| (hash, entry)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.values().map(|x| x.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    // One symbol per line: hash, artifact ID, and the symbol name. The name is last so that it may
+    // contain commas. Lines are sorted so the output is deterministic.
+    pub fn write<W: Write>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        let mut lines: Vec<_> = self.iter().collect();
+        lines.sort_by(|a, b| {
+            (a.0, a.1.artifact_id, &a.1.symbol_name).cmp(&(b.0, b.1.artifact_id, &b.1.symbol_name))
+        });
+
+        for (hash, entry) in lines {
+            writeln!(
+                writer,
+                "{:0>32x},{:0>32x},{}",
+                hash,
+                entry.artifact_id.as_u128(),
+                entry.symbol_name
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn read<R: BufRead>(reader: R) -> std::io::Result<SymbolTable> {
+        fn invalid_data(line: &str) -> std::io::Error {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid symbol table line {:?}", line),
+            )
+        }
+
+        let mut symbol_table = SymbolTable::default();
+        for line in reader.lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut fragments = line.splitn(3, ',');
+            let (Some(hash), Some(artifact_id), Some(symbol_name)) =
+                (fragments.next(), fragments.next(), fragments.next())
+            else {
+                return Err(invalid_data(&line));
+            };
+
+            let hash = u128::from_str_radix(hash, 16).map_err(|_| invalid_data(&line))?;
+            let artifact_id = ArtifactId::from_u128(
+                u128::from_str_radix(artifact_id, 16).map_err(|_| invalid_data(&line))?,
+            );
+
+            let old = symbol_table.register_symbol_with_hash(
+                hash,
+                Arc::new(symbol_name.to_string()),
+                artifact_id,
+            );
+            if old.is_some() {
+                return Err(invalid_data(&line));
+            }
+        }
+
+        Ok(symbol_table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_symbol_at_build_and_resolve_at_load() {
+        let level_artifact_id = ArtifactId::from_u128(1);
+        let mesh_artifact_id = ArtifactId::from_u128(2);
+
+        // Build: register symbols and write the table
+        let mut symbol_table = SymbolTable::default();
+        assert!(symbol_table
+            .register_symbol("db:/levels/level.json", level_artifact_id)
+            .is_none());
+        assert!(symbol_table
+            .register_symbol("db:/meshes/a, b.mesh", mesh_artifact_id)
+            .is_none());
+        assert_eq!(
+            symbol_table.register_symbol("db:/levels/level.json", level_artifact_id),
+            Some(level_artifact_id)
+        );
+
+        let mut written = Vec::default();
+        symbol_table.write(&mut written).unwrap();
+
+        // Load: read the table and resolve by symbol
+        let loaded = SymbolTable::read(std::io::Cursor::new(written)).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(
+            loaded.resolve(&StringHash::from_static_str("db:/levels/level.json")),
+            Some(level_artifact_id)
+        );
+        assert_eq!(
+            loaded.resolve(&StringHash::from_runtime_str("db:/meshes/a, b.mesh")),
+            Some(mesh_artifact_id)
+        );
+        assert_eq!(
+            loaded.resolve(&StringHash::from_static_str("db:/levels/missing.json")),
+            None
+        );
+
+        // A hash without a string still resolves if it's unambiguous
+        let level_hash = StringHash::from_static_str("db:/levels/level.json").hash();
+        assert_eq!(
+            loaded.resolve(&StringHash::from_hash(level_hash)),
+            Some(level_artifact_id)
+        );
+    }
+
+    #[test]
+    fn colliding_symbol_hashes_resolve_by_name() {
+        let first_artifact_id = ArtifactId::from_u128(1);
+        let second_artifact_id = ArtifactId::from_u128(2);
+
+        let mut symbol_table = SymbolTable::default();
+        symbol_table.register_symbol_with_hash(7, Arc::new("first".to_string()), first_artifact_id);
+        symbol_table.register_symbol_with_hash(
+            7,
+            Arc::new("second".to_string()),
+            second_artifact_id,
+        );
+
+        // Collisions survive a round trip through the file format
+        let mut written = Vec::default();
+        symbol_table.write(&mut written).unwrap();
+        let loaded = SymbolTable::read(std::io::Cursor::new(written)).unwrap();
+        assert_eq!(loaded.symbols_with_hash(7).len(), 2);
+
+        assert_eq!(
+            loaded.resolve_with_hash(7, Some("first")),
+            Some(first_artifact_id)
+        );
+        assert_eq!(
+            loaded.resolve_with_hash(7, Some("second")),
+            Some(second_artifact_id)
+        );
+        assert_eq!(loaded.resolve_with_hash(7, Some("third")), None);
+        // Ambiguous without the name
+        assert_eq!(loaded.resolve_with_hash(7, None), None);
+    }
+}
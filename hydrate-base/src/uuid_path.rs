@@ -51,12 +51,12 @@ pub fn path_to_uuid(
     // Split the path by directory paths
     let components: Vec<_> = relative_path_from_root.components().collect();
 
-    let mut filename = components[2].as_os_str().to_str().unwrap();
-
     if components.len() != 3 {
         return None;
     }
 
+    let mut filename = components[2].as_os_str().to_str().unwrap();
+
     if components[0].as_os_str().to_str().unwrap().as_bytes()[0] != filename.as_bytes()[0] {
         return None;
     }
@@ -86,12 +86,12 @@ pub fn path_to_uuid_and_hash(
     // Split the path by directory paths
     let components: Vec<_> = relative_path_from_root.components().collect();
 
-    let mut filename = components[2].as_os_str().to_str().unwrap();
-
     if components.len() != 3 {
         return None;
     }
 
+    let mut filename = components[2].as_os_str().to_str().unwrap();
+
     if components[0].as_os_str().to_str().unwrap().as_bytes()[0] != filename.as_bytes()[0] {
         return None;
     }
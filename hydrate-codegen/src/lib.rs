@@ -106,20 +106,19 @@ fn schema_to_rs(
         .map_err(|x| Box::new(x))?;
     let schema_set = schema_set_builder.build();
 
-    let mut all_schemas_to_build = Vec::default();
-    for named_type_to_build in named_types_to_build {
-        let named_type = schema_set
+    for named_type_to_build in &named_types_to_build {
+        schema_set
             .find_named_type(named_type_to_build)
             .expect("Cannot find linked type in built schema");
-        all_schemas_to_build.push((named_type.fingerprint(), named_type));
     }
 
-    // Sort by name so we have a deterministic output ordering for codegen
-    all_schemas_to_build.sort_by(|lhs, rhs| lhs.1.name().cmp(rhs.1.name()));
-
     let mut code_fragments_as_string = Vec::default();
 
-    for (_fingerprint, named_type) in all_schemas_to_build {
+    // Iterate in sorted order so we have a deterministic output ordering for codegen
+    for named_type in schema_set
+        .iter_sorted()
+        .filter(|x| named_types_to_build.iter().any(|name| name == x.name()))
+    {
         //println!("{:?} {:?}", fingerprint, named_type);
 
         let scopes = match named_type {
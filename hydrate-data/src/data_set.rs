@@ -5,11 +5,14 @@ use crate::{
 };
 pub use crate::{DataSetError, DataSetResult};
 use crate::{NullOverride, SchemaSet};
+use serde::{Deserialize, Serialize};
 use siphasher::sip::SipHasher;
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
+use std::ops::Deref;
 use std::str::FromStr;
 use std::string::ToString;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 #[derive(Copy, Clone, PartialEq)]
@@ -35,6 +38,37 @@ impl AssetName {
         AssetName(name.into())
     }
 
+    /// Like new(), but fails if the name contains path separators or control characters, or has
+    /// leading/trailing whitespace. An empty name is valid.
+    pub fn try_new<T: Into<String>>(name: T) -> DataSetResult<Self> {
+        let name = name.into();
+        if !Self::is_valid(&name) {
+            Err(DataSetError::InvalidAssetName)?;
+        }
+
+        Ok(AssetName(name))
+    }
+
+    /// Produces a valid name by replacing path separators and control characters with '_' and
+    /// trimming whitespace. Useful for names that come from outside the editor, like file names.
+    pub fn sanitize(name: &str) -> Self {
+        let sanitized: String = name
+            .chars()
+            .map(|c| if Self::is_invalid_char(c) { '_' } else { c })
+            .collect();
+        AssetName(sanitized.trim().to_string())
+    }
+
+    pub fn is_valid(name: &str) -> bool {
+        !name.chars().any(Self::is_invalid_char) && name.trim() == name
+    }
+
+    // '/' separates names in asset paths. Backslashes are rejected too so that names behave the
+    // same on every platform.
+    fn is_invalid_char(c: char) -> bool {
+        c == '/' || c == '\\' || c.is_control()
+    }
+
     pub fn empty() -> Self {
         AssetName(String::default())
     }
@@ -92,6 +126,19 @@ pub enum OverrideBehavior {
     Replace,
 }
 
+// What a single asset in a prototype chain contributes to a property, see resolve_property_chain()
+#[derive(Debug, Copy, Clone)]
+pub enum PropertyContribution<'a> {
+    // The asset overrides the property with this value
+    Value(AssetId, &'a Value),
+    // The asset sets whether this nullable property is null
+    NullOverride(AssetId, NullOverride),
+    // The asset doesn't set the property
+    NotSet(AssetId),
+    // The default for the property's schema, used if nothing in the prototype chain sets it
+    SchemaDefault(&'a Value),
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ImporterId(pub Uuid);
 
@@ -133,6 +180,23 @@ impl ImportableName {
     }
 }
 
+/// Where the import data for an asset is kept
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ImportDataStorage {
+    /// Import data is written to the project's import data directory
+    #[default]
+    Copied,
+    /// Import data is not copied into the project. When it's needed, the source file is imported
+    /// again from its original location. The source file must not change after it is imported.
+    External,
+}
+
+impl ImportDataStorage {
+    pub fn is_copied(&self) -> bool {
+        *self == ImportDataStorage::Copied
+    }
+}
+
 /// Describes the conditions that we imported the file
 #[derive(Clone, Debug)]
 pub struct ImportInfo {
@@ -155,12 +219,17 @@ pub struct ImportInfo {
     // file references encountered in the input data, and only changes when data is re-imported)
     path_references: HashMap<PathReferenceHash, CanonicalPathReference>,
 
-    // State of the source file when the asset was imported
+    // State of the source file when the asset was imported. The contents hash is 0 if the
+    // contents were not hashed.
     source_file_modified_timestamp: u64,
     source_file_size: u64,
+    source_file_contents_hash: u64,
 
     // Hash of the import data
     import_data_contents_hash: u64,
+
+    // Whether the import data was copied into the project or is read from the source file
+    import_data_storage: ImportDataStorage,
 }
 
 impl ImportInfo {
@@ -170,6 +239,7 @@ impl ImportInfo {
         path_references: HashMap<PathReferenceHash, CanonicalPathReference>,
         source_file_modified_timestamp: u64,
         source_file_size: u64,
+        source_file_contents_hash: u64,
         import_data_contents_hash: u64,
     ) -> Self {
         ImportInfo {
@@ -178,10 +248,20 @@ impl ImportInfo {
             path_references,
             source_file_modified_timestamp,
             source_file_size,
+            source_file_contents_hash,
             import_data_contents_hash,
+            import_data_storage: ImportDataStorage::Copied,
         }
     }
 
+    pub fn with_import_data_storage(
+        mut self,
+        import_data_storage: ImportDataStorage,
+    ) -> Self {
+        self.import_data_storage = import_data_storage;
+        self
+    }
+
     pub fn importer_id(&self) -> ImporterId {
         self.importer_id
     }
@@ -206,9 +286,17 @@ impl ImportInfo {
         self.source_file_size
     }
 
+    pub fn source_file_contents_hash(&self) -> u64 {
+        self.source_file_contents_hash
+    }
+
     pub fn import_data_contents_hash(&self) -> u64 {
         self.import_data_contents_hash
     }
+
+    pub fn import_data_storage(&self) -> ImportDataStorage {
+        self.import_data_storage
+    }
 }
 
 impl Hash for ImportInfo {
@@ -229,7 +317,12 @@ impl Hash for ImportInfo {
 
         self.source_file_modified_timestamp.hash(state);
         self.source_file_size.hash(state);
+        // Only hashed if present so that hashes of assets imported without it don't change
+        if self.source_file_contents_hash != 0 {
+            self.source_file_contents_hash.hash(state);
+        }
         self.import_data_contents_hash.hash(state);
+        self.import_data_storage.hash(state);
     }
 }
 
@@ -241,6 +334,10 @@ pub struct BuildInfo {
     // imported. We maintain the correlation between paths and imported asset ID here for use when
     // processing the imported data.
     pub path_reference_overrides: HashMap<CanonicalPathReference, AssetId>,
+
+    // Optional sidecar asset holding builder-specific settings (i.e. texture compression format).
+    // Lets users tune how an asset is built without changing the asset or its source file.
+    pub build_settings: Option<AssetId>,
 }
 
 impl Hash for BuildInfo {
@@ -258,6 +355,7 @@ impl Hash for BuildInfo {
         }
 
         path_references_overrides_hashes.hash(state);
+        self.build_settings.hash(state);
     }
 }
 
@@ -271,6 +369,38 @@ pub struct PropertiesBundle {
 }
 
 impl PropertiesBundle {
+    pub(crate) fn new(
+        schema: Schema,
+        properties: HashMap<String, Value>,
+        property_null_overrides: HashMap<String, NullOverride>,
+        properties_in_replace_mode: HashSet<String>,
+        dynamic_collection_entries: HashMap<String, OrderedSet<Uuid>>,
+    ) -> Self {
+        PropertiesBundle {
+            schema,
+            properties,
+            property_null_overrides,
+            properties_in_replace_mode,
+            dynamic_collection_entries,
+        }
+    }
+
+    // An empty path refers to all properties of the asset
+    fn find_bundle_schema(
+        asset_info: &DataSetAssetInfo,
+        path_prefix: &str,
+        schema_set: &SchemaSet,
+    ) -> DataSetResult<Schema> {
+        if path_prefix.is_empty() {
+            Ok(Schema::Record(asset_info.schema().fingerprint()))
+        } else {
+            Ok(asset_info
+                .schema()
+                .find_property_schema(path_prefix, schema_set.schemas())
+                .ok_or(DataSetError::SchemaNotFound)?)
+        }
+    }
+
     fn read(
         asset_info: &DataSetAssetInfo,
         path_prefix: impl AsRef<str>,
@@ -283,10 +413,7 @@ impl PropertiesBundle {
             format!("{}", path_prefix_str)
         };
 
-        let schema = asset_info
-            .schema()
-            .find_property_schema(path_prefix_str, schema_set.schemas())
-            .ok_or(DataSetError::SchemaNotFound)?;
+        let schema = Self::find_bundle_schema(asset_info, path_prefix_str, schema_set)?;
 
         let mut properties = HashMap::<String, Value>::default();
         println!("Look for property {:?}", path_prefix_str);
@@ -343,10 +470,7 @@ impl PropertiesBundle {
         //
         // verify schema match at dest prefix
         //
-        let schema = asset_info
-            .schema()
-            .find_property_schema(path_prefix_str, schema_set.schemas())
-            .ok_or(DataSetError::SchemaNotFound)?;
+        let schema = Self::find_bundle_schema(asset_info, path_prefix_str, schema_set)?;
         assert_eq!(schema, self.schema);
 
         //
@@ -455,29 +579,353 @@ impl DataSetAssetInfo {
     pub fn dynamic_collection_entries(&self) -> &HashMap<String, OrderedSet<Uuid>> {
         &self.dynamic_collection_entries
     }
+
+    // Rough number of bytes used by the asset's properties and overrides, including string and
+    // buffer payloads and the keys of each entry
+    pub fn estimated_memory_bytes(&self) -> usize {
+        let key_bytes = |key: &String| std::mem::size_of::<String>() + key.capacity();
+
+        let mut bytes = std::mem::size_of::<Self>() + self.asset_name.0.capacity();
+        for (key, value) in &self.properties {
+            bytes += key_bytes(key) + value.estimated_memory_bytes();
+        }
+
+        for key in self.property_null_overrides.keys() {
+            bytes += key_bytes(key) + std::mem::size_of::<NullOverride>();
+        }
+
+        for key in &self.properties_in_replace_mode {
+            bytes += key_bytes(key);
+        }
+
+        for (key, entries) in &self.dynamic_collection_entries {
+            // Entries are stored in both a list and a set
+            bytes += key_bytes(key) + entries.iter().len() * 2 * std::mem::size_of::<Uuid>();
+        }
+
+        bytes
+    }
+
+    // Every asset this asset directly points at, not including path references that have not been
+    // overridden
+    fn gather_direct_references(
+        &self,
+        references: &mut HashSet<AssetId>,
+    ) {
+        references.extend(self.prototype);
+        if !self.asset_location.is_null() {
+            references.insert(self.asset_location.path_node_id());
+        }
+        references.extend(self.build_info.build_settings);
+        references.extend(self.build_info.path_reference_overrides.values().copied());
+        for value in self.properties.values() {
+            value.gather_asset_refs(references);
+        }
+    }
+
+    fn remap_references(
+        &mut self,
+        mapping: &HashMap<AssetId, AssetId>,
+    ) {
+        let remap = |asset_id: AssetId| mapping.get(&asset_id).copied().unwrap_or(asset_id);
+        self.prototype = self.prototype.map(remap);
+        if !self.asset_location.is_null() {
+            self.asset_location = AssetLocation::new(remap(self.asset_location.path_node_id()));
+        }
+        self.build_info.build_settings = self.build_info.build_settings.map(remap);
+        for referenced_asset_id in self.build_info.path_reference_overrides.values_mut() {
+            *referenced_asset_id = remap(*referenced_asset_id);
+        }
+        for value in self.properties.values_mut() {
+            value.remap_asset_refs(mapping);
+        }
+    }
+}
+
+// Problems that can only be caused by invalid data, like a corrupt or hand-edited file. Properties
+// can still be resolved when these are present.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConsistencyIssue {
+    // A dynamic array or map entry of the asset is also an entry of the same property in one of its
+    // prototypes. The entry is only included once when the property is resolved.
+    DuplicateDynamicCollectionEntry {
+        asset_id: AssetId,
+        prototype_id: AssetId,
+        path: String,
+        entry_id: Uuid,
+    },
+}
+
+impl std::fmt::Display for ConsistencyIssue {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            ConsistencyIssue::DuplicateDynamicCollectionEntry {
+                asset_id,
+                prototype_id,
+                path,
+                entry_id,
+            } => write!(
+                f,
+                "Asset {} has entry {} in {} which is also in prototype {}",
+                asset_id.as_uuid(),
+                entry_id,
+                path,
+                prototype_id.as_uuid()
+            ),
+        }
+    }
+}
+
+// Keeps the first occurrence of each entry
+fn remove_duplicate_entries(entries: &mut Vec<Uuid>) {
+    let mut seen = HashSet::default();
+    entries.retain(|entry| seen.insert(*entry));
 }
 
 /// A collection of assets. Methods support serializing/deserializing, resolving property values,
 /// etc. This includes being aware of schema and prototypes.
+///
+/// Assets are stored behind an Arc and are copy-on-write. Cloning a DataSet only copies pointers,
+/// and modifying an asset that is shared with another DataSet clones just that asset.
 #[derive(Default, Clone)]
 pub struct DataSet {
-    assets: HashMap<AssetId, DataSetAssetInfo>,
+    assets: HashMap<AssetId, Arc<DataSetAssetInfo>>,
+    // Assets that were imported from each source file, kept in sync with the import info of assets.
+    // More than one asset can have the same source file if an imported asset is duplicated.
+    assets_by_source_file: HashMap<CanonicalPathReference, HashSet<AssetId>>,
+}
+
+/// An immutable snapshot of a DataSet. Taking a snapshot is cheap because asset data is shared with
+/// the DataSet it was taken from. Later edits to that DataSet allocate new copies of the assets they
+/// touch, so the snapshot remains consistent. This is used to let builds read the data set while
+/// the editor continues to make changes.
+///
+/// The cost of taking a snapshot is proportional to the number of assets (one pointer copy and
+/// refcount increment per asset) rather than the total size of all property data in the project.
+#[derive(Clone)]
+pub struct DataSetView {
+    data_set: DataSet,
+}
+
+impl Deref for DataSetView {
+    type Target = DataSet;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data_set
+    }
 }
 
 impl DataSet {
-    pub fn assets(&self) -> &HashMap<AssetId, DataSetAssetInfo> {
+    pub fn assets(&self) -> &HashMap<AssetId, Arc<DataSetAssetInfo>> {
         &self.assets
     }
 
-    // Exposed to allow diffs to apply changes
-    pub(super) fn assets_mut(&mut self) -> &mut HashMap<AssetId, DataSetAssetInfo> {
+    pub fn asset_count(&self) -> usize {
+        self.assets.len()
+    }
+
+    // Rough number of bytes used by all assets. Assets that are shared with snapshots or clones of
+    // this data set are counted in full.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        self.assets
+            .values()
+            .map(|asset| asset.estimated_memory_bytes())
+            .sum()
+    }
+
+    pub fn asset_estimated_memory_bytes(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<usize> {
+        self.assets
+            .get(&asset_id)
+            .map(|asset| asset.estimated_memory_bytes())
+    }
+
+    /// Returns the property values stored on the asset itself, without anything inherited from
+    /// prototypes or defaults from the schema. None if the asset doesn't exist. This is for tools
+    /// that work with stored data directly, like migrations. Use resolve_property() to get the
+    /// value that is actually used.
+    pub fn asset_raw_properties(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<&HashMap<String, Value>> {
+        self.assets.get(&asset_id).map(|asset| asset.properties())
+    }
+
+    /// Returns the null overrides stored on the asset itself. See asset_raw_properties().
+    pub fn asset_raw_property_null_overrides(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<&HashMap<String, NullOverride>> {
+        self.assets
+            .get(&asset_id)
+            .map(|asset| asset.property_null_overrides())
+    }
+
+    /// Returns the paths of the asset's dynamic collections that replace rather than append to
+    /// the prototype's entries. See asset_raw_properties().
+    pub fn asset_raw_properties_in_replace_mode(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<&HashSet<String>> {
+        self.assets
+            .get(&asset_id)
+            .map(|asset| asset.properties_in_replace_mode())
+    }
+
+    /// Returns the dynamic array and map entries added by the asset itself, excluding entries
+    /// inherited from prototypes. See asset_raw_properties().
+    pub fn asset_raw_dynamic_collection_entries(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<&HashMap<String, OrderedSet<Uuid>>> {
+        self.assets
+            .get(&asset_id)
+            .map(|asset| asset.dynamic_collection_entries())
+    }
+
+    /// Returns the asset that was imported from the given source file, if any. This is a lookup in an
+    /// index rather than a scan of all assets. If several assets were imported from the same file
+    /// (i.e. an imported asset was duplicated), the lowest ID is returned so the result is stable.
+    pub fn asset_by_source_file(
+        &self,
+        source_file: &CanonicalPathReference,
+    ) -> Option<AssetId> {
+        self.assets_by_source_file
+            .get(source_file)
+            .and_then(|asset_ids| asset_ids.iter().min().copied())
+    }
+
+    /// Returns every asset imported from the file at the given namespace and path, sorted by ID.
+    /// Unlike asset_by_source_file(), the importable name is ignored, so all assets imported from a
+    /// file that produces several importables (i.e. a .gltf with many meshes) are returned.
+    pub fn assets_imported_from(
+        &self,
+        namespace: &str,
+        path: &str,
+    ) -> Vec<AssetId> {
+        let mut asset_ids: Vec<_> = self
+            .assets_by_source_file
+            .iter()
+            .filter(|(source_file, _)| {
+                source_file.namespace() == namespace && source_file.path() == path
+            })
+            .flat_map(|(_, asset_ids)| asset_ids.iter().copied())
+            .collect();
+        asset_ids.sort();
+        asset_ids
+    }
+
+    /// Returns the other assets imported from the same source file as the given asset, sorted by
+    /// ID. For example, the materials imported from the same .gltf as a mesh. Returns an empty list
+    /// if the asset was not imported.
+    pub fn sibling_importables(
+        &self,
+        asset_id: AssetId,
+    ) -> Vec<AssetId> {
+        let Some(import_info) = self.import_info(asset_id) else {
+            return Vec::default();
+        };
+
+        let source_file = import_info.source_file();
+        let mut asset_ids = self.assets_imported_from(source_file.namespace(), source_file.path());
+        asset_ids.retain(|x| *x != asset_id);
+        asset_ids
+    }
+
+    fn add_to_source_file_index(
+        assets_by_source_file: &mut HashMap<CanonicalPathReference, HashSet<AssetId>>,
+        asset_id: AssetId,
+        asset: &DataSetAssetInfo,
+    ) {
+        if let Some(import_info) = &asset.import_info {
+            assets_by_source_file
+                .entry(import_info.source_file().clone())
+                .or_default()
+                .insert(asset_id);
+        }
+    }
+
+    fn remove_from_source_file_index(
+        assets_by_source_file: &mut HashMap<CanonicalPathReference, HashSet<AssetId>>,
+        asset_id: AssetId,
+        asset: &DataSetAssetInfo,
+    ) {
+        if let Some(import_info) = &asset.import_info {
+            if let Some(asset_ids) = assets_by_source_file.get_mut(import_info.source_file()) {
+                asset_ids.remove(&asset_id);
+                if asset_ids.is_empty() {
+                    assets_by_source_file.remove(import_info.source_file());
+                }
+            }
+        }
+    }
+
+    // Exposed to allow diffs to apply changes. Diffs do not change import info, so the source file
+    // index does not need to be updated.
+    pub(super) fn assets_mut(&mut self) -> &mut HashMap<AssetId, Arc<DataSetAssetInfo>> {
         &mut self.assets
     }
 
-    pub fn take_assets(self) -> HashMap<AssetId, DataSetAssetInfo> {
+    pub fn take_assets(self) -> HashMap<AssetId, Arc<DataSetAssetInfo>> {
         self.assets
     }
 
+    /// Looks for problems caused by invalid data. See ConsistencyIssue. Issues are sorted by asset.
+    pub fn check_consistency(&self) -> Vec<ConsistencyIssue> {
+        let mut issues = Vec::default();
+        for (&asset_id, asset) in &self.assets {
+            for (path, entries) in &asset.dynamic_collection_entries {
+                // Same rules as do_resolve_dynamic_collection_entries() for which prototypes
+                // contribute entries. Visited assets are tracked in case the prototypes form a cycle.
+                let mut visited = HashSet::default();
+                visited.insert(asset_id);
+                let mut replaces_prototype = asset.properties_in_replace_mode.contains(path);
+                let mut prototype_id = asset.prototype;
+                while let Some(prototype_id_iter) = prototype_id {
+                    if replaces_prototype || !visited.insert(prototype_id_iter) {
+                        break;
+                    }
+
+                    let Some(prototype) = self.assets.get(&prototype_id_iter) else {
+                        break;
+                    };
+
+                    if let Some(prototype_entries) = prototype.dynamic_collection_entries.get(path)
+                    {
+                        for entry in entries {
+                            if prototype_entries.contains(entry) {
+                                issues.push(ConsistencyIssue::DuplicateDynamicCollectionEntry {
+                                    asset_id,
+                                    prototype_id: prototype_id_iter,
+                                    path: path.clone(),
+                                    entry_id: *entry,
+                                });
+                            }
+                        }
+                    }
+
+                    replaces_prototype = prototype.properties_in_replace_mode.contains(path);
+                    prototype_id = prototype.prototype;
+                }
+            }
+        }
+
+        issues.sort();
+        issues
+    }
+
+    /// Returns a read-only snapshot of the current state of the data set. See DataSetView.
+    pub fn snapshot(&self) -> DataSetView {
+        DataSetView {
+            data_set: self.clone(),
+        }
+    }
+
     // Inserts the asset but only if the ID is not already in use
     fn insert_asset(
         &mut self,
@@ -487,7 +935,7 @@ impl DataSet {
         if self.assets.contains_key(&id) {
             Err(DataSetError::DuplicateAssetId)?
         } else {
-            let old = self.assets.insert(id, obj_info);
+            let old = self.assets.insert(id, Arc::new(obj_info));
             assert!(old.is_none());
             Ok(())
         }
@@ -527,7 +975,11 @@ impl DataSet {
             dynamic_collection_entries,
         };
 
-        self.assets.insert(asset_id, obj);
+        if let Some(old) = self.assets.get(&asset_id) {
+            Self::remove_from_source_file_index(&mut self.assets_by_source_file, asset_id, old);
+        }
+        Self::add_to_source_file_index(&mut self.assets_by_source_file, asset_id, &obj);
+        self.assets.insert(asset_id, Arc::new(obj));
         Ok(())
     }
 
@@ -556,6 +1008,71 @@ impl DataSet {
         self.insert_asset(asset_id, obj)
     }
 
+    /// Creates an asset with a particular ID that has its import info set from the start. The schema
+    /// must be in the schema set and the location must be null or an existing asset. Nothing is
+    /// added if any check fails, so an imported asset never exists without its import info.
+    pub fn new_imported_asset_with_id(
+        &mut self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        asset_name: AssetName,
+        asset_location: AssetLocation,
+        schema: &SchemaRecord,
+        import_info: ImportInfo,
+    ) -> DataSetResult<()> {
+        if self.assets.contains_key(&asset_id) {
+            return Err(DataSetError::DuplicateAssetId)?;
+        }
+
+        if schema_set
+            .find_named_type_by_fingerprint(schema.fingerprint())
+            .is_none()
+        {
+            return Err(DataSetError::SchemaNotFound)?;
+        }
+
+        if !asset_location.is_null() && !self.assets.contains_key(&asset_location.path_node_id()) {
+            return Err(DataSetError::LocationParentNotFound)?;
+        }
+
+        let obj = DataSetAssetInfo {
+            schema: schema.clone(),
+            asset_name,
+            asset_location,
+            import_info: Some(import_info),
+            build_info: Default::default(),
+            prototype: None,
+            properties: Default::default(),
+            property_null_overrides: Default::default(),
+            properties_in_replace_mode: Default::default(),
+            dynamic_collection_entries: Default::default(),
+        };
+
+        Self::add_to_source_file_index(&mut self.assets_by_source_file, asset_id, &obj);
+        self.insert_asset(asset_id, obj)
+    }
+
+    /// Like new_imported_asset_with_id, but uses a unique UUID
+    pub fn new_imported_asset(
+        &mut self,
+        schema_set: &SchemaSet,
+        asset_name: AssetName,
+        asset_location: AssetLocation,
+        schema: &SchemaRecord,
+        import_info: ImportInfo,
+    ) -> DataSetResult<AssetId> {
+        let asset_id = AssetId::from_uuid(Uuid::new_v4());
+        self.new_imported_asset_with_id(
+            schema_set,
+            asset_id,
+            asset_name,
+            asset_location,
+            schema,
+            import_info,
+        )?;
+        Ok(asset_id)
+    }
+
     /// Creates a new asset with no properties set. Uses a unique UUID and should not fail
     pub fn new_asset(
         &mut self,
@@ -590,10 +1107,12 @@ impl DataSet {
             asset_location,
             &prototype_schema.schema().clone(),
         );
-        self.assets
-            .get_mut(&id)
-            .expect("Newly created asset was not found")
-            .prototype = Some(prototype_asset_id);
+        Arc::make_mut(
+            self.assets
+                .get_mut(&id)
+                .expect("Newly created asset was not found"),
+        )
+        .prototype = Some(prototype_asset_id);
         Ok(id)
     }
 
@@ -607,6 +1126,7 @@ impl DataSet {
         let asset = self
             .assets
             .get_mut(&asset_id)
+            .map(Arc::make_mut)
             .ok_or(DataSetError::AssetNotFound)?;
 
         if asset.schema.fingerprint() != single_object.schema().fingerprint() {
@@ -698,30 +1218,112 @@ impl DataSet {
         Ok(new_asset_id)
     }
 
+    /// Changes the ID of every asset in the mapping and rewrites all references to them: asset ref
+    /// properties, prototypes, locations, build settings and path reference overrides. IDs that
+    /// are not in the mapping are left as they are. This is used when merging projects or copying
+    /// assets with new IDs.
+    ///
+    /// Returns error without changing anything if two assets would end up with the same ID
+    pub fn remap_ids(
+        &mut self,
+        mapping: &HashMap<AssetId, AssetId>,
+    ) -> DataSetResult<()> {
+        let remap = |asset_id: AssetId| mapping.get(&asset_id).copied().unwrap_or(asset_id);
+
+        // Check the result is valid before changing anything so the data set is never left
+        // partially remapped
+        let mut new_asset_ids = HashSet::default();
+        for asset_id in self.assets.keys() {
+            if !new_asset_ids.insert(remap(*asset_id)) {
+                Err(DataSetError::DuplicateAssetId)?;
+            }
+        }
+
+        let old_assets = std::mem::take(&mut self.assets);
+        for (asset_id, mut asset) in old_assets {
+            Arc::make_mut(&mut asset).remap_references(mapping);
+            self.assets.insert(remap(asset_id), asset);
+        }
+
+        self.assets_by_source_file.clear();
+        for (asset_id, asset) in &self.assets {
+            Self::add_to_source_file_index(&mut self.assets_by_source_file, *asset_id, asset);
+        }
+
+        Ok(())
+    }
+
+    /// Rewrites references held by a single asset according to the mapping, without changing any
+    /// asset's ID. Used to repoint references from one asset to another, i.e. when merging
+    /// duplicates. Returns error if the asset does not exist
+    pub fn remap_references(
+        &mut self,
+        asset_id: AssetId,
+        mapping: &HashMap<AssetId, AssetId>,
+    ) -> DataSetResult<()> {
+        let asset = self
+            .assets
+            .get_mut(&asset_id)
+            .map(Arc::make_mut)
+            .ok_or(DataSetError::AssetNotFound)?;
+        asset.remap_references(mapping);
+        Ok(())
+    }
+
+    /// Returns every asset that directly references any of the given assets as a prototype,
+    /// location, build settings, path reference override or asset ref property, sorted by ID. This
+    /// scans all assets.
+    pub fn assets_referencing(
+        &self,
+        referenced_asset_ids: &HashSet<AssetId>,
+    ) -> Vec<AssetId> {
+        let mut referencing_asset_ids: Vec<_> = self
+            .assets
+            .iter()
+            .filter(|(_, asset)| {
+                let mut references = HashSet::default();
+                asset.gather_direct_references(&mut references);
+                !references.is_disjoint(referenced_asset_ids)
+            })
+            .map(|(asset_id, _)| *asset_id)
+            .collect();
+        referencing_asset_ids.sort();
+        referencing_asset_ids
+    }
+
     /// Returns error if asset did not exist
     pub fn delete_asset(
         &mut self,
         asset_id: AssetId,
     ) -> DataSetResult<()> {
-        if self.assets.remove(&asset_id).is_none() {
-            Err(DataSetError::AssetNotFound)?
-        } else {
-            Ok(())
-        }
+        let asset = self
+            .assets
+            .remove(&asset_id)
+            .ok_or(DataSetError::AssetNotFound)?;
+        Self::remove_from_source_file_index(&mut self.assets_by_source_file, asset_id, &asset);
+        Ok(())
     }
 
-    /// Returns error if asset does not exist
+    /// Returns error if asset does not exist, or if the new location is the asset itself or one of
+    /// its children, as that would make the asset its own ancestor
     pub fn set_asset_location(
         &mut self,
         asset_id: AssetId,
         new_location: AssetLocation,
     ) -> DataSetResult<()> {
+        let mut visited_asset_ids = HashSet::default();
         let mut new_parent_asset_id_iter = Some(new_location.path_node_id());
         while let Some(new_parent_asset_id) = new_parent_asset_id_iter {
             if new_parent_asset_id == asset_id {
                 // Cannot make an asset a child of its own children
                 return Err(DataSetError::NewLocationIsChildOfCurrentAsset)?;
             }
+
+            if !visited_asset_ids.insert(new_parent_asset_id) {
+                // The new location's ancestors already form a cycle, don't add anything to it
+                return Err(DataSetError::LocationCycleDetected)?;
+            }
+
             new_parent_asset_id_iter = self
                 .asset_location(new_parent_asset_id)
                 .map(|x| x.path_node_id())
@@ -730,6 +1332,7 @@ impl DataSet {
         let asset = self
             .assets
             .get_mut(&asset_id)
+            .map(Arc::make_mut)
             .ok_or(DataSetError::AssetNotFound)?;
 
         asset.asset_location = new_location;
@@ -745,9 +1348,12 @@ impl DataSet {
         let asset = self
             .assets
             .get_mut(&asset_id)
+            .map(Arc::make_mut)
             .ok_or(DataSetError::AssetNotFound)?;
 
+        Self::remove_from_source_file_index(&mut self.assets_by_source_file, asset_id, asset);
         asset.import_info = Some(import_info);
+        Self::add_to_source_file_index(&mut self.assets_by_source_file, asset_id, asset);
         Ok(())
     }
 
@@ -764,6 +1370,10 @@ impl DataSet {
             .get(&asset_id)
             .ok_or(DataSetError::AssetNotFound)?;
 
+        if let Some(old) = self.assets.get(&asset_id) {
+            Self::remove_from_source_file_index(&mut self.assets_by_source_file, asset_id, old);
+        }
+        Self::add_to_source_file_index(&mut self.assets_by_source_file, asset_id, asset);
         self.assets.insert(asset_id, asset.clone());
         Ok(())
     }
@@ -789,23 +1399,96 @@ impl DataSet {
         let asset = self
             .assets
             .get_mut(&asset_id)
+            .map(Arc::make_mut)
             .ok_or(DataSetError::AssetNotFound)?;
 
         asset.asset_name = asset_name;
         Ok(())
     }
 
-    /// Returns the asset's parent or none if the asset does not exist
-    pub fn asset_location(
+    /// Returns the assets referenced by fields marked as owned. Only the asset's own overrides are
+    /// considered, values inherited from a prototype are owned by the prototype.
+    pub fn owned_assets(
         &self,
+        schema_set: &SchemaSet,
         asset_id: AssetId,
-    ) -> Option<AssetLocation> {
-        self.assets
+    ) -> DataSetResult<Vec<AssetId>> {
+        let asset = self
+            .assets
+            .get(&asset_id)
+            .ok_or(DataSetError::AssetNotFound)?;
+
+        let mut owned_assets = Vec::default();
+        for (path, value) in &asset.properties {
+            let Value::AssetRef(referenced_asset_id) = value else {
+                continue;
+            };
+
+            if referenced_asset_id.is_null() {
+                continue;
+            }
+
+            let is_owned = asset
+                .schema
+                .find_property_field(path, schema_set.schemas())
+                .map(|x| x.markup().owned)
+                .unwrap_or(false);
+            if is_owned {
+                owned_assets.push(*referenced_asset_id);
+            }
+        }
+
+        owned_assets.sort();
+        owned_assets.dedup();
+        Ok(owned_assets)
+    }
+
+    /// Returns the asset's parent or none if the asset does not exist
+    pub fn asset_location(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<AssetLocation> {
+        self.assets
             .get(&asset_id)
             .map(|x| &x.asset_location)
             .copied()
     }
 
+    /// Returns true if the asset is located beneath the given location, directly or through other
+    /// path nodes. Locations that form a cycle or have a missing parent end the search.
+    pub fn is_asset_under_location(
+        &self,
+        asset_id: AssetId,
+        location: AssetLocation,
+    ) -> bool {
+        let mut visited = HashSet::default();
+        let mut parent = self.asset_location(asset_id);
+        while let Some(parent_location) = parent {
+            if parent_location.is_null() || !visited.insert(parent_location) {
+                return false;
+            }
+
+            if parent_location == location {
+                return true;
+            }
+
+            parent = self.asset_location(parent_location.path_node_id);
+        }
+
+        false
+    }
+
+    /// Returns all assets located beneath the given location, not including the path node at the
+    /// location itself. Data sources can pass their root location to find the assets they own.
+    pub fn assets_under_location(
+        &self,
+        location: AssetLocation,
+    ) -> impl Iterator<Item = AssetId> + '_ {
+        self.assets.keys().copied().filter(move |&asset_id| {
+            asset_id != location.path_node_id && self.is_asset_under_location(asset_id, location)
+        })
+    }
+
     /// Returns the asset locations from the parent all the way up to the root parent. If a cycle is
     /// detected or any elements in the chain are not found, an error is returned
     pub fn asset_location_chain(
@@ -1016,6 +1699,44 @@ impl DataSet {
         Ok(all_references)
     }
 
+    /// Returns every asset the given asset directly depends on: its prototype, build settings,
+    /// asset ref properties and path references that resolve to an asset. Follow the returned
+    /// assets' references to gather all transitive dependencies. Missing assets may be included.
+    pub fn resolve_all_references(
+        &self,
+        asset_id: AssetId,
+    ) -> DataSetResult<HashSet<AssetId>> {
+        let asset = self
+            .assets
+            .get(&asset_id)
+            .ok_or(DataSetError::AssetNotFound)?;
+
+        let mut references = HashSet::default();
+        if let Some(prototype) = asset.prototype {
+            references.insert(prototype);
+        }
+
+        if let Some(build_settings) = asset.build_info.build_settings {
+            references.insert(build_settings);
+        }
+
+        // Properties inherited from the prototype are found by following the prototype
+        for value in asset.properties.values() {
+            value.gather_asset_refs(&mut references);
+        }
+
+        for canonical_path in self.resolve_all_hashed_path_references(asset_id)?.values() {
+            let referenced_asset_id = self
+                .do_resolve_canonical_path_reference_into_asset_id(asset, canonical_path)
+                .or_else(|| self.asset_by_source_file(canonical_path));
+            if let Some(referenced_asset_id) = referenced_asset_id {
+                references.insert(referenced_asset_id);
+            }
+        }
+
+        Ok(references)
+    }
+
     pub fn get_all_path_reference_overrides(
         &mut self,
         asset_id: AssetId,
@@ -1034,6 +1755,7 @@ impl DataSet {
         let asset = self
             .assets
             .get_mut(&asset_id)
+            .map(Arc::make_mut)
             .ok_or(DataSetError::AssetNotFound)?;
 
         asset
@@ -1043,6 +1765,32 @@ impl DataSet {
         Ok(())
     }
 
+    /// Returns the asset holding build settings for the given asset, if one is assigned
+    pub fn build_settings(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<AssetId> {
+        self.assets
+            .get(&asset_id)
+            .and_then(|x| x.build_info.build_settings)
+    }
+
+    /// Returns error if asset does not exist
+    pub fn set_build_settings(
+        &mut self,
+        asset_id: AssetId,
+        build_settings: Option<AssetId>,
+    ) -> DataSetResult<()> {
+        let asset = self
+            .assets
+            .get_mut(&asset_id)
+            .map(Arc::make_mut)
+            .ok_or(DataSetError::AssetNotFound)?;
+
+        asset.build_info.build_settings = build_settings;
+        Ok(())
+    }
+
     pub fn asset_prototype(
         &self,
         asset_id: AssetId,
@@ -1057,16 +1805,37 @@ impl DataSet {
         self.assets.get(&asset_id).map(|x| &x.schema)
     }
 
+    /// Returns a readable name for the type of the property at the given path, i.e.
+    /// "DynamicArray<Vec3>"
+    pub fn property_type_name(
+        &self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<String> {
+        let property_schema = self
+            .asset_schema(asset_id)
+            .ok_or(DataSetError::AssetNotFound)?
+            .find_property_schema(path, schema_set.schemas())
+            .ok_or(DataSetError::SchemaNotFound)?;
+        Ok(property_schema.type_name(schema_set.schemas()))
+    }
+
     fn hash_property_data(
         hasher: &mut SipHasher,
         properties: &HashMap<String, Value>,
         property_null_overrides: &HashMap<String, NullOverride>,
         properties_in_replace_mode: &HashSet<String>,
         dynamic_collection_entries: &HashMap<String, OrderedSet<Uuid>>,
+        is_path_ignored: impl Fn(&str) -> bool,
     ) {
         // properties
         let mut properties_hash = 0;
         for (key, value) in properties {
+            if is_path_ignored(key) {
+                continue;
+            }
+
             let mut inner_hasher = siphasher::sip::SipHasher::default();
             key.hash(&mut inner_hasher);
             value.hash(&mut inner_hasher);
@@ -1077,6 +1846,10 @@ impl DataSet {
         // property_null_overrides
         let mut property_null_overrides_hash = 0;
         for (key, value) in property_null_overrides {
+            if is_path_ignored(key) {
+                continue;
+            }
+
             let mut inner_hasher = siphasher::sip::SipHasher::default();
             key.hash(&mut inner_hasher);
             value.hash(&mut inner_hasher);
@@ -1087,6 +1860,10 @@ impl DataSet {
         // properties_in_replace_mode
         let mut properties_in_replace_mode_hash = 0;
         for value in properties_in_replace_mode {
+            if is_path_ignored(value) {
+                continue;
+            }
+
             let mut inner_hasher = siphasher::sip::SipHasher::default();
             value.hash(&mut inner_hasher);
             properties_in_replace_mode_hash =
@@ -1097,6 +1874,10 @@ impl DataSet {
         // dynamic_collection_entries
         let mut dynamic_collection_entries_hash = 0;
         for (key, value) in dynamic_collection_entries {
+            if is_path_ignored(key) {
+                continue;
+            }
+
             let mut inner_hasher = siphasher::sip::SipHasher::default();
             key.hash(&mut inner_hasher);
 
@@ -1171,6 +1952,7 @@ impl DataSet {
             &asset.property_null_overrides,
             &asset.properties_in_replace_mode,
             &asset.dynamic_collection_entries,
+            |_| false,
         );
 
         // Properties only mode hashes up the prototype chain
@@ -1188,6 +1970,39 @@ impl DataSet {
         Ok(asset_hash)
     }
 
+    /// Same as hash_object() with HashObjectMode::PropertiesOnly, except properties of fields
+    /// marked build_ignore are skipped. Use this to detect changes that require a rebuild.
+    pub fn hash_object_build_inputs(
+        &self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+    ) -> DataSetResult<u64> {
+        let asset = self
+            .assets
+            .get(&asset_id)
+            .ok_or(DataSetError::AssetNotFound)?;
+
+        let mut hasher = SipHasher::default();
+        let schema = &asset.schema;
+        schema.fingerprint().hash(&mut hasher);
+
+        Self::hash_property_data(
+            &mut hasher,
+            &asset.properties,
+            &asset.property_null_overrides,
+            &asset.properties_in_replace_mode,
+            &asset.dynamic_collection_entries,
+            |path| schema.is_property_build_ignored(path, schema_set.schemas()),
+        );
+
+        if let Some(prototype) = asset.prototype {
+            self.hash_object_build_inputs(schema_set, prototype)?
+                .hash(&mut hasher);
+        }
+
+        Ok(hasher.finish())
+    }
+
     /// Gets if the property has a null override associated with it *on this object* ignoring the
     /// prototype. An error will be returned if the asset doesn't exist, the schema doesn't exist,
     /// or if this field is not nullable
@@ -1229,6 +2044,7 @@ impl DataSet {
         let asset = self
             .assets
             .get_mut(&asset_id)
+            .map(Arc::make_mut)
             .ok_or(DataSetError::AssetNotFound)?;
         let property_schema = asset
             .schema
@@ -1379,9 +2195,9 @@ impl DataSet {
         if let Some(value) = &value {
             if !value.matches_schema(&property_schema, schema_set.schemas()) {
                 log::debug!(
-                    "Value {:?} doesn't match schema {:?} on schema {:?} path {:?}",
+                    "Value {:?} doesn't match type {} on schema {:?} path {:?}",
                     value,
-                    property_schema,
+                    property_schema.type_name(schema_set.schemas()),
                     asset_schema.name(),
                     path.as_ref()
                 );
@@ -1394,6 +2210,7 @@ impl DataSet {
         let obj = self
             .assets
             .get_mut(&asset_id)
+            .map(Arc::make_mut)
             .ok_or(DataSetError::AssetNotFound)?;
         let old_value = if let Some(value) = value {
             obj.properties.insert(path.as_ref().to_string(), value)
@@ -1403,6 +2220,54 @@ impl DataSet {
         Ok(old_value)
     }
 
+    // Modifies the property's override in place. This avoids re-inserting into the property map
+    // for hot edit loops (i.e. dragging a slider). If the property is not overridden, f is applied
+    // to a copy of the inherited/default value and the result is set as the override. If f
+    // changes the value to something that doesn't match the property's schema, an error is
+    // returned and the override is left as it was.
+    pub fn update_property_override<F: FnOnce(&mut Value)>(
+        &mut self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+        f: F,
+    ) -> DataSetResult<()> {
+        let property_schema = self.validate_parent_paths(schema_set, asset_id, path.as_ref())?;
+
+        let obj = self
+            .assets
+            .get_mut(&asset_id)
+            .map(Arc::make_mut)
+            .ok_or(DataSetError::AssetNotFound)?;
+        if let Some(value) = obj.properties.get_mut(path.as_ref()) {
+            // f works on a copy that only replaces the override if it's valid, so the override is
+            // never left half-updated. Overrides are leaf values, which are copied without
+            // allocating (strings and bytes share their buffer), except for enum symbol names.
+            let mut new_value = value.clone();
+            (f)(&mut new_value);
+            if !new_value.matches_schema(&property_schema, schema_set.schemas()) {
+                log::debug!(
+                    "Value {:?} doesn't match type {} on schema {:?} path {:?}",
+                    new_value,
+                    property_schema.type_name(schema_set.schemas()),
+                    obj.schema.name(),
+                    path.as_ref()
+                );
+                return Err(DataSetError::ValueDoesNotMatchSchema)?;
+            }
+
+            *value = new_value;
+            return Ok(());
+        }
+
+        let mut value = self
+            .resolve_property(schema_set, asset_id, path.as_ref())?
+            .clone();
+        (f)(&mut value);
+        self.set_property_override(schema_set, asset_id, path, Some(value))?;
+        Ok(())
+    }
+
     pub fn apply_property_override_to_prototype(
         &mut self,
         schema_set: &SchemaSet,
@@ -1430,6 +2295,9 @@ impl DataSet {
         Ok(())
     }
 
+    /// Returns the property's value from this asset, the nearest prototype that overrides it, or
+    /// the schema default. The value is borrowed rather than cloned, so reading large bytes or
+    /// string properties is cheap.
     pub fn resolve_property<'a>(
         &'a self,
         schema_set: &'a SchemaSet,
@@ -1457,136 +2325,554 @@ impl DataSet {
         Ok(Value::default_for_schema(&property_schema, schema_set))
     }
 
-    fn get_dynamic_collection_entries(
-        asset: &DataSetAssetInfo,
-        path: impl AsRef<str>,
-    ) -> DataSetResult<std::slice::Iter<Uuid>> {
-        if let Some(overrides) = asset.dynamic_collection_entries.get(path.as_ref()) {
-            Ok(overrides.iter())
-        } else {
-            Ok(std::slice::Iter::default())
-        }
-    }
-
-    pub fn get_dynamic_array_entries(
+    /// Returns true if the asset or any of its prototypes sets the property, even if it is set to
+    /// the schema's default value. resolve_property() can't distinguish this from the property
+    /// being unset. Whether the property's parents are null or missing from their containers is
+    /// not checked.
+    pub fn is_property_explicitly_set(
         &self,
         schema_set: &SchemaSet,
         asset_id: AssetId,
         path: impl AsRef<str>,
-    ) -> DataSetResult<std::slice::Iter<Uuid>> {
+    ) -> DataSetResult<bool> {
         let asset = self
             .assets
             .get(&asset_id)
             .ok_or(DataSetError::AssetNotFound)?;
-        let property_schema = asset
+        asset
             .schema
-            .find_property_schema(&path, schema_set.schemas())
+            .find_property_schema(path.as_ref(), schema_set.schemas())
             .ok_or(DataSetError::SchemaNotFound)?;
 
-        if !property_schema.is_dynamic_array() {
-            return Err(DataSetError::InvalidSchema)?;
+        let mut prototype = Some(&**asset);
+        while let Some(obj) = prototype {
+            if obj.properties.contains_key(path.as_ref()) {
+                return Ok(true);
+            }
+
+            // A missing prototype is treated as unset, same as resolve_property()
+            prototype = obj
+                .prototype
+                .and_then(|x| self.assets.get(&x))
+                .map(|x| &**x);
         }
 
-        Self::get_dynamic_collection_entries(asset, path)
+        Ok(false)
     }
 
-    pub fn get_map_entries(
-        &self,
-        schema_set: &SchemaSet,
+    // Same as resolve_property(), but top-level fields are looked up directly on the asset's
+    // record. They have no parents that could be null or missing from a container, so computing
+    // the path's ancestry (and the allocations that come with it) can be skipped.
+    fn resolve_scalar_property<'a>(
+        &'a self,
+        schema_set: &'a SchemaSet,
         asset_id: AssetId,
-        path: impl AsRef<str>,
-    ) -> DataSetResult<std::slice::Iter<Uuid>> {
+        path: &str,
+    ) -> DataSetResult<&'a Value> {
+        if path.contains('.') {
+            return self.resolve_property(schema_set, asset_id, path);
+        }
+
         let asset = self
             .assets
             .get(&asset_id)
             .ok_or(DataSetError::AssetNotFound)?;
         let property_schema = asset
             .schema
-            .find_property_schema(&path, schema_set.schemas())
+            .field_schema(path)
             .ok_or(DataSetError::SchemaNotFound)?;
 
-        if !property_schema.is_map() {
-            return Err(DataSetError::InvalidSchema)?;
+        let mut prototype = Some(&**asset);
+        while let Some(obj) = prototype {
+            if let Some(value) = obj.properties.get(path) {
+                return Ok(value);
+            }
+
+            // A missing prototype is treated as unset, same as resolve_property()
+            prototype = obj
+                .prototype
+                .and_then(|x| self.assets.get(&x))
+                .map(|x| &**x);
         }
 
-        Self::get_dynamic_collection_entries(asset, path)
+        Ok(Value::default_for_schema(property_schema, schema_set))
     }
 
-    fn add_dynamic_collection_entry(
-        asset: &mut DataSetAssetInfo,
+    /// Resolves a boolean property without allocating when the property is a top-level field
+    pub fn resolve_boolean(
+        &self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
         path: impl AsRef<str>,
-    ) -> DataSetResult<Uuid> {
-        let entry = asset
-            .dynamic_collection_entries
-            .entry(path.as_ref().to_string())
-            .or_insert(Default::default());
-        let new_uuid = Uuid::new_v4();
-        let newly_inserted = entry.try_insert_at_end(new_uuid);
-        if !newly_inserted {
-            panic!("Created a new random UUID but it matched an existing UUID");
-        }
-        Ok(new_uuid)
+    ) -> DataSetResult<bool> {
+        self.resolve_scalar_property(schema_set, asset_id, path.as_ref())?
+            .as_boolean()
     }
 
-    pub fn add_dynamic_array_entry(
-        &mut self,
+    /// Resolves an i32 property without allocating when the property is a top-level field
+    pub fn resolve_i32(
+        &self,
         schema_set: &SchemaSet,
         asset_id: AssetId,
         path: impl AsRef<str>,
-    ) -> DataSetResult<Uuid> {
-        let asset = self
-            .assets
-            .get_mut(&asset_id)
-            .ok_or(DataSetError::AssetNotFound)?;
-        let property_schema = asset
-            .schema
-            .find_property_schema(&path, schema_set.schemas())
-            .ok_or(DataSetError::SchemaNotFound)?;
-
-        if !property_schema.is_dynamic_array() {
-            return Err(DataSetError::InvalidSchema)?;
-        }
+    ) -> DataSetResult<i32> {
+        self.resolve_scalar_property(schema_set, asset_id, path.as_ref())?
+            .as_i32()
+    }
 
-        Self::add_dynamic_collection_entry(asset, path)
+    /// Resolves a u32 property without allocating when the property is a top-level field
+    pub fn resolve_u32(
+        &self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<u32> {
+        self.resolve_scalar_property(schema_set, asset_id, path.as_ref())?
+            .as_u32()
     }
 
-    pub fn add_map_entry(
-        &mut self,
+    /// Resolves an i64 property without allocating when the property is a top-level field
+    pub fn resolve_i64(
+        &self,
         schema_set: &SchemaSet,
         asset_id: AssetId,
         path: impl AsRef<str>,
-    ) -> DataSetResult<Uuid> {
-        let asset = self
-            .assets
-            .get_mut(&asset_id)
-            .ok_or(DataSetError::AssetNotFound)?;
-        let property_schema = asset
-            .schema
-            .find_property_schema(&path, schema_set.schemas())
-            .ok_or(DataSetError::SchemaNotFound)?;
+    ) -> DataSetResult<i64> {
+        self.resolve_scalar_property(schema_set, asset_id, path.as_ref())?
+            .as_i64()
+    }
 
-        if !property_schema.is_map() {
-            return Err(DataSetError::InvalidSchema)?;
-        }
+    /// Resolves a u64 property without allocating when the property is a top-level field
+    pub fn resolve_u64(
+        &self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<u64> {
+        self.resolve_scalar_property(schema_set, asset_id, path.as_ref())?
+            .as_u64()
+    }
 
-        Self::add_dynamic_collection_entry(asset, path)
+    /// Resolves an f32 property without allocating when the property is a top-level field
+    pub fn resolve_f32(
+        &self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<f32> {
+        self.resolve_scalar_property(schema_set, asset_id, path.as_ref())?
+            .as_f32()
     }
 
-    pub fn insert_dynamic_array_entry(
-        &mut self,
+    /// Resolves an f64 property without allocating when the property is a top-level field
+    pub fn resolve_f64(
+        &self,
         schema_set: &SchemaSet,
         asset_id: AssetId,
         path: impl AsRef<str>,
-        index: usize,
-        entry_uuid: Uuid,
-    ) -> DataSetResult<()> {
+    ) -> DataSetResult<f64> {
+        self.resolve_scalar_property(schema_set, asset_id, path.as_ref())?
+            .as_f64()
+    }
+
+    /// Returns a view for reading many resolved properties of one asset. See ResolvedAssetView.
+    pub fn asset_view<'a>(
+        &'a self,
+        schema_set: &'a SchemaSet,
+        asset_id: AssetId,
+    ) -> DataSetResult<ResolvedAssetView<'a>> {
         let asset = self
             .assets
-            .get_mut(&asset_id)
+            .get(&asset_id)
             .ok_or(DataSetError::AssetNotFound)?;
-        let property_schema = asset
-            .schema
-            .find_property_schema(&path, schema_set.schemas())
+
+        let mut prototype_chain = vec![&**asset];
+        let mut prototype_id = asset.prototype;
+        while let Some(prototype_id_iter) = prototype_id {
+            // A missing prototype is treated as unset, same as resolve_property()
+            let Some(prototype) = self.assets.get(&prototype_id_iter) else {
+                break;
+            };
+
+            prototype_chain.push(&**prototype);
+            prototype_id = prototype.prototype;
+        }
+
+        Ok(ResolvedAssetView {
+            data_set: self,
+            schema_set,
+            asset_id,
+            prototype_chain,
+            property_schemas: Default::default(),
+        })
+    }
+
+    /// Returns what every asset in the prototype chain sets the property to, starting with the
+    /// given asset and ending with the schema default. Useful for finding out where a resolved
+    /// value comes from.
+    pub fn resolve_property_chain<'a>(
+        &'a self,
+        schema_set: &'a SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<Vec<PropertyContribution<'a>>> {
+        let property_schema = self.validate_parent_paths(schema_set, asset_id, path.as_ref())?;
+
+        let mut contributions = Vec::default();
+        let mut prototype_id = Some(asset_id);
+        while let Some(prototype_id_iter) = prototype_id {
+            let obj = self.assets.get(&prototype_id_iter);
+            if let Some(obj) = obj {
+                if let Some(value) = obj.properties.get(path.as_ref()) {
+                    contributions.push(PropertyContribution::Value(prototype_id_iter, value));
+                } else if let Some(null_override) = obj.property_null_overrides.get(path.as_ref()) {
+                    contributions.push(PropertyContribution::NullOverride(
+                        prototype_id_iter,
+                        *null_override,
+                    ));
+                } else {
+                    contributions.push(PropertyContribution::NotSet(prototype_id_iter));
+                }
+
+                prototype_id = obj.prototype;
+            } else {
+                // The prototype being referenced was not found, same as resolve_property()
+                prototype_id = None;
+            }
+        }
+
+        contributions.push(PropertyContribution::SchemaDefault(
+            Value::default_for_schema(&property_schema, schema_set),
+        ));
+        Ok(contributions)
+    }
+
+    /// Renders the asset as an indented tree following its schema, for debugging. Values are marked
+    /// as overridden by this asset, inherited from a prototype, or the schema default.
+    pub fn debug_print_asset(
+        &self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+    ) -> DataSetResult<String> {
+        let asset = self
+            .assets
+            .get(&asset_id)
+            .ok_or(DataSetError::AssetNotFound)?;
+
+        let mut output = format!(
+            "{} {:?} ({})",
+            asset_id,
+            asset
+                .asset_name
+                .as_string()
+                .map(|x| x.as_str())
+                .unwrap_or_default(),
+            asset.schema.name()
+        );
+        if let Some(prototype) = asset.prototype {
+            output.push_str(&format!(" prototype: {}", prototype));
+        }
+        output.push('\n');
+
+        for field in asset.schema.fields() {
+            output.push_str(&self.debug_print_property(
+                schema_set,
+                asset_id,
+                field.field_schema(),
+                field.name(),
+                field.name(),
+                1,
+            )?);
+        }
+
+        Ok(output)
+    }
+
+    fn debug_print_property(
+        &self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        schema: &Schema,
+        label: &str,
+        path: &str,
+        depth: usize,
+    ) -> DataSetResult<String> {
+        let indent = "  ".repeat(depth);
+        let mut output = String::new();
+        match schema {
+            Schema::Nullable(inner_schema) => {
+                let origin = self.debug_property_origin(schema_set, asset_id, path)?;
+                if self.resolve_null_override(schema_set, asset_id, path)?
+                    == NullOverride::SetNonNull
+                {
+                    output.push_str(&format!("{}{}: non-null{}\n", indent, label, origin));
+                    output.push_str(&self.debug_print_property(
+                        schema_set,
+                        asset_id,
+                        inner_schema,
+                        "value",
+                        &format!("{}.value", path),
+                        depth + 1,
+                    )?);
+                } else {
+                    output.push_str(&format!("{}{}: null{}\n", indent, label, origin));
+                }
+            }
+            Schema::Record(fingerprint) => {
+                let record = schema_set
+                    .find_named_type_by_fingerprint(*fingerprint)
+                    .and_then(|x| x.try_as_record())
+                    .ok_or(DataSetError::SchemaNotFound)?;
+                output.push_str(&format!("{}{}: {}\n", indent, label, record.name()));
+                for field in record.fields() {
+                    output.push_str(&self.debug_print_property(
+                        schema_set,
+                        asset_id,
+                        field.field_schema(),
+                        field.name(),
+                        &format!("{}.{}", path, field.name()),
+                        depth + 1,
+                    )?);
+                }
+            }
+            Schema::StaticArray(static_array) => {
+                output.push_str(&format!(
+                    "{}{}: [{}]\n",
+                    indent,
+                    label,
+                    static_array.length()
+                ));
+                for index in 0..static_array.length() {
+                    output.push_str(&self.debug_print_property(
+                        schema_set,
+                        asset_id,
+                        static_array.item_type(),
+                        &format!("[{}]", index),
+                        &format!("{}.{}", path, index),
+                        depth + 1,
+                    )?);
+                }
+            }
+            Schema::DynamicArray(dynamic_array) => {
+                let entries = self.resolve_dynamic_array_entries(schema_set, asset_id, path)?;
+                let own_entries: HashSet<Uuid> = self
+                    .get_dynamic_array_entries(schema_set, asset_id, path)?
+                    .copied()
+                    .collect();
+                let replace_mode = self.get_override_behavior(schema_set, asset_id, path)?
+                    == OverrideBehavior::Replace;
+                output.push_str(&format!(
+                    "{}{}: {} entries{}\n",
+                    indent,
+                    label,
+                    entries.len(),
+                    if replace_mode { " (replace)" } else { "" }
+                ));
+                for (index, entry) in entries.iter().enumerate() {
+                    let entry_origin = if own_entries.contains(entry) {
+                        "added here"
+                    } else {
+                        "inherited"
+                    };
+                    output.push_str(&self.debug_print_property(
+                        schema_set,
+                        asset_id,
+                        dynamic_array.item_type(),
+                        &format!("[{}] {} ({})", index, entry, entry_origin),
+                        &format!("{}.{}", path, entry),
+                        depth + 1,
+                    )?);
+                }
+            }
+            Schema::Map(_) => {
+                let entries = self.resolve_map_entries(schema_set, asset_id, path)?;
+                output.push_str(&format!("{}{}: {} entries\n", indent, label, entries.len()));
+                for entry in entries.iter() {
+                    output.push_str(&format!("{}  {}\n", indent, entry));
+                }
+            }
+            _ => {
+                let value = self.resolve_property(schema_set, asset_id, path)?;
+                let origin = self.debug_property_origin(schema_set, asset_id, path)?;
+                let value = match value {
+                    Value::Boolean(x) => x.to_string(),
+                    Value::I32(x) => x.to_string(),
+                    Value::I64(x) => x.to_string(),
+                    Value::U32(x) => x.to_string(),
+                    Value::U64(x) => x.to_string(),
+                    Value::F32(x) => x.to_string(),
+                    Value::F64(x) => x.to_string(),
+                    Value::Bytes(x) => format!("<{} bytes>", x.len()),
+                    Value::String(x) => format!("{:?}", x),
+                    Value::AssetRef(x) => x.to_string(),
+                    Value::Enum(x) => x.symbol_name().to_string(),
+                    _ => format!("{:?}", value),
+                };
+                output.push_str(&format!("{}{}: {}{}\n", indent, label, value, origin));
+            }
+        }
+
+        Ok(output)
+    }
+
+    // Where the resolved value of a property comes from, formatted for debug_print_asset()
+    fn debug_property_origin(
+        &self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: &str,
+    ) -> DataSetResult<String> {
+        for contribution in self.resolve_property_chain(schema_set, asset_id, path)? {
+            match contribution {
+                PropertyContribution::Value(contributor, _)
+                | PropertyContribution::NullOverride(contributor, _) => {
+                    return Ok(if contributor == asset_id {
+                        " (overridden)".to_string()
+                    } else {
+                        format!(" (inherited from {})", contributor)
+                    });
+                }
+                PropertyContribution::NotSet(_) => {}
+                PropertyContribution::SchemaDefault(_) => break,
+            }
+        }
+
+        Ok(" (default)".to_string())
+    }
+
+    fn get_dynamic_collection_entries(
+        asset: &DataSetAssetInfo,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<std::slice::Iter<Uuid>> {
+        if let Some(overrides) = asset.dynamic_collection_entries.get(path.as_ref()) {
+            Ok(overrides.iter())
+        } else {
+            Ok(std::slice::Iter::default())
+        }
+    }
+
+    pub fn get_dynamic_array_entries(
+        &self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<std::slice::Iter<Uuid>> {
+        let asset = self
+            .assets
+            .get(&asset_id)
+            .ok_or(DataSetError::AssetNotFound)?;
+        let property_schema = asset
+            .schema
+            .find_property_schema(&path, schema_set.schemas())
+            .ok_or(DataSetError::SchemaNotFound)?;
+
+        if !property_schema.is_dynamic_array() {
+            return Err(DataSetError::InvalidSchema)?;
+        }
+
+        Self::get_dynamic_collection_entries(asset, path)
+    }
+
+    pub fn get_map_entries(
+        &self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<std::slice::Iter<Uuid>> {
+        let asset = self
+            .assets
+            .get(&asset_id)
+            .ok_or(DataSetError::AssetNotFound)?;
+        let property_schema = asset
+            .schema
+            .find_property_schema(&path, schema_set.schemas())
+            .ok_or(DataSetError::SchemaNotFound)?;
+
+        if !property_schema.is_map() {
+            return Err(DataSetError::InvalidSchema)?;
+        }
+
+        Self::get_dynamic_collection_entries(asset, path)
+    }
+
+    fn add_dynamic_collection_entry(
+        asset: &mut DataSetAssetInfo,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<Uuid> {
+        let entry = asset
+            .dynamic_collection_entries
+            .entry(path.as_ref().to_string())
+            .or_insert(Default::default());
+        let new_uuid = Uuid::new_v4();
+        let newly_inserted = entry.try_insert_at_end(new_uuid);
+        if !newly_inserted {
+            panic!("Created a new random UUID but it matched an existing UUID");
+        }
+        Ok(new_uuid)
+    }
+
+    pub fn add_dynamic_array_entry(
+        &mut self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<Uuid> {
+        let asset = self
+            .assets
+            .get_mut(&asset_id)
+            .map(Arc::make_mut)
+            .ok_or(DataSetError::AssetNotFound)?;
+        let property_schema = asset
+            .schema
+            .find_property_schema(&path, schema_set.schemas())
+            .ok_or(DataSetError::SchemaNotFound)?;
+
+        if !property_schema.is_dynamic_array() {
+            return Err(DataSetError::InvalidSchema)?;
+        }
+
+        Self::add_dynamic_collection_entry(asset, path)
+    }
+
+    pub fn add_map_entry(
+        &mut self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<Uuid> {
+        let asset = self
+            .assets
+            .get_mut(&asset_id)
+            .map(Arc::make_mut)
+            .ok_or(DataSetError::AssetNotFound)?;
+        let property_schema = asset
+            .schema
+            .find_property_schema(&path, schema_set.schemas())
+            .ok_or(DataSetError::SchemaNotFound)?;
+
+        if !property_schema.is_map() {
+            return Err(DataSetError::InvalidSchema)?;
+        }
+
+        Self::add_dynamic_collection_entry(asset, path)
+    }
+
+    pub fn insert_dynamic_array_entry(
+        &mut self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+        index: usize,
+        entry_uuid: Uuid,
+    ) -> DataSetResult<()> {
+        let asset = self
+            .assets
+            .get_mut(&asset_id)
+            .map(Arc::make_mut)
+            .ok_or(DataSetError::AssetNotFound)?;
+        let property_schema = asset
+            .schema
+            .find_property_schema(&path, schema_set.schemas())
             .ok_or(DataSetError::SchemaNotFound)?;
 
         if !property_schema.is_dynamic_array() {
@@ -1604,6 +2890,38 @@ impl DataSet {
         }
     }
 
+    pub fn insert_map_entry(
+        &mut self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+        entry_uuid: Uuid,
+    ) -> DataSetResult<()> {
+        let asset = self
+            .assets
+            .get_mut(&asset_id)
+            .map(Arc::make_mut)
+            .ok_or(DataSetError::AssetNotFound)?;
+        let property_schema = asset
+            .schema
+            .find_property_schema(&path, schema_set.schemas())
+            .ok_or(DataSetError::SchemaNotFound)?;
+
+        if !property_schema.is_map() {
+            return Err(DataSetError::InvalidSchema)?;
+        }
+
+        let entry = asset
+            .dynamic_collection_entries
+            .entry(path.as_ref().to_string())
+            .or_insert(Default::default());
+        if entry.try_insert_at_end(entry_uuid) {
+            Ok(())
+        } else {
+            Err(DataSetError::DuplicateEntryKey)?
+        }
+    }
+
     fn remove_dynamic_collection_entry(
         asset: &mut DataSetAssetInfo,
         path: impl AsRef<str>,
@@ -1629,6 +2947,7 @@ impl DataSet {
         let asset = self
             .assets
             .get_mut(&asset_id)
+            .map(Arc::make_mut)
             .ok_or(DataSetError::AssetNotFound)?;
         let property_schema = asset
             .schema
@@ -1652,6 +2971,7 @@ impl DataSet {
         let asset = self
             .assets
             .get_mut(&asset_id)
+            .map(Arc::make_mut)
             .ok_or(DataSetError::AssetNotFound)?;
         let property_schema = asset
             .schema
@@ -1665,6 +2985,118 @@ impl DataSet {
         Self::remove_dynamic_collection_entry(asset, path, element_id)
     }
 
+    /// Moves an entry of a dynamic array to new_index in the resolved array. The entry keeps its
+    /// ID, so overrides of its properties stay attached to it. Only entries added by this asset
+    /// can be moved. In append mode, entries inherited from the prototype always come first, so
+    /// an entry can't be moved before them.
+    pub fn reorder_dynamic_array_entry(
+        &mut self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+        entry_id: Uuid,
+        new_index: usize,
+    ) -> DataSetResult<()> {
+        let property_schema = self
+            .assets
+            .get(&asset_id)
+            .ok_or(DataSetError::AssetNotFound)?
+            .schema
+            .find_property_schema(&path, schema_set.schemas())
+            .ok_or(DataSetError::SchemaNotFound)?;
+
+        if !property_schema.is_dynamic_array() {
+            return Err(DataSetError::InvalidSchema)?;
+        }
+
+        let resolved_entries = self.resolve_dynamic_collection_entries(asset_id, path.as_ref())?;
+        let asset = self
+            .assets
+            .get_mut(&asset_id)
+            .map(Arc::make_mut)
+            .ok_or(DataSetError::AssetNotFound)?;
+        let Some(entries) = asset.dynamic_collection_entries.get_mut(path.as_ref()) else {
+            return Err(DataSetError::EntryKeyNotFound)?;
+        };
+        if !entries.contains(&entry_id) {
+            return Err(DataSetError::EntryKeyNotFound)?;
+        }
+
+        // Anything resolved that isn't one of this asset's entries was inherited from the prototype
+        let inherited_entry_count = resolved_entries
+            .iter()
+            .filter(|&x| !entries.contains(x))
+            .count();
+        let index = new_index
+            .checked_sub(inherited_entry_count)
+            .filter(|&x| x < entries.iter().len())
+            .ok_or(DataSetError::EntryIndexOutOfRange)?;
+
+        entries.remove(&entry_id);
+        entries.try_insert_at_position(index, entry_id);
+        Ok(())
+    }
+
+    fn move_dynamic_array_entry_next_to(
+        &mut self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+        entry_id: Uuid,
+        other_entry_id: Uuid,
+        after: bool,
+    ) -> DataSetResult<()> {
+        let resolved_entries =
+            self.resolve_dynamic_array_entries(schema_set, asset_id, path.as_ref())?;
+        let other_index = resolved_entries
+            .iter()
+            .filter(|&&x| x != entry_id)
+            .position(|&x| x == other_entry_id)
+            .ok_or(DataSetError::EntryKeyNotFound)?;
+        let new_index = if after { other_index + 1 } else { other_index };
+        self.reorder_dynamic_array_entry(schema_set, asset_id, path, entry_id, new_index)
+    }
+
+    /// Moves an entry of a dynamic array so that it is just before other_entry_id. See
+    /// reorder_dynamic_array_entry().
+    pub fn move_dynamic_array_entry_before(
+        &mut self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+        entry_id: Uuid,
+        other_entry_id: Uuid,
+    ) -> DataSetResult<()> {
+        self.move_dynamic_array_entry_next_to(
+            schema_set,
+            asset_id,
+            path,
+            entry_id,
+            other_entry_id,
+            false,
+        )
+    }
+
+    /// Moves an entry of a dynamic array so that it is just after other_entry_id. See
+    /// reorder_dynamic_array_entry().
+    pub fn move_dynamic_array_entry_after(
+        &mut self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+        entry_id: Uuid,
+        other_entry_id: Uuid,
+    ) -> DataSetResult<()> {
+        self.move_dynamic_array_entry_next_to(
+            schema_set,
+            asset_id,
+            path,
+            entry_id,
+            other_entry_id,
+            true,
+        )
+    }
+
     fn do_resolve_dynamic_collection_entries(
         &self,
         asset_id: AssetId,
@@ -1702,6 +3134,17 @@ impl DataSet {
         Ok(())
     }
 
+    fn resolve_dynamic_collection_entries(
+        &self,
+        asset_id: AssetId,
+        path: &str,
+    ) -> DataSetResult<Box<[Uuid]>> {
+        let mut resolved_entries = vec![];
+        self.do_resolve_dynamic_collection_entries(asset_id, path, &mut resolved_entries)?;
+        remove_duplicate_entries(&mut resolved_entries);
+        Ok(resolved_entries.into_boxed_slice())
+    }
+
     pub fn resolve_dynamic_array_entries(
         &self,
         schema_set: &SchemaSet,
@@ -1713,9 +3156,7 @@ impl DataSet {
             return Err(DataSetError::InvalidSchema)?;
         }
 
-        let mut resolved_entries = vec![];
-        self.do_resolve_dynamic_collection_entries(asset_id, path.as_ref(), &mut resolved_entries)?;
-        Ok(resolved_entries.into_boxed_slice())
+        self.resolve_dynamic_collection_entries(asset_id, path.as_ref())
     }
 
     pub fn resolve_map_entries(
@@ -1729,9 +3170,7 @@ impl DataSet {
             return Err(DataSetError::InvalidSchema)?;
         }
 
-        let mut resolved_entries = vec![];
-        self.do_resolve_dynamic_collection_entries(asset_id, path.as_ref(), &mut resolved_entries)?;
-        Ok(resolved_entries.into_boxed_slice())
+        self.resolve_dynamic_collection_entries(asset_id, path.as_ref())
     }
 
     pub fn get_override_behavior(
@@ -1771,6 +3210,7 @@ impl DataSet {
         let asset = self
             .assets
             .get_mut(&asset_id)
+            .map(Arc::make_mut)
             .ok_or(DataSetError::AssetNotFound)?;
         let property_schema = asset
             .schema
@@ -1806,6 +3246,73 @@ impl DataSet {
         Ok(PropertiesBundle::read(asset, path, schema_set)?)
     }
 
+    /// Removes all property overrides, null overrides, dynamic collection entries, and replace mode
+    /// flags from the asset so that it inherits everything from its prototype (or defaults if it
+    /// has no prototype). The removed overrides are returned and can be restored with
+    /// write_properties_bundle() using an empty path.
+    pub fn clear_all_overrides(
+        &mut self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+    ) -> DataSetResult<PropertiesBundle> {
+        let asset = self
+            .assets
+            .get_mut(&asset_id)
+            .map(Arc::make_mut)
+            .ok_or(DataSetError::AssetNotFound)?;
+        let properties_bundle = PropertiesBundle::read(asset, "", schema_set)?;
+        asset.properties.clear();
+        asset.property_null_overrides.clear();
+        asset.properties_in_replace_mode.clear();
+        asset.dynamic_collection_entries.clear();
+        Ok(properties_bundle)
+    }
+
+    /// Like clear_all_overrides(), but only clears overrides of property paths that should_clear()
+    /// returns true for. Only the removed overrides are returned.
+    pub fn clear_overrides_where<F: Fn(&str) -> bool>(
+        &mut self,
+        asset_id: AssetId,
+        should_clear: F,
+    ) -> DataSetResult<PropertiesBundle> {
+        let asset = self
+            .assets
+            .get_mut(&asset_id)
+            .map(Arc::make_mut)
+            .ok_or(DataSetError::AssetNotFound)?;
+
+        let (properties, kept_properties) = std::mem::take(&mut asset.properties)
+            .into_iter()
+            .partition(|(path, _)| should_clear(path));
+        asset.properties = kept_properties;
+
+        let (property_null_overrides, kept_property_null_overrides) =
+            std::mem::take(&mut asset.property_null_overrides)
+                .into_iter()
+                .partition(|(path, _)| should_clear(path));
+        asset.property_null_overrides = kept_property_null_overrides;
+
+        let (properties_in_replace_mode, kept_properties_in_replace_mode) =
+            std::mem::take(&mut asset.properties_in_replace_mode)
+                .into_iter()
+                .partition(|path| should_clear(path));
+        asset.properties_in_replace_mode = kept_properties_in_replace_mode;
+
+        let (dynamic_collection_entries, kept_dynamic_collection_entries) =
+            std::mem::take(&mut asset.dynamic_collection_entries)
+                .into_iter()
+                .partition(|(path, _)| should_clear(path));
+        asset.dynamic_collection_entries = kept_dynamic_collection_entries;
+
+        Ok(PropertiesBundle::new(
+            Schema::Record(asset.schema().fingerprint()),
+            properties,
+            property_null_overrides,
+            properties_in_replace_mode,
+            dynamic_collection_entries,
+        ))
+    }
+
     pub fn write_properties_bundle(
         &mut self,
         schema_set: &SchemaSet,
@@ -1816,7 +3323,196 @@ impl DataSet {
         let asset = self
             .assets
             .get_mut(&asset_id)
+            .map(Arc::make_mut)
             .ok_or(DataSetError::AssetNotFound)?;
         properties_bundle.write(asset, path, schema_set)
     }
 }
+
+/// Reads resolved properties of a single asset. The prototype chain is looked up once, and the
+/// schema of each property path is validated once and then cached. This makes reading many
+/// properties of the same asset (i.e. from a builder) cheaper than calling DataSet::resolve_property
+/// for each of them.
+pub struct ResolvedAssetView<'a> {
+    data_set: &'a DataSet,
+    schema_set: &'a SchemaSet,
+    asset_id: AssetId,
+    // The asset followed by its prototypes
+    prototype_chain: Vec<&'a DataSetAssetInfo>,
+    // Schemas of paths that were successfully validated, including all parent paths of them
+    property_schemas: Mutex<HashMap<String, Schema>>,
+}
+
+impl<'a> ResolvedAssetView<'a> {
+    pub fn data_set(&self) -> &'a DataSet {
+        self.data_set
+    }
+
+    pub fn schema_set(&self) -> &'a SchemaSet {
+        self.schema_set
+    }
+
+    pub fn asset_id(&self) -> AssetId {
+        self.asset_id
+    }
+
+    pub fn schema(&self) -> &'a SchemaRecord {
+        &self.prototype_chain[0].schema
+    }
+
+    // Equivalent to DataSet::validate_parent_paths, but each parent path is only checked once
+    fn property_schema(
+        &self,
+        path: &str,
+    ) -> DataSetResult<Schema> {
+        let cached = self.property_schemas.lock().unwrap().get(path).cloned();
+        if let Some(schema) = cached {
+            return Ok(schema);
+        }
+
+        #[cfg(feature = "property-read-stats")]
+        super::count_property_ancestry_computation();
+
+        let named_types = self.schema_set.schemas();
+        //TODO: Escape map keys (and probably avoid path strings anyways)
+        let schema = if let Some((parent_path, path_segment)) = path.rsplit_once('.') {
+            let parent_schema = self.property_schema(parent_path)?;
+            match parent_schema {
+                Schema::Nullable(_)
+                    if self.resolve_null_override(parent_path)? != NullOverride::SetNonNull =>
+                {
+                    return Err(DataSetError::PathParentIsNull)?;
+                }
+                Schema::DynamicArray(_) => {
+                    let entry =
+                        Uuid::from_str(path_segment).map_err(|_| DataSetError::UuidParseError)?;
+                    if !self
+                        .resolve_dynamic_collection_entries(parent_path)
+                        .contains(&entry)
+                    {
+                        return Err(DataSetError::PathDynamicArrayEntryDoesNotExist)?;
+                    }
+                }
+                _ => {}
+            }
+
+            parent_schema
+                .find_field_schema(path_segment, named_types)
+                .ok_or(DataSetError::SchemaNotFound)?
+                .clone()
+        } else {
+            Schema::Record(self.schema().fingerprint())
+                .find_field_schema(path, named_types)
+                .ok_or(DataSetError::SchemaNotFound)?
+                .clone()
+        };
+
+        self.property_schemas
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), schema.clone());
+        Ok(schema)
+    }
+
+    pub fn resolve_null_override(
+        &self,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<NullOverride> {
+        let property_schema = self.property_schema(path.as_ref())?;
+
+        // This field is not nullable, return an error
+        if !property_schema.is_nullable() {
+            return Err(DataSetError::InvalidSchema)?;
+        }
+
+        for asset in &self.prototype_chain {
+            if let Some(null_override) = asset.property_null_overrides.get(path.as_ref()) {
+                return Ok(*null_override);
+            }
+        }
+
+        Ok(NullOverride::Unset)
+    }
+
+    pub fn resolve_property(
+        &self,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<&'a Value> {
+        let property_schema = self.property_schema(path.as_ref())?;
+
+        for asset in &self.prototype_chain {
+            if let Some(value) = asset.properties.get(path.as_ref()) {
+                return Ok(value);
+            }
+        }
+
+        Ok(Value::default_for_schema(&property_schema, self.schema_set))
+    }
+
+    /// See DataSet::is_property_explicitly_set()
+    pub fn is_property_explicitly_set(
+        &self,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<bool> {
+        self.schema()
+            .find_property_schema(path.as_ref(), self.schema_set.schemas())
+            .ok_or(DataSetError::SchemaNotFound)?;
+        Ok(self
+            .prototype_chain
+            .iter()
+            .any(|asset| asset.properties.contains_key(path.as_ref())))
+    }
+
+    fn resolve_dynamic_collection_entries(
+        &self,
+        path: &str,
+    ) -> Vec<Uuid> {
+        // Prototypes beyond an asset that replaces the collection do not contribute entries
+        let last_contributor = self
+            .prototype_chain
+            .iter()
+            .position(|asset| asset.properties_in_replace_mode.contains(path))
+            .unwrap_or(self.prototype_chain.len() - 1);
+
+        // Entries from prototypes come first
+        let mut resolved_entries = vec![];
+        for asset in self.prototype_chain[..=last_contributor].iter().rev() {
+            if let Some(entries) = asset.dynamic_collection_entries.get(path) {
+                for entry in entries {
+                    resolved_entries.push(*entry);
+                }
+            }
+        }
+
+        remove_duplicate_entries(&mut resolved_entries);
+        resolved_entries
+    }
+
+    pub fn resolve_dynamic_array_entries(
+        &self,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<Box<[Uuid]>> {
+        let property_schema = self.property_schema(path.as_ref())?;
+        if !property_schema.is_dynamic_array() {
+            return Err(DataSetError::InvalidSchema)?;
+        }
+
+        Ok(self
+            .resolve_dynamic_collection_entries(path.as_ref())
+            .into_boxed_slice())
+    }
+
+    pub fn resolve_map_entries(
+        &self,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<Box<[Uuid]>> {
+        let property_schema = self.property_schema(path.as_ref())?;
+        if !property_schema.is_map() {
+            return Err(DataSetError::InvalidSchema)?;
+        }
+
+        Ok(self
+            .resolve_dynamic_collection_entries(path.as_ref())
+            .into_boxed_slice())
+    }
+}
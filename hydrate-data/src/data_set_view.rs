@@ -1,5 +1,9 @@
 use crate::data_set::DataSetResult;
-use crate::{AssetId, DataSet, NullOverride, OverrideBehavior, SchemaSet, SingleObject, Value};
+use crate::value::ValueEnum;
+use crate::{
+    AssetId, DataSet, DataSetError, NullOverride, OverrideBehavior, PropertiesBundle,
+    ResolvedAssetView, Schema, SchemaEnum, SchemaSet, SingleObject, Value,
+};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -94,6 +98,7 @@ pub enum DataContainerRef<'a> {
     DataSet(&'a DataSet, &'a SchemaSet, AssetId),
     SingleObjectRef(&'a SingleObject, &'a SchemaSet),
     SingleObjectArc(Arc<SingleObject>, &'a SchemaSet),
+    AssetView(Arc<ResolvedAssetView<'a>>),
 }
 
 impl<'a> DataContainerRef<'a> {
@@ -119,11 +124,18 @@ impl<'a> DataContainerRef<'a> {
         DataContainerRef::DataSet(data_set, schema_set, asset_id)
     }
 
+    // Like from_dataset, but reads through a view that caches the prototype chain and property
+    // schemas. Preferable when many properties of the asset will be read.
+    pub fn from_asset_view(asset_view: ResolvedAssetView<'a>) -> Self {
+        DataContainerRef::AssetView(Arc::new(asset_view))
+    }
+
     pub fn schema_set(&self) -> &SchemaSet {
         match *self {
             DataContainerRef::DataSet(_, schema_set, _) => schema_set,
             DataContainerRef::SingleObjectRef(_, schema_set) => schema_set,
             DataContainerRef::SingleObjectArc(_, schema_set) => schema_set,
+            DataContainerRef::AssetView(ref asset_view) => asset_view.schema_set(),
         }
     }
 
@@ -141,9 +153,66 @@ impl<'a> DataContainerRef<'a> {
             DataContainerRef::SingleObjectArc(single_object, schema_set) => {
                 single_object.resolve_property(schema_set, path)
             }
+            DataContainerRef::AssetView(asset_view) => asset_view.resolve_property(path),
         }
     }
 
+    // True if the property is set rather than falling through to the schema default, even if it's
+    // set to the default value. See DataSet::is_property_explicitly_set()
+    pub fn is_property_explicitly_set(
+        &self,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<bool> {
+        match self {
+            DataContainerRef::DataSet(data_set, schema_set, asset_id) => {
+                data_set.is_property_explicitly_set(schema_set, *asset_id, path)
+            }
+            DataContainerRef::SingleObjectRef(single_object, schema_set) => {
+                single_object.is_property_explicitly_set(schema_set, path)
+            }
+            DataContainerRef::SingleObjectArc(single_object, schema_set) => {
+                single_object.is_property_explicitly_set(schema_set, path)
+            }
+            DataContainerRef::AssetView(asset_view) => asset_view.is_property_explicitly_set(path),
+        }
+    }
+
+    // The schema of an enum property. This lets UI list the symbols of enums it has no Rust type for
+    pub fn enum_schema(
+        &self,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<SchemaEnum> {
+        let record = match self {
+            DataContainerRef::DataSet(data_set, _, asset_id) => data_set
+                .asset_schema(*asset_id)
+                .ok_or(DataSetError::AssetNotFound)?,
+            DataContainerRef::SingleObjectRef(single_object, _) => single_object.schema(),
+            DataContainerRef::SingleObjectArc(single_object, _) => single_object.schema(),
+            DataContainerRef::AssetView(asset_view) => asset_view.schema(),
+        };
+
+        let schema_set = self.schema_set();
+        let property_schema = record
+            .find_property_schema(path, schema_set.schemas())
+            .ok_or(DataSetError::SchemaNotFound)?;
+        let Schema::Enum(fingerprint) = property_schema else {
+            return Err(DataSetError::InvalidSchema)?;
+        };
+
+        Ok(schema_set
+            .find_named_type_by_fingerprint(fingerprint)
+            .ok_or(DataSetError::SchemaNotFound)?
+            .as_enum()?
+            .clone())
+    }
+
+    pub fn resolve_enum_symbol(
+        &self,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<&str> {
+        Ok(self.resolve_property(path)?.as_enum()?.symbol_name())
+    }
+
     pub fn get_null_override(
         &self,
         path: impl AsRef<str>,
@@ -158,6 +227,11 @@ impl<'a> DataContainerRef<'a> {
             DataContainerRef::SingleObjectArc(single_object, schema_set) => {
                 single_object.get_null_override(schema_set, path)
             }
+            DataContainerRef::AssetView(asset_view) => asset_view.data_set().get_null_override(
+                asset_view.schema_set(),
+                asset_view.asset_id(),
+                path,
+            ),
         }
     }
 
@@ -175,6 +249,7 @@ impl<'a> DataContainerRef<'a> {
             DataContainerRef::SingleObjectArc(single_object, schema_set) => {
                 single_object.resolve_null_override(schema_set, path)
             }
+            DataContainerRef::AssetView(asset_view) => asset_view.resolve_null_override(path),
         }
     }
 
@@ -192,6 +267,9 @@ impl<'a> DataContainerRef<'a> {
             DataContainerRef::SingleObjectArc(single_object, schema_set) => {
                 single_object.resolve_dynamic_array_entries(schema_set, path)
             }
+            DataContainerRef::AssetView(asset_view) => {
+                asset_view.resolve_dynamic_array_entries(path)
+            }
         }
     }
 
@@ -209,6 +287,7 @@ impl<'a> DataContainerRef<'a> {
             DataContainerRef::SingleObjectArc(single_object, schema_set) => {
                 single_object.resolve_map_entries(schema_set, path)
             }
+            DataContainerRef::AssetView(asset_view) => asset_view.resolve_map_entries(path),
         }
     }
 
@@ -222,6 +301,11 @@ impl<'a> DataContainerRef<'a> {
             }
             DataContainerRef::SingleObjectRef(_, _) => Ok(OverrideBehavior::Replace),
             DataContainerRef::SingleObjectArc(_, _) => Ok(OverrideBehavior::Replace),
+            DataContainerRef::AssetView(asset_view) => asset_view.data_set().get_override_behavior(
+                asset_view.schema_set(),
+                asset_view.asset_id(),
+                path,
+            ),
         }
     }
 }
@@ -315,6 +399,37 @@ impl<'a> DataContainerRefMut<'a> {
         }
     }
 
+    pub fn enum_schema(
+        &self,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<SchemaEnum> {
+        self.read().enum_schema(path)
+    }
+
+    pub fn resolve_enum_symbol(
+        &self,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<&str> {
+        Ok(self.resolve_property(path)?.as_enum()?.symbol_name())
+    }
+
+    // Sets an enum property by symbol name without needing a Rust type for the enum. The symbol may
+    // be an alias, in which case the symbol's actual name is stored.
+    pub fn set_enum_symbol(
+        &mut self,
+        path: impl AsRef<str>,
+        symbol_name: &str,
+    ) -> DataSetResult<Option<Value>> {
+        let enum_schema = self.enum_schema(path.as_ref())?;
+        let symbol = enum_schema
+            .find_symbol_from_name(symbol_name)
+            .ok_or(DataSetError::UnexpectedEnumSymbol)?;
+        self.set_property_override(
+            path,
+            Some(Value::Enum(ValueEnum::new(symbol.name().to_string()))),
+        )
+    }
+
     pub fn get_null_override(
         &self,
         path: impl AsRef<str>,
@@ -450,6 +565,17 @@ impl<'a> DataContainerRefMut<'a> {
             DataContainerRefMut::SingleObject(_, _) => Ok(()),
         }
     }
+
+    pub fn clear_all_overrides(&mut self) -> DataSetResult<PropertiesBundle> {
+        match self {
+            DataContainerRefMut::DataSet(data_set, schema_set, asset_id) => {
+                data_set.clear_all_overrides(schema_set, *asset_id)
+            }
+            DataContainerRefMut::SingleObject(single_object, _) => {
+                Ok(single_object.clear_all_overrides())
+            }
+        }
+    }
 }
 
 impl<'a> DataContainerRead for DataContainerRefMut<'a> {
@@ -4,6 +4,7 @@ use crate::{
     AssetId, AssetLocation, AssetName, DataSet, DataSetAssetInfo, DataSetResult, HashSet,
     NullOverride, OrderedSet, SchemaSet,
 };
+use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Debug)]
@@ -27,6 +28,7 @@ pub struct AssetDiff {
     dynamic_array_entry_deltas: Vec<DynamicArrayEntryDelta>,
     set_canonical_path_references: Vec<(CanonicalPathReference, AssetId)>,
     remove_canonical_path_references: Vec<CanonicalPathReference>,
+    set_build_settings: Option<Option<AssetId>>,
 }
 
 impl AssetDiff {
@@ -43,6 +45,27 @@ impl AssetDiff {
             || !self.dynamic_array_entry_deltas.is_empty()
             || !self.set_canonical_path_references.is_empty()
             || !self.remove_canonical_path_references.is_empty()
+            || self.set_build_settings.is_some()
+    }
+
+    // Property paths this diff touches, sorted. The name, location, prototype, build settings and
+    // path references are not properties so changes to them aren't listed.
+    pub fn changed_properties(&self) -> Vec<String> {
+        let mut changed_properties: Vec<String> = self
+            .set_properties
+            .iter()
+            .map(|(path, _)| path)
+            .chain(&self.remove_properties)
+            .chain(self.set_null_overrides.iter().map(|(path, _)| path))
+            .chain(&self.remove_null_overrides)
+            .chain(&self.add_properties_in_replace_mode)
+            .chain(&self.remove_properties_in_replace_mode)
+            .chain(self.dynamic_array_entry_deltas.iter().map(|x| &x.key))
+            .cloned()
+            .collect();
+        changed_properties.sort();
+        changed_properties.dedup();
+        changed_properties
     }
 
     pub fn apply(
@@ -108,6 +131,10 @@ impl AssetDiff {
         for k in &self.remove_canonical_path_references {
             asset.build_info.path_reference_overrides.remove(k);
         }
+
+        if let Some(set_build_settings) = self.set_build_settings {
+            asset.build_info.build_settings = set_build_settings;
+        }
     }
 }
 
@@ -342,6 +369,11 @@ impl AssetDiffSet {
             }
         }
 
+        if before_obj.build_info.build_settings != after_obj.build_info.build_settings {
+            apply_diff.set_build_settings = Some(after_obj.build_info.build_settings);
+            revert_diff.set_build_settings = Some(before_obj.build_info.build_settings);
+        }
+
         // we only flag the location as modified if we make an edit
         // (if apply_diff doesn't have changes, before_diff doesn't either)
         if apply_diff.has_changes() {
@@ -403,7 +435,7 @@ impl DataSetDiff {
         }
 
         for (asset_id, v) in &self.changes {
-            if let Some(asset) = data_set.assets_mut().get_mut(asset_id) {
+            if let Some(asset) = data_set.assets_mut().get_mut(asset_id).map(Arc::make_mut) {
                 v.apply(asset);
             }
         }
@@ -474,7 +506,8 @@ impl DataSetDiffSet {
                     }
                 } else {
                     // Asset was deleted
-                    let before_asset_info = before.assets().get(&asset_id).unwrap().clone();
+                    let before_asset_info =
+                        DataSetAssetInfo::clone(before.assets().get(&asset_id).unwrap());
                     modified_assets.insert(asset_id);
                     if !modified_locations.contains(&before_asset_info.asset_location) {
                         modified_locations.insert(before_asset_info.asset_location.clone());
@@ -489,6 +522,7 @@ impl DataSetDiffSet {
             } else if existed_after {
                 // Asset was created
                 let after_asset_info = after.assets().get(&asset_id).unwrap();
+                modified_assets.insert(asset_id);
                 if !modified_locations.contains(&after_asset_info.asset_location) {
                     modified_locations.insert(after_asset_info.asset_location.clone());
                 }
@@ -496,7 +530,7 @@ impl DataSetDiffSet {
                 // created
                 apply_diff
                     .creates
-                    .push((asset_id, after_asset_info.clone()));
+                    .push((asset_id, DataSetAssetInfo::clone(after_asset_info)));
                 revert_diff.deletes.push(asset_id);
             }
         }
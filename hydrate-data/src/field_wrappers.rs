@@ -134,8 +134,34 @@ impl<T: Enum> EnumFieldAccessor<T> {
         property_path: &PropertyPath,
         data_container: DataContainerRef,
     ) -> DataSetResult<T> {
-        let e = data_container.resolve_property(property_path.path())?;
-        Ok(T::from_symbol_name(e.as_enum().unwrap().symbol_name())
+        let symbol_name = data_container.resolve_enum_symbol(property_path.path())?;
+        if let Some(value) = T::from_symbol_name(symbol_name) {
+            return Ok(value);
+        }
+
+        // The data may have been saved before the symbol was renamed, in which case the stored
+        // name is an alias of the current symbol
+        let enum_schema = data_container.enum_schema(property_path.path())?;
+        if let Some(value) = enum_schema
+            .find_symbol_from_name(symbol_name)
+            .and_then(|symbol| T::from_symbol_name(symbol.name()))
+        {
+            return Ok(value);
+        }
+
+        if data_container.schema_set().strict_enum_symbols() {
+            return Err(DataSetError::UnexpectedEnumSymbol)?;
+        }
+
+        // The symbol was removed, don't fail the read and break opening old data
+        log::warn!(
+            "Enum {} has no symbol {} at {}, using default symbol {}",
+            enum_schema.name(),
+            symbol_name,
+            property_path.path(),
+            enum_schema.default_value().name()
+        );
+        Ok(T::from_symbol_name(enum_schema.default_value().name())
             .ok_or(DataSetError::UnexpectedEnumSymbol)?)
     }
 
@@ -183,6 +209,12 @@ impl<'a, T: Enum> EnumFieldRef<'a, T> {
     pub fn get(&self) -> DataSetResult<T> {
         EnumFieldAccessor::<T>::do_get(&self.0, self.1.clone())
     }
+
+    // True if the field is set rather than falling through to the schema default, even if it's set
+    // to the default value. Lets exporters omit fields that were never set.
+    pub fn is_set(&self) -> DataSetResult<bool> {
+        self.1.is_property_explicitly_set(self.0.path())
+    }
 }
 
 pub struct EnumFieldRefMut<'a, T: Enum>(
@@ -481,6 +513,10 @@ impl<'a> BooleanFieldRef<'a> {
     pub fn get(&self) -> DataSetResult<bool> {
         BooleanFieldAccessor::do_get(&self.0, self.1.clone())
     }
+
+    pub fn is_set(&self) -> DataSetResult<bool> {
+        self.1.is_property_explicitly_set(self.0.path())
+    }
 }
 
 pub struct BooleanFieldRefMut<'a>(pub PropertyPath, Rc<RefCell<DataContainerRefMut<'a>>>);
@@ -605,6 +641,10 @@ impl<'a> I32FieldRef<'a> {
     pub fn get(&self) -> DataSetResult<i32> {
         I32FieldAccessor::do_get(&self.0, self.1.clone())
     }
+
+    pub fn is_set(&self) -> DataSetResult<bool> {
+        self.1.is_property_explicitly_set(self.0.path())
+    }
 }
 
 pub struct I32FieldRefMut<'a>(pub PropertyPath, Rc<RefCell<DataContainerRefMut<'a>>>);
@@ -729,6 +769,10 @@ impl<'a> I64FieldRef<'a> {
     pub fn get(&self) -> DataSetResult<i64> {
         I64FieldAccessor::do_get(&self.0, self.1.clone())
     }
+
+    pub fn is_set(&self) -> DataSetResult<bool> {
+        self.1.is_property_explicitly_set(self.0.path())
+    }
 }
 
 pub struct I64FieldRefMut<'a>(pub PropertyPath, Rc<RefCell<DataContainerRefMut<'a>>>);
@@ -853,6 +897,10 @@ impl<'a> U32FieldRef<'a> {
     pub fn get(&self) -> DataSetResult<u32> {
         U32FieldAccessor::do_get(&self.0, self.1.clone())
     }
+
+    pub fn is_set(&self) -> DataSetResult<bool> {
+        self.1.is_property_explicitly_set(self.0.path())
+    }
 }
 
 pub struct U32FieldRefMut<'a>(pub PropertyPath, Rc<RefCell<DataContainerRefMut<'a>>>);
@@ -977,6 +1025,10 @@ impl<'a> U64FieldRef<'a> {
     pub fn get(&self) -> DataSetResult<u64> {
         U64FieldAccessor::do_get(&self.0, self.1.clone())
     }
+
+    pub fn is_set(&self) -> DataSetResult<bool> {
+        self.1.is_property_explicitly_set(self.0.path())
+    }
 }
 
 pub struct U64FieldRefMut<'a>(pub PropertyPath, Rc<RefCell<DataContainerRefMut<'a>>>);
@@ -1101,6 +1153,10 @@ impl<'a> F32FieldRef<'a> {
     pub fn get(&self) -> DataSetResult<f32> {
         F32FieldAccessor::do_get(&self.0, self.1.clone())
     }
+
+    pub fn is_set(&self) -> DataSetResult<bool> {
+        self.1.is_property_explicitly_set(self.0.path())
+    }
 }
 
 pub struct F32FieldRefMut<'a>(pub PropertyPath, Rc<RefCell<DataContainerRefMut<'a>>>);
@@ -1225,6 +1281,10 @@ impl<'a> F64FieldRef<'a> {
     pub fn get(&self) -> DataSetResult<f64> {
         F64FieldAccessor::do_get(&self.0, self.1.clone())
     }
+
+    pub fn is_set(&self) -> DataSetResult<bool> {
+        self.1.is_property_explicitly_set(self.0.path())
+    }
 }
 
 pub struct F64FieldRefMut<'a>(pub PropertyPath, Rc<RefCell<DataContainerRefMut<'a>>>);
@@ -1349,6 +1409,10 @@ impl<'a> BytesFieldRef<'a> {
     pub fn get(&self) -> DataSetResult<&Arc<Vec<u8>>> {
         BytesFieldAccessor::do_get(&self.0, &self.1)
     }
+
+    pub fn is_set(&self) -> DataSetResult<bool> {
+        self.1.is_property_explicitly_set(self.0.path())
+    }
 }
 
 pub struct BytesFieldRefMut<'a>(pub PropertyPath, Rc<RefCell<DataContainerRefMut<'a>>>);
@@ -1488,6 +1552,10 @@ impl<'a> StringFieldRef<'a> {
     pub fn get(&'a self) -> DataSetResult<Arc<String>> {
         StringFieldAccessor::do_get(&self.0, self.1.clone())
     }
+
+    pub fn is_set(&self) -> DataSetResult<bool> {
+        self.1.is_property_explicitly_set(self.0.path())
+    }
 }
 
 pub struct StringFieldRefMut<'a>(pub PropertyPath, Rc<RefCell<DataContainerRefMut<'a>>>);
@@ -1691,6 +1759,19 @@ impl<T: FieldAccessor> DynamicArrayFieldAccessor<T> {
         T::new(self.0.push(&entry_uuid.to_string()))
     }
 
+    // Yields an accessor for each entry in resolved order. Accessors are created as the iterator
+    // advances.
+    pub fn iter(
+        &self,
+        data_container: DataContainerRef,
+    ) -> DataSetResult<impl Iterator<Item = T> + '_> {
+        let entries = self.resolve_entries(data_container)?;
+        Ok(entries
+            .into_vec()
+            .into_iter()
+            .map(move |entry_uuid| self.entry(entry_uuid)))
+    }
+
     pub fn add_entry(
         &self,
         data_container: &mut DataContainerRefMut,
@@ -1733,6 +1814,27 @@ impl<'a, T: FieldRef<'a>> DynamicArrayFieldRef<'a, T> {
     ) -> T {
         T::new(self.0.push(&entry_uuid.to_string()), self.1.clone())
     }
+
+    // Yields a ref for each entry in resolved order. Refs are created as the iterator advances.
+    pub fn iter(&self) -> DataSetResult<impl Iterator<Item = T> + 'a> {
+        let entries = self.resolve_entries()?;
+        let property_path = self.0.clone();
+        let data_container = self.1.clone();
+        Ok(entries.into_vec().into_iter().map(move |entry_uuid| {
+            T::new(
+                property_path.push(&entry_uuid.to_string()),
+                data_container.clone(),
+            )
+        }))
+    }
+}
+
+impl<'a> DynamicArrayFieldRef<'a, AssetRefFieldRef<'a>> {
+    // Reads every referenced asset in resolved order, i.e. prototype entries first unless the array
+    // is in replace mode
+    pub fn resolve_asset_ref_entries(&self) -> DataSetResult<Vec<AssetId>> {
+        self.iter()?.map(|entry| entry.get()).collect()
+    }
 }
 
 pub struct DynamicArrayFieldRefMut<'a, T: FieldRefMut<'a>>(
@@ -1764,6 +1866,15 @@ impl<'a, T: FieldRefMut<'a>> DynamicArrayFieldRefMut<'a, T> {
         T::new(self.0.push(&entry_uuid.to_string()), &self.1)
     }
 
+    // Yields a ref for each entry in resolved order. Refs are created as the iterator advances.
+    pub fn iter(&'a self) -> DataSetResult<impl Iterator<Item = T> + 'a> {
+        let entries = self.resolve_entries()?;
+        Ok(entries
+            .into_vec()
+            .into_iter()
+            .map(move |entry_uuid| self.entry(entry_uuid)))
+    }
+
     pub fn add_entry(&self) -> DataSetResult<Uuid> {
         self.1.borrow_mut().add_dynamic_array_entry(self.0.path())
     }
@@ -1809,6 +1920,15 @@ impl<'a, T: Field> DynamicArrayField<T> {
         T::new(self.0.push(&entry_uuid.to_string()), &self.1)
     }
 
+    // Yields a field for each entry in resolved order. Fields are created as the iterator advances.
+    pub fn iter(&'a self) -> DataSetResult<impl Iterator<Item = T> + 'a> {
+        let entries = self.resolve_entries()?;
+        Ok(entries
+            .into_vec()
+            .into_iter()
+            .map(move |entry_uuid| self.entry(entry_uuid)))
+    }
+
     pub fn add_entry(&self) -> DataSetResult<Uuid> {
         self.1
             .borrow_mut()
@@ -2079,6 +2199,10 @@ impl<'a> AssetRefFieldRef<'a> {
     pub fn get(&self) -> DataSetResult<AssetId> {
         AssetRefFieldAccessor::do_get(&self.0, self.1.clone())
     }
+
+    pub fn is_set(&self) -> DataSetResult<bool> {
+        self.1.is_property_explicitly_set(self.0.path())
+    }
 }
 
 pub struct AssetRefFieldRefMut<'a>(pub PropertyPath, Rc<RefCell<DataContainerRefMut<'a>>>);
@@ -0,0 +1,91 @@
+use crate::{AssetId, DataContainerRef, DataSetResult};
+use hydrate_schema::PropertyPath;
+
+/// A type that can be read from the property at a path in a data container. This is implemented
+/// for the types of simple properties, and for every type that implements FromRecord.
+pub trait FromProperty: Sized {
+    fn read_property(
+        data_container: DataContainerRef,
+        property_path: &PropertyPath,
+    ) -> DataSetResult<Self>;
+}
+
+/// A plain struct that can be read from a record, one field at a time. Usually this is implemented
+/// with #[derive(FromHydrateRecord)] rather than by hand.
+pub trait FromRecord: Sized {
+    fn from_record(
+        data_container: DataContainerRef,
+        property_path: &PropertyPath,
+    ) -> DataSetResult<Self>;
+}
+
+impl<T: FromRecord> FromProperty for T {
+    fn read_property(
+        data_container: DataContainerRef,
+        property_path: &PropertyPath,
+    ) -> DataSetResult<Self> {
+        T::from_record(data_container, property_path)
+    }
+}
+
+/// Reads the whole asset or single object in the container into a plain struct
+pub fn read_struct<S: FromRecord>(data_container: DataContainerRef) -> DataSetResult<S> {
+    S::from_record(data_container, &PropertyPath::default())
+}
+
+macro_rules! impl_from_property {
+    ($t:ty, $as_fn:ident) => {
+        impl FromProperty for $t {
+            fn read_property(
+                data_container: DataContainerRef,
+                property_path: &PropertyPath,
+            ) -> DataSetResult<Self> {
+                data_container
+                    .resolve_property(property_path.path())?
+                    .$as_fn()
+            }
+        }
+    };
+}
+
+impl_from_property!(bool, as_boolean);
+impl_from_property!(i32, as_i32);
+impl_from_property!(i64, as_i64);
+impl_from_property!(u32, as_u32);
+impl_from_property!(u64, as_u64);
+impl_from_property!(f32, as_f32);
+impl_from_property!(f64, as_f64);
+impl_from_property!(AssetId, as_asset_ref);
+
+impl FromProperty for String {
+    fn read_property(
+        data_container: DataContainerRef,
+        property_path: &PropertyPath,
+    ) -> DataSetResult<Self> {
+        Ok(data_container
+            .resolve_property(property_path.path())?
+            .as_string()?
+            .to_string())
+    }
+}
+
+// Vectors are records with a field per component (i.e. a Vec3 record has x, y, z fields)
+macro_rules! impl_from_property_for_vector {
+    ($len:literal, $($component:literal),*) => {
+        impl FromProperty for [f32; $len] {
+            fn read_property(
+                data_container: DataContainerRef,
+                property_path: &PropertyPath,
+            ) -> DataSetResult<Self> {
+                Ok([$(f32::read_property(
+                    data_container.clone(),
+                    &property_path.push($component),
+                )?),*])
+            }
+        }
+    };
+}
+
+impl_from_property_for_vector!(2, "x", "y");
+impl_from_property_for_vector!(3, "x", "y", "z");
+impl_from_property_for_vector!(4, "x", "y", "z", "w");
@@ -1,11 +1,11 @@
 use crate::value::ValueEnum;
 use crate::{
-    AssetId, BuildInfo, DataSetAssetInfo, HashMap, HashSet, ImportInfo, ImporterId, NullOverride,
-    PathReference, PathReferenceHash, PathReferenceNamespaceResolver, Schema, SchemaFingerprint,
-    SchemaNamedType, SchemaSet, SingleObject, Value,
+    AssetId, BuildInfo, DataSet, DataSetAssetInfo, HashMap, HashSet, ImportDataStorage, ImportInfo,
+    ImporterId, NullOverride, PathReference, PathReferenceHash, PathReferenceNamespaceResolver,
+    Schema, SchemaFingerprint, SchemaNamedType, SchemaSet, SingleObject, Value,
 };
 use crate::{AssetLocation, AssetName, DataSetResult, ImportableName, OrderedSet};
-use hydrate_schema::{CachedSchemaNamedType, DataSetError, SchemaRecord};
+use hydrate_schema::{CachedSchemaNamedType, DataSetError, SchemaEnum, SchemaRecord};
 use serde::{Deserialize, Serialize};
 use std::hash::Hash;
 use std::str::FromStr;
@@ -176,29 +176,29 @@ fn json_to_property_value_with_schema(
                                 let old_named_type =
                                     old_named_types.get(old_enum_fingerprint).unwrap();
                                 let old_enum = old_named_type.as_enum().unwrap();
-                                let old_symbol = old_enum
-                                    .find_symbol_from_name(json_value.as_str().unwrap())
-                                    .unwrap();
-                                let new_symbol = new_enum
-                                    .find_symbol_from_uuid(old_symbol.symbol_uuid())
-                                    .unwrap();
-                                Value::Enum(ValueEnum::new(new_symbol.name().to_string()))
+                                let symbol_name = json_value.as_str().unwrap();
+                                let new_symbol = old_enum
+                                    .find_symbol_from_name(symbol_name)
+                                    .and_then(|old_symbol| {
+                                        new_enum.find_symbol_from_uuid(old_symbol.symbol_uuid())
+                                    });
+                                Value::Enum(ValueEnum::new(
+                                    new_symbol
+                                        .map(|x| x.name())
+                                        .unwrap_or(symbol_name)
+                                        .to_string(),
+                                ))
                             }
                             Schema::String => {
                                 // Just try and match an enum string value
-                                Value::enum_value_from_string(
-                                    new_enum,
-                                    json_value.as_str().unwrap(),
-                                )
-                                .unwrap()
+                                enum_value_from_json_string(new_enum, json_value.as_str().unwrap())
                             }
                             _ => {
                                 panic!("Cannot migrate schema {:?} into an enum schema", old_schema)
                             }
                         }
                     } else {
-                        Value::enum_value_from_string(new_enum, json_value.as_str().unwrap())
-                            .unwrap()
+                        enum_value_from_json_string(new_enum, json_value.as_str().unwrap())
                     }
                 }
             }
@@ -206,6 +206,16 @@ fn json_to_property_value_with_schema(
     }
 }
 
+// Symbols the schema doesn't know (i.e. removed since the data was saved) are kept as-is rather than
+// failing the load. Reading the field resolves them, see EnumFieldAccessor::do_get
+fn enum_value_from_json_string(
+    schema_enum: &SchemaEnum,
+    symbol_name: &str,
+) -> Value {
+    Value::enum_value_from_string(schema_enum, symbol_name)
+        .unwrap_or_else(|| Value::Enum(ValueEnum::new(symbol_name.to_string())))
+}
+
 fn null_override_to_string_value(null_override: NullOverride) -> &'static str {
     match null_override {
         NullOverride::SetNull => "SetNull",
@@ -497,7 +507,13 @@ pub struct AssetImportInfoJson {
     // These are all encoded as hex to avoid json/u64 weirdness
     source_file_modified_timestamp: String,
     source_file_size: String,
+    // Omitted if the contents were not hashed
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    source_file_contents_hash: String,
     import_data_contents_hash: String,
+
+    #[serde(default, skip_serializing_if = "ImportDataStorage::is_copied")]
+    import_data_storage: ImportDataStorage,
 }
 
 impl AssetImportInfoJson {
@@ -529,7 +545,13 @@ impl AssetImportInfoJson {
                 import_info.source_file_modified_timestamp()
             ),
             source_file_size: format!("{:0>16x}", import_info.source_file_size()),
+            source_file_contents_hash: if import_info.source_file_contents_hash() != 0 {
+                format!("{:0>16x}", import_info.source_file_contents_hash())
+            } else {
+                String::default()
+            },
             import_data_contents_hash: format!("{:0>16x}", import_info.import_data_contents_hash()),
+            import_data_storage: import_info.import_data_storage(),
         }
     }
 
@@ -560,6 +582,12 @@ impl AssetImportInfoJson {
                 .map_err(|_| (DataSetError::StorageFormatError))?;
         let source_file_size = u64::from_str_radix(&self.source_file_size, 16)
             .map_err(|_| (DataSetError::StorageFormatError))?;
+        let source_file_contents_hash = if self.source_file_contents_hash.is_empty() {
+            0
+        } else {
+            u64::from_str_radix(&self.source_file_contents_hash, 16)
+                .map_err(|_| DataSetError::StorageFormatError)?
+        };
         let import_data_contents_hash = u64::from_str_radix(&self.import_data_contents_hash, 16)
             .map_err(|_| (DataSetError::StorageFormatError))?;
 
@@ -569,8 +597,10 @@ impl AssetImportInfoJson {
             path_references,
             source_file_modified_timestamp,
             source_file_size,
+            source_file_contents_hash,
             import_data_contents_hash,
-        ))
+        )
+        .with_import_data_storage(self.import_data_storage))
     }
 }
 
@@ -579,6 +609,8 @@ impl AssetImportInfoJson {
 pub struct AssetBuildInfoJson {
     #[serde(serialize_with = "ordered_map_uuid")]
     file_reference_overrides: HashMap<String, Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    build_settings: Option<Uuid>,
 }
 
 impl AssetBuildInfoJson {
@@ -590,6 +622,7 @@ impl AssetBuildInfoJson {
 
         AssetBuildInfoJson {
             file_reference_overrides,
+            build_settings: import_info.build_settings.map(|x| x.as_uuid()),
         }
     }
 
@@ -609,6 +642,7 @@ impl AssetBuildInfoJson {
 
         BuildInfo {
             path_reference_overrides: file_reference_overrides,
+            build_settings: self.build_settings.map(AssetId::from_uuid),
         }
     }
 }
@@ -666,6 +700,24 @@ impl AssetJson {
             serde_json::from_str(json).unwrap()
         };
 
+        Self::load_asset(
+            restore_asset_impl,
+            schema_set,
+            override_asset_id,
+            default_asset_location,
+            override_asset_location,
+            stored_asset,
+        )
+    }
+
+    fn load_asset(
+        restore_asset_impl: &mut dyn RestoreAssetFromStorageImpl,
+        schema_set: &SchemaSet,
+        override_asset_id: Option<Uuid>,
+        default_asset_location: AssetLocation,
+        override_asset_location: Option<AssetLocation>,
+        stored_asset: AssetJson,
+    ) -> DataSetResult<AssetId> {
         // Use the provided override, or what's in the file, or worst case default to asset_source_id
         let asset_location = if let Some(override_asset_location) = override_asset_location {
             override_asset_location
@@ -724,17 +776,19 @@ impl AssetJson {
             // Find the schema we want to migrate the data to
             let old_root_schema = old_named_types
                 .get(&SchemaFingerprint::from_uuid(stored_asset.root_schema))
-                .unwrap();
+                .ok_or(DataSetError::SchemaNotFound)?;
             let root_type_uuid = old_root_schema.type_uuid();
             let new_named_type = schema_set.find_named_type_by_type_uuid(root_type_uuid)?;
             (new_named_type.clone(), Some(old_named_types))
         } else {
-            panic!(
-                "Can't load asset {} type {} by fingerprint, not stored schemas found.",
-                asset_id, stored_asset.schema_name
+            // The type may be defined by a plugin that isn't loaded. The caller decides what to do
+            // with the asset, the file itself is not modified.
+            log::warn!(
+                "Can't load asset {} type {} by fingerprint, no stored schemas found.",
+                asset_id,
+                stored_asset.schema_name
             );
-            //let named_type = schema_set.find_named_type(stored_asset.schema_name)?;
-            //(named_type.clone(), None)
+            Err(DataSetError::SchemaNotFound)?
         };
 
         let mut properties: HashMap<String, Value> = Default::default();
@@ -787,13 +841,32 @@ impl AssetJson {
     #[profiling::function]
     pub fn save_asset_to_string(
         schema_set: &SchemaSet,
-        assets: &HashMap<AssetId, DataSetAssetInfo>,
+        assets: &HashMap<AssetId, Arc<DataSetAssetInfo>>,
         asset_id: AssetId,
         // We only save the ID in the file if using path-based file system storage. Otherwise the
         // id is the file path/name
         include_asset_id_in_file: bool,
         asset_location: Option<AssetLocation>,
     ) -> String {
+        let stored_asset = Self::save_asset(
+            schema_set,
+            assets,
+            asset_id,
+            include_asset_id_in_file,
+            asset_location,
+        );
+
+        profiling::scope!("serde_json::to_string_pretty");
+        serde_json::to_string_pretty(&stored_asset).unwrap()
+    }
+
+    fn save_asset(
+        schema_set: &SchemaSet,
+        assets: &HashMap<AssetId, Arc<DataSetAssetInfo>>,
+        asset_id: AssetId,
+        include_asset_id_in_file: bool,
+        asset_location: Option<AssetLocation>,
+    ) -> AssetJson {
         let obj = assets.get(&asset_id).unwrap();
         let mut buffers = None;
 
@@ -818,7 +891,7 @@ impl AssetJson {
         } else {
             None
         };
-        let stored_asset = AssetJson {
+        AssetJson {
             id: written_asset_id,
             name: obj.asset_name().as_string().cloned().unwrap_or_default(),
             parent_dir: asset_location.map(|x| x.path_node_id().as_uuid()),
@@ -829,10 +902,69 @@ impl AssetJson {
             prototype: obj.prototype().map(|x| x.as_uuid()),
             properties: json_properties,
             schemas,
-        };
+        }
+    }
+}
 
-        profiling::scope!("serde_json::to_string_pretty");
-        serde_json::to_string_pretty(&stored_asset).unwrap()
+#[derive(Serialize, Deserialize)]
+struct DataSetJson {
+    assets: Vec<AssetJson>,
+}
+
+impl DataSet {
+    /// Serializes the given assets in the same json format data sources store them in, including
+    /// their IDs and locations. Assets that don't exist are skipped. Read the assets back with
+    /// DataSet::deserialize_from_slice().
+    pub fn serialize_to_vec(
+        &self,
+        schema_set: &SchemaSet,
+        asset_ids: &[AssetId],
+    ) -> Vec<u8> {
+        let mut assets = Vec::with_capacity(asset_ids.len());
+        for &asset_id in asset_ids {
+            let Some(asset) = self.assets().get(&asset_id) else {
+                continue;
+            };
+
+            assets.push(AssetJson::save_asset(
+                schema_set,
+                self.assets(),
+                asset_id,
+                true,
+                Some(asset.asset_location()),
+            ));
+        }
+
+        serde_json::to_vec(&DataSetJson { assets }).unwrap()
+    }
+
+    /// Restores assets written by DataSet::serialize_to_vec(), replacing any existing assets with
+    /// the same IDs. Returns the IDs of the restored assets.
+    pub fn deserialize_from_slice(
+        restore_asset_impl: &mut dyn RestoreAssetFromStorageImpl,
+        schema_set: &SchemaSet,
+        data: &[u8],
+    ) -> DataSetResult<Vec<AssetId>> {
+        let data_set_json: DataSetJson =
+            serde_json::from_slice(data).map_err(|_| DataSetError::StorageFormatError)?;
+
+        let mut asset_ids = Vec::with_capacity(data_set_json.assets.len());
+        for stored_asset in data_set_json.assets {
+            if stored_asset.id.is_none() {
+                Err(DataSetError::StorageFormatError)?;
+            }
+
+            asset_ids.push(AssetJson::load_asset(
+                restore_asset_impl,
+                schema_set,
+                None,
+                AssetLocation::null(),
+                None,
+                stored_asset,
+            )?);
+        }
+
+        Ok(asset_ids)
     }
 }
 
@@ -11,14 +11,19 @@ pub use data_set::AssetLocation;
 pub use data_set::AssetName;
 pub use data_set::BuildInfo;
 pub use data_set::BuilderId;
+pub use data_set::ConsistencyIssue;
 pub use data_set::DataSet;
 pub use data_set::DataSetAssetInfo;
+pub use data_set::DataSetView;
 pub use data_set::HashObjectMode;
+pub use data_set::ImportDataStorage;
 pub use data_set::ImportInfo;
 pub use data_set::ImportableName;
 pub use data_set::ImporterId;
 pub use data_set::OverrideBehavior;
 pub use data_set::PropertiesBundle;
+pub use data_set::PropertyContribution;
+pub use data_set::ResolvedAssetView;
 
 mod data_set_view;
 pub use data_set_view::DataContainer;
@@ -29,25 +34,40 @@ mod single_object;
 pub use single_object::SingleObject;
 
 mod diff;
+pub use diff::AssetDiff;
+pub use diff::AssetDiffSet;
 pub use diff::DataSetDiff;
 pub use diff::DataSetDiffSet;
 
 mod property_util_fn;
+#[cfg(feature = "property-read-stats")]
+pub use property_util_fn::property_ancestry_computation_count;
 use property_util_fn::*;
 
+#[cfg(feature = "random-values")]
+mod random_values;
+
 mod field_wrappers;
 pub use field_wrappers::*;
 
+mod from_record;
+pub use from_record::{read_struct, FromProperty, FromRecord};
+pub use hydrate_derive::FromHydrateRecord;
+
 mod schema_set;
-pub use schema_set::{SchemaSet, SchemaSetBuilder};
+pub use schema_set::{InlineSchemaBuilder, SchemaSet, SchemaSetBuilder};
 
 mod ordered_set;
 
 mod path_reference;
+pub use path_reference::canonicalize_source_file_path;
+pub use path_reference::join_archive_path;
+pub use path_reference::split_archive_path;
 pub use path_reference::CanonicalPathReference;
 pub use path_reference::PathReference;
 pub use path_reference::PathReferenceHash;
 pub use path_reference::PathReferenceNamespaceResolver;
+pub use path_reference::PROJECT_ROOT_NAMESPACE;
 
 pub use ordered_set::OrderedSet;
 
@@ -1,4 +1,5 @@
 use crate::ImportableName;
+use hydrate_base::{canonicalize_path, PathCanonicalization};
 use hydrate_schema::{DataSetError, DataSetResult};
 use siphasher::sip128::Hasher128;
 use std::fmt::{Display, Formatter};
@@ -6,6 +7,10 @@ use std::hash::Hash;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
+// Namespace for paths under the project root that aren't in any other namespace. These are stored
+// relative to the root so that the project can be moved to another location or machine.
+pub const PROJECT_ROOT_NAMESPACE: &str = "project";
+
 pub trait PathReferenceNamespaceResolver {
     // Given the namespace, return the path associated with it
     fn namespace_root(
@@ -18,6 +23,79 @@ pub trait PathReferenceNamespaceResolver {
         &self,
         path: &Path,
     ) -> Option<(String, PathBuf)>;
+
+    // How paths are canonicalized before they are compared or stored
+    fn path_canonicalization(&self) -> PathCanonicalization {
+        PathCanonicalization::default()
+    }
+}
+
+// A path may point at a file inside an archive, i.e. "/path/to/archive.zip!inner/path.png". The
+// part after the delimiter is relative to the root of the archive and always uses '/'
+pub const ARCHIVE_ENTRY_DELIMITER: char = '!';
+const ARCHIVE_EXTENSIONS: [&str; 1] = [".zip"];
+
+// Splits a path into the archive file path and the path of the entry within the archive. Returns
+// None if the path does not point into an archive.
+pub fn split_archive_path(path: &str) -> Option<(&str, &str)> {
+    for (position, _) in path.match_indices(ARCHIVE_ENTRY_DELIMITER) {
+        let archive_path = &path[0..position];
+        let archive_path_lowercase = archive_path.to_lowercase();
+        if ARCHIVE_EXTENSIONS
+            .iter()
+            .any(|extension| archive_path_lowercase.ends_with(extension))
+        {
+            return Some((archive_path, &path[position + 1..]));
+        }
+    }
+
+    None
+}
+
+pub fn join_archive_path(
+    archive_path: &Path,
+    entry_path: &str,
+) -> PathBuf {
+    let mut path = archive_path.as_os_str().to_os_string();
+    path.push(ARCHIVE_ENTRY_DELIMITER.to_string());
+    path.push(entry_path);
+    PathBuf::from(path)
+}
+
+// Resolves "." and ".." within an archive without touching the file system. Fails if the path
+// would leave the archive.
+fn normalize_archive_entry_path(entry_path: &str) -> DataSetResult<String> {
+    let mut components = Vec::default();
+    for component in entry_path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop().ok_or(DataSetError::InvalidPath)?;
+            }
+            _ => components.push(component),
+        }
+    }
+
+    Ok(components.join("/"))
+}
+
+// Equivalent to canonicalize_path, except for paths that point into an archive only the archive
+// itself needs to exist on disk
+pub fn canonicalize_source_file_path(
+    path: &Path,
+    canonicalization: PathCanonicalization,
+) -> DataSetResult<PathBuf> {
+    let path_str = path.to_str().ok_or(DataSetError::InvalidPath)?;
+    if let Some((archive_path, entry_path)) = split_archive_path(path_str) {
+        let archive_path = canonicalize_path(Path::new(archive_path), canonicalization)
+            .map_err(|_| DataSetError::InvalidPath)?;
+        Ok(join_archive_path(
+            &archive_path,
+            &normalize_archive_entry_path(entry_path)?,
+        ))
+    } else {
+        Ok(canonicalize_path(path, canonicalization).map_err(|_| DataSetError::InvalidPath)?)
+    }
 }
 
 pub fn canonicalized_absolute_path(
@@ -27,26 +105,41 @@ pub fn canonicalized_absolute_path(
     namespace_resolver: &dyn PathReferenceNamespaceResolver,
     source_file_path: &Path,
 ) -> DataSetResult<PathReference> {
+    let canonicalization = namespace_resolver.path_canonicalization();
     let canonical_absolute_path = if namespace.is_empty() {
         if Path::new(referenced_path).is_relative() {
-            dunce::canonicalize(
-                source_file_path
-                    .parent()
-                    .unwrap()
-                    .join(Path::new(referenced_path))
-                    .as_path(),
-            )
-            .map_err(|_| DataSetError::InvalidPath)?
+            let source_file_path_str = source_file_path.to_string_lossy();
+            if let Some((archive_path, entry_path)) = split_archive_path(&source_file_path_str) {
+                // Relative references from a file inside an archive resolve within the archive
+                let entry_parent = entry_path
+                    .rfind(['/', '\\'])
+                    .map(|position| &entry_path[0..position])
+                    .unwrap_or("");
+                canonicalize_source_file_path(
+                    &join_archive_path(
+                        Path::new(archive_path),
+                        &format!("{}/{}", entry_parent, referenced_path),
+                    ),
+                    canonicalization,
+                )?
+            } else {
+                canonicalize_source_file_path(
+                    source_file_path
+                        .parent()
+                        .unwrap()
+                        .join(Path::new(referenced_path))
+                        .as_path(),
+                    canonicalization,
+                )?
+            }
         } else {
-            dunce::canonicalize(PathBuf::from(referenced_path))
-                .map_err(|_| DataSetError::InvalidPath)?
+            canonicalize_source_file_path(Path::new(referenced_path), canonicalization)?
         }
     } else {
         let namespace_root = namespace_resolver
             .namespace_root(namespace)
             .ok_or(DataSetError::UnknownPathNamespace)?;
-        dunce::canonicalize(namespace_root.join(referenced_path))
-            .map_err(|_| DataSetError::InvalidPath)?
+        canonicalize_source_file_path(&namespace_root.join(referenced_path), canonicalization)?
     };
 
     Ok(PathReference {
@@ -220,13 +313,18 @@ impl PathReference {
         } else {
             // If it's an absolute path, see if it is in a namespace, if it is, we can return a PathReference relative
             // to the namespace
-            let canonicalized_path = dunce::canonicalize(PathBuf::from(&self.path)).unwrap();
+            let canonicalized_path = canonicalize_source_file_path(
+                Path::new(&self.path),
+                namespace_resolver.path_canonicalization(),
+            )
+            .unwrap();
 
             if let Some((namespace, prefix)) = namespace_resolver.simplify_path(&canonicalized_path)
             {
+                // Always store '/' so the path resolves the same way on any platform
                 return CanonicalPathReference {
                     namespace,
-                    path: prefix.to_string_lossy().to_string(),
+                    path: prefix.to_string_lossy().replace('\\', "/"),
                     importable_name: self.importable_name,
                 };
             }
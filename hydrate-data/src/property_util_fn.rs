@@ -1,14 +1,31 @@
 use crate::{
-    DataSetError, DataSetResult, HashMap, Schema, SchemaFingerprint, SchemaNamedType, SchemaRecord,
+    split_property_path, DataSetError, DataSetResult, HashMap, Schema, SchemaFingerprint,
+    SchemaNamedType, SchemaRecord,
 };
 
+#[cfg(feature = "property-read-stats")]
+thread_local! {
+    static PROPERTY_ANCESTRY_COMPUTATION_COUNT: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+// Number of times this thread has looked up the schema and ancestors of a property path. Used to
+// measure how much work repeated property reads do.
+#[cfg(feature = "property-read-stats")]
+pub fn property_ancestry_computation_count() -> usize {
+    PROPERTY_ANCESTRY_COMPUTATION_COUNT.with(|count| count.get())
+}
+
+#[cfg(feature = "property-read-stats")]
+pub(super) fn count_property_ancestry_computation() {
+    PROPERTY_ANCESTRY_COMPUTATION_COUNT.with(|count| count.set(count.get() + 1));
+}
+
 pub(super) fn truncate_property_path(
     path: impl AsRef<str>,
     max_segment_count: usize,
 ) -> String {
     let mut shortened_path = String::default();
-    //TODO: Escape map keys (and probably avoid path strings anyways)
-    let split_path = path.as_ref().split(".");
+    let split_path = split_property_path(path.as_ref());
     for (i, path_segment) in split_path.enumerate() {
         if i > max_segment_count {
             break;
@@ -33,12 +50,21 @@ pub(super) fn property_schema_and_path_ancestors_to_check<'a>(
     accessed_static_array_keys: &mut Vec<(String, String)>,
     accessed_map_keys: &mut Vec<(String, String)>,
 ) -> DataSetResult<Schema> {
+    #[cfg(feature = "property-read-stats")]
+    count_property_ancestry_computation();
     let mut schema = Schema::Record(named_type.fingerprint());
 
-    //TODO: Escape map keys (and probably avoid path strings anyways)
-    let split_path: Vec<_> = path.as_ref().split(".").collect();
+    // Iterated rather than collected so that checking a path doesn't allocate
+    let mut split_path = split_property_path(path.as_ref()).enumerate().peekable();
+    while let Some((i, path_segment)) = split_path.next() {
+        if split_path.peek().is_none() {
+            schema = schema
+                .find_field_schema(path_segment, named_types)
+                .ok_or(DataSetError::SchemaNotFound)?
+                .clone();
+            break;
+        }
 
-    for (i, path_segment) in split_path[0..split_path.len() - 1].iter().enumerate() {
         // If failing to find the schema, check that code is querying a property that actually exists
         let child_schema = schema
             .find_field_schema(path_segment, named_types)
@@ -72,12 +98,5 @@ pub(super) fn property_schema_and_path_ancestors_to_check<'a>(
         schema = child_schema.clone();
     }
 
-    if let Some(last_path_segment) = split_path.last() {
-        schema = schema
-            .find_field_schema(last_path_segment, named_types)
-            .ok_or(DataSetError::SchemaNotFound)?
-            .clone();
-    }
-
     Ok(schema)
 }
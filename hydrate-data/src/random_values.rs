@@ -0,0 +1,254 @@
+use crate::value::ValueEnum;
+use crate::{
+    AssetId, AssetLocation, AssetName, DataSet, DataSetResult, NullOverride, PropertyPath, Schema,
+    SchemaRecord, SchemaSet, Value,
+};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// Past this depth, records and containers are left unset and asset refs point at existing assets
+// rather than generating new ones. Keeps recursive schemas from growing forever.
+const MAX_DEPTH: usize = 2;
+
+// Upper bound on randomly chosen lengths of dynamic arrays, maps, strings and bytes
+const MAX_LENGTH: usize = 4;
+
+impl DataSet {
+    /// Creates an asset of the given type with every property set to a random value that is valid
+    /// for its schema. Asset refs point at other generated assets of the referenced type, which are
+    /// created as needed. The same seeded RNG always produces the same assets, including their IDs.
+    pub fn new_asset_with_random_values<R: Rng>(
+        &mut self,
+        schema_set: &SchemaSet,
+        schema: &SchemaRecord,
+        rng: &mut R,
+    ) -> DataSetResult<AssetId> {
+        self.new_random_asset(schema_set, schema, rng, 0)
+    }
+
+    fn new_random_asset<R: Rng>(
+        &mut self,
+        schema_set: &SchemaSet,
+        schema: &SchemaRecord,
+        rng: &mut R,
+        depth: usize,
+    ) -> DataSetResult<AssetId> {
+        let asset_id = AssetId::from_uuid(Uuid::from_u128(rng.gen()));
+        self.new_asset_with_id(
+            asset_id,
+            AssetName::new(schema.name()),
+            AssetLocation::null(),
+            schema,
+        )?;
+
+        for field in schema.fields() {
+            self.set_random_property(
+                schema_set,
+                asset_id,
+                PropertyPath::default().push(field.name()),
+                field.field_schema(),
+                rng,
+                depth,
+            )?;
+        }
+
+        Ok(asset_id)
+    }
+
+    fn set_random_property<R: Rng>(
+        &mut self,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        path: PropertyPath,
+        schema: &Schema,
+        rng: &mut R,
+        depth: usize,
+    ) -> DataSetResult<()> {
+        if depth >= MAX_DEPTH {
+            match schema {
+                Schema::Nullable(_)
+                | Schema::StaticArray(_)
+                | Schema::DynamicArray(_)
+                | Schema::Map(_)
+                | Schema::Record(_) => return Ok(()),
+                _ => {}
+            }
+        }
+
+        let value = match schema {
+            Schema::Nullable(inner_schema) => {
+                if rng.gen_bool(0.25) {
+                    return self.set_null_override(
+                        schema_set,
+                        asset_id,
+                        path.path(),
+                        NullOverride::SetNull,
+                    );
+                }
+
+                self.set_null_override(
+                    schema_set,
+                    asset_id,
+                    path.path(),
+                    NullOverride::SetNonNull,
+                )?;
+                return self.set_random_property(
+                    schema_set,
+                    asset_id,
+                    path.push("value"),
+                    inner_schema,
+                    rng,
+                    depth + 1,
+                );
+            }
+            Schema::Boolean => Value::Boolean(rng.gen()),
+            Schema::I32 => Value::I32(rng.gen()),
+            Schema::I64 => Value::I64(rng.gen()),
+            Schema::U32 => Value::U32(rng.gen()),
+            Schema::U64 => Value::U64(rng.gen()),
+            // Finite values only, NaN would not compare equal to itself
+            Schema::F32 => Value::F32(rng.gen_range(-1000.0..1000.0)),
+            Schema::F64 => Value::F64(rng.gen_range(-1000.0..1000.0)),
+            Schema::Bytes => {
+                let length = rng.gen_range(0..=MAX_LENGTH);
+                Value::Bytes(Arc::new((0..length).map(|_| rng.gen()).collect()))
+            }
+            Schema::String => {
+                let length = rng.gen_range(0..=MAX_LENGTH);
+                let string = (0..length)
+                    .map(|_| rng.sample(Alphanumeric) as char)
+                    .collect();
+                Value::String(Arc::new(string))
+            }
+            Schema::StaticArray(static_array) => {
+                for i in 0..static_array.length() {
+                    self.set_random_property(
+                        schema_set,
+                        asset_id,
+                        path.push(&i.to_string()),
+                        static_array.item_type(),
+                        rng,
+                        depth + 1,
+                    )?;
+                }
+
+                return Ok(());
+            }
+            Schema::DynamicArray(dynamic_array) => {
+                let length = rng.gen_range(0..=MAX_LENGTH);
+
+                for i in 0..length {
+                    let entry_uuid = Uuid::from_u128(rng.gen());
+                    self.insert_dynamic_array_entry(
+                        schema_set,
+                        asset_id,
+                        path.path(),
+                        i,
+                        entry_uuid,
+                    )?;
+                    self.set_random_property(
+                        schema_set,
+                        asset_id,
+                        path.push(&entry_uuid.to_string()),
+                        dynamic_array.item_type(),
+                        rng,
+                        depth + 1,
+                    )?;
+                }
+
+                return Ok(());
+            }
+            Schema::Map(map) => {
+                let length = rng.gen_range(0..=MAX_LENGTH);
+
+                for _ in 0..length {
+                    let entry_uuid = Uuid::from_u128(rng.gen());
+                    self.insert_map_entry(schema_set, asset_id, path.path(), entry_uuid)?;
+                    self.set_random_property(
+                        schema_set,
+                        asset_id,
+                        path.push(&format!("{}:key", entry_uuid)),
+                        map.key_type(),
+                        rng,
+                        depth + 1,
+                    )?;
+                    self.set_random_property(
+                        schema_set,
+                        asset_id,
+                        path.push(&format!("{}:value", entry_uuid)),
+                        map.value_type(),
+                        rng,
+                        depth + 1,
+                    )?;
+                }
+
+                return Ok(());
+            }
+            Schema::AssetRef(fingerprint) => {
+                let referenced_schema = schema_set
+                    .find_named_type_by_fingerprint(*fingerprint)
+                    .unwrap()
+                    .as_record()?
+                    .clone();
+
+                // Point at an existing asset of the referenced type once we are deep enough, or
+                // leave the reference unset if there are none
+                let referenced_asset_id = if depth >= MAX_DEPTH {
+                    let mut existing_asset_ids: Vec<_> = self
+                        .assets()
+                        .iter()
+                        .filter(|(_, asset)| {
+                            asset.schema().fingerprint() == referenced_schema.fingerprint()
+                        })
+                        .map(|(asset_id, _)| *asset_id)
+                        .collect();
+
+                    // Iteration order of the assets is not deterministic
+                    existing_asset_ids.sort();
+                    if existing_asset_ids.is_empty() {
+                        AssetId::null()
+                    } else {
+                        existing_asset_ids[rng.gen_range(0..existing_asset_ids.len())]
+                    }
+                } else {
+                    self.new_random_asset(schema_set, &referenced_schema, rng, depth + 1)?
+                };
+
+                Value::AssetRef(referenced_asset_id)
+            }
+            Schema::Record(fingerprint) => {
+                let record = schema_set
+                    .find_named_type_by_fingerprint(*fingerprint)
+                    .unwrap()
+                    .as_record()?
+                    .clone();
+                for field in record.fields() {
+                    self.set_random_property(
+                        schema_set,
+                        asset_id,
+                        path.push(field.name()),
+                        field.field_schema(),
+                        rng,
+                        depth + 1,
+                    )?;
+                }
+
+                return Ok(());
+            }
+            Schema::Enum(fingerprint) => {
+                let schema_enum = schema_set
+                    .find_named_type_by_fingerprint(*fingerprint)
+                    .unwrap()
+                    .as_enum()?;
+                let symbols = schema_enum.symbols();
+                let symbol = &symbols[rng.gen_range(0..symbols.len())];
+                Value::Enum(ValueEnum::new(symbol.name().to_string()))
+            }
+        };
+
+        self.set_property_override(schema_set, asset_id, path.path(), Some(value))?;
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
 use crate::value::ValueEnum;
 use crate::{
-    DataSetError, DataSetResult, HashMap, SchemaFingerprint, SchemaLinker, SchemaLinkerResult,
-    SchemaNamedType, Value,
+    DataSetError, DataSetResult, HashMap, PropertyPath, Schema, SchemaDefEnum, SchemaDefEnumSymbol,
+    SchemaDefNamedType, SchemaDefRecord, SchemaDefRecordField, SchemaDefType, SchemaFingerprint,
+    SchemaLinker, SchemaLinkerResult, SchemaNamedType, Value,
 };
 use std::sync::Arc;
 use uuid::Uuid;
@@ -15,15 +16,26 @@ pub struct SchemaSetBuilder {
     schemas_by_name: HashMap<String, SchemaFingerprint>,
     schemas: HashMap<SchemaFingerprint, SchemaNamedType>,
     default_enum_values: HashMap<SchemaFingerprint, Value>,
+    strict_enum_symbols: bool,
 }
 
 impl SchemaSetBuilder {
     pub fn build(self) -> SchemaSet {
+        let mut sorted_fingerprints: Vec<_> = self.schemas.keys().copied().collect();
+        sorted_fingerprints.sort_by_key(|fingerprint| {
+            (
+                self.schemas[fingerprint].name().to_string(),
+                fingerprint.as_uuid(),
+            )
+        });
+
         let inner = SchemaSetInner {
             schemas_by_type_uuid: self.schemas_by_type_uuid,
             schemas_by_name: self.schemas_by_name,
             schemas: self.schemas,
+            sorted_fingerprints,
             default_enum_values: self.default_enum_values,
+            strict_enum_symbols: self.strict_enum_symbols,
         };
 
         SchemaSet {
@@ -31,6 +43,15 @@ impl SchemaSetBuilder {
         }
     }
 
+    /// By default, reading an enum field that holds a symbol the schema doesn't know falls back to
+    /// the enum's default symbol. Validation tooling can set this to make that an error instead.
+    pub fn set_strict_enum_symbols(
+        &mut self,
+        strict_enum_symbols: bool,
+    ) {
+        self.strict_enum_symbols = strict_enum_symbols;
+    }
+
     pub fn add_linked_types(
         &mut self,
         linker: SchemaLinker,
@@ -79,11 +100,124 @@ impl SchemaSetBuilder {
     }
 }
 
+enum InlineNamedType {
+    Record {
+        name: String,
+        fields: Vec<(String, SchemaDefType)>,
+    },
+    Enum {
+        name: String,
+        symbols: Vec<String>,
+    },
+}
+
+/// Defines named types in code rather than loading them from schema files, mostly useful for
+/// tests. Types may reference each other by name in any order, references are resolved and
+/// fingerprints computed by build(). Type and field UUIDs are random.
+#[derive(Default)]
+pub struct InlineSchemaBuilder {
+    named_types: Vec<InlineNamedType>,
+}
+
+impl InlineSchemaBuilder {
+    /// Starts a record type, fields added with field() are added to it
+    pub fn add_record(
+        mut self,
+        name: impl Into<String>,
+    ) -> Self {
+        self.named_types.push(InlineNamedType::Record {
+            name: name.into(),
+            fields: Vec::default(),
+        });
+        self
+    }
+
+    /// Adds a field to the most recently added record. Panics if the last type added was not a
+    /// record.
+    pub fn field(
+        mut self,
+        name: impl Into<String>,
+        field_type: SchemaDefType,
+    ) -> Self {
+        match self.named_types.last_mut() {
+            Some(InlineNamedType::Record { fields, .. }) => fields.push((name.into(), field_type)),
+            _ => panic!("field() must follow add_record()"),
+        }
+        self
+    }
+
+    pub fn add_enum(
+        mut self,
+        name: impl Into<String>,
+        symbols: &[&str],
+    ) -> Self {
+        self.named_types.push(InlineNamedType::Enum {
+            name: name.into(),
+            symbols: symbols.iter().map(|x| x.to_string()).collect(),
+        });
+        self
+    }
+
+    pub fn build(self) -> SchemaLinkerResult<SchemaSet> {
+        let mut linker = SchemaLinker::default();
+        for named_type in self.named_types {
+            let named_type = match named_type {
+                InlineNamedType::Record { name, fields } => {
+                    let mut record_fields = Vec::with_capacity(fields.len());
+                    for (field_name, field_type) in fields {
+                        record_fields.push(SchemaDefRecordField::new(
+                            field_name,
+                            Uuid::new_v4(),
+                            Vec::default(),
+                            field_type,
+                            Default::default(),
+                        )?);
+                    }
+
+                    SchemaDefNamedType::Record(SchemaDefRecord::new(
+                        name,
+                        Uuid::new_v4(),
+                        Vec::default(),
+                        record_fields,
+                        Default::default(),
+                    )?)
+                }
+                InlineNamedType::Enum { name, symbols } => {
+                    let mut enum_symbols = Vec::with_capacity(symbols.len());
+                    for symbol in symbols {
+                        enum_symbols.push(SchemaDefEnumSymbol::new(
+                            symbol,
+                            Uuid::new_v4(),
+                            Vec::default(),
+                        )?);
+                    }
+
+                    SchemaDefNamedType::Enum(SchemaDefEnum::new(
+                        name,
+                        Uuid::new_v4(),
+                        Vec::default(),
+                        enum_symbols,
+                    )?)
+                }
+            };
+
+            linker.add_named_type(named_type)?;
+        }
+
+        let mut schema_set_builder = SchemaSetBuilder::default();
+        schema_set_builder.add_linked_types(linker)?;
+        Ok(schema_set_builder.build())
+    }
+}
+
 pub struct SchemaSetInner {
     schemas_by_type_uuid: HashMap<Uuid, SchemaFingerprint>,
     schemas_by_name: HashMap<String, SchemaFingerprint>,
     schemas: HashMap<SchemaFingerprint, SchemaNamedType>,
+    // Sorted by name, then fingerprint
+    sorted_fingerprints: Vec<SchemaFingerprint>,
     default_enum_values: HashMap<SchemaFingerprint, Value>,
+    strict_enum_symbols: bool,
 }
 
 #[derive(Clone)]
@@ -92,10 +226,24 @@ pub struct SchemaSet {
 }
 
 impl SchemaSet {
+    /// Creates a schema set from types defined in code, see InlineSchemaBuilder
+    pub fn builder() -> InlineSchemaBuilder {
+        InlineSchemaBuilder::default()
+    }
+
     pub fn schemas(&self) -> &HashMap<SchemaFingerprint, SchemaNamedType> {
         &self.inner.schemas
     }
 
+    /// Iterates all named types sorted by name, then fingerprint. Unlike schemas(), the order is the
+    /// same every time, so use this when producing output like generated code.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = &SchemaNamedType> {
+        self.inner
+            .sorted_fingerprints
+            .iter()
+            .map(|fingerprint| &self.inner.schemas[fingerprint])
+    }
+
     pub fn schemas_by_type_uuid(&self) -> &HashMap<Uuid, SchemaFingerprint> {
         &self.inner.schemas_by_type_uuid
     }
@@ -107,6 +255,12 @@ impl SchemaSet {
         self.inner.default_enum_values.get(&fingerprint)
     }
 
+    /// If true, reading an enum field with an unknown symbol is an error rather than falling back to
+    /// the enum's default symbol
+    pub fn strict_enum_symbols(&self) -> bool {
+        self.inner.strict_enum_symbols
+    }
+
     pub fn find_named_type_by_type_uuid(
         &self,
         type_uuid: Uuid,
@@ -153,4 +307,53 @@ impl SchemaSet {
     ) -> Option<&SchemaNamedType> {
         self.inner.schemas.get(&fingerprint)
     }
+
+    /// Returns the path and schema of every leaf property of a record. Nested records and
+    /// nullables are descended into. Arrays and maps are returned as a single node because their
+    /// children depend on the data. Returns nothing if the fingerprint is not a record.
+    ///
+    /// Within each record, fields are visited sorted by name (the order SchemaRecord stores them
+    /// in), not the order they were declared in.
+    pub fn leaf_properties(
+        &self,
+        schema_fingerprint: SchemaFingerprint,
+    ) -> Vec<(String, Schema)> {
+        let mut leaf_properties = Vec::default();
+        self.gather_leaf_properties(
+            &Schema::Record(schema_fingerprint),
+            PropertyPath::default(),
+            &mut leaf_properties,
+        );
+        leaf_properties
+    }
+
+    fn gather_leaf_properties(
+        &self,
+        schema: &Schema,
+        path: PropertyPath,
+        leaf_properties: &mut Vec<(String, Schema)>,
+    ) {
+        match schema {
+            Schema::Nullable(inner_schema) => {
+                self.gather_leaf_properties(inner_schema, path.push("value"), leaf_properties);
+            }
+            Schema::Record(fingerprint) => {
+                let Some(record) = self
+                    .find_named_type_by_fingerprint(*fingerprint)
+                    .and_then(|x| x.try_as_record())
+                else {
+                    return;
+                };
+
+                for field in record.fields() {
+                    self.gather_leaf_properties(
+                        field.field_schema(),
+                        path.push(field.name()),
+                        leaf_properties,
+                    );
+                }
+            }
+            _ => leaf_properties.push((path.path().to_string(), schema.clone())),
+        }
+    }
 }
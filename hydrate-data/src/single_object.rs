@@ -1,7 +1,7 @@
 use crate::{
     DataSetError, DataSetResult, HashMap, OrderedSet, SchemaFingerprint, SchemaRecord, Value,
 };
-use crate::{NullOverride, SchemaSet};
+use crate::{NullOverride, PropertiesBundle, SchemaSet};
 use hydrate_schema::Schema;
 use std::hash::{Hash, Hasher};
 use std::str::FromStr;
@@ -111,6 +111,18 @@ impl SingleObject {
         &self.dynamic_collection_entries
     }
 
+    /// Removes all properties, null overrides, and dynamic collection entries so that everything
+    /// resolves to defaults. The removed overrides are returned.
+    pub fn clear_all_overrides(&mut self) -> PropertiesBundle {
+        PropertiesBundle::new(
+            Schema::Record(self.schema.fingerprint()),
+            std::mem::take(&mut self.properties),
+            std::mem::take(&mut self.property_null_overrides),
+            Default::default(),
+            std::mem::take(&mut self.dynamic_collection_entries),
+        )
+    }
+
     /// Gets if the property has a null override associated with it An error will be returned if
     /// the schema doesn't exist or if this field is not nullable
     pub fn get_null_override(
@@ -159,6 +171,87 @@ impl SingleObject {
         }
     }
 
+    /// Checks that everything stored in the object is consistent with its schema: every property
+    /// value matches the type at its path, null overrides are only set on nullables, dynamic
+    /// collection entries are only stored for dynamic arrays and maps, and paths only go through
+    /// existing entries and non-null nullables. This is used to catch bugs in code that produces
+    /// objects, like importers, before the object is persisted. The first problem found is logged
+    /// with the property path and returned.
+    pub fn validate(
+        &self,
+        schema_set: &SchemaSet,
+    ) -> DataSetResult<()> {
+        // Sorted so that the same problem is reported every time
+        let mut property_paths: Vec<_> = self.properties.keys().collect();
+        property_paths.sort();
+        for path in property_paths {
+            let value = &self.properties[path];
+            let result = self
+                .validate_parent_paths(schema_set, path)
+                .and_then(|property_schema| {
+                    if value.matches_schema(&property_schema, schema_set.schemas()) {
+                        Ok(())
+                    } else if value.as_enum().is_ok() && property_schema.is_enum() {
+                        Err(DataSetError::UnexpectedEnumSymbol)?
+                    } else {
+                        Err(DataSetError::ValueDoesNotMatchSchema)?
+                    }
+                });
+            self.log_validation_error(path, result)?;
+        }
+
+        let mut null_override_paths: Vec<_> = self.property_null_overrides.keys().collect();
+        null_override_paths.sort();
+        for path in null_override_paths {
+            let result = self
+                .validate_parent_paths(schema_set, path)
+                .and_then(|property_schema| {
+                    if property_schema.is_nullable() {
+                        Ok(())
+                    } else {
+                        Err(DataSetError::InvalidSchema)?
+                    }
+                });
+            self.log_validation_error(path, result)?;
+        }
+
+        let mut dynamic_collection_paths: Vec<_> = self.dynamic_collection_entries.keys().collect();
+        dynamic_collection_paths.sort();
+        for path in dynamic_collection_paths {
+            let result = self
+                .validate_parent_paths(schema_set, path)
+                .and_then(|property_schema| {
+                    if property_schema.is_dynamic_array() || property_schema.is_map() {
+                        Ok(())
+                    } else {
+                        Err(DataSetError::InvalidSchema)?
+                    }
+                });
+            self.log_validation_error(path, result)?;
+        }
+
+        // Asset references are only checked for being asset references. The object doesn't know
+        // about other assets, so the type of the referenced asset can't be checked.
+        Ok(())
+    }
+
+    fn log_validation_error(
+        &self,
+        path: &str,
+        result: DataSetResult<()>,
+    ) -> DataSetResult<()> {
+        if let Err(e) = &result {
+            log::warn!(
+                "Property {:?} of {} does not match the schema: {:?}",
+                path,
+                self.schema.name(),
+                e.error
+            );
+        }
+
+        result
+    }
+
     fn validate_parent_paths(
         &self,
         schema_set: &SchemaSet,
@@ -255,9 +348,9 @@ impl SingleObject {
         if let Some(value) = &value {
             if !value.matches_schema(&property_schema, schema_set.schemas()) {
                 log::debug!(
-                    "Value {:?} doesn't match schema {:?} on schema {:?} path {:?}",
+                    "Value {:?} doesn't match type {} on schema {:?} path {:?}",
                     value,
-                    property_schema,
+                    property_schema.type_name(schema_set.schemas()),
                     self.schema.name(),
                     path.as_ref()
                 );
@@ -287,6 +380,18 @@ impl SingleObject {
         Ok(Value::default_for_schema(&property_schema, schema_set))
     }
 
+    // True if the property is set, even if it's set to the schema's default value
+    pub fn is_property_explicitly_set(
+        &self,
+        schema_set: &SchemaSet,
+        path: impl AsRef<str>,
+    ) -> DataSetResult<bool> {
+        self.schema
+            .find_property_schema(path.as_ref(), schema_set.schemas())
+            .ok_or(DataSetError::SchemaNotFound)?;
+        Ok(self.properties.contains_key(path.as_ref()))
+    }
+
     fn get_dynamic_collection_entries(
         &self,
         path: impl AsRef<str>,
@@ -1,5 +1,5 @@
 use crate::AssetId;
-use crate::{HashMap, Schema, SchemaFingerprint, SchemaNamedType, SchemaSet};
+use crate::{HashMap, HashSet, Schema, SchemaFingerprint, SchemaNamedType, SchemaSet};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
@@ -731,6 +731,96 @@ impl Value {
         }
     }
 
+    // Replaces any asset references in the value that are in the mapping. Map keys are left as they
+    // are, data sets store map keys as their own properties rather than inside a ValueMap.
+    pub(crate) fn remap_asset_refs(
+        &mut self,
+        mapping: &HashMap<AssetId, AssetId>,
+    ) {
+        match self {
+            Value::AssetRef(asset_id) => {
+                if let Some(new_asset_id) = mapping.get(asset_id) {
+                    *asset_id = *new_asset_id;
+                }
+            }
+            Value::Nullable(Some(value)) => value.remap_asset_refs(mapping),
+            Value::StaticArray(values) | Value::DynamicArray(values) => {
+                for value in values {
+                    value.remap_asset_refs(mapping);
+                }
+            }
+            Value::Map(map) => {
+                for value in map.properties.values_mut() {
+                    value.remap_asset_refs(mapping);
+                }
+            }
+            Value::Record(record) => {
+                for value in record.properties.values_mut() {
+                    value.remap_asset_refs(mapping);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Adds every non-null asset reference in the value to the set
+    pub(crate) fn gather_asset_refs(
+        &self,
+        asset_refs: &mut HashSet<AssetId>,
+    ) {
+        match self {
+            Value::AssetRef(asset_id) if !asset_id.is_null() => {
+                asset_refs.insert(*asset_id);
+            }
+            Value::Nullable(Some(value)) => value.gather_asset_refs(asset_refs),
+            Value::StaticArray(values) | Value::DynamicArray(values) => {
+                for value in values {
+                    value.gather_asset_refs(asset_refs);
+                }
+            }
+            Value::Map(map) => {
+                for value in map.properties.values() {
+                    value.gather_asset_refs(asset_refs);
+                }
+            }
+            Value::Record(record) => {
+                for value in record.properties.values() {
+                    value.gather_asset_refs(asset_refs);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Rough number of bytes used by the value, including string, bytes and container payloads.
+    // Buffers shared between values are counted once for each value that references them.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        let payload_bytes = match self {
+            Value::Nullable(Some(value)) => value.estimated_memory_bytes(),
+            Value::Bytes(bytes) => bytes.capacity(),
+            Value::String(string) => string.capacity(),
+            Value::StaticArray(values) | Value::DynamicArray(values) => {
+                values.iter().map(|x| x.estimated_memory_bytes()).sum()
+            }
+            Value::Map(map) => map
+                .properties
+                .iter()
+                .map(|(k, v)| k.estimated_memory_bytes() + v.estimated_memory_bytes())
+                .sum(),
+            Value::Record(record) => record
+                .properties
+                .iter()
+                .map(|(k, v)| {
+                    std::mem::size_of::<String>() + k.capacity() + v.estimated_memory_bytes()
+                })
+                .sum(),
+            Value::Enum(value_enum) => value_enum.symbol_name.capacity(),
+            _ => 0,
+        };
+
+        std::mem::size_of::<Value>() + payload_bytes
+    }
+
     pub fn set_asset_ref(
         &mut self,
         value: AssetId,
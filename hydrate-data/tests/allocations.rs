@@ -0,0 +1,174 @@
+// Checks how many allocations data set operations make. These tests are their own test binary
+// because counting needs a global allocator, which would otherwise apply to every other test.
+
+use hydrate_data::{
+    AssetLocation, AssetName, DataSet, SchemaLinker, SchemaRecord, SchemaSet, SchemaSetBuilder,
+    Value,
+};
+use uuid::Uuid;
+
+// Counts allocations made on the current thread so that tests running in parallel don't interfere
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATION_COUNT: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(
+        &self,
+        layout: std::alloc::Layout,
+    ) -> *mut u8 {
+        // try_with because this may be called while the thread is being torn down
+        let _ = ALLOCATION_COUNT.try_with(|count| count.set(count.get() + 1));
+        std::alloc::System.alloc(layout)
+    }
+
+    unsafe fn dealloc(
+        &self,
+        ptr: *mut u8,
+        layout: std::alloc::Layout,
+    ) {
+        std::alloc::System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static COUNTING_ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocation_count() -> usize {
+    ALLOCATION_COUNT.with(|count| count.get())
+}
+
+fn vec3_schema() -> (SchemaSet, SchemaRecord) {
+    let mut linker = SchemaLinker::default();
+    linker
+        .register_record_type("Vec3", Uuid::new_v4(), |builder| {
+            builder.add_f32("x", Uuid::new_v4());
+            builder.add_f32("y", Uuid::new_v4());
+            builder.add_f32("z", Uuid::new_v4());
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+    (schema_set, vec3_type)
+}
+
+#[test]
+fn updating_an_override_in_place_does_not_allocate() {
+    let (schema_set, vec3_type) = vec3_schema();
+    let mut data_set = DataSet::default();
+    let asset_id = data_set.new_asset(AssetName::new("vec3"), AssetLocation::null(), &vec3_type);
+    data_set
+        .set_property_override(&schema_set, asset_id, "x", Some(Value::F32(0.0)))
+        .unwrap();
+
+    // Compare allocations of setting an existing override vs. updating it in place
+    const ITERATIONS: usize = 100;
+    let allocations_before_set = allocation_count();
+    for i in 0..ITERATIONS {
+        data_set
+            .set_property_override(&schema_set, asset_id, "x", Some(Value::F32(i as f32)))
+            .unwrap();
+    }
+    let set_allocations = allocation_count() - allocations_before_set;
+
+    let allocations_before_update = allocation_count();
+    for _ in 0..ITERATIONS {
+        data_set
+            .update_property_override(&schema_set, asset_id, "x", |value| {
+                *value = Value::F32(value.as_f32().unwrap() + 1.0)
+            })
+            .unwrap();
+    }
+    let update_allocations = allocation_count() - allocations_before_update;
+
+    assert_eq!(
+        data_set
+            .resolve_property(&schema_set, asset_id, "x")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        (ITERATIONS - 1 + ITERATIONS) as f32
+    );
+    // Setting allocates a new key for the property map every time, updating doesn't allocate
+    assert!(set_allocations >= ITERATIONS);
+    assert_eq!(update_allocations, 0);
+}
+
+#[test]
+fn scalar_reads_of_top_level_fields_do_not_allocate() {
+    let (schema_set, vec3_type) = vec3_schema();
+    let mut data_set = DataSet::default();
+
+    // x is overridden on the asset, y comes from the prototype and z is the schema default
+    let prototype = data_set.new_asset(
+        AssetName::new("prototype"),
+        AssetLocation::null(),
+        &vec3_type,
+    );
+    data_set
+        .set_property_override(&schema_set, prototype, "y", Some(Value::F32(2.0)))
+        .unwrap();
+    let asset_id = data_set
+        .new_asset_from_prototype(AssetName::new("vec3"), AssetLocation::null(), prototype)
+        .unwrap();
+    data_set
+        .set_property_override(&schema_set, asset_id, "x", Some(Value::F32(1.0)))
+        .unwrap();
+
+    let paths = ["x", "y", "z"];
+    let mut values = Vec::with_capacity(paths.len() * 100);
+    let allocations_before = allocation_count();
+    for i in 0..values.capacity() {
+        values.push(
+            data_set
+                .resolve_f32(&schema_set, asset_id, paths[i % paths.len()])
+                .unwrap(),
+        );
+    }
+    let allocations = allocation_count() - allocations_before;
+
+    assert_eq!(&values[0..3], &[1.0, 2.0, 0.0]);
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn snapshots_do_not_copy_property_data() {
+    let (schema_set, vec3_type) = vec3_schema();
+
+    let snapshot_allocations = |with_overrides: bool| {
+        let mut data_set = DataSet::default();
+        for _ in 0..100 {
+            let asset_id =
+                data_set.new_asset(AssetName::new("vec3"), AssetLocation::null(), &vec3_type);
+            if with_overrides {
+                for path in ["x", "y", "z"] {
+                    data_set
+                        .set_property_override(&schema_set, asset_id, path, Some(Value::F32(1.0)))
+                        .unwrap();
+                }
+            }
+        }
+
+        let allocations_before = allocation_count();
+        let snapshot = data_set.snapshot();
+        let allocations = allocation_count() - allocations_before;
+        assert_eq!(snapshot.asset_count(), 100);
+        allocations
+    };
+
+    // Taking a snapshot copies the asset map but shares the assets themselves
+    let allocations_without_overrides = snapshot_allocations(false);
+    let allocations_with_overrides = snapshot_allocations(true);
+    assert_eq!(allocations_without_overrides, allocations_with_overrides);
+    assert!(allocations_with_overrides <= 1);
+}
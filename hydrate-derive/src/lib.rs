@@ -0,0 +1,72 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+// Implements hydrate_data::FromRecord for a struct with named fields. Each field is read from the
+// record property with the same name, use #[hydrate(rename = "name")] if the names differ. Field
+// types must implement hydrate_data::FromProperty, which includes other types deriving this.
+#[proc_macro_derive(FromHydrateRecord, attributes(hydrate))]
+pub fn derive_from_hydrate_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match from_hydrate_record_impl(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn from_hydrate_record_impl(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Struct(data_struct) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "FromHydrateRecord can only be derived for structs",
+        ));
+    };
+
+    let Fields::Named(fields) = &data_struct.fields else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "FromHydrateRecord can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut field_reads = Vec::default();
+    for field in &fields.named {
+        let field_name = field.ident.as_ref().unwrap();
+        let mut property_name = field_name.to_string();
+        for attr in &field.attrs {
+            if attr.path().is_ident("hydrate") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        property_name = meta.value()?.parse::<LitStr>()?.value();
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported hydrate attribute"))
+                    }
+                })?;
+            }
+        }
+
+        let field_type = &field.ty;
+        field_reads.push(quote! {
+            #field_name: <#field_type as ::hydrate_data::FromProperty>::read_property(
+                data_container.clone(),
+                &property_path.push(#property_name),
+            )?
+        });
+    }
+
+    let struct_name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::hydrate_data::FromRecord for #struct_name #type_generics #where_clause {
+            fn from_record(
+                data_container: ::hydrate_data::DataContainerRef,
+                property_path: &::hydrate_data::PropertyPath,
+            ) -> ::hydrate_data::DataSetResult<Self> {
+                Ok(#struct_name {
+                    #(#field_reads,)*
+                })
+            }
+        }
+    })
+}
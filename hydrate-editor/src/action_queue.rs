@@ -8,14 +8,18 @@ use hydrate_base::hashing::HashMap;
 use hydrate_model::edit_context::EditContext;
 use hydrate_model::pipeline::{AssetEngine, HydrateProjectConfiguration, ImportJobToQueue};
 use hydrate_model::{
-    AssetId, AssetLocation, AssetName, DataSetError, DataSetErrorWithBacktrace, DataSetResult,
-    EditorModel, EndContextBehavior, NullOverride, OverrideBehavior, PropertyPath, Schema,
-    SchemaFingerprint, SchemaRecord, Value,
+    AssetId, AssetLocation, AssetName, AutosaveRecovery, DataSetError, DataSetErrorWithBacktrace,
+    DataSetResult, EditorModel, EndContextBehavior, NullOverride, OverrideBehavior, PropertyPath,
+    Schema, SchemaFingerprint, SchemaRecord, Value,
 };
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+// How often data sources are checked for changes made outside the editor
+const DATA_SOURCE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 pub enum UIAction {
     TryBeginModalAction(Box<dyn ModalAction>),
     EditContext(
@@ -29,6 +33,8 @@ pub enum UIAction {
     RevertAllNoConfirm,
     Quit,
     QuitNoConfirm,
+    RestoreAutosave(AutosaveRecovery),
+    DiscardAutosave,
     PersistAssets(Vec<AssetId>),
     BuildAll,
     ReimportAndRebuild(Vec<AssetId>),
@@ -39,6 +45,8 @@ pub enum UIAction {
     NewAsset(AssetName, AssetLocation, SchemaRecord, Option<AssetId>),
     DuplicateAssets(Vec<AssetId>),
     DeleteAssets(Vec<AssetId>),
+    CreateBuildSettings(AssetId, SchemaRecord),
+    SetBuildSettings(AssetId, Option<AssetId>),
     SetProperty(
         Vec<AssetId>,
         PropertyPath,
@@ -125,6 +133,8 @@ pub struct UIActionQueueReceiver {
     action_queue_tx: Sender<UIAction>,
     action_queue_rx: Receiver<UIAction>,
     anything_has_focus_last_frame: bool,
+    last_data_source_poll: Instant,
+    last_autosave: Instant,
 }
 
 impl Default for UIActionQueueReceiver {
@@ -144,6 +154,8 @@ impl Default for UIActionQueueReceiver {
             action_queue_tx,
             action_queue_rx,
             anything_has_focus_last_frame: false,
+            last_data_source_poll: Instant::now(),
+            last_autosave: Instant::now(),
         }
     }
 }
@@ -232,7 +244,11 @@ impl UIActionQueueReceiver {
                 UIAction::ToggleSelectAllAssetGallery => {
                     ui_state.asset_gallery_ui_state.toggle_select_all();
                 }
-                UIAction::SaveAll => editor_model.save_root_edit_context(),
+                UIAction::SaveAll => {
+                    if let Err(e) = editor_model.save_root_edit_context() {
+                        log::error!("Failed to save: {:?}", e);
+                    }
+                }
                 UIAction::RevertAll => {
                     if editor_model.any_edit_context_has_unsaved_changes() {
                         *modal_action = Some(Box::new(ConfirmRevertChanges {}))
@@ -255,6 +271,12 @@ impl UIActionQueueReceiver {
                     ui_state.user_confirmed_should_quit = true;
                     ctx.send_viewport_cmd(egui::ViewportCommand::Close)
                 }
+                UIAction::RestoreAutosave(recovery) => {
+                    if let Err(e) = editor_model.restore_autosave(&recovery) {
+                        log::error!("Failed to restore autosaved changes: {:?}", e);
+                    }
+                }
+                UIAction::DiscardAutosave => editor_model.remove_autosave(),
                 UIAction::TryBeginModalAction(modal) => {
                     if modal_action.is_none() {
                         *modal_action = Some(modal);
@@ -266,7 +288,9 @@ impl UIActionQueueReceiver {
 
                 UIAction::PersistAssets(asset_ids) => {
                     for asset_id in asset_ids {
-                        editor_model.persist_generated_asset(asset_id);
+                        if let Err(e) = editor_model.persist_generated_asset(asset_id) {
+                            log::error!("Failed to persist asset {:?}: {:?}", asset_id, e);
+                        }
                     }
                 }
                 UIAction::BuildAll => {
@@ -283,9 +307,12 @@ impl UIActionQueueReceiver {
                                         .unwrap();
                                 }
 
-                                edit_context
-                                    .set_asset_location(asset_id, new_location)
-                                    .unwrap();
+                                // Moving a path node beneath itself is rejected, leave it where it is
+                                if let Err(e) =
+                                    edit_context.set_asset_location(asset_id, new_location)
+                                {
+                                    log::warn!("Could not move asset {:?}: {:?}", asset_id, e);
+                                }
                             }
 
                             EndContextBehavior::Finish
@@ -382,7 +409,9 @@ impl UIActionQueueReceiver {
                                         // do nothing
                                     }
                                     Err(DataSetErrorWithBacktrace {
-                                        error: DataSetError::NewLocationIsChildOfCurrentAsset,
+                                        error:
+                                            DataSetError::NewLocationIsChildOfCurrentAsset
+                                            | DataSetError::LocationCycleDetected,
                                         ..
                                     }) => {
                                         // do nothing
@@ -421,11 +450,17 @@ impl UIActionQueueReceiver {
                 }
                 UIAction::DuplicateAssets(asset_ids) => {
                     editor_model.root_edit_context_mut().with_undo_context(
-                        "delete asset",
+                        "duplicate asset",
                         |edit_context| {
                             for asset_id in asset_ids {
                                 let new_asset_id = edit_context.duplicate_asset(asset_id).unwrap();
-                                if edit_context.import_info(asset_id).is_some() {
+                                // External import data is read from the source file, so there
+                                // is nothing to copy
+                                let has_copied_import_data = edit_context
+                                    .import_info(asset_id)
+                                    .map(|x| x.import_data_storage().is_copied())
+                                    .unwrap_or(false);
+                                if has_copied_import_data {
                                     asset_engine
                                         .duplicate_import_data(asset_id, new_asset_id)
                                         .unwrap();
@@ -440,29 +475,69 @@ impl UIActionQueueReceiver {
                         "delete asset",
                         |edit_context| {
                             for asset_id in asset_ids {
-                                edit_context.delete_asset(asset_id).unwrap();
+                                // May have already been deleted as a child of another selected asset
+                                if edit_context.has_asset(asset_id) {
+                                    edit_context.delete_asset_and_children(asset_id).unwrap();
+                                }
                             }
                             EndContextBehavior::Finish
                         },
                     );
                 }
-                UIAction::SetProperty(asset_ids, property_path, value, end_context_behavior) => {
+                UIAction::CreateBuildSettings(asset_id, schema_record) => {
                     editor_model.root_edit_context_mut().with_undo_context(
-                        "set property",
+                        "create build settings",
                         |edit_context| {
-                            for asset_id in asset_ids {
-                                edit_context
-                                    .set_property_override(
-                                        asset_id,
-                                        property_path.path(),
-                                        value.clone(),
-                                    )
-                                    .unwrap();
-                            }
-                            end_context_behavior
+                            // Place the settings next to the asset they apply to
+                            let asset_name =
+                                edit_context.asset_name_or_id_string(asset_id).unwrap();
+                            let asset_location = edit_context.asset_location(asset_id).unwrap();
+                            let build_settings = edit_context.new_asset(
+                                &AssetName::new(format!("{} Build Settings", asset_name)),
+                                &asset_location,
+                                &schema_record,
+                            );
+                            edit_context
+                                .set_build_settings(asset_id, Some(build_settings))
+                                .unwrap();
+
+                            self.sender
+                                .queue_action(UIAction::ShowAssetInAssetGallery(build_settings));
+                            EndContextBehavior::Finish
                         },
                     );
                 }
+                UIAction::SetBuildSettings(asset_id, build_settings) => {
+                    editor_model.root_edit_context_mut().with_undo_context(
+                        "set build settings",
+                        |edit_context| {
+                            edit_context
+                                .set_build_settings(asset_id, build_settings)
+                                .unwrap();
+                            EndContextBehavior::Finish
+                        },
+                    );
+                }
+                UIAction::SetProperty(asset_ids, property_path, value, end_context_behavior) => {
+                    // Intermediate values (i.e. while dragging) are previewed and coalesced into
+                    // one undo step once the final value is set
+                    let root_edit_context = editor_model.root_edit_context_mut();
+                    root_edit_context.with_preview_edit("set property", |edit_context| {
+                        for asset_id in asset_ids {
+                            edit_context
+                                .set_property_override(
+                                    asset_id,
+                                    property_path.path(),
+                                    value.clone(),
+                                )
+                                .unwrap();
+                        }
+                    });
+
+                    if end_context_behavior == EndContextBehavior::Finish {
+                        root_edit_context.commit_preview();
+                    }
+                }
                 UIAction::ClearPropertiesForRecord(
                     asset_ids,
                     property_path,
@@ -480,6 +555,11 @@ impl UIActionQueueReceiver {
                                 .clone();
 
                             for field in record_schema.fields() {
+                                // Readonly fields can't be changed through the edit context
+                                if field.markup().readonly {
+                                    continue;
+                                }
+
                                 let field_path = property_path.push(field.name());
                                 for &asset_id in &asset_ids {
                                     edit_context
@@ -531,6 +611,11 @@ impl UIActionQueueReceiver {
                                 .clone();
 
                             for field in record_schema.fields() {
+                                // Readonly fields can't be changed through the edit context
+                                if field.markup().readonly {
+                                    continue;
+                                }
+
                                 let field_path = property_path.push(field.name());
                                 for &asset_id in &asset_ids {
                                     edit_context
@@ -591,6 +676,11 @@ impl UIActionQueueReceiver {
                                 .clone();
 
                             for field in record_schema.fields() {
+                                // Readonly fields can't be changed through the edit context
+                                if field.markup().readonly {
+                                    continue;
+                                }
+
                                 let field_path = property_path.push(field.name());
                                 let value = edit_context
                                     .resolve_property(src_asset_id, field_path.path())
@@ -912,6 +1002,31 @@ impl UIActionQueueReceiver {
             }
         }
 
+        if self.last_data_source_poll.elapsed() >= DATA_SOURCE_POLL_INTERVAL {
+            let changed_locations =
+                editor_model.poll_data_sources_for_changes(&mut import_job_to_queue);
+            if !changed_locations.is_empty() {
+                log::info!(
+                    "Reloaded {} locations changed outside the editor",
+                    changed_locations.len()
+                );
+            }
+            self.last_data_source_poll = Instant::now();
+        }
+
+        // Don't autosave while a modal is open, it may be asking whether to restore the previous
+        // recovery file
+        let autosave_interval = Duration::from_secs(project_config.autosave_interval_seconds);
+        if project_config.autosave_interval_seconds > 0
+            && modal_action.is_none()
+            && self.last_autosave.elapsed() >= autosave_interval
+        {
+            if let Err(e) = editor_model.autosave() {
+                log::error!("Failed to autosave: {:?}", e);
+            }
+            self.last_autosave = Instant::now();
+        }
+
         if !import_job_to_queue.is_empty() {
             asset_engine.queue_import_operation(import_job_to_queue);
         }
@@ -986,6 +1101,10 @@ fn override_with_default_values_recursively(
                 record_schema.fields()
             );
             for field in record_schema.fields() {
+                if field.markup().readonly {
+                    continue;
+                }
+
                 let field_path = property_path.push(field.name());
                 let field_schema = record_schema.field_schema(field.name()).unwrap();
                 override_with_default_values_recursively(
@@ -6,9 +6,10 @@ use crate::modal_action::{ModalAction, ModalActionControlFlow, ModalContext};
 use crate::persistent_app_state::PersistentAppState;
 use crate::ui::components::inspector_system::InspectorRegistry;
 use crate::ui::components::{
-    AssetGalleryUiState, AssetTreeUiState, InspectorUiState, LogEventViewUiState,
+    AssetGalleryUiState, AssetTreeUiState, DiagnosticConsoleUiState, InspectorUiState,
+    LogEventViewUiState,
 };
-use crate::ui::modals::ImportFilesModal;
+use crate::ui::modals::{ImportFilesModal, RestoreAutosaveModal};
 use crate::ui_state::EditorModelUiState;
 use egui::{Ui, ViewportCommand, WidgetText};
 use egui_tiles::{SimplificationOptions, TileId};
@@ -22,6 +23,7 @@ enum DockingPanelKind {
     AssetGallery,
     Inspector,
     LogEventView,
+    DiagnosticConsole,
 }
 
 struct MainUiContext<'a> {
@@ -59,6 +61,7 @@ impl<'a> egui_tiles::Behavior<DockingPanelKind> for MainUiContext<'a> {
             DockingPanelKind::AssetGallery => draw_asset_gallery(ui, self),
             DockingPanelKind::Inspector => draw_property_inspector(ui, self),
             DockingPanelKind::LogEventView => draw_log_event_view(ui, self),
+            DockingPanelKind::DiagnosticConsole => draw_diagnostic_console(ui, self),
         }
 
         egui_tiles::UiResponse::None
@@ -80,6 +83,23 @@ fn draw_log_event_view(
     );
 }
 
+fn draw_diagnostic_console(
+    ui: &mut egui::Ui,
+    ui_context: &mut MainUiContext,
+) {
+    crate::ui::components::draw_diagnostic_console(
+        ui,
+        &ui_context.db_state.editor_model,
+        &ui_context.ui_state.editor_model_ui_state,
+        &mut ui_context.ui_state.diagnostic_console_ui_state,
+        ui_context.action_queue_sender,
+        ui_context
+            .ui_state
+            .asset_gallery_ui_state
+            .primary_selected_asset(),
+    );
+}
+
 fn draw_asset_tree(
     ui: &mut egui::Ui,
     ui_context: &mut MainUiContext,
@@ -126,6 +146,7 @@ fn draw_property_inspector(
             .primary_selected_asset(),
         ui_context.inspector_registry,
         ui_context.thumbnail_image_loader,
+        ui_context.asset_engine,
     );
 }
 
@@ -136,6 +157,7 @@ pub struct UiState {
     pub inspector_ui_state: InspectorUiState,
     pub editor_model_ui_state: EditorModelUiState,
     pub log_event_view_ui_state: LogEventViewUiState,
+    pub diagnostic_console_ui_state: DiagnosticConsoleUiState,
     pub egui_debug_ui_state: EguiDebugUiState,
     pub user_confirmed_should_quit: bool,
     pub previous_logs: Vec<LogData>,
@@ -159,7 +181,7 @@ impl HydrateEditorApp {
     pub fn new(
         cc: &eframe::CreationContext<'_>,
         db_state: DbState,
-        asset_engine: AssetEngine,
+        mut asset_engine: AssetEngine,
         inspector_registry: InspectorRegistry,
     ) -> Self {
         let persistent_state = if let Some(storage) = cc.storage {
@@ -193,6 +215,7 @@ impl HydrateEditorApp {
         let mut center_tabs = vec![];
         center_tabs.push(tiles.insert_pane(DockingPanelKind::AssetGallery));
         center_tabs.push(tiles.insert_pane(DockingPanelKind::LogEventView));
+        center_tabs.push(tiles.insert_pane(DockingPanelKind::DiagnosticConsole));
         let central_tabs = tiles.insert_tab_tile(center_tabs);
 
         let asset_tree_pane = tiles.insert_pane(DockingPanelKind::AssetTree);
@@ -207,13 +230,40 @@ impl HydrateEditorApp {
 
         let dock_state = egui_tiles::Tree::new("tree", root, tiles);
 
+        // Warn about assets whose schema changed since the project was last opened, their data
+        // may not load the way it was saved
+        for schema_change in db_state.editor_model.detect_schema_changes() {
+            log::warn!(
+                "Schema changed since the project was last opened: {:?}",
+                schema_change
+            );
+        }
+        if let Err(e) = db_state.editor_model.record_schema_fingerprints() {
+            log::error!("Failed to record schema fingerprints: {:?}", e);
+        }
+
+        // Offer to restore changes that were autosaved but never saved
+        let modal_action: Option<Box<dyn ModalAction>> = match db_state.editor_model.read_autosave()
+        {
+            Ok(Some(recovery)) => Some(Box::new(RestoreAutosaveModal::new(recovery))),
+            Ok(None) => None,
+            Err(e) => {
+                log::error!("Failed to read autosave recovery file: {:?}", e);
+                None
+            }
+        };
+
+        // Import and build diagnostics are shown in the diagnostic console
+        let ui_state = UiState::default();
+        asset_engine.add_diagnostic_sink(ui_state.diagnostic_console_ui_state.create_sink());
+
         HydrateEditorApp {
             db_state,
             asset_engine,
             persistent_state,
-            ui_state: Default::default(),
+            ui_state,
             action_queue: UIActionQueueReceiver::default(),
-            modal_action: None,
+            modal_action,
             inspector_registry,
             thumbnail_image_loader: image_loader.clone(),
             dock_state,
@@ -269,6 +319,7 @@ impl eframe::App for HydrateEditorApp {
                     .map(|x| x.path.clone().unwrap())
                     .collect();
                 action_queue_sender.try_set_modal_action(ImportFilesModal::new(
+                    &self.db_state.project_configuration,
                     dropped_files,
                     self.asset_engine.importer_registry(),
                 ));
@@ -437,6 +488,19 @@ impl eframe::App for HydrateEditorApp {
             ctx,
         );
 
+        //
+        // Widgets don't always report a final change when a drag ends, so commit any preview edit
+        // once nothing is being dragged or typed into
+        //
+        let is_interacting =
+            ctx.input(|input| input.pointer.any_down()) || ctx.memory(|mem| mem.focus().is_some());
+        if !is_interacting {
+            self.db_state
+                .editor_model
+                .root_edit_context_mut()
+                .commit_preview();
+        }
+
         //
         // Draw egui debug ui if it's enabled
         //
@@ -1,4 +1,6 @@
-use hydrate_model::pipeline::{HydrateProjectConfiguration, ImportJobToQueue, ImporterRegistry};
+use hydrate_model::pipeline::{
+    HydrateProjectConfiguration, ImportJobToQueue, ImporterRegistry, PipelineResult,
+};
 use hydrate_model::{
     EditorModel, PathNode, PathNodeRoot, SchemaLinker, SchemaSet, SchemaSetBuilder,
 };
@@ -72,7 +74,7 @@ impl DbState {
         }
     }
 
-    pub fn save(&mut self) {
-        self.editor_model.save_root_edit_context();
+    pub fn save(&mut self) -> PipelineResult<()> {
+        self.editor_model.save_root_edit_context()
     }
 }
@@ -14,9 +14,11 @@ use uuid::Uuid;
 
 const THUMBNAIL_ASSET_URI_PREFIX: &str = "thumbnail-asset://";
 const THUMBNAIL_ASSET_TYPE_URI_PREFIX: &str = "thumbnail-asset-type://";
+const THUMBNAIL_ASSET_ICON_URI_PREFIX: &str = "thumbnail-asset-icon://";
 const THUMBNAIL_URI_NO_THUMBNAIL: &str = "thumbnail-special://no-thumbnail";
 const THUMBNAIL_URI_NO_REFERENCE: &str = "thumbnail-special://no-reference";
 const THUMBNAIL_CACHE_SIZE: u32 = 64;
+const THUMBNAIL_CACHE_MAX_BYTES: usize = 32 * 1024 * 1024;
 
 struct CachedThumbnail {
     _thumbnail_input_hash: ThumbnailInputHash,
@@ -29,6 +31,7 @@ pub struct ThumbnailImageLoader {
     thumbnail_system_state: ThumbnailSystemState,
     thumbnail_provider_registry: ThumbnailProviderRegistry,
     default_thumbnails: HashMap<SchemaFingerprint, Arc<ColorImage>>,
+    icons: HashMap<SchemaFingerprint, Arc<ColorImage>>,
     special_thumbnail_no_thumbnail: Arc<ColorImage>,
     special_thumbnail_no_reference: Arc<ColorImage>,
 }
@@ -42,6 +45,7 @@ impl ThumbnailImageLoader {
         let dummy_image = ColorImage::example();
         let mut loaded_images = HashMap::<PathBuf, Arc<ColorImage>>::default();
         let mut default_thumbnails = HashMap::default();
+        let mut icons = HashMap::default();
 
         let no_reference_image = image::load_from_memory_with_format(
             include_bytes!("../thumbnails/no-reference.png"),
@@ -74,6 +78,16 @@ impl ThumbnailImageLoader {
         );
 
         for (k, v) in schema_set.schemas() {
+            if let Some(icon) = thumbnail_provider_registry.icon_for_asset(*k) {
+                icons.insert(
+                    *k,
+                    Arc::new(ColorImage::from_rgba_unmultiplied(
+                        [icon.width as usize, icon.height as usize],
+                        &icon.pixel_data,
+                    )),
+                );
+            }
+
             if let Some(record) = v.try_as_record() {
                 if let Some(path) = &record.markup().default_thumbnail {
                     if let Some(loaded_image) = loaded_images.get(path) {
@@ -93,10 +107,14 @@ impl ThumbnailImageLoader {
 
         ThumbnailImageLoader {
             dummy_image: Arc::new(dummy_image),
-            thumbnail_cache: Mutex::new(LruCache::new(THUMBNAIL_CACHE_SIZE)),
+            thumbnail_cache: Mutex::new(LruCache::new_with_byte_budget(
+                THUMBNAIL_CACHE_SIZE,
+                THUMBNAIL_CACHE_MAX_BYTES,
+            )),
             thumbnail_system_state: thumbnail_system_state.clone(),
             thumbnail_provider_registry: thumbnail_provider_registry.clone(),
             default_thumbnails,
+            icons,
             special_thumbnail_no_thumbnail: Arc::new(no_thumbnail),
             special_thumbnail_no_reference: Arc::new(no_reference),
         }
@@ -132,6 +150,12 @@ impl ThumbnailImageLoader {
                 THUMBNAIL_ASSET_URI_PREFIX,
                 asset_id.as_uuid().to_string()
             )
+        } else if self.icons.contains_key(&schema_fingerprint) {
+            format!(
+                "{}{}",
+                THUMBNAIL_ASSET_ICON_URI_PREFIX,
+                schema_fingerprint.as_uuid().to_string()
+            )
         } else if self.default_thumbnails.contains_key(&schema_fingerprint) {
             format!(
                 "{}{}",
@@ -164,6 +188,12 @@ impl ThumbnailImageLoader {
                     THUMBNAIL_ASSET_URI_PREFIX,
                     asset_id.as_uuid().to_string()
                 );
+            } else if self.icons.contains_key(&schema_fingerprint) {
+                return format!(
+                    "{}{}",
+                    THUMBNAIL_ASSET_ICON_URI_PREFIX,
+                    schema_fingerprint.as_uuid().to_string()
+                );
             } else if self.default_thumbnails.contains_key(&schema_fingerprint) {
                 return format!(
                     "{}{}",
@@ -196,6 +226,19 @@ impl ImageLoader for ThumbnailImageLoader {
             Ok(ImagePoll::Ready {
                 image: self.special_thumbnail_no_reference.clone(),
             })
+        } else if uri.starts_with(THUMBNAIL_ASSET_ICON_URI_PREFIX) {
+            let schema_fingerprint = SchemaFingerprint::from_uuid(
+                Uuid::parse_str(&uri[THUMBNAIL_ASSET_ICON_URI_PREFIX.len()..]).unwrap(),
+            );
+            if let Some(icon) = self.icons.get(&schema_fingerprint) {
+                Ok(ImagePoll::Ready {
+                    image: icon.clone(),
+                })
+            } else {
+                Ok(ImagePoll::Ready {
+                    image: self.dummy_image.clone(),
+                })
+            }
         } else if uri.starts_with(THUMBNAIL_ASSET_TYPE_URI_PREFIX) {
             let schema_fingerprint = SchemaFingerprint::from_uuid(
                 Uuid::parse_str(&uri[THUMBNAIL_ASSET_TYPE_URI_PREFIX.len()..]).unwrap(),
@@ -225,12 +268,14 @@ impl ImageLoader for ThumbnailImageLoader {
                     &cached_entry.image.pixel_data,
                 ));
 
-                cache.insert(
+                let byte_size = image.pixels.len() * std::mem::size_of::<egui::Color32>();
+                cache.insert_with_size(
                     asset_id,
                     CachedThumbnail {
                         _thumbnail_input_hash: cached_entry.hash,
                         color_image: image.clone(),
                     },
+                    byte_size,
                 );
 
                 Ok(ImagePoll::Ready { image })
@@ -257,12 +302,11 @@ impl ImageLoader for ThumbnailImageLoader {
     fn forget_all(&self) {
         self.thumbnail_system_state.forget_all();
         let mut cache = self.thumbnail_cache.lock().unwrap();
-        *cache = LruCache::new(THUMBNAIL_CACHE_SIZE);
+        *cache = LruCache::new_with_byte_budget(THUMBNAIL_CACHE_SIZE, THUMBNAIL_CACHE_MAX_BYTES);
     }
 
     fn byte_size(&self) -> usize {
-        //TODO: Implement this
-        0
+        self.thumbnail_cache.lock().unwrap().total_bytes()
     }
 }
 
@@ -273,7 +317,10 @@ pub struct AssetThumbnailTextureLoader {
 impl AssetThumbnailTextureLoader {
     pub fn new() -> Self {
         AssetThumbnailTextureLoader {
-            cache: Mutex::new(LruCache::new(THUMBNAIL_CACHE_SIZE)),
+            cache: Mutex::new(LruCache::new_with_byte_budget(
+                THUMBNAIL_CACHE_SIZE,
+                THUMBNAIL_CACHE_MAX_BYTES,
+            )),
         }
     }
 }
@@ -300,7 +347,8 @@ impl TextureLoader for AssetThumbnailTextureLoader {
                 ImagePoll::Ready { image } => {
                     let handle = ctx.load_texture(uri, image, texture_options);
                     let texture = SizedTexture::from_handle(&handle);
-                    cache.insert((uri.into(), texture_options), handle);
+                    let byte_size = handle.byte_size();
+                    cache.insert_with_size((uri.into(), texture_options), handle, byte_size);
                     Ok(TexturePoll::Ready { texture })
                 }
             }
@@ -327,7 +375,7 @@ impl TextureLoader for AssetThumbnailTextureLoader {
 
     fn forget_all(&self) {
         let mut cache = self.cache.lock().unwrap();
-        *cache = LruCache::new(THUMBNAIL_CACHE_SIZE)
+        *cache = LruCache::new_with_byte_budget(THUMBNAIL_CACHE_SIZE, THUMBNAIL_CACHE_MAX_BYTES)
     }
 
     fn end_frame(
@@ -337,13 +385,6 @@ impl TextureLoader for AssetThumbnailTextureLoader {
     }
 
     fn byte_size(&self) -> usize {
-        self.cache
-            .lock()
-            .unwrap()
-            .pairs()
-            .iter()
-            .filter_map(|x| x.as_ref())
-            .map(|(_, v)| v.byte_size())
-            .sum()
+        self.cache.lock().unwrap().total_bytes()
     }
 }
@@ -23,6 +23,7 @@ use crate::inspector_system::InspectorRegistry;
 pub use crate::ui::components::inspector_system;
 use hydrate_model::pipeline::{
     AssetEngine, AssetPluginRegistryBuilders, HydrateProjectConfiguration, ImportJobToQueue,
+    PipelineResult,
 };
 
 pub struct Editor {
@@ -40,6 +41,14 @@ impl Editor {
         self.db_state.editor_model.schema_set()
     }
 
+    // Queues a build of everything once the editor is running, see AssetEngine::rebuild_all()
+    pub fn rebuild_all(
+        &mut self,
+        clear_cache: bool,
+    ) -> PipelineResult<()> {
+        self.asset_engine.rebuild_all(clear_cache)
+    }
+
     pub fn new(
         project_configuration: HydrateProjectConfiguration,
         asset_plugin_registry: AssetPluginRegistryBuilders,
@@ -276,6 +276,7 @@ pub fn draw_asset_gallery(
 
             true
         })
+        .map(|(asset_id, info)| (asset_id, info.as_ref()))
         .collect();
 
     all_assets.sort_by(|(_, lhs), (_, rhs)| lhs.asset_name().cmp(&rhs.asset_name()));
@@ -196,6 +196,24 @@ pub fn draw_asset_tree(
                     asset_tree_ui_state.selected_tree_node = None;
                 }
 
+                // Assets that couldn't be placed normally are shown at the root
+                let warnings = editor_model_ui_state.asset_path_cache.warnings();
+                if !warnings.is_empty() {
+                    let warning_text = warnings
+                        .iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 153, 0),
+                        format!(
+                            "{} assets couldn't be placed in the hierarchy",
+                            warnings.len()
+                        ),
+                    )
+                    .on_hover_text(warning_text);
+                }
+
                 for (_, tree_node) in &editor_model_ui_state.location_tree.root_nodes {
                     draw_tree_node(
                         ui,
@@ -0,0 +1,100 @@
+use crate::action_queue::{UIAction, UIActionQueueSender};
+use crate::ui_state::EditorModelUiState;
+use hydrate_base::AssetId;
+use hydrate_model::pipeline::{Diagnostic, DiagnosticSink};
+use hydrate_model::EditorModel;
+use std::sync::{Arc, Mutex};
+
+// Registered with the AssetEngine, appends everything it receives to the console
+struct DiagnosticConsoleSink {
+    diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+impl DiagnosticSink for DiagnosticConsoleSink {
+    fn on_diagnostic(
+        &mut self,
+        diagnostic: Diagnostic,
+    ) {
+        self.diagnostics.lock().unwrap().push(diagnostic);
+    }
+}
+
+#[derive(Default)]
+pub struct DiagnosticConsoleUiState {
+    diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
+    only_selected_asset: bool,
+}
+
+impl DiagnosticConsoleUiState {
+    pub fn create_sink(&self) -> Box<dyn DiagnosticSink> {
+        Box::new(DiagnosticConsoleSink {
+            diagnostics: self.diagnostics.clone(),
+        })
+    }
+}
+
+pub fn draw_diagnostic_console(
+    ui: &mut egui::Ui,
+    editor_model: &EditorModel,
+    editor_model_ui_state: &EditorModelUiState,
+    diagnostic_console_ui_state: &mut DiagnosticConsoleUiState,
+    action_queue_sender: &UIActionQueueSender,
+    selected_asset: Option<AssetId>,
+) {
+    ui.horizontal(|ui| {
+        ui.checkbox(
+            &mut diagnostic_console_ui_state.only_selected_asset,
+            "Only selected asset",
+        );
+        if ui.button("Clear").clicked() {
+            diagnostic_console_ui_state
+                .diagnostics
+                .lock()
+                .unwrap()
+                .clear();
+        }
+    });
+
+    ui.separator();
+
+    let asset_filter = if diagnostic_console_ui_state.only_selected_asset {
+        Some(selected_asset)
+    } else {
+        None
+    };
+
+    let diagnostics = diagnostic_console_ui_state.diagnostics.lock().unwrap();
+    egui::ScrollArea::both()
+        .auto_shrink([false, false])
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 2.0);
+            for diagnostic in diagnostics.iter() {
+                if let Some(asset_filter) = asset_filter {
+                    if asset_filter.is_none() || diagnostic.asset_id != asset_filter {
+                        continue;
+                    }
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(format!("{:?}", diagnostic.level));
+                    if let Some(asset_id) = diagnostic.asset_id {
+                        if ui.button(">>").clicked() {
+                            action_queue_sender
+                                .queue_action(UIAction::ShowAssetInAssetGallery(asset_id));
+                        }
+
+                        ui.label(editor_model.asset_display_name_long(
+                            asset_id,
+                            &editor_model_ui_state.asset_path_cache,
+                        ));
+                    } else if let Some(path) = &diagnostic.path {
+                        ui.label(path.to_string_lossy());
+                    } else if let Some(job_id) = diagnostic.job_id {
+                        ui.label(format!("Job {}", job_id.as_uuid()));
+                    }
+                    ui.label(&diagnostic.message);
+                });
+            }
+        });
+}
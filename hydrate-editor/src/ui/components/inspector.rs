@@ -2,6 +2,7 @@ use crate::action_queue::{UIAction, UIActionQueueSender};
 use crate::image_loader::ThumbnailImageLoader;
 use crate::ui::modals::{MoveAssetsModal, NewAssetModal};
 use crate::ui_state::EditorModelUiState;
+use hydrate_model::pipeline::AssetEngine;
 use hydrate_model::{
     AssetId, EditorModel, HashSet, PropertyPath, Schema, SchemaDefRecordFieldMarkup,
 };
@@ -24,6 +25,7 @@ pub fn draw_inspector(
     primary_asset_id_unpinned: Option<AssetId>,
     inspector_registry: &InspectorRegistry,
     thumbnail_image_loader: &ThumbnailImageLoader,
+    asset_engine: &AssetEngine,
 ) {
     egui::ScrollArea::vertical()
         .max_width(f32::INFINITY)
@@ -252,6 +254,40 @@ pub fn draw_inspector(
                         ui.label(format!("Prototype: {}", prototype_display_name));
                     });
                 }
+
+                //
+                // Build settings
+                //
+                if let Some(build_settings) = edit_context.build_settings(primary_asset_id) {
+                    ui.horizontal(|ui| {
+                        if ui.button(">>").clicked() {
+                            action_sender.queue_action(UIAction::ShowAssetInAssetGallery(build_settings));
+                        }
+
+                        let build_settings_display_name =
+                            editor_model.asset_display_name_long(build_settings, &editor_model_ui_state.asset_path_cache);
+
+                        ui.label(format!("Build Settings: {}", build_settings_display_name));
+
+                        if ui.add_enabled(!are_any_generated, egui::Button::new("Clear")).clicked() {
+                            action_sender.queue_action(UIAction::SetBuildSettings(primary_asset_id, None));
+                        }
+                    });
+                } else if let Some(build_settings_type) = asset_engine
+                    .builder_for_asset(primary_asset_schema.fingerprint())
+                    .and_then(|builder| builder.build_settings_type())
+                {
+                    if let Some(build_settings_schema) = edit_context
+                        .schema_set()
+                        .find_named_type(build_settings_type)
+                        .ok()
+                        .and_then(|x| x.try_as_record())
+                    {
+                        if ui.add_enabled(!are_any_generated, egui::Button::new("Create Build Settings")).clicked() {
+                            action_sender.queue_action(UIAction::CreateBuildSettings(primary_asset_id, build_settings_schema.clone()));
+                        }
+                    }
+                }
             }
 
 
@@ -369,7 +369,7 @@ impl RecordInspector for DefaultRecordInspector {
         let categories: HashSet<String> = record
             .fields()
             .iter()
-            .filter(|x| x.markup().category.is_some())
+            .filter(|x| x.markup().category.is_some() && !x.markup().hidden)
             .map(|x| x.markup().category.clone().unwrap())
             .collect();
 
@@ -403,13 +403,14 @@ impl RecordInspector for DefaultRecordInspector {
             }
             if visible {
                 for field in record.fields() {
-                    if field.markup().category == category {
+                    if field.markup().category == category && !field.markup().hidden {
                         let field_path = ctx.property_path.push(field.name());
                         let ctx = InspectorContext {
                             property_default_display_name: field.name(),
                             property_path: &field_path,
                             schema: field.field_schema(),
                             field_markup: field.markup(),
+                            read_only: ctx.read_only || field.markup().readonly,
                             ..ctx
                         };
                         draw_inspector_rows(table_body, ctx, indent_level);
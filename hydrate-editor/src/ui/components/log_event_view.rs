@@ -12,10 +12,10 @@ pub struct LogEventViewUiState {
 
 pub fn draw_import_log(
     ui: &mut egui::Ui,
-    _editor_model: &EditorModel,
-    _editor_model_ui_state: &EditorModelUiState,
+    editor_model: &EditorModel,
+    editor_model_ui_state: &EditorModelUiState,
     _log_event_view_ui_state: &mut LogEventViewUiState,
-    _action_queue_sender: &UIActionQueueSender,
+    action_queue_sender: &UIActionQueueSender,
     import_log_data: &ImportLogData,
 ) {
     ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 2.0);
@@ -56,7 +56,21 @@ pub fn draw_import_log(
                             ui.label(format!("{:?}", log_event.level));
                         });
                         row.col(|ui| {
-                            ui.label(log_event.path.to_string_lossy());
+                            if let Some(asset_id) = log_event.asset_id {
+                                let long_name = editor_model.asset_display_name_long(
+                                    asset_id,
+                                    &editor_model_ui_state.asset_path_cache,
+                                );
+
+                                if ui.button(">>").clicked() {
+                                    action_queue_sender
+                                        .queue_action(UIAction::ShowAssetInAssetGallery(asset_id));
+                                }
+
+                                ui.label(long_name);
+                            } else {
+                                ui.label(log_event.path.to_string_lossy());
+                            }
                         });
                         row.col(|ui| {
                             ui.label(&log_event.message);
@@ -20,3 +20,6 @@ pub use location_selector::draw_location_selector;
 
 mod log_event_view;
 pub use log_event_view::{draw_log_event_view, LogEventViewUiState};
+
+mod diagnostic_console;
+pub use diagnostic_console::{draw_diagnostic_console, DiagnosticConsoleUiState};
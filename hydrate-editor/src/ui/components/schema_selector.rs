@@ -45,8 +45,7 @@ pub fn schema_record_selector(
     schema_set: &SchemaSet,
 ) -> egui::InnerResponse<Option<SchemaRecord>> {
     let search: Vec<_> = schema_set
-        .schemas()
-        .values()
+        .iter_sorted()
         .filter_map(|x| x.try_as_record())
         .filter(|x| {
             x.markup().tags.contains("asset") && !x.markup().tags.contains("has_import_data")
@@ -60,7 +59,8 @@ pub fn schema_record_selector(
 
     let mut find_by_name = None;
     let mut find_by_display_name = None;
-    for (_, named_type) in schema_set.schemas() {
+    // Sorted so that the same type is found every time if several match
+    for named_type in schema_set.iter_sorted() {
         if let Some(record_type) = named_type.try_as_record() {
             if named_type.name().to_lowercase() == schema_name.to_lowercase() {
                 find_by_name = Some(record_type.clone());
@@ -40,7 +40,7 @@ fn confirm_lose_changes<F: Fn(&mut egui::Ui, &mut ModalActionControlFlow) -> ()>
                 pending_operation_info.push(PendingOperationInfo {
                     kind,
                     asset_id: *asset_id,
-                    asset_info: edit_context.assets().get(&asset_id),
+                    asset_info: edit_context.assets().get(&asset_id).map(|x| x.as_ref()),
                     path: &path,
                 });
             }
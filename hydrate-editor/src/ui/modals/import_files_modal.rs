@@ -2,50 +2,37 @@ use crate::modal_action::{
     default_modal_window, ModalAction, ModalActionControlFlow, ModalContext,
 };
 use crate::ui::components::draw_location_selector;
-use hydrate_model::pipeline::{ImportJobToQueue, ImporterRegistry};
+use hydrate_model::pipeline::{
+    scan_files_to_import, HydrateProjectConfiguration, ImportJobToQueue, ImporterRegistry,
+};
 use hydrate_model::{AssetLocation, HashSet};
 use std::path::PathBuf;
 
 pub struct ImportFilesModal {
     files_to_import: HashSet<PathBuf>,
+    // i.e. "12 files skipped (.psd, .txt)" if some files can't be imported
+    skipped_files_summary: Option<String>,
     selected_location: Option<AssetLocation>,
 }
 
 impl ImportFilesModal {
     pub fn new(
+        project_config: &HydrateProjectConfiguration,
         files_to_import: Vec<PathBuf>,
         importer_registry: &ImporterRegistry,
     ) -> Self {
-        let mut all_files_to_import = HashSet::default();
-        for file in &files_to_import {
-            // Recursively look for files
-            if file.is_dir() {
-                let walker = globwalk::GlobWalkerBuilder::from_patterns(file, &["**"])
-                    .file_type(globwalk::FileType::FILE)
-                    .build()
-                    .unwrap();
-
-                for file in walker {
-                    if let Ok(file) = file {
-                        let file = dunce::canonicalize(&file.path()).unwrap();
-                        if let Some(extension) = file.extension() {
-                            if !importer_registry
-                                .importers_for_file_extension(&*extension.to_string_lossy())
-                                .is_empty()
-                            {
-                                all_files_to_import.insert(file.to_path_buf());
-                                println!("import {:?}", file);
-                            }
-                        }
-                    }
-                }
-            } else {
-                all_files_to_import.insert(file.to_path_buf());
-            }
+        let scan = scan_files_to_import(project_config, &files_to_import, importer_registry);
+        for (extension, files) in &scan.skipped_files {
+            log::info!(
+                "Skipping {} files with extension {:?}, no importer handles them",
+                files.len(),
+                extension
+            );
         }
 
         ImportFilesModal {
-            files_to_import: all_files_to_import,
+            skipped_files_summary: scan.skipped_files_summary(),
+            files_to_import: scan.files_to_import,
             selected_location: None,
         }
     }
@@ -70,6 +57,10 @@ impl ModalAction for ImportFilesModal {
                     }
                 });
 
+            if let Some(skipped_files_summary) = &self.skipped_files_summary {
+                ui.label(skipped_files_summary);
+            }
+
             ui.separator();
             ui.label("Where to import the files");
 
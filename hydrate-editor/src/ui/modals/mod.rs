@@ -10,4 +10,7 @@ pub use move_modal::MoveAssetsModal;
 
 pub use new_asset_modal::NewAssetModal;
 
+mod restore_autosave_modal;
+pub use restore_autosave_modal::RestoreAutosaveModal;
+
 pub use confirm_lose_changes::{ConfirmQuitWithoutSaving, ConfirmRevertChanges};
@@ -0,0 +1,62 @@
+use crate::action_queue::UIAction;
+use crate::modal_action::{
+    default_modal_window, ModalAction, ModalActionControlFlow, ModalContext,
+};
+use hydrate_model::AutosaveRecovery;
+use std::time::SystemTime;
+
+// Shown on startup if the editor exited with unsaved changes that were autosaved
+pub struct RestoreAutosaveModal {
+    recovery: Option<AutosaveRecovery>,
+}
+
+impl RestoreAutosaveModal {
+    pub fn new(recovery: AutosaveRecovery) -> Self {
+        RestoreAutosaveModal {
+            recovery: Some(recovery),
+        }
+    }
+}
+
+impl ModalAction for RestoreAutosaveModal {
+    fn draw(
+        &mut self,
+        context: ModalContext,
+    ) -> ModalActionControlFlow {
+        let mut control_flow = ModalActionControlFlow::Continue;
+        let action_queue = context.action_queue;
+        let recovery = &mut self.recovery;
+        default_modal_window("Restore Unsaved Changes?", context, |_context, ui| {
+            let Some(saved_at) = recovery.as_ref().map(|x| x.saved_at()) else {
+                control_flow = ModalActionControlFlow::End;
+                return;
+            };
+
+            let minutes_ago = SystemTime::now()
+                .duration_since(saved_at)
+                .unwrap_or_default()
+                .as_secs()
+                / 60;
+            ui.label(format!(
+                "The editor exited with unsaved changes. They were autosaved {} minutes ago.",
+                minutes_ago
+            ));
+            ui.label("Restored changes are not saved until you save them.");
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui.button("Restore").clicked() {
+                    action_queue.queue_action(UIAction::RestoreAutosave(recovery.take().unwrap()));
+                    control_flow = ModalActionControlFlow::End;
+                }
+
+                if ui.button("Discard").clicked() {
+                    action_queue.queue_action(UIAction::DiscardAutosave);
+                    control_flow = ModalActionControlFlow::End;
+                }
+            });
+        });
+
+        control_flow
+    }
+}
@@ -129,6 +129,16 @@ impl ArtifactStorageSet {
 // Implement distill's ArtifactStorage - an untyped trait that finds the artifact_type's storage and
 // forwards the call
 impl ArtifactStorage for ArtifactStorageSet {
+    fn has_storage(
+        &self,
+        artifact_type_id: &ArtifactTypeId,
+    ) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .data_to_artifact_type_uuid
+            .contains_key(artifact_type_id)
+    }
+
     fn load_artifact(
         &mut self,
         loader_info: &dyn LoaderInfoProvider,
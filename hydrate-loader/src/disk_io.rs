@@ -1,15 +1,16 @@
 use crate::loader::ArtifactData;
 use crate::loader::{
-    ArtifactMetadata, LoaderEvent, LoaderIO, ManifestBuildHash, RequestDataResult,
-    RequestMetadataResult,
+    ArtifactMetadata, LoaderEvent, LoaderIO, ManifestBuildHash, RequestDataRangeResult,
+    RequestDataResult, RequestMetadataResult,
 };
 use crate::storage::IndirectIdentifier;
 use crate::ArtifactTypeId;
 use crossbeam_channel::{Receiver, Sender};
 use hydrate_base::hashing::HashMap;
-use hydrate_base::{ArtifactId, ArtifactManifestData, DebugManifestFileJson};
+use hydrate_base::{ArtifactId, ArtifactManifestData, DebugManifestFileJson, SymbolTable};
 use hydrate_base::{LoadHandle, StringHash};
 use std::io::{BufRead, SeekFrom};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -29,6 +30,12 @@ struct DiskArtifactIORequestData {
     //subresource: Option<u32>,
 }
 
+struct DiskArtifactIORequestDataRange {
+    artifact_id: ArtifactId,
+    hash: u64,
+    range: Range<u64>,
+}
+
 struct DiskArtifactIORequestCheckNewToc {
     current_manifest_build_hash: ManifestBuildHash,
 }
@@ -40,6 +47,7 @@ struct DiskArtifactIOResponseNewToc {
 enum DiskArtifactIORequest {
     Metadata(DiskArtifactIORequestMetadata),
     Data(DiskArtifactIORequestData),
+    DataRange(DiskArtifactIORequestDataRange),
     CheckNewToc(DiskArtifactIORequestCheckNewToc),
 }
 
@@ -155,6 +163,41 @@ impl DiskArtifactIOWorkerThread {
                                     })
                                 })).unwrap();
 
+                                active_request_count.fetch_sub(1, Ordering::Release);
+                            }
+                            DiskArtifactIORequest::DataRange(msg) => {
+                                profiling::scope!("DiskartifactIORequest::DataRange");
+                                log::trace!("Start read {:?} {:?}", msg.artifact_id, msg.range);
+
+                                use std::io::Read;
+                                use std::io::Seek;
+
+                                // Skip the header and seek straight to the requested part of the payload
+                                let path = hydrate_base::uuid_path::uuid_and_hash_to_path(&root_path, msg.artifact_id.as_uuid(), msg.hash, "bf");
+                                let read_range = || -> std::io::Result<Vec<u8>> {
+                                    let mut reader = std::fs::File::open(path)?;
+                                    let mut length_bytes = [0u8; 8];
+                                    reader.read_exact(&mut length_bytes)?;
+                                    let read_offset = u64::from_le_bytes(length_bytes)
+                                        .checked_add(length_bytes.len() as u64)
+                                        .and_then(|payload_offset| payload_offset.checked_add(msg.range.start))
+                                        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "range is outside the file"))?;
+                                    reader.seek(SeekFrom::Start(read_offset))?;
+                                    let mut data = Vec::default();
+                                    profiling::scope!("std::fs::File::read_to_end");
+                                    reader.take(msg.range.end - msg.range.start).read_to_end(&mut data)?;
+                                    Ok(data)
+                                };
+
+                                load_event_tx.send(LoaderEvent::DataRangeRequestComplete(RequestDataRangeResult {
+                                    artifact_id: msg.artifact_id,
+                                    hash: msg.hash,
+                                    range: msg.range.clone(),
+                                    result: read_range().map(|data| ArtifactData {
+                                        data
+                                    })
+                                })).unwrap();
+
                                 active_request_count.fetch_sub(1, Ordering::Release);
                             }
                         }
@@ -231,7 +274,7 @@ impl DiskArtifactIOThreadPool {
 
 pub struct BuildManifest {
     pub artifact_lookup: HashMap<ArtifactId, ArtifactManifestData>,
-    pub symbol_lookup: HashMap<u128, ArtifactId>,
+    pub symbol_table: SymbolTable,
 }
 
 impl BuildManifest {
@@ -253,7 +296,6 @@ impl BuildManifest {
             let buf_reader = std::io::BufReader::new(file);
 
             let mut artifact_lookup = HashMap::default();
-            let mut symbol_lookup = HashMap::default();
 
             for line in buf_reader.lines() {
                 let line_str = line.unwrap().to_string();
@@ -272,8 +314,6 @@ impl BuildManifest {
                 let symbol_hash_u128 = u128::from_str_radix(fragments[4], 16).unwrap();
 
                 let symbol_hash = if symbol_hash_u128 != 0 {
-                    let old = symbol_lookup.insert(symbol_hash_u128, artifact_id);
-                    assert!(old.is_none());
                     Some(StringHash::from_hash(symbol_hash_u128))
                 } else {
                     None
@@ -295,9 +335,30 @@ impl BuildManifest {
                 artifact_build_hashes.insert(artifact_id, build_hash);
             }
 
+            //
+            // Load the symbol table, this must exist and load correctly
+            //
+            let symbol_table = {
+                profiling::scope!("Load symbol table");
+                let file_name = format!("{:0>16x}.symbols", build_hash.0);
+                let file_path = manifest_dir_path.join(file_name);
+                let file = std::fs::File::open(file_path).unwrap();
+                SymbolTable::read(std::io::BufReader::new(file)).unwrap()
+            };
+
+            // Fill in symbol names for debugging
+            for (hash, entry) in symbol_table.iter() {
+                let manifest_entry = artifact_lookup.get_mut(&entry.artifact_id).unwrap();
+                assert_eq!(
+                    manifest_entry.symbol_hash.as_ref().map(|x| x.hash()),
+                    Some(hash)
+                );
+                manifest_entry.symbol_hash = Some(StringHash::from_runtime_str(&entry.symbol_name));
+            }
+
             BuildManifest {
                 artifact_lookup,
-                symbol_lookup,
+                symbol_table,
             }
         };
 
@@ -526,12 +587,17 @@ impl LoaderIO for DiskArtifactIO {
         indirect_identifier: &IndirectIdentifier,
     ) -> Option<&ArtifactManifestData> {
         let (artifact_id, artifact_type) = match indirect_identifier {
-            IndirectIdentifier::ArtifactId(artifact_id, artifact_type) => {
+            IndirectIdentifier::ArtifactId(artifact_id, artifact_type)
+            | IndirectIdentifier::ShallowArtifactId(artifact_id, artifact_type) => {
                 (*artifact_id, *artifact_type)
             }
             IndirectIdentifier::SymbolWithType(symbol_name, artifact_type) => {
-                let artifact_id = self.manifest.symbol_lookup.get(&symbol_name.hash())?;
-                (*artifact_id, *artifact_type)
+                let artifact_id = self.manifest.symbol_table.resolve(symbol_name)?;
+                (artifact_id, *artifact_type)
+            }
+            IndirectIdentifier::CheckedArtifactId(artifact_id, _) => {
+                // The loader verifies the type once metadata is available
+                return self.manifest.artifact_lookup.get(artifact_id);
             }
         };
 
@@ -609,4 +675,27 @@ impl LoaderIO for DiskArtifactIO {
                 //subresource,
             }));
     }
+
+    fn request_data_range(
+        &self,
+        build_hash: ManifestBuildHash,
+        artifact_id: ArtifactId,
+        hash: u64,
+        range: Range<u64>,
+    ) -> bool {
+        log::debug!("request_data_range {:?} {:?}", artifact_id, range);
+        assert_eq!(self.build_hash, build_hash);
+
+        self.thread_pool
+            .as_ref()
+            .unwrap()
+            .add_request(DiskArtifactIORequest::DataRange(
+                DiskArtifactIORequestDataRange {
+                    artifact_id,
+                    hash,
+                    range,
+                },
+            ));
+        true
+    }
 }
@@ -0,0 +1,302 @@
+use hydrate_base::handle::{
+    ArtifactHandle, GenericHandle, LoadError, LoadState, LoadStateProvider,
+};
+use hydrate_base::hashing::HashMap;
+use hydrate_base::LoadHandle;
+use std::collections::hash_map::Entry;
+
+// How far along the artifacts in a HandleGroup are
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct HandleGroupProgress {
+    // Handles in the group, including null handles
+    pub total_count: usize,
+    // Handles that are loaded or null (null handles refer to nothing, so there is nothing to wait for)
+    pub loaded_count: usize,
+    // Handles that failed to load
+    pub error_count: usize,
+}
+
+impl HandleGroupProgress {
+    // Returns 1.0 if the group is empty
+    pub fn fraction_loaded(&self) -> f32 {
+        if self.total_count == 0 {
+            1.0
+        } else {
+            self.loaded_count as f32 / self.total_count as f32
+        }
+    }
+}
+
+/// Holds strong handles to a group of artifacts that are loaded and released together, like
+/// everything needed for a level or a character. Handles of any artifact type can be added. When
+/// the group is cleared or dropped, all of its strong refs are dropped at once, allowing the loader
+/// to free anything that is not referenced elsewhere.
+#[derive(Default)]
+pub struct HandleGroup {
+    // Keyed by load handle so the same artifact is only held once
+    handles: HashMap<LoadHandle, GenericHandle>,
+}
+
+impl HandleGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    pub fn contains(
+        &self,
+        handle: &impl ArtifactHandle,
+    ) -> bool {
+        self.handles.contains_key(&handle.load_handle())
+    }
+
+    pub fn handles(&self) -> impl Iterator<Item = &GenericHandle> {
+        self.handles.values()
+    }
+
+    // Accepts Handle<T> of any T or a GenericHandle. Returns false if the group already held a
+    // handle for the same load, in which case the passed handle is dropped.
+    pub fn add(
+        &mut self,
+        handle: impl Into<GenericHandle>,
+    ) -> bool {
+        let handle = handle.into();
+        let load_handle = handle.load_handle();
+        match self.handles.entry(load_handle) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(handle);
+                true
+            }
+        }
+    }
+
+    // Drops the group's strong ref for the given handle. Returns false if it was not in the group.
+    pub fn remove(
+        &mut self,
+        handle: &impl ArtifactHandle,
+    ) -> bool {
+        self.handles.remove(&handle.load_handle()).is_some()
+    }
+
+    // Drops the group's strong refs to all its handles
+    pub fn clear(&mut self) {
+        self.handles.clear();
+    }
+
+    pub fn progress<T: LoadStateProvider>(
+        &self,
+        loader: &T,
+    ) -> HandleGroupProgress {
+        let mut progress = HandleGroupProgress {
+            total_count: self.handles.len(),
+            ..Default::default()
+        };
+
+        for handle in self.handles.values() {
+            match handle.load_state(loader) {
+                LoadState::Loaded | LoadState::Null => progress.loaded_count += 1,
+                LoadState::Error(_) => progress.error_count += 1,
+                _ => {}
+            }
+        }
+
+        progress
+    }
+
+    // Returns the first error if any handle failed to load. Otherwise returns the state of the
+    // least-progressed handle, so the group is only Loaded once every handle is. Null handles are
+    // ignored, and an empty group is Loaded.
+    pub fn load_state<T: LoadStateProvider>(
+        &self,
+        loader: &T,
+    ) -> LoadState {
+        let mut group_load_state = LoadState::Loaded;
+        let mut first_error: Option<LoadError> = None;
+        for handle in self.handles.values() {
+            let load_state = handle.load_state(loader);
+            match load_state {
+                LoadState::Error(error) => {
+                    first_error.get_or_insert(error);
+                }
+                LoadState::Null => {}
+                _ => {
+                    if load_state_progress(load_state) < load_state_progress(group_load_state) {
+                        group_load_state = load_state;
+                    }
+                }
+            }
+        }
+
+        if let Some(error) = first_error {
+            LoadState::Error(error)
+        } else {
+            group_load_state
+        }
+    }
+}
+
+// Orders the states an artifact moves through while loading
+fn load_state_progress(load_state: LoadState) -> u32 {
+    match load_state {
+        LoadState::Unloaded => 0,
+        LoadState::WaitingForMetadata => 1,
+        LoadState::WaitingForDependencies => 2,
+        LoadState::WaitingForData => 3,
+        LoadState::Loading => 4,
+        LoadState::Loaded | LoadState::Null | LoadState::Error(_) => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::Loader;
+    use crate::storage::{ArtifactLoadOp, ArtifactStorage, IndirectIdentifier};
+    use crate::test_loader_io::TestLoaderIO;
+    use crate::{process_ref_ops, ArtifactTypeId};
+    use hydrate_base::handle::{Handle, LoaderInfoProvider};
+    use hydrate_base::hashing::HashSet;
+    use hydrate_base::{ArtifactId, ArtifactManifestData};
+
+    // Completes every load immediately and records what is freed
+    #[derive(Default)]
+    struct TestArtifactStorage {
+        loaded: HashSet<LoadHandle>,
+        freed: HashSet<LoadHandle>,
+    }
+
+    impl ArtifactStorage for TestArtifactStorage {
+        fn load_artifact(
+            &mut self,
+            _loader_info: &dyn LoaderInfoProvider,
+            _artifact_type_id: &ArtifactTypeId,
+            _artifact_id: ArtifactId,
+            _data: Vec<u8>,
+            load_handle: LoadHandle,
+            load_op: ArtifactLoadOp,
+        ) -> Result<(), Box<dyn std::error::Error + Send + 'static>> {
+            self.loaded.insert(load_handle);
+            load_op.complete();
+            Ok(())
+        }
+
+        fn commit_artifact(
+            &mut self,
+            _artifact_type: ArtifactTypeId,
+            _load_handle: LoadHandle,
+        ) {
+        }
+
+        fn free_artifact(
+            &mut self,
+            _artifact_type_id: ArtifactTypeId,
+            load_handle: LoadHandle,
+        ) {
+            self.loaded.remove(&load_handle);
+            self.freed.insert(load_handle);
+        }
+    }
+
+    #[test]
+    fn dropping_group_frees_all_artifacts() {
+        let mesh_type = ArtifactTypeId::from_u128(1);
+        let texture_type = ArtifactTypeId::from_u128(2);
+        let artifacts = [
+            (ArtifactId::from_u128(10), mesh_type),
+            (ArtifactId::from_u128(11), texture_type),
+            (ArtifactId::from_u128(12), texture_type),
+        ];
+
+        let mut manifest = HashMap::default();
+        for (artifact_id, artifact_type) in artifacts {
+            manifest.insert(
+                artifact_id,
+                ArtifactManifestData {
+                    artifact_id,
+                    simple_build_hash: 4,
+                    combined_build_hash: 5,
+                    symbol_hash: None,
+                    artifact_type: artifact_type.as_uuid(),
+                    debug_name: None,
+                },
+            );
+        }
+
+        let (events_tx, events_rx) = crossbeam_channel::unbounded();
+        let loader_io = TestLoaderIO::new(manifest, events_tx.clone());
+        let loader = Loader::new(Box::new(loader_io), events_tx, events_rx);
+        let (ref_op_tx, ref_op_rx) = crossbeam_channel::unbounded();
+        let mut artifact_storage = TestArtifactStorage::default();
+
+        let load = |index: usize| {
+            let (artifact_id, artifact_type) = artifacts[index];
+            loader
+                .add_engine_ref_indirect(IndirectIdentifier::ArtifactId(artifact_id, artifact_type))
+        };
+
+        let mut group = HandleGroup::new();
+        // Handles of different artifact types, one of them type-erased already
+        let mesh = Handle::<Vec<f32>>::new(ref_op_tx.clone(), load(0));
+        let texture_a = Handle::<Vec<u8>>::new(ref_op_tx.clone(), load(1));
+        let texture_b = GenericHandle::new(ref_op_tx.clone(), load(2));
+        let resolved_load_handles = [
+            mesh.resolved_load_handle().clone(),
+            texture_a.resolved_load_handle().clone(),
+            texture_b.resolved_load_handle().clone(),
+        ];
+        assert!(group.add(mesh.clone()));
+        assert!(group.add(texture_a));
+        assert!(group.add(texture_b));
+        // The same artifact is only held once
+        assert!(!group.add(mesh.clone()));
+        assert_eq!(group.len(), 3);
+
+        assert_ne!(group.load_state(&loader), LoadState::Loaded);
+        for _ in 0..5 {
+            process_ref_ops(&loader, &ref_op_rx);
+            loader.update(&mut artifact_storage);
+        }
+
+        assert_eq!(group.load_state(&loader), LoadState::Loaded);
+        assert_eq!(
+            group.progress(&loader),
+            HandleGroupProgress {
+                total_count: 3,
+                loaded_count: 3,
+                error_count: 0,
+            }
+        );
+        assert_eq!(artifact_storage.loaded.len(), 3);
+
+        // Removing a handle that is also held outside the group does not free it
+        assert!(group.remove(&mesh));
+        assert!(!group.contains(&mesh));
+        for _ in 0..5 {
+            process_ref_ops(&loader, &ref_op_rx);
+            loader.update(&mut artifact_storage);
+        }
+        assert!(artifact_storage.freed.is_empty());
+        assert!(group.add(mesh));
+
+        // The group now holds the only strong refs, so dropping it frees everything
+        drop(group);
+        for _ in 0..5 {
+            process_ref_ops(&loader, &ref_op_rx);
+            loader.update(&mut artifact_storage);
+        }
+
+        assert!(artifact_storage.loaded.is_empty());
+        assert_eq!(artifact_storage.freed.len(), 3);
+        for resolved_load_handle in &resolved_load_handles {
+            assert_eq!(loader.load_state(resolved_load_handle), LoadState::Unloaded);
+        }
+    }
+}
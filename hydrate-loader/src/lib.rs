@@ -1,15 +1,21 @@
 pub mod artifact_storage;
 mod disk_io;
+mod handle_group;
 pub mod loader;
 pub mod storage;
+#[cfg(test)]
+mod test_loader_io;
 
 pub use crate::artifact_storage::{ArtifactStorageSet, DynArtifactLoader};
 use crate::disk_io::DiskArtifactIO;
-use crate::loader::Loader;
+pub use crate::handle_group::{HandleGroup, HandleGroupProgress};
+use crate::loader::{ArtifactMetadata, Loader, LoaderDebugSnapshot};
+pub use crate::loader::{ArtifactRangeBytes, ArtifactRangeHandle, VersionPin};
 use crossbeam_channel::{Receiver, Sender};
-use hydrate_base::handle::RefOp;
+use hydrate_base::handle::{ArtifactHandle, RefOp};
 use hydrate_base::{ArtifactId, StringHash};
 use std::path::PathBuf;
+use std::sync::Arc;
 use type_uuid::TypeUuid;
 
 mod artifact_type_id;
@@ -101,6 +107,46 @@ impl ArtifactManager {
         Handle::<T>::new(self.ref_op_tx.clone(), load_handle)
     }
 
+    // Like load_artifact, but rather than panicking if the artifact is some other type, the handle
+    // will end up in LoadState::Error(LoadError::TypeMismatch) and the data is never deserialized
+    pub fn load_artifact_checked<T: TypeUuid + 'static + Send>(
+        &self,
+        artifact_id: ArtifactId,
+    ) -> Handle<T> {
+        let data_type_uuid = self
+            .storage()
+            .artifact_to_data_type_uuid::<T>()
+            .expect("Called load_artifact_checked with unregistered asset type");
+        let load_handle =
+            self.loader
+                .add_engine_ref_indirect(IndirectIdentifier::CheckedArtifactId(
+                    artifact_id,
+                    data_type_uuid,
+                ));
+        Handle::<T>::new(self.ref_op_tx.clone(), load_handle)
+    }
+
+    // Like load_artifact, but only the artifact's own data is loaded. Its dependencies are not
+    // ref-counted or loaded, so this is only suitable for types that contain no handles (i.e. a raw
+    // payload). Deserializing a handle to a dependency that is not loaded by some other means will
+    // panic.
+    pub fn load_artifact_shallow<T: TypeUuid + 'static + Send>(
+        &self,
+        artifact_id: ArtifactId,
+    ) -> Handle<T> {
+        let data_type_uuid = self
+            .storage()
+            .artifact_to_data_type_uuid::<T>()
+            .expect("Called load_artifact_shallow with unregistered asset type");
+        let load_handle =
+            self.loader
+                .add_engine_ref_indirect(IndirectIdentifier::ShallowArtifactId(
+                    artifact_id,
+                    data_type_uuid,
+                ));
+        Handle::<T>::new(self.ref_op_tx.clone(), load_handle)
+    }
+
     pub fn load_artifact_symbol_name<T: TypeUuid + 'static + Send>(
         &self,
         symbol_name: &'static str,
@@ -123,6 +169,49 @@ impl ArtifactManager {
         Handle::<T>::new(self.ref_op_tx.clone(), load_handle)
     }
 
+    // Keeps the handle resolving to the version of the artifact it currently has, even if a newer
+    // version is hot-reloaded. The newest version becomes active when the pin is dropped.
+    pub fn pin_version<T>(
+        &self,
+        handle: &Handle<T>,
+    ) -> VersionPin {
+        self.loader.pin_version(handle.load_handle())
+    }
+
+    // Starts reading len bytes at offset of the artifact's payload without loading the artifact,
+    // i.e. to stream audio. The bytes are available from the returned handle once read, and are
+    // freed when it is dropped.
+    pub fn request_artifact_range(
+        &self,
+        artifact_id: ArtifactId,
+        offset: u64,
+        len: u64,
+    ) -> Result<ArtifactRangeHandle, String> {
+        let end = offset.checked_add(len).ok_or_else(|| {
+            format!(
+                "Range of {} bytes at offset {} of artifact {:?} is out of bounds",
+                len, offset, artifact_id
+            )
+        })?;
+        self.loader.request_artifact_range(artifact_id, offset..end)
+    }
+
+    // The type and dependencies of the artifact the handle refers to, without needing its typed
+    // data (i.e. to route an artifact to the right system by type). None until the metadata has
+    // been fetched.
+    pub fn artifact_metadata(
+        &self,
+        handle: &impl ArtifactHandle,
+    ) -> Option<Arc<ArtifactMetadata>> {
+        self.loader.artifact_metadata(handle.resolved_load_handle())
+    }
+
+    // Snapshot of the loader's internal state, for diagnosing loads that never complete. Display it
+    // to print a tree of load handles and what they are waiting on.
+    pub fn debug_dump(&self) -> LoaderDebugSnapshot {
+        self.loader.debug_dump()
+    }
+
     pub fn update(&mut self) {
         process_ref_ops(&self.loader, &self.ref_op_rx);
         self.loader.update(&mut self.artifact_storage);
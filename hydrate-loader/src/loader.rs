@@ -2,13 +2,14 @@ use crate::storage::{ArtifactLoadOp, ArtifactStorage, HandleOp, IndirectIdentifi
 use crate::ArtifactTypeId;
 use crossbeam_channel::{Receiver, Sender};
 use hydrate_base::handle::{
-    ArtifactRef, LoadState, LoadStateProvider, LoaderInfoProvider, ResolvedLoadHandle,
+    ArtifactRef, LoadError, LoadState, LoadStateProvider, LoaderInfoProvider, ResolvedLoadHandle,
 };
 use hydrate_base::hashing::{HashMap, HashSet};
 use hydrate_base::ArtifactId;
 use hydrate_base::{ArtifactManifestData, LoadHandle, StringHash};
 use std::fmt::Formatter;
 use std::hash::Hash;
+use std::ops::Range;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
 
@@ -70,6 +71,17 @@ pub struct RequestDataResult {
     pub result: std::io::Result<ArtifactData>,
 }
 
+// When IO completes a request for a byte range of an artifact's payload, it will send us a loader
+// event containing this. The data may be shorter than requested if the range extends past the end
+// of the payload.
+#[derive(Debug)]
+pub struct RequestDataRangeResult {
+    pub artifact_id: ArtifactId,
+    pub hash: u64,
+    pub range: Range<u64>,
+    pub result: std::io::Result<ArtifactData>,
+}
+
 // A hash of a particular data build. This encompasses everything that was in a single manifest.
 // If it changes, we need to check for artifacts that have changed, load them, and update indirect
 // handles to point at them. The LoaderIO will provide a new build hash to indicate this has occurred.
@@ -132,6 +144,19 @@ pub trait LoaderIO: Sync + Send {
         artifact_id: ArtifactId,
         hash: u64,
     );
+
+    // Load a byte range of the payload for an artifact without loading the artifact itself.
+    // This results in a RequestDataRangeResult being sent to the loader. Returns false if ranges
+    // are not supported, in which case the loader requests the whole payload instead.
+    fn request_data_range(
+        &self,
+        _build_hash: ManifestBuildHash,
+        _artifact_id: ArtifactId,
+        _hash: u64,
+        _range: Range<u64>,
+    ) -> bool {
+        false
+    }
 }
 
 //
@@ -149,8 +174,12 @@ pub enum LoaderEvent {
     DependenciesLoaded(LoadHandle),
     // Sent by LoaderIO when data request succeeds or fails
     DataRequestComplete(RequestDataResult),
+    // Sent by LoaderIO when a request for a range of an artifact's payload succeeds or fails
+    DataRangeRequestComplete(RequestDataRangeResult),
     // Sent by engine code to indicate success or failure at loading an artifact
     LoadResult(HandleOp),
+    // Sent by engine code to report the results of several loads that should be handled together
+    LoadResultBatch(Vec<HandleOp>),
     // Sent by LoaderIO when there are new versions available of the given artifacts.
     //ArtifactsUpdated(ManifestBuildHash),
 }
@@ -163,9 +192,17 @@ struct IndirectLoad {
     // The artifact that the identifier currently maps to. This could change if we reload data.
     //TODO: Update this on reload
     resolved_id_and_hash: Option<ArtifactIdAndHash>,
+    // Set if this is a checked load and the artifact the identifier maps to is some other type. The
+    // handle doesn't resolve to the artifact and reports LoadState::Error instead.
+    type_mismatch: bool,
     // The reference count of external handles (i.e. explicitly requested references, not references
     // due to other artifacts depending on this artifact) matching this indirect identifier
     external_ref_count_indirect: u32,
+    // Number of VersionPins on this handle. While pinned, reloads don't change what it resolves to.
+    pin_count: u32,
+    // A newer version that finished loading while pinned. It holds an internal ref so that it stays
+    // loaded until the pin is released.
+    deferred_load_handle: Option<LoadHandle>,
 }
 
 // Information about direct load handles that are currently loaded or were loaded at some point in
@@ -174,6 +211,8 @@ struct IndirectLoad {
 struct LoadHandleInfo {
     artifact_id: ArtifactId,
     artifact_type_id: ArtifactTypeId,
+    // If true, dependencies reported by metadata are ignored and data is requested immediately
+    shallow: bool,
 
     // Used to uniquely identify a version of this artifact.
     hash: u64,
@@ -206,6 +245,10 @@ struct LoadHandleInfo {
     // implicitly requires these artifacts to load fully before this artifact can finish loading.
     dependencies: Vec<LoadHandle>,
 
+    // Set once metadata has been fetched. For shallow loads this still lists all dependencies,
+    // even though they aren't loaded.
+    metadata: Option<Arc<ArtifactMetadata>>,
+
     // for debugging/convenience, not actually required
     symbol: Option<StringHash>,
     // for debugging/convenience, not actually required
@@ -222,10 +265,68 @@ struct ReloadAction {
     load_handles_to_reload: Vec<LoadHandle>,
 }
 
+// Byte ranges of an artifact's payload requested with Loader::request_artifact_range. These are
+// independent of loading the artifact itself.
+struct ArtifactRanges {
+    // The simple build hash of the payload the ranges were read from
+    hash: u64,
+    // Ranges held by ArtifactRangeHandles, along with how many handles hold each of them
+    requested: HashMap<Range<u64>, usize>,
+    // Ranges requested from IO that have not completed yet
+    pending: Vec<Range<u64>>,
+    // Set if IO can't read ranges and the whole payload was requested instead
+    pending_whole_payload: bool,
+    // Ranges that have been read, along with their bytes
+    resident: Vec<(Range<u64>, Arc<Vec<u8>>)>,
+    // Ranges that could not be read, along with the error
+    failed: Vec<(Range<u64>, String)>,
+    // Known once a read is cut short by the end of the payload or the whole payload was read
+    payload_len: Option<u64>,
+}
+
+impl ArtifactRanges {
+    fn new(hash: u64) -> Self {
+        ArtifactRanges {
+            hash,
+            requested: Default::default(),
+            pending: Default::default(),
+            pending_whole_payload: false,
+            resident: Default::default(),
+            failed: Default::default(),
+            payload_len: None,
+        }
+    }
+
+    // Reads are only kept while a handle holds a range they overlap
+    fn is_requested(
+        &self,
+        range: &Range<u64>,
+    ) -> bool {
+        self.requested
+            .keys()
+            .any(|x| x.start < range.end && range.start < x.end)
+    }
+
+    fn is_resident_or_pending(
+        &self,
+        range: &Range<u64>,
+    ) -> bool {
+        self.pending_whole_payload
+            || self
+                .pending
+                .iter()
+                .chain(self.resident.iter().map(|(x, _)| x))
+                .any(|x| x.start <= range.start && range.end <= x.end)
+    }
+}
+
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 struct ArtifactIdAndHash {
     id: ArtifactId,
     hash: u64,
+    // Shallow loads don't load dependencies, so they get a separate direct handle from full loads
+    // of the same artifact
+    shallow: bool,
 }
 
 struct LoaderInner {
@@ -254,6 +355,9 @@ struct LoaderInner {
     //TODO: The direct handles will be updated on a reload
     indirect_to_load: HashMap<IndirectIdentifier, Arc<ResolvedLoadHandle>>,
 
+    // Byte ranges of artifact payloads that have been requested, see Loader::request_artifact_range
+    artifact_ranges: HashMap<ArtifactId, ArtifactRanges>,
+
     // Update-specific state, mainly to do with reload detection/handling
     current_build_hash: ManifestBuildHash,
     current_reload_action: Option<ReloadAction>,
@@ -297,7 +401,11 @@ impl LoaderInner {
             let mut reload_complete = true;
             for &load_handle in &current_reload_action.load_handles_to_reload {
                 let load_handle_info = self.load_handle_infos.get(&load_handle).unwrap();
-                if load_handle_info.load_state != LoadState::Loaded {
+                // Failed loads will never finish, don't let them stall the reload
+                if !matches!(
+                    load_handle_info.load_state,
+                    LoadState::Loaded | LoadState::Error(_)
+                ) {
                     //log::debug!("Reloading waiting for {:?} {:?} {:?} to load, it's in state {:?}", load_handle, load_handle_info.artifact_id, load_handle_info.debug_name, load_handle_info.load_state);
                     //self.log_load_state_recursive(load_handle, 2);
                     reload_complete = false;
@@ -310,88 +418,22 @@ impl LoaderInner {
                 // New versions of artifacts for indirect handles have loaded, switch over to using them now
                 //
                 log::info!("All artifacts we need to reload are ready, updating indirect handles to point at new data");
-
-                // Update any indirect handles to point at new data
-                for (_, indirect_load) in &mut self.indirect_states {
-                    // Resolve the indirect handle under the new manifest
-                    let new_manifest_entry = self.loader_io.resolve_indirect(&indirect_load.id);
-                    let new_id_and_hash = new_manifest_entry.map(|x| ArtifactIdAndHash {
-                        id: x.artifact_id,
-                        hash: x.combined_build_hash,
-                    });
-                    let old_id_and_hash = indirect_load.resolved_id_and_hash;
-
-                    // If the resolved UUID changes, we need to point the indirect load at the new
-                    // version of the artifact (or None) and update ref counts accordingly
-                    let artifact_changed = old_id_and_hash != new_id_and_hash;
-                    if artifact_changed {
-                        // Get the new direct load handle (and add a ref count to it if it is valid)
-                        let new_load_handle_direct = if let Some(new_id_and_hash) = new_id_and_hash
-                        {
-                            let new_load_handle_direct =
-                                *self.artifact_id_to_handle.get(&new_id_and_hash).unwrap();
-                            let new_load_handle_info = self
-                                .load_handle_infos
-                                .get_mut(&new_load_handle_direct)
-                                .unwrap();
-
-                            // Add indirect references to the new load handle. Each indirect ref
-                            // count represents an external ref count and an internal ref count on the
-                            // direct load handle
-                            //TODO: Cleaner way to do this using self.add_engine_ref_by_handle_direct?
-                            new_load_handle_info.external_ref_count_direct +=
-                                indirect_load.external_ref_count_indirect;
-                            for _ in 0..indirect_load.external_ref_count_indirect {
-                                Self::add_internal_ref(
-                                    &self.events_tx,
-                                    new_load_handle_direct,
-                                    new_load_handle_info,
-                                );
-                            }
-                            new_load_handle_direct
-                        } else {
-                            // The artifact doesn't exist in the new manifest
-                            LoadHandle(0)
-                        };
-
-                        // Point the indirect load to the new version
-                        indirect_load.resolved_id_and_hash = new_id_and_hash;
-                        let old_load_handle_direct = self
-                            .indirect_to_load
-                            .get(&indirect_load.id)
-                            .unwrap()
-                            .direct_load_handle
-                            .swap(new_load_handle_direct.0, Ordering::Relaxed);
-                        log::info!(
-                            "Update indirect handle {:?} => {:?} -> {:?}",
-                            indirect_load.id,
-                            LoadHandle(old_load_handle_direct),
-                            new_load_handle_direct
-                        );
-
-                        // Drop ref count to old version, if it existed
-                        if let Some(old_id_and_hash) = &old_id_and_hash {
-                            let old_load_handle_direct =
-                                *self.artifact_id_to_handle.get(&old_id_and_hash).unwrap();
-                            let old_load_handle_info = self
-                                .load_handle_infos
-                                .get_mut(&old_load_handle_direct)
-                                .unwrap();
-
-                            // Remove the indirect references to the old load handle. Each indirect ref
-                            // count represents an external ref count and an internal ref count on the
-                            // direct load handle
-                            //TODO: Cleaner way to do this using self.remove_engine_ref_direct?
-                            old_load_handle_info.external_ref_count_direct -=
-                                indirect_load.external_ref_count_indirect;
-                            for _ in 0..indirect_load.external_ref_count_indirect {
-                                Self::remove_internal_ref(
-                                    &self.events_tx,
-                                    old_load_handle_direct,
-                                    old_load_handle_info,
-                                );
-                            }
-                        }
+                let current_reload_action = self.current_reload_action.take().unwrap();
+
+                // Update any indirect handles to point at new data. Pinned handles keep pointing at
+                // their current version until the pin is released.
+                let indirect_load_handles: Vec<_> = self.indirect_states.keys().copied().collect();
+                for indirect_load_handle in indirect_load_handles {
+                    if self
+                        .indirect_states
+                        .get(&indirect_load_handle)
+                        .unwrap()
+                        .pin_count
+                        > 0
+                    {
+                        self.defer_indirect_load_update(indirect_load_handle);
+                    } else {
+                        self.point_indirect_load_at_latest(indirect_load_handle);
                     }
                 }
 
@@ -402,12 +444,11 @@ impl LoaderInner {
                     Self::remove_internal_ref(&self.events_tx, load_handle, load_handle_info);
                 }
 
-                // indicate that the reload is complete
+                // indicate that the reload is complete (current_reload_action was taken above)
                 log::info!(
                     "Finished artifact reload, now on manifest build hash {:?}",
                     self.current_build_hash
                 );
-                self.current_reload_action = None;
             }
         } else if let Some(pending_build_hash) = self.loader_io.pending_build_hash() {
             //
@@ -431,11 +472,8 @@ impl LoaderInner {
             let mut artifacts_to_reload = HashSet::default();
             for (_, indirect_load) in &self.indirect_states {
                 // Resolve the indirect handle under the new manifest
-                let new_manifest_entry = self.loader_io.resolve_indirect(&indirect_load.id);
-                let new_id_and_hash = new_manifest_entry.map(|x| ArtifactIdAndHash {
-                    id: x.artifact_id,
-                    hash: x.combined_build_hash,
-                });
+                let (new_id_and_hash, _) =
+                    Self::resolve_indirect_load(&*self.loader_io, &indirect_load.id);
                 let old_id_and_hash = indirect_load.resolved_id_and_hash;
 
                 // If it has changed (and exists), add it to the list of artifacts that need to load
@@ -444,11 +482,8 @@ impl LoaderInner {
                 if artifact_changed {
                     log::info!("indirect load {:?} is in the new manifest but has changed, hash {:?} -> {:?}", indirect_load.id, old_id_and_hash, new_id_and_hash);
                     // Either add the artifact to the reload or unload list
-                    if let Some(new_manifest_entry) = &new_manifest_entry {
-                        artifacts_to_reload.insert(ArtifactIdAndHash {
-                            id: new_manifest_entry.artifact_id,
-                            hash: new_manifest_entry.combined_build_hash,
-                        });
+                    if let Some(new_id_and_hash) = new_id_and_hash {
+                        artifacts_to_reload.insert(new_id_and_hash);
                     }
                 }
             }
@@ -489,10 +524,197 @@ impl LoaderInner {
                 LoaderEvent::DataRequestComplete(result) => {
                     self.handle_request_data_result(result, artifact_storage)
                 }
+                LoaderEvent::DataRangeRequestComplete(result) => {
+                    self.handle_request_data_range_result(result)
+                }
                 LoaderEvent::LoadResult(load_result) => {
                     self.handle_load_result(load_result, artifact_storage)
                 }
+                LoaderEvent::LoadResultBatch(load_results) => {
+                    for load_result in load_results {
+                        self.handle_load_result(load_result, artifact_storage)
+                    }
+                }
+            }
+        }
+    }
+
+    // Points the indirect handle at the version of the artifact in the current manifest, moving its
+    // ref counts from the old version to the new one. The new version should already be loaded.
+    fn point_indirect_load_at_latest(
+        &mut self,
+        indirect_load_handle: LoadHandle,
+    ) {
+        let indirect_load = self.indirect_states.get_mut(&indirect_load_handle).unwrap();
+
+        // Resolve the indirect handle under the new manifest
+        let (new_id_and_hash, type_mismatch) =
+            Self::resolve_indirect_load(&*self.loader_io, &indirect_load.id);
+        indirect_load.type_mismatch = type_mismatch;
+        let old_id_and_hash = indirect_load.resolved_id_and_hash;
+
+        // If the resolved UUID changes, we need to point the indirect load at the new
+        // version of the artifact (or None) and update ref counts accordingly
+        let artifact_changed = old_id_and_hash != new_id_and_hash;
+        if artifact_changed {
+            // Get the new direct load handle (and add a ref count to it if it is valid)
+            let new_load_handle_direct = if let Some(new_id_and_hash) = new_id_and_hash {
+                let new_load_handle_direct =
+                    *self.artifact_id_to_handle.get(&new_id_and_hash).unwrap();
+                let new_load_handle_info = self
+                    .load_handle_infos
+                    .get_mut(&new_load_handle_direct)
+                    .unwrap();
+
+                // Add indirect references to the new load handle. Each indirect ref
+                // count represents an external ref count and an internal ref count on the
+                // direct load handle
+                //TODO: Cleaner way to do this using self.add_engine_ref_by_handle_direct?
+                new_load_handle_info.external_ref_count_direct +=
+                    indirect_load.external_ref_count_indirect;
+                for _ in 0..indirect_load.external_ref_count_indirect {
+                    Self::add_internal_ref(
+                        &self.events_tx,
+                        new_load_handle_direct,
+                        new_load_handle_info,
+                    );
+                }
+                new_load_handle_direct
+            } else {
+                // The artifact doesn't exist in the new manifest
+                LoadHandle(0)
+            };
+
+            // Point the indirect load to the new version
+            indirect_load.resolved_id_and_hash = new_id_and_hash;
+            let old_load_handle_direct = self
+                .indirect_to_load
+                .get(&indirect_load.id)
+                .unwrap()
+                .direct_load_handle
+                .swap(new_load_handle_direct.0, Ordering::Relaxed);
+            log::info!(
+                "Update indirect handle {:?} => {:?} -> {:?}",
+                indirect_load.id,
+                LoadHandle(old_load_handle_direct),
+                new_load_handle_direct
+            );
+
+            // Drop ref count to old version, if it existed
+            if let Some(old_id_and_hash) = &old_id_and_hash {
+                let old_load_handle_direct =
+                    *self.artifact_id_to_handle.get(&old_id_and_hash).unwrap();
+                let old_load_handle_info = self
+                    .load_handle_infos
+                    .get_mut(&old_load_handle_direct)
+                    .unwrap();
+
+                // Remove the indirect references to the old load handle. Each indirect ref
+                // count represents an external ref count and an internal ref count on the
+                // direct load handle
+                //TODO: Cleaner way to do this using self.remove_engine_ref_direct?
+                old_load_handle_info.external_ref_count_direct -=
+                    indirect_load.external_ref_count_indirect;
+                for _ in 0..indirect_load.external_ref_count_indirect {
+                    Self::remove_internal_ref(
+                        &self.events_tx,
+                        old_load_handle_direct,
+                        old_load_handle_info,
+                    );
+                }
+            }
+        }
+    }
+
+    // Called instead of point_indirect_load_at_latest() when a reload completes for a pinned
+    // indirect handle. Holds a ref on the new version so that it is still loaded when the pin is
+    // released.
+    fn defer_indirect_load_update(
+        &mut self,
+        indirect_load_handle: LoadHandle,
+    ) {
+        let indirect_load = self.indirect_states.get_mut(&indirect_load_handle).unwrap();
+        let (new_id_and_hash, _) = Self::resolve_indirect_load(&*self.loader_io, &indirect_load.id);
+
+        let mut new_deferred_load_handle = None;
+        if new_id_and_hash != indirect_load.resolved_id_and_hash {
+            if let Some(new_id_and_hash) = new_id_and_hash {
+                let new_load_handle_direct =
+                    *self.artifact_id_to_handle.get(&new_id_and_hash).unwrap();
+                let new_load_handle_info = self
+                    .load_handle_infos
+                    .get_mut(&new_load_handle_direct)
+                    .unwrap();
+                Self::add_internal_ref(
+                    &self.events_tx,
+                    new_load_handle_direct,
+                    new_load_handle_info,
+                );
+                new_deferred_load_handle = Some(new_load_handle_direct);
+            }
+        }
+
+        log::info!(
+            "Indirect handle {:?} is pinned, not updating it to {:?}",
+            indirect_load.id,
+            new_id_and_hash
+        );
+
+        // A reload may have completed previously while pinned, the version it deferred is stale now
+        let old_deferred_load_handle = std::mem::replace(
+            &mut indirect_load.deferred_load_handle,
+            new_deferred_load_handle,
+        );
+        if let Some(old_deferred_load_handle) = old_deferred_load_handle {
+            let old_load_handle_info = self
+                .load_handle_infos
+                .get_mut(&old_deferred_load_handle)
+                .unwrap();
+            Self::remove_internal_ref(
+                &self.events_tx,
+                old_deferred_load_handle,
+                old_load_handle_info,
+            );
+        }
+    }
+
+    fn pin_version(
+        &mut self,
+        indirect_load_handle: LoadHandle,
+    ) {
+        self.indirect_states
+            .get_mut(&indirect_load_handle)
+            .unwrap()
+            .pin_count += 1;
+    }
+
+    fn unpin_version(
+        &mut self,
+        indirect_load_handle: LoadHandle,
+    ) {
+        let indirect_load = self.indirect_states.get_mut(&indirect_load_handle).unwrap();
+        indirect_load.pin_count -= 1;
+        if indirect_load.pin_count > 0 {
+            return;
+        }
+
+        let deferred_load_handle = indirect_load.deferred_load_handle.take();
+        if let Some(deferred_load_handle) = deferred_load_handle {
+            // If a reload is in progress, the handle will be updated when it completes. Otherwise
+            // switch over to the version that arrived while pinned.
+            if self.current_reload_action.is_none() {
+                self.point_indirect_load_at_latest(indirect_load_handle);
             }
+
+            let deferred_load_handle_info = self
+                .load_handle_infos
+                .get_mut(&deferred_load_handle)
+                .unwrap();
+            Self::remove_internal_ref(
+                &self.events_tx,
+                deferred_load_handle,
+                deferred_load_handle_info,
+            );
         }
     }
 
@@ -614,17 +836,45 @@ impl LoaderInner {
         }
 
         // add references for other artifacts, either wait for dependents metadata or start loading
-        let metadata = result.result.unwrap();
+        let metadata = Arc::new(result.result.unwrap());
+
+        let load_state_info = self.load_handle_infos.get_mut(&result.load_handle).unwrap();
+        load_state_info.metadata = Some(metadata.clone());
+
+        // Shallow loads skip straight to loading data. Handles to dependencies within the artifact
+        // will not be resolved
+        let dependencies: &[ArtifactId] = if load_state_info.shallow {
+            &[]
+        } else {
+            &metadata.dependencies
+        };
+
+        // Fail the load rather than waiting forever on a dependency that can never be loaded
+        for dependency in dependencies {
+            if self.loader_io.manifest_entry(*dependency).is_none() {
+                log::error!(
+                    "Artifact {:?} {:?} depends on artifact {:?} which is not in the manifest",
+                    load_state_info.artifact_id,
+                    load_state_info.debug_name,
+                    dependency
+                );
+                load_state_info.artifact_type_id = metadata.artifact_type_id;
+                load_state_info.load_state =
+                    LoadState::Error(LoadError::MissingDependency(*dependency));
+                return;
+            }
+        }
 
         let mut blocking_dependency_count = 0;
 
         let mut dependency_load_handles = vec![];
-        for dependency in &metadata.dependencies {
+        for dependency in dependencies {
             let dependency_manifest_entry = self.loader_io.manifest_entry(*dependency).unwrap();
 
             let dependency_load_handle = self.get_or_insert_direct(ArtifactIdAndHash {
                 id: *dependency,
                 hash: dependency_manifest_entry.combined_build_hash,
+                shallow: false,
             });
             let dependency_load_handle_info = self
                 .load_handle_infos
@@ -730,6 +980,12 @@ impl LoaderInner {
         //     // version.load_state = LoadState::Unloaded
         // }
 
+        // The whole payload was requested to serve range requests because IO can't read ranges
+        if result.load_handle == LoadHandle(0) {
+            self.handle_whole_payload_for_ranges(result);
+            return;
+        }
+
         // Should always exist, we don't delete load handles
         let (load_op, load_state_info, data) = {
             let load_state_info = self.load_handle_infos.get(&result.load_handle).unwrap();
@@ -747,6 +1003,21 @@ impl LoaderInner {
 
             assert_eq!(load_state_info.load_state, LoadState::WaitingForData);
 
+            // Fail the load rather than handing the data to a storage that doesn't exist
+            if !artifact_storage.has_storage(&load_state_info.artifact_type_id) {
+                log::error!(
+                    "Artifact {:?} {:?} has type {:?} which has no storage, was add_storage() called for it?",
+                    load_state_info.artifact_id,
+                    load_state_info.debug_name,
+                    load_state_info.artifact_type_id
+                );
+                let load_state_info = self.load_handle_infos.get_mut(&result.load_handle).unwrap();
+                load_state_info.load_state = LoadState::Error(LoadError::UnregisteredType(
+                    load_state_info.artifact_type_id.as_uuid(),
+                ));
+                return;
+            }
+
             // start loading
             let data = result.result.unwrap();
 
@@ -779,6 +1050,223 @@ impl LoaderInner {
         load_state_info.load_state = LoadState::Loading;
     }
 
+    fn request_artifact_range(
+        &mut self,
+        artifact_id: ArtifactId,
+        range: Range<u64>,
+    ) -> Result<(), String> {
+        let Some(manifest_entry) = self.loader_io.manifest_entry(artifact_id) else {
+            return Err(format!(
+                "Requested a range of artifact {:?} which is not in the manifest",
+                artifact_id
+            ));
+        };
+        let hash = manifest_entry.simple_build_hash;
+
+        let artifact_ranges = self
+            .artifact_ranges
+            .entry(artifact_id)
+            .or_insert_with(|| ArtifactRanges::new(hash));
+        *artifact_ranges.requested.entry(range).or_default() += 1;
+
+        // Ranges read from a previous build of the artifact are stale, the held ranges are read again
+        if artifact_ranges.hash != hash {
+            let requested = std::mem::take(&mut artifact_ranges.requested);
+            *artifact_ranges = ArtifactRanges::new(hash);
+            artifact_ranges.requested = requested;
+        }
+
+        let mut ranges_to_read: Vec<_> = artifact_ranges
+            .requested
+            .keys()
+            .filter(|x| !x.is_empty())
+            .cloned()
+            .collect();
+        ranges_to_read.sort_by_key(|x| x.start);
+        for range in ranges_to_read {
+            if artifact_ranges.is_resident_or_pending(&range) {
+                continue;
+            }
+
+            if self.loader_io.request_data_range(
+                self.current_build_hash,
+                artifact_id,
+                hash,
+                range.clone(),
+            ) {
+                artifact_ranges.pending.push(range);
+            } else {
+                self.loader_io.request_data(
+                    self.current_build_hash,
+                    LoadHandle(0),
+                    artifact_id,
+                    hash,
+                );
+                artifact_ranges.pending_whole_payload = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn release_artifact_range(
+        &mut self,
+        artifact_id: ArtifactId,
+        range: &Range<u64>,
+    ) {
+        let Some(artifact_ranges) = self.artifact_ranges.get_mut(&artifact_id) else {
+            return;
+        };
+        let Some(handle_count) = artifact_ranges.requested.get_mut(range) else {
+            return;
+        };
+
+        *handle_count -= 1;
+        if *handle_count > 0 {
+            return;
+        }
+
+        artifact_ranges.requested.remove(range);
+        if artifact_ranges.requested.is_empty() {
+            // Reads still in flight are discarded when they complete
+            self.artifact_ranges.remove(&artifact_id);
+            return;
+        }
+
+        let resident = std::mem::take(&mut artifact_ranges.resident);
+        artifact_ranges.resident = resident
+            .into_iter()
+            .filter(|(x, _)| artifact_ranges.is_requested(x))
+            .collect();
+        let failed = std::mem::take(&mut artifact_ranges.failed);
+        artifact_ranges.failed = failed
+            .into_iter()
+            .filter(|(x, _)| artifact_ranges.requested.contains_key(x))
+            .collect();
+    }
+
+    fn handle_request_data_range_result(
+        &mut self,
+        result: RequestDataRangeResult,
+    ) {
+        let Some(artifact_ranges) = self.artifact_ranges.get_mut(&result.artifact_id) else {
+            // The ranges were released while the request was in flight
+            return;
+        };
+        if artifact_ranges.hash != result.hash {
+            return;
+        }
+
+        artifact_ranges.pending.retain(|x| *x != result.range);
+        if !artifact_ranges.is_requested(&result.range) {
+            return;
+        }
+
+        match result.result {
+            Ok(data) => {
+                let range = result.range.start..(result.range.start + data.data.len() as u64);
+                if range.end < result.range.end {
+                    artifact_ranges.payload_len = Some(range.end);
+                }
+                artifact_ranges.resident.push((range, Arc::new(data.data)));
+            }
+            Err(e) => {
+                log::error!(
+                    "Failed to read range {:?} of artifact {:?}: {}",
+                    result.range,
+                    result.artifact_id,
+                    e
+                );
+                artifact_ranges.failed.push((result.range, e.to_string()));
+            }
+        }
+    }
+
+    fn handle_whole_payload_for_ranges(
+        &mut self,
+        result: RequestDataResult,
+    ) {
+        let Some(artifact_ranges) = self.artifact_ranges.get_mut(&result.artifact_id) else {
+            return;
+        };
+        if !artifact_ranges.pending_whole_payload {
+            return;
+        }
+
+        artifact_ranges.pending_whole_payload = false;
+        match result.result {
+            Ok(data) => {
+                // The whole payload covers any other ranges
+                artifact_ranges.pending.clear();
+                artifact_ranges.resident.clear();
+                artifact_ranges.payload_len = Some(data.data.len() as u64);
+                artifact_ranges
+                    .resident
+                    .push((0..data.data.len() as u64, Arc::new(data.data)));
+            }
+            Err(e) => {
+                log::error!(
+                    "Failed to read payload of artifact {:?}: {}",
+                    result.artifact_id,
+                    e
+                );
+                let failed: Vec<_> = artifact_ranges
+                    .requested
+                    .keys()
+                    .map(|x| (x.clone(), e.to_string()))
+                    .collect();
+                artifact_ranges.failed = failed;
+            }
+        }
+    }
+
+    fn artifact_range_bytes(
+        &self,
+        artifact_id: ArtifactId,
+        range: &Range<u64>,
+    ) -> Result<Option<ArtifactRangeBytes>, String> {
+        let Some(artifact_ranges) = self.artifact_ranges.get(&artifact_id) else {
+            return Ok(None);
+        };
+
+        if let Some((_, error)) = artifact_ranges.failed.iter().find(|(x, _)| x == range) {
+            return Err(format!(
+                "Failed to read range {:?} of artifact {:?}: {}",
+                range, artifact_id, error
+            ));
+        }
+
+        if let Some(payload_len) = artifact_ranges.payload_len {
+            if range.end > payload_len {
+                return Err(format!(
+                    "Range {:?} extends past the end of the {} byte payload of artifact {:?}",
+                    range, payload_len, artifact_id
+                ));
+            }
+        }
+
+        if range.is_empty() {
+            return Ok(Some(ArtifactRangeBytes {
+                data: Default::default(),
+                range: 0..0,
+            }));
+        }
+
+        let Some((resident_range, data)) = artifact_ranges
+            .resident
+            .iter()
+            .find(|(x, _)| x.start <= range.start && range.end <= x.end)
+        else {
+            return Ok(None);
+        };
+        let begin = (range.start - resident_range.start) as usize;
+        let end = (range.end - resident_range.start) as usize;
+        Ok(Some(ArtifactRangeBytes {
+            data: data.clone(),
+            range: begin..end,
+        }))
+    }
+
     fn handle_load_result(
         &mut self,
         load_result: HandleOp,
@@ -851,6 +1339,37 @@ impl LoaderInner {
         }
     }
 
+    // Resolves an indirect identifier under the current manifest. A checked load of an artifact of
+    // some other type doesn't resolve, and the second value is true.
+    fn resolve_indirect_load(
+        loader_io: &dyn LoaderIO,
+        indirect_id: &IndirectIdentifier,
+    ) -> (Option<ArtifactIdAndHash>, bool) {
+        let Some(manifest_entry) = loader_io.resolve_indirect(indirect_id) else {
+            return (None, false);
+        };
+
+        if let Some(expected_artifact_type_id) = indirect_id.checked_artifact_type_id() {
+            if expected_artifact_type_id.as_uuid() != manifest_entry.artifact_type {
+                log::error!(
+                    "Artifact {:?} {:?} has type {:?} but was loaded as {:?}",
+                    manifest_entry.artifact_id,
+                    manifest_entry.debug_name,
+                    manifest_entry.artifact_type,
+                    expected_artifact_type_id
+                );
+                return (None, true);
+            }
+        }
+
+        let id_and_hash = ArtifactIdAndHash {
+            id: manifest_entry.artifact_id,
+            hash: manifest_entry.combined_build_hash,
+            shallow: indirect_id.is_shallow(),
+        };
+        (Some(id_and_hash), false)
+    }
+
     // This returns a ResolvedLoadHandle which is either already pointing at a direct load or will need
     // to be populated with a direct load
     fn get_or_insert_indirect(
@@ -871,12 +1390,13 @@ impl LoaderInner {
                     panic!("Couldn't find artifact {:?}", indirect_id);
                 }
 
-                let manifest_entry = resolved.unwrap();
+                let (resolved_id_and_hash, type_mismatch) =
+                    Self::resolve_indirect_load(&**loader_io, indirect_id);
                 log::debug!(
                     "Allocate indirect load handle {:?} for indirect id {:?} -> {:?}",
                     indirect_load_handle,
                     &indirect_id,
-                    manifest_entry.artifact_id
+                    resolved_id_and_hash
                 );
 
                 let resolved_load_handle =
@@ -886,11 +1406,11 @@ impl LoaderInner {
                     indirect_load_handle,
                     IndirectLoad {
                         id: indirect_id.clone(),
-                        resolved_id_and_hash: Some(ArtifactIdAndHash {
-                            id: manifest_entry.artifact_id,
-                            hash: manifest_entry.combined_build_hash,
-                        }),
+                        resolved_id_and_hash,
+                        type_mismatch,
                         external_ref_count_indirect: 0,
+                        pin_count: 0,
+                        deferred_load_handle: None,
                     },
                 );
                 resolved_load_handle
@@ -930,12 +1450,14 @@ impl LoaderInner {
                         external_ref_count_direct: 0,
                         load_state: LoadState::Unloaded,
                         artifact_type_id: ArtifactTypeId::default(),
+                        shallow: artifact_id_and_hash.shallow,
                         hash: artifact_id_and_hash.hash,
                         //replaced_by_newer_version: false,
                         internal_ref_count: 0,
                         blocking_dependency_count: 0,
                         blocked_loads: vec![],
                         dependencies: vec![],
+                        metadata: None,
                         symbol: manifest_entry.symbol_hash.clone(),
                         debug_name: manifest_entry.debug_name.clone(),
                     },
@@ -949,6 +1471,12 @@ impl LoaderInner {
         &mut self,
         id: IndirectIdentifier,
     ) -> Arc<ResolvedLoadHandle> {
+        // Nothing to load for a null ID. The null handle is already in a terminal state and ref
+        // counting it does nothing
+        if id.is_null() {
+            return ResolvedLoadHandle::new_null_handle();
+        }
+
         let indirect_load_handle = self.get_or_insert_indirect(&id);
 
         // It's possible this has already been resolved, but we still need to add a ref count.
@@ -1082,6 +1610,50 @@ impl LoaderInner {
             //path: load_info.versions.last().unwrap().
         })
     }
+
+    pub fn debug_dump(&self) -> LoaderDebugSnapshot {
+        let mut direct_handles: Vec<_> = self
+            .load_handle_infos
+            .iter()
+            .map(|(&load_handle, load_handle_info)| LoadHandleDebugInfo {
+                load_handle,
+                artifact_id: load_handle_info.artifact_id,
+                debug_name: load_handle_info.debug_name.clone(),
+                load_state: load_handle_info.load_state,
+                external_ref_count: load_handle_info.external_ref_count_direct,
+                internal_ref_count: load_handle_info.internal_ref_count,
+                dependencies: load_handle_info.dependencies.clone(),
+                waiting_on: load_handle_info
+                    .dependencies
+                    .iter()
+                    .copied()
+                    .filter(|x| {
+                        self.load_handle_infos.get(x).unwrap().load_state != LoadState::Loaded
+                    })
+                    .collect(),
+            })
+            .collect();
+        direct_handles.sort_by_key(|x| x.load_handle.0);
+
+        let mut indirect_handles: Vec<_> = self
+            .indirect_states
+            .iter()
+            .map(|(&load_handle, indirect_load)| IndirectLoadDebugInfo {
+                load_handle,
+                id: indirect_load.id.clone(),
+                external_ref_count: indirect_load.external_ref_count_indirect,
+                resolved_load_handle: indirect_load
+                    .resolved_id_and_hash
+                    .and_then(|x| self.artifact_id_to_handle.get(&x).copied()),
+            })
+            .collect();
+        indirect_handles.sort_by_key(|x| x.load_handle.0);
+
+        LoaderDebugSnapshot {
+            direct_handles,
+            indirect_handles,
+        }
+    }
 }
 
 /// Information about an artifact load operation.
@@ -1104,6 +1676,198 @@ pub struct LoadInfo {
     //pub asset_name: Option<String>,
 }
 
+/// State of a direct load handle at the time a [`LoaderDebugSnapshot`] was taken
+#[derive(Debug, Clone)]
+pub struct LoadHandleDebugInfo {
+    pub load_handle: LoadHandle,
+    pub artifact_id: ArtifactId,
+    pub debug_name: Option<Arc<String>>,
+    pub load_state: LoadState,
+    /// References from handles held by the game
+    pub external_ref_count: u32,
+    /// All references keeping the artifact loaded, including those from artifacts that depend on it
+    pub internal_ref_count: u32,
+    pub dependencies: Vec<LoadHandle>,
+    /// Dependencies that have not finished loading
+    pub waiting_on: Vec<LoadHandle>,
+}
+
+/// State of an indirect load handle at the time a [`LoaderDebugSnapshot`] was taken
+#[derive(Debug, Clone)]
+pub struct IndirectLoadDebugInfo {
+    pub load_handle: LoadHandle,
+    pub id: IndirectIdentifier,
+    pub external_ref_count: u32,
+    /// The direct load handle the identifier currently resolves to
+    pub resolved_load_handle: Option<LoadHandle>,
+}
+
+/// Snapshot of the loader's internal tables, for diagnosing loads that never complete. The
+/// `Display` impl prints each indirect handle followed by the tree of artifacts it loads.
+#[derive(Debug, Clone)]
+pub struct LoaderDebugSnapshot {
+    pub direct_handles: Vec<LoadHandleDebugInfo>,
+    pub indirect_handles: Vec<IndirectLoadDebugInfo>,
+}
+
+impl LoaderDebugSnapshot {
+    pub fn direct_handle(
+        &self,
+        load_handle: LoadHandle,
+    ) -> Option<&LoadHandleDebugInfo> {
+        self.direct_handles
+            .iter()
+            .find(|x| x.load_handle == load_handle)
+    }
+
+    fn fmt_direct_handle(
+        &self,
+        f: &mut Formatter<'_>,
+        load_handle: LoadHandle,
+        indent: usize,
+        ancestors: &mut Vec<LoadHandle>,
+        visited: &mut HashSet<LoadHandle>,
+    ) -> std::fmt::Result {
+        let Some(info) = self.direct_handle(load_handle) else {
+            return writeln!(f, "{:indent$}{:?} (unknown)", "", load_handle);
+        };
+
+        write!(
+            f,
+            "{:indent$}{:?} {:?} {:?} {:?} refs: {} external, {} internal",
+            "",
+            info.load_handle,
+            info.artifact_id,
+            info.debug_name.as_deref().map(|x| x.as_str()).unwrap_or(""),
+            info.load_state,
+            info.external_ref_count,
+            info.internal_ref_count,
+        )?;
+        if !info.waiting_on.is_empty() {
+            write!(f, " waiting on {:?}", info.waiting_on)?;
+        }
+
+        // Dependency cycles are possible, don't recurse forever
+        if ancestors.contains(&load_handle) {
+            return writeln!(f, " (cycle)");
+        }
+        writeln!(f)?;
+
+        visited.insert(load_handle);
+        ancestors.push(load_handle);
+        for &dependency in &info.dependencies {
+            self.fmt_direct_handle(f, dependency, indent + 2, ancestors, visited)?;
+        }
+        ancestors.pop();
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for LoaderDebugSnapshot {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        let mut visited = HashSet::default();
+        let mut ancestors = Vec::default();
+        for indirect in &self.indirect_handles {
+            writeln!(
+                f,
+                "{:?} {:?} refs: {}",
+                indirect.load_handle, indirect.id, indirect.external_ref_count
+            )?;
+            if let Some(resolved_load_handle) = indirect.resolved_load_handle {
+                self.fmt_direct_handle(f, resolved_load_handle, 2, &mut ancestors, &mut visited)?;
+            } else {
+                writeln!(f, "  (unresolved)")?;
+            }
+        }
+
+        // Direct handles not reachable from an indirect handle, i.e. previous versions of reloaded
+        // artifacts
+        for direct in &self.direct_handles {
+            if !visited.contains(&direct.load_handle) {
+                self.fmt_direct_handle(f, direct.load_handle, 0, &mut ancestors, &mut visited)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Returned by Loader::pin_version. Until this is dropped, the pinned handle keeps resolving to the
+// version of the artifact it resolved to when pinned, even if a reload brings in a newer one.
+pub struct VersionPin {
+    loader: Loader,
+    // Null if the handle can't change version, in which case there is nothing to release
+    indirect_load_handle: LoadHandle,
+}
+
+impl Drop for VersionPin {
+    fn drop(&mut self) {
+        if self.indirect_load_handle != LoadHandle(0) {
+            self.loader
+                .inner
+                .lock()
+                .unwrap()
+                .unpin_version(self.indirect_load_handle);
+        }
+    }
+}
+
+// Returned by Loader::request_artifact_range. The range's bytes are kept in memory until every
+// handle for the range is dropped.
+pub struct ArtifactRangeHandle {
+    loader: Loader,
+    artifact_id: ArtifactId,
+    range: Range<u64>,
+}
+
+impl ArtifactRangeHandle {
+    pub fn artifact_id(&self) -> ArtifactId {
+        self.artifact_id
+    }
+
+    pub fn range(&self) -> &Range<u64> {
+        &self.range
+    }
+
+    // The bytes once they have been read. Fails if the read failed or the range extends past the
+    // end of the payload.
+    pub fn bytes(&self) -> Result<Option<ArtifactRangeBytes>, String> {
+        self.loader
+            .inner
+            .lock()
+            .unwrap()
+            .artifact_range_bytes(self.artifact_id, &self.range)
+    }
+}
+
+impl Drop for ArtifactRangeHandle {
+    fn drop(&mut self) {
+        self.loader
+            .inner
+            .lock()
+            .unwrap()
+            .release_artifact_range(self.artifact_id, &self.range);
+    }
+}
+
+// Bytes read for an ArtifactRangeHandle. Refers to the buffer the bytes were read into instead of
+// copying them out of it.
+pub struct ArtifactRangeBytes {
+    data: Arc<Vec<u8>>,
+    range: Range<usize>,
+}
+
+impl std::ops::Deref for ArtifactRangeBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.data[self.range.clone()]
+    }
+}
+
 //
 // The Loader acts as a semi-public interface for LoaderInner.
 //
@@ -1130,6 +1894,7 @@ impl Loader {
             events_rx,
             indirect_states: Default::default(),
             indirect_to_load: Default::default(),
+            artifact_ranges: Default::default(),
             current_build_hash: build_hash,
             current_reload_action: None,
         };
@@ -1157,7 +1922,10 @@ impl Loader {
         &self,
         load_handle: LoadHandle,
     ) -> LoadHandle {
-        if load_handle.is_indirect() {
+        if load_handle == LoadHandle(0) {
+            // Null handles aren't ref counted
+            load_handle
+        } else if load_handle.is_indirect() {
             self.inner
                 .lock()
                 .unwrap()
@@ -1175,7 +1943,9 @@ impl Loader {
         &self,
         load_handle: LoadHandle,
     ) {
-        if load_handle.is_indirect() {
+        if load_handle == LoadHandle(0) {
+            // Null handles aren't ref counted
+        } else if load_handle.is_indirect() {
             self.inner
                 .lock()
                 .unwrap()
@@ -1188,6 +1958,61 @@ impl Loader {
         }
     }
 
+    // Keeps the handle resolving to the version of the artifact it currently resolves to until the
+    // returned pin is dropped. A newer version that arrives while pinned is loaded in the background
+    // and becomes active once all pins on the handle are dropped. Only indirect handles can change
+    // version, pinning a direct or null handle does nothing.
+    pub fn pin_version(
+        &self,
+        load_handle: LoadHandle,
+    ) -> VersionPin {
+        let indirect_load_handle = if load_handle.is_indirect() {
+            self.inner.lock().unwrap().pin_version(load_handle);
+            load_handle
+        } else {
+            LoadHandle(0)
+        };
+
+        VersionPin {
+            loader: self.clone(),
+            indirect_load_handle,
+        }
+    }
+
+    // Starts reading a byte range of an artifact's payload, i.e. to stream audio. The artifact
+    // itself is not loaded. Once the read completes, the bytes are available from the returned
+    // handle. They are freed when all handles for the range are dropped.
+    pub fn request_artifact_range(
+        &self,
+        artifact_id: ArtifactId,
+        range: Range<u64>,
+    ) -> Result<ArtifactRangeHandle, String> {
+        self.inner
+            .lock()
+            .unwrap()
+            .request_artifact_range(artifact_id, range.clone())?;
+
+        Ok(ArtifactRangeHandle {
+            loader: self.clone(),
+            artifact_id,
+            range,
+        })
+    }
+
+    // Ranges of the artifact's payload that have been read and are held in memory
+    pub fn resident_artifact_ranges(
+        &self,
+        artifact_id: ArtifactId,
+    ) -> Vec<Range<u64>> {
+        self.inner
+            .lock()
+            .unwrap()
+            .artifact_ranges
+            .get(&artifact_id)
+            .map(|x| x.resident.iter().map(|(range, _)| range.clone()).collect())
+            .unwrap_or_default()
+    }
+
     /// Returns handles to all active artifact loads.
     pub fn get_active_loads(&self) -> Vec<LoadHandle> {
         let mut loading_handles = Vec::default();
@@ -1206,6 +2031,31 @@ impl Loader {
         self.inner.lock().unwrap().get_load_info(handle)
     }
 
+    // The metadata of the artifact the handle currently resolves to. None for null handles and
+    // until the metadata has been fetched.
+    pub fn artifact_metadata(
+        &self,
+        load_handle: &Arc<ResolvedLoadHandle>,
+    ) -> Option<Arc<ArtifactMetadata>> {
+        let direct_load_handle = load_handle.direct_load_handle();
+        if direct_load_handle == LoadHandle(0) {
+            return None;
+        }
+
+        self.inner
+            .lock()
+            .unwrap()
+            .load_handle_infos
+            .get(&direct_load_handle)?
+            .metadata
+            .clone()
+    }
+
+    // Snapshot of every load handle and its state, for diagnosing loads that never complete
+    pub fn debug_dump(&self) -> LoaderDebugSnapshot {
+        self.inner.lock().unwrap().debug_dump()
+    }
+
     pub fn log_load_state_recursive(
         &self,
         load_handle: LoadHandle,
@@ -1226,11 +2076,31 @@ impl LoadStateProvider for Loader {
         &self,
         load_handle: &Arc<ResolvedLoadHandle>,
     ) -> LoadState {
+        // Null handles, and indirect handles that no longer resolve to an artifact
+        let direct_load_handle = load_handle.direct_load_handle();
+        if direct_load_handle == LoadHandle(0) {
+            // Checked loads of an artifact of the wrong type don't resolve either
+            let type_mismatch = load_handle.id.is_indirect()
+                && self
+                    .inner
+                    .lock()
+                    .unwrap()
+                    .indirect_states
+                    .get(&load_handle.id)
+                    .map(|x| x.type_mismatch)
+                    .unwrap_or(false);
+            if type_mismatch {
+                return LoadState::Error(LoadError::TypeMismatch);
+            }
+
+            return LoadState::Null;
+        }
+
         self.inner
             .lock()
             .unwrap()
             .load_handle_infos
-            .get(&load_handle.direct_load_handle())
+            .get(&direct_load_handle)
             .unwrap()
             .load_state
     }
@@ -1239,11 +2109,16 @@ impl LoadStateProvider for Loader {
         &self,
         load_handle: &Arc<ResolvedLoadHandle>,
     ) -> ArtifactId {
+        let direct_load_handle = load_handle.direct_load_handle();
+        if direct_load_handle == LoadHandle(0) {
+            return ArtifactId::null();
+        }
+
         self.inner
             .lock()
             .unwrap()
             .load_handle_infos
-            .get(&load_handle.direct_load_handle())
+            .get(&direct_load_handle)
             .unwrap()
             .artifact_id
     }
@@ -1281,6 +2156,7 @@ impl<'a> LoaderInfoProvider for LoadHandleInfoProviderImpl<'a> {
             .get(&ArtifactIdAndHash {
                 id: artifact_id,
                 hash: build_hash,
+                shallow: false,
             })
             .map(|l| *l)?;
         Some(ResolvedLoadHandle::new(load_handle, load_handle))
@@ -1294,3 +2170,759 @@ impl<'a> LoaderInfoProvider for LoadHandleInfoProviderImpl<'a> {
         self.load_handle_infos.get(&load).map(|l| l.artifact_id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::ArtifactLoadOpBatch;
+    use crate::test_loader_io::{RequestLog, TestLoaderIO};
+    use crate::ArtifactStorageSet;
+    use hydrate_base::handle::{ArtifactHandle, Handle};
+    use type_uuid::TypeUuid;
+
+    struct TestArtifactStorage;
+
+    impl ArtifactStorage for TestArtifactStorage {
+        fn load_artifact(
+            &mut self,
+            _loader_info: &dyn LoaderInfoProvider,
+            _artifact_type_id: &ArtifactTypeId,
+            _artifact_id: ArtifactId,
+            _data: Vec<u8>,
+            _load_handle: LoadHandle,
+            _load_op: ArtifactLoadOp,
+        ) -> Result<(), Box<dyn std::error::Error + Send + 'static>> {
+            panic!("an artifact that failed to load should not be deserialized");
+        }
+
+        fn commit_artifact(
+            &mut self,
+            _artifact_type: ArtifactTypeId,
+            _load_handle: LoadHandle,
+        ) {
+        }
+
+        fn free_artifact(
+            &mut self,
+            _artifact_type_id: ArtifactTypeId,
+            _load_handle: LoadHandle,
+        ) {
+        }
+    }
+
+    #[test]
+    fn checked_load_of_wrong_type_is_an_error() {
+        let artifact_id = ArtifactId::from_u128(1);
+        let actual_type = ArtifactTypeId::from_u128(2);
+        let requested_type = ArtifactTypeId::from_u128(3);
+
+        let mut manifest = HashMap::default();
+        manifest.insert(
+            artifact_id,
+            ArtifactManifestData {
+                artifact_id,
+                simple_build_hash: 4,
+                combined_build_hash: 5,
+                symbol_hash: None,
+                artifact_type: actual_type.as_uuid(),
+                debug_name: None,
+            },
+        );
+
+        let (events_tx, events_rx) = crossbeam_channel::unbounded();
+        let loader_io = TestLoaderIO::new(manifest, events_tx.clone());
+        let loader = Loader::new(Box::new(loader_io), events_tx, events_rx);
+
+        let load_handle = loader.add_engine_ref_indirect(IndirectIdentifier::CheckedArtifactId(
+            artifact_id,
+            requested_type,
+        ));
+
+        // The handle doesn't resolve to an artifact of the wrong type, so nothing is loaded
+        assert_eq!(load_handle.direct_load_handle(), LoadHandle(0));
+        assert_eq!(loader.artifact_id(&load_handle), ArtifactId::null());
+
+        let mut artifact_storage = TestArtifactStorage;
+        for _ in 0..3 {
+            loader.update(&mut artifact_storage);
+        }
+
+        assert_eq!(
+            loader.load_state(&load_handle),
+            LoadState::Error(LoadError::TypeMismatch)
+        );
+    }
+
+    #[test]
+    fn checked_load_of_wrong_type_does_not_affect_other_handles() {
+        let artifact_id = ArtifactId::from_u128(1);
+        let actual_type = ArtifactTypeId::from_u128(2);
+        let requested_type = ArtifactTypeId::from_u128(3);
+
+        let mut manifest = HashMap::default();
+        manifest.insert(
+            artifact_id,
+            ArtifactManifestData {
+                artifact_id,
+                simple_build_hash: 4,
+                combined_build_hash: 5,
+                symbol_hash: None,
+                artifact_type: actual_type.as_uuid(),
+                debug_name: None,
+            },
+        );
+
+        let (events_tx, events_rx) = crossbeam_channel::unbounded();
+        let loader_io = TestLoaderIO::new(manifest, events_tx.clone());
+        let loader = Loader::new(Box::new(loader_io), events_tx, events_rx);
+
+        let correct_handle = loader.add_engine_ref_indirect(IndirectIdentifier::CheckedArtifactId(
+            artifact_id,
+            actual_type,
+        ));
+        let wrong_handle = loader.add_engine_ref_indirect(IndirectIdentifier::CheckedArtifactId(
+            artifact_id,
+            requested_type,
+        ));
+        assert_eq!(loader.artifact_id(&correct_handle), artifact_id);
+
+        let mut artifact_storage = CompletingArtifactStorage::default();
+        for _ in 0..5 {
+            loader.update(&mut artifact_storage);
+        }
+
+        assert_eq!(
+            loader.load_state(&wrong_handle),
+            LoadState::Error(LoadError::TypeMismatch)
+        );
+        assert_eq!(loader.load_state(&correct_handle), LoadState::Loaded);
+        assert_eq!(artifact_storage.loaded, vec![artifact_id]);
+    }
+
+    #[test]
+    fn load_with_missing_dependency_is_an_error() {
+        let artifact_id = ArtifactId::from_u128(1);
+        let missing_dependency_id = ArtifactId::from_u128(2);
+        let artifact_type = ArtifactTypeId::from_u128(3);
+
+        let mut manifest = HashMap::default();
+        manifest.insert(
+            artifact_id,
+            ArtifactManifestData {
+                artifact_id,
+                simple_build_hash: 4,
+                combined_build_hash: 5,
+                symbol_hash: None,
+                artifact_type: artifact_type.as_uuid(),
+                debug_name: None,
+            },
+        );
+
+        // The dependency is referenced by metadata but was never added to the manifest
+        let mut dependencies = HashMap::default();
+        dependencies.insert(artifact_id, vec![missing_dependency_id]);
+
+        let (events_tx, events_rx) = crossbeam_channel::unbounded();
+        let mut loader_io = TestLoaderIO::new(manifest, events_tx.clone());
+        loader_io.dependencies = dependencies;
+        let loader = Loader::new(Box::new(loader_io), events_tx, events_rx);
+
+        let load_handle = loader.add_engine_ref_indirect(IndirectIdentifier::CheckedArtifactId(
+            artifact_id,
+            artifact_type,
+        ));
+
+        let mut artifact_storage = TestArtifactStorage;
+        for _ in 0..3 {
+            loader.update(&mut artifact_storage);
+        }
+
+        assert_eq!(
+            loader.load_state(&load_handle),
+            LoadState::Error(LoadError::MissingDependency(missing_dependency_id))
+        );
+    }
+
+    #[derive(serde::Deserialize, type_uuid::TypeUuid)]
+    #[uuid = "9f4f6e8a-3f0c-4a4e-8d55-0e7cbb1c6f21"]
+    struct NullTestArtifact;
+
+    #[test]
+    fn load_of_null_artifact_id_is_null() {
+        // The manifest is empty and resolving an ArtifactId that isn't in it panics, so this also
+        // checks that no IO is issued
+        let (events_tx, events_rx) = crossbeam_channel::unbounded();
+        let loader_io = TestLoaderIO::new(HashMap::default(), events_tx.clone());
+        let loader = Loader::new(Box::new(loader_io), events_tx, events_rx);
+
+        let (ref_op_tx, ref_op_rx) = crossbeam_channel::unbounded();
+        let mut artifact_storage = ArtifactStorageSet::new(ref_op_tx.clone());
+        artifact_storage.add_storage::<NullTestArtifact>();
+
+        let load_handle = loader.add_engine_ref_indirect(IndirectIdentifier::ArtifactId(
+            ArtifactId::null(),
+            ArtifactTypeId::from_bytes(NullTestArtifact::UUID),
+        ));
+        let handle = Handle::<NullTestArtifact>::new(ref_op_tx, load_handle);
+
+        // Cloning and dropping the handle sends ref count changes for the null handle
+        drop(handle.clone());
+        for _ in 0..3 {
+            crate::process_ref_ops(&loader, &ref_op_rx);
+            loader.update(&mut artifact_storage);
+        }
+
+        assert_eq!(handle.load_state(&loader), LoadState::Null);
+        assert!(handle.artifact_id(&loader).is_null());
+        assert!(handle.artifact(&artifact_storage).is_none());
+        assert!(loader.get_load_info(handle.load_handle()).is_none());
+    }
+
+    // Returns the loader along with the recorded data reads and metadata requests
+    fn create_range_test_loader(
+        supports_ranges: bool
+    ) -> (Loader, RequestLog<Range<u64>>, RequestLog<ArtifactId>) {
+        let artifact_id = ArtifactId::from_u128(1);
+        let mut manifest = HashMap::default();
+        manifest.insert(
+            artifact_id,
+            ArtifactManifestData {
+                artifact_id,
+                simple_build_hash: 2,
+                combined_build_hash: 3,
+                symbol_hash: None,
+                artifact_type: ArtifactTypeId::from_u128(4).as_uuid(),
+                debug_name: None,
+            },
+        );
+
+        let (events_tx, events_rx) = crossbeam_channel::unbounded();
+        let mut loader_io = TestLoaderIO::new(manifest, events_tx.clone());
+        loader_io.payload = |_, _| (0..=255).collect();
+        loader_io.supports_ranges = supports_ranges;
+        let reads = loader_io.data_reads.clone();
+        let metadata_requests = loader_io.metadata_requests.clone();
+        let loader = Loader::new(Box::new(loader_io), events_tx, events_rx);
+        (loader, reads, metadata_requests)
+    }
+
+    #[test]
+    fn range_requests_read_only_the_requested_bytes() {
+        let artifact_id = ArtifactId::from_u128(1);
+        let (loader, reads, metadata_requests) = create_range_test_loader(true);
+
+        let first = loader.request_artifact_range(artifact_id, 16..32).unwrap();
+        let second = loader
+            .request_artifact_range(artifact_id, 128..136)
+            .unwrap();
+        assert!(first.bytes().unwrap().is_none());
+
+        loader.update(&mut TestArtifactStorage);
+
+        assert_eq!(*reads.lock().unwrap(), vec![16..32, 128..136]);
+        assert_eq!(
+            loader.resident_artifact_ranges(artifact_id),
+            vec![16..32, 128..136]
+        );
+        assert_eq!(
+            &*first.bytes().unwrap().unwrap(),
+            (16..32).collect::<Vec<u8>>().as_slice()
+        );
+
+        // Ranges that are already resident are not read again, and share the bytes that were read
+        let nested = loader
+            .request_artifact_range(artifact_id, 130..132)
+            .unwrap();
+        loader.update(&mut TestArtifactStorage);
+        assert_eq!(reads.lock().unwrap().len(), 2);
+        let nested_bytes = nested.bytes().unwrap().unwrap();
+        assert_eq!(&*nested_bytes, &[130, 131]);
+        assert_eq!(
+            nested_bytes.as_ptr(),
+            second.bytes().unwrap().unwrap()[2..].as_ptr()
+        );
+
+        // Bytes are freed once no handle needs them
+        drop(first);
+        assert_eq!(loader.resident_artifact_ranges(artifact_id), vec![128..136]);
+        drop(second);
+        assert_eq!(loader.resident_artifact_ranges(artifact_id), vec![128..136]);
+        assert_eq!(&*nested.bytes().unwrap().unwrap(), &[130, 131]);
+        drop(nested);
+        assert!(loader.resident_artifact_ranges(artifact_id).is_empty());
+
+        // Range requests don't load the artifact
+        assert!(metadata_requests.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn range_requests_report_invalid_ranges() {
+        let artifact_id = ArtifactId::from_u128(1);
+        let (loader, _, _) = create_range_test_loader(true);
+
+        assert!(loader
+            .request_artifact_range(ArtifactId::from_u128(5), 0..16)
+            .is_err());
+
+        // The payload is 256 bytes
+        let in_bounds = loader
+            .request_artifact_range(artifact_id, 240..256)
+            .unwrap();
+        let past_end = loader
+            .request_artifact_range(artifact_id, 250..300)
+            .unwrap();
+        loader.update(&mut TestArtifactStorage);
+        assert_eq!(in_bounds.bytes().unwrap().unwrap().len(), 16);
+        assert!(past_end.bytes().is_err());
+    }
+
+    #[test]
+    fn range_requests_fall_back_to_whole_payload() {
+        let artifact_id = ArtifactId::from_u128(1);
+        let (loader, reads, metadata_requests) = create_range_test_loader(false);
+
+        let first = loader.request_artifact_range(artifact_id, 16..32).unwrap();
+        let second = loader
+            .request_artifact_range(artifact_id, 128..136)
+            .unwrap();
+        loader.update(&mut TestArtifactStorage);
+
+        // The payload is read once and serves both ranges
+        assert_eq!(*reads.lock().unwrap(), vec![0..256]);
+        assert_eq!(
+            &*first.bytes().unwrap().unwrap(),
+            (16..32).collect::<Vec<u8>>().as_slice()
+        );
+        assert_eq!(
+            &*second.bytes().unwrap().unwrap(),
+            (128..136).collect::<Vec<u8>>().as_slice()
+        );
+        assert!(metadata_requests.lock().unwrap().is_empty());
+
+        drop(first);
+        drop(second);
+        assert!(loader.resident_artifact_ranges(artifact_id).is_empty());
+    }
+
+    // Completes every load immediately, recording which artifacts were loaded
+    #[derive(Default)]
+    struct CompletingArtifactStorage {
+        loaded: Vec<ArtifactId>,
+    }
+
+    impl ArtifactStorage for CompletingArtifactStorage {
+        fn load_artifact(
+            &mut self,
+            _loader_info: &dyn LoaderInfoProvider,
+            _artifact_type_id: &ArtifactTypeId,
+            artifact_id: ArtifactId,
+            _data: Vec<u8>,
+            _load_handle: LoadHandle,
+            load_op: ArtifactLoadOp,
+        ) -> Result<(), Box<dyn std::error::Error + Send + 'static>> {
+            self.loaded.push(artifact_id);
+            load_op.complete();
+            Ok(())
+        }
+
+        fn commit_artifact(
+            &mut self,
+            _artifact_type: ArtifactTypeId,
+            _load_handle: LoadHandle,
+        ) {
+        }
+
+        fn free_artifact(
+            &mut self,
+            _artifact_type_id: ArtifactTypeId,
+            _load_handle: LoadHandle,
+        ) {
+        }
+    }
+
+    #[test]
+    fn shallow_load_leaves_dependencies_unloaded() {
+        let artifact_id = ArtifactId::from_u128(1);
+        let dependency_id = ArtifactId::from_u128(2);
+        let artifact_type = ArtifactTypeId::from_u128(3);
+
+        let mut manifest = HashMap::default();
+        for (id, hash) in [(artifact_id, 4), (dependency_id, 5)] {
+            manifest.insert(
+                id,
+                ArtifactManifestData {
+                    artifact_id: id,
+                    simple_build_hash: hash,
+                    combined_build_hash: hash,
+                    symbol_hash: None,
+                    artifact_type: artifact_type.as_uuid(),
+                    debug_name: None,
+                },
+            );
+        }
+
+        let mut dependencies = HashMap::default();
+        dependencies.insert(artifact_id, vec![dependency_id]);
+
+        let (events_tx, events_rx) = crossbeam_channel::unbounded();
+        let mut loader_io = TestLoaderIO::new(manifest, events_tx.clone());
+        loader_io.dependencies = dependencies;
+        let requested = loader_io.metadata_requests.clone();
+        let loader = Loader::new(Box::new(loader_io), events_tx, events_rx);
+
+        let shallow_handle = loader.add_engine_ref_indirect(IndirectIdentifier::ShallowArtifactId(
+            artifact_id,
+            artifact_type,
+        ));
+
+        let mut artifact_storage = CompletingArtifactStorage::default();
+        for _ in 0..5 {
+            loader.update(&mut artifact_storage);
+        }
+
+        assert_eq!(loader.load_state(&shallow_handle), LoadState::Loaded);
+        assert_eq!(*requested.lock().unwrap(), vec![artifact_id]);
+        assert_eq!(artifact_storage.loaded, vec![artifact_id]);
+
+        // A full load of the same artifact does not share the shallow load's handle, so its
+        // dependencies are loaded first
+        let full_handle = loader
+            .add_engine_ref_indirect(IndirectIdentifier::ArtifactId(artifact_id, artifact_type));
+        for _ in 0..5 {
+            loader.update(&mut artifact_storage);
+        }
+
+        assert_eq!(loader.load_state(&full_handle), LoadState::Loaded);
+        assert_eq!(
+            artifact_storage.loaded,
+            vec![artifact_id, dependency_id, artifact_id]
+        );
+    }
+
+    // Holds on to every load op so the test can decide when loads complete
+    #[derive(Default)]
+    struct DeferringArtifactStorage {
+        pending: Vec<ArtifactLoadOp>,
+    }
+
+    impl ArtifactStorage for DeferringArtifactStorage {
+        fn load_artifact(
+            &mut self,
+            _loader_info: &dyn LoaderInfoProvider,
+            _artifact_type_id: &ArtifactTypeId,
+            _artifact_id: ArtifactId,
+            _data: Vec<u8>,
+            _load_handle: LoadHandle,
+            load_op: ArtifactLoadOp,
+        ) -> Result<(), Box<dyn std::error::Error + Send + 'static>> {
+            self.pending.push(load_op);
+            Ok(())
+        }
+
+        fn commit_artifact(
+            &mut self,
+            _artifact_type: ArtifactTypeId,
+            _load_handle: LoadHandle,
+        ) {
+        }
+
+        fn free_artifact(
+            &mut self,
+            _artifact_type_id: ArtifactTypeId,
+            _load_handle: LoadHandle,
+        ) {
+        }
+    }
+
+    #[test]
+    fn batched_load_ops_complete_in_the_same_update() {
+        let artifact_type = ArtifactTypeId::from_u128(100);
+        let artifact_ids: Vec<_> = (1..=3).map(ArtifactId::from_u128).collect();
+
+        let mut manifest = HashMap::default();
+        for (i, &id) in artifact_ids.iter().enumerate() {
+            manifest.insert(
+                id,
+                ArtifactManifestData {
+                    artifact_id: id,
+                    simple_build_hash: i as u64,
+                    combined_build_hash: i as u64,
+                    symbol_hash: None,
+                    artifact_type: artifact_type.as_uuid(),
+                    debug_name: None,
+                },
+            );
+        }
+
+        let (events_tx, events_rx) = crossbeam_channel::unbounded();
+        let loader_io = TestLoaderIO::new(manifest, events_tx.clone());
+        let loader = Loader::new(Box::new(loader_io), events_tx, events_rx);
+
+        let handles: Vec<_> = artifact_ids
+            .iter()
+            .map(|&id| {
+                loader.add_engine_ref_indirect(IndirectIdentifier::ArtifactId(id, artifact_type))
+            })
+            .collect();
+
+        let mut artifact_storage = DeferringArtifactStorage::default();
+        for _ in 0..5 {
+            loader.update(&mut artifact_storage);
+        }
+        assert_eq!(artifact_storage.pending.len(), 3);
+
+        let mut batch = ArtifactLoadOpBatch::default();
+        for load_op in artifact_storage.pending.drain(..) {
+            batch.complete(load_op);
+        }
+        assert_eq!(batch.len(), 3);
+
+        // Nothing changes until the batch is committed and the loader updates
+        for handle in &handles {
+            assert_eq!(loader.load_state(handle), LoadState::Loading);
+        }
+        batch.commit();
+        for handle in &handles {
+            assert_eq!(loader.load_state(handle), LoadState::Loading);
+        }
+
+        loader.update(&mut artifact_storage);
+        for handle in &handles {
+            assert_eq!(loader.load_state(handle), LoadState::Loaded);
+        }
+    }
+
+    #[test]
+    fn metadata_of_loaded_artifact_is_available() {
+        let artifact_id = ArtifactId::from_u128(1);
+        let dependency_id = ArtifactId::from_u128(2);
+        let artifact_type = ArtifactTypeId::from_u128(3);
+        let dependency_type = ArtifactTypeId::from_u128(4);
+
+        let mut manifest = HashMap::default();
+        for (id, artifact_type, hash) in [
+            (artifact_id, artifact_type, 5),
+            (dependency_id, dependency_type, 6),
+        ] {
+            manifest.insert(
+                id,
+                ArtifactManifestData {
+                    artifact_id: id,
+                    simple_build_hash: hash,
+                    combined_build_hash: hash,
+                    symbol_hash: None,
+                    artifact_type: artifact_type.as_uuid(),
+                    debug_name: None,
+                },
+            );
+        }
+
+        let mut dependencies = HashMap::default();
+        dependencies.insert(artifact_id, vec![dependency_id]);
+
+        let (events_tx, events_rx) = crossbeam_channel::unbounded();
+        let mut loader_io = TestLoaderIO::new(manifest, events_tx.clone());
+        loader_io.dependencies = dependencies;
+        let loader = Loader::new(Box::new(loader_io), events_tx, events_rx);
+
+        let load_handle = loader
+            .add_engine_ref_indirect(IndirectIdentifier::ArtifactId(artifact_id, artifact_type));
+        assert!(loader.artifact_metadata(&load_handle).is_none());
+
+        let mut artifact_storage = CompletingArtifactStorage::default();
+        for _ in 0..5 {
+            loader.update(&mut artifact_storage);
+        }
+        assert_eq!(loader.load_state(&load_handle), LoadState::Loaded);
+
+        let metadata = loader.artifact_metadata(&load_handle).unwrap();
+        assert_eq!(metadata.artifact_type_id, artifact_type);
+        assert_eq!(metadata.dependencies, vec![dependency_id]);
+        assert_eq!(metadata.hash, 5);
+
+        // Null handles have no metadata
+        let null_handle = loader.add_engine_ref_indirect(IndirectIdentifier::ArtifactId(
+            ArtifactId::null(),
+            artifact_type,
+        ));
+        assert!(loader.artifact_metadata(&null_handle).is_none());
+    }
+
+    #[test]
+    fn load_of_unregistered_type_is_an_error() {
+        let artifact_id = ArtifactId::from_u128(1);
+        let unregistered_type = ArtifactTypeId::from_u128(2);
+
+        let mut manifest = HashMap::default();
+        manifest.insert(
+            artifact_id,
+            ArtifactManifestData {
+                artifact_id,
+                simple_build_hash: 3,
+                combined_build_hash: 3,
+                symbol_hash: None,
+                artifact_type: unregistered_type.as_uuid(),
+                debug_name: None,
+            },
+        );
+
+        let (events_tx, events_rx) = crossbeam_channel::unbounded();
+        let loader_io = TestLoaderIO::new(manifest, events_tx.clone());
+        let loader = Loader::new(Box::new(loader_io), events_tx, events_rx);
+
+        // Storage is added for a different type only
+        let (ref_op_tx, _ref_op_rx) = crossbeam_channel::unbounded();
+        let mut artifact_storage = ArtifactStorageSet::new(ref_op_tx);
+        artifact_storage.add_storage::<NullTestArtifact>();
+        assert!(!artifact_storage.has_storage(&unregistered_type));
+
+        let load_handle = loader.add_engine_ref_indirect(IndirectIdentifier::ArtifactId(
+            artifact_id,
+            unregistered_type,
+        ));
+        for _ in 0..5 {
+            loader.update(&mut artifact_storage);
+        }
+
+        assert_eq!(
+            loader.load_state(&load_handle),
+            LoadState::Error(LoadError::UnregisteredType(unregistered_type.as_uuid()))
+        );
+    }
+
+    #[test]
+    fn debug_dump_shows_blocking_dependency() {
+        let artifact_id = ArtifactId::from_u128(1);
+        let dependency_id = ArtifactId::from_u128(2);
+        let artifact_type = ArtifactTypeId::from_u128(3);
+
+        let mut manifest = HashMap::default();
+        for (id, hash) in [(artifact_id, 4), (dependency_id, 5)] {
+            manifest.insert(
+                id,
+                ArtifactManifestData {
+                    artifact_id: id,
+                    simple_build_hash: hash,
+                    combined_build_hash: hash,
+                    symbol_hash: None,
+                    artifact_type: artifact_type.as_uuid(),
+                    debug_name: None,
+                },
+            );
+        }
+
+        let mut dependencies = HashMap::default();
+        dependencies.insert(artifact_id, vec![dependency_id]);
+
+        // The dependency's metadata never arrives, so the artifact is stuck waiting on it
+        let (events_tx, events_rx) = crossbeam_channel::unbounded();
+        let mut loader_io = TestLoaderIO::new(manifest, events_tx.clone());
+        loader_io.dependencies = dependencies;
+        loader_io.unresponsive = vec![dependency_id];
+        let loader = Loader::new(Box::new(loader_io), events_tx, events_rx);
+
+        let load_handle = loader
+            .add_engine_ref_indirect(IndirectIdentifier::ArtifactId(artifact_id, artifact_type));
+
+        let mut artifact_storage = CompletingArtifactStorage::default();
+        for _ in 0..5 {
+            loader.update(&mut artifact_storage);
+        }
+
+        let snapshot = loader.debug_dump();
+        assert_eq!(snapshot.indirect_handles.len(), 1);
+        assert_eq!(
+            snapshot.indirect_handles[0].resolved_load_handle,
+            Some(load_handle.direct_load_handle())
+        );
+
+        let info = snapshot
+            .direct_handle(load_handle.direct_load_handle())
+            .unwrap();
+        assert_eq!(info.artifact_id, artifact_id);
+        assert_eq!(info.load_state, LoadState::WaitingForDependencies);
+        assert_eq!(info.external_ref_count, 1);
+        assert_eq!(info.waiting_on.len(), 1);
+
+        let dependency_info = snapshot.direct_handle(info.waiting_on[0]).unwrap();
+        assert_eq!(dependency_info.artifact_id, dependency_id);
+        assert_eq!(dependency_info.load_state, LoadState::WaitingForMetadata);
+        assert_eq!(dependency_info.external_ref_count, 0);
+        assert_eq!(dependency_info.internal_ref_count, 1);
+
+        let dump = snapshot.to_string();
+        assert!(dump.contains(&format!("waiting on [{:?}]", dependency_info.load_handle)));
+    }
+
+    #[derive(serde::Deserialize, type_uuid::TypeUuid)]
+    #[uuid = "3c1d8e0a-7b52-4f4e-9a7e-51f0b7a1d6c4"]
+    struct VersionedTestArtifact(u32);
+
+    fn versioned_manifest(
+        artifact_id: ArtifactId,
+        version: u64,
+    ) -> HashMap<ArtifactId, ArtifactManifestData> {
+        let mut manifest = HashMap::default();
+        manifest.insert(
+            artifact_id,
+            ArtifactManifestData {
+                artifact_id,
+                simple_build_hash: version,
+                combined_build_hash: version,
+                symbol_hash: None,
+                artifact_type: ArtifactTypeId::from_bytes(VersionedTestArtifact::UUID).as_uuid(),
+                debug_name: None,
+            },
+        );
+        manifest
+    }
+
+    #[test]
+    fn pinned_version_survives_reload_until_unpinned() {
+        let artifact_id = ArtifactId::from_u128(1);
+        let (events_tx, events_rx) = crossbeam_channel::unbounded();
+        // Each version's payload is its hash, bincode-encoded as a u32
+        let mut loader_io =
+            TestLoaderIO::new(versioned_manifest(artifact_id, 1), events_tx.clone());
+        loader_io.payload = |_, hash| bincode::serialize(&(hash as u32)).unwrap();
+        let pending_manifest = loader_io.pending_manifest.clone();
+        let loader = Loader::new(Box::new(loader_io), events_tx, events_rx);
+
+        let (ref_op_tx, ref_op_rx) = crossbeam_channel::unbounded();
+        let mut artifact_storage = ArtifactStorageSet::new(ref_op_tx.clone());
+        artifact_storage.add_storage::<VersionedTestArtifact>();
+
+        let load_handle = loader.add_engine_ref_indirect(IndirectIdentifier::ArtifactId(
+            artifact_id,
+            ArtifactTypeId::from_bytes(VersionedTestArtifact::UUID),
+        ));
+        let handle = Handle::<VersionedTestArtifact>::new(ref_op_tx, load_handle);
+
+        let update = |loader: &Loader, artifact_storage: &mut ArtifactStorageSet| {
+            for _ in 0..5 {
+                crate::process_ref_ops(loader, &ref_op_rx);
+                loader.update(artifact_storage);
+            }
+        };
+
+        update(&loader, &mut artifact_storage);
+        assert_eq!(handle.artifact(&artifact_storage).unwrap().0, 1);
+
+        // Hot reload a new version while pinned. It loads, but the handle keeps the old one.
+        let pin = loader.pin_version(handle.load_handle());
+        *pending_manifest.lock().unwrap() = Some(versioned_manifest(artifact_id, 2));
+        update(&loader, &mut artifact_storage);
+        assert!(loader.inner.lock().unwrap().current_reload_action.is_none());
+        assert_eq!(handle.artifact(&artifact_storage).unwrap().0, 1);
+
+        // Releasing the pin switches to the new version and frees the old one
+        let old_direct_load_handle = handle.direct_load_handle();
+        drop(pin);
+        update(&loader, &mut artifact_storage);
+        assert_eq!(handle.artifact(&artifact_storage).unwrap().0, 2);
+        let snapshot = loader.debug_dump();
+        let old_info = snapshot.direct_handle(old_direct_load_handle).unwrap();
+        assert_eq!(old_info.internal_ref_count, 0);
+        assert_eq!(old_info.load_state, LoadState::Unloaded);
+    }
+}
@@ -73,11 +73,95 @@ impl Drop for ArtifactLoadOp {
     }
 }
 
+/// Collects the results of several [`ArtifactLoadOp`]s and sends them to the loader as a single
+/// event, so that they are all processed in the same update. This is useful when one piece of data
+/// updates many artifacts at once. Results are sent when the batch is committed or dropped.
+#[derive(Default)]
+pub struct ArtifactLoadOpBatch {
+    sender: Option<Sender<LoaderEvent>>,
+    results: Vec<HandleOp>,
+}
+
+impl ArtifactLoadOpBatch {
+    /// Returns the number of load operations in the batch
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Returns true if no load operations have been added to the batch
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    // Takes ownership of the op's sender so that dropping the op doesn't signal anything
+    fn take_load_op(
+        &mut self,
+        mut load_op: ArtifactLoadOp,
+    ) -> LoadHandle {
+        let sender = load_op.sender.take();
+        if self.sender.is_none() {
+            self.sender = sender;
+        }
+        load_op.handle
+    }
+
+    /// Adds a load operation that has completed successfully to the batch.
+    pub fn complete(
+        &mut self,
+        load_op: ArtifactLoadOp,
+    ) {
+        let load_handle = self.take_load_op(load_op);
+        log::debug!("LoadOp for {:?} complete (batched)", load_handle);
+        self.results.push(HandleOp::Complete(load_handle));
+    }
+
+    /// Adds a load operation that has completed with an error to the batch.
+    pub fn error<E: Error + 'static + Send>(
+        &mut self,
+        load_op: ArtifactLoadOp,
+        error: E,
+    ) {
+        let load_handle = self.take_load_op(load_op);
+        log::debug!("LoadOp for {:?} error {:?} (batched)", load_handle, error);
+        self.results
+            .push(HandleOp::Error(load_handle, Box::new(error)));
+    }
+
+    /// Signals the results of every load operation in the batch to the loader at once.
+    pub fn commit(self) {
+        // Sent by drop
+    }
+}
+
+impl Drop for ArtifactLoadOpBatch {
+    fn drop(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            let results = std::mem::take(&mut self.results);
+            log::debug!("LoadOp batch of {} ops committed", results.len());
+            let _ = sender.send(LoaderEvent::LoadResultBatch(results));
+        }
+    }
+}
+
 /// Storage for all artifacts of all artifact types.
 ///
 /// Consumers are expected to provide the implementation for this, as this is the bridge between
 /// [`Loader`](crate::loader::Loader) and the application.
 pub trait ArtifactStorage {
+    /// Returns whether artifacts of the given type can be loaded. Artifacts of other types are
+    /// never passed to [`load_artifact`](ArtifactStorage::load_artifact), their loads fail with
+    /// `LoadError::UnregisteredType` instead.
+    ///
+    /// # Parameters
+    ///
+    /// * `artifact_type_id`: UUID of the artifact type.
+    fn has_storage(
+        &self,
+        _artifact_type_id: &ArtifactTypeId,
+    ) -> bool {
+        true
+    }
+
     /// Updates the backing data of an artifact.
     ///
     /// An example usage of this is when a texture such as "player.png" changes while the
@@ -130,4 +214,35 @@ pub trait ArtifactStorage {
 pub enum IndirectIdentifier {
     ArtifactId(ArtifactId, ArtifactTypeId),
     SymbolWithType(StringHash, ArtifactTypeId),
+    // Resolves regardless of type. The loader compares the type against the artifact's metadata
+    // and puts the load into an error state if they don't match
+    CheckedArtifactId(ArtifactId, ArtifactTypeId),
+    // Resolves like ArtifactId, but only the artifact's own data is loaded. Dependencies are not
+    // ref-counted or loaded, so handles inside the artifact will not be resolved
+    ShallowArtifactId(ArtifactId, ArtifactTypeId),
+}
+
+impl IndirectIdentifier {
+    // The type the loader must verify against the artifact metadata, if any
+    pub fn checked_artifact_type_id(&self) -> Option<ArtifactTypeId> {
+        match self {
+            IndirectIdentifier::CheckedArtifactId(_, artifact_type_id) => Some(*artifact_type_id),
+            _ => None,
+        }
+    }
+
+    // True if dependencies of the artifact should not be loaded along with it
+    pub fn is_shallow(&self) -> bool {
+        matches!(self, IndirectIdentifier::ShallowArtifactId(_, _))
+    }
+
+    // True if this identifies an artifact by a null ID, which never refers to an artifact
+    pub fn is_null(&self) -> bool {
+        match self {
+            IndirectIdentifier::ArtifactId(artifact_id, _)
+            | IndirectIdentifier::CheckedArtifactId(artifact_id, _)
+            | IndirectIdentifier::ShallowArtifactId(artifact_id, _) => artifact_id.is_null(),
+            IndirectIdentifier::SymbolWithType(_, _) => false,
+        }
+    }
 }
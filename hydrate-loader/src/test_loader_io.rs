@@ -0,0 +1,181 @@
+use crate::loader::{
+    ArtifactData, ArtifactMetadata, LoaderEvent, LoaderIO, ManifestBuildHash,
+    RequestDataRangeResult, RequestDataResult, RequestMetadataResult,
+};
+use crate::storage::IndirectIdentifier;
+use crate::ArtifactTypeId;
+use crossbeam_channel::Sender;
+use hydrate_base::hashing::HashMap;
+use hydrate_base::{ArtifactId, ArtifactManifestData, LoadHandle};
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+// Shared with the test so it can inspect what the loader requested
+pub(crate) type RequestLog<T> = Arc<Mutex<Vec<T>>>;
+
+// Serves artifacts out of an in-memory manifest. Requests complete immediately unless the artifact
+// is listed as unresponsive, and everything that was requested is recorded so tests can check what
+// IO the loader issued. A new manifest can be queued to simulate a hot reload.
+pub(crate) struct TestLoaderIO {
+    pub(crate) manifest: HashMap<ArtifactId, ArtifactManifestData>,
+    pub(crate) dependencies: HashMap<ArtifactId, Vec<ArtifactId>>,
+    // Produces an artifact's payload from its ID and hash
+    pub(crate) payload: fn(ArtifactId, u64) -> Vec<u8>,
+    pub(crate) supports_ranges: bool,
+    // Metadata and data requests for these artifacts never complete
+    pub(crate) unresponsive: Vec<ArtifactId>,
+    pub(crate) pending_manifest: Arc<Mutex<Option<HashMap<ArtifactId, ArtifactManifestData>>>>,
+    pub(crate) metadata_requests: RequestLog<ArtifactId>,
+    pub(crate) data_reads: RequestLog<Range<u64>>,
+    build_hash: u64,
+    events_tx: Sender<LoaderEvent>,
+}
+
+impl TestLoaderIO {
+    pub(crate) fn new(
+        manifest: HashMap<ArtifactId, ArtifactManifestData>,
+        events_tx: Sender<LoaderEvent>,
+    ) -> Self {
+        TestLoaderIO {
+            manifest,
+            dependencies: Default::default(),
+            payload: |_, _| vec![],
+            supports_ranges: false,
+            unresponsive: Default::default(),
+            pending_manifest: Default::default(),
+            metadata_requests: Default::default(),
+            data_reads: Default::default(),
+            build_hash: 0,
+            events_tx,
+        }
+    }
+}
+
+impl LoaderIO for TestLoaderIO {
+    fn update(&mut self) {}
+
+    fn current_build_hash(&self) -> ManifestBuildHash {
+        ManifestBuildHash(self.build_hash)
+    }
+
+    fn pending_build_hash(&self) -> Option<ManifestBuildHash> {
+        self.pending_manifest
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|_| ManifestBuildHash(self.build_hash + 1))
+    }
+
+    fn activate_pending_build_hash(
+        &mut self,
+        new_build_hash: ManifestBuildHash,
+    ) {
+        self.manifest = self.pending_manifest.lock().unwrap().take().unwrap();
+        self.build_hash = new_build_hash.0;
+    }
+
+    fn manifest_entry(
+        &self,
+        artifact_id: ArtifactId,
+    ) -> Option<&ArtifactManifestData> {
+        self.manifest.get(&artifact_id)
+    }
+
+    fn resolve_indirect(
+        &self,
+        indirect_identifier: &IndirectIdentifier,
+    ) -> Option<&ArtifactManifestData> {
+        match indirect_identifier {
+            IndirectIdentifier::ArtifactId(artifact_id, _)
+            | IndirectIdentifier::ShallowArtifactId(artifact_id, _)
+            | IndirectIdentifier::CheckedArtifactId(artifact_id, _) => {
+                self.manifest.get(artifact_id)
+            }
+            _ => unimplemented!(),
+        }
+    }
+
+    fn request_metadata(
+        &self,
+        _build_hash: ManifestBuildHash,
+        load_handle: LoadHandle,
+        artifact_id: ArtifactId,
+    ) {
+        self.metadata_requests.lock().unwrap().push(artifact_id);
+        if self.unresponsive.contains(&artifact_id) {
+            return;
+        }
+
+        let manifest_entry = self.manifest.get(&artifact_id).unwrap();
+        self.events_tx
+            .send(LoaderEvent::MetadataRequestComplete(
+                RequestMetadataResult {
+                    artifact_id,
+                    load_handle,
+                    result: Ok(ArtifactMetadata {
+                        dependencies: self
+                            .dependencies
+                            .get(&artifact_id)
+                            .cloned()
+                            .unwrap_or_default(),
+                        artifact_type_id: ArtifactTypeId::from_uuid(manifest_entry.artifact_type),
+                        hash: manifest_entry.simple_build_hash,
+                    }),
+                },
+            ))
+            .unwrap();
+    }
+
+    fn request_data(
+        &self,
+        _build_hash: ManifestBuildHash,
+        load_handle: LoadHandle,
+        artifact_id: ArtifactId,
+        hash: u64,
+    ) {
+        if self.unresponsive.contains(&artifact_id) {
+            return;
+        }
+
+        let data = (self.payload)(artifact_id, hash);
+        self.data_reads.lock().unwrap().push(0..data.len() as u64);
+        self.events_tx
+            .send(LoaderEvent::DataRequestComplete(RequestDataResult {
+                artifact_id,
+                load_handle,
+                result: Ok(ArtifactData { data }),
+            }))
+            .unwrap();
+    }
+
+    fn request_data_range(
+        &self,
+        _build_hash: ManifestBuildHash,
+        artifact_id: ArtifactId,
+        hash: u64,
+        range: Range<u64>,
+    ) -> bool {
+        if !self.supports_ranges {
+            return false;
+        }
+
+        self.data_reads.lock().unwrap().push(range.clone());
+        // Like reading a file, the data is cut short at the end of the payload
+        let payload = (self.payload)(artifact_id, hash);
+        let data = payload
+            .get(range.start as usize..(range.end as usize).min(payload.len()))
+            .unwrap_or_default()
+            .to_vec();
+        self.events_tx
+            .send(LoaderEvent::DataRangeRequestComplete(
+                RequestDataRangeResult {
+                    artifact_id,
+                    hash,
+                    range,
+                    result: Ok(ArtifactData { data }),
+                },
+            ))
+            .unwrap();
+        true
+    }
+}
@@ -1,9 +1,10 @@
 use crate::edit_context::EditContext;
 use crate::{AssetId, AssetSourceId, DataSource, PathNodeRoot, PendingFileOperations};
-use hydrate_base::hashing::HashMap;
+use hydrate_base::canonicalize_path;
+use hydrate_base::hashing::{HashMap, HashSet};
 use hydrate_base::uuid_path::{path_to_uuid, uuid_to_path};
 use hydrate_data::{AssetLocation, HashObjectMode};
-use hydrate_pipeline::{HydrateProjectConfiguration, ImportJobToQueue};
+use hydrate_pipeline::{HydrateProjectConfiguration, ImportJobToQueue, PipelineResult};
 use hydrate_schema::SchemaNamedType;
 use std::path::PathBuf;
 
@@ -116,16 +117,18 @@ impl DataSource for FileSystemIdBasedDataSource {
 
     fn persist_generated_asset(
         &mut self,
+        _project_config: &HydrateProjectConfiguration,
         _edit_context: &mut EditContext,
         _asset_id: AssetId,
-    ) {
+    ) -> PipelineResult<()> {
         // this data source does not contain source files so can't have generated assets
+        Ok(())
     }
 
     #[profiling::function]
     fn load_from_storage(
         &mut self,
-        _project_config: &HydrateProjectConfiguration,
+        project_config: &HydrateProjectConfiguration,
         edit_context: &mut EditContext,
         _import_job_to_queue: &mut ImportJobToQueue,
     ) {
@@ -161,7 +164,8 @@ impl DataSource for FileSystemIdBasedDataSource {
 
         for file in walker {
             if let Ok(file) = file {
-                let file = dunce::canonicalize(&file.path()).unwrap();
+                let file =
+                    canonicalize_path(file.path(), project_config.path_canonicalization).unwrap();
 
                 let asset_file_metadata = FileMetadata::new(&std::fs::metadata(&file).unwrap());
 
@@ -172,15 +176,26 @@ impl DataSource for FileSystemIdBasedDataSource {
                     AssetLocation::new(AssetId(*self.asset_source_id.uuid()));
 
                 let schema_set = edit_context.schema_set().clone();
-                crate::json_storage::AssetJson::load_asset_from_string(
+                let load_result = crate::json_storage::AssetJson::load_asset_from_string(
                     edit_context,
                     &schema_set,
                     Some(file_uuid),
                     default_asset_location,
                     None,
                     &contents,
-                )
-                .unwrap();
+                );
+
+                // i.e. the asset's type is from a plugin that isn't loaded. The asset is not
+                // tracked, so saving will not overwrite or delete the file.
+                if let Err(e) = load_result {
+                    log::warn!(
+                        "Skipping asset file {:?} that failed to load: {:?}",
+                        file,
+                        e
+                    );
+                    continue;
+                }
+
                 let asset_id = AssetId::from_uuid(file_uuid);
 
                 let object_hash = edit_context
@@ -202,8 +217,9 @@ impl DataSource for FileSystemIdBasedDataSource {
 
     fn flush_to_storage(
         &mut self,
+        project_config: &HydrateProjectConfiguration,
         edit_context: &mut EditContext,
-    ) {
+    ) -> PipelineResult<()> {
         profiling::scope!(&format!(
             "flush_to_storage {:?}",
             self.file_system_root_path
@@ -275,16 +291,16 @@ impl DataSource for FileSystemIdBasedDataSource {
             let file_path = self.path_for_asset(asset_id);
 
             if let Some(parent) = file_path.parent() {
-                std::fs::create_dir_all(parent).unwrap();
+                std::fs::create_dir_all(parent)?;
             }
 
-            std::fs::write(&file_path, data).unwrap();
+            super::write_file_to_storage(&file_path, data, project_config.fsync_on_save)?;
 
             let object_hash = edit_context
                 .data_set()
                 .hash_object(asset_id, HashObjectMode::FullObjectWithLocationId)
                 .unwrap();
-            let asset_file_metadata = FileMetadata::new(&std::fs::metadata(&file_path).unwrap());
+            let asset_file_metadata = FileMetadata::new(&std::fs::metadata(&file_path)?);
 
             self.assets_disk_state.insert(
                 asset_id,
@@ -296,15 +312,28 @@ impl DataSource for FileSystemIdBasedDataSource {
         }
 
         //
-        // Delete assets that no longer exist
+        // Delete assets that no longer exist. This happens after all writes are complete.
         //
+        let mut directories_to_sync = HashSet::<PathBuf>::default();
         for asset_id in pending_deletes {
             let file_path = self.path_for_asset(asset_id);
-            std::fs::remove_file(&file_path).unwrap();
+            std::fs::remove_file(&file_path)?;
             self.assets_disk_state.remove(&asset_id);
 
+            if let Some(parent) = file_path.parent() {
+                directories_to_sync.insert(parent.to_path_buf());
+            }
+
             //TODO: Clean up empty parent dirs?
         }
+
+        if project_config.fsync_on_save {
+            for directory in directories_to_sync {
+                super::sync_directory(&directory)?;
+            }
+        }
+
+        Ok(())
     }
 
     fn edit_context_has_unsaved_changes(
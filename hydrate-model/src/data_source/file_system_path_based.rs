@@ -2,6 +2,7 @@ use crate::edit_context::EditContext;
 use crate::{AssetSourceId, DataSource, PendingFileOperations};
 use crate::{PathNode, PathNodeRoot};
 use hydrate_base::hashing::HashSet;
+use hydrate_base::{canonicalize_path, PathCanonicalization};
 use hydrate_data::json_storage::{MetaFile, MetaFileJson};
 use hydrate_data::{
     AssetId, AssetLocation, AssetName, CanonicalPathReference, DataSetAssetInfo, HashObjectMode,
@@ -16,6 +17,7 @@ use hydrate_schema::{HashMap, SchemaNamedType};
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::SystemTime;
 use uuid::Uuid;
 
 // New trait design
@@ -43,27 +45,96 @@ use uuid::Uuid;
 
 // Temporary struct used during load_from_storage call
 struct ScannedSourceFile<'a> {
+    source_file_path: PathBuf,
     meta_file: MetaFile,
     importer: &'a Arc<dyn Importer>,
     scanned_importables: Vec<ScannedImportable>,
 }
 
+#[derive(PartialEq)]
 struct FileMetadata {
-    // size_in_bytes: u64,
-    // last_modified_time: Option<SystemTime>,
+    size_in_bytes: u64,
+    last_modified_time: Option<SystemTime>,
 }
 
 impl FileMetadata {
-    pub fn new(_metadata: &std::fs::Metadata) -> Self {
+    pub fn new(metadata: &std::fs::Metadata) -> Self {
         FileMetadata {
-            // size_in_bytes: metadata.len(),
-            // last_modified_time: metadata.modified().ok()
+            size_in_bytes: metadata.len(),
+            last_modified_time: metadata.modified().ok(),
         }
     }
+}
 
-    // pub fn has_changed(&self, metadata: &std::fs::Metadata) -> bool {
-    //     self.size_in_bytes != metadata.len() || self.last_modified_time != metadata.modified().ok()
-    // }
+// Everything under the data source's root as of the last load, save, or poll. Polling compares
+// against this to find what was changed outside the editor.
+#[derive(Default)]
+struct StorageSnapshot {
+    directories: HashSet<PathBuf>,
+    files: HashMap<PathBuf, FileMetadata>,
+}
+
+impl StorageSnapshot {
+    fn scan(
+        file_system_root_path: &Path,
+        path_canonicalization: PathCanonicalization,
+    ) -> Self {
+        profiling::scope!("StorageSnapshot::scan");
+        let mut storage_snapshot = StorageSnapshot::default();
+        let walker = globwalk::GlobWalkerBuilder::from_patterns(file_system_root_path, &["**"])
+            .build()
+            .unwrap();
+
+        for file in walker.flatten() {
+            let path = canonicalize_path(file.path(), path_canonicalization).unwrap();
+            // The file may have been removed since it was found
+            let Ok(metadata) = std::fs::metadata(&path) else {
+                continue;
+            };
+
+            if metadata.is_dir() {
+                storage_snapshot.directories.insert(path);
+            } else {
+                storage_snapshot
+                    .files
+                    .insert(path, FileMetadata::new(&metadata));
+            }
+        }
+
+        storage_snapshot
+    }
+
+    // Records the current state of a path that exists on disk
+    fn record_path(
+        &mut self,
+        path: &Path,
+        path_canonicalization: PathCanonicalization,
+    ) {
+        let Ok(path) = canonicalize_path(path, path_canonicalization) else {
+            return;
+        };
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            return;
+        };
+
+        if metadata.is_dir() {
+            self.directories.insert(path);
+        } else {
+            self.files.insert(path, FileMetadata::new(&metadata));
+        }
+    }
+
+    // Must be called before the path is removed, it can't be canonicalized afterwards
+    fn forget_path(
+        &mut self,
+        path: &Path,
+        path_canonicalization: PathCanonicalization,
+    ) {
+        let path =
+            canonicalize_path(path, path_canonicalization).unwrap_or_else(|_| path.to_path_buf());
+        self.directories.remove(&path);
+        self.files.remove(&path);
+    }
 }
 
 // Key: PathBuf
@@ -72,8 +143,8 @@ struct SourceFileDiskState {
     generated_assets: HashSet<AssetId>,
     persisted_assets: HashSet<AssetId>,
     //source_file_metadata: FileMetadata,
-    _importer_id: ImporterId,
-    _importables: HashMap<ImportableName, AssetId>,
+    importer_id: ImporterId,
+    importables: HashMap<ImportableName, AssetId>,
 }
 
 // Key: AssetId
@@ -124,6 +195,9 @@ pub struct FileSystemPathBasedDataSource {
     //all_assigned_path_ids: HashMap<PathBuf, AssetId>,
     source_files_disk_state: HashMap<PathBuf, SourceFileDiskState>,
     assets_disk_state: HashMap<AssetId, AssetDiskState>,
+    storage_snapshot: StorageSnapshot,
+    // Walking the whole data source is slow for large projects, so polling scans on another thread
+    pending_storage_scan: Option<std::thread::JoinHandle<StorageSnapshot>>,
 
     path_node_schema: SchemaNamedType,
     path_node_root_schema: SchemaNamedType,
@@ -164,6 +238,8 @@ impl FileSystemPathBasedDataSource {
 
             source_files_disk_state: Default::default(),
             assets_disk_state: Default::default(),
+            storage_snapshot: Default::default(),
+            pending_storage_scan: None,
 
             path_node_schema,
             path_node_root_schema,
@@ -387,11 +463,384 @@ impl FileSystemPathBasedDataSource {
         AssetLocation::new(previous_asset_id)
     }
 
+    // Ensures a path node exists for the directory, returning the disk state to track for it
+    fn load_directory(
+        &self,
+        directory: PathBuf,
+        path_to_path_node_id: &mut HashMap<PathBuf, AssetId>,
+        edit_context: &mut EditContext,
+    ) -> (AssetId, AssetDiskState) {
+        let asset_location =
+            self.ensure_asset_location_exists(&directory, path_to_path_node_id, edit_context);
+        let asset_id = asset_location.path_node_id();
+
+        let asset_file_metadata = FileMetadata::new(&std::fs::metadata(&directory).unwrap());
+        let object_hash = edit_context
+            .data_set()
+            .hash_object(asset_id, HashObjectMode::FullObjectWithLocationChainNames)
+            .unwrap();
+
+        (
+            asset_id,
+            AssetDiskState::Persisted(PersistedAssetDiskState {
+                asset_file_path: directory,
+                _asset_file_metadata: asset_file_metadata,
+                object_hash,
+            }),
+        )
+    }
+
+    // Loads the asset file into the edit context, replacing the asset if it is already loaded.
+    // Returns the disk state to track for it, or None if the file failed to load.
+    fn load_asset_file(
+        &self,
+        asset_file: PathBuf,
+        path_to_path_node_id: &mut HashMap<PathBuf, AssetId>,
+        edit_context: &mut EditContext,
+    ) -> Option<(AssetId, AssetDiskState)> {
+        //println!("asset file {:?}", asset_file);
+        let contents = std::fs::read_to_string(asset_file.as_path()).unwrap();
+
+        let asset_location = self.ensure_asset_location_exists(
+            asset_file.as_path().parent().unwrap(),
+            path_to_path_node_id,
+            edit_context,
+        );
+        let default_asset_location = AssetLocation::new(AssetId(*self.asset_source_id.uuid()));
+        let schema_set = edit_context.schema_set().clone();
+        let load_result = crate::json_storage::AssetJson::load_asset_from_string(
+            edit_context,
+            &schema_set,
+            None,
+            default_asset_location,
+            Some(asset_location.clone()),
+            &contents,
+        );
+
+        // i.e. the asset's type is from a plugin that isn't loaded. The asset is not
+        // tracked, so saving will not overwrite or delete the file.
+        let asset_id = match load_result {
+            Ok(asset_id) => asset_id,
+            Err(e) => {
+                log::warn!(
+                    "Skipping asset file {:?} that failed to load: {:?}",
+                    asset_file,
+                    e
+                );
+                return None;
+            }
+        };
+
+        let asset_file_metadata = FileMetadata::new(&std::fs::metadata(&asset_file).unwrap());
+
+        let object_hash = edit_context
+            .data_set()
+            .hash_object(asset_id, HashObjectMode::FullObjectWithLocationChainNames)
+            .unwrap();
+
+        Some((
+            asset_id,
+            AssetDiskState::Persisted(PersistedAssetDiskState {
+                asset_file_path: asset_file,
+                _asset_file_metadata: asset_file_metadata,
+                object_hash,
+            }),
+        ))
+    }
+
+    // Scans the source file and ensures stable IDs exist for all its importables, reusing the IDs
+    // in the meta file and writing any new ones back to it. Returns None if there isn't exactly
+    // one importer for the file, scanning failed, or the meta file could not be written.
+    fn scan_source_file<'a>(
+        importer_registry: &'a ImporterRegistry,
+        project_config: &HydrateProjectConfiguration,
+        edit_context: &EditContext,
+        source_file: &Path,
+        mut meta_file: MetaFile,
+        import_job_to_queue: &mut ImportJobToQueue,
+    ) -> Option<(ScannedSourceFile<'a>, SourceFileDiskState)> {
+        //println!("source file first pass {:?}", source_file);
+        // Does a meta file exist?
+        // - If it does: re-import it, but only create new assets if there is not already an asset file
+        // - If it does not: re-import it and create all new asset files
+
+        let extension = &source_file.extension();
+        if extension.is_none() {
+            // Can happen for files like .DS_Store
+            return None;
+        }
+
+        let importers =
+            importer_registry.importers_for_file_extension(&extension.unwrap().to_string_lossy());
+
+        if importers.is_empty() {
+            // No importer found
+            return None;
+        } else if importers.len() > 1 {
+            // Multiple importers found, no way of disambiguating
+            return None;
+        }
+
+        let importer = importer_registry.importer(importers[0]).unwrap();
+
+        let mut scanned_importables = HashMap::default();
+        {
+            profiling::scope!(&format!(
+                "Importer::scan_file {}",
+                source_file.to_string_lossy()
+            ));
+            let scan_result = importer.scan_file(ScanContext::new(
+                source_file,
+                edit_context.schema_set(),
+                importer_registry,
+                project_config,
+                &mut scanned_importables,
+                &mut import_job_to_queue.log_data.log_events,
+            ));
+
+            if let Err(e) = scan_result {
+                import_job_to_queue
+                    .log_data
+                    .log_events
+                    .push(ImportLogEvent {
+                        path: source_file.to_path_buf(),
+                        asset_id: None,
+                        level: LogEventLevel::FatalError,
+                        message: format!("scan_file returned error: {}", e.to_string()),
+                    });
+
+                return None;
+            }
+        };
+
+        for (_, scanned_importable) in &scanned_importables {
+            // Does it exist in the meta file? If so, we need to reuse the ID
+            meta_file
+                .past_id_assignments
+                .entry(scanned_importable.name.clone())
+                .or_insert_with(|| AssetId::from_uuid(Uuid::new_v4()));
+        }
+
+        let mut meta_file_path = source_file.as_os_str().to_os_string();
+        meta_file_path.push(".meta");
+
+        //let source_file_metadata = FileMetadata::new(&std::fs::metadata(&source_file).unwrap());
+
+        let mut importables = HashMap::<ImportableName, AssetId>::default();
+        for (_, scanned_importable) in &scanned_importables {
+            let imporable_asset_id = meta_file.past_id_assignments.get(&scanned_importable.name);
+            importables.insert(
+                scanned_importable.name.clone(),
+                *imporable_asset_id.unwrap(),
+            );
+        }
+
+        let source_file_disk_state = SourceFileDiskState {
+            generated_assets: Default::default(),
+            persisted_assets: Default::default(),
+            //source_file_metadata,
+            importer_id: importer.importer_id(),
+            importables,
+        };
+
+        // The importables' IDs would not be stable without the meta file
+        if let Err(e) = super::write_file_to_storage(
+            Path::new(&meta_file_path),
+            MetaFileJson::store_to_string(&meta_file),
+            project_config.fsync_on_save,
+        ) {
+            import_job_to_queue
+                .log_data
+                .log_events
+                .push(ImportLogEvent {
+                    path: source_file.to_path_buf(),
+                    asset_id: None,
+                    level: LogEventLevel::FatalError,
+                    message: format!("Failed to write meta file {:?}: {}", meta_file_path, e),
+                });
+
+            return None;
+        }
+
+        Some((
+            ScannedSourceFile {
+                source_file_path: source_file.to_path_buf(),
+                meta_file,
+                importer,
+                scanned_importables: scanned_importables.into_values().collect(),
+            },
+            source_file_disk_state,
+        ))
+    }
+
+    // Requests import of the source file's importables into the given location. The disk state of
+    // the source file must already be in source_files_disk_state, and is updated with the assets
+    // that the import will produce.
+    fn queue_source_file_import(
+        project_config: &HydrateProjectConfiguration,
+        edit_context: &EditContext,
+        scanned_source_file: &ScannedSourceFile,
+        import_location: AssetLocation,
+        source_files_disk_state: &mut HashMap<PathBuf, SourceFileDiskState>,
+        assets_disk_state: &mut HashMap<AssetId, AssetDiskState>,
+        import_job_to_queue: &mut ImportJobToQueue,
+    ) {
+        let source_file_path = &scanned_source_file.source_file_path;
+        let mut requested_importables = HashMap::default();
+        for scanned_importable in &scanned_source_file.scanned_importables {
+            // The ID assigned to this importable. We have this now because we previously scanned
+            // all source files and assigned IDs to any importable
+            let importable_asset_id = *scanned_source_file
+                .meta_file
+                .past_id_assignments
+                .get(&scanned_importable.name)
+                .unwrap();
+
+            // Create an asset name for this asset
+            let asset_name =
+                hydrate_pipeline::create_asset_name(source_file_path, scanned_importable);
+
+            let asset_file_exists = assets_disk_state.get(&importable_asset_id).is_some();
+            let asset_is_persisted = scanned_source_file
+                .meta_file
+                .persisted_assets
+                .contains(&importable_asset_id);
+
+            if asset_is_persisted && !asset_file_exists {
+                // If the asset is persisted but deleted, we do not want to import it
+                continue;
+            }
+
+            let source_file_disk_state = source_files_disk_state.get_mut(source_file_path).unwrap();
+            if !asset_is_persisted {
+                assets_disk_state.insert(
+                    importable_asset_id,
+                    AssetDiskState::Generated(GeneratedAssetDiskState {
+                        source_file_path: source_file_path.clone(),
+                    }),
+                );
+                source_file_disk_state
+                    .generated_assets
+                    .insert(importable_asset_id);
+            } else {
+                assert!(asset_file_exists);
+                assert_eq!(
+                    edit_context
+                        .asset_schema(importable_asset_id)
+                        .unwrap()
+                        .fingerprint(),
+                    scanned_importable.asset_type.fingerprint()
+                );
+                //edit_context.set_asset_name(importable_asset_id, asset_name);
+                //edit_context.set_asset_location(importable_asset_id, *import_location);
+                //edit_context.set_import_info(importable_asset_id, import_info);
+
+                // We iterated through asset files already, so just check that we inserted a AssetDiskState::Persisted into this map
+                assert!(assets_disk_state
+                    .get(&importable_asset_id)
+                    .unwrap()
+                    .is_persisted());
+                source_file_disk_state
+                    .persisted_assets
+                    .insert(importable_asset_id);
+            }
+
+            let canonical_path_references = Self::find_canonical_path_references(
+                project_config,
+                source_file_path,
+                &scanned_importable,
+                source_files_disk_state,
+            );
+            match canonical_path_references {
+                Ok(canonical_path_references) => {
+                    let source_file = PathReference::new(
+                        "".to_string(),
+                        source_file_path.to_string_lossy().to_string(),
+                        scanned_importable.name.clone(),
+                    )
+                    .simplify(project_config);
+
+                    let requested_importable = RequestedImportable {
+                        asset_id: importable_asset_id,
+                        schema: scanned_importable.asset_type.clone(),
+                        asset_name,
+                        asset_location: import_location,
+                        //importer_id: scanned_source_file.importer.importer_id(),
+                        source_file,
+                        canonical_path_references,
+                        path_references: scanned_importable.referenced_source_files.clone(),
+                        replace_with_default_asset: !asset_is_persisted,
+                    };
+
+                    requested_importables
+                        .insert(scanned_importable.name.clone(), requested_importable);
+                }
+                Err(e) => {
+                    import_job_to_queue
+                        .log_data
+                        .log_events
+                        .push(ImportLogEvent {
+                            path: source_file_path.clone(),
+                            asset_id: Some(importable_asset_id),
+                            level: LogEventLevel::FatalError,
+                            message: format!(
+                                "While resolving references to other assets: {}",
+                                e.to_string()
+                            ),
+                        });
+                }
+            }
+        }
+
+        if !requested_importables.is_empty() {
+            import_job_to_queue
+                .import_job_source_files
+                .push(ImportJobSourceFile {
+                    source_file_path: source_file_path.to_path_buf(),
+                    importer_id: scanned_source_file.importer.importer_id(),
+                    requested_importables,
+                    import_type: ImportType::ImportIfImportDataStale,
+                });
+        }
+    }
+
+    // Files written by the editor are not changes to pick up when polling. This records a written
+    // path and any directories created for it in the snapshot.
+    fn record_written_path(
+        &mut self,
+        path: &Path,
+        project_config: &HydrateProjectConfiguration,
+    ) {
+        for ancestor in path.ancestors() {
+            if ancestor == self.file_system_root_path
+                || !ancestor.starts_with(&self.file_system_root_path)
+            {
+                break;
+            }
+
+            self.storage_snapshot
+                .record_path(ancestor, project_config.path_canonicalization);
+        }
+
+        // A scan that is in progress may have missed the write
+        self.pending_storage_scan = None;
+    }
+
+    fn forget_removed_path(
+        &mut self,
+        path: &Path,
+        project_config: &HydrateProjectConfiguration,
+    ) {
+        self.storage_snapshot
+            .forget_path(path, project_config.path_canonicalization);
+        self.pending_storage_scan = None;
+    }
+
     fn find_canonical_path_references(
         project_config: &HydrateProjectConfiguration,
         source_file_path: &PathBuf,
         scanned_importable: &ScannedImportable,
-        scanned_source_files: &HashMap<PathBuf, ScannedSourceFile>,
+        source_files_disk_state: &HashMap<PathBuf, SourceFileDiskState>,
     ) -> PipelineResult<HashMap<CanonicalPathReference, AssetId>> {
         // For any referenced file, locate the AssetID at that path. It must be in this data source,
         // and at this point must exist in the meta file.
@@ -404,7 +853,7 @@ impl FileSystemPathBasedDataSource {
             //println!("referenced {:?} {:?}", path_reference_absolute_path, scanned_source_files.keys());
             //println!("pull from {:?}", scanned_source_files.keys());
             //println!("referenced {:?}", path_reference_absolute_path);
-            let referenced_scanned_source_file = scanned_source_files
+            let referenced_source_file = source_files_disk_state
                 .get(&PathBuf::from(path_reference_absolute.path()))
                 .ok_or_else(|| format!(
                     "{:?} is referencing source file {:?} via absolute path {:?} but it does not exist or failed to import",
@@ -412,23 +861,18 @@ impl FileSystemPathBasedDataSource {
                     path_reference.path(),
                     path_reference_absolute
                 ))?;
-            assert_eq!(
-                importer_id,
-                referenced_scanned_source_file.importer.importer_id()
-            );
+            assert_eq!(importer_id, referenced_source_file.importer_id);
             canonical_path_references.insert(
                 path_reference.clone(),
-                *referenced_scanned_source_file
-                    .meta_file
-                    .past_id_assignments
+                *referenced_source_file
+                    .importables
                     .get(path_reference.importable_name())
                     .ok_or_else(|| format!(
                         "{:?} is referencing importable {:?} in {:?} but it was not found when the file was scanned",
                         source_file_path,
                         path_reference.path(),
                         path_reference.importable_name())
-                    )
-                    .unwrap()
+                    )?
             );
         }
         Ok(canonical_path_references)
@@ -454,25 +898,24 @@ impl DataSource for FileSystemPathBasedDataSource {
 
     fn persist_generated_asset(
         &mut self,
+        project_config: &HydrateProjectConfiguration,
         edit_context: &mut EditContext,
         asset_id: AssetId,
-    ) {
+    ) -> PipelineResult<()> {
         if !self.is_asset_owned_by_this_data_source(edit_context, asset_id) {
-            return;
+            return Ok(());
         }
 
-        let old_asset_disk_state = self.assets_disk_state.get(&asset_id).unwrap();
-        if !old_asset_disk_state.is_generated() {
-            return;
-        }
-
-        let old_asset_disk_state = self.assets_disk_state.remove(&asset_id);
-        let source_file_path = old_asset_disk_state
+        // The asset stays generated if writing it fails
+        let Some(old_asset_disk_state) = self
+            .assets_disk_state
+            .get(&asset_id)
             .unwrap()
             .as_generated_asset_disk_state()
-            .unwrap()
-            .source_file_path
-            .clone();
+        else {
+            return Ok(());
+        };
+        let source_file_path = old_asset_disk_state.source_file_path.clone();
 
         let mut meta_file_path = source_file_path.clone().into_os_string();
         meta_file_path.push(".meta");
@@ -492,20 +935,20 @@ impl DataSource for FileSystemPathBasedDataSource {
             None,
         );
 
-        std::fs::create_dir_all(&containing_file_path).unwrap();
-        std::fs::write(&asset_file_path, data).unwrap();
+        std::fs::create_dir_all(&containing_file_path)?;
+        super::write_file_to_storage(&asset_file_path, data, project_config.fsync_on_save)?;
 
         //
         // Update the meta file
         //
-        let contents = std::fs::read_to_string(&meta_file_path).unwrap();
+        let contents = std::fs::read_to_string(&meta_file_path)?;
         let mut meta_file_contents = MetaFileJson::load_from_string(&contents);
         meta_file_contents.persisted_assets.insert(asset_id);
-        std::fs::write(
-            &meta_file_path,
+        super::write_file_to_storage(
+            Path::new(&meta_file_path),
             MetaFileJson::store_to_string(&meta_file_contents),
-        )
-        .unwrap();
+            project_config.fsync_on_save,
+        )?;
 
         //
         // Update representation of disk state
@@ -515,7 +958,7 @@ impl DataSource for FileSystemPathBasedDataSource {
             .hash_object(asset_id, HashObjectMode::FullObjectWithLocationChainNames)
             .unwrap();
 
-        let asset_file_metadata = FileMetadata::new(&std::fs::metadata(&asset_file_path).unwrap());
+        let asset_file_metadata = FileMetadata::new(&std::fs::metadata(&asset_file_path)?);
         self.assets_disk_state.insert(
             asset_id,
             AssetDiskState::Persisted(PersistedAssetDiskState {
@@ -531,6 +974,10 @@ impl DataSource for FileSystemPathBasedDataSource {
             .unwrap();
         source_file_disk_state.generated_assets.remove(&asset_id);
         source_file_disk_state.persisted_assets.insert(asset_id);
+
+        self.record_written_path(&asset_file_path, project_config);
+        self.record_written_path(Path::new(&meta_file_path), project_config);
+        Ok(())
     }
 
     fn load_from_storage(
@@ -546,12 +993,9 @@ impl DataSource for FileSystemPathBasedDataSource {
         //
         // Delete all assets from the database owned by this data source
         //
-        let mut assets_to_delete = Vec::default();
-        for (asset_id, _) in edit_context.assets() {
-            if self.is_asset_owned_by_this_data_source(edit_context, *asset_id) {
-                assets_to_delete.push(*asset_id);
-            }
-        }
+        let assets_to_delete: Vec<_> = edit_context
+            .assets_for_source(self.asset_source_id)
+            .collect();
 
         for asset_to_delete in assets_to_delete {
             edit_context.delete_asset(asset_to_delete).unwrap();
@@ -583,29 +1027,12 @@ impl DataSource for FileSystemPathBasedDataSource {
 
             for file in walker {
                 if let Ok(file) = file {
-                    let asset_file = dunce::canonicalize(&file.path()).unwrap();
-                    let asset_location = self.ensure_asset_location_exists(
-                        &asset_file,
-                        &mut path_to_path_node_id,
-                        edit_context,
-                    );
-                    let asset_id = asset_location.path_node_id();
-
-                    let asset_file_metadata =
-                        FileMetadata::new(&std::fs::metadata(&asset_file).unwrap());
-                    let object_hash = edit_context
-                        .data_set()
-                        .hash_object(asset_id, HashObjectMode::FullObjectWithLocationChainNames)
-                        .unwrap();
-
-                    assets_disk_state.insert(
-                        asset_id,
-                        AssetDiskState::Persisted(PersistedAssetDiskState {
-                            asset_file_path: asset_file,
-                            _asset_file_metadata: asset_file_metadata,
-                            object_hash,
-                        }),
-                    );
+                    let asset_file =
+                        canonicalize_path(file.path(), project_config.path_canonicalization)
+                            .unwrap();
+                    let (asset_id, asset_disk_state) =
+                        self.load_directory(asset_file, &mut path_to_path_node_id, edit_context);
+                    assets_disk_state.insert(asset_id, asset_disk_state);
                 }
             }
 
@@ -623,7 +1050,8 @@ impl DataSource for FileSystemPathBasedDataSource {
 
             for file in walker {
                 if let Ok(file) = file {
-                    let file = dunce::canonicalize(&file.path()).unwrap();
+                    let file = canonicalize_path(file.path(), project_config.path_canonicalization)
+                        .unwrap();
                     if file.extension() == Some(OsStr::new("meta")) {
                         meta_files.push(file.to_path_buf());
                     } else if file.extension() == Some(OsStr::new("af")) {
@@ -664,43 +1092,11 @@ impl DataSource for FileSystemPathBasedDataSource {
         {
             profiling::scope!("Load Asset Files");
             for asset_file in asset_files {
-                //println!("asset file {:?}", asset_file);
-                let contents = std::fs::read_to_string(asset_file.as_path()).unwrap();
-
-                let asset_location = self.ensure_asset_location_exists(
-                    asset_file.as_path().parent().unwrap(),
-                    &mut path_to_path_node_id,
-                    edit_context,
-                );
-                let default_asset_location =
-                    AssetLocation::new(AssetId(*self.asset_source_id.uuid()));
-                let schema_set = edit_context.schema_set().clone();
-                let asset_id = crate::json_storage::AssetJson::load_asset_from_string(
-                    edit_context,
-                    &schema_set,
-                    None,
-                    default_asset_location,
-                    Some(asset_location.clone()),
-                    &contents,
-                )
-                .unwrap();
-
-                let asset_file_metadata =
-                    FileMetadata::new(&std::fs::metadata(&asset_file).unwrap());
-
-                let object_hash = edit_context
-                    .data_set()
-                    .hash_object(asset_id, HashObjectMode::FullObjectWithLocationChainNames)
-                    .unwrap();
-
-                assets_disk_state.insert(
-                    asset_id,
-                    AssetDiskState::Persisted(PersistedAssetDiskState {
-                        asset_file_path: asset_file,
-                        _asset_file_metadata: asset_file_metadata,
-                        object_hash,
-                    }),
-                );
+                if let Some((asset_id, asset_disk_state)) =
+                    self.load_asset_file(asset_file, &mut path_to_path_node_id, edit_context)
+                {
+                    assets_disk_state.insert(asset_id, asset_disk_state);
+                }
             }
         }
 
@@ -724,113 +1120,21 @@ impl DataSource for FileSystemPathBasedDataSource {
             profiling::scope!("Scan Source Files");
 
             for source_file in source_files {
-                //println!("source file first pass {:?}", source_file);
-                // Does a meta file exist?
-                // - If it does: re-import it, but only create new assets if there is not already an asset file
-                // - If it does not: re-import it and create all new asset files
-
-                let extension = &source_file.extension();
-                if extension.is_none() {
-                    // Can happen for files like .DS_Store
-                    continue;
-                }
-
-                let importers = self
-                    .importer_registry
-                    .importers_for_file_extension(&extension.unwrap().to_string_lossy());
-
-                if importers.is_empty() {
-                    // No importer found
-                } else if importers.len() > 1 {
-                    // Multiple importers found, no way of disambiguating
-                } else {
-                    let importer = self.importer_registry.importer(importers[0]).unwrap();
-
-                    let mut scanned_importables = HashMap::default();
-                    {
-                        profiling::scope!(&format!(
-                            "Importer::scan_file {}",
-                            source_file.to_string_lossy()
-                        ));
-                        let scan_result = importer.scan_file(ScanContext::new(
-                            &source_file,
-                            edit_context.schema_set(),
-                            &self.importer_registry,
-                            project_config,
-                            &mut scanned_importables,
-                            &mut import_job_to_queue.log_data.log_events,
-                        ));
-
-                        match scan_result {
-                            Ok(result) => result,
-                            Err(e) => {
-                                import_job_to_queue
-                                    .log_data
-                                    .log_events
-                                    .push(ImportLogEvent {
-                                        path: source_file.clone(),
-                                        asset_id: None,
-                                        level: LogEventLevel::FatalError,
-                                        message: format!(
-                                            "scan_file returned error: {}",
-                                            e.to_string()
-                                        ),
-                                    });
-
-                                continue;
-                            }
-                        }
-                    };
-
-                    //println!("  find meta file {:?}", source_file);
-                    let mut meta_file = source_file_meta_files
-                        .get(&source_file)
-                        .cloned()
-                        .unwrap_or_default();
-                    for (_, scanned_importable) in &scanned_importables {
-                        // Does it exist in the meta file? If so, we need to reuse the ID
-                        meta_file
-                            .past_id_assignments
-                            .entry(scanned_importable.name.clone())
-                            .or_insert_with(|| AssetId::from_uuid(Uuid::new_v4()));
-                    }
-
-                    let mut meta_file_path = source_file.clone().into_os_string();
-                    meta_file_path.push(".meta");
-
-                    //let source_file_metadata = FileMetadata::new(&std::fs::metadata(&source_file).unwrap());
-
-                    let mut importables = HashMap::<ImportableName, AssetId>::default();
-                    for (_, scanned_importable) in &scanned_importables {
-                        let imporable_asset_id =
-                            meta_file.past_id_assignments.get(&scanned_importable.name);
-                        importables.insert(
-                            scanned_importable.name.clone(),
-                            *imporable_asset_id.unwrap(),
-                        );
-                    }
-
-                    source_files_disk_state.insert(
-                        source_file.clone(),
-                        SourceFileDiskState {
-                            generated_assets: Default::default(),
-                            persisted_assets: Default::default(),
-                            //source_file_metadata,
-                            _importer_id: importer.importer_id(),
-                            _importables: importables,
-                        },
-                    );
-
-                    std::fs::write(meta_file_path, MetaFileJson::store_to_string(&meta_file))
-                        .unwrap();
-                    scanned_source_files.insert(
-                        source_file,
-                        ScannedSourceFile {
-                            meta_file,
-                            importer,
-                            scanned_importables: scanned_importables.into_values().collect(),
-                        },
-                    );
+                //println!("  find meta file {:?}", source_file);
+                let meta_file = source_file_meta_files
+                    .get(&source_file)
+                    .cloned()
+                    .unwrap_or_default();
+                if let Some((scanned_source_file, source_file_disk_state)) = Self::scan_source_file(
+                    &self.importer_registry,
+                    project_config,
+                    edit_context,
+                    &source_file,
+                    meta_file,
+                    import_job_to_queue,
+                ) {
+                    source_files_disk_state.insert(source_file.clone(), source_file_disk_state);
+                    scanned_source_files.insert(source_file, scanned_source_file);
                 }
             }
         }
@@ -846,131 +1150,25 @@ impl DataSource for FileSystemPathBasedDataSource {
                 let import_location =
                     AssetLocation::new(*path_to_path_node_id.get(parent_dir).unwrap());
 
-                let source_file_disk_state =
-                    source_files_disk_state.get_mut(source_file_path).unwrap();
-
-                let mut requested_importables = HashMap::default();
-                for scanned_importable in &scanned_source_file.scanned_importables {
-                    // The ID assigned to this importable. We have this now because we previously scanned
-                    // all source files and assigned IDs to any importable
-                    let importable_asset_id = *scanned_source_files
-                        .get(source_file_path)
-                        .unwrap()
-                        .meta_file
-                        .past_id_assignments
-                        .get(&scanned_importable.name)
-                        .unwrap();
-
-                    // Create an asset name for this asset
-                    let asset_name =
-                        hydrate_pipeline::create_asset_name(source_file_path, scanned_importable);
-
-                    let asset_file_exists = assets_disk_state.get(&importable_asset_id).is_some();
-                    let asset_is_persisted = scanned_source_file
-                        .meta_file
-                        .persisted_assets
-                        .contains(&importable_asset_id);
-
-                    if asset_is_persisted && !asset_file_exists {
-                        // If the asset is persisted but deleted, we do not want to import it
-                        continue;
-                    }
-
-                    if !asset_is_persisted {
-                        assets_disk_state.insert(
-                            importable_asset_id,
-                            AssetDiskState::Generated(GeneratedAssetDiskState {
-                                source_file_path: source_file_path.clone(),
-                            }),
-                        );
-                        source_file_disk_state
-                            .generated_assets
-                            .insert(importable_asset_id);
-                    } else {
-                        assert!(asset_file_exists);
-                        assert_eq!(
-                            edit_context
-                                .asset_schema(importable_asset_id)
-                                .unwrap()
-                                .fingerprint(),
-                            scanned_importable.asset_type.fingerprint()
-                        );
-                        //edit_context.set_asset_name(importable_asset_id, asset_name);
-                        //edit_context.set_asset_location(importable_asset_id, *import_location);
-                        //edit_context.set_import_info(importable_asset_id, import_info);
-
-                        // We iterated through asset files already, so just check that we inserted a AssetDiskState::Persisted into this map
-                        assert!(assets_disk_state
-                            .get(&importable_asset_id)
-                            .unwrap()
-                            .is_persisted());
-                        source_file_disk_state
-                            .persisted_assets
-                            .insert(importable_asset_id);
-                    }
-
-                    let canonical_path_references = Self::find_canonical_path_references(
-                        project_config,
-                        source_file_path,
-                        &scanned_importable,
-                        &scanned_source_files,
-                    );
-                    match canonical_path_references {
-                        Ok(canonical_path_references) => {
-                            let source_file = PathReference::new(
-                                "".to_string(),
-                                source_file_path.to_string_lossy().to_string(),
-                                scanned_importable.name.clone(),
-                            )
-                            .simplify(project_config);
-
-                            let requested_importable = RequestedImportable {
-                                asset_id: importable_asset_id,
-                                schema: scanned_importable.asset_type.clone(),
-                                asset_name,
-                                asset_location: import_location,
-                                //importer_id: scanned_source_file.importer.importer_id(),
-                                source_file,
-                                canonical_path_references,
-                                path_references: scanned_importable.referenced_source_files.clone(),
-                                replace_with_default_asset: !asset_is_persisted,
-                            };
-
-                            requested_importables
-                                .insert(scanned_importable.name.clone(), requested_importable);
-                        }
-                        Err(e) => {
-                            import_job_to_queue
-                                .log_data
-                                .log_events
-                                .push(ImportLogEvent {
-                                    path: source_file_path.clone(),
-                                    asset_id: Some(importable_asset_id),
-                                    level: LogEventLevel::FatalError,
-                                    message: format!(
-                                        "While resolving references to other assets: {}",
-                                        e.to_string()
-                                    ),
-                                });
-                        }
-                    }
-                }
-
-                if !requested_importables.is_empty() {
-                    import_job_to_queue
-                        .import_job_source_files
-                        .push(ImportJobSourceFile {
-                            source_file_path: source_file_path.to_path_buf(),
-                            importer_id: scanned_source_file.importer.importer_id(),
-                            requested_importables,
-                            import_type: ImportType::ImportIfImportDataStale,
-                        });
-                }
+                Self::queue_source_file_import(
+                    project_config,
+                    edit_context,
+                    scanned_source_file,
+                    import_location,
+                    &mut source_files_disk_state,
+                    &mut assets_disk_state,
+                    import_job_to_queue,
+                );
             }
         }
 
         self.assets_disk_state = assets_disk_state;
         self.source_files_disk_state = source_files_disk_state;
+        self.storage_snapshot = StorageSnapshot::scan(
+            &self.file_system_root_path,
+            project_config.path_canonicalization,
+        );
+        self.pending_storage_scan = None;
 
         // //
         // // Import the file
@@ -991,45 +1189,333 @@ impl DataSource for FileSystemPathBasedDataSource {
         //      it may break asset references?
     }
 
-    fn flush_to_storage(
+    fn poll_storage_for_changes(
         &mut self,
+        project_config: &HydrateProjectConfiguration,
         edit_context: &mut EditContext,
-    ) {
+        import_job_to_queue: &mut ImportJobToQueue,
+    ) -> Vec<AssetLocation> {
         profiling::scope!(&format!(
-            "flush_to_storage {:?}",
+            "poll_storage_for_changes {:?}",
             self.file_system_root_path
         ));
 
-        let mut pending_writes = Vec::<AssetId>::default();
-        let mut pending_deletes = Vec::<AssetId>::default();
+        // Start a scan if there isn't one, changes are picked up by the first poll after it finishes
+        let Some(pending_storage_scan) = &self.pending_storage_scan else {
+            let file_system_root_path = self.file_system_root_path.clone();
+            let path_canonicalization = project_config.path_canonicalization;
+            self.pending_storage_scan = Some(std::thread::spawn(move || {
+                StorageSnapshot::scan(&file_system_root_path, path_canonicalization)
+            }));
+            return Vec::default();
+        };
+
+        if !pending_storage_scan.is_finished() {
+            return Vec::default();
+        }
+
+        let storage_snapshot = match self.pending_storage_scan.take().unwrap().join() {
+            Ok(storage_snapshot) => storage_snapshot,
+            Err(_) => {
+                log::error!(
+                    "Scanning {:?} for changes failed",
+                    self.file_system_root_path
+                );
+                return Vec::default();
+            }
+        };
+        let previous_storage_snapshot =
+            std::mem::replace(&mut self.storage_snapshot, storage_snapshot);
+
+        let mut removed_paths: Vec<PathBuf> = previous_storage_snapshot
+            .directories
+            .iter()
+            .filter(|x| !self.storage_snapshot.directories.contains(*x))
+            .chain(
+                previous_storage_snapshot
+                    .files
+                    .keys()
+                    .filter(|x| !self.storage_snapshot.files.contains_key(*x)),
+            )
+            .cloned()
+            .collect();
+        let mut added_directories: Vec<PathBuf> = self
+            .storage_snapshot
+            .directories
+            .iter()
+            .filter(|x| !previous_storage_snapshot.directories.contains(*x))
+            .cloned()
+            .collect();
+        // Files that were added or modified
+        let mut changed_files: Vec<PathBuf> = self
+            .storage_snapshot
+            .files
+            .iter()
+            .filter(|(path, metadata)| {
+                previous_storage_snapshot.files.get(*path) != Some(*metadata)
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        if removed_paths.is_empty() && added_directories.is_empty() && changed_files.is_empty() {
+            return Vec::default();
+        }
+
+        let mut changed_locations = HashSet::<AssetLocation>::default();
+        let mut path_to_path_node_id = self.canonicalize_all_path_nodes(edit_context);
+        let persisted_asset_paths: HashMap<PathBuf, AssetId> = self
+            .assets_disk_state
+            .iter()
+            .filter_map(|(asset_id, asset_disk_state)| match asset_disk_state {
+                AssetDiskState::Persisted(x) => Some((x.asset_file_path.clone(), *asset_id)),
+                AssetDiskState::Generated(_) => None,
+            })
+            .collect();
+        let has_unsaved_changes =
+            |edit_context: &EditContext, asset_id: AssetId, object_hash: u64| {
+                edit_context
+                    .data_set()
+                    .hash_object(asset_id, HashObjectMode::FullObjectWithLocationChainNames)
+                    .unwrap()
+                    != object_hash
+            };
+
+        //
+        // Remove assets whose files were removed. Reverse sort ensures that files are removed before
+        // the directories that contain them.
+        //
+        removed_paths.sort_by(|lhs, rhs| rhs.cmp(lhs));
+        for removed_path in removed_paths {
+            if let Some(source_file_disk_state) = self.source_files_disk_state.remove(&removed_path)
+            {
+                // Generated assets only exist because the source file did. Persisted assets are
+                // kept since they have their own asset file.
+                for asset_id in source_file_disk_state.generated_assets {
+                    self.assets_disk_state.remove(&asset_id);
+                    if let Some(asset_location) = edit_context.asset_location(asset_id) {
+                        edit_context.delete_asset(asset_id).unwrap();
+                        changed_locations.insert(asset_location);
+                    }
+                }
 
-        for &asset_id in edit_context.assets().keys() {
-            if asset_id.as_uuid() == *self.asset_source_id.uuid() {
-                // ignore the root asset
                 continue;
             }
 
-            if self.is_asset_owned_by_this_data_source(edit_context, asset_id) {
-                match self.assets_disk_state.get(&asset_id) {
-                    None => {
-                        // There is a newly created asset that has never been saved
-                        pending_writes.push(asset_id);
+            // Meta files and files that failed to load are not tracked
+            let Some(&asset_id) = persisted_asset_paths.get(&removed_path) else {
+                continue;
+            };
+
+            let Some(AssetDiskState::Persisted(asset_disk_state)) =
+                self.assets_disk_state.remove(&asset_id)
+            else {
+                continue;
+            };
+
+            let Some(asset_location) = edit_context.asset_location(asset_id) else {
+                continue;
+            };
+
+            // Without its disk state, the asset is treated like a new asset that was never saved
+            if has_unsaved_changes(edit_context, asset_id, asset_disk_state.object_hash) {
+                log::warn!(
+                    "Asset file {:?} was removed outside the editor, but the asset has unsaved changes. Keeping the asset.",
+                    removed_path
+                );
+                continue;
+            }
+
+            // Keep directories that still hold assets, i.e. new assets that were never saved
+            let is_directory = asset_disk_state.asset_file_path.extension().is_none();
+            if is_directory
+                && edit_context
+                    .assets()
+                    .values()
+                    .any(|x| x.asset_location().path_node_id() == asset_id)
+            {
+                continue;
+            }
+
+            edit_context.delete_asset(asset_id).unwrap();
+            path_to_path_node_id.retain(|_, path_node_id| *path_node_id != asset_id);
+            changed_locations.insert(asset_location);
+        }
+
+        //
+        // Create path nodes for new directories. Sorting ensures parents are visited first.
+        //
+        added_directories.sort();
+        for added_directory in added_directories {
+            let (asset_id, asset_disk_state) =
+                self.load_directory(added_directory, &mut path_to_path_node_id, edit_context);
+            self.assets_disk_state.insert(asset_id, asset_disk_state);
+            changed_locations.insert(edit_context.asset_location(asset_id).unwrap());
+        }
+
+        //
+        // Load asset files that were added or modified
+        //
+        changed_files.sort();
+        let mut changed_source_files = Vec::default();
+        for changed_file in changed_files {
+            if changed_file.extension() == Some(OsStr::new("meta")) {
+                // Meta files are read when their source file is scanned
+                continue;
+            } else if changed_file.extension() != Some(OsStr::new("af")) {
+                changed_source_files.push(changed_file);
+                continue;
+            }
+
+            if let Some(&asset_id) = persisted_asset_paths.get(&changed_file) {
+                if let Some(AssetDiskState::Persisted(asset_disk_state)) =
+                    self.assets_disk_state.get(&asset_id)
+                {
+                    if edit_context.has_asset(asset_id)
+                        && has_unsaved_changes(edit_context, asset_id, asset_disk_state.object_hash)
+                    {
+                        log::warn!(
+                            "Asset file {:?} was modified outside the editor, but the asset has unsaved changes. Keeping the unsaved changes.",
+                            changed_file
+                        );
+                        continue;
                     }
-                    Some(asset_disk_state) => {
-                        let object_hash = edit_context
-                            .data_set()
-                            .hash_object(asset_id, HashObjectMode::FullObjectWithLocationChainNames)
-                            .unwrap();
-                        match asset_disk_state {
-                            AssetDiskState::Generated(_) => {
-                                // We never consider a generated asset as modified, and we expect UI to never alter
-                                // the asset data
-                            }
-                            AssetDiskState::Persisted(persisted_asset_disk_state) => {
-                                if persisted_asset_disk_state.object_hash != object_hash {
-                                    // The object has been modified and no longer matches disk state
-                                    pending_writes.push(asset_id);
-                                }
+                }
+
+                // The asset may be moved by loading the file
+                if let Some(asset_location) = edit_context.asset_location(asset_id) {
+                    changed_locations.insert(asset_location);
+                }
+            }
+
+            if let Some((asset_id, asset_disk_state)) =
+                self.load_asset_file(changed_file, &mut path_to_path_node_id, edit_context)
+            {
+                self.assets_disk_state.insert(asset_id, asset_disk_state);
+                changed_locations.insert(edit_context.asset_location(asset_id).unwrap());
+            }
+        }
+
+        //
+        // Re-scan source files that were added or modified and queue them to be imported
+        //
+        let mut scanned_source_files = Vec::default();
+        for source_file in changed_source_files {
+            let mut meta_file_path = source_file.clone().into_os_string();
+            meta_file_path.push(".meta");
+            let meta_file = std::fs::read_to_string(&meta_file_path)
+                .map(|contents| MetaFileJson::load_from_string(&contents))
+                .unwrap_or_default();
+
+            let previous_source_file_disk_state = self.source_files_disk_state.remove(&source_file);
+            let scan_result = Self::scan_source_file(
+                &self.importer_registry,
+                project_config,
+                edit_context,
+                &source_file,
+                meta_file,
+                import_job_to_queue,
+            );
+
+            // Generated assets for importables that are no longer in the file are removed
+            let importables = scan_result
+                .as_ref()
+                .map(|(_, source_file_disk_state)| &source_file_disk_state.importables);
+            for asset_id in previous_source_file_disk_state
+                .map(|x| x.generated_assets)
+                .unwrap_or_default()
+            {
+                if importables.is_some_and(|x| x.values().any(|&id| id == asset_id)) {
+                    continue;
+                }
+
+                self.assets_disk_state.remove(&asset_id);
+                if let Some(asset_location) = edit_context.asset_location(asset_id) {
+                    edit_context.delete_asset(asset_id).unwrap();
+                    changed_locations.insert(asset_location);
+                }
+            }
+
+            if let Some((scanned_source_file, source_file_disk_state)) = scan_result {
+                self.source_files_disk_state
+                    .insert(source_file.clone(), source_file_disk_state);
+                scanned_source_files.push((source_file, scanned_source_file));
+            }
+        }
+
+        for (source_file_path, scanned_source_file) in &scanned_source_files {
+            let import_location = self.ensure_asset_location_exists(
+                source_file_path.parent().unwrap(),
+                &mut path_to_path_node_id,
+                edit_context,
+            );
+
+            Self::queue_source_file_import(
+                project_config,
+                edit_context,
+                scanned_source_file,
+                import_location,
+                &mut self.source_files_disk_state,
+                &mut self.assets_disk_state,
+                import_job_to_queue,
+            );
+            changed_locations.insert(import_location);
+        }
+
+        // Scanning source files writes their meta files
+        let meta_file_paths: Vec<PathBuf> = scanned_source_files
+            .iter()
+            .map(|(source_file_path, _)| {
+                let mut meta_file_path = source_file_path.clone().into_os_string();
+                meta_file_path.push(".meta");
+                PathBuf::from(meta_file_path)
+            })
+            .collect();
+        for meta_file_path in meta_file_paths {
+            self.record_written_path(&meta_file_path, project_config);
+        }
+
+        let mut changed_locations: Vec<_> = changed_locations.into_iter().collect();
+        changed_locations.sort();
+        changed_locations
+    }
+
+    fn is_polling_storage(&self) -> bool {
+        self.pending_storage_scan.is_some()
+    }
+
+    fn flush_to_storage(
+        &mut self,
+        project_config: &HydrateProjectConfiguration,
+        edit_context: &mut EditContext,
+    ) -> PipelineResult<()> {
+        profiling::scope!(&format!(
+            "flush_to_storage {:?}",
+            self.file_system_root_path
+        ));
+
+        let mut pending_writes = Vec::<AssetId>::default();
+        let mut pending_deletes = Vec::<AssetId>::default();
+
+        for asset_id in edit_context.assets_for_source(self.asset_source_id) {
+            match self.assets_disk_state.get(&asset_id) {
+                None => {
+                    // There is a newly created asset that has never been saved
+                    pending_writes.push(asset_id);
+                }
+                Some(asset_disk_state) => {
+                    let object_hash = edit_context
+                        .data_set()
+                        .hash_object(asset_id, HashObjectMode::FullObjectWithLocationChainNames)
+                        .unwrap();
+                    match asset_disk_state {
+                        AssetDiskState::Generated(_) => {
+                            // We never consider a generated asset as modified, and we expect UI to never alter
+                            // the asset data
+                        }
+                        AssetDiskState::Persisted(persisted_asset_disk_state) => {
+                            if persisted_asset_disk_state.object_hash != object_hash {
+                                // The object has been modified and no longer matches disk state
+                                pending_writes.push(asset_id);
                             }
                         }
                     }
@@ -1093,7 +1579,11 @@ impl DataSource for FileSystemPathBasedDataSource {
 
                     if is_directory {
                         // It's a path node, ensure the dir exists
-                        std::fs::create_dir_all(&asset_file_path).unwrap();
+                        std::fs::create_dir_all(&asset_file_path)?;
+                        if project_config.fsync_on_save {
+                            super::sync_directory(&containing_file_path)?;
+                        }
+                        self.record_written_path(&asset_file_path, project_config);
                     } else {
                         // It's a asset, create an asset file
                         let data = crate::json_storage::AssetJson::save_asset_to_string(
@@ -1104,8 +1594,12 @@ impl DataSource for FileSystemPathBasedDataSource {
                             None,
                         );
 
-                        std::fs::create_dir_all(&containing_file_path).unwrap();
-                        std::fs::write(&asset_file_path, data).unwrap();
+                        std::fs::create_dir_all(&containing_file_path)?;
+                        super::write_file_to_storage(
+                            &asset_file_path,
+                            data,
+                            project_config.fsync_on_save,
+                        )?;
 
                         let object_hash = edit_context
                             .data_set()
@@ -1116,7 +1610,7 @@ impl DataSource for FileSystemPathBasedDataSource {
                             .unwrap();
 
                         let asset_file_metadata =
-                            FileMetadata::new(&std::fs::metadata(&asset_file_path).unwrap());
+                            FileMetadata::new(&std::fs::metadata(&asset_file_path)?);
                         self.assets_disk_state.insert(
                             *asset_id,
                             AssetDiskState::Persisted(PersistedAssetDiskState {
@@ -1125,6 +1619,7 @@ impl DataSource for FileSystemPathBasedDataSource {
                                 object_hash,
                             }),
                         );
+                        self.record_written_path(&asset_file_path, project_config);
 
                         // We know the asset was already persisted so we don't need to update source files state
                     }
@@ -1133,8 +1628,9 @@ impl DataSource for FileSystemPathBasedDataSource {
         }
 
         let mut deferred_directory_deletes = Vec::default();
+        let mut directories_to_sync = HashSet::<PathBuf>::default();
 
-        // First pass to delete files
+        // Deletes happen only after all writes are complete. First pass to delete files
         for &asset_id in &pending_deletes {
             match self.assets_disk_state.get(&asset_id) {
                 None => {
@@ -1155,7 +1651,12 @@ impl DataSource for FileSystemPathBasedDataSource {
                                 deferred_directory_deletes
                                     .push((asset_id, disk_state.asset_file_path.clone()));
                             } else {
-                                std::fs::remove_file(&disk_state.asset_file_path).unwrap();
+                                let asset_file_path = disk_state.asset_file_path.clone();
+                                self.forget_removed_path(&asset_file_path, project_config);
+                                std::fs::remove_file(&asset_file_path)?;
+                                if let Some(parent) = asset_file_path.parent() {
+                                    directories_to_sync.insert(parent.to_path_buf());
+                                }
                                 self.assets_disk_state.remove(&asset_id);
                             }
                         }
@@ -1169,11 +1670,24 @@ impl DataSource for FileSystemPathBasedDataSource {
 
         // Second pass to delete directories if they are empty and path node does not exist
         for (_, directory) in deferred_directory_deletes {
-            let is_empty = directory.read_dir().unwrap().next().is_none();
+            let is_empty = directory.read_dir()?.next().is_none();
             if is_empty {
-                std::fs::remove_dir(&directory).unwrap();
+                self.forget_removed_path(&directory, project_config);
+                std::fs::remove_dir(&directory)?;
+                directories_to_sync.remove(&directory);
+                if let Some(parent) = directory.parent() {
+                    directories_to_sync.insert(parent.to_path_buf());
+                }
             }
         }
+
+        if project_config.fsync_on_save {
+            for directory in directories_to_sync {
+                super::sync_directory(&directory)?;
+            }
+        }
+
+        Ok(())
     }
 
     fn edit_context_has_unsaved_changes(
@@ -1,14 +1,15 @@
 mod file_system_id_based;
 
 pub use file_system_id_based::*;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use crate::edit_context::EditContext;
-use crate::AssetId;
+use crate::{AssetId, AssetLocation};
 
 mod file_system_path_based;
 pub use file_system_path_based::*;
-use hydrate_pipeline::{HydrateProjectConfiguration, ImportJobToQueue};
+use hydrate_pipeline::{HydrateProjectConfiguration, ImportJobToQueue, PipelineResult};
 
 #[derive(Default)]
 pub struct PendingFileOperations {
@@ -28,12 +29,32 @@ pub trait DataSource {
         import_job_to_queue: &mut ImportJobToQueue,
     );
 
+    // Bring memory up to date with changes made to storage outside the editor since the last
+    // load, flush, or poll. Returns the locations that changed. Storage may be scanned in the
+    // background, in which case changes are returned by a later poll.
+    fn poll_storage_for_changes(
+        &mut self,
+        _project_config: &HydrateProjectConfiguration,
+        _edit_context: &mut EditContext,
+        _import_job_to_queue: &mut ImportJobToQueue,
+    ) -> Vec<AssetLocation> {
+        Vec::default()
+    }
+
+    // True if a poll started scanning storage and the results have not been picked up yet
+    fn is_polling_storage(&self) -> bool {
+        false
+    }
+
     // Replace storage state with memory state
-    // Flush memory to storage
+    // Flush memory to storage. All writes are completed before any deletes are performed. If
+    // project_config.fsync_on_save is set, this does not return until written files and their
+    // containing directories have been synced to disk. Fails if anything could not be written.
     fn flush_to_storage(
         &mut self,
+        project_config: &HydrateProjectConfiguration,
         edit_context: &mut EditContext,
-    );
+    ) -> PipelineResult<()>;
 
     fn is_generated_asset(
         &self,
@@ -42,9 +63,10 @@ pub trait DataSource {
 
     fn persist_generated_asset(
         &mut self,
+        project_config: &HydrateProjectConfiguration,
         edit_context: &mut EditContext,
         asset_id: AssetId,
-    );
+    ) -> PipelineResult<()>;
 
     fn edit_context_has_unsaved_changes(
         &self,
@@ -57,3 +79,54 @@ pub trait DataSource {
         pending_file_operations: &mut PendingFileOperations,
     );
 }
+
+// Writes the file. When fsync is requested, the data is written to a temporary file alongside the
+// destination, synced, and renamed over the destination. The containing directory is synced after
+// the rename so that the rename itself is durable.
+pub(crate) fn write_file_to_storage(
+    path: &Path,
+    contents: impl AsRef<[u8]>,
+    fsync: bool,
+) -> std::io::Result<()> {
+    if !fsync {
+        return std::fs::write(path, contents);
+    }
+
+    let mut tmp_path = path.to_path_buf().into_os_string();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    {
+        let mut file = std::fs::File::create(&tmp_path)?;
+        file.write_all(contents.as_ref())?;
+        file.sync_all()?;
+    }
+
+    if let Err(e) = std::fs::rename(&tmp_path, path) {
+        // Don't leave the temporary file behind, the original file is untouched
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+
+    if let Some(parent) = path.parent() {
+        sync_directory(parent)?;
+    }
+
+    Ok(())
+}
+
+// Ensures that directory entries (creates, renames, deletes) are durable. Only supported on unix,
+// other platforms do not allow opening a directory as a file and this is a no-op.
+pub(crate) fn sync_directory(path: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::fs::File::open(path)?.sync_all()?;
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+    }
+
+    Ok(())
+}
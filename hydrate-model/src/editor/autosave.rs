@@ -0,0 +1,113 @@
+use crate::AssetId;
+use hydrate_base::b3f::{B3FReader, B3FWriter};
+use hydrate_pipeline::{HydrateProjectConfiguration, PipelineResult};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+// Recovery files are B3F files. Block 0 is a json header, block 1 is the unsaved assets as written
+// by DataSet::serialize_to_vec()
+const AUTOSAVE_TAG: &[u8; 4] = b"HYAS";
+const AUTOSAVE_VERSION: u32 = 1;
+const AUTOSAVE_FILE_NAME: &str = "autosave.b3f";
+
+#[derive(Serialize, Deserialize)]
+struct AutosaveLocationJson {
+    id: Uuid,
+    path: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AutosaveHeaderJson {
+    // Milliseconds since the unix epoch
+    saved_at: u64,
+    deleted_assets: Vec<Uuid>,
+    locations: Vec<AutosaveLocationJson>,
+}
+
+/// Unsaved changes to the root edit context, written by EditorModel::autosave(). Read with
+/// EditorModel::read_autosave() and applied with EditorModel::restore_autosave().
+pub struct AutosaveRecovery {
+    pub(super) saved_at: SystemTime,
+    // Created or modified assets, as written by DataSet::serialize_to_vec()
+    pub(super) assets: Vec<u8>,
+    pub(super) deleted_assets: Vec<AssetId>,
+    // Locations of the assets that are not in the recovery file, along with the names of the path
+    // nodes leading to them starting with the data source. Path node IDs are not stable between
+    // sessions, so these are found again by name when restoring.
+    pub(super) locations: Vec<(AssetId, Vec<String>)>,
+}
+
+impl AutosaveRecovery {
+    pub fn saved_at(&self) -> SystemTime {
+        self.saved_at
+    }
+
+    pub fn deleted_assets(&self) -> &[AssetId] {
+        &self.deleted_assets
+    }
+
+    pub(super) fn path(project_config: &HydrateProjectConfiguration) -> PathBuf {
+        project_config.job_data_path.join(AUTOSAVE_FILE_NAME)
+    }
+
+    pub(super) fn write<W: std::io::Write>(
+        &self,
+        write: W,
+    ) -> PipelineResult<()> {
+        let saved_at = self
+            .saved_at
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let header = serde_json::to_string(&AutosaveHeaderJson {
+            saved_at,
+            deleted_assets: self.deleted_assets.iter().map(|x| x.as_uuid()).collect(),
+            locations: self
+                .locations
+                .iter()
+                .map(|(id, path)| AutosaveLocationJson {
+                    id: id.as_uuid(),
+                    path: path.clone(),
+                })
+                .collect(),
+        })?;
+
+        let mut b3f_writer = B3FWriter::new_from_u8_tag(*AUTOSAVE_TAG, AUTOSAVE_VERSION);
+        b3f_writer.add_block(header.as_bytes());
+        b3f_writer.add_block(&self.assets);
+        b3f_writer.write(write);
+        Ok(())
+    }
+
+    pub(super) fn read<R: std::io::Read + std::io::Seek>(
+        read: &mut R
+    ) -> PipelineResult<AutosaveRecovery> {
+        let b3f = B3FReader::new(read)?.ok_or("Not a B3F file")?;
+        if b3f.file_tag_as_u8() != AUTOSAVE_TAG
+            || b3f.version() != AUTOSAVE_VERSION
+            || b3f.block_count() != 2
+        {
+            Err("Not an autosave recovery file, or the file is from an unsupported version")?;
+        }
+
+        let header: AutosaveHeaderJson = serde_json::from_slice(&b3f.read_block(read, 0)?)?;
+        let assets = b3f.read_block(read, 1)?;
+
+        Ok(AutosaveRecovery {
+            saved_at: SystemTime::UNIX_EPOCH + Duration::from_millis(header.saved_at),
+            assets,
+            deleted_assets: header
+                .deleted_assets
+                .into_iter()
+                .map(AssetId::from_uuid)
+                .collect(),
+            locations: header
+                .locations
+                .into_iter()
+                .map(|x| (AssetId::from_uuid(x.id), x.path))
+                .collect(),
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use hydrate_base::PathCanonicalization;
 use hydrate_data::json_storage::RestoreAssetFromStorageImpl;
 use hydrate_data::{
     CanonicalPathReference, OrderedSet, PathReference, PathReferenceNamespaceResolver,
@@ -5,15 +6,35 @@ use hydrate_data::{
 };
 use hydrate_pipeline::{DynEditContext, HydrateProjectConfiguration};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::editor::undo::{UndoContext, UndoStack};
 use crate::{
-    AssetId, AssetLocation, AssetName, BuildInfo, DataSet, DataSetAssetInfo, DataSetDiff,
-    DataSetResult, EditContextKey, EndContextBehavior, HashMap, HashSet, ImportInfo, NullOverride,
-    OverrideBehavior, SchemaFingerprint, SchemaNamedType, SchemaRecord, SchemaSet, Value,
+    AssetId, AssetLocation, AssetName, AssetSourceId, BuildInfo, DataSet, DataSetAssetInfo,
+    DataSetDiff, DataSetError, DataSetResult, EditContextKey, EndContextBehavior, HashMap, HashSet,
+    ImportInfo, NullOverride, OverrideBehavior, Schema, SchemaFingerprint, SchemaNamedType,
+    SchemaRecord, SchemaSet, Value,
 };
 
+// The outcome of EditContext::set_property_on_many()
+#[derive(Debug, Default)]
+pub struct SetPropertyOnManyResult {
+    // Each changed asset and its previous override, in the order the assets were given
+    pub changed: Vec<(AssetId, Option<Value>)>,
+    // Assets whose schema doesn't have the property. They were not changed.
+    pub skipped: Vec<AssetId>,
+}
+
+// The outcome of EditContext::replace_asset_ref_properties()
+#[derive(Debug, Default)]
+pub struct ReplaceAssetRefPropertiesResult {
+    // How many properties were changed
+    pub changed: usize,
+    // Assets with a readonly property referencing the old asset. Those properties were not changed.
+    pub skipped: Vec<AssetId>,
+}
+
 //TODO: Delete unused property data when path ancestor is null or in replace mode
 
 //TODO: Should we make a struct that refs the schema/data? We could have transactions and databases
@@ -46,6 +67,8 @@ pub struct EditContext {
     schema_set: SchemaSet,
     pub(super) data_set: DataSet,
     undo_context: UndoContext,
+    // Name of the undo context used by the preview edit in progress, if any
+    preview_edit_name: Option<&'static str>,
 }
 
 impl PathReferenceNamespaceResolver for EditContext {
@@ -62,6 +85,10 @@ impl PathReferenceNamespaceResolver for EditContext {
     ) -> Option<(String, PathBuf)> {
         self.project_config.simplify_path(path)
     }
+
+    fn path_canonicalization(&self) -> PathCanonicalization {
+        self.project_config.path_canonicalization
+    }
 }
 
 impl RestoreAssetFromStorageImpl for EditContext {
@@ -135,6 +162,23 @@ impl EditContext {
         Ok(())
     }
 
+    // Fields marked readonly can only be changed through the DataSet, i.e. by importers and builders
+    fn check_property_not_readonly(
+        &self,
+        asset_id: AssetId,
+        path: &str,
+    ) -> DataSetResult<()> {
+        let schema = self
+            .data_set
+            .asset_schema(asset_id)
+            .ok_or(DataSetError::AssetNotFound)?;
+        if schema.is_property_readonly(path, self.schema_set.schemas()) {
+            Err(DataSetError::FieldReadOnly)?;
+        }
+
+        Ok(())
+    }
+
     pub fn apply_diff(
         &mut self,
         diff: &DataSetDiff,
@@ -154,6 +198,7 @@ impl EditContext {
             schema_set,
             data_set: Default::default(),
             undo_context: UndoContext::new(undo_stack, edit_context_key),
+            preview_edit_name: None,
         }
     }
 
@@ -168,6 +213,7 @@ impl EditContext {
             schema_set,
             data_set: Default::default(),
             undo_context: UndoContext::new(undo_stack, edit_context_key),
+            preview_edit_name: None,
         }
     }
 
@@ -176,17 +222,59 @@ impl EditContext {
         name: &'static str,
         f: F,
     ) {
+        // Any other edit ends the preview, keeping what was previewed
+        self.commit_preview();
         self.undo_context.begin_context(&self.data_set, name);
         let end_context_behavior = (f)(self);
         self.undo_context
             .end_context(&self.data_set, end_context_behavior);
     }
 
+    /// Applies an intermediate edit, like a step of dragging a slider. The data set is changed
+    /// immediately for live feedback, but all preview edits with the same name are coalesced into
+    /// a single undo entry when commit_preview() is called. Until then, the edits are not saved to
+    /// data sources and do not trigger builds. cancel_preview() reverts all of them.
+    pub fn with_preview_edit<F: FnOnce(&mut Self)>(
+        &mut self,
+        name: &'static str,
+        f: F,
+    ) {
+        if self.preview_edit_name != Some(name) {
+            self.commit_preview();
+        }
+
+        self.undo_context.begin_context(&self.data_set, name);
+        self.preview_edit_name = Some(name);
+        (f)(self);
+    }
+
+    pub fn has_preview_edit(&self) -> bool {
+        self.preview_edit_name.is_some()
+    }
+
+    /// Ends the preview edit in progress, keeping its changes as a single undo entry
+    pub fn commit_preview(&mut self) {
+        if self.preview_edit_name.take().is_some() {
+            self.undo_context.commit_context(&self.data_set);
+        }
+    }
+
+    /// Ends the preview edit in progress, reverting everything it changed
+    pub fn cancel_preview(&mut self) -> DataSetResult<()> {
+        if self.preview_edit_name.take().is_some() {
+            self.undo_context.cancel_context(&mut self.data_set)?;
+        }
+
+        Ok(())
+    }
+
     pub fn commit_pending_undo_context(&mut self) {
+        self.preview_edit_name = None;
         self.undo_context.commit_context(&mut self.data_set);
     }
 
     pub fn cancel_pending_undo_context(&mut self) -> DataSetResult<()> {
+        self.preview_edit_name = None;
         self.undo_context.cancel_context(&mut self.data_set)
     }
 
@@ -216,10 +304,21 @@ impl EditContext {
     //     &mut self.data_set
     // }
 
-    pub fn assets(&self) -> &HashMap<AssetId, DataSetAssetInfo> {
+    pub fn assets(&self) -> &HashMap<AssetId, Arc<DataSetAssetInfo>> {
         self.data_set.assets()
     }
 
+    // Assets belonging to a data source are located beneath the path node that has the source's ID
+    pub fn assets_for_source(
+        &self,
+        asset_source_id: AssetSourceId,
+    ) -> impl Iterator<Item = AssetId> + '_ {
+        self.data_set
+            .assets_under_location(AssetLocation::new(AssetId::from_uuid(
+                *asset_source_id.uuid(),
+            )))
+    }
+
     pub fn has_asset(
         &self,
         asset_id: AssetId,
@@ -272,20 +371,63 @@ impl EditContext {
         Ok(asset_id)
     }
 
+    // Creates the asset with its import info already set, so there is never a moment where an
+    // imported asset exists without it. On failure, no asset is created.
+    pub fn new_imported_asset_with_id(
+        &mut self,
+        asset_id: AssetId,
+        asset_name: &AssetName,
+        asset_location: &AssetLocation,
+        schema: &SchemaRecord,
+        import_info: ImportInfo,
+    ) -> DataSetResult<()> {
+        self.data_set.new_imported_asset_with_id(
+            &self.schema_set,
+            asset_id,
+            asset_name.clone(),
+            *asset_location,
+            schema,
+            import_info,
+        )?;
+        self.track_new_asset(asset_id);
+        Ok(())
+    }
+
+    pub fn new_imported_asset(
+        &mut self,
+        asset_name: &AssetName,
+        asset_location: &AssetLocation,
+        schema: &SchemaRecord,
+        import_info: ImportInfo,
+    ) -> DataSetResult<AssetId> {
+        let asset_id = self.data_set.new_imported_asset(
+            &self.schema_set,
+            asset_name.clone(),
+            *asset_location,
+            schema,
+            import_info,
+        )?;
+        self.track_new_asset(asset_id);
+        Ok(asset_id)
+    }
+
+    // Creates an imported asset with the properties of the single object
     pub fn init_from_single_object(
         &mut self,
         asset_id: AssetId,
         asset_name: AssetName,
         asset_location: AssetLocation,
         single_object: &SingleObject,
+        import_info: ImportInfo,
     ) -> DataSetResult<()> {
-        self.track_new_asset(asset_id);
-        self.data_set.new_asset_with_id(
+        self.new_imported_asset_with_id(
             asset_id,
-            asset_name,
-            asset_location,
+            &asset_name,
+            &asset_location,
             single_object.schema(),
+            import_info,
         )?;
+        // Does not fail, the asset was just created with the single object's schema
         self.data_set
             .copy_from_single_object(asset_id, single_object)
     }
@@ -361,6 +503,135 @@ impl EditContext {
         self.data_set.delete_asset(asset_id)
     }
 
+    // Deletes the asset, every asset located beneath it, and every asset they own. All of them are
+    // tracked so that undo restores them with their original IDs.
+    pub fn delete_asset_and_children(
+        &mut self,
+        asset_id: AssetId,
+    ) -> DataSetResult<()> {
+        let mut assets_to_delete = vec![asset_id];
+        assets_to_delete.extend(
+            self.data_set
+                .assets_under_location(AssetLocation::new(asset_id)),
+        );
+
+        // Assets referenced by owned fields are deleted too, other referenced assets are not
+        let mut i = 0;
+        while i < assets_to_delete.len() {
+            for owned_asset_id in self
+                .data_set
+                .owned_assets(&self.schema_set, assets_to_delete[i])?
+            {
+                if self.has_asset(owned_asset_id) && !assets_to_delete.contains(&owned_asset_id) {
+                    assets_to_delete.push(owned_asset_id);
+                }
+            }
+            i += 1;
+        }
+
+        for asset_to_delete in assets_to_delete {
+            self.delete_asset(asset_to_delete)?;
+        }
+
+        Ok(())
+    }
+
+    // Repoints every reference to an asset in the mapping to the asset it maps to. Assets that
+    // hold references are tracked so that undo restores the original references.
+    pub fn replace_asset_references(
+        &mut self,
+        mapping: &HashMap<AssetId, AssetId>,
+    ) -> DataSetResult<()> {
+        let referenced_asset_ids = mapping.keys().copied().collect();
+        for asset_id in self.data_set.assets_referencing(&referenced_asset_ids) {
+            self.track_existing_asset(asset_id)?;
+            self.data_set.remap_references(asset_id, mapping)?;
+        }
+
+        Ok(())
+    }
+
+    // Repoints asset ref properties from one asset to another. Unlike replace_asset_references(),
+    // prototypes, locations and other kinds of references are left alone. Readonly properties are
+    // not changed, and their assets are returned as skipped. When validating, every property being
+    // changed must accept the type of the new asset or nothing is changed.
+    pub fn replace_asset_ref_properties(
+        &mut self,
+        old_asset_id: AssetId,
+        new_asset_id: AssetId,
+        validate_ref_types: bool,
+    ) -> DataSetResult<ReplaceAssetRefPropertiesResult> {
+        if old_asset_id == new_asset_id {
+            return Ok(ReplaceAssetRefPropertiesResult::default());
+        }
+
+        let new_asset_fingerprint = self
+            .data_set
+            .asset_schema(new_asset_id)
+            .map(|schema| schema.fingerprint());
+
+        let mut properties_to_change = Vec::default();
+        let mut skipped = Vec::default();
+        let referenced_asset_ids = [old_asset_id].into_iter().collect();
+        for asset_id in self.data_set.assets_referencing(&referenced_asset_ids) {
+            let asset = &self.data_set.assets()[&asset_id];
+            let mut paths: Vec<_> = asset
+                .properties()
+                .iter()
+                .filter(|(_, value)| value.try_as_asset_ref() == Some(old_asset_id))
+                .map(|(path, _)| path.clone())
+                .collect();
+            paths.sort();
+
+            for path in paths {
+                if self.check_property_not_readonly(asset_id, &path).is_err() {
+                    log::warn!(
+                        "Property {:?} of asset {} is readonly, its reference was not replaced",
+                        path,
+                        self.asset_name_or_id_string(asset_id)?
+                    );
+                    if skipped.last() != Some(&asset_id) {
+                        skipped.push(asset_id);
+                    }
+                    continue;
+                }
+
+                if validate_ref_types {
+                    let accepts_new_asset = match asset
+                        .schema()
+                        .find_property_schema(&path, self.schema_set.schemas())
+                    {
+                        Some(Schema::AssetRef(fingerprint)) => {
+                            Some(fingerprint) == new_asset_fingerprint
+                        }
+                        _ => false,
+                    };
+
+                    if !accepts_new_asset {
+                        Err(DataSetError::ValueDoesNotMatchSchema)?;
+                    }
+                }
+
+                properties_to_change.push((asset_id, path));
+            }
+        }
+
+        for (asset_id, path) in &properties_to_change {
+            self.track_existing_asset(*asset_id)?;
+            self.data_set.set_property_override(
+                &self.schema_set,
+                *asset_id,
+                path,
+                Some(Value::AssetRef(new_asset_id)),
+            )?;
+        }
+
+        Ok(ReplaceAssetRefPropertiesResult {
+            changed: properties_to_change.len(),
+            skipped,
+        })
+    }
+
     pub fn set_asset_location(
         &mut self,
         asset_id: AssetId,
@@ -378,8 +649,8 @@ impl EditContext {
         asset_id: AssetId,
         import_info: ImportInfo,
     ) -> DataSetResult<()> {
-        self.data_set.set_import_info(asset_id, import_info)?;
         self.track_existing_asset(asset_id)?;
+        self.data_set.set_import_info(asset_id, import_info)?;
         Ok(())
     }
 
@@ -474,6 +745,22 @@ impl EditContext {
             .set_path_reference_override(asset_id, path, referenced_asset_id)
     }
 
+    pub fn build_settings(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<AssetId> {
+        self.data_set.build_settings(asset_id)
+    }
+
+    pub fn set_build_settings(
+        &mut self,
+        asset_id: AssetId,
+        build_settings: Option<AssetId>,
+    ) -> DataSetResult<()> {
+        self.track_existing_asset(asset_id)?;
+        self.data_set.set_build_settings(asset_id, build_settings)
+    }
+
     pub fn asset_prototype(
         &self,
         asset_id: AssetId,
@@ -503,6 +790,7 @@ impl EditContext {
         path: impl AsRef<str>,
         null_override: NullOverride,
     ) -> DataSetResult<()> {
+        self.check_property_not_readonly(asset_id, path.as_ref())?;
         self.track_existing_asset(asset_id)?;
         self.data_set
             .set_null_override(&self.schema_set, asset_id, path, null_override)
@@ -542,16 +830,103 @@ impl EditContext {
         path: impl AsRef<str>,
         value: Option<Value>,
     ) -> DataSetResult<Option<Value>> {
+        self.check_property_not_readonly(asset_id, path.as_ref())?;
         self.track_existing_asset(asset_id)?;
         self.data_set
             .set_property_override(&self.schema_set, asset_id, path, value)
     }
 
+    /// Sets the same property override on many assets, i.e. when several selected assets are edited
+    /// at once. This is a single undo step. Assets whose schema doesn't have the property are left
+    /// unchanged and returned as skipped. The value is checked against every other asset first, and
+    /// nothing is changed if any of them fail. Returns the previous override of each changed asset.
+    pub fn set_property_on_many(
+        &mut self,
+        asset_ids: &[AssetId],
+        path: impl AsRef<str>,
+        value: Option<Value>,
+    ) -> DataSetResult<SetPropertyOnManyResult> {
+        let path = path.as_ref();
+        let mut assets_to_change = Vec::with_capacity(asset_ids.len());
+        let mut visited_assets = HashSet::default();
+        let mut skipped = Vec::default();
+        for &asset_id in asset_ids {
+            if !visited_assets.insert(asset_id) {
+                continue;
+            }
+
+            let asset_schema = self
+                .data_set
+                .asset_schema(asset_id)
+                .ok_or(DataSetError::AssetNotFound)?;
+            let Some(property_schema) =
+                asset_schema.find_property_schema(path, self.schema_set.schemas())
+            else {
+                log::warn!(
+                    "Asset {} of type {} has no property {:?}, it was not changed",
+                    self.asset_name_or_id_string(asset_id)?,
+                    asset_schema.name(),
+                    path
+                );
+                skipped.push(asset_id);
+                continue;
+            };
+
+            if let Some(value) = &value {
+                if !value.matches_schema(&property_schema, self.schema_set.schemas()) {
+                    Err(DataSetError::ValueDoesNotMatchSchema)?;
+                }
+            }
+
+            self.check_property_not_readonly(asset_id, path)?;
+            assets_to_change.push(asset_id);
+        }
+
+        let mut result = Ok(Vec::default());
+        self.with_undo_context("set property on many", |edit_context| {
+            result = assets_to_change
+                .iter()
+                .map(|&asset_id| {
+                    edit_context
+                        .set_property_override(asset_id, path, value.clone())
+                        .map(|old_value| (asset_id, old_value))
+                })
+                .collect();
+
+            if result.is_err() {
+                // Revert whatever was changed before the error
+                if let Err(e) = edit_context.cancel_pending_undo_context() {
+                    log::error!("Failed to revert setting property on many assets: {:?}", e);
+                }
+            }
+
+            EndContextBehavior::Finish
+        });
+
+        Ok(SetPropertyOnManyResult {
+            changed: result?,
+            skipped,
+        })
+    }
+
+    pub fn update_property_override<F: FnOnce(&mut Value)>(
+        &mut self,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+        f: F,
+    ) -> DataSetResult<()> {
+        self.check_property_not_readonly(asset_id, path.as_ref())?;
+        self.track_existing_asset(asset_id)?;
+        self.data_set
+            .update_property_override(&self.schema_set, asset_id, path, f)
+    }
+
     pub fn apply_property_override_to_prototype(
         &mut self,
         asset_id: AssetId,
         path: impl AsRef<str>,
     ) -> DataSetResult<()> {
+        self.check_property_not_readonly(asset_id, path.as_ref())?;
         self.track_existing_asset(asset_id)?;
         if let Some(prototype) = self.asset_prototype(asset_id) {
             self.track_existing_asset(prototype)?;
@@ -593,6 +968,7 @@ impl EditContext {
         asset_id: AssetId,
         path: impl AsRef<str>,
     ) -> DataSetResult<Uuid> {
+        self.check_property_not_readonly(asset_id, path.as_ref())?;
         self.track_existing_asset(asset_id)?;
         self.data_set
             .add_dynamic_array_entry(&self.schema_set, asset_id, path)
@@ -603,6 +979,7 @@ impl EditContext {
         asset_id: AssetId,
         path: impl AsRef<str>,
     ) -> DataSetResult<Uuid> {
+        self.check_property_not_readonly(asset_id, path.as_ref())?;
         self.track_existing_asset(asset_id)?;
         self.data_set
             .add_map_entry(&self.schema_set, asset_id, path)
@@ -615,6 +992,7 @@ impl EditContext {
         index: usize,
         entry_uuid: Uuid,
     ) -> DataSetResult<()> {
+        self.check_property_not_readonly(asset_id, path.as_ref())?;
         self.track_existing_asset(asset_id)?;
         self.data_set.insert_dynamic_array_entry(
             &self.schema_set,
@@ -631,6 +1009,7 @@ impl EditContext {
         path: impl AsRef<str>,
         element_id: Uuid,
     ) -> DataSetResult<bool> {
+        self.check_property_not_readonly(asset_id, path.as_ref())?;
         self.track_existing_asset(asset_id)?;
         self.data_set
             .remove_dynamic_array_entry(&self.schema_set, asset_id, path, element_id)
@@ -642,11 +1021,81 @@ impl EditContext {
         path: impl AsRef<str>,
         element_id: Uuid,
     ) -> DataSetResult<bool> {
+        self.check_property_not_readonly(asset_id, path.as_ref())?;
         self.track_existing_asset(asset_id)?;
         self.data_set
             .remove_map_entry(&self.schema_set, asset_id, path, element_id)
     }
 
+    // Reordering is a single undo step
+    fn reorder_in_undo_context<F: FnOnce(&mut DataSet, &SchemaSet) -> DataSetResult<()>>(
+        &mut self,
+        asset_id: AssetId,
+        path: &str,
+        f: F,
+    ) -> DataSetResult<()> {
+        self.check_property_not_readonly(asset_id, path)?;
+        let mut result = Ok(());
+        self.with_undo_context("reorder dynamic array entry", |edit_context| {
+            result = edit_context
+                .track_existing_asset(asset_id)
+                .and_then(|_| f(&mut edit_context.data_set, &edit_context.schema_set));
+            EndContextBehavior::Finish
+        });
+        result
+    }
+
+    pub fn reorder_dynamic_array_entry(
+        &mut self,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+        entry_id: Uuid,
+        new_index: usize,
+    ) -> DataSetResult<()> {
+        let path = path.as_ref();
+        self.reorder_in_undo_context(asset_id, path, |data_set, schema_set| {
+            data_set.reorder_dynamic_array_entry(schema_set, asset_id, path, entry_id, new_index)
+        })
+    }
+
+    pub fn move_dynamic_array_entry_before(
+        &mut self,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+        entry_id: Uuid,
+        other_entry_id: Uuid,
+    ) -> DataSetResult<()> {
+        let path = path.as_ref();
+        self.reorder_in_undo_context(asset_id, path, |data_set, schema_set| {
+            data_set.move_dynamic_array_entry_before(
+                schema_set,
+                asset_id,
+                path,
+                entry_id,
+                other_entry_id,
+            )
+        })
+    }
+
+    pub fn move_dynamic_array_entry_after(
+        &mut self,
+        asset_id: AssetId,
+        path: impl AsRef<str>,
+        entry_id: Uuid,
+        other_entry_id: Uuid,
+    ) -> DataSetResult<()> {
+        let path = path.as_ref();
+        self.reorder_in_undo_context(asset_id, path, |data_set, schema_set| {
+            data_set.move_dynamic_array_entry_after(
+                schema_set,
+                asset_id,
+                path,
+                entry_id,
+                other_entry_id,
+            )
+        })
+    }
+
     pub fn resolve_dynamic_array_entries(
         &self,
         asset_id: AssetId,
@@ -680,11 +1129,30 @@ impl EditContext {
         path: impl AsRef<str>,
         behavior: OverrideBehavior,
     ) -> DataSetResult<()> {
+        self.check_property_not_readonly(asset_id, path.as_ref())?;
         self.track_existing_asset(asset_id)?;
         self.data_set
             .set_override_behavior(&self.schema_set, asset_id, path, behavior)
     }
 
+    // Overrides of readonly properties can't be edited, so they are kept. Only the cleared
+    // overrides are returned.
+    pub fn clear_all_overrides(
+        &mut self,
+        asset_id: AssetId,
+    ) -> DataSetResult<PropertiesBundle> {
+        let schema = self
+            .data_set
+            .asset_schema(asset_id)
+            .ok_or(DataSetError::AssetNotFound)?
+            .clone();
+        self.track_existing_asset(asset_id)?;
+        let named_types = self.schema_set.schemas();
+        self.data_set.clear_overrides_where(asset_id, |path| {
+            !schema.is_property_readonly(path, named_types)
+        })
+    }
+
     pub fn read_properties_bundle(
         &self,
         schema_set: &SchemaSet,
@@ -702,6 +1170,7 @@ impl EditContext {
         path: impl AsRef<str>,
         properties_bundle: &PropertiesBundle,
     ) -> DataSetResult<()> {
+        self.check_property_not_readonly(asset_id, path.as_ref())?;
         self.track_existing_asset(asset_id)?;
         self.data_set
             .write_properties_bundle(schema_set, asset_id, path, properties_bundle)
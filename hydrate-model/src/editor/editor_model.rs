@@ -1,28 +1,62 @@
-use crate::edit_context::EditContext;
-use crate::editor::undo::UndoStack;
+use crate::edit_context::{EditContext, ReplaceAssetRefPropertiesResult};
+use crate::editor::schema_changes;
+use crate::editor::undo::{UndoEntryInfo, UndoStack};
 use crate::{
-    AssetId, AssetPath, AssetPathCache, AssetSourceId, DataSet, DataSource,
-    FileSystemIdBasedDataSource, FileSystemPathBasedDataSource, HashMap, PathNode, PathNodeRoot,
-    PendingFileOperations, SchemaNamedType, SchemaSet,
+    AssetId, AssetPath, AssetPathCache, AssetSourceId, AutosaveRecovery, DataSet, DataSource,
+    EndContextBehavior, ExportBundle, ExportBundleAsset, FileSystemIdBasedDataSource,
+    FileSystemPathBasedDataSource, HashMap, HashSet, PathNode, PathNodeRoot, PendingFileOperations,
+    SchemaChange, SchemaNamedType, SchemaSet,
 };
+use hydrate_base::canonicalize_path;
+use hydrate_data::json_storage::AssetJson;
 use hydrate_data::{
-    AssetLocation, AssetName, CanonicalPathReference, DataSetError, DataSetResult, ImportInfo,
-    PathReferenceHash, SingleObject,
+    canonicalize_source_file_path, AssetDiffSet, AssetLocation, AssetName, CanonicalPathReference,
+    DataSetError, DataSetResult, ImportDataStorage, ImportInfo, PathReferenceHash,
+    PathReferenceNamespaceResolver, SingleObject,
 };
 use hydrate_pipeline::{
-    DynEditorModel, HydrateProjectConfiguration, ImportJobToQueue, ImporterRegistry,
+    read_import_data_bytes, write_import_data_bytes, DynEditorModel, HydrateProjectConfiguration,
+    ImportJobToQueue, ImporterRegistry, PipelineResult,
 };
 use hydrate_schema::{SchemaFingerprint, SchemaRecord};
 use slotmap::DenseSlotMap;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
 slotmap::new_key_type! { pub struct EditContextKey; }
 
+// An asset that was changed in both an edit context and the root context after the edit context
+// was opened
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditContextConflict {
+    pub asset_id: AssetId,
+    // Properties changed in the root context since the edit context was opened
+    pub root_changed_properties: Vec<String>,
+    // Properties changed in the edit context
+    pub edit_context_changed_properties: Vec<String>,
+    // The asset was deleted from the root context. Flushing would bring it back.
+    pub deleted_in_root: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EditContextConflictResolution {
+    // If there are any conflicts, nothing is flushed
+    Abort,
+    // Conflicting assets are overwritten with the edit context's version
+    KeepEditContext,
+    // Conflicting assets are not flushed, keeping the root context's version
+    KeepRoot,
+}
+
 pub struct EditorModel {
     project_config: HydrateProjectConfiguration,
     schema_set: SchemaSet,
     undo_stack: UndoStack,
     root_edit_context_key: EditContextKey,
     edit_contexts: DenseSlotMap<EditContextKey, EditContext>,
+    // The root context's version of the assets in each edit context, from when they were last in
+    // sync. Used to detect conflicts when flushing.
+    edit_context_root_versions: HashMap<EditContextKey, DataSet>,
     //TODO: slot_map?
     data_sources: HashMap<AssetSourceId, Box<dyn DataSource>>,
 
@@ -60,18 +94,18 @@ impl<'a> DynEditorModel for EditorModelWithCache<'a> {
                 edit_context.delete_asset(asset_id)?;
             }
 
+            // The import info is set as part of creating the asset
             edit_context.init_from_single_object(
                 asset_id,
                 asset_name,
                 asset_location,
                 default_asset,
+                import_info,
             )?;
+        } else {
+            edit_context.set_import_info(asset_id, import_info)?;
         }
 
-        //
-        // Whether it is regenerated or not, update import data
-        //
-        edit_context.set_import_info(asset_id, import_info)?;
         for (path_reference, referenced_asset_id) in canonical_path_references {
             edit_context.set_path_reference_override(
                 asset_id,
@@ -102,6 +136,14 @@ impl<'a> DynEditorModel for EditorModelWithCache<'a> {
         self.editor_model
             .asset_display_name_long(asset_id, &self.asset_path_cache)
     }
+
+    fn has_preview_edit(&self) -> bool {
+        self.editor_model.root_edit_context().has_preview_edit()
+    }
+
+    fn flush_pending_edits(&mut self) {
+        self.editor_model.flush_pending_edits()
+    }
 }
 
 impl EditorModel {
@@ -132,6 +174,7 @@ impl EditorModel {
             undo_stack,
             root_edit_context_key,
             edit_contexts,
+            edit_context_root_versions: Default::default(),
             data_sources: Default::default(),
             //location_tree: Default::default(),
             //asset_path_cache: AssetPathCache::empty(),
@@ -176,14 +219,28 @@ impl EditorModel {
     pub fn persist_generated_asset(
         &mut self,
         asset_id: AssetId,
-    ) {
+    ) -> PipelineResult<()> {
         for (_, data_source) in &mut self.data_sources {
             let root_edit_context = self
                 .edit_contexts
                 .get_mut(self.root_edit_context_key)
                 .unwrap();
 
-            data_source.persist_generated_asset(root_edit_context, asset_id);
+            data_source.persist_generated_asset(
+                &self.project_config,
+                root_edit_context,
+                asset_id,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Commits any pending undo contexts so the data set is fully up to date. Unlike saving, this
+    // does not write to data sources, so the edits still show as unsaved.
+    pub fn flush_pending_edits(&mut self) {
+        for (_, context) in &mut self.edit_contexts {
+            context.commit_pending_undo_context();
         }
     }
 
@@ -242,20 +299,35 @@ impl EditorModel {
             .unwrap()
     }
 
+    pub fn edit_context(
+        &self,
+        edit_context: EditContextKey,
+    ) -> Option<&EditContext> {
+        self.edit_contexts.get(edit_context)
+    }
+
+    pub fn edit_context_mut(
+        &mut self,
+        edit_context: EditContextKey,
+    ) -> Option<&mut EditContext> {
+        self.edit_contexts.get_mut(edit_context)
+    }
+
     pub fn asset_path(
         &self,
         asset_id: AssetId,
         asset_path_cache: &AssetPathCache,
     ) -> Option<AssetPath> {
         let root_data_set = &self.root_edit_context().data_set;
-        let location = root_data_set.asset_location(asset_id);
+        let location = root_data_set.asset_location(asset_id)?;
 
         // Look up the location, if we don't find it just assume the asset is at the root. This
         // allows some degree of robustness even when data is in a bad state (like cyclical references)
-        let path = location
-            .map(|x| asset_path_cache.path_to_id_lookup().get(&x.path_node_id()))
-            .flatten()
-            .cloned()?;
+        let path = asset_path_cache
+            .path_to_id_lookup()
+            .get(&location.path_node_id())
+            .cloned()
+            .unwrap_or_else(|| AssetPath::root_ref().clone());
 
         let name = root_data_set.asset_name(asset_id).unwrap().as_string();
         if let Some(name) = name {
@@ -282,6 +354,33 @@ impl EditorModel {
         self.data_sources.get(&asset_source_id).map(|x| &**x)
     }
 
+    // The source the asset is saved to, determined by the root of its location chain. None if the
+    // asset doesn't exist or its location chain doesn't end at a data source's root asset.
+    pub fn asset_source_id_for_asset(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<AssetSourceId> {
+        let root_location = self
+            .root_edit_context()
+            .asset_location_chain(asset_id)
+            .ok()?
+            .last()
+            .cloned()?;
+
+        self.data_sources
+            .keys()
+            .find(|x| *x.uuid() == root_location.path_node_id().as_uuid())
+            .copied()
+    }
+
+    pub fn data_source_for_asset(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<&dyn DataSource> {
+        let asset_source_id = self.asset_source_id_for_asset(asset_id)?;
+        self.data_sources.get(&asset_source_id).map(|x| &**x)
+    }
+
     pub fn is_a_root_asset(
         &self,
         asset_id: AssetId,
@@ -295,6 +394,274 @@ impl EditorModel {
         false
     }
 
+    /// Where the asset was imported from, None if the asset does not exist or was not imported
+    pub fn import_info(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<&ImportInfo> {
+        self.root_edit_context().data_set.import_info(asset_id)
+    }
+
+    /// All assets imported from the given file on disk, i.e. to re-import them when a file watcher
+    /// reports that the file changed
+    pub fn assets_imported_from(
+        &self,
+        source_path: &Path,
+    ) -> Vec<AssetId> {
+        // The file may have been deleted, in which case it can't be canonicalized
+        let source_path =
+            canonicalize_source_file_path(source_path, self.project_config.path_canonicalization)
+                .unwrap_or_else(|_| source_path.to_path_buf());
+
+        // Source files are referenced relative to their namespace when they are in one
+        let (namespace, path) = self
+            .project_config
+            .simplify_path(&source_path)
+            .unwrap_or_else(|| ("".to_string(), source_path));
+
+        self.root_edit_context()
+            .data_set
+            .assets_imported_from(&namespace, &path.to_string_lossy())
+    }
+
+    // Source files may have been stored with different spellings of the same path (i.e. "./" or
+    // ".." components), so resolve them to the file on disk and simplify them again to compare
+    fn normalized_source_file(
+        &self,
+        source_file: &CanonicalPathReference,
+    ) -> (String, PathBuf) {
+        let absolute_path = if source_file.namespace().is_empty() {
+            PathBuf::from(source_file.path())
+        } else if let Some(namespace_root) =
+            self.project_config.namespace_root(source_file.namespace())
+        {
+            namespace_root.join(source_file.path())
+        } else {
+            // Unknown namespace, nothing to resolve it against
+            return (
+                source_file.namespace().to_string(),
+                PathBuf::from(source_file.path()),
+            );
+        };
+
+        // The file may have been deleted, in which case it can't be canonicalized
+        let absolute_path = canonicalize_source_file_path(
+            &absolute_path,
+            self.project_config.path_canonicalization,
+        )
+        .unwrap_or(absolute_path);
+
+        self.project_config
+            .simplify_path(&absolute_path)
+            .unwrap_or_else(|| ("".to_string(), absolute_path))
+    }
+
+    /// Groups of assets that were imported from the same source file and importable, i.e. because
+    /// the file was imported again under a different spelling of its path. Each group is sorted by
+    /// ID and has at least two assets. Use merge_duplicates() to clean them up.
+    pub fn find_duplicate_imports(&self) -> Vec<Vec<AssetId>> {
+        let mut assets_by_source_file: HashMap<_, Vec<AssetId>> = HashMap::default();
+        for (asset_id, asset) in self.root_edit_context().assets() {
+            if let Some(import_info) = asset.import_info() {
+                let source_file = import_info.source_file();
+                let (namespace, path) = self.normalized_source_file(source_file);
+                assets_by_source_file
+                    .entry((namespace, path, source_file.importable_name().clone()))
+                    .or_default()
+                    .push(*asset_id);
+            }
+        }
+
+        let mut duplicates: Vec<_> = assets_by_source_file
+            .into_values()
+            .filter(|asset_ids| asset_ids.len() > 1)
+            .map(|mut asset_ids| {
+                asset_ids.sort();
+                asset_ids
+            })
+            .collect();
+        duplicates.sort();
+        duplicates
+    }
+
+    /// Repoints every reference to the assets in the group to the asset to keep, then deletes the
+    /// others. This is a single undo step. Nothing is changed if it fails.
+    pub fn merge_duplicates(
+        &mut self,
+        group: &[AssetId],
+        keep: AssetId,
+    ) -> DataSetResult<()> {
+        if !group.contains(&keep) {
+            Err(DataSetError::AssetNotFound)?;
+        }
+
+        let mapping: HashMap<AssetId, AssetId> = group
+            .iter()
+            .filter(|asset_id| **asset_id != keep)
+            .map(|asset_id| (*asset_id, keep))
+            .collect();
+
+        let mut result = Ok(());
+        self.root_edit_context_mut()
+            .with_undo_context("merge duplicates", |edit_context| {
+                result = edit_context
+                    .replace_asset_references(&mapping)
+                    .and_then(|_| {
+                        mapping
+                            .keys()
+                            .try_for_each(|asset_id| edit_context.delete_asset(*asset_id))
+                    });
+
+                if result.is_err() {
+                    // Revert whatever was changed before the error
+                    if let Err(e) = edit_context.cancel_pending_undo_context() {
+                        log::error!("Failed to revert merging duplicates: {:?}", e);
+                    }
+                }
+
+                EndContextBehavior::Finish
+            });
+
+        result
+    }
+
+    /// Repoints every asset ref property that references the old asset to the new one, i.e. to swap
+    /// one texture for another across the whole project. Prototypes, locations and other kinds of
+    /// references are not changed. If validate_ref_types is set, nothing is changed when any of
+    /// the properties can't reference an asset of the new asset's type. Readonly properties are
+    /// not changed, and their assets are returned as skipped. This is a single undo step.
+    pub fn replace_references(
+        &mut self,
+        old_asset_id: AssetId,
+        new_asset_id: AssetId,
+        validate_ref_types: bool,
+    ) -> DataSetResult<ReplaceAssetRefPropertiesResult> {
+        let mut result = Ok(ReplaceAssetRefPropertiesResult::default());
+        self.root_edit_context_mut()
+            .with_undo_context("replace references", |edit_context| {
+                result = edit_context.replace_asset_ref_properties(
+                    old_asset_id,
+                    new_asset_id,
+                    validate_ref_types,
+                );
+
+                if result.is_err() {
+                    // Revert whatever was changed before the error
+                    if let Err(e) = edit_context.cancel_pending_undo_context() {
+                        log::error!("Failed to revert replacing references: {:?}", e);
+                    }
+                }
+
+                EndContextBehavior::Finish
+            });
+
+        result
+    }
+
+    /// Gathers the asset, its prototype chain and everything it transitively references into a
+    /// bundle that can be added to another project with import_bundle(). Copied import data is
+    /// included, external import data is not since it is produced from the source file.
+    pub fn export_closure(
+        &self,
+        asset_id: AssetId,
+    ) -> PipelineResult<ExportBundle> {
+        let root_data_set = &self.root_edit_context().data_set;
+        if !root_data_set.assets().contains_key(&asset_id) {
+            Err(DataSetError::AssetNotFound)?;
+        }
+
+        let mut visited = HashSet::default();
+        let mut pending = vec![asset_id];
+        let mut assets = Vec::default();
+        while let Some(asset_id) = pending.pop() {
+            // Broken references are left broken
+            if !root_data_set.assets().contains_key(&asset_id) || !visited.insert(asset_id) {
+                continue;
+            }
+
+            let import_data = match root_data_set.import_info(asset_id) {
+                Some(import_info)
+                    if import_info.import_data_storage() == ImportDataStorage::Copied =>
+                {
+                    read_import_data_bytes(&self.project_config, asset_id)?
+                }
+                _ => None,
+            };
+
+            // The location is not included, it's chosen when the bundle is imported
+            assets.push(ExportBundleAsset {
+                asset_id,
+                asset_json: AssetJson::save_asset_to_string(
+                    &self.schema_set,
+                    root_data_set.assets(),
+                    asset_id,
+                    false,
+                    None,
+                ),
+                import_data,
+            });
+
+            pending.extend(root_data_set.resolve_all_references(asset_id)?);
+        }
+
+        Ok(ExportBundle {
+            root_asset_id: asset_id,
+            assets,
+        })
+    }
+
+    /// Adds the assets in a bundle created by export_closure() to the given location. Every asset
+    /// gets a new ID so that importing a bundle never collides with existing assets, even when
+    /// importing the same bundle twice. Returns the new ID of the bundle's root asset.
+    pub fn import_bundle(
+        &mut self,
+        bundle: &ExportBundle,
+        asset_location: AssetLocation,
+    ) -> PipelineResult<AssetId> {
+        let mapping: HashMap<AssetId, AssetId> = bundle
+            .assets
+            .iter()
+            .map(|x| (x.asset_id, AssetId::from_uuid(Uuid::new_v4())))
+            .collect();
+
+        // Load the assets as they were, then rewrite references between them to use the new IDs
+        let mut imported_assets = EditContext::new(
+            &self.project_config,
+            EditContextKey::default(),
+            self.schema_set.clone(),
+            &self.undo_stack,
+        );
+        for asset in &bundle.assets {
+            AssetJson::load_asset_from_string(
+                &mut imported_assets,
+                &self.schema_set,
+                Some(asset.asset_id.as_uuid()),
+                asset_location,
+                Some(asset_location),
+                &asset.asset_json,
+            )?;
+        }
+        imported_assets.data_set.remap_ids(&mapping)?;
+
+        for asset in &bundle.assets {
+            if let Some(import_data) = &asset.import_data {
+                write_import_data_bytes(
+                    &self.project_config,
+                    mapping[&asset.asset_id],
+                    import_data,
+                )?;
+            }
+        }
+
+        self.root_edit_context_mut()
+            .restore_assets_from(imported_assets.data_set)?;
+
+        Ok(mapping
+            .get(&bundle.root_asset_id)
+            .copied()
+            .ok_or(DataSetError::AssetNotFound)?)
+    }
+
     pub fn add_file_system_id_based_asset_source<RootPathT: Into<PathBuf>>(
         &mut self,
         project_config: &HydrateProjectConfiguration,
@@ -302,7 +669,11 @@ impl EditorModel {
         file_system_root_path: RootPathT,
         import_job_to_queue: &mut ImportJobToQueue,
     ) -> AssetSourceId {
-        let file_system_root_path = dunce::canonicalize(&file_system_root_path.into()).unwrap();
+        let file_system_root_path = canonicalize_path(
+            &file_system_root_path.into(),
+            project_config.path_canonicalization,
+        )
+        .unwrap();
         let path_node_root_schema = self.path_node_root_schema.as_record().unwrap().clone();
         let root_edit_context = self.root_edit_context_mut();
 
@@ -334,6 +705,7 @@ impl EditorModel {
         fs.load_from_storage(project_config, root_edit_context, import_job_to_queue);
 
         self.data_sources.insert(asset_source_id, Box::new(fs));
+        self.log_consistency_issues();
 
         asset_source_id
     }
@@ -346,7 +718,11 @@ impl EditorModel {
         importer_registry: &ImporterRegistry,
         import_jobs_to_queue: &mut ImportJobToQueue,
     ) -> AssetSourceId {
-        let file_system_root_path = dunce::canonicalize(&file_system_root_path.into()).unwrap();
+        let file_system_root_path = canonicalize_path(
+            &file_system_root_path.into(),
+            project_config.path_canonicalization,
+        )
+        .unwrap();
         let path_node_root_schema = self.path_node_root_schema.as_record().unwrap().clone();
         let root_edit_context = self.root_edit_context_mut();
 
@@ -379,11 +755,21 @@ impl EditorModel {
         fs.load_from_storage(project_config, root_edit_context, import_jobs_to_queue);
 
         self.data_sources.insert(asset_source_id, Box::new(fs));
+        self.log_consistency_issues();
 
         asset_source_id
     }
 
-    pub fn save_root_edit_context(&mut self) {
+    // Data loaded from disk may be invalid in ways that don't prevent using it. Report it so that
+    // it can be fixed.
+    fn log_consistency_issues(&self) {
+        for issue in self.root_edit_context().data_set().check_consistency() {
+            log::warn!("{}", issue);
+        }
+    }
+
+    // Fails if a data source could not write everything, in which case the recovery file is kept
+    pub fn save_root_edit_context(&mut self) -> PipelineResult<()> {
         //
         // Ensure pending edits are flushed to the data set so that our modified assets list is fully up to date
         //
@@ -391,11 +777,22 @@ impl EditorModel {
             .edit_contexts
             .get_mut(self.root_edit_context_key)
             .unwrap();
+
+        // Preview edits are only saved once they are committed
+        if root_edit_context.has_preview_edit() {
+            log::warn!("Not saving while a preview edit is in progress");
+            return Ok(());
+        }
+
         root_edit_context.commit_pending_undo_context();
 
         for (_id, data_source) in &mut self.data_sources {
-            data_source.flush_to_storage(root_edit_context);
+            data_source.flush_to_storage(&self.project_config, root_edit_context)?;
         }
+
+        // Everything in the recovery file has been saved
+        self.remove_autosave();
+        Ok(())
     }
 
     pub fn revert_root_edit_context(
@@ -418,6 +815,8 @@ impl EditorModel {
         for (_id, data_source) in &mut self.data_sources {
             data_source.load_from_storage(project_config, root_edit_context, import_job_to_queue);
         }
+        self.log_consistency_issues();
+        self.remove_autosave();
 
         //
         // Clear modified assets list since we reloaded everything from disk.
@@ -428,24 +827,285 @@ impl EditorModel {
         //self.refresh_location_tree();
     }
 
+    /// Writes unsaved changes in the root edit context to a recovery file in job_data_path, so that
+    /// they can be restored with restore_autosave() if the editor exits without saving. Nothing is
+    /// written to data sources, so the changes still show as unsaved. If there are no unsaved
+    /// changes, any existing recovery file is removed.
+    pub fn autosave(&self) -> PipelineResult<()> {
+        let root_edit_context = self.root_edit_context();
+
+        // Preview edits are only autosaved once they are committed
+        if root_edit_context.has_preview_edit() {
+            return Ok(());
+        }
+
+        let mut pending_file_operations = PendingFileOperations::default();
+        for data_source in self.data_sources.values() {
+            data_source
+                .append_pending_file_operations(root_edit_context, &mut pending_file_operations);
+        }
+
+        let modified_assets: Vec<AssetId> = pending_file_operations
+            .create_operations
+            .iter()
+            .chain(&pending_file_operations.modify_operations)
+            .map(|(asset_id, _)| *asset_id)
+            .collect();
+        // An asset moved between data sources is deleted from one and created in the other
+        let deleted_assets: Vec<AssetId> = pending_file_operations
+            .delete_operations
+            .iter()
+            .map(|(asset_id, _)| *asset_id)
+            .filter(|asset_id| !modified_assets.contains(asset_id))
+            .collect();
+
+        if modified_assets.is_empty() && deleted_assets.is_empty() {
+            self.remove_autosave();
+            return Ok(());
+        }
+
+        let data_set = &root_edit_context.data_set;
+        let mut locations = Vec::<(AssetId, Vec<String>)>::default();
+        for &asset_id in &modified_assets {
+            let location_id = data_set
+                .asset_location(asset_id)
+                .ok_or(DataSetError::AssetNotFound)?
+                .path_node_id();
+            if location_id.is_null()
+                || modified_assets.contains(&location_id)
+                || locations.iter().any(|(x, _)| *x == location_id)
+            {
+                continue;
+            }
+
+            let mut path_node_ids = vec![location_id];
+            path_node_ids.extend(
+                root_edit_context
+                    .asset_location_chain(location_id)?
+                    .iter()
+                    .map(|x| x.path_node_id()),
+            );
+            let path = path_node_ids
+                .iter()
+                .rev()
+                .map(|&x| {
+                    data_set
+                        .asset_name(x)
+                        .ok()
+                        .and_then(|x| x.as_string().cloned())
+                        .unwrap_or_default()
+                })
+                .collect();
+            locations.push((location_id, path));
+        }
+
+        let recovery = AutosaveRecovery {
+            saved_at: std::time::SystemTime::now(),
+            assets: data_set.serialize_to_vec(&self.schema_set, &modified_assets),
+            deleted_assets,
+            locations,
+        };
+
+        // Write to a temporary file first so that a crash while autosaving doesn't lose the
+        // previous recovery file
+        let path = AutosaveRecovery::path(&self.project_config);
+        let temp_path = path.with_extension("tmp");
+        std::fs::create_dir_all(&self.project_config.job_data_path)?;
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(&temp_path)?);
+        recovery.write(&mut writer)?;
+        writer.flush()?;
+        drop(writer);
+        std::fs::rename(&temp_path, &path)?;
+
+        log::debug!(
+            "Autosaved {} modified and {} deleted assets",
+            modified_assets.len(),
+            recovery.deleted_assets.len()
+        );
+        Ok(())
+    }
+
+    /// Reads the recovery file written by autosave(). Returns None if there is no recovery file,
+    /// which is the case if everything was saved or reverted since the last autosave.
+    pub fn read_autosave(&self) -> PipelineResult<Option<AutosaveRecovery>> {
+        let path = AutosaveRecovery::path(&self.project_config);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let mut file = std::io::BufReader::new(std::fs::File::open(path)?);
+        Ok(Some(AutosaveRecovery::read(&mut file)?))
+    }
+
+    /// Applies the changes in a recovery file to the root edit context. Like reverting, this is not
+    /// an undo step. The changes are not saved, so they show as unsaved until the user saves.
+    pub fn restore_autosave(
+        &mut self,
+        recovery: &AutosaveRecovery,
+    ) -> PipelineResult<()> {
+        // Load the assets separately so that their locations can be fixed up before adding them
+        let mut recovered_assets = EditContext::new(
+            &self.project_config,
+            EditContextKey::default(),
+            self.schema_set.clone(),
+            &self.undo_stack,
+        );
+        DataSet::deserialize_from_slice(&mut recovered_assets, &self.schema_set, &recovery.assets)?;
+
+        let mut mapping = HashMap::default();
+        for (location_id, path) in &recovery.locations {
+            if self.root_edit_context().has_asset(*location_id) {
+                continue;
+            }
+
+            if let Some(new_location_id) = self.find_path_node(path) {
+                mapping.insert(*location_id, new_location_id);
+            } else {
+                log::warn!("Could not find location {:?} of autosaved assets", path);
+            }
+        }
+        recovered_assets.data_set.remap_ids(&mapping)?;
+
+        let root_edit_context = self.root_edit_context_mut();
+        root_edit_context.commit_pending_undo_context();
+        root_edit_context.restore_assets_from(recovered_assets.data_set)?;
+        for &asset_id in &recovery.deleted_assets {
+            if root_edit_context.has_asset(asset_id) {
+                root_edit_context.delete_asset(asset_id)?;
+            }
+        }
+
+        self.log_consistency_issues();
+        Ok(())
+    }
+
+    // Finds a path node by the names leading to it, starting with the name of the data source
+    fn find_path_node(
+        &self,
+        path: &[String],
+    ) -> Option<AssetId> {
+        let data_set = &self.root_edit_context().data_set;
+        let (data_source_name, path) = path.split_first()?;
+        let mut path_node_id = data_set
+            .assets()
+            .iter()
+            .find(|(_, asset)| {
+                asset.schema().fingerprint() == self.path_node_root_schema.fingerprint()
+                    && asset.asset_name().as_string() == Some(data_source_name)
+            })
+            .map(|(asset_id, _)| *asset_id)?;
+
+        for name in path {
+            path_node_id = data_set
+                .assets()
+                .iter()
+                .find(|(_, asset)| {
+                    asset.asset_location().path_node_id() == path_node_id
+                        && asset.asset_name().as_string() == Some(name)
+                })
+                .map(|(asset_id, _)| *asset_id)?;
+        }
+
+        Some(path_node_id)
+    }
+
+    /// Removes the recovery file written by autosave(), i.e. if the user chose not to restore it
+    pub fn remove_autosave(&self) {
+        let path = AutosaveRecovery::path(&self.project_config);
+        if path.exists() {
+            if let Err(e) = std::fs::remove_file(&path) {
+                log::error!("Failed to remove autosave recovery file {:?}: {}", path, e);
+            }
+        }
+    }
+
+    /// Compares the current schemas against the ones recorded by record_schema_fingerprints() when
+    /// the project was last opened. Returns which named types were added, removed or changed,
+    /// sorted by name. Nothing is reported if schemas were never recorded for the project.
+    pub fn detect_schema_changes(&self) -> Vec<SchemaChange> {
+        match schema_changes::find_schema_changes(&self.project_config, &self.schema_set) {
+            Ok(changes) => changes.unwrap_or_default(),
+            Err(e) => {
+                log::error!("Failed to read recorded schema fingerprints: {:?}", e);
+                Vec::default()
+            }
+        }
+    }
+
+    /// Records the fingerprints of the current schemas in the job data directory, so that the next
+    /// session can detect schema changes with detect_schema_changes()
+    pub fn record_schema_fingerprints(&self) -> PipelineResult<()> {
+        schema_changes::write_schema_fingerprints(&self.project_config, &self.schema_set)
+    }
+
+    // Picks up changes made to data sources outside the editor, returning the locations that
+    // changed. Imports for new or modified source files are added to import_job_to_queue.
+    pub fn poll_data_sources_for_changes(
+        &mut self,
+        import_job_to_queue: &mut ImportJobToQueue,
+    ) -> Vec<AssetLocation> {
+        let root_edit_context = self
+            .edit_contexts
+            .get_mut(self.root_edit_context_key)
+            .unwrap();
+
+        // Reloading assets under a preview edit would be reverted when the preview ends
+        if root_edit_context.has_preview_edit() {
+            return Vec::default();
+        }
+
+        // Changes on disk should not become part of the user's pending edit
+        root_edit_context.commit_pending_undo_context();
+
+        let mut changed_locations = Vec::default();
+        for data_source in self.data_sources.values_mut() {
+            changed_locations.append(&mut data_source.poll_storage_for_changes(
+                &self.project_config,
+                root_edit_context,
+                import_job_to_queue,
+            ));
+        }
+
+        if !changed_locations.is_empty() {
+            self.log_consistency_issues();
+        }
+
+        changed_locations
+    }
+
+    // True if a data source is still scanning for changes started by poll_data_sources_for_changes
+    pub fn is_polling_data_sources(&self) -> bool {
+        self.data_sources
+            .values()
+            .any(|data_source| data_source.is_polling_storage())
+    }
+
     pub fn close_file_system_source(
         &mut self,
-        _asset_source_id: AssetSourceId,
+        asset_source_id: AssetSourceId,
     ) {
-        unimplemented!();
-        // kill edit contexts or fail
+        //TODO: kill edit contexts or fail
 
-        // clear root_edit_context of data from this source
+        // clear root_edit_context of data from this source, including the source's root path node
+        let root_edit_context = self.root_edit_context_mut();
+        let mut assets_to_delete: Vec<_> = root_edit_context
+            .assets_for_source(asset_source_id)
+            .collect();
+        assets_to_delete.push(AssetId::from_uuid(*asset_source_id.uuid()));
+        for asset_id in assets_to_delete {
+            root_edit_context.delete_asset(asset_id).unwrap();
+        }
 
         // drop the source
-        //let old = self.data_sources.remove(&asset_source_id);
-        //assert!(old.is_some());
+        let old = self.data_sources.remove(&asset_source_id);
+        assert!(old.is_some());
     }
 
     // Spawns a separate edit context with copies of the given assets. The undo stack will be shared
     // globally, but changes will not be visible on the root context. The edit context will be flushed
     // to the root context in a single operation. Generally, we don't expect assets opened in a
     // separate edit context to change in the root context, but there is nothing that prevents it.
+    // Use find_edit_context_conflicts() to check for this.
     pub fn open_edit_context(
         &mut self,
         assets: &[AssetId],
@@ -470,41 +1130,185 @@ impl EditorModel {
             }
         }
 
+        let mut root_versions = DataSet::default();
         for &asset_id in assets {
             new_edit_context
                 .data_set
                 .copy_from(root_edit_context.data_set(), asset_id)
                 .expect("Could not copy asset to newly created edit context");
+            root_versions
+                .copy_from(root_edit_context.data_set(), asset_id)
+                .expect("Could not copy asset to newly created edit context");
         }
 
+        self.edit_context_root_versions
+            .insert(new_edit_context_key, root_versions);
+
         Ok(new_edit_context_key)
     }
 
+    /// Returns mutable references to several edit contexts at once so that they can be edited in
+    /// parallel, i.e. from threads spawned with std::thread::scope(). Each context can then be
+    /// flushed with flush_edit_context_to_root(). Returns DataSetError::EditContextsOverlap if a
+    /// context is requested more than once or an asset is in more than one of the contexts,
+    /// because flushing them would overwrite each other's changes. Panics if any of the keys is
+    /// the root context or has been closed.
+    pub fn disjoint_edit_contexts_mut<const N: usize>(
+        &mut self,
+        edit_contexts: [EditContextKey; N],
+    ) -> DataSetResult<[&mut EditContext; N]> {
+        for edit_context in edit_contexts {
+            assert_ne!(edit_context, self.root_edit_context_key);
+            assert!(self.edit_contexts.contains_key(edit_context));
+        }
+
+        let mut seen_assets = HashSet::default();
+        for (i, edit_context) in edit_contexts.iter().enumerate() {
+            if edit_contexts[..i].contains(edit_context) {
+                return Err(DataSetError::EditContextsOverlap)?;
+            }
+
+            for &asset_id in self.edit_contexts[*edit_context].assets().keys() {
+                if !seen_assets.insert(asset_id) {
+                    return Err(DataSetError::EditContextsOverlap)?;
+                }
+            }
+        }
+
+        Ok(self.edit_contexts.get_disjoint_mut(edit_contexts).unwrap())
+    }
+
+    /// Returns the assets that were changed in both the edit context and the root context since
+    /// the edit context was opened or last flushed
+    pub fn find_edit_context_conflicts(
+        &self,
+        edit_context: EditContextKey,
+    ) -> Vec<EditContextConflict> {
+        assert_ne!(edit_context, self.root_edit_context_key);
+        let root_context = self.root_edit_context();
+        let context_to_flush = self.edit_contexts.get(edit_context).unwrap();
+        let Some(root_versions) = self.edit_context_root_versions.get(&edit_context) else {
+            return Vec::default();
+        };
+
+        let changed_properties = |after_data_set: &DataSet, asset_id: AssetId| {
+            let diff = AssetDiffSet::diff_assets(
+                root_versions,
+                asset_id,
+                after_data_set,
+                asset_id,
+                &mut HashSet::default(),
+            );
+            if diff.has_changes() {
+                Some(diff.apply_diff.changed_properties())
+            } else {
+                None
+            }
+        };
+
+        let mut conflicts = Vec::default();
+        for &asset_id in context_to_flush.assets().keys() {
+            // Assets created in the edit context can't conflict
+            if !root_versions.assets().contains_key(&asset_id) {
+                continue;
+            }
+
+            let Some(edit_context_changed_properties) =
+                changed_properties(&context_to_flush.data_set, asset_id)
+            else {
+                continue;
+            };
+
+            if !root_context.has_asset(asset_id) {
+                conflicts.push(EditContextConflict {
+                    asset_id,
+                    root_changed_properties: Vec::default(),
+                    edit_context_changed_properties,
+                    deleted_in_root: true,
+                });
+            } else if let Some(root_changed_properties) =
+                changed_properties(&root_context.data_set, asset_id)
+            {
+                conflicts.push(EditContextConflict {
+                    asset_id,
+                    root_changed_properties,
+                    edit_context_changed_properties,
+                    deleted_in_root: false,
+                });
+            }
+        }
+
+        conflicts.sort_by_key(|x| x.asset_id);
+        conflicts
+    }
+
+    // Overwrites the root context's assets with the edit context's, even if the root context
+    // changed them too
     pub fn flush_edit_context_to_root(
         &mut self,
         edit_context: EditContextKey,
     ) -> DataSetResult<()> {
+        self.flush_edit_context_to_root_with_conflict_resolution(
+            edit_context,
+            EditContextConflictResolution::KeepEditContext,
+        )?;
+        Ok(())
+    }
+
+    /// Flushes the edit context to the root context, handling assets that changed in both
+    /// according to conflict_resolution. Returns the conflicts that were found. With
+    /// EditContextConflictResolution::Abort, nothing is flushed if any are returned.
+    pub fn flush_edit_context_to_root_with_conflict_resolution(
+        &mut self,
+        edit_context: EditContextKey,
+        conflict_resolution: EditContextConflictResolution,
+    ) -> DataSetResult<Vec<EditContextConflict>> {
         assert_ne!(edit_context, self.root_edit_context_key);
+        let conflicts = self.find_edit_context_conflicts(edit_context);
+        if conflict_resolution == EditContextConflictResolution::Abort && !conflicts.is_empty() {
+            return Ok(conflicts);
+        }
+
         let [root_context, context_to_flush] = self
             .edit_contexts
             .get_disjoint_mut([self.root_edit_context_key, edit_context])
             .unwrap();
+        let root_versions = self
+            .edit_context_root_versions
+            .entry(edit_context)
+            .or_default();
 
         // In the case of failure we want to flush as much as we can, so keep the error around and
         // return it after trying to flush all the assetsa
         let mut first_error = None;
         for &asset_id in context_to_flush.assets().keys() {
+            if conflict_resolution == EditContextConflictResolution::KeepRoot
+                && conflicts.iter().any(|x| x.asset_id == asset_id)
+            {
+                continue;
+            }
+
             if let Err(e) = root_context
                 .data_set
                 .copy_from(&context_to_flush.data_set, asset_id)
             {
                 if first_error.is_none() {
-                    first_error = Some(Err(e));
+                    first_error = Some(e);
                 }
+                continue;
             }
+
+            // The root context is now in sync with the edit context for this asset
+            root_versions
+                .copy_from(&root_context.data_set, asset_id)
+                .unwrap();
+        }
+
+        if let Some(e) = first_error {
+            return Err(e);
         }
 
-        first_error.unwrap_or(Ok(()))
+        Ok(conflicts)
     }
 
     pub fn close_edit_context(
@@ -513,6 +1317,7 @@ impl EditorModel {
     ) {
         assert_ne!(edit_context, self.root_edit_context_key);
         self.edit_contexts.remove(edit_context);
+        self.edit_context_root_versions.remove(&edit_context);
     }
 
     pub fn undo(&mut self) -> DataSetResult<()> {
@@ -522,4 +1327,26 @@ impl EditorModel {
     pub fn redo(&mut self) -> DataSetResult<()> {
         self.undo_stack.redo(&mut self.edit_contexts)
     }
+
+    pub fn undo_history(&mut self) -> &[UndoEntryInfo] {
+        self.undo_stack.undo_history()
+    }
+
+    pub fn redo_history(&mut self) -> &[UndoEntryInfo] {
+        self.undo_stack.redo_history()
+    }
+
+    pub fn undo_n(
+        &mut self,
+        count: usize,
+    ) -> DataSetResult<()> {
+        self.undo_stack.undo_n(&mut self.edit_contexts, count)
+    }
+
+    pub fn redo_to(
+        &mut self,
+        index: usize,
+    ) -> DataSetResult<()> {
+        self.undo_stack.redo_to(&mut self.edit_contexts, index)
+    }
 }
@@ -0,0 +1,120 @@
+use crate::AssetId;
+use hydrate_base::b3f::{B3FReader, B3FWriter};
+use hydrate_pipeline::PipelineResult;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// Bundles are B3F files. Block 0 is a json manifest listing the assets as they would be saved in a
+// data source. Import data is stored as is in the following blocks.
+const EXPORT_BUNDLE_TAG: &[u8; 4] = b"HYEB";
+const EXPORT_BUNDLE_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct ExportBundleAssetJson {
+    id: Uuid,
+    asset: String,
+    import_data_block: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExportBundleManifestJson {
+    root_asset_id: Uuid,
+    assets: Vec<ExportBundleAssetJson>,
+}
+
+pub struct ExportBundleAsset {
+    pub asset_id: AssetId,
+    // The asset as it would be stored in a data source
+    pub asset_json: String,
+    // The asset's copied import data, if it has any
+    pub import_data: Option<Vec<u8>>,
+}
+
+/// A self-contained copy of an asset and everything it depends on, i.e. to share an asset with
+/// another project. Created with EditorModel::export_closure() and added to a project with
+/// EditorModel::import_bundle().
+pub struct ExportBundle {
+    pub(super) root_asset_id: AssetId,
+    pub(super) assets: Vec<ExportBundleAsset>,
+}
+
+impl ExportBundle {
+    pub fn root_asset_id(&self) -> AssetId {
+        self.root_asset_id
+    }
+
+    pub fn assets(&self) -> &[ExportBundleAsset] {
+        &self.assets
+    }
+
+    pub fn write<W: std::io::Write>(
+        &self,
+        write: W,
+    ) -> PipelineResult<()> {
+        let mut import_data_blocks = Vec::default();
+        let mut assets = Vec::with_capacity(self.assets.len());
+        for asset in &self.assets {
+            let import_data_block = asset.import_data.as_ref().map(|import_data| {
+                import_data_blocks.push(import_data);
+                import_data_blocks.len()
+            });
+
+            assets.push(ExportBundleAssetJson {
+                id: asset.asset_id.as_uuid(),
+                asset: asset.asset_json.clone(),
+                import_data_block,
+            });
+        }
+
+        let manifest = serde_json::to_string_pretty(&ExportBundleManifestJson {
+            root_asset_id: self.root_asset_id.as_uuid(),
+            assets,
+        })?;
+
+        let mut b3f_writer = B3FWriter::new_from_u8_tag(*EXPORT_BUNDLE_TAG, EXPORT_BUNDLE_VERSION);
+        b3f_writer.add_block(manifest.as_bytes());
+        for import_data in import_data_blocks {
+            b3f_writer.add_block(import_data);
+        }
+
+        b3f_writer.write(write);
+        Ok(())
+    }
+
+    pub fn read<R: std::io::Read + std::io::Seek>(read: &mut R) -> PipelineResult<ExportBundle> {
+        let b3f = B3FReader::new(read)?.ok_or("Not a B3F file")?;
+        if b3f.file_tag_as_u8() != EXPORT_BUNDLE_TAG || b3f.version() != EXPORT_BUNDLE_VERSION {
+            Err("Not an export bundle, or the bundle is from an unsupported version")?;
+        }
+
+        let manifest_block = b3f.read_block(read, 0)?;
+        let manifest: ExportBundleManifestJson = serde_json::from_slice(&manifest_block)?;
+
+        let mut assets = Vec::with_capacity(manifest.assets.len());
+        for asset in manifest.assets {
+            let import_data = if let Some(import_data_block) = asset.import_data_block {
+                if import_data_block >= b3f.block_count() {
+                    Err(format!(
+                        "Export bundle is missing import data for asset {}",
+                        asset.id
+                    ))?;
+                }
+
+                Some(b3f.read_block(read, import_data_block)?)
+            } else {
+                None
+            };
+
+            assets.push(ExportBundleAsset {
+                asset_id: AssetId::from_uuid(asset.id),
+                asset_json: asset.asset,
+                import_data,
+            });
+        }
+
+        Ok(ExportBundle {
+            root_asset_id: AssetId::from_uuid(manifest.root_asset_id),
+            assets,
+        })
+    }
+}
@@ -1,93 +1,192 @@
 use crate::{AssetPath, EditorModel};
 use hydrate_base::hashing::HashMap;
 use hydrate_base::AssetId;
-use hydrate_data::DataSet;
+use hydrate_data::{AssetLocation, DataSet};
 use hydrate_schema::{DataSetResult, SchemaNamedType};
 
+// Problems found while placing assets in the path hierarchy. The hierarchy is still built, assets
+// that can't be placed normally are put at the root instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeBuildWarning {
+    // The asset's location is a path node that does not exist
+    OrphanedAsset {
+        asset_id: AssetId,
+        missing_path_node_id: AssetId,
+    },
+    // Following the path node's parents leads back to the path node
+    CyclicalLocation {
+        asset_id: AssetId,
+    },
+    // Path nodes need a name to be part of a path
+    UnnamedPathNode {
+        asset_id: AssetId,
+    },
+}
+
+impl std::fmt::Display for TreeBuildWarning {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            TreeBuildWarning::OrphanedAsset {
+                asset_id,
+                missing_path_node_id,
+            } => write!(
+                f,
+                "Asset {} is in path node {} which does not exist",
+                asset_id.as_uuid(),
+                missing_path_node_id.as_uuid()
+            ),
+            TreeBuildWarning::CyclicalLocation { asset_id } => {
+                write!(f, "Path node {} is its own parent", asset_id.as_uuid())
+            }
+            TreeBuildWarning::UnnamedPathNode { asset_id } => {
+                write!(f, "Path node {} has no name", asset_id.as_uuid())
+            }
+        }
+    }
+}
+
+// Like DataSet::asset_location_chain(), but stops at a missing parent or a cycle rather than
+// failing, so the returned chain always ends at the highest ancestor that could be found
+pub(crate) fn location_chain_to_root(
+    data_set: &DataSet,
+    asset_id: AssetId,
+) -> Vec<AssetLocation> {
+    let mut chain: Vec<AssetLocation> = Vec::default();
+    let mut location = data_set.asset_location(asset_id);
+    while let Some(current) = location {
+        if current.is_null()
+            || current.path_node_id() == asset_id
+            || chain.contains(&current)
+            || !data_set.assets().contains_key(&current.path_node_id())
+        {
+            break;
+        }
+
+        location = data_set.asset_location(current.path_node_id());
+        chain.push(current);
+    }
+
+    chain
+}
+
 pub fn build_path_lookup(
     data_set: &DataSet,
     path_node_type: &SchemaNamedType,
     path_node_root_type: &SchemaNamedType,
-) -> DataSetResult<HashMap<AssetId, AssetPath>> {
+) -> DataSetResult<(HashMap<AssetId, AssetPath>, Vec<TreeBuildWarning>)> {
     let mut paths = HashMap::<AssetId, AssetPath>::default();
+    let mut warnings = Vec::default();
     for (asset_id, info) in data_set.assets() {
+        // Any asset can be orphaned, but only path nodes have paths
+        let location = info.asset_location();
+        if !location.is_null() && !data_set.assets().contains_key(&location.path_node_id()) {
+            warnings.push(TreeBuildWarning::OrphanedAsset {
+                asset_id: *asset_id,
+                missing_path_node_id: location.path_node_id(),
+            });
+        }
+
         if info.schema().fingerprint() == path_node_root_type.fingerprint() {
             // Special case for path node roots
             if let Some(name) = info.asset_name().as_string() {
                 paths.insert(*asset_id, AssetPath::new_root(name));
             }
-        } else {
-            // For assets that *are* path nodes, use their ID directly. For assets that aren't
-            // path nodes, use their location asset ID
-            let path_node_id = if info.schema().fingerprint() == path_node_type.fingerprint()
-                || info.schema().fingerprint() == path_node_root_type.fingerprint()
-            {
-                *asset_id
-            } else {
-                // We could process assets so that if for some reason the parent nodes don't exist, we can still
-                // generate path lookups for them. Instead we will consider a parent not being found as
-                // the asset being at the root level. We could also have a "lost and found" UI.
-                //info.asset_location().path_node_id()
+        } else if info.schema().fingerprint() == path_node_type.fingerprint() {
+            let Some(name) = info.asset_name().as_string() else {
+                warnings.push(TreeBuildWarning::UnnamedPathNode {
+                    asset_id: *asset_id,
+                });
                 continue;
             };
 
-            // We will walk up the location chain and cache the path_node_id/path pairs. (We resolve
-            // the parents recursively going all the way up to the root, and then appending the
-            // current node to it's parent's resolved path.)
-            let mut chain = data_set.asset_location_chain(path_node_id)?;
-            if !chain.is_empty() {
-                let name = data_set.asset_name(chain.pop().unwrap().path_node_id())?;
-                if let Some(name) = name.as_string() {
-                    let mut asset_path = AssetPath::new_root(name);
-
-                    let mut path_is_valid = true;
-                    for i in (0..chain.len()).rev() {
-                        let name = data_set.asset_name(chain[i].path_node_id())?;
-                        if let Some(name) = name.as_string() {
-                            asset_path = asset_path.join(name);
-                        } else {
-                            path_is_valid = false;
-                            break;
-                        }
-                    }
+            // Walk up the location chain to the highest ancestor we can find. If that isn't a
+            // path node root (i.e. the chain is orphaned or cyclical) the path starts at the root.
+            let mut chain = location_chain_to_root(data_set, *asset_id);
+            let top_location = chain.last().cloned();
+            if top_location
+                .as_ref()
+                .and_then(|x| data_set.asset_location(x.path_node_id()))
+                .map(|x| x.path_node_id() == *asset_id)
+                .unwrap_or(false)
+            {
+                warnings.push(TreeBuildWarning::CyclicalLocation {
+                    asset_id: *asset_id,
+                });
+            }
 
-                    if path_is_valid {
-                        let name = data_set.asset_name(*asset_id)?;
-                        if let Some(name) = name.as_string() {
-                            asset_path = asset_path.join(name);
-                            paths.insert(*asset_id, asset_path);
-                        }
+            let mut asset_path = AssetPath::root_ref().clone();
+            if let Some(top_location) = &top_location {
+                if data_set
+                    .asset_schema(top_location.path_node_id())
+                    .map(|x| x.fingerprint())
+                    == Some(path_node_root_type.fingerprint())
+                {
+                    chain.pop();
+                    match data_set
+                        .asset_name(top_location.path_node_id())?
+                        .as_string()
+                    {
+                        Some(root_name) => asset_path = AssetPath::new_root(root_name),
+                        None => continue,
                     }
                 }
             }
+
+            // Unnamed ancestors are reported on their own, their children can't have a path
+            let mut path_is_valid = true;
+            for location in chain.iter().rev() {
+                if let Some(name) = data_set.asset_name(location.path_node_id())?.as_string() {
+                    asset_path = asset_path.join(name);
+                } else {
+                    path_is_valid = false;
+                    break;
+                }
+            }
+
+            if path_is_valid {
+                paths.insert(*asset_id, asset_path.join(name));
+            }
         }
     }
 
-    Ok(paths)
+    Ok((paths, warnings))
 }
 
 pub struct AssetPathCache {
     path_to_id_lookup: HashMap<AssetId, AssetPath>,
+    warnings: Vec<TreeBuildWarning>,
 }
 
 impl AssetPathCache {
     pub fn empty() -> Self {
         AssetPathCache {
             path_to_id_lookup: Default::default(),
+            warnings: Default::default(),
         }
     }
 
     pub fn build(editor_model: &EditorModel) -> DataSetResult<Self> {
-        let path_to_id_lookup = build_path_lookup(
+        let (path_to_id_lookup, warnings) = build_path_lookup(
             editor_model.root_edit_context().data_set(),
             editor_model.path_node_schema(),
             editor_model.path_node_root_schema(),
         )?;
 
-        Ok(AssetPathCache { path_to_id_lookup })
+        Ok(AssetPathCache {
+            path_to_id_lookup,
+            warnings,
+        })
     }
 
     pub fn path_to_id_lookup(&self) -> &HashMap<AssetId, AssetPath> {
         &self.path_to_id_lookup
     }
+
+    // Assets that couldn't be placed in the hierarchy normally, see TreeBuildWarning
+    pub fn warnings(&self) -> &[TreeBuildWarning] {
+        &self.warnings
+    }
 }
@@ -1,3 +1,4 @@
+use super::location_cache::location_chain_to_root;
 use crate::{AssetId, AssetLocation, AssetPathCache, DataSet, EditorModel};
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
@@ -69,15 +70,11 @@ impl LocationTree {
         tree_node_id: AssetId,
     ) {
         let mut path_asset_stack = vec![AssetLocation::new(tree_node_id)];
-        path_asset_stack.append(
-            &mut data_set
-                .asset_location_chain(tree_node_id)
-                .unwrap_or_default(),
-        );
+        path_asset_stack.append(&mut location_chain_to_root(data_set, tree_node_id));
 
         //
         // Get the node key for the first element of the path. It should already exist because we create
-        // nodes for the data sources.
+        // nodes for the data sources, unless the path is orphaned or cyclical.
         //
         let root_location = path_asset_stack.last().cloned().unwrap(); //.unwrap_or(AssetLocation::new(tree_node_id));
         let root_location_path_node_id = root_location.path_node_id();
@@ -94,41 +91,48 @@ impl LocationTree {
 
         path_asset_stack.pop();
 
-        if let Some(mut tree_node) = self.root_nodes.get_mut(&root_tree_node_key) {
-            while let Some(node_object) = path_asset_stack.pop() {
-                // Unnamed assets can't be paths
-                //let node_location = AssetLocation::new(node_asset);
-                //let location_chain = data_set.asset_location_chain(node_asset.path_node_id());
-
-                let node_name = data_set
-                    .asset_name(node_object.path_node_id())
-                    .unwrap()
-                    .as_string()
-                    .cloned()
-                    .unwrap(); //.unwrap_or_else(|| node_asset.as_uuid().to_string());
-
-                let node_key = LocationTreeNodeKey {
-                    name: node_name,
-                    location: node_object.clone(),
-                };
-
-                tree_node = tree_node.children.entry(node_key).or_insert_with(|| {
-                    //let path = paths.get(&node_asset).unwrap().clone();
-                    //let node_location = AssetLocation::new(source, location.parent_tree_node());
-                    //let location = AssetLocation::new(nod)
-                    let has_changes = false; //unsaved_paths.contains(&node_location);
-                    LocationTreeNode {
-                        //path,
-                        //source: node_location.source(),
-                        location: node_object,
-                        location_root: root_location.clone(),
-                        children: Default::default(),
-                        has_changes,
-                    }
-                });
-            }
-        } else {
-            //TODO: Handle this
+        // Paths that don't lead up to a data source are placed at the root
+        let mut tree_node = self
+            .root_nodes
+            .entry(root_tree_node_key)
+            .or_insert_with(|| LocationTreeNode {
+                location: root_location,
+                location_root: AssetLocation::null(),
+                children: Default::default(),
+                has_changes: false,
+            });
+
+        while let Some(node_object) = path_asset_stack.pop() {
+            // Unnamed assets can't be paths
+            //let node_location = AssetLocation::new(node_asset);
+            //let location_chain = data_set.asset_location_chain(node_asset.path_node_id());
+
+            let node_name = data_set
+                .asset_name(node_object.path_node_id())
+                .unwrap()
+                .as_string()
+                .cloned()
+                .unwrap(); //.unwrap_or_else(|| node_asset.as_uuid().to_string());
+
+            let node_key = LocationTreeNodeKey {
+                name: node_name,
+                location: node_object.clone(),
+            };
+
+            tree_node = tree_node.children.entry(node_key).or_insert_with(|| {
+                //let path = paths.get(&node_asset).unwrap().clone();
+                //let node_location = AssetLocation::new(source, location.parent_tree_node());
+                //let location = AssetLocation::new(nod)
+                let has_changes = false; //unsaved_paths.contains(&node_location);
+                LocationTreeNode {
+                    //path,
+                    //source: node_location.source(),
+                    location: node_object,
+                    location_root: root_location,
+                    children: Default::default(),
+                    has_changes,
+                }
+            });
         }
     }
 
@@ -1,10 +1,23 @@
 pub mod edit_context;
 
 mod editor_model;
-pub use editor_model::{EditContextKey, EditorModel, EditorModelWithCache};
+pub use editor_model::{
+    EditContextConflict, EditContextConflictResolution, EditContextKey, EditorModel,
+    EditorModelWithCache,
+};
+
+mod autosave;
+pub use autosave::AutosaveRecovery;
+
+mod schema_changes;
+pub use schema_changes::SchemaChange;
+
+mod export_bundle;
+pub use export_bundle::{ExportBundle, ExportBundleAsset};
 
 mod undo;
 pub use undo::EndContextBehavior;
+pub use undo::UndoEntryInfo;
 pub use undo::UndoStack;
 
 mod location_tree;
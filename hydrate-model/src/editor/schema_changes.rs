@@ -0,0 +1,143 @@
+use crate::{HashMap, SchemaFingerprint, SchemaNamedType, SchemaSet};
+use hydrate_pipeline::{HydrateProjectConfiguration, PipelineResult};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+const SCHEMA_FINGERPRINTS_FILE_NAME: &str = "schema_fingerprints.json";
+
+#[derive(Serialize, Deserialize)]
+struct RecordedSchemaJson {
+    name: String,
+    fingerprint: Uuid,
+    // Field names of records, symbol names of enums
+    fields: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecordedSchemasJson {
+    schemas: Vec<RecordedSchemaJson>,
+}
+
+/// A named type that is different from when the project was last opened, as reported by
+/// EditorModel::detect_schema_changes(). Assets of changed types may no longer load as they were
+/// saved, i.e. properties of removed fields are dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    Added {
+        type_name: String,
+    },
+    Removed {
+        type_name: String,
+    },
+    // Changed fingerprint. Fields may be empty if only the type of a field changed.
+    Changed {
+        type_name: String,
+        old_fingerprint: SchemaFingerprint,
+        new_fingerprint: SchemaFingerprint,
+        added_fields: Vec<String>,
+        removed_fields: Vec<String>,
+    },
+}
+
+impl SchemaChange {
+    pub fn type_name(&self) -> &str {
+        match self {
+            SchemaChange::Added { type_name }
+            | SchemaChange::Removed { type_name }
+            | SchemaChange::Changed { type_name, .. } => type_name,
+        }
+    }
+}
+
+fn field_names(named_type: &SchemaNamedType) -> Vec<String> {
+    match named_type {
+        SchemaNamedType::Record(x) => x.fields().iter().map(|x| x.name().to_string()).collect(),
+        SchemaNamedType::Enum(x) => x.symbols().iter().map(|x| x.name().to_string()).collect(),
+    }
+}
+
+pub(super) fn schema_fingerprints_path(project_config: &HydrateProjectConfiguration) -> PathBuf {
+    project_config
+        .job_data_path
+        .join(SCHEMA_FINGERPRINTS_FILE_NAME)
+}
+
+pub(super) fn write_schema_fingerprints(
+    project_config: &HydrateProjectConfiguration,
+    schema_set: &SchemaSet,
+) -> PipelineResult<()> {
+    let mut schemas: Vec<_> = schema_set
+        .schemas()
+        .values()
+        .map(|named_type| RecordedSchemaJson {
+            name: named_type.name().to_string(),
+            fingerprint: named_type.fingerprint().as_uuid(),
+            fields: field_names(named_type),
+        })
+        .collect();
+    schemas.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+
+    std::fs::create_dir_all(&project_config.job_data_path)?;
+    let json = serde_json::to_string_pretty(&RecordedSchemasJson { schemas })?;
+    std::fs::write(schema_fingerprints_path(project_config), json)?;
+    Ok(())
+}
+
+// Returns None if schemas were never recorded for the project
+pub(super) fn find_schema_changes(
+    project_config: &HydrateProjectConfiguration,
+    schema_set: &SchemaSet,
+) -> PipelineResult<Option<Vec<SchemaChange>>> {
+    let path = schema_fingerprints_path(project_config);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let recorded: RecordedSchemasJson = serde_json::from_slice(&std::fs::read(path)?)?;
+    let mut recorded_schemas: HashMap<_, _> = recorded
+        .schemas
+        .into_iter()
+        .map(|x| (x.name.clone(), x))
+        .collect();
+
+    let mut changes = Vec::default();
+    for named_type in schema_set.schemas().values() {
+        let type_name = named_type.name().to_string();
+        let Some(recorded_schema) = recorded_schemas.remove(&type_name) else {
+            changes.push(SchemaChange::Added { type_name });
+            continue;
+        };
+
+        let old_fingerprint = SchemaFingerprint::from_uuid(recorded_schema.fingerprint);
+        if old_fingerprint == named_type.fingerprint() {
+            continue;
+        }
+
+        let fields = field_names(named_type);
+        changes.push(SchemaChange::Changed {
+            type_name,
+            old_fingerprint,
+            new_fingerprint: named_type.fingerprint(),
+            added_fields: fields
+                .iter()
+                .filter(|x| !recorded_schema.fields.contains(x))
+                .cloned()
+                .collect(),
+            removed_fields: recorded_schema
+                .fields
+                .iter()
+                .filter(|x| !fields.contains(x))
+                .cloned()
+                .collect(),
+        });
+    }
+
+    changes.extend(
+        recorded_schemas
+            .into_keys()
+            .map(|type_name| SchemaChange::Removed { type_name }),
+    );
+    changes.sort_by(|lhs, rhs| lhs.type_name().cmp(rhs.type_name()));
+    Ok(Some(changes))
+}
@@ -20,13 +20,23 @@ pub enum EndContextBehavior {
     AllowResume,
 }
 
+// Describes a step in the undo chain, for displaying undo history
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndoEntryInfo {
+    pub label: &'static str,
+    pub affected_asset_count: usize,
+}
+
 pub struct CompletedUndoContextMessage {
     edit_context_key: EditContextKey,
     diff_set: DataSetDiffSet,
+    info: UndoEntryInfo,
 }
 
 pub struct UndoStack {
     undo_chain: Vec<CompletedUndoContextMessage>,
+    // Kept parallel to undo_chain so that history can be returned as a slice
+    undo_chain_info: Vec<UndoEntryInfo>,
     // Undo/Redo will decrease/increase this value, using apply/revert diffs to move backward and
     // forward. Appending new diffs will truncate the chain at current position and push a new
     // step on the chain. Zero means we have undone everything or there are no steps to undo.
@@ -40,6 +50,7 @@ impl Default for UndoStack {
         let (tx, rx) = mpsc::channel();
         UndoStack {
             undo_chain: Default::default(),
+            undo_chain_info: Default::default(),
             current_undo_index: 0,
             completed_undo_context_tx: tx,
             completed_undo_context_rx: rx,
@@ -54,11 +65,26 @@ impl UndoStack {
     fn drain_rx(&mut self) {
         while let Ok(diff) = self.completed_undo_context_rx.try_recv() {
             self.undo_chain.truncate(self.current_undo_index);
+            self.undo_chain_info.truncate(self.current_undo_index);
+            self.undo_chain_info.push(diff.info.clone());
             self.undo_chain.push(diff);
             self.current_undo_index += 1;
         }
     }
 
+    // Steps that can be undone, oldest first. Edits in an undo context that is still open (i.e.
+    // left resumable) are not included until that context is committed
+    pub fn undo_history(&mut self) -> &[UndoEntryInfo] {
+        self.drain_rx();
+        &self.undo_chain_info[0..self.current_undo_index]
+    }
+
+    // Steps that can be redone, the next step to redo first
+    pub fn redo_history(&mut self) -> &[UndoEntryInfo] {
+        self.drain_rx();
+        &self.undo_chain_info[self.current_undo_index..]
+    }
+
     pub fn undo(
         &mut self,
         edit_contexts: &mut DenseSlotMap<EditContextKey, EditContext>,
@@ -112,6 +138,38 @@ impl UndoStack {
 
         Ok(())
     }
+
+    // Undo up to count steps, stopping early if there is nothing left to undo
+    pub fn undo_n(
+        &mut self,
+        edit_contexts: &mut DenseSlotMap<EditContextKey, EditContext>,
+        count: usize,
+    ) -> DataSetResult<()> {
+        for _ in 0..count {
+            // undo() commits pending undo contexts, so check after the first step is undone
+            self.undo(edit_contexts)?;
+            if self.current_undo_index == 0 {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Redo steps up to and including the step at the given index of redo_history()
+    pub fn redo_to(
+        &mut self,
+        edit_contexts: &mut DenseSlotMap<EditContextKey, EditContext>,
+        index: usize,
+    ) -> DataSetResult<()> {
+        self.drain_rx();
+        let target_undo_index = (self.current_undo_index + index + 1).min(self.undo_chain.len());
+        while self.current_undo_index < target_undo_index {
+            self.redo(edit_contexts)?;
+        }
+
+        Ok(())
+    }
 }
 
 // Transaction that holds exclusive access for the data and will directly commit changes. It can
@@ -261,6 +319,10 @@ impl UndoContext {
                 self.completed_undo_context_tx
                     .send(CompletedUndoContextMessage {
                         edit_context_key: self.edit_context_key,
+                        info: UndoEntryInfo {
+                            label: self.context_name.unwrap_or_default(),
+                            affected_asset_count: diff_set.modified_assets.len(),
+                        },
                         diff_set,
                     })
                     .unwrap();
@@ -1,13 +1,28 @@
 use crate::edit_context::EditContext;
 use crate::{
-    AssetLocation, AssetPath, AssetSourceId, EditContextKey, NullOverride, OverrideBehavior,
-    SchemaDefType, SchemaLinker, SchemaLinkerResult, SchemaSet, UndoStack, Value,
+    AssetLocation, AssetPath, AssetSourceId, DataContainerRefMut, DataSet, EditContextKey,
+    EndContextBehavior, NullOverride, OverrideBehavior, PropertyContribution, PropertyPath, Schema,
+    SchemaDefDynamicArray, SchemaDefType, SchemaLinker, SchemaLinkerResult, SchemaMap, SchemaSet,
+    SingleObject, UndoEntryInfo, UndoStack, Value,
 };
+use hydrate_base::hashing::HashMap;
 use hydrate_base::AssetId;
-use hydrate_data::{AssetName, SchemaSetBuilder};
-use hydrate_pipeline::HydrateProjectConfiguration;
+use hydrate_data::{
+    AssetName, CanonicalPathReference, ConsistencyIssue, DataSetError, ImportInfo, ImportableName,
+    ImporterId, OrderedSet, PathReference, SchemaSetBuilder, PROJECT_ROOT_NAMESPACE,
+};
+use hydrate_pipeline::{
+    hash_asset_build_inputs, AssetEngine, AssetEngineState, AssetPlugin, AssetPluginSetupContext,
+    Builder, BuilderContext, HydrateProjectConfiguration, JobInput, JobOutput, JobProcessor,
+    PipelineResult, RunContext,
+};
 use hydrate_schema::Schema::Nullable;
+use serde::{Deserialize, Serialize};
+use slotmap::DenseSlotMap;
+use std::path::Path;
 use std::sync::Arc;
+use std::sync::Mutex;
+use type_uuid::TypeUuid;
 use uuid::Uuid;
 
 fn asset_location() -> AssetLocation {
@@ -24,19 +39,122 @@ fn create_vec3_schema(linker: &mut SchemaLinker) -> SchemaLinkerResult<()> {
     })
 }
 
-fn default_project_config() -> HydrateProjectConfiguration {
-    HydrateProjectConfiguration {
-        schema_def_paths: vec![],
-        import_data_path: Default::default(),
-        build_data_path: Default::default(),
-        job_data_path: Default::default(),
-        id_based_asset_sources: vec![],
-        path_based_asset_sources: vec![],
-        source_file_locations: vec![],
-        schema_codegen_jobs: vec![],
+// Job that produces one artifact per Vec3 asset from its x value. Runs are recorded per asset so
+// that tests running in parallel can each count the runs for their own assets.
+static VEC3_JOB_RUNS: Mutex<Vec<AssetId>> = Mutex::new(Vec::new());
+
+fn vec3_job_run_count(asset_id: AssetId) -> usize {
+    VEC3_JOB_RUNS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|x| **x == asset_id)
+        .count()
+}
+
+#[derive(Hash, Serialize, Deserialize)]
+struct Vec3JobInput {
+    asset_id: AssetId,
+}
+impl JobInput for Vec3JobInput {}
+
+#[derive(Serialize, Deserialize)]
+struct Vec3JobOutput {}
+impl JobOutput for Vec3JobOutput {}
+
+#[derive(TypeUuid, Serialize)]
+#[uuid = "5b0e6f3a-2c47-4d19-8a6e-f1c9d3b7a204"]
+struct Vec3Artifact {
+    x: f32,
+}
+
+#[derive(TypeUuid, Default)]
+#[uuid = "c2a8d4e1-7f36-4b5a-9e02-6d1f8b3c5a97"]
+struct Vec3JobProcessor;
+
+impl JobProcessor for Vec3JobProcessor {
+    type InputT = Vec3JobInput;
+    type OutputT = Vec3JobOutput;
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn run<'a>(
+        &'a self,
+        context: &'a RunContext<'a, Self::InputT>,
+    ) -> PipelineResult<Vec3JobOutput> {
+        VEC3_JOB_RUNS.lock().unwrap().push(context.input.asset_id);
+        let x = context
+            .data_set
+            .resolve_property(context.schema_set, context.input.asset_id, "x")?
+            .as_f32()?;
+        context.produce_default_artifact(context.input.asset_id, Vec3Artifact { x })?;
+        Ok(Vec3JobOutput {})
     }
 }
 
+#[derive(Default)]
+struct Vec3Builder;
+
+impl Builder for Vec3Builder {
+    fn asset_type(&self) -> &'static str {
+        "Vec3"
+    }
+
+    fn start_jobs(
+        &self,
+        context: BuilderContext,
+    ) -> PipelineResult<()> {
+        context.enqueue_job::<Vec3JobProcessor>(
+            context.data_set,
+            context.schema_set,
+            context.job_api,
+            Vec3JobInput {
+                asset_id: context.asset_id,
+            },
+        )?;
+        Ok(())
+    }
+}
+
+struct Vec3Plugin;
+
+impl AssetPlugin for Vec3Plugin {
+    fn setup(context: AssetPluginSetupContext) {
+        context.builder_registry.register_handler::<Vec3Builder>();
+        context
+            .job_processor_registry
+            .register_job_processor::<Vec3JobProcessor>();
+    }
+}
+
+// Queues a build of every asset and updates the engine until the build finishes
+fn run_build_to_completion(
+    asset_engine: &mut AssetEngine,
+    asset_path_cache: &crate::AssetPathCache,
+    editor_model: &mut crate::EditorModel,
+) {
+    asset_engine.queue_build_all();
+    loop {
+        let state = asset_engine
+            .update(&mut crate::EditorModelWithCache {
+                asset_path_cache,
+                editor_model: &mut *editor_model,
+            })
+            .unwrap();
+        if let AssetEngineState::BuildCompleted(_) = state {
+            break;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+}
+
+fn default_project_config() -> HydrateProjectConfiguration {
+    HydrateProjectConfiguration::for_test(Path::new(""))
+}
+
 // We want the same fingerprint out of a record as a Schema::Record(record)
 #[test]
 fn set_struct_values() {
@@ -635,3 +753,6086 @@ fn dynamic_array_override_behavior() {
 
 
  */
+
+#[test]
+fn dynamic_array_entry_shared_with_prototype_is_resolved_once() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("OuterStruct", Uuid::new_v4(), |builder| {
+            builder.add_dynamic_array(
+                "array",
+                Uuid::new_v4(),
+                SchemaDefType::NamedType("Vec3".to_string()),
+            );
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let outer_struct_type = schema_set
+        .find_named_type("OuterStruct")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut data_set = DataSet::default();
+    let prototype = data_set.new_asset(
+        AssetName::new("prototype"),
+        asset_location(),
+        &outer_struct_type,
+    );
+    let shared_entry = data_set
+        .add_dynamic_array_entry(&schema_set, prototype, "array")
+        .unwrap();
+    assert!(data_set.check_consistency().is_empty());
+
+    // Restore a child whose entries collide with the prototype's, as could happen with corrupt data
+    let child_entry = Uuid::new_v4();
+    let mut entries = OrderedSet::default();
+    entries.try_insert_at_end(shared_entry);
+    entries.try_insert_at_end(child_entry);
+    let mut dynamic_collection_entries = HashMap::default();
+    dynamic_collection_entries.insert("array".to_string(), entries);
+    let child = AssetId::from_uuid(Uuid::new_v4());
+    data_set
+        .restore_asset(
+            child,
+            AssetName::new("child"),
+            asset_location(),
+            None,
+            Default::default(),
+            &schema_set,
+            Some(prototype),
+            outer_struct_type.fingerprint(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            dynamic_collection_entries,
+        )
+        .unwrap();
+
+    assert_eq!(
+        data_set
+            .resolve_dynamic_array_entries(&schema_set, child, "array")
+            .unwrap(),
+        vec![shared_entry, child_entry].into_boxed_slice()
+    );
+    assert_eq!(
+        data_set
+            .asset_view(&schema_set, child)
+            .unwrap()
+            .resolve_dynamic_array_entries("array")
+            .unwrap(),
+        vec![shared_entry, child_entry].into_boxed_slice()
+    );
+    assert_eq!(
+        data_set.check_consistency(),
+        vec![ConsistencyIssue::DuplicateDynamicCollectionEntry {
+            asset_id: child,
+            prototype_id: prototype,
+            path: "array".to_string(),
+            entry_id: shared_entry,
+        }]
+    );
+
+    // A child that replaces the prototype's entries does not use them, so the collision is harmless
+    data_set
+        .set_override_behavior(&schema_set, child, "array", OverrideBehavior::Replace)
+        .unwrap();
+    assert!(data_set.check_consistency().is_empty());
+}
+
+#[test]
+fn reorder_dynamic_array_entries() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("OuterStruct", Uuid::new_v4(), |builder| {
+            builder.add_dynamic_array(
+                "array",
+                Uuid::new_v4(),
+                SchemaDefType::NamedType("Vec3".to_string()),
+            );
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let outer_struct_type = schema_set
+        .find_named_type("OuterStruct")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut edit_contexts = DenseSlotMap::<EditContextKey, EditContext>::default();
+    let key = edit_contexts.insert_with_key(|key| {
+        EditContext::new(&project_config, key, schema_set.clone(), &undo_stack)
+    });
+    let asset_location = asset_location();
+
+    let mut asset_id = AssetId::null();
+    let mut entries = vec![];
+    edit_contexts[key].with_undo_context("create asset", |edit_context| {
+        asset_id =
+            edit_context.new_asset(&AssetName::new("test"), &asset_location, &outer_struct_type);
+        for x in [1.0, 2.0, 3.0] {
+            let entry = edit_context
+                .add_dynamic_array_entry(asset_id, "array")
+                .unwrap();
+            edit_context
+                .set_property_override(asset_id, format!("array.{}.x", entry), Some(Value::F32(x)))
+                .unwrap();
+            entries.push(entry);
+        }
+        EndContextBehavior::Finish
+    });
+    let (a, b, c) = (entries[0], entries[1], entries[2]);
+
+    let resolved = |edit_contexts: &DenseSlotMap<EditContextKey, EditContext>| {
+        edit_contexts[key]
+            .resolve_dynamic_array_entries(asset_id, "array")
+            .unwrap()
+            .to_vec()
+    };
+    let x = |edit_contexts: &DenseSlotMap<EditContextKey, EditContext>, entry: Uuid| {
+        edit_contexts[key]
+            .resolve_property(asset_id, format!("array.{}.x", entry))
+            .unwrap()
+            .as_f32()
+            .unwrap()
+    };
+
+    edit_contexts[key]
+        .reorder_dynamic_array_entry(asset_id, "array", c, 0)
+        .unwrap();
+    assert_eq!(resolved(&edit_contexts), vec![c, a, b]);
+    // The entries keep their data
+    assert_eq!(x(&edit_contexts, a), 1.0);
+    assert_eq!(x(&edit_contexts, b), 2.0);
+    assert_eq!(x(&edit_contexts, c), 3.0);
+
+    edit_contexts[key]
+        .move_dynamic_array_entry_after(asset_id, "array", c, b)
+        .unwrap();
+    assert_eq!(resolved(&edit_contexts), vec![a, b, c]);
+    edit_contexts[key]
+        .move_dynamic_array_entry_before(asset_id, "array", c, b)
+        .unwrap();
+    assert_eq!(resolved(&edit_contexts), vec![a, c, b]);
+
+    assert!(matches!(
+        edit_contexts[key]
+            .reorder_dynamic_array_entry(asset_id, "array", a, 3)
+            .unwrap_err()
+            .error,
+        DataSetError::EntryIndexOutOfRange
+    ));
+    assert!(matches!(
+        edit_contexts[key]
+            .reorder_dynamic_array_entry(asset_id, "array", Uuid::new_v4(), 0)
+            .unwrap_err()
+            .error,
+        DataSetError::EntryKeyNotFound
+    ));
+
+    // Each reorder is a single undo step
+    assert_eq!(undo_stack.undo_history().len(), 4);
+    undo_stack.undo(&mut edit_contexts).unwrap();
+    assert_eq!(resolved(&edit_contexts), vec![a, b, c]);
+    undo_stack.undo(&mut edit_contexts).unwrap();
+    assert_eq!(resolved(&edit_contexts), vec![c, a, b]);
+    assert_eq!(x(&edit_contexts, c), 3.0);
+
+    // In append mode, the entries inherited from the prototype stay first and can't be moved
+    let db = &mut edit_contexts[key];
+    let child = db
+        .new_asset_from_prototype(&AssetName::new("child"), &asset_location, asset_id)
+        .unwrap();
+    let d = db.add_dynamic_array_entry(child, "array").unwrap();
+    let e = db.add_dynamic_array_entry(child, "array").unwrap();
+    assert!(matches!(
+        db.reorder_dynamic_array_entry(child, "array", e, 2)
+            .unwrap_err()
+            .error,
+        DataSetError::EntryIndexOutOfRange
+    ));
+    assert!(matches!(
+        db.reorder_dynamic_array_entry(child, "array", a, 4)
+            .unwrap_err()
+            .error,
+        DataSetError::EntryKeyNotFound
+    ));
+    db.reorder_dynamic_array_entry(child, "array", e, 3)
+        .unwrap();
+    assert_eq!(
+        db.resolve_dynamic_array_entries(child, "array")
+            .unwrap()
+            .to_vec(),
+        vec![c, a, b, e, d]
+    );
+
+    // In replace mode, only the child's own entries are resolved
+    db.set_override_behavior(child, "array", OverrideBehavior::Replace)
+        .unwrap();
+    db.move_dynamic_array_entry_after(child, "array", e, d)
+        .unwrap();
+    assert_eq!(
+        db.resolve_dynamic_array_entries(child, "array")
+            .unwrap()
+            .to_vec(),
+        vec![d, e]
+    );
+}
+
+#[test]
+fn raw_asset_data_excludes_inherited_and_default_values() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("OuterStruct", Uuid::new_v4(), |builder| {
+            builder.add_named_type("position", Uuid::new_v4(), "Vec3");
+            builder.add_nullable(
+                "nullable",
+                Uuid::new_v4(),
+                SchemaDefType::NamedType("Vec3".to_string()),
+            );
+            builder.add_dynamic_array(
+                "array",
+                Uuid::new_v4(),
+                SchemaDefType::NamedType("Vec3".to_string()),
+            );
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let outer_struct_type = schema_set
+        .find_named_type("OuterStruct")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut data_set = DataSet::default();
+    let prototype = data_set.new_asset(
+        AssetName::new("prototype"),
+        asset_location(),
+        &outer_struct_type,
+    );
+    data_set
+        .set_property_override(&schema_set, prototype, "position.x", Some(Value::F32(1.0)))
+        .unwrap();
+    data_set
+        .set_null_override(&schema_set, prototype, "nullable", NullOverride::SetNonNull)
+        .unwrap();
+    let prototype_entry = data_set
+        .add_dynamic_array_entry(&schema_set, prototype, "array")
+        .unwrap();
+
+    let child = data_set
+        .new_asset_from_prototype(AssetName::new("child"), asset_location(), prototype)
+        .unwrap();
+    data_set
+        .set_property_override(&schema_set, child, "position.y", Some(Value::F32(2.0)))
+        .unwrap();
+    let child_entry = data_set
+        .add_dynamic_array_entry(&schema_set, child, "array")
+        .unwrap();
+
+    // The child resolves values from its prototype and the schema...
+    assert_eq!(
+        data_set
+            .resolve_property(&schema_set, child, "position.x")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        1.0
+    );
+    assert_eq!(
+        data_set
+            .resolve_dynamic_array_entries(&schema_set, child, "array")
+            .unwrap()
+            .to_vec(),
+        vec![prototype_entry, child_entry]
+    );
+
+    // ...but only what was set on the child is stored on it
+    let raw_properties = data_set.asset_raw_properties(child).unwrap();
+    assert_eq!(raw_properties.len(), 1);
+    assert_eq!(raw_properties["position.y"].as_f32().unwrap(), 2.0);
+    assert!(data_set
+        .asset_raw_property_null_overrides(child)
+        .unwrap()
+        .is_empty());
+    assert!(data_set
+        .asset_raw_properties_in_replace_mode(child)
+        .unwrap()
+        .is_empty());
+    let raw_entries = data_set
+        .asset_raw_dynamic_collection_entries(child)
+        .unwrap();
+    assert_eq!(raw_entries.len(), 1);
+    assert_eq!(
+        raw_entries["array"].iter().copied().collect::<Vec<_>>(),
+        vec![child_entry]
+    );
+
+    data_set
+        .set_override_behavior(&schema_set, child, "array", OverrideBehavior::Replace)
+        .unwrap();
+    assert!(data_set
+        .asset_raw_properties_in_replace_mode(child)
+        .unwrap()
+        .contains("array"));
+    assert_eq!(
+        data_set
+            .asset_raw_property_null_overrides(prototype)
+            .unwrap()["nullable"],
+        NullOverride::SetNonNull
+    );
+
+    assert!(data_set
+        .asset_raw_properties(AssetId::from_uuid(Uuid::new_v4()))
+        .is_none());
+}
+
+#[test]
+fn snapshot_unaffected_by_later_edits() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut db = EditContext::new(
+        &project_config,
+        EditContextKey::default(),
+        schema_set.clone(),
+        &undo_stack,
+    );
+    let asset_location = asset_location();
+
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let obj1 = db.new_asset(&AssetName::new("obj1"), &asset_location, &vec3_type);
+    let obj2 = db.new_asset(&AssetName::new("obj2"), &asset_location, &vec3_type);
+    db.set_property_override(obj1, "x", Some(Value::F32(10.0)))
+        .unwrap();
+
+    let snapshot = db.data_set().snapshot();
+
+    // Edit an asset, delete an asset, and create an asset after the snapshot was taken
+    db.set_property_override(obj1, "x", Some(Value::F32(20.0)))
+        .unwrap();
+    db.delete_asset(obj2).unwrap();
+    let obj3 = db.new_asset(&AssetName::new("obj3"), &asset_location, &vec3_type);
+
+    assert_eq!(
+        snapshot
+            .resolve_property(&schema_set, obj1, "x")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        10.0
+    );
+    assert!(snapshot.assets().contains_key(&obj2));
+    assert!(!snapshot.assets().contains_key(&obj3));
+
+    assert_eq!(
+        db.resolve_property(obj1, "x").unwrap().as_f32().unwrap(),
+        20.0
+    );
+    assert!(!db.has_asset(obj2));
+}
+
+#[test]
+fn data_set_clones_share_assets_until_modified() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut data_set = DataSet::default();
+    let asset_ids: Vec<_> = (0..3)
+        .map(|i| {
+            let asset_id = data_set.new_asset(
+                AssetName::new(format!("obj{}", i)),
+                asset_location(),
+                &vec3_type,
+            );
+            data_set
+                .set_property_override(&schema_set, asset_id, "x", Some(Value::F32(i as f32)))
+                .unwrap();
+            asset_id
+        })
+        .collect();
+    let (obj0, obj1, obj2) = (asset_ids[0], asset_ids[1], asset_ids[2]);
+
+    let is_shared = |a: &DataSet, b: &DataSet, asset_id: AssetId| {
+        Arc::ptr_eq(
+            a.assets().get(&asset_id).unwrap(),
+            b.assets().get(&asset_id).unwrap(),
+        )
+    };
+
+    // Cloning and reading don't copy any assets
+    let snapshot = data_set.snapshot();
+    let mut data_set_clone = data_set.clone();
+    for &asset_id in &asset_ids {
+        data_set
+            .resolve_property(&schema_set, asset_id, "x")
+            .unwrap();
+        data_set_clone
+            .resolve_property(&schema_set, asset_id, "x")
+            .unwrap();
+        assert!(is_shared(&data_set, &snapshot, asset_id));
+        assert!(is_shared(&data_set, &data_set_clone, asset_id));
+    }
+
+    // Modifying an asset copies only that asset, and only on the first modification
+    data_set
+        .set_property_override(&schema_set, obj0, "x", Some(Value::F32(10.0)))
+        .unwrap();
+    let copied_obj0 = Arc::as_ptr(data_set.assets().get(&obj0).unwrap());
+    data_set
+        .set_property_override(&schema_set, obj0, "y", Some(Value::F32(10.0)))
+        .unwrap();
+    assert_eq!(
+        Arc::as_ptr(data_set.assets().get(&obj0).unwrap()),
+        copied_obj0
+    );
+    assert!(!is_shared(&data_set, &snapshot, obj0));
+    assert!(is_shared(&snapshot, &data_set_clone, obj0));
+    assert!(is_shared(&data_set, &snapshot, obj1));
+    assert!(is_shared(&data_set, &snapshot, obj2));
+
+    // Renaming counts as a modification too
+    data_set
+        .set_asset_name(obj1, AssetName::new("renamed"))
+        .unwrap();
+    assert!(!is_shared(&data_set, &snapshot, obj1));
+    assert!(is_shared(&data_set, &snapshot, obj2));
+
+    // Modifying a clone leaves the data set it was cloned from alone
+    data_set_clone
+        .set_property_override(&schema_set, obj2, "x", Some(Value::F32(20.0)))
+        .unwrap();
+    assert!(!is_shared(&data_set, &data_set_clone, obj2));
+    assert!(is_shared(&data_set, &snapshot, obj2));
+
+    let resolve_x = |data_set: &DataSet, asset_id: AssetId| {
+        data_set
+            .resolve_property(&schema_set, asset_id, "x")
+            .unwrap()
+            .as_f32()
+            .unwrap()
+    };
+    assert_eq!(resolve_x(&data_set, obj0), 10.0);
+    assert_eq!(resolve_x(&snapshot, obj0), 0.0);
+    assert_eq!(resolve_x(&data_set_clone, obj0), 0.0);
+    assert_eq!(resolve_x(&data_set, obj2), 2.0);
+    assert_eq!(resolve_x(&data_set_clone, obj2), 20.0);
+    assert_eq!(
+        snapshot.asset_name(obj1).unwrap().as_string().unwrap(),
+        "obj1"
+    );
+}
+
+fn create_reference_schema(linker: &mut SchemaLinker) -> SchemaLinkerResult<()> {
+    linker.register_record_type("HasReference", Uuid::new_v4(), |builder| {
+        builder.add_reference("reference", Uuid::new_v4(), "Vec3");
+    })
+}
+
+#[test]
+fn undo_delete_restores_referenced_asset() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    create_reference_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let mut undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut edit_contexts = DenseSlotMap::<EditContextKey, EditContext>::default();
+    let key = edit_contexts.insert_with_key(|key| {
+        EditContext::new(&project_config, key, schema_set.clone(), &undo_stack)
+    });
+    let asset_location = asset_location();
+
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+    let has_reference_type = schema_set
+        .find_named_type("HasReference")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let db = &mut edit_contexts[key];
+    let referenced = db.new_asset(&AssetName::new("referenced"), &asset_location, &vec3_type);
+    db.set_property_override(referenced, "x", Some(Value::F32(10.0)))
+        .unwrap();
+    let referencing = db.new_asset(
+        &AssetName::new("referencing"),
+        &asset_location,
+        &has_reference_type,
+    );
+    db.set_property_override(referencing, "reference", Some(Value::AssetRef(referenced)))
+        .unwrap();
+
+    db.with_undo_context("delete asset", |edit_context| {
+        edit_context.delete_asset(referenced).unwrap();
+        EndContextBehavior::Finish
+    });
+
+    let db = &edit_contexts[key];
+    assert!(!db.has_asset(referenced));
+    let referenced_id = db
+        .resolve_property(referencing, "reference")
+        .unwrap()
+        .as_asset_ref()
+        .unwrap();
+    assert!(!db.has_asset(referenced_id));
+
+    undo_stack.undo(&mut edit_contexts).unwrap();
+
+    // The asset comes back with the same ID and properties, so the reference is valid again
+    let db = &edit_contexts[key];
+    let referenced_id = db
+        .resolve_property(referencing, "reference")
+        .unwrap()
+        .as_asset_ref()
+        .unwrap();
+    assert_eq!(referenced_id, referenced);
+    assert!(db.has_asset(referenced_id));
+    assert_eq!(
+        db.resolve_property(referenced_id, "x")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        10.0
+    );
+    assert_eq!(
+        db.asset_name(referenced_id).unwrap().as_string().unwrap(),
+        "referenced"
+    );
+
+    undo_stack.redo(&mut edit_contexts).unwrap();
+    assert!(!edit_contexts[key].has_asset(referenced));
+}
+
+#[test]
+fn preview_edits_coalesce_into_one_undo_entry() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let mut undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut edit_contexts = DenseSlotMap::<EditContextKey, EditContext>::default();
+    let key = edit_contexts.insert_with_key(|key| {
+        EditContext::new(&project_config, key, schema_set.clone(), &undo_stack)
+    });
+
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let db = &mut edit_contexts[key];
+    let asset_id = db.new_asset(&AssetName::new("asset"), &asset_location(), &vec3_type);
+    db.set_property_override(asset_id, "x", Some(Value::F32(1.0)))
+        .unwrap();
+    db.commit_pending_undo_context();
+    let undo_history_len = undo_stack.undo_history().len();
+
+    // Simulate dragging a slider, every intermediate value is applied immediately
+    let db = &mut edit_contexts[key];
+    for i in 0..100 {
+        db.with_preview_edit("drag", |edit_context| {
+            edit_context
+                .set_property_override(asset_id, "x", Some(Value::F32(i as f32)))
+                .unwrap();
+        });
+        assert_eq!(
+            db.resolve_property(asset_id, "x")
+                .unwrap()
+                .as_f32()
+                .unwrap(),
+            i as f32
+        );
+    }
+
+    assert!(db.has_preview_edit());
+    assert_eq!(undo_stack.undo_history().len(), undo_history_len);
+
+    let db = &mut edit_contexts[key];
+    db.commit_preview();
+    assert!(!db.has_preview_edit());
+    assert_eq!(undo_stack.undo_history().len(), undo_history_len + 1);
+
+    // A single undo goes back to the value from before the drag
+    undo_stack.undo(&mut edit_contexts).unwrap();
+    assert_eq!(
+        edit_contexts[key]
+            .resolve_property(asset_id, "x")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        1.0
+    );
+
+    // Cancelling a preview reverts it without adding an undo entry
+    let db = &mut edit_contexts[key];
+    db.with_preview_edit("drag", |edit_context| {
+        edit_context
+            .set_property_override(asset_id, "x", Some(Value::F32(50.0)))
+            .unwrap();
+    });
+    db.cancel_preview().unwrap();
+    assert_eq!(
+        db.resolve_property(asset_id, "x")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        1.0
+    );
+    assert_eq!(undo_stack.undo_history().len(), undo_history_len);
+}
+
+#[test]
+fn reparenting_a_folder_beneath_itself_is_rejected() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    // a contains b, b contains c
+    let mut data_set = DataSet::default();
+    let a = data_set.new_asset(AssetName::new("a"), AssetLocation::null(), &vec3_type);
+    let b = data_set.new_asset(AssetName::new("b"), AssetLocation::new(a), &vec3_type);
+    let c = data_set.new_asset(AssetName::new("c"), AssetLocation::new(b), &vec3_type);
+
+    for new_parent in [a, b, c] {
+        assert!(matches!(
+            data_set
+                .set_asset_location(a, AssetLocation::new(new_parent))
+                .unwrap_err()
+                .error,
+            DataSetError::NewLocationIsChildOfCurrentAsset
+        ));
+    }
+    assert_eq!(data_set.asset_location(a), Some(AssetLocation::null()));
+    assert_eq!(
+        data_set.asset_location_chain(c).unwrap(),
+        vec![AssetLocation::new(b), AssetLocation::new(a)]
+    );
+
+    // Moving c up beside b is fine
+    data_set
+        .set_asset_location(c, AssetLocation::new(a))
+        .unwrap();
+    assert_eq!(
+        data_set.asset_location_chain(c).unwrap(),
+        vec![AssetLocation::new(a)]
+    );
+
+    // And b can now be moved beneath c
+    data_set
+        .set_asset_location(b, AssetLocation::new(c))
+        .unwrap();
+    assert_eq!(
+        data_set.asset_location_chain(b).unwrap(),
+        vec![AssetLocation::new(c), AssetLocation::new(a)]
+    );
+}
+
+#[test]
+fn undo_create_duplicate_and_reparent() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let mut undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut edit_contexts = DenseSlotMap::<EditContextKey, EditContext>::default();
+    let key = edit_contexts.insert_with_key(|key| {
+        EditContext::new(&project_config, key, schema_set.clone(), &undo_stack)
+    });
+    let asset_location = asset_location();
+
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut obj = None;
+    edit_contexts[key].with_undo_context("new asset", |edit_context| {
+        obj = Some(edit_context.new_asset(&AssetName::new("obj"), &asset_location, &vec3_type));
+        EndContextBehavior::Finish
+    });
+    let obj = obj.unwrap();
+
+    let mut parent = None;
+    edit_contexts[key].with_undo_context("new asset", |edit_context| {
+        parent =
+            Some(edit_context.new_asset(&AssetName::new("parent"), &asset_location, &vec3_type));
+        EndContextBehavior::Finish
+    });
+    let parent = parent.unwrap();
+
+    edit_contexts[key].with_undo_context("move asset", |edit_context| {
+        edit_context
+            .set_asset_location(obj, AssetLocation::new(parent))
+            .unwrap();
+        EndContextBehavior::Finish
+    });
+
+    let mut duplicate = None;
+    edit_contexts[key].with_undo_context("duplicate asset", |edit_context| {
+        duplicate = Some(edit_context.duplicate_asset(obj).unwrap());
+        EndContextBehavior::Finish
+    });
+    let duplicate = duplicate.unwrap();
+
+    // Deleting the parent deletes the assets beneath it
+    edit_contexts[key].with_undo_context("delete asset", |edit_context| {
+        edit_context.delete_asset_and_children(parent).unwrap();
+        EndContextBehavior::Finish
+    });
+    assert!(!edit_contexts[key].has_asset(parent));
+    assert!(!edit_contexts[key].has_asset(obj));
+    assert!(!edit_contexts[key].has_asset(duplicate));
+
+    // Undo delete
+    undo_stack.undo(&mut edit_contexts).unwrap();
+    assert!(edit_contexts[key].has_asset(parent));
+    assert!(edit_contexts[key].has_asset(obj));
+    assert!(edit_contexts[key].has_asset(duplicate));
+
+    // Undo duplicate
+    undo_stack.undo(&mut edit_contexts).unwrap();
+    assert!(!edit_contexts[key].has_asset(duplicate));
+    assert!(edit_contexts[key].has_asset(obj));
+
+    // Undo reparent
+    assert_eq!(
+        edit_contexts[key].asset_location(obj),
+        Some(AssetLocation::new(parent))
+    );
+    undo_stack.undo(&mut edit_contexts).unwrap();
+    assert_eq!(edit_contexts[key].asset_location(obj), Some(asset_location));
+
+    // Undo both creates
+    undo_stack.undo(&mut edit_contexts).unwrap();
+    assert!(!edit_contexts[key].has_asset(parent));
+    undo_stack.undo(&mut edit_contexts).unwrap();
+    assert!(!edit_contexts[key].has_asset(obj));
+
+    // Redo the create
+    undo_stack.redo(&mut edit_contexts).unwrap();
+    assert!(edit_contexts[key].has_asset(obj));
+}
+
+#[test]
+fn undo_history_and_multi_step_undo() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let mut undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut edit_contexts = DenseSlotMap::<EditContextKey, EditContext>::default();
+    let key = edit_contexts.insert_with_key(|key| {
+        EditContext::new(&project_config, key, schema_set.clone(), &undo_stack)
+    });
+    let asset_location = asset_location();
+
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut assets = vec![];
+    edit_contexts[key].with_undo_context("create assets", |edit_context| {
+        assets.push(edit_context.new_asset(&AssetName::new("a"), &asset_location, &vec3_type));
+        assets.push(edit_context.new_asset(&AssetName::new("b"), &asset_location, &vec3_type));
+        EndContextBehavior::Finish
+    });
+
+    edit_contexts[key].with_undo_context("set x", |edit_context| {
+        edit_context
+            .set_property_override(assets[0], "x", Some(Value::F32(1.0)))
+            .unwrap();
+        EndContextBehavior::Finish
+    });
+
+    // A resumable context is not part of the history until it is committed
+    edit_contexts[key].with_undo_context("drag y", |edit_context| {
+        edit_context
+            .set_property_override(assets[1], "y", Some(Value::F32(2.0)))
+            .unwrap();
+        EndContextBehavior::AllowResume
+    });
+    edit_contexts[key].with_undo_context("drag y", |edit_context| {
+        edit_context
+            .set_property_override(assets[1], "y", Some(Value::F32(3.0)))
+            .unwrap();
+        EndContextBehavior::AllowResume
+    });
+    assert_eq!(undo_stack.undo_history().len(), 2);
+
+    edit_contexts[key].commit_pending_undo_context();
+    assert_eq!(
+        undo_stack.undo_history(),
+        &[
+            UndoEntryInfo {
+                label: "create assets",
+                affected_asset_count: 2
+            },
+            UndoEntryInfo {
+                label: "set x",
+                affected_asset_count: 1
+            },
+            UndoEntryInfo {
+                label: "drag y",
+                affected_asset_count: 1
+            },
+        ]
+    );
+    assert!(undo_stack.redo_history().is_empty());
+
+    // Undo the drag and the set in one call
+    undo_stack.undo_n(&mut edit_contexts, 2).unwrap();
+    assert_eq!(undo_stack.undo_history().len(), 1);
+    assert_eq!(undo_stack.redo_history().len(), 2);
+    assert_eq!(undo_stack.redo_history()[0].label, "set x");
+    assert!(edit_contexts[key]
+        .get_property_override(assets[0], "x")
+        .unwrap()
+        .is_none());
+    assert!(edit_contexts[key]
+        .get_property_override(assets[1], "y")
+        .unwrap()
+        .is_none());
+
+    // Undoing more steps than exist stops at the start of the chain
+    undo_stack.undo_n(&mut edit_contexts, 10).unwrap();
+    assert!(undo_stack.undo_history().is_empty());
+    assert!(!edit_contexts[key].has_asset(assets[0]));
+
+    // Redo through "set x"
+    undo_stack.redo_to(&mut edit_contexts, 1).unwrap();
+    assert_eq!(undo_stack.undo_history().len(), 2);
+    assert_eq!(undo_stack.redo_history()[0].label, "drag y");
+    assert_eq!(
+        edit_contexts[key]
+            .get_property_override(assets[0], "x")
+            .unwrap()
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        1.0
+    );
+    assert!(edit_contexts[key]
+        .get_property_override(assets[1], "y")
+        .unwrap()
+        .is_none());
+
+    // A new edit discards the steps that could have been redone
+    edit_contexts[key].with_undo_context("set z", |edit_context| {
+        edit_context
+            .set_property_override(assets[0], "z", Some(Value::F32(4.0)))
+            .unwrap();
+        EndContextBehavior::Finish
+    });
+    assert_eq!(undo_stack.undo_history().len(), 3);
+    assert_eq!(undo_stack.undo_history()[2].label, "set z");
+    assert!(undo_stack.redo_history().is_empty());
+}
+
+#[test]
+fn build_settings_change_invalidates_build_hash() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut db = EditContext::new(
+        &project_config,
+        EditContextKey::default(),
+        schema_set.clone(),
+        &undo_stack,
+    );
+    let asset_location = asset_location();
+
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let asset = db.new_asset(&AssetName::new("asset"), &asset_location, &vec3_type);
+    let settings = db.new_asset(&AssetName::new("settings"), &asset_location, &vec3_type);
+    let unrelated = db.new_asset(&AssetName::new("unrelated"), &asset_location, &vec3_type);
+
+    let hash_without_settings = hash_asset_build_inputs(db.data_set(), &schema_set, asset).unwrap();
+
+    // Assigning build settings changes the hash
+    db.set_build_settings(asset, Some(settings)).unwrap();
+    let hash_with_settings = hash_asset_build_inputs(db.data_set(), &schema_set, asset).unwrap();
+    assert_ne!(hash_without_settings, hash_with_settings);
+
+    // Editing the settings changes the hash
+    db.set_property_override(settings, "x", Some(Value::F32(10.0)))
+        .unwrap();
+    let hash_with_edited_settings =
+        hash_asset_build_inputs(db.data_set(), &schema_set, asset).unwrap();
+    assert_ne!(hash_with_settings, hash_with_edited_settings);
+
+    // Editing an unrelated asset does not
+    db.set_property_override(unrelated, "x", Some(Value::F32(10.0)))
+        .unwrap();
+    assert_eq!(
+        hash_with_edited_settings,
+        hash_asset_build_inputs(db.data_set(), &schema_set, asset).unwrap()
+    );
+}
+
+#[test]
+fn build_settings_change_triggers_rebuild() {
+    use crate::{AssetPathCache, EditorModel, EditorModelWithCache, PathNode, PathNodeRoot};
+    use hydrate_pipeline::{AssetPluginRegistryBuilders, FileSystemBuildCacheProvider};
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let root_path = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    let mut project_config = default_project_config();
+    project_config.import_data_path = root_path.join("import_data");
+    project_config.build_data_path = root_path.join("build_data");
+    project_config.job_data_path = root_path.join("job_data");
+
+    let mut editor_model = EditorModel::new(project_config.clone(), schema_set.clone());
+    let path_node_root_type = editor_model
+        .path_node_root_schema()
+        .as_record()
+        .unwrap()
+        .clone();
+    let edit_context = editor_model.root_edit_context_mut();
+    let root = edit_context.new_asset(
+        &AssetName::new("project"),
+        &AssetLocation::null(),
+        &path_node_root_type,
+    );
+    let asset_id = edit_context.new_asset(
+        &AssetName::new("vec3"),
+        &AssetLocation::new(root),
+        &vec3_type,
+    );
+    let settings = edit_context.new_asset(
+        &AssetName::new("settings"),
+        &AssetLocation::new(root),
+        &vec3_type,
+    );
+    edit_context
+        .set_build_settings(asset_id, Some(settings))
+        .unwrap();
+
+    let asset_path_cache = AssetPathCache::build(&editor_model).unwrap();
+    let mut asset_engine = AssetEngine::new(
+        &schema_set,
+        AssetPluginRegistryBuilders::new()
+            .register_plugin::<Vec3Plugin>()
+            .finish(&schema_set),
+        &EditorModelWithCache {
+            asset_path_cache: &asset_path_cache,
+            editor_model: &mut editor_model,
+        },
+        &project_config,
+    );
+    // Jobs are reused from the build cache while the hashes of the assets they read match
+    asset_engine.set_build_cache_provider(Some(Arc::new(FileSystemBuildCacheProvider::new(
+        &root_path.join("build_cache"),
+    ))));
+
+    run_build_to_completion(&mut asset_engine, &asset_path_cache, &mut editor_model);
+    assert_eq!(vec3_job_run_count(asset_id), 1);
+
+    // Assigning the same build settings again leaves the asset's hash unchanged, so building again
+    // skips the job
+    editor_model
+        .root_edit_context_mut()
+        .set_build_settings(asset_id, Some(settings))
+        .unwrap();
+    run_build_to_completion(&mut asset_engine, &asset_path_cache, &mut editor_model);
+    assert_eq!(vec3_job_run_count(asset_id), 1);
+
+    // Editing the build settings changes the asset's hash even though the job doesn't read them,
+    // so the job runs again
+    editor_model
+        .root_edit_context_mut()
+        .set_property_override(settings, "x", Some(Value::F32(10.0)))
+        .unwrap();
+    run_build_to_completion(&mut asset_engine, &asset_path_cache, &mut editor_model);
+    assert_eq!(vec3_job_run_count(asset_id), 2);
+
+    std::fs::remove_dir_all(&root_path).unwrap();
+}
+
+#[test]
+fn build_ignored_fields_do_not_affect_build_input_hash() {
+    let mut linker = SchemaLinker::default();
+    linker
+        .register_record_type("Annotation", Uuid::new_v4(), |builder| {
+            builder
+                .add_string("comment", Uuid::new_v4())
+                .set_build_ignore(true);
+            builder.add_u32("priority", Uuid::new_v4());
+        })
+        .unwrap();
+    linker
+        .register_record_type("AnnotatedAsset", Uuid::new_v4(), |builder| {
+            builder.add_f32("value", Uuid::new_v4());
+            builder
+                .add_string("note", Uuid::new_v4())
+                .set_build_ignore(true);
+            builder.add_named_type("annotation", Uuid::new_v4(), "Annotation");
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut db = EditContext::new(
+        &project_config,
+        EditContextKey::default(),
+        schema_set.clone(),
+        &undo_stack,
+    );
+
+    let annotated_type = schema_set
+        .find_named_type("AnnotatedAsset")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+    let asset = db.new_asset(&AssetName::new("asset"), &asset_location(), &annotated_type);
+    let initial_hash = hash_asset_build_inputs(db.data_set(), &schema_set, asset).unwrap();
+
+    // Editing build-ignored fields, including one in a nested record, does not require a rebuild
+    db.set_property_override(
+        asset,
+        "note",
+        Some(Value::String(Arc::new("remember to fix this".to_string()))),
+    )
+    .unwrap();
+    db.set_property_override(
+        asset,
+        "annotation.comment",
+        Some(Value::String(Arc::new("looks good".to_string()))),
+    )
+    .unwrap();
+    assert_eq!(
+        initial_hash,
+        hash_asset_build_inputs(db.data_set(), &schema_set, asset).unwrap()
+    );
+
+    // Editing any other field does
+    db.set_property_override(asset, "value", Some(Value::F32(2.0)))
+        .unwrap();
+    let edited_hash = hash_asset_build_inputs(db.data_set(), &schema_set, asset).unwrap();
+    assert_ne!(initial_hash, edited_hash);
+
+    db.set_property_override(asset, "annotation.priority", Some(Value::U32(3)))
+        .unwrap();
+    assert_ne!(
+        edited_hash,
+        hash_asset_build_inputs(db.data_set(), &schema_set, asset).unwrap()
+    );
+}
+
+#[test]
+fn readonly_fields_reject_edits_through_edit_context() {
+    let mut linker = SchemaLinker::default();
+    linker
+        .register_record_type("Bounds", Uuid::new_v4(), |builder| {
+            builder.add_f32("radius", Uuid::new_v4());
+        })
+        .unwrap();
+    linker
+        .register_record_type("MeshAsset", Uuid::new_v4(), |builder| {
+            builder.add_f32("scale", Uuid::new_v4());
+            builder
+                .add_u64("cached_hash", Uuid::new_v4())
+                .set_readonly(true);
+            builder
+                .add_named_type("bounds", Uuid::new_v4(), "Bounds")
+                .set_readonly(true);
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let mesh_type = schema_set
+        .find_named_type("MeshAsset")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut db = EditContext::new(
+        &project_config,
+        EditContextKey::default(),
+        schema_set.clone(),
+        &undo_stack,
+    );
+    let asset = db.new_asset(&AssetName::new("mesh"), &asset_location(), &mesh_type);
+
+    // Readonly fields, and fields nested within them, can't be edited through the edit context
+    for path in ["cached_hash", "bounds.radius"] {
+        let value = if path == "cached_hash" {
+            Value::U64(5)
+        } else {
+            Value::F32(5.0)
+        };
+        assert!(matches!(
+            db.set_property_override(asset, path, Some(value))
+                .unwrap_err()
+                .error,
+            DataSetError::FieldReadOnly
+        ));
+        assert!(!db.has_property_override(asset, path).unwrap());
+    }
+    assert!(matches!(
+        db.set_override_behavior(asset, "bounds", OverrideBehavior::Replace)
+            .unwrap_err()
+            .error,
+        DataSetError::FieldReadOnly
+    ));
+    db.set_property_override(asset, "scale", Some(Value::F32(2.0)))
+        .unwrap();
+
+    // Builders and importers write through the data set directly
+    let mut data_set = DataSet::default();
+    let built_asset = data_set.new_asset(AssetName::new("mesh"), asset_location(), &mesh_type);
+    data_set
+        .set_property_override(&schema_set, built_asset, "cached_hash", Some(Value::U64(5)))
+        .unwrap();
+    data_set
+        .set_property_override(
+            &schema_set,
+            built_asset,
+            "bounds.radius",
+            Some(Value::F32(5.0)),
+        )
+        .unwrap();
+    assert_eq!(
+        data_set
+            .resolve_property(&schema_set, built_asset, "cached_hash")
+            .unwrap()
+            .as_u64()
+            .unwrap(),
+        5
+    );
+}
+
+#[test]
+fn clear_all_overrides_keeps_readonly_fields() {
+    let mut linker = SchemaLinker::default();
+    linker
+        .register_record_type("Bounds", Uuid::new_v4(), |builder| {
+            builder.add_f32("radius", Uuid::new_v4());
+        })
+        .unwrap();
+    linker
+        .register_record_type("MeshAsset", Uuid::new_v4(), |builder| {
+            builder.add_f32("scale", Uuid::new_v4());
+            builder
+                .add_u64("cached_hash", Uuid::new_v4())
+                .set_readonly(true);
+            builder
+                .add_named_type("bounds", Uuid::new_v4(), "Bounds")
+                .set_readonly(true);
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let mesh_type = schema_set
+        .find_named_type("MeshAsset")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    // Readonly fields are written by builders and importers, which write to the data set directly
+    let mut data_set = DataSet::default();
+    let asset = data_set.new_asset(AssetName::new("mesh"), asset_location(), &mesh_type);
+    for (path, value) in [
+        ("scale", Value::F32(2.0)),
+        ("cached_hash", Value::U64(5)),
+        ("bounds.radius", Value::F32(3.0)),
+    ] {
+        data_set
+            .set_property_override(&schema_set, asset, path, Some(value))
+            .unwrap();
+    }
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut db = EditContext::new(
+        &project_config,
+        EditContextKey::default(),
+        schema_set.clone(),
+        &undo_stack,
+    );
+    db.restore_assets_from(data_set).unwrap();
+
+    let cleared_overrides = db.clear_all_overrides(asset).unwrap();
+    assert!(!db.has_property_override(asset, "scale").unwrap());
+    assert!(db.has_property_override(asset, "cached_hash").unwrap());
+    assert!(db.has_property_override(asset, "bounds.radius").unwrap());
+
+    // Only the cleared override is returned, so writing it back doesn't touch readonly fields
+    let mut restored = DataSet::default();
+    let restored_asset = restored.new_asset(AssetName::new("mesh"), asset_location(), &mesh_type);
+    restored
+        .write_properties_bundle(&schema_set, restored_asset, "", &cleared_overrides)
+        .unwrap();
+    assert_eq!(
+        restored
+            .get_property_override(restored_asset, "scale")
+            .unwrap()
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        2.0
+    );
+    assert!(!restored
+        .has_property_override(restored_asset, "cached_hash")
+        .unwrap());
+}
+
+#[test]
+fn update_property_override_in_place() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut db = EditContext::new(
+        &project_config,
+        EditContextKey::default(),
+        schema_set.clone(),
+        &undo_stack,
+    );
+    let asset_location = asset_location();
+
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let obj1 = db.new_asset(&AssetName::new("test"), &asset_location, &vec3_type);
+    let obj2 = db
+        .new_asset_from_prototype(&AssetName::new("test2"), &asset_location, obj1)
+        .unwrap();
+
+    fn increment(value: &mut Value) {
+        *value = Value::F32(value.as_f32().unwrap() + 1.0);
+    }
+
+    // Updating an inherited property starts from the inherited value and creates an override
+    db.set_property_override(obj1, "x", Some(Value::F32(10.0)))
+        .unwrap();
+    db.update_property_override(obj2, "x", increment).unwrap();
+    assert_eq!(
+        db.get_property_override(obj2, "x")
+            .unwrap()
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        11.0
+    );
+    assert_eq!(
+        db.resolve_property(obj1, "x").unwrap().as_f32().unwrap(),
+        10.0
+    );
+
+    // Updating an existing override modifies it in place
+    for _ in 0..10 {
+        db.update_property_override(obj2, "x", increment).unwrap();
+    }
+    assert_eq!(
+        db.resolve_property(obj2, "x").unwrap().as_f32().unwrap(),
+        21.0
+    );
+
+    // Changing the value to the wrong type is an error, and the override is left as it was
+    assert!(matches!(
+        db.update_property_override(obj2, "x", |value| *value = Value::I32(1))
+            .unwrap_err()
+            .error,
+        DataSetError::ValueDoesNotMatchSchema
+    ));
+    assert_eq!(
+        db.get_property_override(obj2, "x")
+            .unwrap()
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        21.0
+    );
+}
+
+#[test]
+fn set_enum_by_symbol_name() {
+    let mut linker = SchemaLinker::default();
+    linker
+        .register_enum_type("Color", Uuid::new_v4(), |builder| {
+            builder.add_symbol("Red", Uuid::new_v4());
+            builder
+                .add_symbol("Green", Uuid::new_v4())
+                .add_symbol_alias("Lime");
+        })
+        .unwrap();
+    linker
+        .register_record_type("Light", Uuid::new_v4(), |builder| {
+            builder.add_named_type("color", Uuid::new_v4(), "Color");
+            builder.add_f32("intensity", Uuid::new_v4());
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let light_type = schema_set
+        .find_named_type("Light")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+    let mut light = SingleObject::new(&light_type);
+    let mut container = DataContainerRefMut::from_single_object(&mut light, &schema_set);
+
+    // The symbols can be listed without a Rust type for the enum
+    let enum_schema = container.enum_schema("color").unwrap();
+    let symbol_names: Vec<_> = enum_schema.symbols().iter().map(|x| x.name()).collect();
+    assert_eq!(symbol_names, vec!["Green", "Red"]);
+    assert_eq!(container.resolve_enum_symbol("color").unwrap(), "Green");
+
+    container.set_enum_symbol("color", "Red").unwrap();
+    assert_eq!(container.resolve_enum_symbol("color").unwrap(), "Red");
+
+    // Aliases are stored as the symbol's actual name
+    container.set_enum_symbol("color", "Lime").unwrap();
+    assert_eq!(container.resolve_enum_symbol("color").unwrap(), "Green");
+
+    assert!(container.set_enum_symbol("color", "Blue").is_err());
+    assert!(container.enum_schema("intensity").is_err());
+    assert_eq!(container.resolve_enum_symbol("color").unwrap(), "Green");
+}
+
+#[derive(Debug, PartialEq)]
+enum TestColorEnum {
+    Red,
+    Green,
+}
+
+impl hydrate_data::Enum for TestColorEnum {
+    fn to_symbol_name(&self) -> &'static str {
+        match self {
+            TestColorEnum::Red => "Red",
+            TestColorEnum::Green => "Green",
+        }
+    }
+
+    fn from_symbol_name(str: &str) -> Option<TestColorEnum> {
+        match str {
+            "Red" => Some(TestColorEnum::Red),
+            "Green" => Some(TestColorEnum::Green),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn unknown_enum_symbols_resolve_in_lenient_mode() {
+    use hydrate_data::json_storage::SingleObjectJson;
+    use hydrate_data::{DataContainerRef, EnumFieldRef, FieldRef};
+    use hydrate_schema::PropertyPath;
+
+    for strict in [false, true] {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_enum_type("Color", Uuid::new_v4(), |builder| {
+                builder
+                    .add_symbol("Red", Uuid::new_v4())
+                    .add_symbol_alias("Crimson");
+                builder.add_symbol("Green", Uuid::new_v4());
+            })
+            .unwrap();
+        linker
+            .register_record_type("Light", Uuid::new_v4(), |builder| {
+                builder.add_named_type("color", Uuid::new_v4(), "Color");
+            })
+            .unwrap();
+
+        let mut schema_set_builder = SchemaSetBuilder::default();
+        schema_set_builder.add_linked_types(linker).unwrap();
+        schema_set_builder.set_strict_enum_symbols(strict);
+        let schema_set = schema_set_builder.build();
+        let light_type = schema_set
+            .find_named_type("Light")
+            .unwrap()
+            .as_record()
+            .unwrap()
+            .clone();
+
+        let load = |symbol_name: &str| -> SingleObject {
+            let json = serde_json::json!({
+                "root_schema": light_type.fingerprint().as_uuid(),
+                "schema_name": "Light",
+                "properties": { "color": symbol_name },
+            });
+            let object_json: SingleObjectJson = serde_json::from_value(json).unwrap();
+            object_json.to_single_object(&schema_set, &mut None)
+        };
+
+        let read = |object: &SingleObject| {
+            EnumFieldRef::<TestColorEnum>::new(
+                PropertyPath::default().push("color"),
+                DataContainerRef::from_single_object(object, &schema_set),
+            )
+            .get()
+        };
+
+        // Renamed symbols resolve through their alias in either mode
+        let renamed = load("Crimson");
+        assert_eq!(read(&renamed).unwrap(), TestColorEnum::Red);
+
+        // Removed symbols are kept in the data, and only fail to read in strict mode
+        let removed = load("Blue");
+        let container = DataContainerRef::from_single_object(&removed, &schema_set);
+        assert_eq!(container.resolve_enum_symbol("color").unwrap(), "Blue");
+        if strict {
+            assert!(matches!(
+                read(&removed).unwrap_err().error,
+                DataSetError::UnexpectedEnumSymbol
+            ));
+        } else {
+            assert_eq!(read(&removed).unwrap(), TestColorEnum::Green);
+        }
+    }
+}
+
+#[test]
+fn asset_name_validation() {
+    // Separators and control characters are rejected rather than breaking asset paths
+    assert!(AssetName::try_new("a/b").is_err());
+    assert!(AssetName::try_new("a\\b").is_err());
+    assert!(AssetName::try_new("line\nbreak").is_err());
+    assert!(AssetName::try_new(" padded ").is_err());
+    assert!(AssetName::try_new("Brick Wall.png").is_ok());
+    assert!(AssetName::try_new("テクスチャ.png").is_ok());
+
+    // Empty names are still representable
+    let empty = AssetName::try_new("").unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(empty.as_string(), None);
+
+    // Sanitizing a name from outside the editor produces a valid one
+    let sanitized = AssetName::sanitize(" meshes/cube\t.gltf.Body\\Mesh ");
+    assert_eq!(
+        sanitized.as_string().unwrap().as_str(),
+        "meshes_cube_.gltf.Body_Mesh"
+    );
+    assert!(AssetName::is_valid(sanitized.as_string().unwrap()));
+    assert_eq!(
+        AssetName::sanitize("テクスチャ.png")
+            .as_string()
+            .unwrap()
+            .as_str(),
+        "テクスチャ.png"
+    );
+    assert!(AssetName::sanitize("  ").is_empty());
+
+    let path = AssetPath::new_root("db").join(sanitized.as_string().unwrap());
+    assert_eq!(path.as_str(), "db://meshes_cube_.gltf.Body_Mesh");
+}
+
+#[test]
+fn assets_split_across_sources() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut db = EditContext::new(
+        &project_config,
+        EditContextKey::default(),
+        schema_set.clone(),
+        &undo_stack,
+    );
+
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    // Each source has a root path node with the source's ID
+    let source_a = AssetSourceId::new();
+    let source_b = AssetSourceId::new();
+    let root_a = AssetId::from_uuid(*source_a.uuid());
+    let root_b = AssetId::from_uuid(*source_b.uuid());
+    db.new_asset_with_id(
+        root_a,
+        &AssetName::new("a"),
+        &AssetLocation::null(),
+        &vec3_type,
+    )
+    .unwrap();
+    db.new_asset_with_id(
+        root_b,
+        &AssetName::new("b"),
+        &AssetLocation::null(),
+        &vec3_type,
+    )
+    .unwrap();
+
+    let folder_a = db.new_asset(
+        &AssetName::new("folder"),
+        &AssetLocation::new(root_a),
+        &vec3_type,
+    );
+    let obj_a1 = db.new_asset(
+        &AssetName::new("obj_a1"),
+        &AssetLocation::new(root_a),
+        &vec3_type,
+    );
+    let obj_a2 = db.new_asset(
+        &AssetName::new("obj_a2"),
+        &AssetLocation::new(folder_a),
+        &vec3_type,
+    );
+    let obj_b1 = db.new_asset(
+        &AssetName::new("obj_b1"),
+        &AssetLocation::new(root_b),
+        &vec3_type,
+    );
+    let unowned = db.new_asset(
+        &AssetName::new("unowned"),
+        &AssetLocation::null(),
+        &vec3_type,
+    );
+
+    let mut assets_a: Vec<_> = db.assets_for_source(source_a).collect();
+    assets_a.sort();
+    let mut expected_a = vec![folder_a, obj_a1, obj_a2];
+    expected_a.sort();
+    assert_eq!(assets_a, expected_a);
+
+    let assets_b: Vec<_> = db.assets_for_source(source_b).collect();
+    assert_eq!(assets_b, vec![obj_b1]);
+
+    // Nested locations only include assets beneath them
+    let under_folder: Vec<_> = db
+        .data_set()
+        .assets_under_location(AssetLocation::new(folder_a))
+        .collect();
+    assert_eq!(under_folder, vec![obj_a2]);
+    assert!(!db
+        .data_set()
+        .is_asset_under_location(unowned, AssetLocation::new(root_a)));
+
+    // Moving an asset moves it between sources
+    db.set_asset_location(obj_a2, AssetLocation::new(root_b))
+        .unwrap();
+    assert!(db.assets_for_source(source_b).any(|x| x == obj_a2));
+    assert!(!db.assets_for_source(source_a).any(|x| x == obj_a2));
+}
+
+#[test]
+fn data_source_for_asset_resolves_through_location() {
+    use crate::{EditorModel, PathNode, PathNodeRoot};
+    use hydrate_pipeline::ImportJobToQueue;
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let root_a = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    let root_b = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&root_a).unwrap();
+    std::fs::create_dir_all(&root_b).unwrap();
+    let project_config = default_project_config();
+    let mut editor_model = EditorModel::new(project_config.clone(), schema_set);
+    let source_a = editor_model.add_file_system_id_based_asset_source(
+        &project_config,
+        "a",
+        &root_a,
+        &mut ImportJobToQueue::default(),
+    );
+    let source_b = editor_model.add_file_system_id_based_asset_source(
+        &project_config,
+        "b",
+        &root_b,
+        &mut ImportJobToQueue::default(),
+    );
+
+    let edit_context = editor_model.root_edit_context_mut();
+    let folder_a = edit_context.new_asset(
+        &AssetName::new("folder"),
+        &AssetLocation::new(AssetId::from_uuid(*source_a.uuid())),
+        &vec3_type,
+    );
+    let asset_a = edit_context.new_asset(
+        &AssetName::new("asset_a"),
+        &AssetLocation::new(folder_a),
+        &vec3_type,
+    );
+    let asset_b = edit_context.new_asset(
+        &AssetName::new("asset_b"),
+        &AssetLocation::new(AssetId::from_uuid(*source_b.uuid())),
+        &vec3_type,
+    );
+    let unowned = edit_context.new_asset(
+        &AssetName::new("unowned"),
+        &AssetLocation::null(),
+        &vec3_type,
+    );
+
+    assert_eq!(
+        editor_model.asset_source_id_for_asset(folder_a),
+        Some(source_a)
+    );
+    assert_eq!(
+        editor_model.asset_source_id_for_asset(asset_a),
+        Some(source_a)
+    );
+    assert_eq!(
+        editor_model.asset_source_id_for_asset(asset_b),
+        Some(source_b)
+    );
+    assert!(editor_model.data_source_for_asset(asset_a).is_some());
+    assert!(editor_model.data_source_for_asset(asset_b).is_some());
+
+    // Assets with a null location, and unknown assets, aren't in any source
+    assert_eq!(editor_model.asset_source_id_for_asset(unowned), None);
+    assert!(editor_model.data_source_for_asset(unowned).is_none());
+    assert!(editor_model
+        .data_source_for_asset(AssetId::from_uuid(Uuid::new_v4()))
+        .is_none());
+
+    std::fs::remove_dir_all(&root_a).unwrap();
+    std::fs::remove_dir_all(&root_b).unwrap();
+}
+
+#[test]
+fn resolve_property_borrows_without_cloning() {
+    let mut linker = SchemaLinker::default();
+    linker
+        .register_record_type("Buffer", Uuid::new_v4(), |builder| {
+            builder.add_bytes("data", Uuid::new_v4());
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut db = EditContext::new(
+        &project_config,
+        EditContextKey::default(),
+        schema_set.clone(),
+        &undo_stack,
+    );
+    let asset_location = asset_location();
+
+    let buffer_type = schema_set
+        .find_named_type("Buffer")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let obj1 = db.new_asset(&AssetName::new("obj1"), &asset_location, &buffer_type);
+    let obj2 = db
+        .new_asset_from_prototype(&AssetName::new("obj2"), &asset_location, obj1)
+        .unwrap();
+
+    let data = Arc::new(vec![0u8; 1024 * 1024]);
+    db.set_property_override(obj1, "data", Some(Value::Bytes(data.clone())))
+        .unwrap();
+
+    // Both the local override and the value inherited from the prototype are the stored buffer
+    let local = db
+        .resolve_property(obj1, "data")
+        .unwrap()
+        .as_bytes()
+        .unwrap();
+    let inherited = db
+        .resolve_property(obj2, "data")
+        .unwrap()
+        .as_bytes()
+        .unwrap();
+    assert!(Arc::ptr_eq(local, &data));
+    assert!(Arc::ptr_eq(inherited, &data));
+}
+
+#[test]
+fn asset_view_caches_property_path_resolution() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("Mesh", Uuid::new_v4(), |builder| {
+            builder.add_nullable(
+                "bounds",
+                Uuid::new_v4(),
+                SchemaDefType::NamedType("Vec3".to_string()),
+            );
+            builder.add_dynamic_array(
+                "points",
+                Uuid::new_v4(),
+                SchemaDefType::NamedType("Vec3".to_string()),
+            );
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut db = EditContext::new(
+        &project_config,
+        EditContextKey::default(),
+        schema_set.clone(),
+        &undo_stack,
+    );
+    let asset_location = asset_location();
+
+    let mesh_type = schema_set
+        .find_named_type("Mesh")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let prototype = db.new_asset(&AssetName::new("prototype"), &asset_location, &mesh_type);
+    db.set_null_override(prototype, "bounds", NullOverride::SetNonNull)
+        .unwrap();
+    db.set_property_override(prototype, "bounds.value.x", Some(Value::F32(1.0)))
+        .unwrap();
+    let mut points = vec![];
+    for i in 0..8 {
+        let point = db.add_dynamic_array_entry(prototype, "points").unwrap();
+        db.set_property_override(
+            prototype,
+            format!("points.{}.y", point),
+            Some(Value::F32(i as f32)),
+        )
+        .unwrap();
+        points.push(point);
+    }
+
+    let obj = db
+        .new_asset_from_prototype(&AssetName::new("obj"), &asset_location, prototype)
+        .unwrap();
+    let point = db.add_dynamic_array_entry(obj, "points").unwrap();
+    db.set_property_override(obj, format!("points.{}.z", point), Some(Value::F32(5.0)))
+        .unwrap();
+    points.push(point);
+
+    let mut paths = vec![];
+    for axis in ["x", "y", "z"] {
+        paths.push(format!("bounds.value.{}", axis));
+        for point in &points {
+            paths.push(format!("points.{}.{}", point, axis));
+        }
+    }
+
+    // Read every field several times, as a builder reading the asset through multiple readers would
+    let read_count = 10;
+    let data_set = db.data_set();
+
+    let count_before = hydrate_data::property_ancestry_computation_count();
+    let mut expected_values = vec![];
+    for _ in 0..read_count {
+        expected_values.clear();
+        for path in &paths {
+            expected_values.push(
+                data_set
+                    .resolve_property(&schema_set, obj, path)
+                    .unwrap()
+                    .as_f32()
+                    .unwrap(),
+            );
+        }
+    }
+    let without_view = hydrate_data::property_ancestry_computation_count() - count_before;
+
+    let count_before = hydrate_data::property_ancestry_computation_count();
+    let view = data_set.asset_view(&schema_set, obj).unwrap();
+    let mut values = vec![];
+    for _ in 0..read_count {
+        values.clear();
+        for path in &paths {
+            values.push(view.resolve_property(path).unwrap().as_f32().unwrap());
+        }
+    }
+    let with_view = hydrate_data::property_ancestry_computation_count() - count_before;
+
+    assert_eq!(values, expected_values);
+    assert_eq!(values[0], 1.0);
+    assert_eq!(
+        view.resolve_dynamic_array_entries("points").unwrap(),
+        data_set
+            .resolve_dynamic_array_entries(&schema_set, obj, "points")
+            .unwrap()
+    );
+
+    // Without the view, every read resolves the full path again plus the path of the nullable or
+    // dynamic array containing it. With it, each distinct path and parent path is resolved once.
+    assert_eq!(without_view, 2 * read_count * paths.len());
+    assert!(with_view <= 2 * paths.len());
+
+    // Invalid paths fail the same way as through the data set
+    assert!(view.resolve_property("points.not_a_uuid.x").is_err());
+    assert!(view
+        .resolve_property(format!("points.{}.x", Uuid::new_v4()))
+        .is_err());
+}
+
+#[test]
+fn estimated_memory_includes_bytes_payload() {
+    let mut linker = SchemaLinker::default();
+    linker
+        .register_record_type("Buffer", Uuid::new_v4(), |builder| {
+            builder.add_bytes("data", Uuid::new_v4());
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let buffer_type = schema_set
+        .find_named_type("Buffer")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut data_set = DataSet::default();
+    assert_eq!(data_set.asset_count(), 0);
+    assert_eq!(data_set.estimated_memory_bytes(), 0);
+
+    let small = data_set.new_asset(AssetName::new("small"), asset_location(), &buffer_type);
+    let large = data_set.new_asset(AssetName::new("large"), asset_location(), &buffer_type);
+    data_set
+        .set_property_override(
+            &schema_set,
+            large,
+            "data",
+            Some(Value::Bytes(Arc::new(vec![0u8; 1024 * 1024]))),
+        )
+        .unwrap();
+    assert_eq!(data_set.asset_count(), 2);
+
+    // The estimate covers the buffer itself, not just the size of the property map
+    let small_bytes = data_set.asset_estimated_memory_bytes(small).unwrap();
+    let large_bytes = data_set.asset_estimated_memory_bytes(large).unwrap();
+    assert!(small_bytes > 0);
+    assert!(large_bytes >= 1024 * 1024);
+    assert!(large_bytes < 2 * 1024 * 1024);
+    assert_eq!(data_set.estimated_memory_bytes(), small_bytes + large_bytes);
+    assert!(data_set
+        .asset_estimated_memory_bytes(AssetId::from_u128(1))
+        .is_none());
+}
+
+#[test]
+fn delete_cascades_to_owned_assets_only() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("Part", Uuid::new_v4(), |builder| {
+            builder
+                .add_reference("owned_part", Uuid::new_v4(), "Vec3")
+                .set_owned(true);
+        })
+        .unwrap();
+    linker
+        .register_record_type("Assembly", Uuid::new_v4(), |builder| {
+            builder
+                .add_reference("owned_child", Uuid::new_v4(), "Part")
+                .set_owned(true);
+            builder.add_reference("shared", Uuid::new_v4(), "Vec3");
+        })
+        .unwrap();
+
+    // Only fields that contain asset references can be owned
+    assert!(linker
+        .register_record_type("InvalidOwned", Uuid::new_v4(), |builder| {
+            builder.add_f32("x", Uuid::new_v4()).set_owned(true);
+        })
+        .is_err());
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut db = EditContext::new(
+        &project_config,
+        EditContextKey::default(),
+        schema_set.clone(),
+        &undo_stack,
+    );
+    let asset_location = asset_location();
+    let record = |name: &str| {
+        schema_set
+            .find_named_type(name)
+            .unwrap()
+            .as_record()
+            .unwrap()
+            .clone()
+    };
+
+    let assembly = db.new_asset(
+        &AssetName::new("assembly"),
+        &asset_location,
+        &record("Assembly"),
+    );
+    let child = db.new_asset(&AssetName::new("child"), &asset_location, &record("Part"));
+    let grandchild = db.new_asset(
+        &AssetName::new("grandchild"),
+        &asset_location,
+        &record("Vec3"),
+    );
+    let shared = db.new_asset(&AssetName::new("shared"), &asset_location, &record("Vec3"));
+    db.set_property_override(assembly, "owned_child", Some(Value::AssetRef(child)))
+        .unwrap();
+    db.set_property_override(assembly, "shared", Some(Value::AssetRef(shared)))
+        .unwrap();
+    db.set_property_override(child, "owned_part", Some(Value::AssetRef(grandchild)))
+        .unwrap();
+
+    assert_eq!(
+        db.data_set().owned_assets(&schema_set, assembly).unwrap(),
+        vec![child]
+    );
+
+    // Deleting the assembly deletes what it owns, transitively, but not what it only references
+    db.delete_asset_and_children(assembly).unwrap();
+    assert!(!db.has_asset(assembly));
+    assert!(!db.has_asset(child));
+    assert!(!db.has_asset(grandchild));
+    assert!(db.has_asset(shared));
+}
+
+#[test]
+fn clear_all_overrides_resets_to_prototype() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("Thing", Uuid::new_v4(), |builder| {
+            builder.add_f32("value", Uuid::new_v4());
+            builder.add_nullable(
+                "maybe",
+                Uuid::new_v4(),
+                SchemaDefType::NamedType("Vec3".to_string()),
+            );
+            builder.add_dynamic_array(
+                "items",
+                Uuid::new_v4(),
+                SchemaDefType::NamedType("Vec3".to_string()),
+            );
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let mut undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut edit_contexts = DenseSlotMap::<EditContextKey, EditContext>::default();
+    let key = edit_contexts.insert_with_key(|key| {
+        EditContext::new(&project_config, key, schema_set.clone(), &undo_stack)
+    });
+    let asset_location = asset_location();
+
+    let thing_type = schema_set
+        .find_named_type("Thing")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let db = &mut edit_contexts[key];
+    let prototype = db.new_asset(&AssetName::new("prototype"), &asset_location, &thing_type);
+    db.set_property_override(prototype, "value", Some(Value::F32(1.0)))
+        .unwrap();
+    db.set_null_override(prototype, "maybe", NullOverride::SetNonNull)
+        .unwrap();
+    let prototype_item = db.add_dynamic_array_entry(prototype, "items").unwrap();
+
+    let child = db
+        .new_asset_from_prototype(&AssetName::new("child"), &asset_location, prototype)
+        .unwrap();
+
+    edit_contexts[key].with_undo_context("override child", |db| {
+        db.set_property_override(child, "value", Some(Value::F32(2.0)))
+            .unwrap();
+        db.set_null_override(child, "maybe", NullOverride::SetNull)
+            .unwrap();
+        let child_item = db.add_dynamic_array_entry(child, "items").unwrap();
+        db.set_property_override(
+            child,
+            format!("items.{}.x", child_item),
+            Some(Value::F32(3.0)),
+        )
+        .unwrap();
+        db.set_override_behavior(child, "items", OverrideBehavior::Replace)
+            .unwrap();
+        EndContextBehavior::Finish
+    });
+
+    let assert_resolves_like_prototype = |db: &EditContext| {
+        assert_eq!(
+            db.resolve_property(child, "value")
+                .unwrap()
+                .as_f32()
+                .unwrap(),
+            1.0
+        );
+        assert_eq!(
+            db.resolve_null_override(child, "maybe").unwrap(),
+            NullOverride::SetNonNull
+        );
+        assert_eq!(
+            db.get_override_behavior(child, "items").unwrap(),
+            OverrideBehavior::Append
+        );
+        assert_eq!(
+            db.resolve_dynamic_array_entries(child, "items").unwrap(),
+            vec![prototype_item].into_boxed_slice()
+        );
+        let child_info = &db.data_set().assets()[&child];
+        assert!(child_info.properties().is_empty());
+        assert!(child_info.dynamic_collection_entries().is_empty());
+    };
+
+    let mut cleared_overrides = None;
+    edit_contexts[key].with_undo_context("reset to prototype", |db| {
+        cleared_overrides = Some(db.clear_all_overrides(child).unwrap());
+        EndContextBehavior::Finish
+    });
+    let cleared_overrides = cleared_overrides.unwrap();
+    assert_resolves_like_prototype(&edit_contexts[key]);
+
+    // The removed overrides are recorded for undo
+    undo_stack.undo(&mut edit_contexts).unwrap();
+    assert_eq!(
+        edit_contexts[key]
+            .resolve_property(child, "value")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        2.0
+    );
+    assert_eq!(
+        edit_contexts[key]
+            .get_override_behavior(child, "items")
+            .unwrap(),
+        OverrideBehavior::Replace
+    );
+    undo_stack.redo(&mut edit_contexts).unwrap();
+    assert_resolves_like_prototype(&edit_contexts[key]);
+
+    // The returned overrides can be written back
+    edit_contexts[key]
+        .write_properties_bundle(&schema_set, child, "", &cleared_overrides)
+        .unwrap();
+    assert_eq!(
+        edit_contexts[key]
+            .resolve_property(child, "value")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        2.0
+    );
+    assert_eq!(
+        edit_contexts[key]
+            .resolve_null_override(child, "maybe")
+            .unwrap(),
+        NullOverride::SetNull
+    );
+    assert_eq!(
+        edit_contexts[key]
+            .resolve_dynamic_array_entries(child, "items")
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn property_type_names() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("Container", Uuid::new_v4(), |builder| {
+            builder.add_nullable(
+                "nullable_vec3",
+                Uuid::new_v4(),
+                SchemaDefType::NamedType("Vec3".to_string()),
+            );
+            builder.add_dynamic_array(
+                "nullable_array",
+                Uuid::new_v4(),
+                SchemaDefType::Nullable(Box::new(SchemaDefType::NamedType("Vec3".to_string()))),
+            );
+            builder.add_dynamic_array(
+                "nested_array",
+                Uuid::new_v4(),
+                SchemaDefType::DynamicArray(SchemaDefDynamicArray::new(Box::new(
+                    SchemaDefType::String,
+                ))),
+            );
+            builder.add_reference("reference", Uuid::new_v4(), "Vec3");
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let container_type = schema_set
+        .find_named_type("Container")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+    let mut data_set = DataSet::default();
+    let asset_id = data_set.new_asset(
+        AssetName::new("container"),
+        AssetLocation::null(),
+        &container_type,
+    );
+
+    let type_name = |path: &str| {
+        data_set
+            .property_type_name(&schema_set, asset_id, path)
+            .unwrap()
+    };
+    assert_eq!(type_name("nullable_vec3"), "Nullable<Vec3>");
+    assert_eq!(type_name("nullable_vec3.value"), "Vec3");
+    assert_eq!(type_name("nullable_vec3.value.x"), "F32");
+    assert_eq!(type_name("nullable_array"), "DynamicArray<Nullable<Vec3>>");
+    let entry = Uuid::new_v4();
+    assert_eq!(
+        type_name(&format!("nullable_array.{}", entry)),
+        "Nullable<Vec3>"
+    );
+    assert_eq!(
+        type_name("nested_array"),
+        "DynamicArray<DynamicArray<String>>"
+    );
+    assert_eq!(type_name("reference"), "AssetRef<Vec3>");
+    assert!(data_set
+        .property_type_name(&schema_set, asset_id, "missing")
+        .is_err());
+
+    // Maps can't be defined with the builder, so check one directly
+    let vec3_fingerprint = schema_set.find_named_type("Vec3").unwrap().fingerprint();
+    let map = Schema::Map(SchemaMap::new(
+        Box::new(Schema::String),
+        Box::new(Schema::Nullable(Box::new(Schema::Record(vec3_fingerprint)))),
+    ));
+    assert_eq!(
+        map.type_name(schema_set.schemas()),
+        "Map<String, Nullable<Vec3>>"
+    );
+}
+
+#[test]
+fn resolve_property_chain_through_three_prototypes() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("MaybeVec3", Uuid::new_v4(), |builder| {
+            builder.add_nullable(
+                "maybe",
+                Uuid::new_v4(),
+                SchemaDefType::NamedType("Vec3".to_string()),
+            );
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    // root <- middle <- leaf, where both root and middle override x
+    let mut data_set = DataSet::default();
+    let root = data_set.new_asset(AssetName::new("root"), AssetLocation::null(), &vec3_type);
+    let middle = data_set
+        .new_asset_from_prototype(AssetName::new("middle"), AssetLocation::null(), root)
+        .unwrap();
+    let leaf = data_set
+        .new_asset_from_prototype(AssetName::new("leaf"), AssetLocation::null(), middle)
+        .unwrap();
+    data_set
+        .set_property_override(&schema_set, root, "x", Some(Value::F32(1.0)))
+        .unwrap();
+    data_set
+        .set_property_override(&schema_set, middle, "x", Some(Value::F32(2.0)))
+        .unwrap();
+
+    let chain = data_set
+        .resolve_property_chain(&schema_set, leaf, "x")
+        .unwrap();
+    assert_eq!(chain.len(), 4);
+    assert!(matches!(chain[0], PropertyContribution::NotSet(id) if id == leaf));
+    assert!(
+        matches!(chain[1], PropertyContribution::Value(id, value) if id == middle && value.as_f32().ok() == Some(2.0))
+    );
+    assert!(
+        matches!(chain[2], PropertyContribution::Value(id, value) if id == root && value.as_f32().ok() == Some(1.0))
+    );
+    assert!(
+        matches!(chain[3], PropertyContribution::SchemaDefault(value) if value.as_f32().ok() == Some(0.0))
+    );
+
+    // The first value in the chain is the resolved value
+    assert_eq!(
+        data_set
+            .resolve_property(&schema_set, leaf, "x")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        2.0
+    );
+
+    // Nothing in the chain sets y
+    let chain = data_set
+        .resolve_property_chain(&schema_set, middle, "y")
+        .unwrap();
+    assert_eq!(chain.len(), 3);
+    assert!(matches!(chain[0], PropertyContribution::NotSet(id) if id == middle));
+    assert!(matches!(chain[1], PropertyContribution::NotSet(id) if id == root));
+    assert!(
+        matches!(chain[2], PropertyContribution::SchemaDefault(value) if value.as_f32().ok() == Some(0.0))
+    );
+
+    // Null overrides on nullable properties are reported as well
+    let maybe_vec3_type = schema_set
+        .find_named_type("MaybeVec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+    let prototype = data_set.new_asset(
+        AssetName::new("prototype"),
+        AssetLocation::null(),
+        &maybe_vec3_type,
+    );
+    let instance = data_set
+        .new_asset_from_prototype(AssetName::new("instance"), AssetLocation::null(), prototype)
+        .unwrap();
+    data_set
+        .set_null_override(&schema_set, prototype, "maybe", NullOverride::SetNonNull)
+        .unwrap();
+    let chain = data_set
+        .resolve_property_chain(&schema_set, instance, "maybe")
+        .unwrap();
+    assert_eq!(chain.len(), 3);
+    assert!(matches!(chain[0], PropertyContribution::NotSet(id) if id == instance));
+    assert!(matches!(
+        chain[1],
+        PropertyContribution::NullOverride(id, NullOverride::SetNonNull) if id == prototype
+    ));
+}
+
+#[test]
+fn remap_ids_rewrites_all_references() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    create_reference_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+    let has_reference_type = schema_set
+        .find_named_type("HasReference")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    // A folder containing a prototype, an instance of it, and an asset that references the prototype
+    let mut data_set = DataSet::default();
+    let folder = data_set.new_asset(AssetName::new("folder"), AssetLocation::null(), &vec3_type);
+    let prototype = data_set.new_asset(
+        AssetName::new("prototype"),
+        AssetLocation::new(folder),
+        &vec3_type,
+    );
+    let instance = data_set
+        .new_asset_from_prototype(
+            AssetName::new("instance"),
+            AssetLocation::new(folder),
+            prototype,
+        )
+        .unwrap();
+    let referencing = data_set.new_asset(
+        AssetName::new("referencing"),
+        AssetLocation::new(folder),
+        &has_reference_type,
+    );
+    data_set
+        .set_property_override(
+            &schema_set,
+            referencing,
+            "reference",
+            Some(Value::AssetRef(prototype)),
+        )
+        .unwrap();
+
+    // The instance is not remapped
+    let new_folder = AssetId::from_uuid(Uuid::new_v4());
+    let new_prototype = AssetId::from_uuid(Uuid::new_v4());
+    let new_referencing = AssetId::from_uuid(Uuid::new_v4());
+    let mut mapping = HashMap::default();
+    mapping.insert(folder, new_folder);
+    mapping.insert(prototype, new_prototype);
+    mapping.insert(referencing, new_referencing);
+    data_set.remap_ids(&mapping).unwrap();
+
+    assert_eq!(data_set.assets().len(), 4);
+    for old_asset_id in [folder, prototype, referencing] {
+        assert!(!data_set.assets().contains_key(&old_asset_id));
+    }
+    assert!(data_set.assets().contains_key(&instance));
+    assert_eq!(
+        data_set
+            .asset_name(new_prototype)
+            .unwrap()
+            .as_string()
+            .unwrap(),
+        "prototype"
+    );
+    assert!(data_set.asset_location(new_folder).unwrap().is_null());
+    for asset_id in [new_prototype, instance, new_referencing] {
+        assert_eq!(
+            data_set.asset_location(asset_id).unwrap().path_node_id(),
+            new_folder
+        );
+    }
+    assert_eq!(data_set.asset_prototype(instance), Some(new_prototype));
+    assert_eq!(
+        data_set
+            .get_property_override(new_referencing, "reference")
+            .unwrap()
+            .unwrap()
+            .as_asset_ref()
+            .unwrap(),
+        new_prototype
+    );
+
+    // Mapping onto an ID that is still in use fails without changing anything
+    let mut mapping = HashMap::default();
+    mapping.insert(new_prototype, instance);
+    assert!(data_set.remap_ids(&mapping).is_err());
+    assert!(data_set.assets().contains_key(&new_prototype));
+    assert_eq!(data_set.asset_prototype(instance), Some(new_prototype));
+}
+
+// Every kind of property, including records, containers and asset refs that contain themselves
+const RANDOM_VALUES_SCHEMA: &str = r#"[
+  {
+    "type": "enum",
+    "name": "RandomEnum",
+    "uuid": "c5d0f2a4-63b1-4a8e-9d2f-1b7e4c3a9f01",
+    "symbols": [
+      { "name": "A", "uuid": "0f6f5b7e-2c4a-4d1b-8e3f-5a9c7d2b1e02" },
+      { "name": "B", "uuid": "7a3c9e1d-4b5f-4c2a-9d8e-6f1b3a5c7d03" },
+      { "name": "C", "uuid": "2e8b4d6f-1a3c-4e5b-8f7d-9c1a2b4e6f04" }
+    ]
+  },
+  {
+    "type": "record",
+    "name": "RandomLeaf",
+    "uuid": "9b1d3f5a-7c2e-4a6b-8d4f-0e2c6a8b1d05",
+    "fields": [
+      { "name": "x", "type": "f32", "uuid": "4c6e8a0b-2d4f-4b6c-9e1a-3f5b7d9c1e06" },
+      { "name": "name", "type": "string", "uuid": "8e0a2c4d-6f8b-4d0e-a2c4-7b9d1f3e5a07" }
+    ]
+  },
+  {
+    "type": "record",
+    "name": "RandomValues",
+    "uuid": "1f3b5d7e-9a2c-4e4f-b6d8-2a4c6e8f0b08",
+    "fields": [
+      { "name": "boolean", "type": "bool", "uuid": "5a7c9e1f-3b5d-4f7a-8c9e-4b6d8f0a2c09" },
+      { "name": "i32", "type": "i32", "uuid": "6b8d0f2a-4c6e-4a8b-9d0f-5c7e9a1b3d10" },
+      { "name": "i64", "type": "i64", "uuid": "7c9e1a3b-5d7f-4b9c-8e1a-6d8f0b2c4e11" },
+      { "name": "u32", "type": "u32", "uuid": "8d0f2b4c-6e8a-4c0d-9f2b-7e9a1c3d5f12" },
+      { "name": "u64", "type": "u64", "uuid": "9e1a3c5d-7f9b-4d1e-8a3c-8f0b2d4e6a13" },
+      { "name": "f32", "type": "f32", "uuid": "0f2b4d6e-8a0c-4e2f-9b4d-9a1c3e5f7b14" },
+      { "name": "f64", "type": "f64", "uuid": "1a3c5e7f-9b1d-4f3a-8c5e-0b2d4f6a8c15" },
+      { "name": "bytes", "type": "bytes", "uuid": "2b4d6f8a-0c2e-4a4b-9d6f-1c3e5a7b9d16" },
+      { "name": "string", "type": "string", "uuid": "3c5e7a9b-1d3f-4b5c-8e7a-2d4f6b8c0e17" },
+      { "name": "enum", "type": "RandomEnum", "uuid": "4d6f8b0c-2e4a-4c6d-9f8b-3e5a7c9d1f18" },
+      { "name": "leaf", "type": "RandomLeaf", "uuid": "5e7a9c1d-3f5b-4d7e-8a9c-4f6b8d0e2a19" },
+      {
+        "name": "nullable_leaf",
+        "type": { "name": "nullable", "inner_type": "RandomLeaf" },
+        "uuid": "6f8b0d2e-4a6c-4e8f-9b0d-5a7c9e1f3b20"
+      },
+      {
+        "name": "static_array",
+        "type": { "name": "static_array", "inner_type": "i32", "length": 3 },
+        "uuid": "7a9c1e3f-5b7d-4f9a-8c1e-6b8d0f2a4c21"
+      },
+      {
+        "name": "dynamic_array",
+        "type": { "name": "dynamic_array", "inner_type": "string" },
+        "uuid": "8b0d2f4a-6c8e-4a0b-9d2f-7c9e1a3b5d22"
+      },
+      {
+        "name": "map",
+        "type": { "name": "map", "key_type": "RandomEnum", "value_type": "RandomLeaf" },
+        "uuid": "9c1e3a5b-7d9f-4b1c-8e3a-8d0f2b4c6e23"
+      },
+      {
+        "name": "reference",
+        "type": { "name": "asset_ref", "inner_type": "RandomLeaf" },
+        "uuid": "0d2f4b6c-8e0a-4c2d-9f4b-9e1a3c5d7f24"
+      },
+      {
+        "name": "recursive_reference",
+        "type": { "name": "asset_ref", "inner_type": "RandomValues" },
+        "uuid": "1e3a5c7d-9f1b-4d3e-8a5c-0f2b4d6e8a25"
+      },
+      {
+        "name": "recursive_dynamic_array",
+        "type": { "name": "dynamic_array", "inner_type": "RandomValues" },
+        "uuid": "2f4b6d8e-0a2c-4e4f-9b6d-1a3c5e7f9b26"
+      }
+    ]
+  }
+]"#;
+
+#[test]
+fn random_assets_survive_serialization_round_trip() {
+    use crate::json_storage::AssetJson;
+    use hydrate_data::HashObjectMode;
+    use rand::SeedableRng;
+
+    let schema_dir = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&schema_dir).unwrap();
+    std::fs::write(schema_dir.join("random_values.json"), RANDOM_VALUES_SCHEMA).unwrap();
+    let mut linker = SchemaLinker::default();
+    linker.add_source_dir(&schema_dir, "**.json").unwrap();
+    std::fs::remove_dir_all(&schema_dir).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let random_values_type = schema_set
+        .find_named_type("RandomValues")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+
+    for seed in 0..20 {
+        let mut data_set = DataSet::default();
+        let asset_id = data_set
+            .new_asset_with_random_values(
+                &schema_set,
+                &random_values_type,
+                &mut rand::rngs::StdRng::seed_from_u64(seed),
+            )
+            .unwrap();
+        let json =
+            AssetJson::save_asset_to_string(&schema_set, data_set.assets(), asset_id, true, None);
+
+        // The same seed produces the same assets
+        let mut other_data_set = DataSet::default();
+        let other_asset_id = other_data_set
+            .new_asset_with_random_values(
+                &schema_set,
+                &random_values_type,
+                &mut rand::rngs::StdRng::seed_from_u64(seed),
+            )
+            .unwrap();
+        assert_eq!(asset_id, other_asset_id);
+        assert_eq!(data_set.assets().len(), other_data_set.assets().len());
+        assert_eq!(
+            json,
+            AssetJson::save_asset_to_string(
+                &schema_set,
+                other_data_set.assets(),
+                other_asset_id,
+                true,
+                None
+            )
+        );
+
+        // Loading the saved asset gives back the same asset
+        let mut edit_contexts = DenseSlotMap::<EditContextKey, EditContext>::default();
+        let key = edit_contexts.insert_with_key(|key| {
+            EditContext::new(&project_config, key, schema_set.clone(), &undo_stack)
+        });
+        let loaded_asset_id = AssetJson::load_asset_from_string(
+            &mut edit_contexts[key],
+            &schema_set,
+            None,
+            AssetLocation::null(),
+            None,
+            &json,
+        )
+        .unwrap();
+        assert_eq!(loaded_asset_id, asset_id);
+
+        let loaded_data_set = edit_contexts[key].data_set();
+        assert_eq!(
+            json,
+            AssetJson::save_asset_to_string(
+                &schema_set,
+                loaded_data_set.assets(),
+                asset_id,
+                true,
+                None
+            )
+        );
+        assert_eq!(
+            data_set
+                .hash_object(asset_id, HashObjectMode::PropertiesOnly)
+                .unwrap(),
+            loaded_data_set
+                .hash_object(asset_id, HashObjectMode::PropertiesOnly)
+                .unwrap()
+        );
+    }
+}
+
+#[test]
+fn schema_set_defined_in_code_round_trips_an_asset() {
+    use crate::json_storage::AssetJson;
+
+    let schema_set = SchemaSet::builder()
+        .add_record("Transform")
+        .field("position", SchemaDefType::NamedType("Vec3".to_string()))
+        .field("scale", SchemaDefType::F32)
+        .add_record("Vec3")
+        .field("x", SchemaDefType::F32)
+        .field("y", SchemaDefType::F32)
+        .field("z", SchemaDefType::F32)
+        .build()
+        .unwrap();
+
+    // The reference to Vec3 is resolved, even though it was defined after Transform
+    let vec3_type = schema_set.find_named_type("Vec3").unwrap().clone();
+    let transform_type = schema_set
+        .find_named_type("Transform")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+    assert!(matches!(
+        transform_type.find_property_schema("position", schema_set.schemas()),
+        Some(Schema::Record(fingerprint)) if fingerprint == vec3_type.fingerprint()
+    ));
+
+    let mut data_set = DataSet::default();
+    let asset_id = data_set.new_asset(
+        AssetName::new("transform"),
+        AssetLocation::null(),
+        &transform_type,
+    );
+    data_set
+        .set_property_override(&schema_set, asset_id, "position.y", Some(Value::F32(2.0)))
+        .unwrap();
+    data_set
+        .set_property_override(&schema_set, asset_id, "scale", Some(Value::F32(3.0)))
+        .unwrap();
+    let json =
+        AssetJson::save_asset_to_string(&schema_set, data_set.assets(), asset_id, true, None);
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut edit_contexts = DenseSlotMap::<EditContextKey, EditContext>::default();
+    let key = edit_contexts.insert_with_key(|key| {
+        EditContext::new(&project_config, key, schema_set.clone(), &undo_stack)
+    });
+    let loaded_asset_id = AssetJson::load_asset_from_string(
+        &mut edit_contexts[key],
+        &schema_set,
+        None,
+        AssetLocation::null(),
+        None,
+        &json,
+    )
+    .unwrap();
+    assert_eq!(loaded_asset_id, asset_id);
+
+    let edit_context = &edit_contexts[key];
+    assert_eq!(
+        edit_context
+            .resolve_property(asset_id, "position.y")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        2.0
+    );
+    assert_eq!(
+        edit_context
+            .resolve_property(asset_id, "scale")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        3.0
+    );
+}
+
+#[test]
+fn asset_by_source_file_follows_import_info() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let project_config = default_project_config();
+    let source_file = |path: &str| {
+        CanonicalPathReference::new(
+            &project_config,
+            "textures".to_string(),
+            path.to_string(),
+            ImportableName::default(),
+        )
+    };
+    let import_info = |path: &str| {
+        ImportInfo::new(
+            ImporterId(Uuid::new_v4()),
+            source_file(path),
+            Default::default(),
+            0,
+            0,
+            0,
+            0,
+        )
+    };
+
+    // Many assets that share a few source files, like textures referenced by many materials, are
+    // still found one asset per file
+    let mut data_set = DataSet::default();
+    let mut imported_assets = HashMap::default();
+    for i in 0..100 {
+        let path = format!("texture_{}.png", i % 10);
+        if data_set.asset_by_source_file(&source_file(&path)).is_none() {
+            let asset_id = data_set.new_asset(AssetName::new(&path), asset_location(), &vec3_type);
+            data_set
+                .set_import_info(asset_id, import_info(&path))
+                .unwrap();
+            imported_assets.insert(path, asset_id);
+        }
+    }
+    assert_eq!(data_set.asset_count(), 10);
+    for (path, asset_id) in &imported_assets {
+        assert_eq!(
+            data_set.asset_by_source_file(&source_file(path)),
+            Some(*asset_id)
+        );
+    }
+
+    // Changing the import info moves the asset to the new source file
+    let asset_id = imported_assets["texture_0.png"];
+    data_set
+        .set_import_info(asset_id, import_info("moved.png"))
+        .unwrap();
+    assert_eq!(
+        data_set.asset_by_source_file(&source_file("texture_0.png")),
+        None
+    );
+    assert_eq!(
+        data_set.asset_by_source_file(&source_file("moved.png")),
+        Some(asset_id)
+    );
+
+    // A duplicate shares the source file, deleting either one leaves the other findable
+    let duplicate_asset_id = data_set.duplicate_asset(asset_id, &schema_set).unwrap();
+    data_set.delete_asset(asset_id).unwrap();
+    assert_eq!(
+        data_set.asset_by_source_file(&source_file("moved.png")),
+        Some(duplicate_asset_id)
+    );
+    data_set.delete_asset(duplicate_asset_id).unwrap();
+    assert_eq!(
+        data_set.asset_by_source_file(&source_file("moved.png")),
+        None
+    );
+
+    // Remapped IDs are returned
+    let old_asset_id = imported_assets["texture_1.png"];
+    let new_asset_id = AssetId::from_uuid(Uuid::new_v4());
+    let mut mapping = HashMap::default();
+    mapping.insert(old_asset_id, new_asset_id);
+    data_set.remap_ids(&mapping).unwrap();
+    assert_eq!(
+        data_set.asset_by_source_file(&source_file("texture_1.png")),
+        Some(new_asset_id)
+    );
+}
+
+#[test]
+fn asset_with_unknown_schema_is_skipped_and_kept_on_disk() {
+    use crate::json_storage::AssetJson;
+    use crate::{DataSource, FileSystemIdBasedDataSource, PathNode, PathNodeRoot};
+    use hydrate_base::uuid_path::uuid_to_path;
+    use hydrate_pipeline::ImportJobToQueue;
+
+    // The project was saved with a plugin type that the editor no longer has
+    let build_schema_set = |include_plugin_type: bool| {
+        let mut linker = SchemaLinker::default();
+        PathNode::register_schema(&mut linker);
+        PathNodeRoot::register_schema(&mut linker);
+        linker
+            .register_record_type(
+                "Known",
+                Uuid::parse_str("6d1a1a8c-67c4-4a55-a1fa-c87f0ef6a7b4").unwrap(),
+                |builder| {
+                    builder.add_f32(
+                        "x",
+                        Uuid::parse_str("0b8f2d6e-1f8b-4fd1-9a55-bb0cc4a0e2f5").unwrap(),
+                    );
+                },
+            )
+            .unwrap();
+        if include_plugin_type {
+            linker
+                .register_record_type(
+                    "FromPlugin",
+                    Uuid::parse_str("f0c3bb16-7e49-4b8d-9b4b-0d5a1c5e2c61").unwrap(),
+                    |builder| {
+                        builder.add_f32(
+                            "y",
+                            Uuid::parse_str("93e46a0e-3e43-4e0c-8f0e-6d4fb7c1b1a2").unwrap(),
+                        );
+                    },
+                )
+                .unwrap();
+        }
+
+        let mut schema_set_builder = SchemaSetBuilder::default();
+        schema_set_builder.add_linked_types(linker).unwrap();
+        schema_set_builder.build()
+    };
+    let saved_schema_set = build_schema_set(true);
+    let schema_set = build_schema_set(false);
+
+    let mut data_set = DataSet::default();
+    let known_asset_id = data_set.new_asset(
+        AssetName::new("known"),
+        AssetLocation::null(),
+        saved_schema_set
+            .find_named_type("Known")
+            .unwrap()
+            .as_record()
+            .unwrap(),
+    );
+    let plugin_asset_id = data_set.new_asset(
+        AssetName::new("from_plugin"),
+        AssetLocation::null(),
+        saved_schema_set
+            .find_named_type("FromPlugin")
+            .unwrap()
+            .as_record()
+            .unwrap(),
+    );
+    data_set
+        .set_property_override(
+            &saved_schema_set,
+            plugin_asset_id,
+            "y",
+            Some(Value::F32(5.0)),
+        )
+        .unwrap();
+
+    let root_path = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&root_path).unwrap();
+    let root_path = std::fs::canonicalize(&root_path).unwrap();
+    for asset_id in [known_asset_id, plugin_asset_id] {
+        let file_path = uuid_to_path(&root_path, asset_id.as_uuid(), "af");
+        std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+        let json = AssetJson::save_asset_to_string(
+            &saved_schema_set,
+            data_set.assets(),
+            asset_id,
+            false,
+            None,
+        );
+        std::fs::write(file_path, json).unwrap();
+    }
+    let plugin_asset_path = uuid_to_path(&root_path, plugin_asset_id.as_uuid(), "af");
+    let plugin_asset_json = std::fs::read_to_string(&plugin_asset_path).unwrap();
+
+    // Loading skips the asset with the unknown type rather than failing
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut edit_context = EditContext::new(
+        &project_config,
+        EditContextKey::default(),
+        schema_set,
+        &undo_stack,
+    );
+    let mut data_source =
+        FileSystemIdBasedDataSource::new(&root_path, &mut edit_context, AssetSourceId::new());
+    data_source.load_from_storage(
+        &project_config,
+        &mut edit_context,
+        &mut ImportJobToQueue::default(),
+    );
+    assert!(edit_context.has_asset(known_asset_id));
+    assert!(!edit_context.has_asset(plugin_asset_id));
+
+    // Saving after an edit leaves the skipped file as it was
+    edit_context
+        .set_property_override(known_asset_id, "x", Some(Value::F32(1.0)))
+        .unwrap();
+    data_source
+        .flush_to_storage(&project_config, &mut edit_context)
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&plugin_asset_path).unwrap(),
+        plugin_asset_json
+    );
+
+    std::fs::remove_dir_all(&root_path).unwrap();
+}
+
+#[test]
+fn read_struct_from_record() {
+    use hydrate_data::{read_struct, DataContainerRef, FromHydrateRecord};
+
+    #[derive(FromHydrateRecord, Debug, PartialEq)]
+    struct Transform {
+        position: [f32; 3],
+        scale: [f32; 3],
+    }
+
+    #[derive(FromHydrateRecord, Debug, PartialEq)]
+    struct Object {
+        #[hydrate(rename = "object_name")]
+        name: String,
+        visible: bool,
+        transform: Transform,
+        mesh: AssetId,
+    }
+
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("Transform", Uuid::new_v4(), |builder| {
+            builder.add_named_type("position", Uuid::new_v4(), "Vec3");
+            builder.add_named_type("scale", Uuid::new_v4(), "Vec3");
+        })
+        .unwrap();
+    linker
+        .register_record_type("Object", Uuid::new_v4(), |builder| {
+            builder.add_string("object_name", Uuid::new_v4());
+            builder.add_boolean("visible", Uuid::new_v4());
+            builder.add_named_type("transform", Uuid::new_v4(), "Transform");
+            builder.add_reference("mesh", Uuid::new_v4(), "Transform");
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let object_type = schema_set
+        .find_named_type("Object")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut data_set = DataSet::default();
+    let mesh = AssetId::from_uuid(Uuid::new_v4());
+    let object = data_set.new_asset(AssetName::new("object"), asset_location(), &object_type);
+    for (path, value) in [
+        ("object_name", Value::String(Arc::new("crate".to_string()))),
+        ("visible", Value::Boolean(true)),
+        ("transform.position.x", Value::F32(1.0)),
+        ("transform.position.y", Value::F32(2.0)),
+        ("transform.position.z", Value::F32(3.0)),
+        ("mesh", Value::AssetRef(mesh)),
+    ] {
+        data_set
+            .set_property_override(&schema_set, object, path, Some(value))
+            .unwrap();
+    }
+
+    // Unset properties read as their defaults
+    let read_object: Object = read_struct(DataContainerRef::from_dataset(
+        &data_set,
+        &schema_set,
+        object,
+    ))
+    .unwrap();
+    assert_eq!(
+        read_object,
+        Object {
+            name: "crate".to_string(),
+            visible: true,
+            transform: Transform {
+                position: [1.0, 2.0, 3.0],
+                scale: [0.0, 0.0, 0.0],
+            },
+            mesh,
+        }
+    );
+
+    // Reading a struct from a record of a different shape fails rather than panicking
+    let transform_type = schema_set
+        .find_named_type("Transform")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+    let transform = data_set.new_asset(
+        AssetName::new("transform"),
+        asset_location(),
+        &transform_type,
+    );
+    assert!(read_struct::<Object>(DataContainerRef::from_dataset(
+        &data_set,
+        &schema_set,
+        transform
+    ))
+    .is_err());
+}
+
+#[test]
+fn field_refs_borrow_data_set_through_prototype_chain() {
+    use hydrate_data::{DataContainerRef, F32FieldRef, FieldRef, StringFieldRef};
+    use hydrate_schema::PropertyPath;
+
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("Material", Uuid::new_v4(), |builder| {
+            builder.add_string("name", Uuid::new_v4());
+            builder.add_named_type("base_color", Uuid::new_v4(), "Vec3");
+            builder.add_f32("roughness", Uuid::new_v4());
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let material_type = schema_set
+        .find_named_type("Material")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut data_set = DataSet::default();
+    let prototype = data_set.new_asset(
+        AssetName::new("prototype"),
+        asset_location(),
+        &material_type,
+    );
+    let material = data_set
+        .new_asset_from_prototype(AssetName::new("material"), asset_location(), prototype)
+        .unwrap();
+    for (asset_id, path, value) in [
+        (
+            prototype,
+            "name",
+            Value::String(Arc::new("brick".to_string())),
+        ),
+        (prototype, "roughness", Value::F32(0.75)),
+        (material, "base_color.x", Value::F32(0.5)),
+    ] {
+        data_set
+            .set_property_override(&schema_set, asset_id, path, Some(value))
+            .unwrap();
+    }
+
+    // The readers borrow the data set, the material is never copied into a SingleObject
+    let root = PropertyPath::default();
+    let container = DataContainerRef::from_dataset(&data_set, &schema_set, material);
+    let name = StringFieldRef::new(root.push("name"), container.clone());
+    let roughness = F32FieldRef::new(root.push("roughness"), container.clone());
+    let base_color_x = F32FieldRef::new(root.push("base_color.x"), container.clone());
+    let base_color_y = F32FieldRef::new(root.push("base_color.y"), container);
+
+    assert_eq!(&*name.get().unwrap(), "brick");
+    assert_eq!(roughness.get().unwrap(), 0.75);
+    assert_eq!(base_color_x.get().unwrap(), 0.5);
+    assert_eq!(base_color_y.get().unwrap(), 0.0);
+}
+
+#[test]
+fn import_info_is_available_from_editor_model() {
+    use crate::{EditorModel, PathNode, PathNodeRoot};
+    use hydrate_pipeline::NamePathPair;
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let source_root = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&source_root).unwrap();
+    let source_root = std::fs::canonicalize(&source_root).unwrap();
+    std::fs::write(source_root.join("model.gltf"), "").unwrap();
+    std::fs::write(source_root.join("other.gltf"), "").unwrap();
+
+    let mut project_config = default_project_config();
+    project_config.source_file_locations.push(NamePathPair {
+        name: "art".to_string(),
+        path: source_root.clone(),
+    });
+    let mut editor_model = EditorModel::new(project_config.clone(), schema_set);
+
+    // Import two meshes from one file and one from another, the way an importer records them
+    let importer_id = ImporterId(Uuid::new_v4());
+    let mut import = |file_name: &str, importable_name: &str| {
+        let edit_context = editor_model.root_edit_context_mut();
+        let asset_id = edit_context.new_asset(
+            &AssetName::new(importable_name),
+            &asset_location(),
+            &vec3_type,
+        );
+        let source_file = CanonicalPathReference::new(
+            &project_config,
+            "".to_string(),
+            source_root.join(file_name).to_string_lossy().to_string(),
+            ImportableName::new(importable_name.to_string()),
+        );
+        edit_context
+            .set_import_info(
+                asset_id,
+                ImportInfo::new(importer_id, source_file, Default::default(), 1234, 0, 0, 0),
+            )
+            .unwrap();
+        asset_id
+    };
+    let mesh_a = import("model.gltf", "mesh_a");
+    let mesh_b = import("model.gltf", "mesh_b");
+    let other_mesh = import("other.gltf", "mesh");
+
+    let import_info = editor_model.import_info(mesh_a).unwrap();
+    assert_eq!(import_info.importer_id(), importer_id);
+    assert_eq!(import_info.source_file().namespace(), "art");
+    assert_eq!(import_info.source_file().path(), "model.gltf");
+    assert_eq!(import_info.source_file_modified_timestamp(), 1234);
+
+    let mut expected = vec![mesh_a, mesh_b];
+    expected.sort();
+    assert_eq!(
+        editor_model.assets_imported_from(&source_root.join("model.gltf")),
+        expected
+    );
+    assert_eq!(
+        editor_model.assets_imported_from(&source_root.join("other.gltf")),
+        vec![other_mesh]
+    );
+
+    // Assets that were not imported have no import info
+    let not_imported = editor_model.root_edit_context_mut().new_asset(
+        &AssetName::new("not_imported"),
+        &asset_location(),
+        &vec3_type,
+    );
+    assert!(editor_model.import_info(not_imported).is_none());
+
+    std::fs::remove_dir_all(&source_root).unwrap();
+}
+
+#[test]
+fn failed_imported_asset_creation_leaves_no_partial_asset() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    // A record type that is not part of the edit context's schema set
+    let mut other_linker = SchemaLinker::default();
+    other_linker
+        .register_record_type("Unregistered", Uuid::new_v4(), |builder| {
+            builder.add_f32("value", Uuid::new_v4());
+        })
+        .unwrap();
+    let mut other_schema_set_builder = SchemaSetBuilder::default();
+    other_schema_set_builder
+        .add_linked_types(other_linker)
+        .unwrap();
+    let unregistered_type = other_schema_set_builder
+        .build()
+        .find_named_type("Unregistered")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut db = EditContext::new(
+        &project_config,
+        EditContextKey::default(),
+        schema_set.clone(),
+        &undo_stack,
+    );
+
+    let source_file = |path: &str| {
+        CanonicalPathReference::new(
+            &project_config,
+            "textures".to_string(),
+            path.to_string(),
+            ImportableName::default(),
+        )
+    };
+    let import_info = |path: &str| {
+        ImportInfo::new(
+            ImporterId(Uuid::new_v4()),
+            source_file(path),
+            Default::default(),
+            0,
+            0,
+            0,
+            0,
+        )
+    };
+
+    // The import info is available as soon as the asset exists
+    let folder = db.new_asset(
+        &AssetName::new("folder"),
+        &AssetLocation::null(),
+        &vec3_type,
+    );
+    let imported = db
+        .new_imported_asset(
+            &AssetName::new("imported"),
+            &AssetLocation::new(folder),
+            &vec3_type,
+            import_info("imported.png"),
+        )
+        .unwrap();
+    assert!(db.import_info(imported).is_some());
+    assert_eq!(
+        db.data_set()
+            .asset_by_source_file(&source_file("imported.png")),
+        Some(imported)
+    );
+    assert_eq!(db.data_set().asset_count(), 2);
+
+    // None of these create anything, not even an asset without import info
+    assert!(db
+        .new_imported_asset(
+            &AssetName::new("missing_location"),
+            &AssetLocation::new(AssetId::from_uuid(Uuid::new_v4())),
+            &vec3_type,
+            import_info("missing_location.png"),
+        )
+        .is_err());
+    assert!(db
+        .new_imported_asset(
+            &AssetName::new("unregistered_schema"),
+            &AssetLocation::null(),
+            &unregistered_type,
+            import_info("unregistered_schema.png"),
+        )
+        .is_err());
+    assert!(db
+        .new_imported_asset_with_id(
+            imported,
+            &AssetName::new("duplicate_id"),
+            &AssetLocation::null(),
+            &vec3_type,
+            import_info("duplicate_id.png"),
+        )
+        .is_err());
+
+    assert_eq!(db.data_set().asset_count(), 2);
+    for path in [
+        "missing_location.png",
+        "unregistered_schema.png",
+        "duplicate_id.png",
+    ] {
+        assert!(db
+            .data_set()
+            .asset_by_source_file(&source_file(path))
+            .is_none());
+    }
+    assert_eq!(
+        db.data_set()
+            .asset_by_source_file(&source_file("imported.png")),
+        Some(imported)
+    );
+    assert_eq!(
+        db.asset_name(imported).unwrap(),
+        &AssetName::new("imported")
+    );
+}
+
+#[test]
+fn orphaned_assets_are_reported_and_placed_at_root() {
+    use crate::{
+        AssetPathCache, EditorModel, LocationTree, PathNode, PathNodeRoot, TreeBuildWarning,
+    };
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut editor_model = EditorModel::new(default_project_config(), schema_set);
+    let path_node_root_type = editor_model
+        .path_node_root_schema()
+        .as_record()
+        .unwrap()
+        .clone();
+    let path_node_type = editor_model.path_node_schema().as_record().unwrap().clone();
+
+    let edit_context = editor_model.root_edit_context_mut();
+    let root = edit_context.new_asset(
+        &AssetName::new("project"),
+        &AssetLocation::null(),
+        &path_node_root_type,
+    );
+    let folder = edit_context.new_asset(
+        &AssetName::new("folder"),
+        &AssetLocation::new(root),
+        &path_node_type,
+    );
+
+    // A folder whose parent was deleted, and an asset in it
+    let missing_path_node_id = AssetId::from_uuid(Uuid::new_v4());
+    let lost_folder = edit_context.new_asset(
+        &AssetName::new("lost_folder"),
+        &AssetLocation::new(missing_path_node_id),
+        &path_node_type,
+    );
+    let asset_in_lost_folder = edit_context.new_asset(
+        &AssetName::new("in_lost_folder"),
+        &AssetLocation::new(lost_folder),
+        &vec3_type,
+    );
+
+    // An asset whose folder was deleted
+    let orphaned_asset = edit_context.new_asset(
+        &AssetName::new("orphaned"),
+        &AssetLocation::new(missing_path_node_id),
+        &vec3_type,
+    );
+
+    let asset_path_cache = AssetPathCache::build(&editor_model).unwrap();
+    let mut warnings = asset_path_cache.warnings().to_vec();
+    warnings.sort_by_key(|x| match x {
+        TreeBuildWarning::OrphanedAsset { asset_id, .. } => *asset_id,
+        _ => unreachable!(),
+    });
+    let mut expected_warnings = vec![
+        TreeBuildWarning::OrphanedAsset {
+            asset_id: lost_folder,
+            missing_path_node_id,
+        },
+        TreeBuildWarning::OrphanedAsset {
+            asset_id: orphaned_asset,
+            missing_path_node_id,
+        },
+    ];
+    expected_warnings.sort_by_key(|x| match x {
+        TreeBuildWarning::OrphanedAsset { asset_id, .. } => *asset_id,
+        _ => unreachable!(),
+    });
+    assert_eq!(warnings, expected_warnings);
+
+    // Healthy paths are unaffected, the orphaned ones start at the root
+    assert_eq!(
+        editor_model.asset_path(folder, &asset_path_cache).unwrap(),
+        AssetPath::new_root("project").join("folder")
+    );
+    assert_eq!(
+        editor_model
+            .asset_path(lost_folder, &asset_path_cache)
+            .unwrap(),
+        AssetPath::new("db:/lost_folder")
+    );
+    assert_eq!(
+        editor_model
+            .asset_path(asset_in_lost_folder, &asset_path_cache)
+            .unwrap(),
+        AssetPath::new("db:/lost_folder/in_lost_folder")
+    );
+    assert_eq!(
+        editor_model
+            .asset_path(orphaned_asset, &asset_path_cache)
+            .unwrap(),
+        AssetPath::new("db:/orphaned")
+    );
+
+    // The orphaned folder is a root of the location tree so its contents can still be browsed
+    let location_tree = LocationTree::build(&editor_model, &asset_path_cache);
+    assert!(location_tree
+        .root_nodes
+        .values()
+        .any(|x| x.location == AssetLocation::new(lost_folder)));
+}
+
+#[test]
+fn export_closure_and_import_bundle_into_another_project() {
+    use crate::{EditorModel, ExportBundle, PathNode, PathNodeRoot};
+    use hydrate_pipeline::{read_import_data_bytes, write_import_data_bytes};
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    linker
+        .register_record_type("Texture", Uuid::new_v4(), |builder| {
+            builder.add_u32("width", Uuid::new_v4());
+        })
+        .unwrap();
+    linker
+        .register_record_type("Material", Uuid::new_v4(), |builder| {
+            builder.add_reference("color_texture", Uuid::new_v4(), "Texture");
+            builder.add_reference("normal_texture", Uuid::new_v4(), "Texture");
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let texture_type = schema_set
+        .find_named_type("Texture")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+    let material_type = schema_set
+        .find_named_type("Material")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let project_config = |import_data_path: &std::path::Path| {
+        let mut project_config = default_project_config();
+        project_config.import_data_path = import_data_path.to_path_buf();
+        project_config
+    };
+    let temp_path = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    let source_project_config = project_config(&temp_path.join("source"));
+    let target_project_config = project_config(&temp_path.join("target"));
+
+    //
+    // A material that references two textures, one of which has import data
+    //
+    let mut source_model = EditorModel::new(source_project_config.clone(), schema_set.clone());
+    let edit_context = source_model.root_edit_context_mut();
+    let mut new_texture = |name: &str, width: u32| {
+        let texture =
+            edit_context.new_asset(&AssetName::new(name), &asset_location(), &texture_type);
+        edit_context
+            .set_property_override(texture, "width", Some(Value::U32(width)))
+            .unwrap();
+        texture
+    };
+    let color_texture = new_texture("color", 256);
+    let normal_texture = new_texture("normal", 512);
+    let material = edit_context.new_asset(
+        &AssetName::new("material"),
+        &asset_location(),
+        &material_type,
+    );
+    edit_context
+        .set_property_override(
+            material,
+            "color_texture",
+            Some(Value::AssetRef(color_texture)),
+        )
+        .unwrap();
+    edit_context
+        .set_property_override(
+            material,
+            "normal_texture",
+            Some(Value::AssetRef(normal_texture)),
+        )
+        .unwrap();
+    edit_context
+        .set_import_info(
+            color_texture,
+            ImportInfo::new(
+                ImporterId(Uuid::new_v4()),
+                CanonicalPathReference::new(
+                    &source_project_config,
+                    "".to_string(),
+                    "color.png".to_string(),
+                    ImportableName::default(),
+                ),
+                Default::default(),
+                0,
+                0,
+                0,
+                0,
+            ),
+        )
+        .unwrap();
+    write_import_data_bytes(&source_project_config, color_texture, b"color import data").unwrap();
+
+    // Unrelated assets are not exported
+    source_model.root_edit_context_mut().new_asset(
+        &AssetName::new("unrelated"),
+        &asset_location(),
+        &texture_type,
+    );
+
+    let bundle = source_model.export_closure(material).unwrap();
+    assert_eq!(bundle.assets().len(), 3);
+
+    let mut bundle_bytes = Vec::default();
+    bundle.write(&mut bundle_bytes).unwrap();
+    let bundle = ExportBundle::read(&mut std::io::Cursor::new(bundle_bytes)).unwrap();
+
+    //
+    // Import into a fresh project, the references between the assets still resolve
+    //
+    let mut target_model = EditorModel::new(target_project_config.clone(), schema_set);
+    let imported_material = target_model
+        .import_bundle(&bundle, asset_location())
+        .unwrap();
+    assert_ne!(imported_material, material);
+
+    let edit_context = target_model.root_edit_context();
+    assert_eq!(edit_context.assets().len(), 3);
+    let imported_color_texture = edit_context
+        .resolve_property(imported_material, "color_texture")
+        .unwrap()
+        .as_asset_ref()
+        .unwrap();
+    let imported_normal_texture = edit_context
+        .resolve_property(imported_material, "normal_texture")
+        .unwrap()
+        .as_asset_ref()
+        .unwrap();
+    assert_ne!(imported_color_texture, color_texture);
+    assert_eq!(
+        edit_context
+            .resolve_property(imported_color_texture, "width")
+            .unwrap()
+            .as_u32()
+            .unwrap(),
+        256
+    );
+    assert_eq!(
+        edit_context
+            .resolve_property(imported_normal_texture, "width")
+            .unwrap()
+            .as_u32()
+            .unwrap(),
+        512
+    );
+    assert_eq!(
+        edit_context.asset_location(imported_material),
+        Some(asset_location())
+    );
+
+    // Import data came along with the texture
+    assert!(edit_context.import_info(imported_color_texture).is_some());
+    assert_eq!(
+        read_import_data_bytes(&target_project_config, imported_color_texture)
+            .unwrap()
+            .unwrap(),
+        b"color import data"
+    );
+
+    // Importing again creates another copy rather than colliding with the first
+    let imported_again = target_model
+        .import_bundle(&bundle, asset_location())
+        .unwrap();
+    assert_ne!(imported_again, imported_material);
+    assert_eq!(target_model.root_edit_context().assets().len(), 6);
+
+    std::fs::remove_dir_all(&temp_path).unwrap();
+}
+
+#[test]
+fn schema_set_iter_sorted_is_deterministic() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    create_reference_schema(&mut linker).unwrap();
+    for name in ["Zebra", "Apple", "Mango"] {
+        linker
+            .register_record_type(name, Uuid::new_v4(), |builder| {
+                builder.add_u32("value", Uuid::new_v4());
+            })
+            .unwrap();
+    }
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let first: Vec<_> = schema_set.iter_sorted().map(|x| x.fingerprint()).collect();
+    let second: Vec<_> = schema_set.iter_sorted().map(|x| x.fingerprint()).collect();
+    assert_eq!(first, second);
+    assert_eq!(first.len(), schema_set.schemas().len());
+
+    let names: Vec<_> = schema_set.iter_sorted().map(|x| x.name()).collect();
+    let mut sorted_names = names.clone();
+    sorted_names.sort();
+    assert_eq!(names, sorted_names);
+}
+
+#[test]
+fn find_and_merge_duplicate_imports() {
+    use crate::{EditorModel, PathNode, PathNodeRoot};
+    use hydrate_pipeline::NamePathPair;
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+    create_reference_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+    let reference_type = schema_set
+        .find_named_type("HasReference")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let source_root = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&source_root).unwrap();
+    let source_root = std::fs::canonicalize(&source_root).unwrap();
+    std::fs::write(source_root.join("model.gltf"), "").unwrap();
+
+    let mut project_config = default_project_config();
+    project_config.source_file_locations.push(NamePathPair {
+        name: "art".to_string(),
+        path: source_root.clone(),
+    });
+    let mut editor_model = EditorModel::new(project_config.clone(), schema_set);
+
+    // The same file imported twice, stored with different spellings of its path
+    let importer_id = ImporterId(Uuid::new_v4());
+    let mut import = |path: &str| {
+        let edit_context = editor_model.root_edit_context_mut();
+        let asset_id =
+            edit_context.new_asset(&AssetName::new("mesh"), &asset_location(), &vec3_type);
+        let source_file = CanonicalPathReference::new(
+            &project_config,
+            "art".to_string(),
+            path.to_string(),
+            ImportableName::new("mesh".to_string()),
+        );
+        edit_context
+            .set_import_info(
+                asset_id,
+                ImportInfo::new(importer_id, source_file, Default::default(), 0, 0, 0, 0),
+            )
+            .unwrap();
+        asset_id
+    };
+    let first_import = import("model.gltf");
+    let second_import = import("./model.gltf");
+
+    let edit_context = editor_model.root_edit_context_mut();
+    let referencing_asset =
+        edit_context.new_asset(&AssetName::new("user"), &asset_location(), &reference_type);
+    edit_context
+        .set_property_override(
+            referencing_asset,
+            "reference",
+            Some(Value::AssetRef(second_import)),
+        )
+        .unwrap();
+
+    let mut expected = vec![first_import, second_import];
+    expected.sort();
+    assert_eq!(
+        editor_model.find_duplicate_imports(),
+        vec![expected.clone()]
+    );
+
+    editor_model
+        .merge_duplicates(&expected, first_import)
+        .unwrap();
+    assert!(editor_model.find_duplicate_imports().is_empty());
+    let edit_context = editor_model.root_edit_context();
+    assert!(!edit_context.has_asset(second_import));
+    assert_eq!(
+        edit_context
+            .resolve_property(referencing_asset, "reference")
+            .unwrap()
+            .as_asset_ref()
+            .unwrap(),
+        first_import
+    );
+
+    // Merging is a single undo step, undoing it brings back the duplicate and its reference
+    assert_eq!(editor_model.undo_history().len(), 1);
+    editor_model.undo().unwrap();
+    let edit_context = editor_model.root_edit_context();
+    assert!(edit_context.has_asset(second_import));
+    assert_eq!(
+        edit_context
+            .resolve_property(referencing_asset, "reference")
+            .unwrap()
+            .as_asset_ref()
+            .unwrap(),
+        second_import
+    );
+
+    std::fs::remove_dir_all(&source_root).unwrap();
+}
+
+const ICON_SCHEMA: &str = r#"[
+  {
+    "type": "record",
+    "name": "IconMesh",
+    "uuid": "3d5f7a9c-1b2e-4c4d-8e6f-0a1b2c3d4e01",
+    "icon": "mesh",
+    "fields": []
+  },
+  {
+    "type": "record",
+    "name": "IconMissing",
+    "uuid": "6a8c0e2f-4b5d-4f7a-9c1e-3b5d7f9a1c02",
+    "icon": "not-registered",
+    "fields": []
+  },
+  {
+    "type": "record",
+    "name": "IconNone",
+    "uuid": "9c1e3a5b-7d9f-4b1c-a3e5-6d8f0b2c4e03",
+    "fields": []
+  }
+]"#;
+
+#[test]
+fn schema_icon_resolves_against_registered_icons() {
+    use hydrate_pipeline::{ThumbnailImage, ThumbnailProviderRegistryBuilder};
+
+    let schema_dir = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&schema_dir).unwrap();
+    std::fs::write(schema_dir.join("icons.json"), ICON_SCHEMA).unwrap();
+    let mut linker = SchemaLinker::default();
+    linker.add_source_dir(&schema_dir, "**.json").unwrap();
+    std::fs::remove_dir_all(&schema_dir).unwrap();
+    linker
+        .register_record_type("IconBuilt", Uuid::new_v4(), |builder| {
+            builder.set_icon("mesh");
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let record = |name: &str| {
+        schema_set
+            .find_named_type(name)
+            .unwrap()
+            .as_record()
+            .unwrap()
+            .clone()
+    };
+
+    assert_eq!(record("IconMesh").markup().icon.as_deref(), Some("mesh"));
+    assert_eq!(record("IconBuilt").markup().icon.as_deref(), Some("mesh"));
+    assert_eq!(record("IconNone").markup().icon, None);
+
+    let mut registry_builder = ThumbnailProviderRegistryBuilder::default();
+    registry_builder.register_icon(
+        "mesh",
+        ThumbnailImage {
+            width: 1,
+            height: 1,
+            pixel_data: vec![255, 0, 0, 255],
+        },
+    );
+    let registry = registry_builder.build(&schema_set);
+
+    let icon = registry
+        .icon_for_asset(record("IconMesh").fingerprint())
+        .unwrap();
+    assert_eq!(icon.pixel_data, vec![255, 0, 0, 255]);
+    assert!(registry
+        .icon_for_asset(record("IconBuilt").fingerprint())
+        .is_some());
+    // Icons are not thumbnails, so no provider is registered for the type
+    assert!(!registry.has_provider_for_asset(record("IconMesh").fingerprint()));
+    assert!(registry
+        .icon_for_asset(record("IconMissing").fingerprint())
+        .is_none());
+    assert!(registry
+        .icon_for_asset(record("IconNone").fingerprint())
+        .is_none());
+}
+
+#[test]
+fn flush_pending_edits_does_not_save() {
+    use crate::{EditorModel, PathNode, PathNodeRoot};
+    use hydrate_pipeline::ImportJobToQueue;
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let source_root = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&source_root).unwrap();
+    let project_config = default_project_config();
+    let mut editor_model = EditorModel::new(project_config.clone(), schema_set);
+    let asset_source_id = editor_model.add_file_system_id_based_asset_source(
+        &project_config,
+        "data",
+        &source_root,
+        &mut ImportJobToQueue::default(),
+    );
+
+    let asset_id = editor_model.root_edit_context_mut().new_asset(
+        &AssetName::new("vec3"),
+        &AssetLocation::new(AssetId::from_uuid(*asset_source_id.uuid())),
+        &vec3_type,
+    );
+    editor_model.save_root_edit_context().unwrap();
+    assert!(!editor_model.any_edit_context_has_unsaved_changes());
+    let undo_step_count = editor_model.undo_history().len();
+
+    // An edit left in an open undo context, like typing into a text field
+    editor_model
+        .root_edit_context_mut()
+        .with_undo_context("set x", |edit_context| {
+            edit_context
+                .set_property_override(asset_id, "x", Some(Value::F32(5.0)))
+                .unwrap();
+            EndContextBehavior::AllowResume
+        });
+    assert_eq!(editor_model.undo_history().len(), undo_step_count);
+
+    editor_model.flush_pending_edits();
+    assert_eq!(editor_model.undo_history().len(), undo_step_count + 1);
+    assert_eq!(
+        editor_model
+            .root_edit_context()
+            .resolve_property(asset_id, "x")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        5.0
+    );
+
+    // Nothing was written, so the edit is still unsaved
+    assert!(editor_model.any_edit_context_has_unsaved_changes());
+    let pending_file_operations = editor_model.pending_file_operations();
+    assert_eq!(pending_file_operations.modify_operations[0].0, asset_id);
+
+    std::fs::remove_dir_all(&source_root).unwrap();
+}
+
+#[test]
+fn autosave_restores_unsaved_edits_after_crash() {
+    use crate::{EditorModel, PathNode, PathNodeRoot};
+    use hydrate_pipeline::ImportJobToQueue;
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let root_path = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    let source_root = root_path.join("data");
+    std::fs::create_dir_all(&source_root).unwrap();
+    let mut project_config = default_project_config();
+    project_config.job_data_path = root_path.join("job_data");
+
+    let open_editor_model = || {
+        let mut editor_model = EditorModel::new(project_config.clone(), schema_set.clone());
+        let asset_source_id = editor_model.add_file_system_id_based_asset_source(
+            &project_config,
+            "data",
+            &source_root,
+            &mut ImportJobToQueue::default(),
+        );
+        let location = AssetLocation::new(AssetId::from_uuid(*asset_source_id.uuid()));
+        (editor_model, location)
+    };
+
+    let (mut editor_model, location) = open_editor_model();
+    let edit_context = editor_model.root_edit_context_mut();
+    let modified = edit_context.new_asset(&AssetName::new("modified"), &location, &vec3_type);
+    let deleted = edit_context.new_asset(&AssetName::new("deleted"), &location, &vec3_type);
+    editor_model.save_root_edit_context().unwrap();
+
+    let edit_context = editor_model.root_edit_context_mut();
+    edit_context
+        .set_property_override(modified, "x", Some(Value::F32(5.0)))
+        .unwrap();
+    let created = edit_context.new_asset(&AssetName::new("created"), &location, &vec3_type);
+    edit_context.delete_asset(deleted).unwrap();
+
+    // Autosaving doesn't save, the edits are still unsaved
+    editor_model.autosave().unwrap();
+    assert!(editor_model.any_edit_context_has_unsaved_changes());
+    assert_eq!(
+        editor_model
+            .pending_file_operations()
+            .modify_operations
+            .len(),
+        1
+    );
+
+    // Simulate a crash by never saving. Reopening the project loads what is on disk.
+    drop(editor_model);
+    let (mut editor_model, location) = open_editor_model();
+    assert!(!editor_model.any_edit_context_has_unsaved_changes());
+    assert!(editor_model.root_edit_context().has_asset(deleted));
+    assert!(!editor_model.root_edit_context().has_asset(created));
+
+    let recovery = editor_model.read_autosave().unwrap().unwrap();
+    assert!(recovery.saved_at() <= std::time::SystemTime::now());
+    assert_eq!(recovery.deleted_assets(), &[deleted]);
+    editor_model.restore_autosave(&recovery).unwrap();
+
+    let edit_context = editor_model.root_edit_context();
+    assert_eq!(
+        edit_context
+            .resolve_property(modified, "x")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        5.0
+    );
+    assert!(!edit_context.has_asset(deleted));
+    // The data source's root asset has a new ID, restored assets are moved to it
+    assert_eq!(edit_context.asset_location(created), Some(location));
+    assert_eq!(edit_context.asset_location(modified), Some(location));
+
+    // The restored edits are unsaved until saved, and saving removes the recovery file
+    assert!(editor_model.any_edit_context_has_unsaved_changes());
+    editor_model.save_root_edit_context().unwrap();
+    assert!(!editor_model.any_edit_context_has_unsaved_changes());
+    assert!(editor_model.read_autosave().unwrap().is_none());
+
+    std::fs::remove_dir_all(&root_path).unwrap();
+}
+
+// Importer for the polling test, it only needs to be scanned
+#[derive(TypeUuid, Default)]
+#[uuid = "8d3e1f47-5b2a-4c69-9f0e-2a7c4b6d1e53"]
+struct Vec3Importer;
+
+impl hydrate_pipeline::Importer for Vec3Importer {
+    fn supported_file_extensions(&self) -> &[&'static str] {
+        &["vec3"]
+    }
+
+    fn scan_file(
+        &self,
+        context: hydrate_pipeline::ScanContext,
+    ) -> PipelineResult<()> {
+        let vec3_type = context
+            .schema_set
+            .find_named_type("Vec3")?
+            .as_record()?
+            .clone();
+        context.add_importable_with_record(ImportableName::default(), vec3_type)?;
+        Ok(())
+    }
+
+    fn import_file(
+        &self,
+        _context: hydrate_pipeline::ImportContext,
+    ) -> PipelineResult<()> {
+        Ok(())
+    }
+}
+
+// Data sources scan for changes in the background, so poll until the scan has been picked up
+fn poll_data_sources_to_completion(
+    editor_model: &mut crate::EditorModel,
+    import_job_to_queue: &mut hydrate_pipeline::ImportJobToQueue,
+) -> Vec<AssetLocation> {
+    let mut changed_locations = editor_model.poll_data_sources_for_changes(import_job_to_queue);
+    while editor_model.is_polling_data_sources() {
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        changed_locations
+            .append(&mut editor_model.poll_data_sources_for_changes(import_job_to_queue));
+    }
+    changed_locations
+}
+
+#[test]
+fn polling_path_based_source_picks_up_external_changes() {
+    use crate::json_storage::AssetJson;
+    use crate::{EditorModel, PathNode, PathNodeRoot};
+    use hydrate_pipeline::{ImportJobToQueue, ImporterRegistryBuilder};
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let source_root = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&source_root).unwrap();
+    let project_config = default_project_config();
+    let mut importer_registry = ImporterRegistryBuilder::default();
+    importer_registry.register_handler::<Vec3Importer>();
+    let mut editor_model = EditorModel::new(project_config.clone(), schema_set.clone());
+    let asset_source_id = editor_model.add_file_system_path_based_data_source(
+        &project_config,
+        "data",
+        &source_root,
+        &importer_registry.build(),
+        &mut ImportJobToQueue::default(),
+    );
+
+    // Nothing changed since loading
+    assert!(
+        poll_data_sources_to_completion(&mut editor_model, &mut ImportJobToQueue::default())
+            .is_empty()
+    );
+
+    // Another tool writes an asset into a new directory
+    let mut data_set = DataSet::default();
+    let asset_id = data_set.new_asset(
+        AssetName::new("external"),
+        AssetLocation::null(),
+        schema_set
+            .find_named_type("Vec3")
+            .unwrap()
+            .as_record()
+            .unwrap(),
+    );
+    data_set
+        .set_property_override(&schema_set, asset_id, "x", Some(Value::F32(3.0)))
+        .unwrap();
+    let asset_file_path = source_root.join("props").join("external.af");
+    std::fs::create_dir_all(asset_file_path.parent().unwrap()).unwrap();
+    std::fs::write(
+        &asset_file_path,
+        AssetJson::save_asset_to_string(&schema_set, data_set.assets(), asset_id, true, None),
+    )
+    .unwrap();
+
+    let changed_locations =
+        poll_data_sources_to_completion(&mut editor_model, &mut ImportJobToQueue::default());
+    assert!(
+        changed_locations.contains(&AssetLocation::new(AssetId::from_uuid(
+            *asset_source_id.uuid()
+        )))
+    );
+    let root_edit_context = editor_model.root_edit_context();
+    assert_eq!(
+        root_edit_context
+            .resolve_property(asset_id, "x")
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        3.0
+    );
+    let props_path_node_id = root_edit_context
+        .asset_location(asset_id)
+        .unwrap()
+        .path_node_id();
+    assert_eq!(
+        root_edit_context
+            .asset_name(props_path_node_id)
+            .unwrap()
+            .as_string()
+            .unwrap(),
+        "props"
+    );
+    assert!(!editor_model.any_edit_context_has_unsaved_changes());
+
+    // Removing the directory removes the asset and its path node
+    std::fs::remove_dir_all(source_root.join("props")).unwrap();
+    poll_data_sources_to_completion(&mut editor_model, &mut ImportJobToQueue::default());
+    assert!(!editor_model.root_edit_context().has_asset(asset_id));
+    assert!(!editor_model
+        .root_edit_context()
+        .has_asset(props_path_node_id));
+
+    // A new source file is imported
+    let source_file_path = source_root.join("imported.vec3");
+    std::fs::write(&source_file_path, "1").unwrap();
+    let mut import_job_to_queue = ImportJobToQueue::default();
+    poll_data_sources_to_completion(&mut editor_model, &mut import_job_to_queue);
+    assert_eq!(import_job_to_queue.import_job_source_files.len(), 1);
+    assert_eq!(
+        import_job_to_queue.import_job_source_files[0].source_file_path,
+        source_file_path
+    );
+
+    // Writing its meta file while scanning is not picked up as a change
+    let mut import_job_to_queue = ImportJobToQueue::default();
+    assert!(
+        poll_data_sources_to_completion(&mut editor_model, &mut import_job_to_queue).is_empty()
+    );
+    assert!(import_job_to_queue.import_job_source_files.is_empty());
+
+    // Changing the source file outside the editor re-imports it
+    std::fs::write(&source_file_path, "1 2").unwrap();
+    let mut import_job_to_queue = ImportJobToQueue::default();
+    poll_data_sources_to_completion(&mut editor_model, &mut import_job_to_queue);
+    assert_eq!(import_job_to_queue.import_job_source_files.len(), 1);
+    assert_eq!(
+        import_job_to_queue.import_job_source_files[0].source_file_path,
+        source_file_path
+    );
+
+    // Files written by saving are not picked up as changes
+    let root_location = AssetLocation::new(AssetId::from_uuid(*asset_source_id.uuid()));
+    let saved_asset_id = editor_model.root_edit_context_mut().new_asset(
+        &AssetName::new("saved"),
+        &root_location,
+        schema_set
+            .find_named_type("Vec3")
+            .unwrap()
+            .as_record()
+            .unwrap(),
+    );
+    editor_model.save_root_edit_context().unwrap();
+    assert!(source_root.join("saved.af").exists());
+    assert!(
+        poll_data_sources_to_completion(&mut editor_model, &mut ImportJobToQueue::default())
+            .is_empty()
+    );
+    assert!(editor_model.root_edit_context().has_asset(saved_asset_id));
+
+    std::fs::remove_dir_all(&source_root).unwrap();
+}
+
+// Opens a path-based data source at source_root that imports .vec3 files
+fn open_path_based_vec3_source(
+    project_config: &HydrateProjectConfiguration,
+    schema_set: &SchemaSet,
+    source_root: &Path,
+) -> (crate::EditorModel, AssetSourceId) {
+    let mut importer_registry = hydrate_pipeline::ImporterRegistryBuilder::default();
+    importer_registry.register_handler::<Vec3Importer>();
+    let mut editor_model = crate::EditorModel::new(project_config.clone(), schema_set.clone());
+    let asset_source_id = editor_model.add_file_system_path_based_data_source(
+        project_config,
+        "data",
+        source_root,
+        &importer_registry.build(),
+        &mut hydrate_pipeline::ImportJobToQueue::default(),
+    );
+    (editor_model, asset_source_id)
+}
+
+fn path_based_vec3_schema_set() -> SchemaSet {
+    let mut linker = SchemaLinker::default();
+    crate::PathNode::register_schema(&mut linker);
+    crate::PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    schema_set_builder.build()
+}
+
+// With fsync_on_save, files are written to a temporary file that is synced and renamed over the
+// original, so a rewritten file is a new file. Without it, files are overwritten in place.
+#[cfg(unix)]
+#[test]
+fn fsync_on_save_replaces_files_instead_of_overwriting_them() {
+    use hydrate_data::json_storage::MetaFileJson;
+    use std::os::unix::fs::MetadataExt;
+
+    let schema_set = path_based_vec3_schema_set();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+    let inode = |path: &Path| std::fs::metadata(path).unwrap().ino();
+
+    for fsync_on_save in [false, true] {
+        let source_root = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&source_root).unwrap();
+        std::fs::write(source_root.join("generated.vec3"), "1").unwrap();
+        let mut project_config = default_project_config();
+        project_config.fsync_on_save = fsync_on_save;
+        let (mut editor_model, asset_source_id) =
+            open_path_based_vec3_source(&project_config, &schema_set, &source_root);
+        let root_location = AssetLocation::new(AssetId::from_uuid(*asset_source_id.uuid()));
+
+        // Saving changes to an asset rewrites its file
+        let asset_id = editor_model.root_edit_context_mut().new_asset(
+            &AssetName::new("saved"),
+            &root_location,
+            &vec3_type,
+        );
+        editor_model.save_root_edit_context().unwrap();
+        let asset_file_path = source_root.join("saved.af");
+        let original_asset_file_inode = inode(&asset_file_path);
+        editor_model
+            .root_edit_context_mut()
+            .set_property_override(asset_id, "x", Some(Value::F32(1.0)))
+            .unwrap();
+        editor_model.save_root_edit_context().unwrap();
+        assert_eq!(
+            inode(&asset_file_path) != original_asset_file_inode,
+            fsync_on_save
+        );
+
+        // Persisting a generated asset rewrites the source file's meta file
+        let meta_file_path = source_root.join("generated.vec3.meta");
+        let original_meta_file_inode = inode(&meta_file_path);
+        let generated_asset_id =
+            *MetaFileJson::load_from_string(&std::fs::read_to_string(&meta_file_path).unwrap())
+                .past_id_assignments
+                .values()
+                .next()
+                .unwrap();
+        assert!(editor_model.is_generated_asset(generated_asset_id));
+        // Stand in for the queued import creating the asset
+        editor_model
+            .root_edit_context_mut()
+            .new_asset_with_id(
+                generated_asset_id,
+                &AssetName::new("generated"),
+                &root_location,
+                &vec3_type,
+            )
+            .unwrap();
+        editor_model
+            .persist_generated_asset(generated_asset_id)
+            .unwrap();
+        assert!(!editor_model.is_generated_asset(generated_asset_id));
+        let meta_file =
+            MetaFileJson::load_from_string(&std::fs::read_to_string(&meta_file_path).unwrap());
+        assert!(meta_file.persisted_assets.contains(&generated_asset_id));
+        assert_eq!(
+            inode(&meta_file_path) != original_meta_file_inode,
+            fsync_on_save
+        );
+
+        // No temporary files are left behind
+        assert_eq!(
+            globwalk::GlobWalkerBuilder::from_patterns(&source_root, &["**.tmp"])
+                .build()
+                .unwrap()
+                .count(),
+            0
+        );
+
+        std::fs::remove_dir_all(&source_root).unwrap();
+    }
+}
+
+#[test]
+fn saving_reports_write_errors() {
+    let schema_set = path_based_vec3_schema_set();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    for fsync_on_save in [false, true] {
+        let source_root = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&source_root).unwrap();
+        let mut project_config = default_project_config();
+        project_config.fsync_on_save = fsync_on_save;
+        let (mut editor_model, asset_source_id) =
+            open_path_based_vec3_source(&project_config, &schema_set, &source_root);
+        let root_location = AssetLocation::new(AssetId::from_uuid(*asset_source_id.uuid()));
+
+        // A directory where the asset file should go can't be written over
+        let asset_file_path = source_root.join("blocked.af");
+        std::fs::create_dir_all(&asset_file_path).unwrap();
+        editor_model.root_edit_context_mut().new_asset(
+            &AssetName::new("blocked"),
+            &root_location,
+            &vec3_type,
+        );
+        assert!(editor_model.save_root_edit_context().is_err());
+        assert!(editor_model.any_edit_context_has_unsaved_changes());
+        assert!(!source_root.join("blocked.af.tmp").exists());
+
+        // Saving succeeds once the file can be written
+        std::fs::remove_dir(&asset_file_path).unwrap();
+        editor_model.save_root_edit_context().unwrap();
+        assert!(!editor_model.any_edit_context_has_unsaved_changes());
+        assert!(asset_file_path.is_file());
+
+        std::fs::remove_dir_all(&source_root).unwrap();
+    }
+}
+
+#[test]
+fn relocated_data_paths_keep_build_results() {
+    use crate::{AssetPathCache, EditorModel, EditorModelWithCache, PathNode, PathNodeRoot};
+    use hydrate_base::uuid_path::uuid_to_path;
+    use hydrate_pipeline::AssetPluginRegistryBuilders;
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let root_path = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    let mut project_config = default_project_config();
+    project_config.import_data_path = root_path.join("import_data");
+    project_config.build_data_path = root_path.join("build_data");
+    project_config.job_data_path = root_path.join("job_data");
+    let import_data_file_path =
+        uuid_to_path(&project_config.import_data_path, Uuid::new_v4(), "if");
+    std::fs::create_dir_all(import_data_file_path.parent().unwrap()).unwrap();
+    std::fs::write(&import_data_file_path, "import data").unwrap();
+
+    let mut editor_model = EditorModel::new(project_config.clone(), schema_set.clone());
+    let path_node_root_type = editor_model
+        .path_node_root_schema()
+        .as_record()
+        .unwrap()
+        .clone();
+    let edit_context = editor_model.root_edit_context_mut();
+    let root = edit_context.new_asset(
+        &AssetName::new("project"),
+        &AssetLocation::null(),
+        &path_node_root_type,
+    );
+    let asset_id = edit_context.new_asset(
+        &AssetName::new("vec3"),
+        &AssetLocation::new(root),
+        &vec3_type,
+    );
+
+    let asset_path_cache = AssetPathCache::build(&editor_model).unwrap();
+    let mut asset_engine = AssetEngine::new(
+        &schema_set,
+        AssetPluginRegistryBuilders::new().finish(&schema_set),
+        &EditorModelWithCache {
+            asset_path_cache: &asset_path_cache,
+            editor_model: &mut editor_model,
+        },
+        &project_config,
+    );
+
+    let update = |asset_engine: &mut AssetEngine, editor_model: &mut EditorModel| {
+        asset_engine
+            .update(&mut EditorModelWithCache {
+                asset_path_cache: &asset_path_cache,
+                editor_model,
+            })
+            .unwrap()
+    };
+    let build_all = |asset_engine: &mut AssetEngine, editor_model: &mut EditorModel| {
+        run_build_to_completion(&mut *asset_engine, &asset_path_cache, &mut *editor_model);
+
+        // needs_build() is only refreshed when the engine next considers starting a build
+        update(&mut *asset_engine, &mut *editor_model);
+    };
+    // Each build writes a TOC
+    let build_count = |build_data_path: &std::path::Path| {
+        std::fs::read_dir(build_data_path.join("toc"))
+            .unwrap()
+            .count()
+    };
+
+    build_all(&mut asset_engine, &mut editor_model);
+    assert!(!asset_engine.needs_build());
+    assert_eq!(build_count(&project_config.build_data_path), 1);
+
+    let new_import_data_path = root_path.join("moved").join("import_data");
+    let new_build_data_path = root_path.join("moved").join("build_data");
+    asset_engine
+        .relocate(&new_import_data_path, &new_build_data_path)
+        .unwrap();
+
+    // Everything was moved
+    assert!(!project_config.import_data_path.exists());
+    assert!(!project_config.build_data_path.exists());
+    assert_eq!(
+        std::fs::read_to_string(
+            new_import_data_path.join(
+                import_data_file_path
+                    .strip_prefix(&project_config.import_data_path)
+                    .unwrap()
+            )
+        )
+        .unwrap(),
+        "import data"
+    );
+    assert_eq!(build_count(&new_build_data_path), 1);
+
+    // Nothing changed, so there is nothing to rebuild
+    update(&mut asset_engine, &mut editor_model);
+    assert!(!asset_engine.needs_build());
+
+    // Changes are built into the new location
+    editor_model
+        .root_edit_context_mut()
+        .set_property_override(asset_id, "x", Some(Value::F32(5.0)))
+        .unwrap();
+    update(&mut asset_engine, &mut editor_model);
+    assert!(asset_engine.needs_build());
+    build_all(&mut asset_engine, &mut editor_model);
+    assert!(!asset_engine.needs_build());
+    assert_eq!(build_count(&new_build_data_path), 2);
+    assert!(!project_config.build_data_path.exists());
+
+    drop(asset_engine);
+    std::fs::remove_dir_all(&root_path).unwrap();
+}
+
+#[test]
+fn diagnostic_sink_receives_diagnostics_logged_during_build() {
+    use crate::{AssetPathCache, EditorModel, EditorModelWithCache, PathNode, PathNodeRoot};
+    use hydrate_pipeline::{
+        AssetPluginRegistryBuilders, Diagnostic, DiagnosticSink, LogEventLevel,
+    };
+
+    #[derive(Default)]
+    struct WarningVec3Builder;
+
+    impl Builder for WarningVec3Builder {
+        fn asset_type(&self) -> &'static str {
+            "Vec3"
+        }
+
+        fn start_jobs(
+            &self,
+            context: BuilderContext,
+        ) -> PipelineResult<()> {
+            context.warn("vec3 is not normalized");
+            Ok(())
+        }
+    }
+
+    struct WarningVec3Plugin;
+
+    impl AssetPlugin for WarningVec3Plugin {
+        fn setup(context: AssetPluginSetupContext) {
+            context
+                .builder_registry
+                .register_handler::<WarningVec3Builder>();
+        }
+    }
+
+    struct TestSink(Arc<Mutex<Vec<Diagnostic>>>);
+
+    impl DiagnosticSink for TestSink {
+        fn on_diagnostic(
+            &mut self,
+            diagnostic: Diagnostic,
+        ) {
+            self.0.lock().unwrap().push(diagnostic);
+        }
+    }
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let root_path = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    let mut project_config = default_project_config();
+    project_config.import_data_path = root_path.join("import_data");
+    project_config.build_data_path = root_path.join("build_data");
+    project_config.job_data_path = root_path.join("job_data");
+
+    let mut editor_model = EditorModel::new(project_config.clone(), schema_set.clone());
+    let path_node_root_type = editor_model
+        .path_node_root_schema()
+        .as_record()
+        .unwrap()
+        .clone();
+    let edit_context = editor_model.root_edit_context_mut();
+    let root = edit_context.new_asset(
+        &AssetName::new("project"),
+        &AssetLocation::null(),
+        &path_node_root_type,
+    );
+    let asset_id = edit_context.new_asset(
+        &AssetName::new("vec3"),
+        &AssetLocation::new(root),
+        &vec3_type,
+    );
+
+    let asset_path_cache = AssetPathCache::build(&editor_model).unwrap();
+    let mut asset_engine = AssetEngine::new(
+        &schema_set,
+        AssetPluginRegistryBuilders::new()
+            .register_plugin::<WarningVec3Plugin>()
+            .finish(&schema_set),
+        &EditorModelWithCache {
+            asset_path_cache: &asset_path_cache,
+            editor_model: &mut editor_model,
+        },
+        &project_config,
+    );
+
+    let diagnostics = Arc::new(Mutex::new(Vec::default()));
+    asset_engine.add_diagnostic_sink(Box::new(TestSink(diagnostics.clone())));
+
+    run_build_to_completion(&mut asset_engine, &asset_path_cache, &mut editor_model);
+
+    let diagnostics = diagnostics.lock().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(diagnostics[0].level, LogEventLevel::Warning));
+    assert_eq!(diagnostics[0].message, "vec3 is not normalized");
+    assert_eq!(diagnostics[0].asset_id, Some(asset_id));
+    assert_eq!(diagnostics[0].job_id, None);
+
+    drop(asset_engine);
+    let _ = std::fs::remove_dir_all(&root_path);
+}
+
+#[test]
+fn asset_engines_share_job_results_through_build_cache() {
+    use crate::{AssetPathCache, EditorModel, EditorModelWithCache, PathNode, PathNodeRoot};
+    use hydrate_pipeline::{AssetPluginRegistryBuilders, FileSystemBuildCacheProvider};
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let root_path = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    let build_cache_path = root_path.join("build_cache");
+    let root_id = AssetId::from_uuid(Uuid::new_v4());
+    let asset_id = AssetId::from_uuid(Uuid::new_v4());
+
+    // Builds the same project in a separate checkout and returns where its build data went
+    let build_checkout = |checkout_name: &str, x: f32| {
+        let mut project_config = default_project_config();
+        project_config.import_data_path = root_path.join(checkout_name).join("import_data");
+        project_config.build_data_path = root_path.join(checkout_name).join("build_data");
+        project_config.job_data_path = root_path.join(checkout_name).join("job_data");
+
+        let mut editor_model = EditorModel::new(project_config.clone(), schema_set.clone());
+        let path_node_root_type = editor_model
+            .path_node_root_schema()
+            .as_record()
+            .unwrap()
+            .clone();
+        let edit_context = editor_model.root_edit_context_mut();
+        edit_context
+            .new_asset_with_id(
+                root_id,
+                &AssetName::new("project"),
+                &AssetLocation::null(),
+                &path_node_root_type,
+            )
+            .unwrap();
+        edit_context
+            .new_asset_with_id(
+                asset_id,
+                &AssetName::new("vec3"),
+                &AssetLocation::new(root_id),
+                &vec3_type,
+            )
+            .unwrap();
+        edit_context
+            .set_property_override(asset_id, "x", Some(Value::F32(x)))
+            .unwrap();
+
+        let asset_path_cache = AssetPathCache::build(&editor_model).unwrap();
+        let mut asset_engine = AssetEngine::new(
+            &schema_set,
+            AssetPluginRegistryBuilders::new()
+                .register_plugin::<Vec3Plugin>()
+                .finish(&schema_set),
+            &EditorModelWithCache {
+                asset_path_cache: &asset_path_cache,
+                editor_model: &mut editor_model,
+            },
+            &project_config,
+        );
+        asset_engine.set_build_cache_provider(Some(Arc::new(FileSystemBuildCacheProvider::new(
+            &build_cache_path,
+        ))));
+
+        run_build_to_completion(&mut asset_engine, &asset_path_cache, &mut editor_model);
+
+        project_config.build_data_path
+    };
+    // Build data file names are unique to the artifact and its contents
+    let artifact_file_names = |build_data_path: &Path| {
+        globwalk::GlobWalkerBuilder::from_patterns(build_data_path, &["**.bf"])
+            .file_type(globwalk::FileType::FILE)
+            .build()
+            .unwrap()
+            .map(|x| x.unwrap().file_name().to_os_string())
+            .collect::<Vec<_>>()
+    };
+
+    let first_build_data_path = build_checkout("first", 3.0);
+    assert_eq!(vec3_job_run_count(asset_id), 1);
+
+    // The second checkout fetches the artifact the first one built instead of running the job
+    let second_build_data_path = build_checkout("second", 3.0);
+    assert_eq!(vec3_job_run_count(asset_id), 1);
+    let first_artifacts = artifact_file_names(&first_build_data_path);
+    assert_eq!(first_artifacts.len(), 1);
+    assert_eq!(
+        artifact_file_names(&second_build_data_path),
+        first_artifacts
+    );
+
+    // Different asset data doesn't match the cached result, so the job runs
+    let third_build_data_path = build_checkout("third", 5.0);
+    assert_eq!(vec3_job_run_count(asset_id), 2);
+    assert_ne!(artifact_file_names(&third_build_data_path), first_artifacts);
+
+    std::fs::remove_dir_all(&root_path).unwrap();
+}
+
+#[test]
+fn build_output_estimate_uses_previous_build_manifest() {
+    use crate::{AssetPathCache, EditorModel, EditorModelWithCache, PathNode, PathNodeRoot};
+    use hydrate_pipeline::{AssetPluginRegistryBuilders, BuildOutputEstimateSource};
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let root_path = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    let mut project_config = default_project_config();
+    project_config.import_data_path = root_path.join("import_data");
+    project_config.build_data_path = root_path.join("build_data");
+    project_config.job_data_path = root_path.join("job_data");
+
+    let mut editor_model = EditorModel::new(project_config.clone(), schema_set.clone());
+    let path_node_root_type = editor_model
+        .path_node_root_schema()
+        .as_record()
+        .unwrap()
+        .clone();
+    let edit_context = editor_model.root_edit_context_mut();
+    let root = edit_context.new_asset(
+        &AssetName::new("project"),
+        &AssetLocation::null(),
+        &path_node_root_type,
+    );
+    let changed_asset_id = edit_context.new_asset(
+        &AssetName::new("changed"),
+        &AssetLocation::new(root),
+        &vec3_type,
+    );
+    let unchanged_asset_id = edit_context.new_asset(
+        &AssetName::new("unchanged"),
+        &AssetLocation::new(root),
+        &vec3_type,
+    );
+
+    let asset_path_cache = AssetPathCache::build(&editor_model).unwrap();
+    let mut asset_engine = AssetEngine::new(
+        &schema_set,
+        AssetPluginRegistryBuilders::new()
+            .register_plugin::<Vec3Plugin>()
+            .finish(&schema_set),
+        &EditorModelWithCache {
+            asset_path_cache: &asset_path_cache,
+            editor_model: &mut editor_model,
+        },
+        &project_config,
+    );
+
+    // Nothing has been built yet, and the builder can't estimate its output
+    let estimate = asset_engine
+        .estimate_build_output(&EditorModelWithCache {
+            asset_path_cache: &asset_path_cache,
+            editor_model: &mut editor_model,
+        })
+        .unwrap();
+    assert!(estimate.assets().is_empty());
+    let mut expected_unestimated_assets = [changed_asset_id, unchanged_asset_id];
+    expected_unestimated_assets.sort();
+    assert_eq!(
+        estimate.unestimated_assets(),
+        &expected_unestimated_assets[..]
+    );
+
+    run_build_to_completion(&mut asset_engine, &asset_path_cache, &mut editor_model);
+
+    let edit_context = editor_model.root_edit_context_mut();
+    edit_context
+        .set_property_override(changed_asset_id, "x", Some(Value::F32(5.0)))
+        .unwrap();
+    let new_asset_id = edit_context.new_asset(
+        &AssetName::new("new"),
+        &AssetLocation::new(root),
+        &vec3_type,
+    );
+
+    let estimate = asset_engine
+        .estimate_build_output(&EditorModelWithCache {
+            asset_path_cache: &asset_path_cache,
+            editor_model: &mut editor_model,
+        })
+        .unwrap();
+    let unchanged_estimate = estimate.asset(unchanged_asset_id).unwrap();
+    assert_eq!(
+        unchanged_estimate.source,
+        BuildOutputEstimateSource::PreviousBuild
+    );
+    assert_eq!(unchanged_estimate.size.artifact_count, 1);
+    assert!(unchanged_estimate.size.size_bytes > 0);
+
+    // The changed asset's previous output is still the best guess, but it's flagged as stale
+    let changed_estimate = estimate.asset(changed_asset_id).unwrap();
+    assert_eq!(
+        changed_estimate.source,
+        BuildOutputEstimateSource::PreviousBuildStale
+    );
+    assert_eq!(changed_estimate.size.artifact_count, 1);
+
+    assert_eq!(estimate.unestimated_assets(), &[new_asset_id]);
+    let total = estimate.total();
+    assert_eq!(total.artifact_count, 2);
+    assert_eq!(
+        total.size_bytes,
+        unchanged_estimate.size.size_bytes + changed_estimate.size.size_bytes
+    );
+    assert_eq!(estimate.largest_assets(1).len(), 1);
+
+    drop(asset_engine);
+    let _ = std::fs::remove_dir_all(&root_path);
+}
+
+#[test]
+fn set_property_on_many_assets() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("Vec2", Uuid::new_v4(), |builder| {
+            builder.add_f32("x", Uuid::new_v4());
+            builder.add_f32("y", Uuid::new_v4());
+        })
+        .unwrap();
+    linker
+        .register_record_type("Color", Uuid::new_v4(), |builder| {
+            builder.add_f32("r", Uuid::new_v4());
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let record_type = |name: &str| {
+        schema_set
+            .find_named_type(name)
+            .unwrap()
+            .as_record()
+            .unwrap()
+            .clone()
+    };
+
+    let mut undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut edit_contexts = DenseSlotMap::<EditContextKey, EditContext>::default();
+    let key = edit_contexts.insert_with_key(|key| {
+        EditContext::new(&project_config, key, schema_set.clone(), &undo_stack)
+    });
+    let asset_location = asset_location();
+
+    let mut assets = vec![];
+    edit_contexts[key].with_undo_context("create assets", |edit_context| {
+        for (name, type_name) in [("a", "Vec3"), ("b", "Vec3"), ("c", "Vec2"), ("d", "Color")] {
+            assets.push(edit_context.new_asset(
+                &AssetName::new(name),
+                &asset_location,
+                &record_type(type_name),
+            ));
+        }
+        edit_context
+            .set_property_override(assets[1], "x", Some(Value::F32(1.0)))
+            .unwrap();
+        EndContextBehavior::Finish
+    });
+    let x = |edit_contexts: &DenseSlotMap<EditContextKey, EditContext>, asset_id| {
+        edit_contexts[key]
+            .get_property_override(asset_id, "x")
+            .unwrap()
+            .map(|x| x.as_f32().unwrap())
+    };
+
+    // A value that doesn't match the property changes nothing
+    assert!(matches!(
+        edit_contexts[key]
+            .set_property_on_many(&assets, "x", Some(Value::U32(5)))
+            .unwrap_err()
+            .error,
+        DataSetError::ValueDoesNotMatchSchema
+    ));
+    assert_eq!(x(&edit_contexts, assets[1]), Some(1.0));
+    assert_eq!(undo_stack.undo_history().len(), 1);
+
+    // The Vec3s and the Vec2 are set, the Color has no x and is skipped
+    let result = edit_contexts[key]
+        .set_property_on_many(&assets, "x", Some(Value::F32(5.0)))
+        .unwrap();
+    assert_eq!(result.skipped, vec![assets[3]]);
+    let old_values = result.changed;
+    assert_eq!(old_values.len(), 3);
+    assert_eq!(old_values[0].0, assets[0]);
+    assert!(old_values[0].1.is_none());
+    assert_eq!(old_values[1].0, assets[1]);
+    assert_eq!(old_values[1].1.as_ref().unwrap().as_f32().unwrap(), 1.0);
+    assert_eq!(old_values[2].0, assets[2]);
+    assert!(old_values[2].1.is_none());
+    for &asset_id in &assets[0..3] {
+        assert_eq!(x(&edit_contexts, asset_id), Some(5.0));
+    }
+    assert!(edit_contexts[key].assets()[&assets[3]]
+        .properties()
+        .is_empty());
+
+    // It's a single undo step
+    assert_eq!(
+        undo_stack.undo_history().last().unwrap(),
+        &UndoEntryInfo {
+            label: "set property on many",
+            affected_asset_count: 3
+        }
+    );
+    undo_stack.undo_n(&mut edit_contexts, 1).unwrap();
+    assert_eq!(x(&edit_contexts, assets[0]), None);
+    assert_eq!(x(&edit_contexts, assets[1]), Some(1.0));
+    assert_eq!(x(&edit_contexts, assets[2]), None);
+}
+
+#[test]
+fn leaf_properties_descends_into_records_and_nullables() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("Transform", Uuid::new_v4(), |builder| {
+            builder.add_named_type("position", Uuid::new_v4(), "Vec3");
+            builder.add_nullable("scale", Uuid::new_v4(), SchemaDefType::F32);
+            builder.add_nullable(
+                "pivot",
+                Uuid::new_v4(),
+                SchemaDefType::NamedType("Vec3".to_string()),
+            );
+            builder.add_dynamic_array("tags", Uuid::new_v4(), SchemaDefType::String);
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let transform_type = schema_set.find_named_type("Transform").unwrap();
+
+    let leaf_properties = schema_set.leaf_properties(transform_type.fingerprint());
+    let paths: Vec<_> = leaf_properties
+        .iter()
+        .map(|(path, _)| path.as_str())
+        .collect();
+    assert_eq!(
+        paths,
+        // Fields are sorted by name, not declaration order
+        vec![
+            "pivot.value.x",
+            "pivot.value.y",
+            "pivot.value.z",
+            "position.x",
+            "position.y",
+            "position.z",
+            "scale.value",
+            "tags",
+        ]
+    );
+
+    // Leaves carry their own schema, and the array is a single node rather than being expanded
+    assert_eq!(leaf_properties[6].1, Schema::F32);
+    assert!(matches!(leaf_properties[7].1, Schema::DynamicArray(_)));
+
+    // A record with no nesting returns its fields directly
+    let vec3_type = schema_set.find_named_type("Vec3").unwrap();
+    assert_eq!(schema_set.leaf_properties(vec3_type.fingerprint()).len(), 3);
+}
+
+#[test]
+fn flushing_edit_context_reports_conflicts_with_root() {
+    use crate::{
+        EditContextConflict, EditContextConflictResolution, EditorModel, PathNode, PathNodeRoot,
+    };
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut editor_model = EditorModel::new(default_project_config(), schema_set);
+    let root_context = editor_model.root_edit_context_mut();
+    let conflicting = root_context.new_asset(
+        &AssetName::new("conflicting"),
+        &asset_location(),
+        &vec3_type,
+    );
+    let unconflicting = root_context.new_asset(
+        &AssetName::new("unconflicting"),
+        &asset_location(),
+        &vec3_type,
+    );
+
+    let edit_context_key = editor_model
+        .open_edit_context(&[conflicting, unconflicting])
+        .unwrap();
+    let edit_context = editor_model.edit_context_mut(edit_context_key).unwrap();
+    edit_context
+        .set_property_override(conflicting, "x", Some(Value::F32(1.0)))
+        .unwrap();
+    edit_context
+        .set_property_override(unconflicting, "y", Some(Value::F32(1.0)))
+        .unwrap();
+
+    // Edit the same asset in the root context after the edit context was opened
+    editor_model
+        .root_edit_context_mut()
+        .set_property_override(conflicting, "z", Some(Value::F32(2.0)))
+        .unwrap();
+
+    let conflicts = editor_model.find_edit_context_conflicts(edit_context_key);
+    assert_eq!(
+        conflicts,
+        vec![EditContextConflict {
+            asset_id: conflicting,
+            root_changed_properties: vec!["z".to_string()],
+            edit_context_changed_properties: vec!["x".to_string()],
+            deleted_in_root: false,
+        }]
+    );
+
+    // Aborting leaves the root context untouched
+    let reported = editor_model
+        .flush_edit_context_to_root_with_conflict_resolution(
+            edit_context_key,
+            EditContextConflictResolution::Abort,
+        )
+        .unwrap();
+    assert_eq!(reported, conflicts);
+    let root_context = editor_model.root_edit_context();
+    assert!(!root_context
+        .has_property_override(conflicting, "x")
+        .unwrap());
+    assert!(!root_context
+        .has_property_override(unconflicting, "y")
+        .unwrap());
+
+    // Keeping the root's version flushes everything else
+    editor_model
+        .flush_edit_context_to_root_with_conflict_resolution(
+            edit_context_key,
+            EditContextConflictResolution::KeepRoot,
+        )
+        .unwrap();
+    let root_context = editor_model.root_edit_context();
+    assert!(!root_context
+        .has_property_override(conflicting, "x")
+        .unwrap());
+    assert!(root_context
+        .has_property_override(conflicting, "z")
+        .unwrap());
+    assert!(root_context
+        .has_property_override(unconflicting, "y")
+        .unwrap());
+
+    // Keeping the edit context's version overwrites the root, after which they are in sync
+    editor_model
+        .flush_edit_context_to_root_with_conflict_resolution(
+            edit_context_key,
+            EditContextConflictResolution::KeepEditContext,
+        )
+        .unwrap();
+    let root_context = editor_model.root_edit_context();
+    assert!(root_context
+        .has_property_override(conflicting, "x")
+        .unwrap());
+    assert!(!root_context
+        .has_property_override(conflicting, "z")
+        .unwrap());
+    assert!(editor_model
+        .find_edit_context_conflicts(edit_context_key)
+        .is_empty());
+
+    editor_model.close_edit_context(edit_context_key);
+}
+
+#[test]
+fn disjoint_edit_contexts_are_edited_in_parallel() {
+    use crate::{EditorModel, PathNode, PathNodeRoot};
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut editor_model = EditorModel::new(default_project_config(), schema_set);
+    let root_context = editor_model.root_edit_context_mut();
+    let assets: Vec<_> = (0..4)
+        .map(|i| {
+            root_context.new_asset(
+                &AssetName::new(format!("asset{}", i)),
+                &asset_location(),
+                &vec3_type,
+            )
+        })
+        .collect();
+
+    let first_key = editor_model.open_edit_context(&assets[0..2]).unwrap();
+    let second_key = editor_model.open_edit_context(&assets[2..4]).unwrap();
+
+    // Contexts that share an asset can't be checked out together
+    let overlapping_key = editor_model.open_edit_context(&assets[1..3]).unwrap();
+    assert!(matches!(
+        editor_model
+            .disjoint_edit_contexts_mut([first_key, overlapping_key])
+            .err()
+            .unwrap()
+            .error,
+        DataSetError::EditContextsOverlap
+    ));
+    assert!(matches!(
+        editor_model
+            .disjoint_edit_contexts_mut([first_key, first_key])
+            .err()
+            .unwrap()
+            .error,
+        DataSetError::EditContextsOverlap
+    ));
+    editor_model.close_edit_context(overlapping_key);
+
+    // Process each set of assets on its own thread
+    let [first_context, second_context] = editor_model
+        .disjoint_edit_contexts_mut([first_key, second_key])
+        .unwrap();
+    std::thread::scope(|scope| {
+        for (edit_context, value) in [(first_context, 1.0), (second_context, 2.0)] {
+            scope.spawn(move || {
+                let asset_ids: Vec<_> = edit_context.assets().keys().copied().collect();
+                edit_context.with_undo_context("batch edit", |edit_context| {
+                    for asset_id in asset_ids {
+                        edit_context
+                            .set_property_override(asset_id, "x", Some(Value::F32(value)))
+                            .unwrap();
+                    }
+                    EndContextBehavior::Finish
+                });
+            });
+        }
+    });
+
+    // Nothing is visible in the root context until the edit contexts are flushed
+    assert!(!editor_model
+        .root_edit_context()
+        .has_property_override(assets[0], "x")
+        .unwrap());
+
+    editor_model.flush_edit_context_to_root(first_key).unwrap();
+    editor_model.flush_edit_context_to_root(second_key).unwrap();
+    editor_model.close_edit_context(first_key);
+    editor_model.close_edit_context(second_key);
+
+    let root_context = editor_model.root_edit_context();
+    for (i, &asset_id) in assets.iter().enumerate() {
+        let expected = if i < 2 { 1.0 } else { 2.0 };
+        assert_eq!(
+            root_context
+                .resolve_property(asset_id, "x")
+                .unwrap()
+                .as_f32()
+                .unwrap(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn dynamic_array_iter_yields_entries_in_insertion_order() {
+    use hydrate_data::{
+        DataContainerRef, DynamicArrayFieldAccessor, DynamicArrayFieldRef, F32FieldAccessor,
+        F32FieldRef, FieldAccessor, FieldRef,
+    };
+    use hydrate_schema::PropertyPath;
+
+    let mut linker = SchemaLinker::default();
+    linker
+        .register_record_type("Mesh", Uuid::new_v4(), |builder| {
+            builder.add_dynamic_array("weights", Uuid::new_v4(), SchemaDefType::F32);
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let mesh_type = schema_set
+        .find_named_type("Mesh")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut data_set = DataSet::default();
+    let mesh = data_set.new_asset(AssetName::new("mesh"), asset_location(), &mesh_type);
+
+    let root = PropertyPath::default();
+    let weights = DynamicArrayFieldAccessor::<F32FieldAccessor>::new(root.push("weights"));
+    {
+        let mut container = DataContainerRefMut::from_dataset(&mut data_set, &schema_set, mesh);
+        for value in [3.0, 1.0, 2.0] {
+            let entry = weights.add_entry(&mut container).unwrap();
+            weights.entry(entry).set(&mut container, value).unwrap();
+        }
+    }
+
+    let container = DataContainerRef::from_dataset(&data_set, &schema_set, mesh);
+    let values: Vec<f32> = weights
+        .iter(container.clone())
+        .unwrap()
+        .map(|x| x.get(container.clone()).unwrap())
+        .collect();
+    assert_eq!(values, vec![3.0, 1.0, 2.0]);
+
+    let weights_ref = DynamicArrayFieldRef::<F32FieldRef>::new(root.push("weights"), container);
+    let values: Vec<f32> = weights_ref
+        .iter()
+        .unwrap()
+        .map(|x| x.get().unwrap())
+        .collect();
+    assert_eq!(values, vec![3.0, 1.0, 2.0]);
+}
+
+#[test]
+fn debug_print_asset_marks_where_values_come_from() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("Light", Uuid::new_v4(), |builder| {
+            builder.add_named_type("color", Uuid::new_v4(), "Vec3");
+            builder.add_f32("intensity", Uuid::new_v4());
+            builder.add_nullable("shadow_bias", Uuid::new_v4(), SchemaDefType::F32);
+            builder.add_dynamic_array("tags", Uuid::new_v4(), SchemaDefType::String);
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let light_type = schema_set
+        .find_named_type("Light")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut data_set = DataSet::default();
+    let prototype = data_set.new_asset(AssetName::new("prototype"), asset_location(), &light_type);
+    data_set
+        .set_property_override(&schema_set, prototype, "intensity", Some(Value::F32(2.0)))
+        .unwrap();
+    data_set
+        .add_dynamic_array_entry(&schema_set, prototype, "tags")
+        .unwrap();
+
+    let light = data_set
+        .new_asset_from_prototype(AssetName::new("light"), asset_location(), prototype)
+        .unwrap();
+    data_set
+        .set_property_override(&schema_set, light, "color.x", Some(Value::F32(1.0)))
+        .unwrap();
+    data_set
+        .set_null_override(&schema_set, light, "shadow_bias", NullOverride::SetNonNull)
+        .unwrap();
+    data_set
+        .set_property_override(
+            &schema_set,
+            light,
+            "shadow_bias.value",
+            Some(Value::F32(0.5)),
+        )
+        .unwrap();
+    let tag = data_set
+        .add_dynamic_array_entry(&schema_set, light, "tags")
+        .unwrap();
+    data_set
+        .set_property_override(
+            &schema_set,
+            light,
+            format!("tags.{}", tag),
+            Some(Value::String(Arc::new("outdoor".to_string()))),
+        )
+        .unwrap();
+
+    let rendered = data_set.debug_print_asset(&schema_set, light).unwrap();
+    for expected in [
+        format!("{} \"light\" (Light) prototype: {}", light, prototype),
+        "  color: Vec3\n    x: 1 (overridden)\n    y: 0 (default)".to_string(),
+        format!("  intensity: 2 (inherited from {})", prototype),
+        "  shadow_bias: non-null (overridden)\n    value: 0.5 (overridden)".to_string(),
+        "  tags: 2 entries".to_string(),
+        "(added here): \"outdoor\" (overridden)".to_string(),
+        "(inherited): \"\" (default)".to_string(),
+    ] {
+        assert!(
+            rendered.contains(&expected),
+            "expected {:?} in:\n{}",
+            expected,
+            rendered
+        );
+    }
+}
+
+#[test]
+fn project_relative_source_paths_resolve_against_moved_project() {
+    let create_root = || {
+        let root = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("textures")).unwrap();
+        let root = std::fs::canonicalize(&root).unwrap();
+        std::fs::write(root.join("textures").join("brick.png"), "").unwrap();
+        root
+    };
+    let root_a = create_root();
+    let root_b = create_root();
+
+    let mut project_config_a = default_project_config();
+    project_config_a.project_root_path = root_a.clone();
+    let mut project_config_b = default_project_config();
+    project_config_b.project_root_path = root_b.clone();
+
+    // A file under the project root that isn't in a source file location is stored relative to it
+    let source_file = CanonicalPathReference::new(
+        &project_config_a,
+        "".to_string(),
+        root_a
+            .join("textures")
+            .join("brick.png")
+            .to_string_lossy()
+            .to_string(),
+        ImportableName::default(),
+    );
+    assert_eq!(source_file.namespace(), PROJECT_ROOT_NAMESPACE);
+    assert_eq!(source_file.path(), "textures/brick.png");
+    assert_eq!(source_file.to_string(), "project://textures/brick.png");
+
+    // Reading it back in the moved project produces the same reference
+    let round_tripped =
+        PathReference::from(source_file.to_string().as_str()).simplify(&project_config_b);
+    assert_eq!(round_tripped, source_file);
+
+    // It only becomes absolute when resolved, and it resolves within whichever root is in use
+    let resolved_a = source_file
+        .canonicalized_absolute_path(&project_config_a, &root_a)
+        .unwrap();
+    assert_eq!(
+        Path::new(resolved_a.path()),
+        root_a.join("textures").join("brick.png")
+    );
+    let resolved_b = round_tripped
+        .canonicalized_absolute_path(&project_config_b, &root_b)
+        .unwrap();
+    assert_eq!(
+        Path::new(resolved_b.path()),
+        root_b.join("textures").join("brick.png")
+    );
+
+    std::fs::remove_dir_all(&root_a).unwrap();
+    std::fs::remove_dir_all(&root_b).unwrap();
+}
+
+#[test]
+fn map_entry_with_dotted_key_is_addressable() {
+    let mut linker = SchemaLinker::default();
+    linker
+        .register_record_type("FileWeights", Uuid::new_v4(), |builder| {
+            builder.add_map(
+                "weights",
+                Uuid::new_v4(),
+                SchemaDefType::String,
+                SchemaDefType::F32,
+            );
+        })
+        .unwrap();
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let record_type = schema_set
+        .find_named_type("FileWeights")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let mut data_set = DataSet::default();
+    let asset_id = data_set.new_asset(AssetName::new("asset"), asset_location(), &record_type);
+
+    let weights_path = PropertyPath::default().push("weights");
+    let entry = data_set
+        .add_map_entry(&schema_set, asset_id, weights_path.path())
+        .unwrap();
+    let entry_path = weights_path.push(&entry.to_string());
+    data_set
+        .set_property_override(
+            &schema_set,
+            asset_id,
+            format!("{}:key", entry_path.path()),
+            Some(Value::String(Arc::new("texture.png".to_string()))),
+        )
+        .unwrap();
+    data_set
+        .set_property_override(
+            &schema_set,
+            asset_id,
+            format!("{}:value", entry_path.path()),
+            Some(Value::F32(0.5)),
+        )
+        .unwrap();
+
+    // The dotted key is stored as a value, so it doesn't break the entry's path
+    let entries = data_set
+        .resolve_map_entries(&schema_set, asset_id, weights_path.path())
+        .unwrap();
+    let found = entries
+        .iter()
+        .find(|&&entry| {
+            let key_path = format!("{}.{}:key", weights_path.path(), entry);
+            data_set
+                .resolve_property(&schema_set, asset_id, key_path)
+                .unwrap()
+                .as_string()
+                .unwrap()
+                .as_str()
+                == "texture.png"
+        })
+        .copied();
+    assert_eq!(found, Some(entry));
+    assert_eq!(
+        data_set
+            .resolve_property(
+                &schema_set,
+                asset_id,
+                format!("{}:value", entry_path.path())
+            )
+            .unwrap()
+            .as_f32()
+            .unwrap(),
+        0.5
+    );
+
+    // A segment built from the key escapes its dot, so the path still splits into two segments
+    let key_segment_path = weights_path.push_raw("texture.png");
+    assert_eq!(key_segment_path.path(), "weights.texture%2Epng");
+    assert_eq!(
+        key_segment_path.segments(),
+        vec!["weights".to_string(), "texture.png".to_string()]
+    );
+    assert_eq!(
+        PropertyPath::parse("a.50%25%2E%2Fb").segments(),
+        vec!["a".to_string(), "50%.%2Fb".to_string()]
+    );
+}
+
+#[test]
+fn scalar_resolvers_match_resolve_property() {
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+
+    let undo_stack = UndoStack::default();
+    let project_config = default_project_config();
+    let mut db = EditContext::new(
+        &project_config,
+        EditContextKey::default(),
+        schema_set.clone(),
+        &undo_stack,
+    );
+    let asset_location = asset_location();
+
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    // x is overridden on the asset, y comes from the prototype and z is the schema default
+    let prototype = db.new_asset(&AssetName::new("prototype"), &asset_location, &vec3_type);
+    db.set_property_override(prototype, "y", Some(Value::F32(2.0)))
+        .unwrap();
+    let obj = db
+        .new_asset_from_prototype(&AssetName::new("obj"), &asset_location, prototype)
+        .unwrap();
+    db.set_property_override(obj, "x", Some(Value::F32(1.0)))
+        .unwrap();
+
+    let data_set = db.data_set();
+    let paths = ["x", "y", "z"];
+    let read_count = 1000;
+
+    let mut expected_values = Vec::with_capacity(read_count);
+    for i in 0..read_count {
+        expected_values.push(
+            data_set
+                .resolve_property(&schema_set, obj, paths[i % paths.len()])
+                .unwrap()
+                .as_f32()
+                .unwrap(),
+        );
+    }
+
+    let mut values = Vec::with_capacity(read_count);
+    for i in 0..read_count {
+        values.push(
+            data_set
+                .resolve_f32(&schema_set, obj, paths[i % paths.len()])
+                .unwrap(),
+        );
+    }
+
+    assert_eq!(values, expected_values);
+    assert_eq!(&values[0..3], &[1.0, 2.0, 0.0]);
+
+    // Unknown fields and type mismatches are reported the same as with resolve_property()
+    assert!(matches!(
+        data_set
+            .resolve_f32(&schema_set, obj, "w")
+            .unwrap_err()
+            .error,
+        DataSetError::SchemaNotFound
+    ));
+    assert!(matches!(
+        data_set
+            .resolve_boolean(&schema_set, obj, "x")
+            .unwrap_err()
+            .error,
+        DataSetError::InvalidSchema
+    ));
+}
+
+#[test]
+fn replace_references_repoints_every_material_to_the_new_texture() {
+    use crate::{EditorModel, PathNode, PathNodeRoot};
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("Texture", Uuid::new_v4(), |builder| {
+            builder.add_u32("width", Uuid::new_v4());
+        })
+        .unwrap();
+    linker
+        .register_record_type("Material", Uuid::new_v4(), |builder| {
+            builder.add_reference("albedo", Uuid::new_v4(), "Texture");
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let find_record = |name: &str| {
+        schema_set
+            .find_named_type(name)
+            .unwrap()
+            .as_record()
+            .unwrap()
+            .clone()
+    };
+    let vec3_type = find_record("Vec3");
+    let texture_type = find_record("Texture");
+    let material_type = find_record("Material");
+
+    let mut editor_model = EditorModel::new(default_project_config(), schema_set.clone());
+    let edit_context = editor_model.root_edit_context_mut();
+    let old_rock = edit_context.new_asset(
+        &AssetName::new("old_rock"),
+        &asset_location(),
+        &texture_type,
+    );
+    let new_rock = edit_context.new_asset(
+        &AssetName::new("new_rock"),
+        &asset_location(),
+        &texture_type,
+    );
+    let not_a_texture =
+        edit_context.new_asset(&AssetName::new("vec3"), &asset_location(), &vec3_type);
+
+    let mut materials = vec![];
+    for i in 0..3 {
+        let material = edit_context.new_asset(
+            &AssetName::new(format!("material{}", i)),
+            &asset_location(),
+            &material_type,
+        );
+        edit_context
+            .set_property_override(material, "albedo", Some(Value::AssetRef(old_rock)))
+            .unwrap();
+        materials.push(material);
+    }
+
+    // A material that inherits the texture from its prototype doesn't have its own reference to
+    // change
+    let derived_material = edit_context
+        .new_asset_from_prototype(&AssetName::new("derived"), &asset_location(), materials[0])
+        .unwrap();
+
+    let resolve_albedo = |editor_model: &EditorModel, material: AssetId| {
+        editor_model
+            .root_edit_context()
+            .resolve_property(material, "albedo")
+            .unwrap()
+            .as_asset_ref()
+            .unwrap()
+    };
+
+    // Texture properties can't reference a Vec3, so nothing is changed
+    assert!(matches!(
+        editor_model
+            .replace_references(old_rock, not_a_texture, true)
+            .unwrap_err()
+            .error,
+        DataSetError::ValueDoesNotMatchSchema
+    ));
+    for material in &materials {
+        assert_eq!(resolve_albedo(&editor_model, *material), old_rock);
+    }
+
+    let result = editor_model
+        .replace_references(old_rock, new_rock, true)
+        .unwrap();
+    assert_eq!(result.changed, 3);
+    assert!(result.skipped.is_empty());
+    for material in &materials {
+        assert_eq!(resolve_albedo(&editor_model, *material), new_rock);
+    }
+    assert_eq!(resolve_albedo(&editor_model, derived_material), new_rock);
+    assert!(editor_model
+        .root_edit_context()
+        .data_set()
+        .assets_referencing(&[old_rock].into_iter().collect())
+        .is_empty());
+
+    // Replacing is a single undo step
+    assert_eq!(editor_model.undo_history().len(), 1);
+    editor_model.undo().unwrap();
+    for material in &materials {
+        assert_eq!(resolve_albedo(&editor_model, *material), old_rock);
+    }
+}
+
+#[test]
+fn replace_references_skips_readonly_properties() {
+    use crate::{EditorModel, PathNode, PathNodeRoot};
+
+    let mut linker = SchemaLinker::default();
+    PathNode::register_schema(&mut linker);
+    PathNodeRoot::register_schema(&mut linker);
+    linker
+        .register_record_type("Texture", Uuid::new_v4(), |builder| {
+            builder.add_u32("width", Uuid::new_v4());
+        })
+        .unwrap();
+    linker
+        .register_record_type("Material", Uuid::new_v4(), |builder| {
+            builder.add_reference("albedo", Uuid::new_v4(), "Texture");
+            builder
+                .add_reference("baked_albedo", Uuid::new_v4(), "Texture")
+                .set_readonly(true);
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let find_record = |name: &str| {
+        schema_set
+            .find_named_type(name)
+            .unwrap()
+            .as_record()
+            .unwrap()
+            .clone()
+    };
+    let texture_type = find_record("Texture");
+    let material_type = find_record("Material");
+
+    // The readonly reference is written by an importer, which writes to the data set directly
+    let mut data_set = DataSet::default();
+    let old_rock = data_set.new_asset(AssetName::new("old_rock"), asset_location(), &texture_type);
+    let new_rock = data_set.new_asset(AssetName::new("new_rock"), asset_location(), &texture_type);
+    let material = data_set.new_asset(AssetName::new("material"), asset_location(), &material_type);
+    for path in ["albedo", "baked_albedo"] {
+        data_set
+            .set_property_override(&schema_set, material, path, Some(Value::AssetRef(old_rock)))
+            .unwrap();
+    }
+
+    let mut editor_model = EditorModel::new(default_project_config(), schema_set.clone());
+    editor_model
+        .root_edit_context_mut()
+        .restore_assets_from(data_set)
+        .unwrap();
+
+    let result = editor_model
+        .replace_references(old_rock, new_rock, true)
+        .unwrap();
+    assert_eq!(result.changed, 1);
+    assert_eq!(result.skipped, vec![material]);
+
+    let resolve = |path: &str| {
+        editor_model
+            .root_edit_context()
+            .resolve_property(material, path)
+            .unwrap()
+            .as_asset_ref()
+            .unwrap()
+    };
+    assert_eq!(resolve("albedo"), new_rock);
+    assert_eq!(resolve("baked_albedo"), old_rock);
+}
+
+#[test]
+fn schema_changes_since_the_project_was_last_opened_are_detected() {
+    use crate::{EditorModel, PathNode, PathNodeRoot, SchemaChange};
+
+    let build_schema_set = |register: &dyn Fn(&mut SchemaLinker)| {
+        let mut linker = SchemaLinker::default();
+        PathNode::register_schema(&mut linker);
+        PathNodeRoot::register_schema(&mut linker);
+        register(&mut linker);
+        let mut schema_set_builder = SchemaSetBuilder::default();
+        schema_set_builder.add_linked_types(linker).unwrap();
+        schema_set_builder.build()
+    };
+
+    let vec3_uuid = Uuid::new_v4();
+    let old_schema_set = build_schema_set(&|linker| {
+        linker
+            .register_record_type("Vec3", vec3_uuid, |builder| {
+                builder.add_f32("x", Uuid::new_v4());
+                builder.add_f32("y", Uuid::new_v4());
+                builder.add_f32("z", Uuid::new_v4());
+            })
+            .unwrap();
+        linker
+            .register_record_type("Removed", Uuid::new_v4(), |builder| {
+                builder.add_f32("value", Uuid::new_v4());
+            })
+            .unwrap();
+    });
+
+    // A plugin replaced z with w and added a type
+    let new_schema_set = build_schema_set(&|linker| {
+        linker
+            .register_record_type("Vec3", vec3_uuid, |builder| {
+                builder.add_f32("x", Uuid::new_v4());
+                builder.add_f32("y", Uuid::new_v4());
+                builder.add_f32("w", Uuid::new_v4());
+            })
+            .unwrap();
+        linker
+            .register_record_type("Added", Uuid::new_v4(), |builder| {
+                builder.add_f32("value", Uuid::new_v4());
+            })
+            .unwrap();
+    });
+
+    let root_path = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+    let mut project_config = default_project_config();
+    project_config.job_data_path = root_path.join("job_data");
+
+    // Nothing is reported for a project that never recorded its schemas
+    let editor_model = EditorModel::new(project_config.clone(), old_schema_set.clone());
+    assert!(editor_model.detect_schema_changes().is_empty());
+    editor_model.record_schema_fingerprints().unwrap();
+    assert!(editor_model.detect_schema_changes().is_empty());
+
+    let editor_model = EditorModel::new(project_config.clone(), new_schema_set.clone());
+    let find_fingerprint =
+        |schema_set: &SchemaSet| schema_set.find_named_type("Vec3").unwrap().fingerprint();
+    assert_eq!(
+        editor_model.detect_schema_changes(),
+        vec![
+            SchemaChange::Added {
+                type_name: "Added".to_string()
+            },
+            SchemaChange::Removed {
+                type_name: "Removed".to_string()
+            },
+            SchemaChange::Changed {
+                type_name: "Vec3".to_string(),
+                old_fingerprint: find_fingerprint(&old_schema_set),
+                new_fingerprint: find_fingerprint(&new_schema_set),
+                added_fields: vec!["w".to_string()],
+                removed_fields: vec!["z".to_string()],
+            },
+        ]
+    );
+
+    std::fs::remove_dir_all(&root_path).unwrap();
+}
+
+#[test]
+fn reading_import_data_buffers_does_not_clone_them() {
+    use hydrate_data::{BytesFieldRef, DataContainerRef, FieldRef};
+
+    let mut linker = SchemaLinker::default();
+    linker
+        .register_record_type("MeshImportData", Uuid::new_v4(), |builder| {
+            builder.add_bytes("vertices", Uuid::new_v4());
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let import_data_type = schema_set
+        .find_named_type("MeshImportData")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    let vertices = Arc::new(vec![0u8; 16 * 1024 * 1024]);
+    let mut import_data = SingleObject::new(&import_data_type);
+    import_data
+        .set_property_override(
+            &schema_set,
+            "vertices",
+            Some(Value::Bytes(vertices.clone())),
+        )
+        .unwrap();
+
+    // Borrowed, and shared the way RunContext::imported_data() shares fetched import data
+    let import_data_arc = Arc::new(import_data.clone());
+    let containers = [
+        DataContainerRef::from_single_object(&import_data, &schema_set),
+        DataContainerRef::from_single_object_arc(import_data_arc.clone(), &schema_set),
+    ];
+
+    for container in containers {
+        let reader = BytesFieldRef::new(PropertyPath::default().push("vertices"), container);
+        assert!(Arc::ptr_eq(reader.get().unwrap(), &vertices));
+    }
+
+    // The clone of the object above shares the buffer too
+    assert_eq!(Arc::strong_count(&vertices), 3);
+}
+
+#[test]
+fn dynamic_array_of_asset_refs_resolves_to_ordered_asset_ids() {
+    use hydrate_data::{AssetRefFieldRef, DataContainerRef, DynamicArrayFieldRef, FieldRef};
+
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+    linker
+        .register_record_type("Mesh", Uuid::new_v4(), |builder| {
+            builder.add_dynamic_array(
+                "material_slots",
+                Uuid::new_v4(),
+                SchemaDefType::AssetRef("Vec3".to_string()),
+            );
+        })
+        .unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let find_record = |name: &str| {
+        schema_set
+            .find_named_type(name)
+            .unwrap()
+            .as_record()
+            .unwrap()
+            .clone()
+    };
+    let vec3_type = find_record("Vec3");
+    let mesh_type = find_record("Mesh");
+
+    let mut data_set = DataSet::default();
+    let materials: Vec<_> = (0..3)
+        .map(|i| {
+            data_set.new_asset(
+                AssetName::new(format!("material{}", i)),
+                asset_location(),
+                &vec3_type,
+            )
+        })
+        .collect();
+
+    // The prototype has the first two slots and the mesh appends the third
+    let prototype = data_set.new_asset(AssetName::new("prototype"), asset_location(), &mesh_type);
+    let mesh = data_set
+        .new_asset_from_prototype(AssetName::new("mesh"), asset_location(), prototype)
+        .unwrap();
+    for (asset_id, material) in [
+        (prototype, materials[0]),
+        (prototype, materials[1]),
+        (mesh, materials[2]),
+    ] {
+        let entry = data_set
+            .add_dynamic_array_entry(&schema_set, asset_id, "material_slots")
+            .unwrap();
+        data_set
+            .set_property_override(
+                &schema_set,
+                asset_id,
+                format!("material_slots.{}", entry),
+                Some(Value::AssetRef(material)),
+            )
+            .unwrap();
+    }
+
+    let read_slots = |data_set: &DataSet| {
+        DynamicArrayFieldRef::<AssetRefFieldRef>::new(
+            PropertyPath::default().push("material_slots"),
+            DataContainerRef::from_dataset(data_set, &schema_set, mesh),
+        )
+        .resolve_asset_ref_entries()
+        .unwrap()
+    };
+    assert_eq!(read_slots(&data_set), materials);
+
+    // In replace mode only the mesh's own slot is used
+    data_set
+        .set_override_behavior(
+            &schema_set,
+            mesh,
+            "material_slots",
+            OverrideBehavior::Replace,
+        )
+        .unwrap();
+    assert_eq!(read_slots(&data_set), vec![materials[2]]);
+}
+
+#[test]
+fn field_set_to_default_value_is_distinguished_from_unset_field() {
+    use hydrate_data::{DataContainerRef, F32FieldRef, FieldRef};
+
+    let mut linker = SchemaLinker::default();
+    create_vec3_schema(&mut linker).unwrap();
+
+    let mut schema_set_builder = SchemaSetBuilder::default();
+    schema_set_builder.add_linked_types(linker).unwrap();
+    let schema_set = schema_set_builder.build();
+    let vec3_type = schema_set
+        .find_named_type("Vec3")
+        .unwrap()
+        .as_record()
+        .unwrap()
+        .clone();
+
+    // x is set to its default value, y is inherited from the prototype and z is never set
+    let mut data_set = DataSet::default();
+    let prototype = data_set.new_asset(AssetName::new("prototype"), asset_location(), &vec3_type);
+    data_set
+        .set_property_override(&schema_set, prototype, "y", Some(Value::F32(2.0)))
+        .unwrap();
+    let asset = data_set
+        .new_asset_from_prototype(AssetName::new("asset"), asset_location(), prototype)
+        .unwrap();
+    data_set
+        .set_property_override(&schema_set, asset, "x", Some(Value::F32(0.0)))
+        .unwrap();
+
+    let is_set = |path: &str| {
+        data_set
+            .is_property_explicitly_set(&schema_set, asset, path)
+            .unwrap()
+    };
+    assert!(is_set("x"));
+    assert!(is_set("y"));
+    assert!(!is_set("z"));
+    assert!(matches!(
+        data_set
+            .is_property_explicitly_set(&schema_set, asset, "w")
+            .unwrap_err()
+            .error,
+        DataSetError::SchemaNotFound
+    ));
+
+    // Both resolve to the default value, only the field wrapper can tell them apart
+    let field = |path: &str| {
+        F32FieldRef::new(
+            PropertyPath::default().push(path),
+            DataContainerRef::from_dataset(&data_set, &schema_set, asset),
+        )
+    };
+    assert_eq!(field("x").get().unwrap(), field("z").get().unwrap());
+    assert!(field("x").is_set().unwrap());
+    assert!(!field("z").is_set().unwrap());
+
+    // Same when reading through an asset view
+    let view = DataContainerRef::from_asset_view(data_set.asset_view(&schema_set, asset).unwrap());
+    assert!(view.is_property_explicitly_set("y").unwrap());
+    assert!(!view.is_property_explicitly_set("z").unwrap());
+}
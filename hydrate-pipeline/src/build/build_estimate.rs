@@ -0,0 +1,214 @@
+use super::{hash_asset_build_inputs, BuilderRegistry};
+use crate::{DynEditorModel, PipelineResult};
+use hydrate_base::hashing::HashMap;
+use hydrate_base::uuid_path::uuid_and_hash_to_path;
+use hydrate_base::{AssetId, DebugManifestFileJson};
+use std::path::Path;
+
+// The number and total size of artifacts produced for an asset
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct BuildOutputSize {
+    pub artifact_count: usize,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BuildOutputEstimateSource {
+    // The asset's builder estimated it from the asset
+    Builder,
+    // The asset hasn't changed since the previous build, so it will produce the same output
+    PreviousBuild,
+    // The asset changed since the previous build. Its output from that build is the best guess.
+    PreviousBuildStale,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AssetBuildOutputEstimate {
+    pub asset_id: AssetId,
+    pub size: BuildOutputSize,
+    pub source: BuildOutputEstimateSource,
+}
+
+// Estimated output of building every asset. See AssetEngine::estimate_build_output()
+#[derive(Debug, Clone, Default)]
+pub struct BuildOutputEstimate {
+    assets: HashMap<AssetId, AssetBuildOutputEstimate>,
+    // Assets with a builder that can't be estimated, because the builder doesn't support it and
+    // the assets weren't in the previous build. Sorted by ID.
+    unestimated_assets: Vec<AssetId>,
+}
+
+impl BuildOutputEstimate {
+    pub fn asset(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<&AssetBuildOutputEstimate> {
+        self.assets.get(&asset_id)
+    }
+
+    pub fn assets(&self) -> &HashMap<AssetId, AssetBuildOutputEstimate> {
+        &self.assets
+    }
+
+    pub fn unestimated_assets(&self) -> &[AssetId] {
+        &self.unestimated_assets
+    }
+
+    pub fn total(&self) -> BuildOutputSize {
+        let mut total = BuildOutputSize::default();
+        for estimate in self.assets.values() {
+            total.artifact_count += estimate.size.artifact_count;
+            total.size_bytes += estimate.size.size_bytes;
+        }
+        total
+    }
+
+    // The assets with the largest estimated output, largest first
+    pub fn largest_assets(
+        &self,
+        count: usize,
+    ) -> Vec<AssetBuildOutputEstimate> {
+        let mut assets: Vec<_> = self.assets.values().copied().collect();
+        assets.sort_by(|a, b| {
+            b.size
+                .size_bytes
+                .cmp(&a.size.size_bytes)
+                .then(a.asset_id.cmp(&b.asset_id))
+        });
+        assets.truncate(count);
+        assets
+    }
+}
+
+// What each asset produced in the previous build, and the hash of its build inputs at the time
+#[derive(Default)]
+struct PreviousAssetOutput {
+    size: BuildOutputSize,
+    asset_build_hash: Option<u64>,
+}
+
+// The manifest hash written to the most recent TOC, if anything has been built
+fn find_latest_manifest_build_hash(build_data_root_path: &Path) -> PipelineResult<Option<u64>> {
+    let toc_path = build_data_root_path.join("toc");
+    if !toc_path.exists() {
+        return Ok(None);
+    }
+
+    // TOC files are named by timestamp in fixed width hex, so the latest sorts last
+    let mut latest_toc = None;
+    for entry in std::fs::read_dir(&toc_path)? {
+        let path = entry?.path();
+        if path.extension().map(|x| x == "toc").unwrap_or(false)
+            && latest_toc.as_ref().map(|x| path > *x).unwrap_or(true)
+        {
+            latest_toc = Some(path);
+        }
+    }
+
+    let Some(latest_toc) = latest_toc else {
+        return Ok(None);
+    };
+
+    let contents = std::fs::read_to_string(&latest_toc)?;
+    let manifest_build_hash = u64::from_str_radix(contents.trim(), 16)
+        .map_err(|e| format!("Could not parse TOC {:?}: {}", latest_toc, e))?;
+    Ok(Some(manifest_build_hash))
+}
+
+fn read_previous_output(
+    build_data_root_path: &Path
+) -> PipelineResult<HashMap<AssetId, PreviousAssetOutput>> {
+    let mut previous_output = HashMap::<AssetId, PreviousAssetOutput>::default();
+    let Some(manifest_build_hash) = find_latest_manifest_build_hash(build_data_root_path)? else {
+        return Ok(previous_output);
+    };
+
+    let manifest_path = build_data_root_path
+        .join("manifests")
+        .join(format!("{:0>16x}.manifest_debug", manifest_build_hash));
+    let manifest_json: DebugManifestFileJson =
+        serde_json::from_str(&std::fs::read_to_string(manifest_path)?)?;
+
+    for artifact in manifest_json.artifacts {
+        let Ok(build_hash) = u64::from_str_radix(&artifact.build_hash, 16) else {
+            continue;
+        };
+
+        // Build data may have been cleared since the manifest was written
+        let path = uuid_and_hash_to_path(
+            build_data_root_path,
+            artifact.artifact_id.as_uuid(),
+            build_hash,
+            "bf",
+        );
+        let Ok(metadata) = std::fs::metadata(path) else {
+            continue;
+        };
+
+        // Older manifests don't say which asset an artifact belongs to. An asset's default
+        // artifact has the same ID as the asset, so at least those can be attributed.
+        let asset_id = artifact
+            .asset_id
+            .unwrap_or_else(|| AssetId::from_uuid(artifact.artifact_id.as_uuid()));
+        let output = previous_output.entry(asset_id).or_default();
+        output.size.artifact_count += 1;
+        output.size.size_bytes += metadata.len();
+        output.asset_build_hash = artifact
+            .asset_build_hash
+            .and_then(|x| u64::from_str_radix(&x, 16).ok());
+    }
+
+    Ok(previous_output)
+}
+
+pub(crate) fn estimate_build_output(
+    build_data_root_path: &Path,
+    builder_registry: &BuilderRegistry,
+    editor_model: &dyn DynEditorModel,
+) -> PipelineResult<BuildOutputEstimate> {
+    let previous_output = read_previous_output(build_data_root_path)?;
+    let data_set = editor_model.data_set();
+    let schema_set = editor_model.schema_set();
+
+    let mut estimate = BuildOutputEstimate::default();
+    for (&asset_id, asset) in data_set.assets() {
+        if editor_model.is_path_node_or_root(asset.schema()) {
+            continue;
+        }
+
+        let Some(builder) = builder_registry.builder_for_asset(asset.schema().fingerprint()) else {
+            continue;
+        };
+
+        let asset_estimate =
+            if let Some(size) = builder.estimate_output(asset_id, data_set, schema_set) {
+                Some((size, BuildOutputEstimateSource::Builder))
+            } else if let Some(output) = previous_output.get(&asset_id) {
+                let asset_build_hash = hash_asset_build_inputs(data_set, schema_set, asset_id)?;
+                let source = if output.asset_build_hash == Some(asset_build_hash) {
+                    BuildOutputEstimateSource::PreviousBuild
+                } else {
+                    BuildOutputEstimateSource::PreviousBuildStale
+                };
+                Some((output.size, source))
+            } else {
+                None
+            };
+
+        if let Some((size, source)) = asset_estimate {
+            estimate.assets.insert(
+                asset_id,
+                AssetBuildOutputEstimate {
+                    asset_id,
+                    size,
+                    source,
+                },
+            );
+        } else {
+            estimate.unestimated_assets.push(asset_id);
+        }
+    }
+
+    estimate.unestimated_assets.sort();
+    Ok(estimate)
+}
@@ -1,12 +1,15 @@
-use crate::import::ImportJobs;
-use crate::{BuildLogData, BuildLogEvent, DynEditorModel, LogEventLevel, PipelineResult};
+use crate::import::{ImportJobs, ImporterRegistry};
+use crate::{
+    BuildLogData, BuildLogEvent, DynEditorModel, HydrateProjectConfiguration, LogEventLevel,
+    PipelineResult,
+};
 use hydrate_base::hashing::HashSet;
 use hydrate_base::{hashing::HashMap, AssetId};
 use hydrate_base::{
     ArtifactId, BuiltArtifactHeaderData, DebugArtifactManifestDataJson, DebugManifestFileJson,
-    StringHash,
+    StringHash, SymbolTable,
 };
-use hydrate_data::{DataSet, HashObjectMode, SchemaSet};
+use hydrate_data::{DataSet, DataSetResult, DataSetView, SchemaSet};
 use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::hash::{Hash, Hasher};
@@ -21,6 +24,35 @@ struct BuildRequest {
     asset_id: AssetId,
 }
 
+// Hash of everything about an asset that affects how it builds: its properties (including the
+// prototype chain), its import data, and the properties of its build settings asset if it has one.
+// Fields marked build_ignore are not included.
+pub fn hash_asset_build_inputs(
+    data_set: &DataSet,
+    schema_set: &SchemaSet,
+    asset_id: AssetId,
+) -> DataSetResult<u64> {
+    let mut hash = data_set.hash_object_build_inputs(schema_set, asset_id)?;
+
+    if let Some(import_data) = data_set.import_info(asset_id) {
+        hash ^= import_data.import_data_contents_hash();
+    }
+
+    if let Some(build_settings) = data_set.build_settings(asset_id) {
+        // A missing build settings asset is treated as if no build settings were assigned
+        if let Ok(build_settings_hash) =
+            data_set.hash_object_build_inputs(schema_set, build_settings)
+        {
+            let mut inner_hasher = siphasher::sip::SipHasher::default();
+            build_settings.hash(&mut inner_hasher);
+            build_settings_hash.hash(&mut inner_hasher);
+            hash ^= inner_hasher.finish();
+        }
+    }
+
+    Ok(hash)
+}
+
 // A known build job, each existing asset will have an associated build job.
 // It could be in a completed state, or there could be a problem with it and we need to re-run it.
 struct BuildJob {
@@ -60,9 +92,11 @@ struct BuildTask {
     build_hashes: HashMap<ArtifactId, u64>,
     artifact_asset_lookup: HashMap<ArtifactId, AssetId>,
     built_artifact_info: HashMap<ArtifactId, BuiltArtifactInfo>,
-    data_set: Arc<DataSet>,
+    data_set: Arc<DataSetView>,
     schema_set: SchemaSet,
     manifest_build_hash: u64,
+    // Hashes of each asset's build inputs when the build started, recorded in the manifest
+    asset_build_hashes: HashMap<AssetId, u64>,
     log_data: BuildLogData,
 }
 
@@ -79,6 +113,7 @@ pub struct BuildJobs {
     request_build: bool,
     needs_build: bool,
     force_build_queue: HashSet<AssetId>,
+    write_build_profile: bool,
 }
 
 impl BuildJobs {
@@ -90,21 +125,25 @@ impl BuildJobs {
         self.current_build_task.is_some()
     }
 
+    // The artifacts each build job produced the last time it ran successfully
+    pub fn job_artifacts(&self) -> &HashMap<JobId, Vec<ArtifactId>> {
+        self.job_executor.job_artifacts()
+    }
+
     pub fn new(
         schema_set: &SchemaSet,
         job_processor_registry: &JobProcessorRegistry,
-        import_data_root_path: PathBuf,
-        job_data_root_path: PathBuf,
-        build_data_root_path: PathBuf,
+        importer_registry: &ImporterRegistry,
+        project_config: &HydrateProjectConfiguration,
     ) -> Self {
         //TODO: May need to scan disk to see what is cached?
         let job_executor = JobExecutor::new(
             schema_set,
             job_processor_registry,
-            import_data_root_path,
-            job_data_root_path,
-            build_data_root_path.clone(),
+            importer_registry,
+            project_config,
         );
+        let build_data_root_path = project_config.build_data_path.clone();
         let build_jobs = Default::default();
 
         BuildJobs {
@@ -117,6 +156,7 @@ impl BuildJobs {
             request_build: false,
             needs_build: false,
             force_build_queue: Default::default(),
+            write_build_profile: project_config.write_build_profile,
         }
     }
 
@@ -127,20 +167,90 @@ impl BuildJobs {
         self.force_build_queue.insert(asset_id);
     }
 
+    // Build jobs for the asset in the current build that haven't finished
+    pub fn active_jobs_for_asset(
+        &self,
+        asset_id: AssetId,
+    ) -> Vec<JobId> {
+        self.job_executor.active_jobs_for_asset(asset_id)
+    }
+
+    // Stops building the asset in the current build. If it is requested again during the build,
+    // its builder runs again.
+    pub fn cancel_jobs_for_asset(
+        &mut self,
+        asset_id: AssetId,
+    ) {
+        if let Some(build_task) = &mut self.current_build_task {
+            build_task
+                .requested_build_ops
+                .retain(|x| x.asset_id != asset_id);
+            build_task.started_build_ops.remove(&asset_id);
+        }
+
+        self.job_executor.cancel_jobs_for_asset(asset_id);
+    }
+
+    // Points at build data that has been moved to project_config.build_data_path. What has already
+    // been built is kept, so the next build will only build what has changed.
+    pub fn relocate(
+        &mut self,
+        project_config: &HydrateProjectConfiguration,
+    ) {
+        assert!(!self.is_building());
+        self.build_data_root_path = project_config.build_data_path.clone();
+        self.job_executor.relocate(project_config);
+    }
+
     pub fn build(&mut self) {
         self.request_build = true;
     }
 
+    pub fn set_build_cache_provider(
+        &mut self,
+        build_cache: Option<Arc<dyn BuildCacheProvider>>,
+    ) {
+        self.job_executor.set_build_cache_provider(build_cache);
+    }
+
+    // Requests a build of everything. If clear_build_data is set, existing build data is deleted
+    // first so that every artifact is written again even if its inputs haven't changed.
+    pub fn rebuild_all(
+        &mut self,
+        clear_build_data: bool,
+    ) -> PipelineResult<()> {
+        if clear_build_data {
+            if self.is_building() {
+                Err("Cannot clear build data while building")?;
+            }
+
+            self.job_executor.clear_build_data()?;
+            self.previous_manifest_build_hash = None;
+            self.needs_build = true;
+        }
+
+        self.request_build = true;
+        Ok(())
+    }
+
     pub fn needs_build(&self) -> bool {
         self.needs_build
     }
 
+    pub fn estimate_build_output(
+        &self,
+        builder_registry: &BuilderRegistry,
+        editor_model: &dyn DynEditorModel,
+    ) -> PipelineResult<BuildOutputEstimate> {
+        estimate_build_output(&self.build_data_root_path, builder_registry, editor_model)
+    }
+
     #[profiling::function]
     pub fn update(
         &mut self,
         builder_registry: &BuilderRegistry,
         editor_model: &mut dyn DynEditorModel,
-        import_jobs: &ImportJobs,
+        import_jobs: &mut ImportJobs,
     ) -> PipelineResult<BuildStatus> {
         profiling::scope!("Process Build Operations");
 
@@ -171,13 +281,22 @@ impl BuildJobs {
                         continue;
                     };
 
-                    if let Err(e) = builder.start_jobs(BuilderContext {
+                    self.job_executor.begin_builder_run(asset_id);
+                    let start_instant = std::time::Instant::now();
+                    let result = builder.start_jobs(BuilderContext {
                         asset_id,
                         data_set: &build_task.data_set,
                         schema_set: &build_task.schema_set,
                         job_api: self.job_executor.job_api(),
                         log_events: &Rc::new(RefCell::new(&mut build_task.log_data.log_events)),
-                    }) {
+                    });
+                    build_task.log_data.profile.record_builder(
+                        builder.asset_type(),
+                        asset_id,
+                        start_instant.elapsed(),
+                    );
+
+                    if let Err(e) = result {
                         let log_event = BuildLogEvent {
                             job_id: None,
                             asset_id: Some(asset_id),
@@ -248,6 +367,33 @@ impl BuildJobs {
                         },
                     );
                 }
+
+                //
+                // Jobs that are re-run may stop producing artifacts they produced previously. The
+                // executor deletes their build data, so forget about them here too.
+                //
+                for removed_artifact_id in self.job_executor.take_removed_artifacts() {
+                    for job in self.build_jobs.values_mut() {
+                        job.build_data_exists
+                            .retain(|(artifact_id, _)| *artifact_id != removed_artifact_id);
+                    }
+                }
+            }
+
+            //
+            // Jobs may read artifacts built for other assets, so those assets need to be built too.
+            // Once there is nothing left to start, anything jobs are still waiting on will never be
+            // produced.
+            //
+            for asset_id in self.job_executor.take_built_data_requests() {
+                build_task
+                    .requested_build_ops
+                    .push_back(BuildRequest { asset_id });
+            }
+
+            if build_task.requested_build_ops.is_empty() {
+                self.job_executor
+                    .fail_jobs_waiting_on_missing_built_data(&mut build_task.log_data);
             }
 
             if !(build_task.requested_build_ops.is_empty() && self.job_executor.is_idle()) {
@@ -266,7 +412,7 @@ impl BuildJobs {
         //
         // Finish the current build task
         //
-        if let Some(build_task) = self.current_build_task.take() {
+        if let Some(mut build_task) = self.current_build_task.take() {
             //
             // Write the manifest file
             //TODO: Only if it doesn't already exist? We could skip the whole building process in that case
@@ -291,7 +437,8 @@ impl BuildJobs {
 
             let mut manifest_json = DebugManifestFileJson::default();
 
-            let mut all_hashes = HashSet::default();
+            // Maps symbol names to artifacts so that the loader can load by symbol
+            let mut symbol_table = SymbolTable::default();
             for (&artifact_id, &build_hash) in &build_task.build_hashes {
                 let built_artifact_info = build_task.built_artifact_info.get(&artifact_id).unwrap();
                 let asset_id = built_artifact_info.asset_id;
@@ -348,10 +495,13 @@ impl BuildJobs {
 
                 let symbol_name_hash =
                     StringHash::from_runtime_str(&symbol_name.clone().unwrap_or_default()).hash();
-                if symbol_name_hash != 0 {
-                    let newly_inserted = all_hashes.insert(symbol_name_hash);
-                    if !newly_inserted {
-                        // We have a hash collision if this fires
+                if let Some(symbol_name) = &symbol_name {
+                    // Different names with the same hash are fine, the symbol table keeps the full
+                    // name to tell them apart. The same name twice is not.
+                    if symbol_table
+                        .register_symbol(symbol_name, artifact_id)
+                        .is_some()
+                    {
                         panic!("Two artifacts have been produced with the same symbol name. Check for assets with the same name: {:?}", symbol_name);
                     }
                 }
@@ -376,6 +526,11 @@ impl BuildJobs {
                     symbol_name: symbol_name.unwrap_or_default(),
                     artifact_type: built_artifact_info.metadata.asset_type,
                     debug_name,
+                    asset_id: Some(asset_id),
+                    asset_build_hash: build_task
+                        .asset_build_hashes
+                        .get(&asset_id)
+                        .map(|x| format!("{:0>16x}", x)),
                     //dependencies: artifact_metadata.dependencies.clone(),
                 });
 
@@ -394,6 +549,15 @@ impl BuildJobs {
 
             drop(manifest_release_file_writer);
 
+            {
+                profiling::scope!("Write symbol table");
+                let symbol_table_path =
+                    manifest_path.join(format!("{:0>16x}.symbols", build_task.manifest_build_hash));
+                let symbol_table_file = std::fs::File::create(symbol_table_path).unwrap();
+                let mut symbol_table_file_writer = std::io::BufWriter::new(symbol_table_file);
+                symbol_table.write(&mut symbol_table_file_writer).unwrap();
+            }
+
             {
                 profiling::scope!("Write debug manifest data");
                 let json = {
@@ -424,6 +588,30 @@ impl BuildJobs {
             )
             .unwrap();
 
+            let end_instant = std::time::Instant::now();
+            build_task.log_data.end_instant = Some(end_instant);
+            build_task.log_data.profile.total_duration =
+                end_instant - build_task.log_data.start_instant;
+
+            if self.write_build_profile {
+                profiling::scope!("Write build profile");
+                let build_profile_path = manifest_path.join(format!(
+                    "{:0>16x}.build_profile.json",
+                    build_task.manifest_build_hash
+                ));
+                let json = build_task
+                    .log_data
+                    .profile
+                    .to_json(|asset_id| editor_model.asset_display_name_long(asset_id));
+                std::fs::write(build_profile_path, json)?;
+            }
+
+            build_task.log_data.built_artifact_count = build_task.build_hashes.len();
+            log::info!(
+                "Build completed, {} artifacts built",
+                build_task.log_data.built_artifact_count
+            );
+
             self.previous_manifest_build_hash = Some(build_task.manifest_build_hash);
             return Ok(BuildStatus::Completed(Arc::new(build_task.log_data)));
         }
@@ -431,6 +619,12 @@ impl BuildJobs {
         //
         // Consider starting a new build task
         //
+
+        // Don't build intermediate values of an edit that is being previewed
+        if editor_model.has_preview_edit() {
+            return Ok(BuildStatus::Idle);
+        }
+
         //
         // If we don't have any pending import jobs, and we don't have a build in-flight, and
         // something has been changed since the last build, we can start a build now. We need to
@@ -439,14 +633,12 @@ impl BuildJobs {
         let mut manifest_build_hash = 0;
         let mut asset_hashes = HashMap::default();
         for (asset_id, object) in editor_model.data_set().assets() {
-            let mut hash = editor_model
-                .data_set()
-                .hash_object(*asset_id, HashObjectMode::PropertiesOnly)
-                .unwrap();
-
-            if let Some(import_data) = editor_model.data_set().import_info(*asset_id) {
-                hash ^= import_data.import_data_contents_hash();
-            }
+            let hash = hash_asset_build_inputs(
+                editor_model.data_set(),
+                editor_model.schema_set(),
+                *asset_id,
+            )
+            .unwrap();
 
             if !editor_model.is_path_node_or_root(object.schema()) {
                 asset_hashes.insert(*asset_id, hash);
@@ -503,9 +695,12 @@ impl BuildJobs {
 
         self.job_executor.reset();
 
+        // Close any open undo context so the snapshot is taken at an undo step boundary
+        editor_model.flush_pending_edits();
+
         let data_set = {
-            profiling::scope!("Clone Dataset");
-            Arc::new(editor_model.data_set().clone())
+            profiling::scope!("Snapshot Dataset");
+            Arc::new(editor_model.data_set().snapshot())
         };
         let schema_set = editor_model.schema_set().clone();
 
@@ -520,7 +715,11 @@ impl BuildJobs {
             data_set,
             schema_set,
             manifest_build_hash: manifest_build_hash,
-            log_data: Default::default(),
+            asset_build_hashes: asset_hashes,
+            log_data: BuildLogData {
+                profile: import_jobs.take_import_profile(),
+                ..Default::default()
+            },
         });
 
         Ok(BuildStatus::Building(BuildStatusBuilding {
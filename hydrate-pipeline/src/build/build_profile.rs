@@ -0,0 +1,182 @@
+use hydrate_base::hashing::HashMap;
+use hydrate_base::AssetId;
+use serde::Serialize;
+use std::time::Duration;
+
+// How many assets are listed in the JSON report
+const BUILD_PROFILE_JSON_SLOWEST_ASSET_COUNT: usize = 20;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuildProfileEntry {
+    pub name: String,
+    // How many times the importer/builder/job ran
+    pub count: usize,
+    pub duration: Duration,
+}
+
+#[derive(Serialize)]
+struct BuildProfileEntryJson {
+    name: String,
+    count: usize,
+    seconds: f64,
+}
+
+#[derive(Serialize)]
+struct BuildProfileAssetJson {
+    asset_id: AssetId,
+    name: String,
+    seconds: f64,
+}
+
+#[derive(Serialize)]
+struct BuildProfileJson {
+    total_seconds: f64,
+    importers: Vec<BuildProfileEntryJson>,
+    builders: Vec<BuildProfileEntryJson>,
+    jobs: Vec<BuildProfileEntryJson>,
+    slowest_assets: Vec<BuildProfileAssetJson>,
+}
+
+// Where time went during a build. Imports that ran since the previous build started are included.
+// Time is summed across threads, so it can add up to more than total_duration.
+#[derive(Debug, Clone, Default)]
+pub struct BuildProfile {
+    pub(crate) total_duration: Duration,
+    // Keyed by importer name
+    importers: HashMap<String, BuildProfileEntry>,
+    // Keyed by the asset type the builder handles
+    builders: HashMap<String, BuildProfileEntry>,
+    // Keyed by job processor type name
+    jobs: HashMap<String, BuildProfileEntry>,
+    // A job shared by several assets counts toward all of them
+    assets: HashMap<AssetId, Duration>,
+}
+
+fn record_entry(
+    entries: &mut HashMap<String, BuildProfileEntry>,
+    name: &str,
+    duration: Duration,
+) {
+    let entry = entries
+        .entry(name.to_string())
+        .or_insert_with(|| BuildProfileEntry {
+            name: name.to_string(),
+            ..Default::default()
+        });
+    entry.count += 1;
+    entry.duration += duration;
+}
+
+// Slowest first
+fn sorted_entries(entries: &HashMap<String, BuildProfileEntry>) -> Vec<BuildProfileEntry> {
+    let mut entries: Vec<_> = entries.values().cloned().collect();
+    entries.sort_by(|a, b| b.duration.cmp(&a.duration).then(a.name.cmp(&b.name)));
+    entries
+}
+
+fn entries_json(entries: Vec<BuildProfileEntry>) -> Vec<BuildProfileEntryJson> {
+    entries
+        .into_iter()
+        .map(|x| BuildProfileEntryJson {
+            name: x.name,
+            count: x.count,
+            seconds: x.duration.as_secs_f64(),
+        })
+        .collect()
+}
+
+impl BuildProfile {
+    pub fn total_duration(&self) -> Duration {
+        self.total_duration
+    }
+
+    pub fn importer_durations(&self) -> Vec<BuildProfileEntry> {
+        sorted_entries(&self.importers)
+    }
+
+    pub fn builder_durations(&self) -> Vec<BuildProfileEntry> {
+        sorted_entries(&self.builders)
+    }
+
+    pub fn job_durations(&self) -> Vec<BuildProfileEntry> {
+        sorted_entries(&self.jobs)
+    }
+
+    pub fn asset_duration(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<Duration> {
+        self.assets.get(&asset_id).copied()
+    }
+
+    pub fn slowest_assets(
+        &self,
+        count: usize,
+    ) -> Vec<(AssetId, Duration)> {
+        let mut assets: Vec<_> = self
+            .assets
+            .iter()
+            .map(|(&asset_id, &duration)| (asset_id, duration))
+            .collect();
+        assets.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        assets.truncate(count);
+        assets
+    }
+
+    pub(crate) fn record_import(
+        &mut self,
+        importer_name: &str,
+        asset_ids: impl Iterator<Item = AssetId>,
+        duration: Duration,
+    ) {
+        record_entry(&mut self.importers, importer_name, duration);
+        for asset_id in asset_ids {
+            *self.assets.entry(asset_id).or_default() += duration;
+        }
+    }
+
+    pub(crate) fn record_builder(
+        &mut self,
+        asset_type: &str,
+        asset_id: AssetId,
+        duration: Duration,
+    ) {
+        record_entry(&mut self.builders, asset_type, duration);
+        *self.assets.entry(asset_id).or_default() += duration;
+    }
+
+    pub(crate) fn record_job(
+        &mut self,
+        job_type_name: &str,
+        asset_ids: &[AssetId],
+        duration: Duration,
+    ) {
+        record_entry(&mut self.jobs, job_type_name, duration);
+        for &asset_id in asset_ids {
+            *self.assets.entry(asset_id).or_default() += duration;
+        }
+    }
+
+    pub(crate) fn to_json(
+        &self,
+        asset_name: impl Fn(AssetId) -> String,
+    ) -> String {
+        let json = BuildProfileJson {
+            total_seconds: self.total_duration.as_secs_f64(),
+            importers: entries_json(self.importer_durations()),
+            builders: entries_json(self.builder_durations()),
+            jobs: entries_json(self.job_durations()),
+            slowest_assets: self
+                .slowest_assets(BUILD_PROFILE_JSON_SLOWEST_ASSET_COUNT)
+                .into_iter()
+                .map(|(asset_id, duration)| BuildProfileAssetJson {
+                    asset_id,
+                    name: asset_name(asset_id),
+                    seconds: duration.as_secs_f64(),
+                })
+                .collect(),
+        };
+
+        serde_json::to_string_pretty(&json).unwrap()
+    }
+}
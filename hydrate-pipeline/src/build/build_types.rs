@@ -1,11 +1,13 @@
-use super::{JobApi, JobId, JobProcessor, JobRequestor};
+use super::{BuildOutputSize, JobApi, JobId, JobProcessor, JobRequestor};
 use crate::{BuildLogEvent, LogEventLevel, PipelineResult};
 use hydrate_base::{ArtifactId, BuiltArtifactHeaderData};
 use hydrate_data::{AssetId, DataSet, SchemaSet};
 use std::cell::RefCell;
 use std::rc::Rc;
+use uuid::Uuid;
 
 pub struct BuiltArtifact {
+    pub job_id: JobId,
     pub asset_id: AssetId,
     pub artifact_id: ArtifactId,
     pub metadata: BuiltArtifactHeaderData,
@@ -13,7 +15,23 @@ pub struct BuiltArtifact {
     pub artifact_key_debug_name: Option<String>,
 }
 
+// Writes an artifact's data and returns its metadata
+pub type ArtifactDataWriter<'a> =
+    Box<dyn FnOnce(&mut dyn std::io::Write) -> PipelineResult<BuiltArtifactHeaderData> + 'a>;
+
+// Like BuiltArtifact, except the data is written straight to storage by write_data rather than
+// held in memory. The metadata is returned by write_data because the artifacts the data references
+// are only known once it has been serialized.
+pub struct StreamedArtifact<'a> {
+    pub job_id: JobId,
+    pub asset_id: AssetId,
+    pub artifact_id: ArtifactId,
+    pub write_data: ArtifactDataWriter<'a>,
+    pub artifact_key_debug_name: Option<String>,
+}
+
 pub struct WrittenArtifact {
+    pub job_id: JobId,
     pub asset_id: AssetId,
     pub artifact_id: ArtifactId,
     pub metadata: BuiltArtifactHeaderData,
@@ -83,6 +101,30 @@ pub trait Builder {
     // The type of asset that this builder handles
     fn asset_type(&self) -> &'static str;
 
+    // The type of asset that holds per-asset build settings for this builder, if it has any. An
+    // asset can be assigned a build settings asset of this type to change how it is built.
+    fn build_settings_type(&self) -> Option<&'static str> {
+        None
+    }
+
+    // The TypeUuid of each type of artifact this builder's jobs produce for an asset. This is what
+    // the built asset will be loaded as, so it tells tooling which storage needs to be registered.
+    fn artifact_types(&self) -> Vec<Uuid> {
+        Vec::new()
+    }
+
+    // Estimates what the asset's jobs will produce without running them, for builders that can
+    // work it out cheaply (e.g. from a texture's dimensions). Used to preview the size of a build.
+    // If None, the asset's output from the previous build is used as the estimate.
+    fn estimate_output(
+        &self,
+        _asset_id: AssetId,
+        _data_set: &DataSet,
+        _schema_set: &SchemaSet,
+    ) -> Option<BuildOutputSize> {
+        None
+    }
+
     fn start_jobs(
         &self,
         context: BuilderContext,
@@ -1,5 +1,6 @@
 use hydrate_data::{BuilderId, HashMap, SchemaFingerprint, SchemaSet};
 use std::sync::Arc;
+use uuid::Uuid;
 
 use super::build_types::*;
 
@@ -7,6 +8,7 @@ use super::build_types::*;
 pub struct BuilderRegistryInner {
     registered_builders: Vec<Box<dyn Builder>>,
     asset_type_to_builder: HashMap<SchemaFingerprint, BuilderId>,
+    asset_type_to_artifact_types: HashMap<SchemaFingerprint, Vec<Uuid>>,
 }
 
 #[derive(Clone)]
@@ -25,12 +27,32 @@ impl BuilderRegistry {
             .copied()
             .map(|x| &self.inner.registered_builders[x.0])
     }
+
+    // The artifact types that building an asset of the given schema produces. Empty if there is no
+    // builder for the schema, or the builder doesn't report what it produces.
+    pub fn artifact_types_for_asset(
+        &self,
+        fingerprint: SchemaFingerprint,
+    ) -> &[Uuid] {
+        self.inner
+            .asset_type_to_artifact_types
+            .get(&fingerprint)
+            .map(|x| x.as_slice())
+            .unwrap_or_default()
+    }
+
+    // Every buildable schema and the artifact types it produces
+    pub fn asset_type_to_artifact_types(&self) -> &HashMap<SchemaFingerprint, Vec<Uuid>> {
+        &self.inner.asset_type_to_artifact_types
+    }
 }
 
 // Keeps track of all known builders
 #[derive(Default)]
 pub struct BuilderRegistryBuilder {
-    registered_builders: Vec<Box<dyn Builder>>,
+    // Builders registered with a fingerprint handle only that exact schema. Otherwise the builder
+    // handles any schema matching the name returned by asset_type()
+    registered_builders: Vec<(Box<dyn Builder>, Option<SchemaFingerprint>)>,
 }
 
 impl BuilderRegistryBuilder {
@@ -38,8 +60,7 @@ impl BuilderRegistryBuilder {
     // Called before creating the schema to add handlers
     //
     pub fn register_handler<T: Builder + Default + 'static>(&mut self) {
-        let handler = Box::new(T::default());
-        self.registered_builders.push(handler);
+        self.register_handler_instance(T::default());
     }
 
     pub fn register_handler_instance<T: Builder + 'static>(
@@ -47,7 +68,26 @@ impl BuilderRegistryBuilder {
         instance: T,
     ) {
         let handler = Box::new(instance);
-        self.registered_builders.push(handler);
+        self.registered_builders.push((handler, None));
+    }
+
+    // Registers a builder for one particular version of a schema. This takes priority over builders
+    // registered by name, which allows old and new versions of a schema with the same name to be
+    // built by different builders.
+    pub fn register_handler_for_fingerprint<T: Builder + Default + 'static>(
+        &mut self,
+        fingerprint: SchemaFingerprint,
+    ) {
+        self.register_handler_instance_for_fingerprint(T::default(), fingerprint);
+    }
+
+    pub fn register_handler_instance_for_fingerprint<T: Builder + 'static>(
+        &mut self,
+        instance: T,
+        fingerprint: SchemaFingerprint,
+    ) {
+        let handler = Box::new(instance);
+        self.registered_builders.push((handler, Some(fingerprint)));
     }
 
     //
@@ -58,27 +98,52 @@ impl BuilderRegistryBuilder {
         schema_set: &SchemaSet,
     ) -> BuilderRegistry {
         let mut asset_type_to_builder = HashMap::default();
+        let mut asset_name_to_builder = HashMap::<&str, BuilderId>::default();
 
-        for (builder_index, builder) in self.registered_builders.iter().enumerate() {
+        for (builder_index, (builder, fingerprint)) in self.registered_builders.iter().enumerate() {
             let builder_id = BuilderId(builder_index);
-            let asset_type = schema_set
-                .find_named_type(builder.asset_type())
-                .unwrap()
-                .fingerprint();
-            let insert_result = asset_type_to_builder.insert(asset_type, builder_id);
-            // println!(
-            //     "builder {} handles asset fingerprint {}",
-            //     builder_id.0,
-            //     asset_type.as_uuid()
-            // );
+            let insert_result = if let Some(fingerprint) = fingerprint {
+                asset_type_to_builder.insert(*fingerprint, builder_id)
+            } else {
+                // Panics if the schema doesn't exist
+                schema_set.find_named_type(builder.asset_type()).unwrap();
+                asset_name_to_builder.insert(builder.asset_type(), builder_id)
+            };
             if insert_result.is_some() {
                 panic!("Multiple handlers registered to handle the same asset")
             }
         }
 
+        // Builders registered by name handle every version of the schema with that name that doesn't
+        // have a builder registered for its exact fingerprint
+        for (fingerprint, named_type) in schema_set.schemas() {
+            if let Some(builder_id) = asset_name_to_builder.get(named_type.name()) {
+                asset_type_to_builder
+                    .entry(*fingerprint)
+                    .or_insert(*builder_id);
+            }
+        }
+
+        let registered_builders: Vec<_> = self
+            .registered_builders
+            .into_iter()
+            .map(|(builder, _)| builder)
+            .collect();
+
+        let asset_type_to_artifact_types = asset_type_to_builder
+            .iter()
+            .map(|(fingerprint, builder_id)| {
+                (
+                    *fingerprint,
+                    registered_builders[builder_id.0].artifact_types(),
+                )
+            })
+            .collect();
+
         let inner = BuilderRegistryInner {
-            registered_builders: self.registered_builders,
+            registered_builders,
             asset_type_to_builder,
+            asset_type_to_artifact_types,
         };
 
         BuilderRegistry {
@@ -86,3 +151,106 @@ impl BuilderRegistryBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PipelineResult;
+    use hydrate_data::{SchemaLinker, SchemaNamedType, SchemaSetBuilder};
+
+    struct TestBuilder {
+        // Used to tell the builders apart
+        build_settings_type: &'static str,
+    }
+
+    impl Builder for TestBuilder {
+        fn asset_type(&self) -> &'static str {
+            "VersionedAsset"
+        }
+
+        fn build_settings_type(&self) -> Option<&'static str> {
+            Some(self.build_settings_type)
+        }
+
+        fn start_jobs(
+            &self,
+            _context: BuilderContext,
+        ) -> PipelineResult<()> {
+            Ok(())
+        }
+    }
+
+    fn versioned_asset_schema(field_names: &[&str]) -> SchemaNamedType {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("VersionedAsset", uuid::Uuid::nil(), |builder| {
+                for (i, field_name) in field_names.iter().enumerate() {
+                    builder.add_f32(*field_name, uuid::Uuid::from_u128(i as u128 + 1));
+                }
+            })
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        schema_set
+            .build()
+            .find_named_type("VersionedAsset")
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn builder_registered_by_fingerprint_takes_priority() {
+        // The current version of the schema is linked, an older version with the same name is
+        // restored alongside it
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("VersionedAsset", uuid::Uuid::nil(), |builder| {
+                builder.add_f32("x", uuid::Uuid::from_u128(1));
+                builder.add_f32("y", uuid::Uuid::from_u128(2));
+            })
+            .unwrap();
+        let old_schema = versioned_asset_schema(&["x"]);
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        schema_set.restore_named_types(vec![old_schema.clone()]);
+        let schema_set = schema_set.build();
+
+        let new_fingerprint = schema_set
+            .find_named_type("VersionedAsset")
+            .unwrap()
+            .fingerprint();
+        let old_fingerprint = old_schema.fingerprint();
+        assert_ne!(new_fingerprint, old_fingerprint);
+
+        let builder_for = |builder_registry: &BuilderRegistry, fingerprint| {
+            builder_registry
+                .builder_for_asset(fingerprint)
+                .unwrap()
+                .build_settings_type()
+                .unwrap()
+        };
+
+        let mut builder_registry = BuilderRegistryBuilder::default();
+        builder_registry.register_handler_instance(TestBuilder {
+            build_settings_type: "new",
+        });
+        builder_registry.register_handler_instance_for_fingerprint(
+            TestBuilder {
+                build_settings_type: "old",
+            },
+            old_fingerprint,
+        );
+        let builder_registry = builder_registry.build(&schema_set);
+        assert_eq!(builder_for(&builder_registry, new_fingerprint), "new");
+        assert_eq!(builder_for(&builder_registry, old_fingerprint), "old");
+
+        // Without a fingerprint match, the builder registered by name handles both versions
+        let mut builder_registry = BuilderRegistryBuilder::default();
+        builder_registry.register_handler_instance(TestBuilder {
+            build_settings_type: "new",
+        });
+        let builder_registry = builder_registry.build(&schema_set);
+        assert_eq!(builder_for(&builder_registry, new_fingerprint), "new");
+        assert_eq!(builder_for(&builder_registry, old_fingerprint), "new");
+    }
+}
@@ -0,0 +1,142 @@
+use super::{JobId, JobTypeId};
+use crate::PipelineResult;
+use hydrate_base::hashing::HashMap;
+use hydrate_base::uuid_path::uuid_to_path;
+use hydrate_base::ArtifactId;
+use serde::{Deserialize, Serialize};
+use siphasher::sip128::Hasher128;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Storage for the results of build jobs that can be shared between checkouts of a project, i.e.
+/// a directory on a network share that CI populates. Before a job runs, the cache is checked for
+/// a result produced from the same inputs. If there is one, the artifacts are fetched from the
+/// cache instead of running the job. Entries are opaque bytes looked up by a 128-bit key.
+pub trait BuildCacheProvider: Send + Sync {
+    /// Returns the entry stored for the key, or None if there isn't one
+    fn get(
+        &self,
+        key: u128,
+    ) -> PipelineResult<Option<Vec<u8>>>;
+
+    /// Stores an entry for the key, replacing any existing entry
+    fn put(
+        &self,
+        key: u128,
+        data: &[u8],
+    ) -> PipelineResult<()>;
+}
+
+/// A build cache that stores each entry as a file in a directory
+pub struct FileSystemBuildCacheProvider {
+    root_path: PathBuf,
+}
+
+impl FileSystemBuildCacheProvider {
+    pub fn new(root_path: &Path) -> Self {
+        FileSystemBuildCacheProvider {
+            root_path: root_path.to_path_buf(),
+        }
+    }
+
+    pub fn root_path(&self) -> &Path {
+        &self.root_path
+    }
+}
+
+impl BuildCacheProvider for FileSystemBuildCacheProvider {
+    fn get(
+        &self,
+        key: u128,
+    ) -> PipelineResult<Option<Vec<u8>>> {
+        let path = uuid_to_path(&self.root_path, Uuid::from_u128(key), "bc");
+        match std::fs::read(&path) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e)?,
+        }
+    }
+
+    fn put(
+        &self,
+        key: u128,
+        data: &[u8],
+    ) -> PipelineResult<()> {
+        let path = uuid_to_path(&self.root_path, Uuid::from_u128(key), "bc");
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // Other processes may be reading the cache, so the entry is written to a temporary file
+        // and renamed into place to avoid them seeing a partially written entry
+        let temp_path = path.with_extension(format!("{}.tmp", Uuid::new_v4().simple()));
+        std::fs::write(&temp_path, data)?;
+        if let Err(e) = std::fs::rename(&temp_path, &path) {
+            let _ = std::fs::remove_file(&temp_path);
+            Err(e)?;
+        }
+
+        Ok(())
+    }
+}
+
+// The key for a job's cached result. The job ID is a hash of the job's input, so together with the
+// processor version and the built data the job reads, this covers everything that determines
+// its result except the assets it reads. Those are checked against CachedJob::asset_hashes.
+pub(crate) fn job_cache_key(
+    job_type: JobTypeId,
+    job_version: u32,
+    job_id: JobId,
+    built_data_hashes: &HashMap<ArtifactId, u64>,
+) -> u128 {
+    let mut built_data_hashes: Vec<_> = built_data_hashes.iter().collect();
+    built_data_hashes.sort();
+
+    let mut hasher = siphasher::sip128::SipHasher::default();
+    "job".hash(&mut hasher);
+    job_type.hash(&mut hasher);
+    job_version.hash(&mut hasher);
+    job_id.hash(&mut hasher);
+    built_data_hashes.hash(&mut hasher);
+    hasher.finish128().as_u128()
+}
+
+// The key for the contents of a built artifact, including its header
+pub(crate) fn artifact_cache_key(
+    artifact_id: ArtifactId,
+    build_hash: u64,
+) -> u128 {
+    let mut hasher = siphasher::sip128::SipHasher::default();
+    "artifact".hash(&mut hasher);
+    artifact_id.hash(&mut hasher);
+    build_hash.hash(&mut hasher);
+    hasher.finish128().as_u128()
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct CachedJobArtifact {
+    pub(crate) asset_id: Uuid,
+    pub(crate) artifact_id: Uuid,
+    pub(crate) build_hash: u64,
+    pub(crate) artifact_key_debug_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CachedJob {
+    // Build input hashes of the assets the job read. The result is only reused if they all match.
+    pub(crate) asset_hashes: Vec<(Uuid, u64)>,
+    pub(crate) artifacts: Vec<CachedJobArtifact>,
+    // (asset, artifact) pairs of the handles the job created
+    pub(crate) artifact_handles: Vec<(Uuid, Uuid)>,
+    pub(crate) output_data: Vec<u8>,
+}
+
+// What a running job produced, so that it can be stored in the build cache when the job completes
+#[derive(Default)]
+pub(crate) struct JobRecording {
+    pub(crate) artifacts: Vec<CachedJobArtifact>,
+    pub(crate) artifact_handles: Vec<(Uuid, Uuid)>,
+    // A cache hit doesn't request any jobs, so jobs that request other jobs aren't cached
+    pub(crate) requested_jobs: bool,
+}
@@ -1,19 +1,21 @@
-use crate::build::{BuiltArtifact, WrittenArtifact};
-use crate::import::ImportData;
-use crate::{BuildLogData, BuildLogEvent, LogEventLevel, PipelineResult};
+use crate::build::{BuiltArtifact, StreamedArtifact, WrittenArtifact};
+use crate::import::{ImportData, ImporterRegistry};
+use crate::{
+    BuildLogData, BuildLogEvent, HydrateProjectConfiguration, LogEventLevel, PipelineResult,
+};
 use crossbeam_channel::{Receiver, Sender};
-use hydrate_base::hashing::HashMap;
+use hydrate_base::hashing::{HashMap, HashSet};
 use hydrate_base::uuid_path::uuid_and_hash_to_path;
-use hydrate_base::{ArtifactId, AssetId};
-use hydrate_data::{DataSet, SchemaSet};
+use hydrate_base::{ArtifactId, AssetId, BuiltArtifactHeaderData};
+use hydrate_data::{DataSet, DataSetView, ImportDataStorage, SchemaSet};
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
-use std::hash::Hasher;
-use std::io::{BufWriter, Write};
+use std::hash::{BuildHasher, Hasher};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 use std::panic::RefUnwindSafe;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use super::*;
 
@@ -57,6 +59,7 @@ where
         job_api: &dyn JobApi,
         fetched_asset_data: &mut HashMap<AssetId, FetchedAssetData>,
         fetched_import_data: &mut HashMap<AssetId, FetchedImportData>,
+        fetched_built_data: &HashMap<ArtifactId, FetchedBuiltData>,
         log_events: &mut Vec<BuildLogEvent>,
     ) -> PipelineResult<Arc<Vec<u8>>> {
         let data: <T as JobProcessor>::InputT = bincode::deserialize(input.as_slice()).unwrap();
@@ -69,6 +72,7 @@ where
                 schema_set,
                 fetched_asset_data: &Rc::new(RefCell::new(fetched_asset_data)),
                 fetched_import_data: &Rc::new(RefCell::new(fetched_import_data)),
+                fetched_built_data,
                 job_api,
                 log_events: &Rc::new(RefCell::new(log_events)),
             })
@@ -91,12 +95,29 @@ where
 //     downstream_jobs: Vec<QueuedJob>,
 // }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum JobCancellation {
+    NotCancelled,
+    // The job was never sent to the thread pool. Its output is set to an error.
+    BeforeRunning,
+    // The job was already running. Anything it produces is discarded. If the same job is requested
+    // again before it finishes, it is run again once it finishes.
+    WhileRunning { rerun: bool },
+}
+
 struct JobState {
     job_type: JobTypeId,
     dependencies: Arc<JobEnumeratedDependencies>,
     input_data: Arc<Vec<u8>>,
     debug_name: Arc<String>,
 
+    // The asset whose builder requested this job, directly or through other jobs
+    originating_asset: Option<AssetId>,
+    // Which run of the originating asset's builder requested this job. Jobs from older runs are
+    // superseded by jobs of the same type from newer runs.
+    builder_run: u64,
+    cancellation: JobCancellation,
+
     // When we send the job to the thread pool, this is set to true
     has_been_scheduled: bool,
     // This would eventually be stored on file system
@@ -107,6 +128,19 @@ struct JobStateOutput {
     _output_data: PipelineResult<Arc<Vec<u8>>>,
     _fetched_asset_data: HashMap<AssetId, FetchedAssetData>,
     _fetched_import_data: HashMap<AssetId, FetchedImportData>,
+    // Build hashes of the artifacts the job read
+    _fetched_built_data: HashMap<ArtifactId, u64>,
+}
+
+impl JobStateOutput {
+    fn cancelled() -> Self {
+        JobStateOutput {
+            _output_data: Err("Job was cancelled".into()),
+            _fetched_asset_data: Default::default(),
+            _fetched_import_data: Default::default(),
+            _fetched_built_data: Default::default(),
+        }
+    }
 }
 
 //TODO: Future optimization, we clone this and it could be big, especially when we re-run jobs. We
@@ -119,6 +153,8 @@ struct QueuedJob {
     input_data: Arc<Vec<u8>>,
     dependencies: PipelineResult<JobEnumeratedDependencies>,
     debug_name: Arc<String>,
+    // Only meaningful for jobs requested by a builder. Other jobs inherit it from their requestor.
+    builder_run: u64,
 }
 
 #[derive(Default)]
@@ -207,12 +243,17 @@ impl JobProcessorRegistry {
 
 struct JobApiImplInner {
     schema_set: SchemaSet,
-    import_data_root_path: PathBuf,
-    build_data_root_path: PathBuf,
+    project_config: HydrateProjectConfiguration,
+    importer_registry: ImporterRegistry,
     job_processor_registry: JobProcessorRegistry,
     job_create_queue_tx: Sender<QueuedJob>,
     artifact_handle_created_tx: Sender<AssetArtifactIdPair>,
     written_artifact_queue_tx: Sender<WrittenArtifact>,
+    // The most recent run of each asset's builder in this job batch
+    builder_runs: Mutex<HashMap<AssetId, u64>>,
+    build_cache: Mutex<Option<Arc<dyn BuildCacheProvider>>>,
+    // What running jobs have produced so far, only tracked if there is a build cache
+    job_recordings: Mutex<HashMap<JobId, JobRecording>>,
 }
 
 #[derive(Clone)]
@@ -244,6 +285,30 @@ impl JobApi for JobApiImpl {
             .get(new_job.job_type)
             .unwrap();
 
+        if let JobRequestor::Job(requestor_job_id) = job_requestor {
+            if let Some(recording) = self
+                .inner
+                .job_recordings
+                .lock()
+                .unwrap()
+                .get_mut(&requestor_job_id)
+            {
+                recording.requested_jobs = true;
+            }
+        }
+
+        let builder_run = match job_requestor {
+            JobRequestor::Builder(asset_id) => self
+                .inner
+                .builder_runs
+                .lock()
+                .unwrap()
+                .get(&asset_id)
+                .copied()
+                .unwrap_or_default(),
+            JobRequestor::Job(_) => 0,
+        };
+
         let dependencies = processor.enumerate_dependencies_inner(
             job_id,
             job_requestor,
@@ -262,6 +327,7 @@ impl JobApi for JobApiImpl {
                 input_data: Arc::new(new_job.input_data),
                 dependencies,
                 debug_name: Arc::new(debug_name),
+                builder_run,
             })
             .unwrap();
 
@@ -270,9 +336,16 @@ impl JobApi for JobApiImpl {
 
     fn artifact_handle_created(
         &self,
+        job_id: JobId,
         asset_id: AssetId,
         artifact_id: ArtifactId,
     ) {
+        if let Some(recording) = self.inner.job_recordings.lock().unwrap().get_mut(&job_id) {
+            recording
+                .artifact_handles
+                .push((asset_id.as_uuid(), artifact_id.as_uuid()));
+        }
+
         //TODO: Is this necessary, can we handle it when the job result is returned?
         self.inner
             .artifact_handle_created_tx
@@ -291,7 +364,11 @@ impl JobApi for JobApiImpl {
         //
         // Hash the artifact
         //
-        let mut hasher = siphasher::sip::SipHasher::default();
+        let mut hasher = self
+            .inner
+            .project_config
+            .content_hash_algorithm
+            .build_hasher();
         artifact.data.hash(&mut hasher);
         artifact.metadata.hash(&mut hasher);
         let build_hash = hasher.finish();
@@ -300,7 +377,7 @@ impl JobApi for JobApiImpl {
         // Determine where we will store the asset and ensure the directory exists
         //
         let path = uuid_and_hash_to_path(
-            &self.inner.build_data_root_path,
+            &self.inner.project_config.build_data_path,
             artifact.artifact_id.as_uuid(),
             build_hash,
             "bf",
@@ -316,7 +393,19 @@ impl JobApi for JobApiImpl {
         let file = std::fs::File::create(&path).unwrap();
         let mut buf_writer = BufWriter::new(file);
         artifact.metadata.write_header(&mut buf_writer).unwrap();
-        buf_writer.write(&artifact.data).unwrap();
+        buf_writer.write_all(&artifact.data).unwrap();
+
+        // Other jobs may read the artifact as soon as we report it as written
+        buf_writer.flush().unwrap();
+        drop(buf_writer);
+
+        self.record_artifact(
+            artifact.job_id,
+            artifact.asset_id,
+            artifact.artifact_id,
+            build_hash,
+            &artifact.artifact_key_debug_name,
+        );
 
         //
         // Send info about the written asset back to main thread for inclusion in the manifest
@@ -325,6 +414,7 @@ impl JobApi for JobApiImpl {
         self.inner
             .written_artifact_queue_tx
             .send(WrittenArtifact {
+                job_id: artifact.job_id,
                 asset_id: artifact.asset_id,
                 artifact_id: artifact.artifact_id,
                 metadata: artifact.metadata,
@@ -334,15 +424,322 @@ impl JobApi for JobApiImpl {
             .unwrap();
     }
 
+    fn produce_artifact_streaming(
+        &self,
+        artifact: StreamedArtifact,
+    ) -> PipelineResult<()> {
+        profiling::scope!("Stream Asset to Disk");
+        // The final path depends on the hash of the data, so the data goes to a temporary file
+        // until it has all been written
+        let temp_dir = self.inner.project_config.build_data_path.join("tmp");
+        std::fs::create_dir_all(&temp_dir)?;
+        let temp_path = temp_dir.join(format!("{}.tmp", uuid::Uuid::new_v4()));
+
+        let result = self.write_streamed_artifact(&temp_path, artifact);
+        if temp_path.exists() {
+            std::fs::remove_file(&temp_path)?;
+        }
+
+        result
+    }
+
     fn fetch_import_data(
         &self,
+        data_set: &DataSet,
         asset_id: AssetId,
     ) -> PipelineResult<ImportData> {
-        crate::import::load_import_data(
-            &self.inner.import_data_root_path,
-            &self.inner.schema_set,
-            asset_id,
-        )
+        let import_data_storage = data_set
+            .import_info(asset_id)
+            .map(|x| x.import_data_storage())
+            .unwrap_or_default();
+
+        match import_data_storage {
+            ImportDataStorage::Copied => crate::import::load_import_data(
+                &self.inner.project_config,
+                &self.inner.schema_set,
+                asset_id,
+            ),
+            ImportDataStorage::External => crate::import::load_external_import_data(
+                &self.inner.project_config,
+                &self.inner.importer_registry,
+                &self.inner.schema_set,
+                data_set,
+                asset_id,
+            ),
+        }
+    }
+
+    fn fetch_built_artifact(
+        &self,
+        artifact_id: ArtifactId,
+        build_hash: u64,
+    ) -> PipelineResult<Vec<u8>> {
+        let path = uuid_and_hash_to_path(
+            &self.inner.project_config.build_data_path,
+            artifact_id.as_uuid(),
+            build_hash,
+            "bf",
+        );
+
+        let mut reader = BufReader::new(std::fs::File::open(&path)?);
+        BuiltArtifactHeaderData::read_header(&mut reader)?;
+        let mut data = Vec::default();
+        reader.read_to_end(&mut data)?;
+        Ok(data)
+    }
+}
+
+impl JobApiImpl {
+    fn record_artifact(
+        &self,
+        job_id: JobId,
+        asset_id: AssetId,
+        artifact_id: ArtifactId,
+        build_hash: u64,
+        artifact_key_debug_name: &Option<String>,
+    ) {
+        if let Some(recording) = self.inner.job_recordings.lock().unwrap().get_mut(&job_id) {
+            recording.artifacts.push(CachedJobArtifact {
+                asset_id: asset_id.as_uuid(),
+                artifact_id: artifact_id.as_uuid(),
+                build_hash,
+                artifact_key_debug_name: artifact_key_debug_name.clone(),
+            });
+        }
+    }
+
+    pub(crate) fn build_cache(&self) -> Option<Arc<dyn BuildCacheProvider>> {
+        self.inner.build_cache.lock().unwrap().clone()
+    }
+
+    pub(crate) fn begin_job_recording(
+        &self,
+        job_id: JobId,
+    ) {
+        self.inner
+            .job_recordings
+            .lock()
+            .unwrap()
+            .insert(job_id, JobRecording::default());
+    }
+
+    pub(crate) fn end_job_recording(
+        &self,
+        job_id: JobId,
+    ) -> Option<JobRecording> {
+        self.inner.job_recordings.lock().unwrap().remove(&job_id)
+    }
+
+    // Copies the artifacts of a job's cached result into the build data and reports them as if the
+    // job had written them. Returns the job's output, or None if there is no usable cached result.
+    pub(crate) fn fetch_cached_job(
+        &self,
+        build_cache: &dyn BuildCacheProvider,
+        cache_key: u128,
+        data_set: &DataSet,
+        job_id: JobId,
+    ) -> PipelineResult<Option<Arc<Vec<u8>>>> {
+        let Some(cached_job_bytes) = build_cache.get(cache_key)? else {
+            return Ok(None);
+        };
+        let Ok(cached_job) = bincode::deserialize::<CachedJob>(&cached_job_bytes) else {
+            return Ok(None);
+        };
+
+        for &(asset_id, hash) in &cached_job.asset_hashes {
+            let current_hash = crate::build::hash_asset_build_inputs(
+                data_set,
+                &self.inner.schema_set,
+                AssetId::from_uuid(asset_id),
+            );
+            if current_hash.ok() != Some(hash) {
+                return Ok(None);
+            }
+        }
+
+        // Fetch everything before writing anything so that a partial hit doesn't leave files behind
+        let mut artifacts_to_write = Vec::default();
+        let mut written_artifacts = Vec::default();
+        for artifact in &cached_job.artifacts {
+            let artifact_id = ArtifactId::from_uuid(artifact.artifact_id);
+            let path = uuid_and_hash_to_path(
+                &self.inner.project_config.build_data_path,
+                artifact.artifact_id,
+                artifact.build_hash,
+                "bf",
+            );
+
+            let metadata = if path.exists() {
+                BuiltArtifactHeaderData::read_header(&mut BufReader::new(std::fs::File::open(
+                    &path,
+                )?))?
+            } else {
+                let Some(artifact_bytes) =
+                    build_cache.get(artifact_cache_key(artifact_id, artifact.build_hash))?
+                else {
+                    return Ok(None);
+                };
+                let metadata =
+                    BuiltArtifactHeaderData::read_header(&mut artifact_bytes.as_slice())?;
+                artifacts_to_write.push((path, artifact_bytes));
+                metadata
+            };
+
+            written_artifacts.push(WrittenArtifact {
+                job_id,
+                asset_id: AssetId::from_uuid(artifact.asset_id),
+                artifact_id,
+                metadata,
+                build_hash: artifact.build_hash,
+                artifact_key_debug_name: artifact.artifact_key_debug_name.clone(),
+            });
+        }
+
+        for (path, artifact_bytes) in artifacts_to_write {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, artifact_bytes)?;
+        }
+
+        for &(asset_id, artifact_id) in &cached_job.artifact_handles {
+            self.artifact_handle_created(
+                job_id,
+                AssetId::from_uuid(asset_id),
+                ArtifactId::from_uuid(artifact_id),
+            );
+        }
+
+        for written_artifact in written_artifacts {
+            self.inner
+                .written_artifact_queue_tx
+                .send(written_artifact)
+                .unwrap();
+        }
+
+        Ok(Some(Arc::new(cached_job.output_data)))
+    }
+
+    // Stores the result of a job that completed successfully in the build cache
+    pub(crate) fn store_cached_job(
+        &self,
+        build_cache: &dyn BuildCacheProvider,
+        cache_key: u128,
+        data_set: &DataSet,
+        read_assets: impl Iterator<Item = AssetId>,
+        recording: JobRecording,
+        output_data: &[u8],
+    ) -> PipelineResult<()> {
+        let mut asset_hashes = Vec::default();
+        for asset_id in read_assets {
+            let hash =
+                crate::build::hash_asset_build_inputs(data_set, &self.inner.schema_set, asset_id)?;
+            asset_hashes.push((asset_id.as_uuid(), hash));
+        }
+
+        for artifact in &recording.artifacts {
+            let path = uuid_and_hash_to_path(
+                &self.inner.project_config.build_data_path,
+                artifact.artifact_id,
+                artifact.build_hash,
+                "bf",
+            );
+            build_cache.put(
+                artifact_cache_key(
+                    ArtifactId::from_uuid(artifact.artifact_id),
+                    artifact.build_hash,
+                ),
+                &std::fs::read(&path)?,
+            )?;
+        }
+
+        // The job is stored last so that the artifacts it refers to are always in the cache
+        let cached_job = CachedJob {
+            asset_hashes,
+            artifacts: recording.artifacts,
+            artifact_handles: recording.artifact_handles,
+            output_data: output_data.to_vec(),
+        };
+        build_cache.put(cache_key, &bincode::serialize(&cached_job)?)
+    }
+
+    fn write_streamed_artifact(
+        &self,
+        temp_path: &Path,
+        artifact: StreamedArtifact,
+    ) -> PipelineResult<()> {
+        let mut temp_writer = BufWriter::new(std::fs::File::create(temp_path)?);
+        let metadata = (artifact.write_data)(&mut temp_writer)?;
+        temp_writer.flush()?;
+        drop(temp_writer);
+
+        //
+        // Hash the artifact. This reads the data back in chunks and hashes it the same way as the
+        // data of a BuiltArtifact, so the build hash doesn't depend on how it was produced.
+        //
+        let mut hasher = self
+            .inner
+            .project_config
+            .content_hash_algorithm
+            .build_hasher();
+        hasher.write_usize(temp_path.metadata()?.len() as usize);
+        let mut reader = BufReader::new(std::fs::File::open(temp_path)?);
+        loop {
+            let chunk = reader.fill_buf()?;
+            if chunk.is_empty() {
+                break;
+            }
+
+            hasher.write(chunk);
+            let chunk_length = chunk.len();
+            reader.consume(chunk_length);
+        }
+        metadata.hash(&mut hasher);
+        let build_hash = hasher.finish();
+
+        //
+        // Copy the data after the header into its final location
+        //
+        let path = uuid_and_hash_to_path(
+            &self.inner.project_config.build_data_path,
+            artifact.artifact_id.as_uuid(),
+            build_hash,
+            "bf",
+        );
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut buf_writer = BufWriter::new(std::fs::File::create(&path)?);
+        metadata.write_header(&mut buf_writer)?;
+        std::io::copy(&mut std::fs::File::open(temp_path)?, &mut buf_writer)?;
+
+        // Other jobs may read the artifact as soon as we report it as written
+        buf_writer.flush()?;
+        drop(buf_writer);
+
+        self.record_artifact(
+            artifact.job_id,
+            artifact.asset_id,
+            artifact.artifact_id,
+            build_hash,
+            &artifact.artifact_key_debug_name,
+        );
+
+        self.inner
+            .written_artifact_queue_tx
+            .send(WrittenArtifact {
+                job_id: artifact.job_id,
+                asset_id: artifact.asset_id,
+                artifact_id: artifact.artifact_id,
+                metadata,
+                build_hash,
+                artifact_key_debug_name: artifact.artifact_key_debug_name,
+            })
+            .unwrap();
+
+        Ok(())
     }
 }
 
@@ -361,6 +758,8 @@ pub struct JobExecutor {
 
     // All the jobs that we have run or will run in this job batch
     current_jobs: HashMap<JobId, JobState>,
+    // The jobs of each type requested by the most recent builder run of each asset, and that run
+    asset_jobs: HashMap<(AssetId, JobTypeId), (u64, Vec<JobId>)>,
 
     // Queue for jobs to request additional jobs to run
     job_create_queue_rx: Receiver<QueuedJob>,
@@ -369,6 +768,21 @@ pub struct JobExecutor {
     artifact_handle_created_rx: Receiver<AssetArtifactIdPair>,
 
     written_artifact_queue_rx: Receiver<WrittenArtifact>,
+    // Artifacts pulled off the written artifact queue that haven't been taken yet
+    written_artifacts: Vec<WrittenArtifact>,
+
+    // Artifacts written by jobs in this job batch that haven't completed yet
+    running_job_artifacts: HashMap<JobId, Vec<ArtifactId>>,
+    // All artifacts written in this job batch. Jobs that read built data wait for it to be in here
+    batch_written_artifacts: HashSet<ArtifactId>,
+    // Assets that produce built data read by jobs in this job batch, these need to be built too
+    built_data_requests: Vec<AssetId>,
+    // Artifacts produced by the most recent successful run of each job. This persists across job
+    // batches so that we can tell when a job stops producing an artifact.
+    job_artifacts: HashMap<JobId, Vec<ArtifactId>>,
+    artifact_build_hashes: HashMap<ArtifactId, u64>,
+    // Artifacts that are no longer produced by any job and have been removed from the build data
+    removed_artifacts: Vec<ArtifactId>,
 
     thread_pool_result_rx: Receiver<JobExecutorThreadPoolOutcome>,
     thread_pool: Option<JobExecutorThreadPool>,
@@ -388,16 +802,21 @@ impl JobExecutor {
     pub fn reset(&mut self) {
         assert!(self.is_idle());
         self.current_jobs.clear();
+        self.asset_jobs.clear();
+        self.job_api_impl.inner.builder_runs.lock().unwrap().clear();
+        self.running_job_artifacts.clear();
+        self.batch_written_artifacts.clear();
+        self.built_data_requests.clear();
         self.completed_job_count = 0;
     }
 
     pub fn new(
         schema_set: &SchemaSet,
         job_processor_registry: &JobProcessorRegistry,
-        import_data_root_path: PathBuf,
-        job_data_root_path: PathBuf,
-        build_data_root_path: PathBuf,
+        importer_registry: &ImporterRegistry,
+        project_config: &HydrateProjectConfiguration,
     ) -> Self {
+        let job_data_root_path = project_config.job_data_path.clone();
         let (job_create_queue_tx, job_create_queue_rx) = crossbeam_channel::unbounded();
         //let (job_completed_queue_tx, job_completed_queue_rx) = crossbeam_channel::unbounded();
         //let (built_asset_queue_tx, built_asset_queue_rx) = crossbeam_channel::unbounded();
@@ -409,25 +828,27 @@ impl JobExecutor {
         let job_api_impl = JobApiImpl {
             inner: Arc::new(JobApiImplInner {
                 schema_set: schema_set.clone(),
-                import_data_root_path: import_data_root_path.clone(),
-                build_data_root_path,
+                project_config: project_config.clone(),
+                importer_registry: importer_registry.clone(),
                 job_processor_registry: job_processor_registry.clone(),
                 job_create_queue_tx,
                 artifact_handle_created_tx,
                 written_artifact_queue_tx,
+                builder_runs: Default::default(),
+                build_cache: Mutex::new(project_config.build_cache_path.as_ref().map(|x| {
+                    Arc::new(FileSystemBuildCacheProvider::new(x)) as Arc<dyn BuildCacheProvider>
+                })),
+                job_recordings: Default::default(),
             }),
         };
 
-        let thread_count = num_cpus::get();
-        //let thread_count = 1;
-
         let (thread_pool_result_tx, thread_pool_result_rx) = crossbeam_channel::unbounded();
         let thread_pool = JobExecutorThreadPool::new(
             job_processor_registry.clone(),
             schema_set.clone(),
             &job_data_root_path,
             job_api_impl.clone(),
-            thread_count,
+            Self::thread_count(project_config),
             thread_pool_result_tx,
         );
 
@@ -437,6 +858,7 @@ impl JobExecutor {
             job_processor_registry: job_processor_registry.clone(),
             //job_history: Default::default(),
             current_jobs: Default::default(),
+            asset_jobs: Default::default(),
             //job_create_queue_tx,
             job_create_queue_rx,
             //job_completed_queue_tx,
@@ -447,6 +869,13 @@ impl JobExecutor {
             artifact_handle_created_rx,
             //built_artifact_queue_tx,
             written_artifact_queue_rx,
+            written_artifacts: Default::default(),
+            running_job_artifacts: Default::default(),
+            batch_written_artifacts: Default::default(),
+            built_data_requests: Default::default(),
+            job_artifacts: Default::default(),
+            artifact_build_hashes: Default::default(),
+            removed_artifacts: Default::default(),
             thread_pool_result_rx,
             thread_pool: Some(thread_pool),
             completed_job_count: 0,
@@ -454,10 +883,76 @@ impl JobExecutor {
         }
     }
 
+    fn thread_count(project_config: &HydrateProjectConfiguration) -> usize {
+        project_config
+            .build_thread_count
+            .unwrap_or_else(num_cpus::get)
+            .max(1)
+    }
+
+    // Jobs read import data and write build data using the paths in project_config. The worker
+    // threads hold the old configuration, so they are replaced.
+    pub fn relocate(
+        &mut self,
+        project_config: &HydrateProjectConfiguration,
+    ) {
+        assert!(self.is_idle());
+        let old_inner = self.job_api_impl.inner.clone();
+        self.job_api_impl = JobApiImpl {
+            inner: Arc::new(JobApiImplInner {
+                schema_set: old_inner.schema_set.clone(),
+                project_config: project_config.clone(),
+                importer_registry: old_inner.importer_registry.clone(),
+                job_processor_registry: old_inner.job_processor_registry.clone(),
+                job_create_queue_tx: old_inner.job_create_queue_tx.clone(),
+                artifact_handle_created_tx: old_inner.artifact_handle_created_tx.clone(),
+                written_artifact_queue_tx: old_inner.written_artifact_queue_tx.clone(),
+                builder_runs: Default::default(),
+                build_cache: Mutex::new(old_inner.build_cache.lock().unwrap().clone()),
+                job_recordings: Default::default(),
+            }),
+        };
+
+        self.thread_pool.take().unwrap().finish();
+        let (thread_pool_result_tx, thread_pool_result_rx) = crossbeam_channel::unbounded();
+        self.thread_pool_result_rx = thread_pool_result_rx;
+        self.thread_pool = Some(JobExecutorThreadPool::new(
+            self.job_processor_registry.clone(),
+            self.job_api_impl.inner.schema_set.clone(),
+            &self._root_path,
+            self.job_api_impl.clone(),
+            Self::thread_count(project_config),
+            thread_pool_result_tx,
+        ));
+    }
+
     pub fn job_api(&self) -> &dyn JobApi {
         &self.job_api_impl
     }
 
+    // Jobs check the build cache for a result before running and store their result in it after
+    pub fn set_build_cache_provider(
+        &mut self,
+        build_cache: Option<Arc<dyn BuildCacheProvider>>,
+    ) {
+        *self.job_api_impl.inner.build_cache.lock().unwrap() = build_cache;
+    }
+
+    // Deletes all build data and forgets what previous job runs produced, so the next run of every
+    // job writes its artifacts from scratch
+    pub fn clear_build_data(&mut self) -> PipelineResult<()> {
+        assert!(self.is_idle());
+        let build_data_path = &self.job_api_impl.inner.project_config.build_data_path;
+        if build_data_path.exists() {
+            std::fs::remove_dir_all(build_data_path)?;
+        }
+
+        self.job_artifacts.clear();
+        self.artifact_build_hashes.clear();
+        self.removed_artifacts.clear();
+        Ok(())
+    }
+
     // pub fn take_built_assets(&self) -> Vec<BuiltAsset> {
     //     let mut built_assets = Vec::default();
     //     while let Ok(built_asset) = self.built_asset_queue_rx.try_recv() {
@@ -467,12 +962,28 @@ impl JobExecutor {
     //     built_assets
     // }
 
+    // The artifacts each job produced the last time it ran successfully
+    pub fn job_artifacts(&self) -> &HashMap<JobId, Vec<ArtifactId>> {
+        &self.job_artifacts
+    }
+
+    // Artifacts that a job produced previously but stopped producing when it was re-run. Their
+    // build data has already been deleted.
+    pub fn take_removed_artifacts(&mut self) -> Vec<ArtifactId> {
+        std::mem::take(&mut self.removed_artifacts)
+    }
+
+    // Assets that need to be built because a job reads their built data
+    pub fn take_built_data_requests(&mut self) -> Vec<AssetId> {
+        std::mem::take(&mut self.built_data_requests)
+    }
+
     pub fn take_written_artifacts(
-        &self,
+        &mut self,
         artifact_asset_lookup: &mut HashMap<ArtifactId, AssetId>,
     ) -> Vec<WrittenArtifact> {
         let mut written_artifacts = Vec::default();
-        while let Ok(written_artifact) = self.written_artifact_queue_rx.try_recv() {
+        for written_artifact in std::mem::take(&mut self.written_artifacts) {
             let old = artifact_asset_lookup
                 .insert(written_artifact.artifact_id, written_artifact.asset_id);
             //assert!(old.is_none());
@@ -510,21 +1021,55 @@ impl JobExecutor {
                 .entry(queued_job.job_id)
                 .or_default()
                 .push(queued_job.job_requestor);
+
+            // Jobs requested by other jobs belong to the same asset and builder run as their requestor
+            let (originating_asset, builder_run, requestor_cancelled) = match queued_job
+                .job_requestor
+            {
+                JobRequestor::Builder(asset_id) => (Some(asset_id), queued_job.builder_run, false),
+                JobRequestor::Job(requestor_job_id) => self
+                    .current_jobs
+                    .get(&requestor_job_id)
+                    .map(|x| {
+                        (
+                            x.originating_asset,
+                            x.builder_run,
+                            x.cancellation != JobCancellation::NotCancelled,
+                        )
+                    })
+                    .unwrap_or((None, 0, false)),
+            };
+            let is_stale = originating_asset
+                .map(|asset_id| builder_run < self.latest_builder_run(asset_id))
+                .unwrap_or(false);
+
+            let job_id = queued_job.job_id;
+            let job_type = queued_job.job_type;
             // If key exists, we already queued a job with these exact inputs and we can reuse the outputs
-            if !self.current_jobs.contains_key(&queued_job.job_id) {
+            let is_new_job = !self.current_jobs.contains_key(&job_id);
+            if is_new_job {
                 assert!(self
                     .job_processor_registry
                     .contains_key(queued_job.job_type));
 
                 let job_state = match queued_job.dependencies {
-                    Ok(dependencies) => JobState {
-                        job_type: queued_job.job_type,
-                        dependencies: Arc::new(dependencies),
-                        input_data: queued_job.input_data,
-                        debug_name: queued_job.debug_name,
-                        has_been_scheduled: false,
-                        output_data: None,
-                    },
+                    Ok(dependencies) => {
+                        for built_data in &dependencies.built_data {
+                            self.built_data_requests.push(built_data.asset_id);
+                        }
+
+                        JobState {
+                            job_type: queued_job.job_type,
+                            dependencies: Arc::new(dependencies),
+                            input_data: queued_job.input_data,
+                            debug_name: queued_job.debug_name,
+                            originating_asset,
+                            builder_run,
+                            cancellation: JobCancellation::NotCancelled,
+                            has_been_scheduled: false,
+                            output_data: None,
+                        }
+                    }
                     Err(e) => {
                         let log_error = BuildLogEvent {
                             job_id: Some(queued_job.job_id),
@@ -543,39 +1088,226 @@ impl JobExecutor {
                             dependencies: Arc::new(JobEnumeratedDependencies::default()),
                             input_data: queued_job.input_data,
                             debug_name: queued_job.debug_name,
+                            originating_asset,
+                            builder_run,
+                            cancellation: JobCancellation::NotCancelled,
                             has_been_scheduled: true,
                             output_data: Some(JobStateOutput {
                                 _output_data: Err(e),
                                 _fetched_asset_data: Default::default(),
                                 _fetched_import_data: Default::default(),
+                                _fetched_built_data: Default::default(),
                             }),
                         }
                     }
                 };
 
-                self.current_jobs.insert(queued_job.job_id, job_state);
+                self.current_jobs.insert(job_id, job_state);
+            } else if !requestor_cancelled && !is_stale {
+                // The job now belongs to the newer builder run so the jobs it requests aren't stale
+                let job_state = self.current_jobs.get_mut(&job_id).unwrap();
+                if job_state.originating_asset == originating_asset {
+                    job_state.builder_run = job_state.builder_run.max(builder_run);
+                }
+                self.restore_cancelled_job(job_id);
+            }
+
+            if requestor_cancelled || is_stale {
+                // Don't cancel an existing job, something else may still need it
+                if is_new_job {
+                    self.cancel_job(job_id);
+                }
+            } else if let Some(asset_id) = originating_asset {
+                self.supersede_jobs_from_older_builder_runs(
+                    asset_id,
+                    job_type,
+                    builder_run,
+                    job_id,
+                );
             }
         }
     }
 
-    fn handle_completed_queue(
+    fn latest_builder_run(
+        &self,
+        asset_id: AssetId,
+    ) -> u64 {
+        self.job_api_impl
+            .inner
+            .builder_runs
+            .lock()
+            .unwrap()
+            .get(&asset_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // Jobs enqueued by the asset's builder after this are part of a new run, and supersede jobs of
+    // the same type from earlier runs
+    pub fn begin_builder_run(
         &mut self,
-        log_events: &mut Vec<BuildLogEvent>,
+        asset_id: AssetId,
+    ) {
+        *self
+            .job_api_impl
+            .inner
+            .builder_runs
+            .lock()
+            .unwrap()
+            .entry(asset_id)
+            .or_default() += 1;
+    }
+
+    fn supersede_jobs_from_older_builder_runs(
+        &mut self,
+        asset_id: AssetId,
+        job_type: JobTypeId,
+        builder_run: u64,
+        job_id: JobId,
+    ) {
+        if let Some((run, job_ids)) = self.asset_jobs.get_mut(&(asset_id, job_type)) {
+            if *run == builder_run {
+                if !job_ids.contains(&job_id) {
+                    job_ids.push(job_id);
+                }
+                return;
+            }
+        }
+
+        let superseded_jobs = self
+            .asset_jobs
+            .insert((asset_id, job_type), (builder_run, vec![job_id]))
+            .map(|(_, job_ids)| job_ids)
+            .unwrap_or_default();
+
+        for superseded_job_id in superseded_jobs {
+            if superseded_job_id != job_id {
+                log::debug!(
+                    "Job {:?} for asset {:?} superseded by job {:?}",
+                    superseded_job_id,
+                    asset_id,
+                    job_id
+                );
+                self.cancel_job(superseded_job_id);
+            }
+        }
+    }
+
+    fn cancel_job(
+        &mut self,
+        job_id: JobId,
+    ) {
+        let Some(job_state) = self.current_jobs.get_mut(&job_id) else {
+            return;
+        };
+
+        if job_state.cancellation != JobCancellation::NotCancelled {
+            return;
+        }
+
+        if !job_state.has_been_scheduled {
+            job_state.cancellation = JobCancellation::BeforeRunning;
+            job_state.has_been_scheduled = true;
+            job_state.output_data = Some(JobStateOutput::cancelled());
+            self.completed_job_count += 1;
+        } else if job_state.output_data.is_none() {
+            job_state.cancellation = JobCancellation::WhileRunning { rerun: false };
+        }
+
+        // Jobs that already finished can't be cancelled, their artifacts have already been taken
+    }
+
+    // Called when a job that may have been cancelled is requested again
+    fn restore_cancelled_job(
+        &mut self,
+        job_id: JobId,
+    ) {
+        let job_state = self.current_jobs.get_mut(&job_id).unwrap();
+        match job_state.cancellation {
+            JobCancellation::NotCancelled => {}
+            JobCancellation::WhileRunning { .. } if job_state.output_data.is_none() => {
+                // Run it again once the cancelled run finishes
+                job_state.cancellation = JobCancellation::WhileRunning { rerun: true };
+            }
+            JobCancellation::BeforeRunning | JobCancellation::WhileRunning { .. } => {
+                job_state.cancellation = JobCancellation::NotCancelled;
+                job_state.has_been_scheduled = false;
+                job_state.output_data = None;
+                self.completed_job_count -= 1;
+            }
+        }
+    }
+
+    // Cancels every job requested by the asset's builder, including jobs those jobs requested.
+    // Jobs that haven't started won't run, and anything produced by jobs that are already running
+    // is discarded.
+    pub fn cancel_jobs_for_asset(
+        &mut self,
+        asset_id: AssetId,
     ) {
+        // Jobs that were enqueued but haven't been pulled off the create queue yet will be stale
+        self.begin_builder_run(asset_id);
+        self.asset_jobs.retain(|(x, _), _| *x != asset_id);
+
+        let job_ids: Vec<_> = self
+            .current_jobs
+            .iter()
+            .filter(|(_, job_state)| job_state.originating_asset == Some(asset_id))
+            .map(|(&job_id, _)| job_id)
+            .collect();
+        for job_id in job_ids {
+            self.cancel_job(job_id);
+        }
+    }
+
+    // Jobs requested by the asset's builder that are waiting to run or are running
+    pub fn active_jobs_for_asset(
+        &self,
+        asset_id: AssetId,
+    ) -> Vec<JobId> {
+        self.current_jobs
+            .iter()
+            .filter(|(_, job_state)| {
+                job_state.originating_asset == Some(asset_id)
+                    && job_state.cancellation == JobCancellation::NotCancelled
+                    && job_state.output_data.is_none()
+            })
+            .map(|(&job_id, _)| job_id)
+            .collect()
+    }
+
+    fn handle_completed_queue(
+        &mut self,
+        log_data: &mut BuildLogData,
+    ) -> Vec<JobId> {
+        let mut completed_jobs = Vec::default();
         while let Ok(result) = self.thread_pool_result_rx.try_recv() {
             match result {
                 JobExecutorThreadPoolOutcome::RunJobComplete(msg) => {
+                    completed_jobs.push(msg.request.job_id);
+                    let asset_ids = log_data.assets_relying_on_job(msg.request.job_id);
+                    log_data
+                        .profile
+                        .record_job(&msg.request.debug_name, &asset_ids, msg.duration);
                     let job = self.current_jobs.get_mut(&msg.request.job_id).unwrap();
+                    if let JobCancellation::WhileRunning { .. } = job.cancellation {
+                        // Discard the results, including any log events
+                        job.output_data = Some(JobStateOutput::cancelled());
+                        self.completed_job_count += 1;
+                        continue;
+                    }
+
                     match msg.result {
                         Ok(data) => {
                             job.output_data = Some(JobStateOutput {
                                 _output_data: Ok(data.output_data),
                                 _fetched_asset_data: data.fetched_asset_data,
                                 _fetched_import_data: data.fetched_import_data,
+                                _fetched_built_data: data.fetched_built_data,
                             });
 
                             for log_event in data.log_events {
-                                log_events.push(log_event);
+                                log_data.log_events.push(log_event);
                             }
                         }
                         Err(e) => {
@@ -586,12 +1318,13 @@ impl JobExecutor {
                                 message: format!("Build job returned error: {}", e.to_string()),
                             };
                             log::error!("Build Error: {:?}", log_event);
-                            log_events.push(log_event);
+                            log_data.log_events.push(log_event);
 
                             job.output_data = Some(JobStateOutput {
                                 _output_data: Err(e),
                                 _fetched_asset_data: Default::default(),
                                 _fetched_import_data: Default::default(),
+                                _fetched_built_data: Default::default(),
                             });
                         }
                     }
@@ -602,15 +1335,92 @@ impl JobExecutor {
                 }
             }
         }
+
+        completed_jobs
     }
 
-    #[profiling::function]
-    pub fn update(
-        &mut self,
-        data_set: &Arc<DataSet>,
-        log_data: &mut BuildLogData,
-    ) {
-        //
+    fn handle_written_artifact_queue(&mut self) {
+        while let Ok(written_artifact) = self.written_artifact_queue_rx.try_recv() {
+            // Artifacts from cancelled jobs are dropped. Their build data is left in place because
+            // a newer job may have written identical data to the same file.
+            let job_cancelled = self
+                .current_jobs
+                .get(&written_artifact.job_id)
+                .map(|x| x.cancellation != JobCancellation::NotCancelled)
+                .unwrap_or(false);
+            if job_cancelled {
+                continue;
+            }
+
+            self.running_job_artifacts
+                .entry(written_artifact.job_id)
+                .or_default()
+                .push(written_artifact.artifact_id);
+            self.artifact_build_hashes
+                .insert(written_artifact.artifact_id, written_artifact.build_hash);
+            self.batch_written_artifacts
+                .insert(written_artifact.artifact_id);
+            self.written_artifacts.push(written_artifact);
+        }
+    }
+
+    // Record what the job produced, and delete build data for anything it produced last time but
+    // didn't produce this time
+    fn update_job_artifacts(
+        &mut self,
+        job_id: JobId,
+    ) {
+        let produced_artifacts = self
+            .running_job_artifacts
+            .remove(&job_id)
+            .unwrap_or_default();
+
+        // If the job failed, keep the results of the last successful run
+        let job_succeeded = self
+            .current_jobs
+            .get(&job_id)
+            .and_then(|job| job.output_data.as_ref())
+            .map(|output_data| output_data._output_data.is_ok())
+            .unwrap_or(false);
+        if !job_succeeded {
+            return;
+        }
+
+        let Some(previous_artifacts) = self
+            .job_artifacts
+            .insert(job_id, produced_artifacts.clone())
+        else {
+            return;
+        };
+
+        for artifact_id in previous_artifacts {
+            if produced_artifacts.contains(&artifact_id) {
+                continue;
+            }
+
+            if let Some(build_hash) = self.artifact_build_hashes.remove(&artifact_id) {
+                let path = uuid_and_hash_to_path(
+                    &self.job_api_impl.inner.project_config.build_data_path,
+                    artifact_id.as_uuid(),
+                    build_hash,
+                    "bf",
+                );
+                if let Err(e) = std::fs::remove_file(&path) {
+                    log::warn!("Could not remove stale artifact {:?}: {:?}", path, e);
+                }
+            }
+
+            self.removed_artifacts.push(artifact_id);
+        }
+    }
+
+    #[profiling::function]
+    pub fn update(
+        &mut self,
+        data_set: &Arc<DataSetView>,
+        log_data: &mut BuildLogData,
+    ) {
+        //
         // Pull jobs off the create queue. Determine their dependencies and prepare them to run.
         //
         self.handle_create_queue(log_data);
@@ -650,6 +1460,18 @@ impl JobExecutor {
                 continue;
             }
 
+            //
+            // See if the built data the job reads has been written
+            //
+            let waiting_on_built_data = job_state
+                .dependencies
+                .built_data
+                .iter()
+                .any(|x| !self.batch_written_artifacts.contains(&x.artifact_id));
+            if waiting_on_built_data {
+                continue;
+            }
+
             //
             // If we've run this job in the past and have a cached result, we can reuse the result.
             // But we still need to schedule downstream jobs in case their dependencies changed and
@@ -691,9 +1513,16 @@ impl JobExecutor {
                         job_id,
                         job_type: job_state.job_type,
                         data_set: data_set.clone(),
-                        _debug_name: job_state.debug_name.clone(),
+                        debug_name: job_state.debug_name.clone(),
                         _dependencies: job_state.dependencies.clone(),
+                        built_data_hashes: job_state
+                            .dependencies
+                            .built_data
+                            .iter()
+                            .map(|x| (x.artifact_id, self.artifact_build_hashes[&x.artifact_id]))
+                            .collect(),
                         input_data: job_state.input_data.clone(),
+                        originating_asset: job_state.originating_asset,
                     },
                 ));
 
@@ -707,7 +1536,23 @@ impl JobExecutor {
                 .has_been_scheduled = true;
         }
 
-        self.handle_completed_queue(&mut log_data.log_events);
+        let completed_jobs = self.handle_completed_queue(log_data);
+
+        // Jobs send their artifacts before they complete, so this picks up everything produced by
+        // the jobs that just completed
+        self.handle_written_artifact_queue();
+        for job_id in completed_jobs {
+            self.update_job_artifacts(job_id);
+
+            // A cancelled job that was requested again while it was running needs to run again
+            let job_state = self.current_jobs.get_mut(&job_id).unwrap();
+            if job_state.cancellation == (JobCancellation::WhileRunning { rerun: true }) {
+                job_state.cancellation = JobCancellation::NotCancelled;
+                job_state.has_been_scheduled = false;
+                job_state.output_data = None;
+                self.completed_job_count -= 1;
+            }
+        }
 
         let now = std::time::Instant::now();
         let mut print_progress = true;
@@ -727,6 +1572,86 @@ impl JobExecutor {
         }
     }
 
+    // Fails jobs that are waiting on built data that can't be produced anymore. Call this once
+    // there are no more builds to start. If no jobs are running or waiting to be created, nothing
+    // else will be written in this job batch.
+    pub fn fail_jobs_waiting_on_missing_built_data(
+        &mut self,
+        log_data: &mut BuildLogData,
+    ) {
+        if !self.job_create_queue_rx.is_empty()
+            || !self.thread_pool.as_ref().unwrap().is_idle()
+            || !self.written_artifact_queue_rx.is_empty()
+        {
+            return;
+        }
+
+        let is_waiting_on_built_data = |job_state: &JobState| {
+            job_state
+                .dependencies
+                .built_data
+                .iter()
+                .any(|x| !self.batch_written_artifacts.contains(&x.artifact_id))
+        };
+
+        for job_state in self.current_jobs.values() {
+            if job_state.has_been_scheduled {
+                // The job is still running
+                if job_state.output_data.is_none() {
+                    return;
+                }
+            } else {
+                // The job will be started on the next update
+                let upstream_jobs_complete = job_state.dependencies.upstream_jobs.iter().all(|x| {
+                    self.current_jobs
+                        .get(x)
+                        .map(|x| x.output_data.is_some())
+                        .unwrap_or(false)
+                });
+                if upstream_jobs_complete && !is_waiting_on_built_data(job_state) {
+                    return;
+                }
+            }
+        }
+
+        for (&job_id, job_state) in &mut self.current_jobs {
+            if job_state.has_been_scheduled {
+                continue;
+            }
+
+            let Some(missing_built_data) = job_state
+                .dependencies
+                .built_data
+                .iter()
+                .find(|x| !self.batch_written_artifacts.contains(&x.artifact_id))
+            else {
+                continue;
+            };
+
+            let message = format!(
+                "Built artifact {:?} for asset {:?} was never produced",
+                missing_built_data.artifact_id, missing_built_data.asset_id
+            );
+            let log_event = BuildLogEvent {
+                job_id: Some(job_id),
+                asset_id: None,
+                level: LogEventLevel::FatalError,
+                message: message.clone(),
+            };
+            log::error!("Build Error: {:?}", log_event);
+            log_data.log_events.push(log_event);
+
+            job_state.has_been_scheduled = true;
+            job_state.output_data = Some(JobStateOutput {
+                _output_data: Err(message.into()),
+                _fetched_asset_data: Default::default(),
+                _fetched_import_data: Default::default(),
+                _fetched_built_data: Default::default(),
+            });
+            self.completed_job_count += 1;
+        }
+    }
+
     pub fn completed_job_count(&self) -> usize {
         self.completed_job_count
     }
@@ -752,7 +1677,7 @@ impl JobExecutor {
         //     return false;
         // }
 
-        if !self.written_artifact_queue_rx.is_empty() {
+        if !self.written_artifact_queue_rx.is_empty() || !self.written_artifacts.is_empty() {
             return false;
         }
 
@@ -766,3 +1691,1263 @@ impl JobExecutor {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImporterRegistryBuilder;
+    use hydrate_data::{
+        AssetLocation, AssetName, DataSetError, ImportInfo, ImportableName, ImporterId,
+        PathReference, SchemaLinker, SchemaSetBuilder, Value,
+    };
+    use type_uuid::TypeUuid;
+
+    #[derive(Hash, Serialize, Deserialize)]
+    struct CountedArtifactsJobInput {
+        asset_id: AssetId,
+    }
+    impl JobInput for CountedArtifactsJobInput {}
+
+    #[derive(Serialize, Deserialize)]
+    struct CountedArtifactsJobOutput {}
+    impl JobOutput for CountedArtifactsJobOutput {}
+
+    #[derive(TypeUuid, Serialize)]
+    #[uuid = "8d3b2a6e-4f0c-4b7e-9f51-0c6a2d9e7b13"]
+    struct CountedArtifact {
+        index: u32,
+    }
+
+    // Produces one keyed artifact per "count" on the asset. The count is read from the data set so
+    // it is not part of the job's input hash, so the job ID stays the same when it changes.
+    #[derive(TypeUuid, Default)]
+    #[uuid = "1f7c9e42-6b8d-4a3f-a0e5-93d2c7b4e681"]
+    struct CountedArtifactsJobProcessor;
+
+    impl JobProcessor for CountedArtifactsJobProcessor {
+        type InputT = CountedArtifactsJobInput;
+        type OutputT = CountedArtifactsJobOutput;
+
+        fn version(&self) -> u32 {
+            1
+        }
+
+        fn run<'a>(
+            &'a self,
+            context: &'a RunContext<'a, Self::InputT>,
+        ) -> PipelineResult<CountedArtifactsJobOutput> {
+            let count = context
+                .data_set
+                .resolve_property(context.schema_set, context.input.asset_id, "count")?
+                .as_u32()?;
+            for index in 0..count {
+                context.produce_artifact(
+                    context.input.asset_id,
+                    Some(index),
+                    CountedArtifact { index },
+                )?;
+            }
+
+            Ok(CountedArtifactsJobOutput {})
+        }
+    }
+
+    #[derive(Hash, Serialize, Deserialize)]
+    struct TextArtifactJobInput {
+        asset_id: AssetId,
+        text: String,
+        dependency: ArtifactId,
+    }
+    impl JobInput for TextArtifactJobInput {}
+
+    #[derive(Serialize, Deserialize)]
+    struct TextArtifactJobOutput {}
+    impl JobOutput for TextArtifactJobOutput {}
+
+    const TEXT_ARTIFACT_TYPE: uuid::Uuid =
+        uuid::Uuid::from_u128(0x5c0e8b7a_2d4f_4e19_8a63_f1b9d2c47e08);
+
+    // Stores the text as raw UTF-8 instead of bincode
+    #[derive(TypeUuid, Default)]
+    #[uuid = "b2e6f0d3-9a1c-4d58-b7e4-6c3f8a0d2e91"]
+    struct TextArtifactJobProcessor;
+
+    impl JobProcessor for TextArtifactJobProcessor {
+        type InputT = TextArtifactJobInput;
+        type OutputT = TextArtifactJobOutput;
+
+        fn version(&self) -> u32 {
+            1
+        }
+
+        fn run<'a>(
+            &'a self,
+            context: &'a RunContext<'a, Self::InputT>,
+        ) -> PipelineResult<TextArtifactJobOutput> {
+            context.produce_artifact_bytes(
+                context.input.asset_id,
+                None::<u32>,
+                TEXT_ARTIFACT_TYPE,
+                context.input.text.as_bytes().to_vec(),
+                vec![context.input.dependency],
+            )?;
+
+            Ok(TextArtifactJobOutput {})
+        }
+    }
+
+    #[derive(Hash, Serialize, Deserialize)]
+    struct MeshMaterialSlotsJobInput {
+        asset_id: AssetId,
+    }
+    impl JobInput for MeshMaterialSlotsJobInput {}
+
+    #[derive(Serialize, Deserialize)]
+    struct MeshMaterialSlotsJobOutput {}
+    impl JobOutput for MeshMaterialSlotsJobOutput {}
+
+    #[derive(TypeUuid, Serialize)]
+    #[uuid = "6e2b9d40-7c1a-4f85-b3e9-0a8d5c2f1e67"]
+    struct MeshMaterialSlot {
+        material: u128,
+    }
+
+    // Produces an artifact for each material imported from the same file as the mesh, keyed by the
+    // material's slot name
+    #[derive(TypeUuid, Default)]
+    #[uuid = "c8f1a3e5-2b7d-4e90-a6c4-9d3e1f0b5a82"]
+    struct MeshMaterialSlotsJobProcessor;
+
+    impl JobProcessor for MeshMaterialSlotsJobProcessor {
+        type InputT = MeshMaterialSlotsJobInput;
+        type OutputT = MeshMaterialSlotsJobOutput;
+
+        fn version(&self) -> u32 {
+            1
+        }
+
+        fn run<'a>(
+            &'a self,
+            context: &'a RunContext<'a, Self::InputT>,
+        ) -> PipelineResult<MeshMaterialSlotsJobOutput> {
+            for sibling in context.sibling_importables(context.input.asset_id) {
+                let sibling_schema = context
+                    .data_set
+                    .asset_schema(sibling)
+                    .ok_or(DataSetError::AssetNotFound)?;
+                if sibling_schema.name() != "TestMaterial" {
+                    continue;
+                }
+
+                let slot_name = context
+                    .data_set
+                    .resolve_property(context.schema_set, sibling, "slot_name")?
+                    .as_string()?
+                    .to_string();
+                context.produce_artifact(
+                    context.input.asset_id,
+                    Some(slot_name),
+                    MeshMaterialSlot {
+                        material: sibling.as_uuid().as_u128(),
+                    },
+                )?;
+            }
+
+            Ok(MeshMaterialSlotsJobOutput {})
+        }
+    }
+
+    fn run_job_to_completion<T: JobProcessor>(
+        job_executor: &mut JobExecutor,
+        data_set: &DataSet,
+        schema_set: &SchemaSet,
+        asset_id: AssetId,
+        input: <T as JobProcessor>::InputT,
+    ) -> Vec<WrittenArtifact> {
+        job_executor.reset();
+        let mut log_data = BuildLogData::default();
+        enqueue_job::<T>(
+            JobRequestor::Builder(asset_id),
+            data_set,
+            schema_set,
+            job_executor.job_api(),
+            input,
+            &mut log_data.log_events,
+        )
+        .unwrap();
+
+        let data_set = Arc::new(data_set.snapshot());
+        let mut artifact_asset_lookup = HashMap::default();
+        let mut written_artifacts = Vec::default();
+        loop {
+            job_executor.update(&data_set, &mut log_data);
+            written_artifacts
+                .append(&mut job_executor.take_written_artifacts(&mut artifact_asset_lookup));
+            if job_executor.is_idle() {
+                break;
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        assert!(log_data.log_events.is_empty());
+        written_artifacts
+    }
+
+    #[test]
+    fn stale_job_artifacts_are_removed() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("CountedArtifactsAsset", uuid::Uuid::new_v4(), |builder| {
+                builder.add_u32("count", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let mut job_processor_registry = JobProcessorRegistryBuilder::default();
+        job_processor_registry.register_job_processor::<CountedArtifactsJobProcessor>();
+        let job_processor_registry = job_processor_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+        let build_data_root_path = project_config.build_data_path.clone();
+        let mut job_executor = JobExecutor::new(
+            &schema_set,
+            &job_processor_registry,
+            &ImporterRegistryBuilder::default().build(),
+            &project_config,
+        );
+
+        let record = schema_set
+            .find_named_type("CountedArtifactsAsset")
+            .unwrap()
+            .as_record()
+            .unwrap()
+            .clone();
+        let mut data_set = DataSet::default();
+        let asset_id = data_set.new_asset(AssetName::new("asset"), AssetLocation::null(), &record);
+        data_set
+            .set_property_override(&schema_set, asset_id, "count", Some(Value::U32(2)))
+            .unwrap();
+
+        let artifact_path = |written_artifact: &WrittenArtifact| {
+            uuid_and_hash_to_path(
+                &build_data_root_path,
+                written_artifact.artifact_id.as_uuid(),
+                written_artifact.build_hash,
+                "bf",
+            )
+        };
+
+        // The first run produces two keyed artifacts
+        let written_artifacts = run_job_to_completion::<CountedArtifactsJobProcessor>(
+            &mut job_executor,
+            &data_set,
+            &schema_set,
+            asset_id,
+            CountedArtifactsJobInput { asset_id },
+        );
+        assert_eq!(written_artifacts.len(), 2);
+        let job_id = written_artifacts[0].job_id;
+        let mut produced_artifacts: Vec<_> = written_artifacts
+            .iter()
+            .map(|written_artifact| written_artifact.artifact_id)
+            .collect();
+        produced_artifacts.sort();
+        let mut job_artifacts = job_executor.job_artifacts()[&job_id].clone();
+        job_artifacts.sort();
+        assert_eq!(job_artifacts, produced_artifacts);
+        for written_artifact in &written_artifacts {
+            assert!(artifact_path(written_artifact).exists());
+        }
+        assert!(job_executor.take_removed_artifacts().is_empty());
+
+        let kept_artifact = written_artifacts
+            .iter()
+            .find(|x| x.artifact_key_debug_name.as_deref() == Some("0"))
+            .unwrap();
+        let stale_artifact = written_artifacts
+            .iter()
+            .find(|x| x.artifact_key_debug_name.as_deref() == Some("1"))
+            .unwrap();
+
+        // Re-running the same job after the count drops no longer produces the second artifact,
+        // and its build data is removed
+        data_set
+            .set_property_override(&schema_set, asset_id, "count", Some(Value::U32(1)))
+            .unwrap();
+        let rerun_written_artifacts = run_job_to_completion::<CountedArtifactsJobProcessor>(
+            &mut job_executor,
+            &data_set,
+            &schema_set,
+            asset_id,
+            CountedArtifactsJobInput { asset_id },
+        );
+        assert_eq!(rerun_written_artifacts.len(), 1);
+        assert_eq!(rerun_written_artifacts[0].job_id, job_id);
+        assert_eq!(
+            job_executor.job_artifacts()[&job_id],
+            vec![kept_artifact.artifact_id]
+        );
+        assert_eq!(
+            job_executor.take_removed_artifacts(),
+            vec![stale_artifact.artifact_id]
+        );
+        assert!(artifact_path(kept_artifact).exists());
+        assert!(!artifact_path(stale_artifact).exists());
+
+        drop(job_executor);
+        std::fs::remove_dir_all(&root_path).unwrap();
+    }
+
+    #[test]
+    fn cleared_build_data_is_produced_again() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("CountedArtifactsAsset", uuid::Uuid::new_v4(), |builder| {
+                builder.add_u32("count", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let mut job_processor_registry = JobProcessorRegistryBuilder::default();
+        job_processor_registry.register_job_processor::<CountedArtifactsJobProcessor>();
+        let job_processor_registry = job_processor_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+        let build_data_root_path = project_config.build_data_path.clone();
+        let mut job_executor = JobExecutor::new(
+            &schema_set,
+            &job_processor_registry,
+            &ImporterRegistryBuilder::default().build(),
+            &project_config,
+        );
+
+        let record = schema_set
+            .find_named_type("CountedArtifactsAsset")
+            .unwrap()
+            .as_record()
+            .unwrap()
+            .clone();
+        let mut data_set = DataSet::default();
+        let asset_id = data_set.new_asset(AssetName::new("asset"), AssetLocation::null(), &record);
+        data_set
+            .set_property_override(&schema_set, asset_id, "count", Some(Value::U32(3)))
+            .unwrap();
+
+        let artifact_path = |written_artifact: &WrittenArtifact| {
+            uuid_and_hash_to_path(
+                &build_data_root_path,
+                written_artifact.artifact_id.as_uuid(),
+                written_artifact.build_hash,
+                "bf",
+            )
+        };
+        let sorted_artifacts = |written_artifacts: &[WrittenArtifact]| {
+            let mut artifacts: Vec<_> = written_artifacts
+                .iter()
+                .map(|x| (x.artifact_id, x.build_hash))
+                .collect();
+            artifacts.sort();
+            artifacts
+        };
+
+        let written_artifacts = run_job_to_completion::<CountedArtifactsJobProcessor>(
+            &mut job_executor,
+            &data_set,
+            &schema_set,
+            asset_id,
+            CountedArtifactsJobInput { asset_id },
+        );
+        assert_eq!(written_artifacts.len(), 3);
+
+        job_executor.clear_build_data().unwrap();
+        assert!(!build_data_root_path.exists());
+        assert!(job_executor.job_artifacts().is_empty());
+
+        // Nothing changed, but every artifact is written again
+        let rebuilt_artifacts = run_job_to_completion::<CountedArtifactsJobProcessor>(
+            &mut job_executor,
+            &data_set,
+            &schema_set,
+            asset_id,
+            CountedArtifactsJobInput { asset_id },
+        );
+        assert_eq!(
+            sorted_artifacts(&rebuilt_artifacts),
+            sorted_artifacts(&written_artifacts)
+        );
+        for rebuilt_artifact in &rebuilt_artifacts {
+            assert!(artifact_path(rebuilt_artifact).exists());
+        }
+        assert!(job_executor.take_removed_artifacts().is_empty());
+
+        drop(job_executor);
+        std::fs::remove_dir_all(&root_path).unwrap();
+    }
+
+    #[test]
+    fn mesh_build_reads_sibling_material_slot_names() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("TestMesh", uuid::Uuid::new_v4(), |_builder| {})
+            .unwrap();
+        linker
+            .register_record_type("TestMaterial", uuid::Uuid::new_v4(), |builder| {
+                builder.add_string("slot_name", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let mut job_processor_registry = JobProcessorRegistryBuilder::default();
+        job_processor_registry.register_job_processor::<MeshMaterialSlotsJobProcessor>();
+        let job_processor_registry = job_processor_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+        let mut job_executor = JobExecutor::new(
+            &schema_set,
+            &job_processor_registry,
+            &ImporterRegistryBuilder::default().build(),
+            &project_config,
+        );
+
+        let mut data_set = DataSet::default();
+        let mut new_imported_asset = |schema_name: &str, path: &str, importable_name: &str| {
+            let record = schema_set
+                .find_named_type(schema_name)
+                .unwrap()
+                .as_record()
+                .unwrap()
+                .clone();
+            let asset_id = data_set.new_asset(
+                AssetName::new(importable_name),
+                AssetLocation::null(),
+                &record,
+            );
+            let source_file = PathReference::new(
+                "".to_string(),
+                path.to_string(),
+                ImportableName::new(importable_name.to_string()),
+            )
+            .simplify(&project_config);
+            data_set
+                .set_import_info(
+                    asset_id,
+                    ImportInfo::new(
+                        ImporterId(uuid::Uuid::new_v4()),
+                        source_file,
+                        Default::default(),
+                        0,
+                        0,
+                        0,
+                        0,
+                    ),
+                )
+                .unwrap();
+            if schema_name == "TestMaterial" {
+                data_set
+                    .set_property_override(
+                        &schema_set,
+                        asset_id,
+                        "slot_name",
+                        Some(Value::String(Arc::new(format!("{}_slot", importable_name)))),
+                    )
+                    .unwrap();
+            }
+            asset_id
+        };
+
+        // Two meshes and two materials from one file, and a material from another file
+        let mesh = new_imported_asset("TestMesh", "scene.gltf", "mesh");
+        let other_mesh = new_imported_asset("TestMesh", "scene.gltf", "other_mesh");
+        let body = new_imported_asset("TestMaterial", "scene.gltf", "body");
+        let glass = new_imported_asset("TestMaterial", "scene.gltf", "glass");
+        new_imported_asset("TestMaterial", "other_scene.gltf", "unrelated");
+
+        let mut expected_siblings = vec![other_mesh, body, glass];
+        expected_siblings.sort();
+        assert_eq!(data_set.sibling_importables(mesh), expected_siblings);
+
+        // The mesh finds the slot names of only the materials imported with it
+        let written_artifacts = run_job_to_completion::<MeshMaterialSlotsJobProcessor>(
+            &mut job_executor,
+            &data_set,
+            &schema_set,
+            mesh,
+            MeshMaterialSlotsJobInput { asset_id: mesh },
+        );
+        let mut slot_names: Vec<_> = written_artifacts
+            .iter()
+            .map(|x| x.artifact_key_debug_name.clone().unwrap())
+            .collect();
+        slot_names.sort();
+        assert_eq!(slot_names, vec!["body_slot", "glass_slot"]);
+
+        drop(job_executor);
+        let _ = std::fs::remove_dir_all(&root_path);
+    }
+
+    #[test]
+    fn produce_artifact_from_raw_bytes() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("TextAsset", uuid::Uuid::new_v4(), |_builder| {})
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let mut job_processor_registry = JobProcessorRegistryBuilder::default();
+        job_processor_registry.register_job_processor::<TextArtifactJobProcessor>();
+        let job_processor_registry = job_processor_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+        let build_data_root_path = project_config.build_data_path.clone();
+        let mut job_executor = JobExecutor::new(
+            &schema_set,
+            &job_processor_registry,
+            &ImporterRegistryBuilder::default().build(),
+            &project_config,
+        );
+
+        let record = schema_set
+            .find_named_type("TextAsset")
+            .unwrap()
+            .as_record()
+            .unwrap()
+            .clone();
+        let mut data_set = DataSet::default();
+        let asset_id = data_set.new_asset(AssetName::new("asset"), AssetLocation::null(), &record);
+
+        let text = "[config]\nvalue = 5\n".to_string();
+        let dependency = ArtifactId::from_u128(1);
+        let written_artifacts = run_job_to_completion::<TextArtifactJobProcessor>(
+            &mut job_executor,
+            &data_set,
+            &schema_set,
+            asset_id,
+            TextArtifactJobInput {
+                asset_id,
+                text: text.clone(),
+                dependency,
+            },
+        );
+        assert_eq!(written_artifacts.len(), 1);
+        let written_artifact = &written_artifacts[0];
+        assert_eq!(
+            written_artifact.artifact_id,
+            ArtifactId::from_uuid(asset_id.as_uuid())
+        );
+
+        // Read the artifact back the way the loader does and treat the payload as a string
+        let path = uuid_and_hash_to_path(
+            &build_data_root_path,
+            written_artifact.artifact_id.as_uuid(),
+            written_artifact.build_hash,
+            "bf",
+        );
+        let mut reader = std::io::BufReader::new(std::fs::File::open(&path).unwrap());
+        let header = hydrate_base::BuiltArtifactHeaderData::read_header(&mut reader).unwrap();
+        assert_eq!(header.asset_type, TEXT_ARTIFACT_TYPE);
+        assert_eq!(header.dependencies, vec![dependency]);
+        let mut data = Vec::default();
+        std::io::Read::read_to_end(&mut reader, &mut data).unwrap();
+        assert_eq!(String::from_utf8(data).unwrap(), text);
+
+        drop(job_executor);
+        std::fs::remove_dir_all(&root_path).unwrap();
+    }
+
+    #[derive(Hash, Serialize, Deserialize)]
+    struct LargeArtifactJobInput {
+        asset_id: AssetId,
+        chunk_count: usize,
+        temp_dir: PathBuf,
+    }
+    impl JobInput for LargeArtifactJobInput {}
+
+    #[derive(Serialize, Deserialize)]
+    struct LargeArtifactJobOutput {}
+    impl JobOutput for LargeArtifactJobOutput {}
+
+    const LARGE_ARTIFACT_CHUNK_SIZE: usize = 64 * 1024;
+
+    fn large_artifact_chunk(index: usize) -> Vec<u8> {
+        vec![(index % 251) as u8; LARGE_ARTIFACT_CHUNK_SIZE]
+    }
+
+    // Streams an artifact one chunk at a time, only ever holding a single chunk in memory
+    #[derive(TypeUuid, Default)]
+    #[uuid = "4a9d7c21-e3b8-4f65-8d0a-b2c1f6e93a57"]
+    struct LargeArtifactJobProcessor;
+
+    impl JobProcessor for LargeArtifactJobProcessor {
+        type InputT = LargeArtifactJobInput;
+        type OutputT = LargeArtifactJobOutput;
+
+        fn version(&self) -> u32 {
+            1
+        }
+
+        fn run<'a>(
+            &'a self,
+            context: &'a RunContext<'a, Self::InputT>,
+        ) -> PipelineResult<LargeArtifactJobOutput> {
+            context.produce_artifact_bytes_streaming(
+                context.input.asset_id,
+                None::<u32>,
+                TEXT_ARTIFACT_TYPE,
+                vec![],
+                |writer| {
+                    for index in 0..context.input.chunk_count {
+                        writer.write_all(&large_artifact_chunk(index))?;
+                    }
+
+                    // Everything but what the writer buffers must already be on disk rather than
+                    // held in memory
+                    let mut bytes_on_disk = 0;
+                    for entry in std::fs::read_dir(&context.input.temp_dir)? {
+                        bytes_on_disk += entry?.metadata()?.len() as usize;
+                    }
+                    let bytes_written = context.input.chunk_count * LARGE_ARTIFACT_CHUNK_SIZE;
+                    if bytes_on_disk + LARGE_ARTIFACT_CHUNK_SIZE < bytes_written {
+                        Err(format!(
+                            "Only {} of {} bytes were written to disk",
+                            bytes_on_disk, bytes_written
+                        ))?;
+                    }
+
+                    Ok(())
+                },
+            )?;
+
+            Ok(LargeArtifactJobOutput {})
+        }
+    }
+
+    #[test]
+    fn produce_large_artifact_streaming() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("LargeAsset", uuid::Uuid::new_v4(), |_builder| {})
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let mut job_processor_registry = JobProcessorRegistryBuilder::default();
+        job_processor_registry.register_job_processor::<LargeArtifactJobProcessor>();
+        let job_processor_registry = job_processor_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+        let build_data_root_path = project_config.build_data_path.clone();
+        let mut job_executor = JobExecutor::new(
+            &schema_set,
+            &job_processor_registry,
+            &ImporterRegistryBuilder::default().build(),
+            &project_config,
+        );
+
+        let record = schema_set
+            .find_named_type("LargeAsset")
+            .unwrap()
+            .as_record()
+            .unwrap()
+            .clone();
+        let mut data_set = DataSet::default();
+        let asset_id = data_set.new_asset(AssetName::new("asset"), AssetLocation::null(), &record);
+
+        // 64MB
+        let chunk_count = 1024;
+        let written_artifacts = run_job_to_completion::<LargeArtifactJobProcessor>(
+            &mut job_executor,
+            &data_set,
+            &schema_set,
+            asset_id,
+            LargeArtifactJobInput {
+                asset_id,
+                chunk_count,
+                temp_dir: build_data_root_path.join("tmp"),
+            },
+        );
+        assert_eq!(written_artifacts.len(), 1);
+        let written_artifact = &written_artifacts[0];
+
+        // The build hash is the same as if the data had been produced in memory
+        let mut data = Vec::with_capacity(chunk_count * LARGE_ARTIFACT_CHUNK_SIZE);
+        for index in 0..chunk_count {
+            data.extend_from_slice(&large_artifact_chunk(index));
+        }
+        let mut hasher = project_config.content_hash_algorithm.build_hasher();
+        data.hash(&mut hasher);
+        written_artifact.metadata.hash(&mut hasher);
+        assert_eq!(written_artifact.build_hash, hasher.finish());
+
+        let path = uuid_and_hash_to_path(
+            &build_data_root_path,
+            written_artifact.artifact_id.as_uuid(),
+            written_artifact.build_hash,
+            "bf",
+        );
+        let mut reader = std::io::BufReader::new(std::fs::File::open(&path).unwrap());
+        let header = hydrate_base::BuiltArtifactHeaderData::read_header(&mut reader).unwrap();
+        assert_eq!(header.asset_type, TEXT_ARTIFACT_TYPE);
+        let mut written_data = Vec::default();
+        std::io::Read::read_to_end(&mut reader, &mut written_data).unwrap();
+        assert!(written_data == data);
+
+        // The temporary file is cleaned up
+        assert_eq!(
+            std::fs::read_dir(build_data_root_path.join("tmp"))
+                .unwrap()
+                .count(),
+            0
+        );
+
+        drop(job_executor);
+        std::fs::remove_dir_all(&root_path).unwrap();
+    }
+
+    #[derive(Hash, Serialize, Deserialize)]
+    struct CopyBuiltArtifactJobInput {
+        asset_id: AssetId,
+        source_asset_id: AssetId,
+        source_artifact_id: ArtifactId,
+    }
+    impl JobInput for CopyBuiltArtifactJobInput {}
+
+    #[derive(Serialize, Deserialize)]
+    struct CopyBuiltArtifactJobOutput {}
+    impl JobOutput for CopyBuiltArtifactJobOutput {}
+
+    // Reads another asset's built artifact and produces a copy of it for its own asset
+    #[derive(TypeUuid, Default)]
+    #[uuid = "e7a1c4f9-05b2-4d8e-a3c6-7f19d0b25e84"]
+    struct CopyBuiltArtifactJobProcessor;
+
+    impl JobProcessor for CopyBuiltArtifactJobProcessor {
+        type InputT = CopyBuiltArtifactJobInput;
+        type OutputT = CopyBuiltArtifactJobOutput;
+
+        fn version(&self) -> u32 {
+            1
+        }
+
+        fn enumerate_dependencies(
+            &self,
+            context: EnumerateDependenciesContext<Self::InputT>,
+        ) -> PipelineResult<JobEnumeratedDependencies> {
+            Ok(JobEnumeratedDependencies {
+                built_data: vec![AssetArtifactIdPair {
+                    asset_id: context.input.source_asset_id,
+                    artifact_id: context.input.source_artifact_id,
+                }],
+                ..Default::default()
+            })
+        }
+
+        fn run<'a>(
+            &'a self,
+            context: &'a RunContext<'a, Self::InputT>,
+        ) -> PipelineResult<CopyBuiltArtifactJobOutput> {
+            let data = context.read_built_artifact(context.input.source_artifact_id)?;
+            context.produce_artifact_bytes(
+                context.input.asset_id,
+                Some("copy"),
+                TEXT_ARTIFACT_TYPE,
+                data.to_vec(),
+                vec![],
+            )?;
+
+            Ok(CopyBuiltArtifactJobOutput {})
+        }
+    }
+
+    #[test]
+    fn job_reads_built_artifact_of_another_job() {
+        let schema_set = SchemaSetBuilder::default().build();
+
+        let mut job_processor_registry = JobProcessorRegistryBuilder::default();
+        job_processor_registry.register_job_processor::<TextArtifactJobProcessor>();
+        job_processor_registry.register_job_processor::<CopyBuiltArtifactJobProcessor>();
+        let job_processor_registry = job_processor_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+        let mut job_executor = JobExecutor::new(
+            &schema_set,
+            &job_processor_registry,
+            &ImporterRegistryBuilder::default().build(),
+            &project_config,
+        );
+
+        let source_asset_id = AssetId::from_u128(1);
+        let copy_asset_id = AssetId::from_u128(2);
+        let source_artifact_id = ArtifactId::from_uuid(source_asset_id.as_uuid());
+        let data_set = DataSet::default();
+
+        let mut build = |text: &str| {
+            job_executor.reset();
+            let mut log_data = BuildLogData::default();
+
+            // The copy job is enqueued first, it must wait for the source artifact to be written
+            enqueue_job::<CopyBuiltArtifactJobProcessor>(
+                JobRequestor::Builder(copy_asset_id),
+                &data_set,
+                &schema_set,
+                job_executor.job_api(),
+                CopyBuiltArtifactJobInput {
+                    asset_id: copy_asset_id,
+                    source_asset_id,
+                    source_artifact_id,
+                },
+                &mut log_data.log_events,
+            )
+            .unwrap();
+            enqueue_job::<TextArtifactJobProcessor>(
+                JobRequestor::Builder(source_asset_id),
+                &data_set,
+                &schema_set,
+                job_executor.job_api(),
+                TextArtifactJobInput {
+                    asset_id: source_asset_id,
+                    text: text.to_string(),
+                    dependency: ArtifactId::null(),
+                },
+                &mut log_data.log_events,
+            )
+            .unwrap();
+
+            let data_set = Arc::new(data_set.snapshot());
+            let mut artifact_asset_lookup = HashMap::default();
+            let mut written_artifacts = Vec::default();
+            loop {
+                job_executor.update(&data_set, &mut log_data);
+                written_artifacts
+                    .append(&mut job_executor.take_written_artifacts(&mut artifact_asset_lookup));
+                if job_executor.is_idle() {
+                    break;
+                }
+
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+
+            assert!(log_data.log_events.is_empty());
+            assert_eq!(
+                job_executor.take_built_data_requests(),
+                vec![source_asset_id]
+            );
+
+            let copied_artifact = written_artifacts
+                .iter()
+                .find(|x| x.asset_id == copy_asset_id)
+                .unwrap();
+            let copied_data = job_executor
+                .job_api()
+                .fetch_built_artifact(copied_artifact.artifact_id, copied_artifact.build_hash)
+                .unwrap();
+            String::from_utf8(copied_data).unwrap()
+        };
+
+        assert_eq!(build("first"), "first");
+
+        // The copy job's input didn't change, but it runs again and sees the new source artifact
+        assert_eq!(build("second"), "second");
+
+        drop(job_executor);
+        let _ = std::fs::remove_dir_all(&root_path);
+    }
+
+    #[test]
+    fn newer_builder_run_supersedes_older_jobs_for_asset() {
+        let schema_set = SchemaSetBuilder::default().build();
+
+        let mut job_processor_registry = JobProcessorRegistryBuilder::default();
+        job_processor_registry.register_job_processor::<TextArtifactJobProcessor>();
+        let job_processor_registry = job_processor_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+        let mut job_executor = JobExecutor::new(
+            &schema_set,
+            &job_processor_registry,
+            &ImporterRegistryBuilder::default().build(),
+            &project_config,
+        );
+
+        let asset_id = AssetId::from_u128(1);
+        let data_set = DataSet::default();
+        let mut log_data = BuildLogData::default();
+
+        // The asset is built twice before the executor gets a chance to run anything
+        let mut build = |job_executor: &mut JobExecutor, text: &str| {
+            job_executor.begin_builder_run(asset_id);
+            enqueue_job::<TextArtifactJobProcessor>(
+                JobRequestor::Builder(asset_id),
+                &data_set,
+                &schema_set,
+                job_executor.job_api(),
+                TextArtifactJobInput {
+                    asset_id,
+                    text: text.to_string(),
+                    dependency: ArtifactId::null(),
+                },
+                &mut log_data.log_events,
+            )
+            .unwrap()
+        };
+        job_executor.reset();
+        let old_job_id = build(&mut job_executor, "old");
+        let new_job_id = build(&mut job_executor, "new");
+        assert_ne!(old_job_id, new_job_id);
+
+        let data_set = Arc::new(data_set.snapshot());
+        let mut artifact_asset_lookup = HashMap::default();
+        let mut written_artifacts = Vec::default();
+        job_executor.update(&data_set, &mut log_data);
+        assert_eq!(
+            job_executor.active_jobs_for_asset(asset_id),
+            vec![new_job_id]
+        );
+        loop {
+            written_artifacts
+                .append(&mut job_executor.take_written_artifacts(&mut artifact_asset_lookup));
+            if job_executor.is_idle() {
+                break;
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            job_executor.update(&data_set, &mut log_data);
+        }
+
+        // Only the latest job produced anything
+        assert!(log_data.log_events.is_empty());
+        assert_eq!(written_artifacts.len(), 1);
+        assert_eq!(written_artifacts[0].job_id, new_job_id);
+        let built_data = job_executor
+            .job_api()
+            .fetch_built_artifact(
+                written_artifacts[0].artifact_id,
+                written_artifacts[0].build_hash,
+            )
+            .unwrap();
+        assert_eq!(String::from_utf8(built_data).unwrap(), "new");
+        assert!(job_executor.job_artifacts().contains_key(&new_job_id));
+        assert!(!job_executor.job_artifacts().contains_key(&old_job_id));
+
+        // Cancelling the asset's jobs before they run means nothing is produced
+        let mut log_data = BuildLogData::default();
+        job_executor.reset();
+        enqueue_job::<TextArtifactJobProcessor>(
+            JobRequestor::Builder(asset_id),
+            &DataSet::default(),
+            &schema_set,
+            job_executor.job_api(),
+            TextArtifactJobInput {
+                asset_id,
+                text: "cancelled".to_string(),
+                dependency: ArtifactId::null(),
+            },
+            &mut log_data.log_events,
+        )
+        .unwrap();
+        job_executor.cancel_jobs_for_asset(asset_id);
+        loop {
+            job_executor.update(&data_set, &mut log_data);
+            assert!(job_executor
+                .take_written_artifacts(&mut artifact_asset_lookup)
+                .is_empty());
+            if job_executor.is_idle() {
+                break;
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert!(job_executor.active_jobs_for_asset(asset_id).is_empty());
+
+        drop(job_executor);
+        let _ = std::fs::remove_dir_all(&root_path);
+    }
+
+    #[derive(Hash, Serialize, Deserialize)]
+    struct RecordedJobInput {
+        name: String,
+        upstream_jobs: Vec<JobId>,
+    }
+    impl JobInput for RecordedJobInput {}
+
+    #[derive(Serialize, Deserialize)]
+    struct RecordedJobOutput {}
+    impl JobOutput for RecordedJobOutput {}
+
+    #[derive(Default)]
+    struct JobRecorder {
+        // (job name, true for start/false for end) in the order they happened
+        events: std::sync::Mutex<Vec<(String, bool)>>,
+        running_count: std::sync::atomic::AtomicUsize,
+        max_running_count: std::sync::atomic::AtomicUsize,
+    }
+
+    // Records when each job starts and ends, and how many jobs were running at the same time
+    #[derive(TypeUuid)]
+    #[uuid = "4a9d7c21-e83b-4f6a-9c05-d1b2e7f84a36"]
+    struct RecordedJobProcessor(Arc<JobRecorder>);
+
+    impl JobProcessor for RecordedJobProcessor {
+        type InputT = RecordedJobInput;
+        type OutputT = RecordedJobOutput;
+
+        fn version(&self) -> u32 {
+            1
+        }
+
+        fn enumerate_dependencies(
+            &self,
+            context: EnumerateDependenciesContext<Self::InputT>,
+        ) -> PipelineResult<JobEnumeratedDependencies> {
+            Ok(JobEnumeratedDependencies {
+                upstream_jobs: context.input.upstream_jobs.clone(),
+                ..Default::default()
+            })
+        }
+
+        fn run<'a>(
+            &'a self,
+            context: &'a RunContext<'a, Self::InputT>,
+        ) -> PipelineResult<RecordedJobOutput> {
+            use std::sync::atomic::Ordering;
+            let recorder = &self.0;
+            recorder
+                .events
+                .lock()
+                .unwrap()
+                .push((context.input.name.clone(), true));
+            let running_count = recorder.running_count.fetch_add(1, Ordering::SeqCst) + 1;
+            recorder
+                .max_running_count
+                .fetch_max(running_count, Ordering::SeqCst);
+
+            // Give independent jobs time to start while this one is running
+            std::thread::sleep(std::time::Duration::from_millis(100));
+
+            recorder.running_count.fetch_sub(1, Ordering::SeqCst);
+            recorder
+                .events
+                .lock()
+                .unwrap()
+                .push((context.input.name.clone(), false));
+            Ok(RecordedJobOutput {})
+        }
+    }
+
+    #[test]
+    fn diamond_job_graph_runs_in_dependency_order() {
+        let schema_set = SchemaSetBuilder::default().build();
+        let recorder = Arc::new(JobRecorder::default());
+
+        let mut job_processor_registry = JobProcessorRegistryBuilder::default();
+        job_processor_registry
+            .register_job_processor_instance(RecordedJobProcessor(recorder.clone()));
+        let job_processor_registry = job_processor_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        let mut project_config = HydrateProjectConfiguration::for_test(&root_path);
+        project_config.build_thread_count = Some(4);
+        let mut job_executor = JobExecutor::new(
+            &schema_set,
+            &job_processor_registry,
+            &ImporterRegistryBuilder::default().build(),
+            &project_config,
+        );
+
+        //   top
+        //  /   \
+        // left right
+        //  \   /
+        //  bottom
+        let data_set = DataSet::default();
+        let mut log_data = BuildLogData::default();
+        let mut enqueue = |name: &str, upstream_jobs: Vec<JobId>| {
+            enqueue_job::<RecordedJobProcessor>(
+                JobRequestor::Builder(AssetId::null()),
+                &data_set,
+                &schema_set,
+                job_executor.job_api(),
+                RecordedJobInput {
+                    name: name.to_string(),
+                    upstream_jobs,
+                },
+                &mut log_data.log_events,
+            )
+            .unwrap()
+        };
+        let top = enqueue("top", vec![]);
+        let left = enqueue("left", vec![top]);
+        let right = enqueue("right", vec![top]);
+        enqueue("bottom", vec![left, right]);
+
+        let data_set = Arc::new(data_set.snapshot());
+        loop {
+            job_executor.update(&data_set, &mut log_data);
+            if job_executor.is_idle() {
+                break;
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        assert!(log_data.log_events.is_empty());
+        assert_eq!(job_executor.completed_job_count(), 4);
+
+        let events = recorder.events.lock().unwrap().clone();
+        let position = |name: &str, is_start: bool| {
+            events
+                .iter()
+                .position(|(x, y)| x == name && *y == is_start)
+                .unwrap()
+        };
+
+        // Jobs only start after their upstream jobs have ended
+        assert!(position("top", false) < position("left", true));
+        assert!(position("top", false) < position("right", true));
+        assert!(position("left", false) < position("bottom", true));
+        assert!(position("right", false) < position("bottom", true));
+
+        // left and right don't depend on each other, so they run at the same time
+        assert!(position("left", true) < position("right", false));
+        assert!(position("right", true) < position("left", false));
+        assert_eq!(
+            recorder
+                .max_running_count
+                .load(std::sync::atomic::Ordering::SeqCst),
+            2
+        );
+
+        drop(job_executor);
+        let _ = std::fs::remove_dir_all(&root_path);
+    }
+
+    #[derive(Hash, Serialize, Deserialize)]
+    struct SleepJobInput {
+        asset_id: AssetId,
+    }
+    impl JobInput for SleepJobInput {}
+
+    #[derive(Serialize, Deserialize)]
+    struct SleepJobOutput {}
+    impl JobOutput for SleepJobOutput {}
+
+    const SLEEP_JOB_DURATION: std::time::Duration = std::time::Duration::from_millis(20);
+
+    #[derive(TypeUuid, Default)]
+    #[uuid = "4a9d2e71-c6b3-4f08-8e15-d72b0a3f9c64"]
+    struct SleepJobProcessor;
+
+    impl JobProcessor for SleepJobProcessor {
+        type InputT = SleepJobInput;
+        type OutputT = SleepJobOutput;
+
+        fn version(&self) -> u32 {
+            1
+        }
+
+        fn run<'a>(
+            &'a self,
+            _context: &'a RunContext<'a, Self::InputT>,
+        ) -> PipelineResult<SleepJobOutput> {
+            std::thread::sleep(SLEEP_JOB_DURATION);
+            Ok(SleepJobOutput {})
+        }
+    }
+
+    #[test]
+    fn build_profile_records_job_time() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("SleepAsset", uuid::Uuid::new_v4(), |_| {})
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let mut job_processor_registry = JobProcessorRegistryBuilder::default();
+        job_processor_registry.register_job_processor::<SleepJobProcessor>();
+        let job_processor_registry = job_processor_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+        let mut job_executor = JobExecutor::new(
+            &schema_set,
+            &job_processor_registry,
+            &ImporterRegistryBuilder::default().build(),
+            &project_config,
+        );
+
+        let record = schema_set
+            .find_named_type("SleepAsset")
+            .unwrap()
+            .as_record()
+            .unwrap()
+            .clone();
+        let mut data_set = DataSet::default();
+        let asset_id = data_set.new_asset(AssetName::new("asset"), AssetLocation::null(), &record);
+        let other_asset_id =
+            data_set.new_asset(AssetName::new("other"), AssetLocation::null(), &record);
+
+        let mut log_data = BuildLogData::default();
+        enqueue_job::<SleepJobProcessor>(
+            JobRequestor::Builder(asset_id),
+            &data_set,
+            &schema_set,
+            job_executor.job_api(),
+            SleepJobInput { asset_id },
+            &mut log_data.log_events,
+        )
+        .unwrap();
+
+        let data_set = Arc::new(data_set.snapshot());
+        loop {
+            job_executor.update(&data_set, &mut log_data);
+            if job_executor.is_idle() {
+                break;
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert!(log_data.log_events.is_empty());
+
+        // The job's time is recorded for its type and for the asset whose builder requested it
+        let profile = log_data.profile();
+        let job_durations = profile.job_durations();
+        assert_eq!(job_durations.len(), 1);
+        assert_eq!(
+            job_durations[0].name,
+            std::any::type_name::<SleepJobProcessor>()
+        );
+        assert_eq!(job_durations[0].count, 1);
+        assert!(job_durations[0].duration >= SLEEP_JOB_DURATION);
+
+        assert_eq!(
+            profile.asset_duration(asset_id),
+            Some(job_durations[0].duration)
+        );
+        assert_eq!(profile.asset_duration(other_asset_id), None);
+        assert_eq!(
+            profile.slowest_assets(10),
+            vec![(asset_id, job_durations[0].duration)]
+        );
+
+        drop(job_executor);
+        let _ = std::fs::remove_dir_all(&root_path);
+    }
+}
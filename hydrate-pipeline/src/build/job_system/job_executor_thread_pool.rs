@@ -1,12 +1,13 @@
-use super::job_system_traits::{FetchedAssetData, FetchedImportData};
+use super::job_system_traits::{FetchedAssetData, FetchedBuiltData, FetchedImportData};
 use super::{
-    JobApi, JobApiImpl, JobEnumeratedDependencies, JobId, JobProcessorRegistry, JobTypeId,
+    job_cache_key, JobApi, JobApiImpl, JobEnumeratedDependencies, JobId, JobProcessorRegistry,
+    JobTypeId,
 };
 use crate::{BuildLogEvent, PipelineResult};
 use crossbeam_channel::{Receiver, Sender};
-use hydrate_base::hashing::HashMap;
-use hydrate_base::AssetId;
-use hydrate_data::{DataSet, SchemaSet};
+use hydrate_base::hashing::{HashMap, HashSet};
+use hydrate_base::{ArtifactId, AssetId};
+use hydrate_data::{DataSetView, SchemaSet};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -16,10 +17,14 @@ use std::thread::JoinHandle;
 pub(crate) struct JobExecutorThreadPoolRequestRunJob {
     pub job_id: JobId,
     pub job_type: JobTypeId,
-    pub _debug_name: Arc<String>,
+    pub debug_name: Arc<String>,
     pub _dependencies: Arc<JobEnumeratedDependencies>,
+    // Build hashes of the built data the job reads
+    pub built_data_hashes: HashMap<ArtifactId, u64>,
     pub input_data: Arc<Vec<u8>>,
-    pub data_set: Arc<DataSet>,
+    pub data_set: Arc<DataSetView>,
+    // The asset whose builder requested the job, directly or through other jobs
+    pub originating_asset: Option<AssetId>,
 }
 
 pub(crate) enum JobExecutorThreadPoolRequest {
@@ -30,6 +35,7 @@ pub struct JobExecutorThreadPoolOutcomeRunJobCompleteData {
     pub output_data: Arc<Vec<u8>>,
     pub fetched_asset_data: HashMap<AssetId, FetchedAssetData>,
     pub fetched_import_data: HashMap<AssetId, FetchedImportData>,
+    pub fetched_built_data: HashMap<ArtifactId, u64>,
     pub log_events: Vec<BuildLogEvent>,
 }
 
@@ -37,6 +43,8 @@ pub struct JobExecutorThreadPoolOutcomeRunJobCompleteData {
 pub(crate) struct JobExecutorThreadPoolOutcomeRunJobComplete {
     pub request: JobExecutorThreadPoolRequestRunJob,
     pub result: PipelineResult<JobExecutorThreadPoolOutcomeRunJobCompleteData>,
+    // How long the job took to run, including fetching the built data it reads
+    pub duration: std::time::Duration,
     //asset: SingleObject,
     //import_data: SingleObject,
 }
@@ -54,19 +62,62 @@ struct JobExecutorWorkerThread {
 fn do_build(
     job_processor_registry: &JobProcessorRegistry,
     schema_set: &SchemaSet,
-    job_api: &dyn JobApi,
+    job_api: &JobApiImpl,
     request: &JobExecutorThreadPoolRequestRunJob,
 ) -> PipelineResult<JobExecutorThreadPoolOutcomeRunJobCompleteData> {
-    profiling::scope!(&format!("Handle Job {}", request._debug_name));
+    profiling::scope!(&format!("Handle Job {}", request.debug_name));
+
+    let job_processor = job_processor_registry
+        .get_processor(request.job_type)
+        .unwrap();
+
+    let build_cache = job_api.build_cache();
+    let cache_key = job_cache_key(
+        request.job_type,
+        job_processor.version_inner(),
+        request.job_id,
+        &request.built_data_hashes,
+    );
+    if let Some(build_cache) = &build_cache {
+        profiling::scope!("Fetch Cached Job");
+        match job_api.fetch_cached_job(&**build_cache, cache_key, &request.data_set, request.job_id)
+        {
+            Ok(Some(output_data)) => {
+                log::debug!("Reused cached result of job {}", request.debug_name);
+                return Ok(JobExecutorThreadPoolOutcomeRunJobCompleteData {
+                    output_data,
+                    fetched_asset_data: Default::default(),
+                    fetched_import_data: Default::default(),
+                    fetched_built_data: request.built_data_hashes.clone(),
+                    log_events: Default::default(),
+                });
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("Could not read build cache: {}", e),
+        }
+    }
 
     let mut fetched_asset_data = HashMap::<AssetId, FetchedAssetData>::default();
     let mut fetched_import_data = HashMap::<AssetId, FetchedImportData>::default();
     let mut log_events = Vec::default();
 
+    let mut fetched_built_data = HashMap::<ArtifactId, FetchedBuiltData>::default();
+    for (&artifact_id, &build_hash) in &request.built_data_hashes {
+        fetched_built_data.insert(
+            artifact_id,
+            FetchedBuiltData {
+                build_hash,
+                data: job_api.fetch_built_artifact(artifact_id, build_hash)?,
+            },
+        );
+    }
+
+    // Record what the job produces so that it can be stored in the build cache
+    if build_cache.is_some() {
+        job_api.begin_job_recording(request.job_id);
+    }
+
     // Execute the job
-    let job_processor = job_processor_registry
-        .get_processor(request.job_type)
-        .unwrap();
     let output_data = {
         profiling::scope!(&format!("JobProcessor::run_inner"));
         job_processor.run_inner(
@@ -77,14 +128,45 @@ fn do_build(
             job_api,
             &mut fetched_asset_data,
             &mut fetched_import_data,
+            &fetched_built_data,
             &mut log_events,
         )
-    }?;
+    };
+
+    let recording = job_api.end_job_recording(request.job_id);
+    let output_data = output_data?;
+
+    // Jobs that logged anything aren't cached so that the messages are logged again next time
+    if let (Some(build_cache), Some(recording)) = (&build_cache, recording) {
+        if !recording.requested_jobs && log_events.is_empty() {
+            profiling::scope!("Store Cached Job");
+            let read_assets = fetched_asset_data
+                .keys()
+                .chain(fetched_import_data.keys())
+                .chain(request.originating_asset.iter())
+                .copied()
+                .collect::<HashSet<_>>();
+            if let Err(e) = job_api.store_cached_job(
+                &**build_cache,
+                cache_key,
+                &request.data_set,
+                read_assets.into_iter(),
+                recording,
+                &output_data,
+            ) {
+                log::warn!("Could not write to build cache: {}", e);
+            }
+        }
+    }
 
     Ok(JobExecutorThreadPoolOutcomeRunJobCompleteData {
         output_data,
         fetched_asset_data,
         fetched_import_data,
+        fetched_built_data: fetched_built_data
+            .into_iter()
+            .map(|(artifact_id, x)| (artifact_id, x.build_hash))
+            .collect(),
         log_events,
     })
 
@@ -113,6 +195,7 @@ impl JobExecutorWorkerThread {
                         recv(request_rx) -> msg => {
                             match msg.unwrap() {
                                 JobExecutorThreadPoolRequest::RunJob(msg) => {
+                                    let start_instant = std::time::Instant::now();
                                     let result = std::panic::catch_unwind(|| {
                                         profiling::scope!("JobExecutorThreadPoolRequest::RequestBuild");
                                         do_build(
@@ -122,18 +205,21 @@ impl JobExecutorWorkerThread {
                                             &msg
                                         )
                                     });
+                                    let duration = start_instant.elapsed();
 
                                     match result {
                                         Ok(result) => {
                                             outcome_tx.send(JobExecutorThreadPoolOutcome::RunJobComplete(JobExecutorThreadPoolOutcomeRunJobComplete {
                                                 request: msg,
                                                 result,
+                                                duration,
                                             })).unwrap();
                                         },
                                         Err(_) => {
                                             outcome_tx.send(JobExecutorThreadPoolOutcome::RunJobComplete(JobExecutorThreadPoolOutcomeRunJobComplete {
                                                 request: msg,
-                                                result: Err("Panic detected in build job.".into())
+                                                result: Err("Panic detected in build job.".into()),
+                                                duration,
                                             })).unwrap();
                                         }
                                     }
@@ -1,13 +1,13 @@
 use super::{JobId, JobTypeId};
-use crate::build::{AssetArtifactIdPair, BuiltArtifact};
+use crate::build::{AssetArtifactIdPair, BuiltArtifact, StreamedArtifact};
 use crate::import::{ImportData, ImportJobs};
 use crate::{BuildLogEvent, LogEventLevel, PipelineResult};
 use hydrate_base::handle::DummySerdeContextHandle;
 use hydrate_base::hashing::HashMap;
 use hydrate_base::{ArtifactId, AssetId, BuiltArtifactHeaderData, Handle};
 use hydrate_data::{
-    DataContainerRef, DataSet, DataSetError, FieldRef, HashObjectMode, PropertyPath, Record,
-    SchemaSet, SingleObject,
+    DataContainerRef, DataSet, DataSetError, FieldRef, PropertyPath, Record, SchemaSet,
+    SingleObject,
 };
 use serde::{Deserialize, Serialize};
 use siphasher::sip128::Hasher128;
@@ -38,7 +38,7 @@ impl ImportDataProvider for ImportJobs {
         schema_set: &SchemaSet,
         asset_id: AssetId,
     ) -> PipelineResult<ImportData> {
-        crate::import::load_import_data(self.import_data_root_path(), schema_set, asset_id)
+        crate::import::load_import_data(self.project_config(), schema_set, asset_id)
     }
 }
 
@@ -85,6 +85,7 @@ pub trait JobApi: Send + Sync {
 
     fn artifact_handle_created(
         &self,
+        job_id: JobId,
         asset_id: AssetId,
         artifact_id: ArtifactId,
     );
@@ -94,10 +95,25 @@ pub trait JobApi: Send + Sync {
         artifact: BuiltArtifact,
     );
 
+    // Writes the artifact's data directly to storage as it is produced, so that large artifacts
+    // are never held in memory in full
+    fn produce_artifact_streaming(
+        &self,
+        artifact: StreamedArtifact,
+    ) -> PipelineResult<()>;
+
     fn fetch_import_data(
         &self,
+        data_set: &DataSet,
         asset_id: AssetId,
     ) -> PipelineResult<ImportData>;
+
+    // Returns the data of an artifact that was already built, without its header
+    fn fetch_built_artifact(
+        &self,
+        artifact_id: ArtifactId,
+        build_hash: u64,
+    ) -> PipelineResult<Vec<u8>>;
 }
 
 //
@@ -123,7 +139,10 @@ pub struct JobEnumeratedDependencies {
     // Alternatively, jobs that read assets must always copy data out of the data set into a hashable
     // form and pass it as input to a job.
     //pub import_data: Vec<AssetId>,
-    //pub built_data: Vec<ArtifactId>,
+
+    // Artifacts built for other assets that the job reads with RunContext::read_built_artifact. The
+    // assets are built if they weren't already, and the job doesn't run until the artifacts exist.
+    pub built_data: Vec<AssetArtifactIdPair>,
     pub upstream_jobs: Vec<JobId>,
 }
 
@@ -149,6 +168,7 @@ pub(crate) trait JobProcessorAbstract: Send + Sync + RefUnwindSafe {
         job_api: &dyn JobApi,
         fetched_asset_data: &mut HashMap<AssetId, FetchedAssetData>,
         fetched_import_data: &mut HashMap<AssetId, FetchedImportData>,
+        fetched_built_data: &HashMap<ArtifactId, FetchedBuiltData>,
         log_events: &mut Vec<BuildLogEvent>,
     ) -> PipelineResult<Arc<Vec<u8>>>;
 }
@@ -226,6 +246,11 @@ pub(crate) struct FetchedImportData {
     pub(crate) import_data: Arc<SingleObject>,
 }
 
+pub(crate) struct FetchedBuiltData {
+    pub(crate) build_hash: u64,
+    pub(crate) data: Vec<u8>,
+}
+
 #[derive(Copy, Clone)]
 pub struct RunContext<'a, InputT> {
     pub job_id: JobId,
@@ -234,6 +259,7 @@ pub struct RunContext<'a, InputT> {
     pub schema_set: &'a SchemaSet,
     pub(crate) fetched_asset_data: &'a Rc<RefCell<&'a mut HashMap<AssetId, FetchedAssetData>>>,
     pub(crate) fetched_import_data: &'a Rc<RefCell<&'a mut HashMap<AssetId, FetchedImportData>>>,
+    pub(crate) fetched_built_data: &'a HashMap<ArtifactId, FetchedBuiltData>,
     pub(crate) job_api: &'a dyn JobApi,
     pub(crate) log_events: &'a Rc<RefCell<&'a mut Vec<BuildLogEvent>>>,
 }
@@ -269,10 +295,10 @@ impl<'a, InputT> RunContext<'a, InputT> {
         log_events.push(log_event);
     }
 
-    pub fn asset<T: Record>(
-        &'a self,
+    fn fetch_asset<T: Record>(
+        &self,
         asset_id: AssetId,
-    ) -> PipelineResult<T::Reader<'a>> {
+    ) -> PipelineResult<()> {
         if self
             .data_set
             .asset_schema(asset_id)
@@ -289,16 +315,64 @@ impl<'a, InputT> RunContext<'a, InputT> {
             .or_insert_with(|| FetchedAssetData {
                 _contents_hash: self
                     .data_set
-                    .hash_object(asset_id, HashObjectMode::PropertiesOnly)
+                    .hash_object_build_inputs(self.schema_set, asset_id)
                     .unwrap(),
             });
 
+        Ok(())
+    }
+
+    pub fn asset<T: Record>(
+        &'a self,
+        asset_id: AssetId,
+    ) -> PipelineResult<T::Reader<'a>> {
+        self.fetch_asset::<T>(asset_id)?;
+
+        // Builders usually read most fields of the asset, so avoid re-walking the prototype chain
+        // and re-validating parent paths for every read
+        let asset_view = self.data_set.asset_view(self.schema_set, asset_id)?;
+        Ok(<T as Record>::Reader::new(
+            PropertyPath::default(),
+            DataContainerRef::from_asset_view(asset_view),
+        ))
+    }
+
+    // Like asset(), but the reader borrows the data set directly rather than caching the asset's
+    // prototype chain. Cheaper when only a few properties of the asset are read.
+    pub fn asset_reader<T: Record>(
+        &'a self,
+        asset_id: AssetId,
+    ) -> PipelineResult<T::Reader<'a>> {
+        self.fetch_asset::<T>(asset_id)?;
+
         Ok(<T as Record>::Reader::new(
             PropertyPath::default(),
             DataContainerRef::from_dataset(self.data_set, self.schema_set, asset_id),
         ))
     }
 
+    // Returns the other assets imported from the same source file as the given asset, i.e. the
+    // materials imported from the same .gltf as a mesh. They can be read like any other asset.
+    pub fn sibling_importables(
+        &self,
+        asset_id: AssetId,
+    ) -> Vec<AssetId> {
+        self.data_set.sibling_importables(asset_id)
+    }
+
+    // Reads the build settings assigned to the given asset. Returns None if the asset has no build
+    // settings assigned, in which case the job should use its defaults.
+    pub fn build_settings<T: Record>(
+        &'a self,
+        asset_id: AssetId,
+    ) -> PipelineResult<Option<T::Reader<'a>>> {
+        let Some(build_settings) = self.data_set.build_settings(asset_id) else {
+            return Ok(None);
+        };
+
+        Ok(Some(self.asset::<T>(build_settings)?))
+    }
+
     pub fn imported_data<T: Record>(
         &'a self,
         asset_id: AssetId,
@@ -307,7 +381,8 @@ impl<'a, InputT> RunContext<'a, InputT> {
         let import_data = if let Some(fetched_import_data) = fetched_import_data.get(&asset_id) {
             fetched_import_data.import_data.clone()
         } else {
-            let newly_fetched_import_data = self.job_api.fetch_import_data(asset_id)?;
+            let newly_fetched_import_data =
+                self.job_api.fetch_import_data(self.data_set, asset_id)?;
             let import_data = Arc::new(newly_fetched_import_data.import_data);
 
             let old = fetched_import_data.insert(
@@ -328,12 +403,30 @@ impl<'a, InputT> RunContext<'a, InputT> {
             Err(DataSetError::InvalidSchema)?;
         }
 
+        // The reader shares the fetched import data through the Arc rather than cloning it. It
+        // can't borrow it because the cache is behind a RefCell.
         return Ok(<T as Record>::Reader::new(
             PropertyPath::default(),
-            DataContainerRef::from_single_object_arc(import_data.clone(), self.schema_set),
+            DataContainerRef::from_single_object_arc(import_data, self.schema_set),
         ));
     }
 
+    // Reads the data of an artifact built for another asset. The artifact must be listed in the
+    // job's built data dependencies.
+    pub fn read_built_artifact(
+        &self,
+        artifact_id: ArtifactId,
+    ) -> PipelineResult<&'a [u8]> {
+        let fetched_built_data = self.fetched_built_data.get(&artifact_id).ok_or_else(|| {
+            format!(
+                "Built artifact {:?} was read but is not in the job's built data dependencies",
+                artifact_id
+            )
+        })?;
+
+        Ok(&fetched_built_data.data)
+    }
+
     pub fn enqueue_job<JobProcessorT: JobProcessor>(
         &self,
         input: <JobProcessorT as JobProcessor>::InputT,
@@ -354,7 +447,7 @@ impl<'a, InputT> RunContext<'a, InputT> {
         artifact_key: Option<KeyT>,
         asset: ArtifactT,
     ) -> PipelineResult<AssetArtifactIdPair> {
-        produce_artifact(self.job_api, asset_id, artifact_key, asset)
+        produce_artifact(self.job_api, self.job_id, asset_id, artifact_key, asset)
     }
 
     pub fn produce_artifact_with_handles<
@@ -367,7 +460,68 @@ impl<'a, InputT> RunContext<'a, InputT> {
         artifact_key: Option<KeyT>,
         asset_fn: F,
     ) -> PipelineResult<ArtifactId> {
-        produce_artifact_with_handles(self.job_api, asset_id, artifact_key, asset_fn)
+        produce_artifact_with_handles(self.job_api, self.job_id, asset_id, artifact_key, asset_fn)
+    }
+
+    // Produces an artifact from bytes that were already serialized by the caller, for artifact types
+    // that don't use bincode. The artifact storage for the given type will receive these bytes as-is
+    pub fn produce_artifact_bytes<KeyT: Hash + std::fmt::Display>(
+        &self,
+        asset_id: AssetId,
+        artifact_key: Option<KeyT>,
+        artifact_type: uuid::Uuid,
+        data: Vec<u8>,
+        dependencies: Vec<ArtifactId>,
+    ) -> PipelineResult<ArtifactId> {
+        produce_artifact_bytes(
+            self.job_api,
+            self.job_id,
+            asset_id,
+            artifact_key,
+            artifact_type,
+            data,
+            dependencies,
+        )
+    }
+
+    // Same as produce_artifact_with_handles, but the artifact is serialized directly to storage
+    // instead of into a buffer first. Use this for large artifacts (i.e. vertex buffers) so that
+    // the artifact and its serialized bytes don't both need to fit in memory.
+    pub fn produce_artifact_streaming<
+        KeyT: Hash + std::fmt::Display,
+        ArtifactT: TypeUuid + Serialize,
+        F: FnOnce(HandleFactory) -> PipelineResult<ArtifactT>,
+    >(
+        &self,
+        asset_id: AssetId,
+        artifact_key: Option<KeyT>,
+        asset_fn: F,
+    ) -> PipelineResult<ArtifactId> {
+        produce_artifact_streaming(self.job_api, self.job_id, asset_id, artifact_key, asset_fn)
+    }
+
+    // Same as produce_artifact_bytes, but the caller writes the bytes to the given writer as they
+    // are produced rather than passing them all at once
+    pub fn produce_artifact_bytes_streaming<
+        KeyT: Hash + std::fmt::Display,
+        F: FnOnce(&mut dyn std::io::Write) -> PipelineResult<()>,
+    >(
+        &self,
+        asset_id: AssetId,
+        artifact_key: Option<KeyT>,
+        artifact_type: uuid::Uuid,
+        dependencies: Vec<ArtifactId>,
+        write_fn: F,
+    ) -> PipelineResult<ArtifactId> {
+        produce_artifact_bytes_streaming(
+            self.job_api,
+            self.job_id,
+            asset_id,
+            artifact_key,
+            artifact_type,
+            dependencies,
+            write_fn,
+        )
     }
 
     pub fn produce_default_artifact<AssetT: TypeUuid + Serialize>(
@@ -375,7 +529,7 @@ impl<'a, InputT> RunContext<'a, InputT> {
         asset_id: AssetId,
         asset: AssetT,
     ) -> PipelineResult<ArtifactId> {
-        produce_default_artifact(self.job_api, asset_id, asset)
+        produce_default_artifact(self.job_api, self.job_id, asset_id, asset)
     }
 
     pub fn produce_default_artifact_with_handles<
@@ -386,7 +540,7 @@ impl<'a, InputT> RunContext<'a, InputT> {
         asset_id: AssetId,
         asset_fn: F,
     ) -> PipelineResult<ArtifactId> {
-        produce_default_artifact_with_handles(self.job_api, asset_id, asset_fn)
+        produce_default_artifact_with_handles(self.job_api, self.job_id, asset_id, asset_fn)
     }
 }
 
@@ -442,10 +596,13 @@ pub(crate) fn enqueue_job<T: JobProcessor>(
 
 fn produce_default_artifact<T: TypeUuid + Serialize>(
     job_api: &dyn JobApi,
+    job_id: JobId,
     asset_id: AssetId,
     asset: T,
 ) -> PipelineResult<ArtifactId> {
-    produce_artifact_with_handles(job_api, asset_id, None::<u32>, |_handle_factory| Ok(asset))
+    produce_artifact_with_handles(job_api, job_id, asset_id, None::<u32>, |_handle_factory| {
+        Ok(asset)
+    })
 }
 
 fn produce_default_artifact_with_handles<
@@ -453,22 +610,27 @@ fn produce_default_artifact_with_handles<
     F: FnOnce(HandleFactory) -> PipelineResult<T>,
 >(
     job_api: &dyn JobApi,
+    job_id: JobId,
     asset_id: AssetId,
     asset_fn: F,
 ) -> PipelineResult<ArtifactId> {
-    produce_artifact_with_handles(job_api, asset_id, None::<u32>, asset_fn)
+    produce_artifact_with_handles(job_api, job_id, asset_id, None::<u32>, asset_fn)
 }
 
 fn produce_artifact<T: TypeUuid + Serialize, U: Hash + std::fmt::Display>(
     job_api: &dyn JobApi,
+    job_id: JobId,
     asset_id: AssetId,
     artifact_key: Option<U>,
     asset: T,
 ) -> PipelineResult<AssetArtifactIdPair> {
-    let artifact_id =
-        produce_artifact_with_handles(job_api, asset_id, artifact_key, |_handle_factory| {
-            Ok(asset)
-        })?;
+    let artifact_id = produce_artifact_with_handles(
+        job_api,
+        job_id,
+        asset_id,
+        artifact_key,
+        |_handle_factory| Ok(asset),
+    )?;
     Ok(AssetArtifactIdPair {
         asset_id,
         artifact_id,
@@ -481,6 +643,7 @@ fn produce_artifact_with_handles<
     F: FnOnce(HandleFactory) -> PipelineResult<T>,
 >(
     job_api: &dyn JobApi,
+    job_id: JobId,
     asset_id: AssetId,
     artifact_key: Option<U>,
     asset_fn: F,
@@ -492,7 +655,7 @@ fn produce_artifact_with_handles<
     ctx.begin_serialize_artifact(artifact_id);
 
     let (built_data, asset_type) = ctx.scope(|| {
-        let asset = (asset_fn)(HandleFactory { job_api });
+        let asset = (asset_fn)(HandleFactory { job_api, job_id });
         asset.map(|x| (bincode::serialize(&x), x.uuid()))
     })?;
 
@@ -505,6 +668,7 @@ fn produce_artifact_with_handles<
         artifact_key_debug_name
     );
     job_api.produce_artifact(BuiltArtifact {
+        job_id,
         asset_id,
         artifact_id,
         metadata: BuiltArtifactHeaderData {
@@ -521,9 +685,130 @@ fn produce_artifact_with_handles<
     Ok(artifact_id)
 }
 
+fn produce_artifact_bytes<U: Hash + std::fmt::Display>(
+    job_api: &dyn JobApi,
+    job_id: JobId,
+    asset_id: AssetId,
+    artifact_key: Option<U>,
+    artifact_type: uuid::Uuid,
+    data: Vec<u8>,
+    dependencies: Vec<ArtifactId>,
+) -> PipelineResult<ArtifactId> {
+    let artifact_key_debug_name = artifact_key.as_ref().map(|x| format!("{}", x));
+    let artifact_id = create_artifact_id(asset_id, artifact_key);
+
+    log::trace!(
+        "produce_artifact_bytes {:?} {:?} {:?}",
+        asset_id,
+        artifact_id,
+        artifact_key_debug_name
+    );
+    job_api.produce_artifact(BuiltArtifact {
+        job_id,
+        asset_id,
+        artifact_id,
+        metadata: BuiltArtifactHeaderData {
+            dependencies,
+            asset_type: artifact_type,
+        },
+        data,
+        artifact_key_debug_name,
+    });
+
+    Ok(artifact_id)
+}
+
+fn produce_artifact_streaming<
+    T: TypeUuid + Serialize,
+    U: Hash + std::fmt::Display,
+    F: FnOnce(HandleFactory) -> PipelineResult<T>,
+>(
+    job_api: &dyn JobApi,
+    job_id: JobId,
+    asset_id: AssetId,
+    artifact_key: Option<U>,
+    asset_fn: F,
+) -> PipelineResult<ArtifactId> {
+    let artifact_key_debug_name = artifact_key.as_ref().map(|x| format!("{}", x));
+    let artifact_id = create_artifact_id(asset_id, artifact_key);
+
+    log::trace!(
+        "produce_artifact_streaming {:?} {:?} {:?}",
+        asset_id,
+        artifact_id,
+        artifact_key_debug_name
+    );
+    job_api.produce_artifact_streaming(StreamedArtifact {
+        job_id,
+        asset_id,
+        artifact_id,
+        write_data: Box::new(move |writer| {
+            let mut ctx = DummySerdeContextHandle::default();
+            ctx.begin_serialize_artifact(artifact_id);
+
+            let asset_type = ctx.scope(|| -> PipelineResult<_> {
+                let asset = (asset_fn)(HandleFactory { job_api, job_id })?;
+                bincode::serialize_into(writer, &asset)?;
+                Ok(asset.uuid())
+            })?;
+
+            let referenced_assets = ctx.end_serialize_artifact(artifact_id);
+            Ok(BuiltArtifactHeaderData {
+                dependencies: referenced_assets
+                    .into_iter()
+                    .map(|x| ArtifactId::from_uuid(x.0.as_uuid()))
+                    .collect(),
+                asset_type: uuid::Uuid::from_bytes(asset_type),
+            })
+        }),
+        artifact_key_debug_name,
+    })?;
+
+    Ok(artifact_id)
+}
+
+fn produce_artifact_bytes_streaming<
+    U: Hash + std::fmt::Display,
+    F: FnOnce(&mut dyn std::io::Write) -> PipelineResult<()>,
+>(
+    job_api: &dyn JobApi,
+    job_id: JobId,
+    asset_id: AssetId,
+    artifact_key: Option<U>,
+    artifact_type: uuid::Uuid,
+    dependencies: Vec<ArtifactId>,
+    write_fn: F,
+) -> PipelineResult<ArtifactId> {
+    let artifact_key_debug_name = artifact_key.as_ref().map(|x| format!("{}", x));
+    let artifact_id = create_artifact_id(asset_id, artifact_key);
+
+    log::trace!(
+        "produce_artifact_bytes_streaming {:?} {:?} {:?}",
+        asset_id,
+        artifact_id,
+        artifact_key_debug_name
+    );
+    job_api.produce_artifact_streaming(StreamedArtifact {
+        job_id,
+        asset_id,
+        artifact_id,
+        write_data: Box::new(move |writer| {
+            (write_fn)(writer)?;
+            Ok(BuiltArtifactHeaderData {
+                dependencies,
+                asset_type: artifact_type,
+            })
+        }),
+        artifact_key_debug_name,
+    })?;
+
+    Ok(artifact_id)
+}
+
 #[derive(Copy, Clone)]
 pub struct HandleFactory<'a> {
     job_api: &'a dyn JobApi,
+    job_id: JobId,
 }
 
 impl<'a> HandleFactory<'a> {
@@ -539,6 +824,7 @@ impl<'a> HandleFactory<'a> {
         asset_artifact_id_pair: AssetArtifactIdPair,
     ) -> Handle<T> {
         self.job_api.artifact_handle_created(
+            self.job_id,
             asset_artifact_id_pair.asset_id,
             asset_artifact_id_pair.artifact_id,
         );
@@ -552,7 +838,8 @@ impl<'a> HandleFactory<'a> {
         asset_id: AssetId,
         artifact_id: ArtifactId,
     ) -> Handle<T> {
-        self.job_api.artifact_handle_created(asset_id, artifact_id);
+        self.job_api
+            .artifact_handle_created(self.job_id, asset_id, artifact_id);
         hydrate_base::handle::make_handle_within_serde_context::<T>(artifact_id)
     }
 
@@ -562,7 +849,8 @@ impl<'a> HandleFactory<'a> {
         artifact_key: Option<K>,
     ) -> Handle<T> {
         let artifact_id = create_artifact_id(asset_id, artifact_key);
-        self.job_api.artifact_handle_created(asset_id, artifact_id);
+        self.job_api
+            .artifact_handle_created(self.job_id, asset_id, artifact_id);
         hydrate_base::handle::make_handle_within_serde_context::<T>(artifact_id)
     }
 }
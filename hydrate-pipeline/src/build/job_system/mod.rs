@@ -7,6 +7,10 @@ pub use executor::*;
 mod job_executor_thread_pool;
 use job_executor_thread_pool::*;
 
+mod build_cache;
+use build_cache::*;
+pub use build_cache::{BuildCacheProvider, FileSystemBuildCacheProvider};
+
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -1,9 +1,15 @@
 pub mod job_system;
 pub use job_system::*;
 
+mod build_estimate;
+pub use build_estimate::*;
+
 mod build_jobs;
 pub use build_jobs::*;
 
+mod build_profile;
+pub use build_profile::*;
+
 mod build_types;
 pub use build_types::*;
 
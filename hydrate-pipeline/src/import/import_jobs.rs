@@ -1,8 +1,8 @@
 use crossbeam_channel::Receiver;
 use hydrate_base::hashing::HashMap;
-use hydrate_base::AssetId;
+use hydrate_base::{canonicalize_path, AssetId};
 use std::collections::VecDeque;
-use std::hash::{Hash, Hasher};
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -12,23 +12,47 @@ use crate::import::import_thread_pool::{
     ImportThreadOutcome, ImportThreadRequest, ImportThreadRequestImport, ImportWorkerThreadPool,
 };
 use crate::import::import_util::RequestedImportable;
+use crate::import::packed_import_data::PackedImportData;
+use crate::import::source_file::SourceFileState;
 use crate::{
-    DynEditorModel, HydrateProjectConfiguration, ImportJobToQueue, ImportLogData, ImportLogEvent,
-    LogEventLevel, PipelineResult,
+    BuildProfile, DynEditorModel, HydrateProjectConfiguration, ImportJobToQueue, ImportLogData,
+    ImportLogEvent, LogEventLevel, PipelineResult,
 };
 use hydrate_base::uuid_path::{path_to_uuid, uuid_to_path};
 use hydrate_data::ImportableName;
-use hydrate_data::{ImporterId, SchemaSet, SingleObject};
+use hydrate_data::{DataSet, ImporterId, SchemaSet, SingleObject};
 
 use super::import_types::*;
 use super::importer_registry::*;
 
 pub fn load_import_data(
-    import_data_root_path: &Path,
+    project_config: &HydrateProjectConfiguration,
     schema_set: &SchemaSet,
     asset_id: AssetId,
 ) -> PipelineResult<ImportData> {
     profiling::scope!(&format!("Load asset import data {:?}", asset_id));
+    let import_data_root_path = &project_config.import_data_path;
+
+    if project_config.pack_import_data {
+        let packed_import_data = PackedImportData::open(import_data_root_path)?;
+        let data = packed_import_data
+            .read(asset_id)?
+            .ok_or_else(|| format!("No packed import data for asset {:?}", asset_id))?;
+        let metadata_hash = packed_import_data.metadata_hash(asset_id).unwrap();
+
+        // b3f format
+        let import_data = super::import_storage::load_import_data_from_b3f(
+            schema_set,
+            &mut std::io::Cursor::new(data),
+        )?;
+
+        return Ok(ImportData {
+            import_data: import_data.single_object,
+            contents_hash: import_data.metadata.import_data_contents_hash,
+            metadata_hash,
+        });
+    }
+
     let path = uuid_to_path(import_data_root_path, asset_id.as_uuid(), "if");
 
     // b3f format
@@ -47,6 +71,152 @@ pub fn load_import_data(
     })
 }
 
+// The asset's copied import data exactly as it is stored, whether it is packed or in its own file.
+// Returns None if the project has no import data for the asset.
+pub fn read_import_data_bytes(
+    project_config: &HydrateProjectConfiguration,
+    asset_id: AssetId,
+) -> PipelineResult<Option<Vec<u8>>> {
+    let import_data_root_path = &project_config.import_data_path;
+    if project_config.pack_import_data {
+        return PackedImportData::open(import_data_root_path)?.read(asset_id);
+    }
+
+    let path = uuid_to_path(import_data_root_path, asset_id.as_uuid(), "if");
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    Ok(Some(std::fs::read(path)?))
+}
+
+// Stores import data that was read with read_import_data_bytes(), possibly from another project
+pub fn write_import_data_bytes(
+    project_config: &HydrateProjectConfiguration,
+    asset_id: AssetId,
+    data: &[u8],
+) -> PipelineResult<()> {
+    let import_data_root_path = &project_config.import_data_path;
+    if project_config.pack_import_data {
+        return PackedImportData::open(import_data_root_path)?.write(asset_id, data);
+    }
+
+    let path = uuid_to_path(import_data_root_path, asset_id.as_uuid(), "if");
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(path, data)?;
+    Ok(())
+}
+
+// Import data that was not copied into the project is produced by importing the source file again
+// from where it is. This fails if the source file is missing or changed since it was imported,
+// because then the import data would not match the asset.
+pub fn load_external_import_data(
+    project_config: &HydrateProjectConfiguration,
+    importer_registry: &ImporterRegistry,
+    schema_set: &SchemaSet,
+    data_set: &DataSet,
+    asset_id: AssetId,
+) -> PipelineResult<ImportData> {
+    profiling::scope!(&format!("Load asset external import data {:?}", asset_id));
+    let import_info = data_set
+        .import_info(asset_id)
+        .ok_or_else(|| format!("Asset {:?} was not imported", asset_id))?;
+
+    let source_file_path = import_info
+        .source_file()
+        .canonicalized_absolute_path(project_config, Path::new(""))
+        .map_err(|_| {
+            format!(
+                "External source file {:?} for asset {:?} is missing",
+                import_info.source_file().path(),
+                asset_id
+            )
+        })?;
+    let source_file_path = PathBuf::from(source_file_path.path());
+
+    if !SourceFileState::new(
+        &source_file_path,
+        project_config.source_file_change_detection,
+    )?
+    .matches(&ImportDataMetadata::from_import_info(import_info))?
+    {
+        Err(format!(
+            "External source file {:?} for asset {:?} changed since it was imported, it must be re-imported",
+            source_file_path, asset_id
+        ))?;
+    }
+
+    let importer = importer_registry
+        .importer(import_info.importer_id())
+        .ok_or_else(|| {
+            format!(
+                "Importer {:?} for asset {:?} is not registered",
+                import_info.importer_id(),
+                asset_id
+            )
+        })?;
+    let source_file_bytes = super::source_file::read_source_file(&source_file_path)?;
+
+    let importable_name = import_info.importable_name().clone();
+    let mut importable_assets = HashMap::default();
+    importable_assets.insert(
+        importable_name.clone(),
+        ImportableAsset {
+            id: asset_id,
+            canonical_path_references: data_set
+                .resolve_all_path_reference_overrides(asset_id)
+                .unwrap_or_default(),
+            path_references: import_info.path_references().clone(),
+        },
+    );
+
+    // Any problems were already reported when the file was imported
+    let mut imported_importables = HashMap::default();
+    let mut log_events = Vec::default();
+    let mut warnings = Vec::default();
+    importer.import_file(ImportContext::new(
+        ImportSourceFile {
+            path: &source_file_path,
+            bytes: &source_file_bytes,
+        },
+        &importable_assets,
+        schema_set,
+        project_config,
+        &mut imported_importables,
+        &mut log_events,
+        &mut warnings,
+    ))?;
+
+    let import_data = imported_importables
+        .remove(&importable_name)
+        .and_then(|x| x.import_data)
+        .ok_or_else(|| {
+            format!(
+                "Importer produced no import data for asset {:?} from {:?}",
+                asset_id, source_file_path
+            )
+        })?;
+
+    // The source file matches what was imported, so hash what was recorded at import. If the
+    // contents were hashed, only they are used, so touching the file doesn't change the hash.
+    let mut hasher = siphasher::sip::SipHasher::default();
+    if import_info.source_file_contents_hash() != 0 {
+        import_info.source_file_contents_hash().hash(&mut hasher);
+    } else {
+        import_info
+            .source_file_modified_timestamp()
+            .hash(&mut hasher);
+        import_info.source_file_size().hash(&mut hasher);
+    }
+    let metadata_hash = hasher.finish();
+
+    Ok(ImportData {
+        contents_hash: project_config.content_hash_algorithm.hash_one(&import_data),
+        import_data,
+        metadata_hash,
+    })
+}
+
 pub(super) fn hash_file_metadata(metadata: &std::fs::Metadata) -> u64 {
     let mut hasher = siphasher::sip::SipHasher::default();
     metadata.modified().unwrap().hash(&mut hasher);
@@ -128,6 +298,8 @@ pub struct ImportJobs {
     import_jobs: HashMap<AssetId, ImportJob>,
     import_operations: VecDeque<ImportJobToQueue>,
     current_import_task: Option<ImportTask>,
+    // Import timings since the last build started, they are reported in that build's profile
+    import_profile: BuildProfile,
 }
 
 impl ImportJobs {
@@ -136,6 +308,14 @@ impl ImportJobs {
         old_asset_id: AssetId,
         new_asset_id: AssetId,
     ) -> PipelineResult<()> {
+        if self.project_config.pack_import_data {
+            let packed_import_data = PackedImportData::open(&self.import_data_root_path)?;
+            let data = packed_import_data
+                .read(old_asset_id)?
+                .ok_or_else(|| format!("No packed import data for asset {:?}", old_asset_id))?;
+            return packed_import_data.write(new_asset_id, &data);
+        }
+
         let old_path = uuid_to_path(&self.import_data_root_path, old_asset_id.as_uuid(), "if");
         let new_path = uuid_to_path(&self.import_data_root_path, new_asset_id.as_uuid(), "if");
         std::fs::create_dir_all(new_path.parent().unwrap())?;
@@ -155,14 +335,33 @@ impl ImportJobs {
         &self.import_data_root_path
     }
 
+    pub fn project_config(&self) -> &HydrateProjectConfiguration {
+        &self.project_config
+    }
+
+    // Points at import data that has been moved to project_config.import_data_path
+    pub fn relocate(
+        &mut self,
+        project_config: &HydrateProjectConfiguration,
+    ) {
+        assert!(!self.is_importing());
+        PackedImportData::close(&self.import_data_root_path);
+        self.project_config = project_config.clone();
+        self.import_data_root_path = project_config.import_data_path.clone();
+    }
+
     pub fn new(
         project_config: &HydrateProjectConfiguration,
         importer_registry: &ImporterRegistry,
         editor_model: &dyn DynEditorModel,
         import_data_root_path: &Path,
     ) -> Self {
-        let import_jobs =
-            ImportJobs::find_all_jobs(importer_registry, editor_model, import_data_root_path);
+        let import_jobs = ImportJobs::find_all_jobs(
+            project_config,
+            importer_registry,
+            editor_model,
+            import_data_root_path,
+        );
 
         ImportJobs {
             project_config: project_config.clone(),
@@ -170,9 +369,15 @@ impl ImportJobs {
             import_jobs,
             import_operations: Default::default(),
             current_import_task: None,
+            import_profile: Default::default(),
         }
     }
 
+    // Starts a new profile for the next build, returning the import timings gathered so far
+    pub(crate) fn take_import_profile(&mut self) -> BuildProfile {
+        std::mem::take(&mut self.import_profile)
+    }
+
     pub fn queue_import_operation(
         &mut self,
         import_job_to_queue: ImportJobToQueue,
@@ -188,6 +393,14 @@ impl ImportJobs {
         &self,
         asset_id: AssetId,
     ) -> ImportDataMetadataHash {
+        if self.project_config.pack_import_data {
+            let metadata_hash = PackedImportData::open(&self.import_data_root_path)
+                .unwrap()
+                .metadata_hash(asset_id)
+                .unwrap();
+            return ImportDataMetadataHash { metadata_hash };
+        }
+
         let path = uuid_to_path(&self.import_data_root_path, asset_id.as_uuid(), "if");
         //println!("LOAD DATA HASH PATH {:?}", path);
         let metadata = path.metadata().unwrap();
@@ -237,12 +450,8 @@ impl ImportJobs {
         let mut existing_asset_import_state = HashMap::default();
         for (asset_id, asset_info) in editor_model.data_set().assets() {
             if let Some(import_info) = asset_info.import_info() {
-                let import_metadata = ImportDataMetadata {
-                    source_file_size: import_info.source_file_size(),
-                    source_file_modified_timestamp: import_info.source_file_modified_timestamp(),
-                    import_data_contents_hash: import_info.import_data_contents_hash(),
-                };
-                existing_asset_import_state.insert(*asset_id, import_metadata);
+                existing_asset_import_state
+                    .insert(*asset_id, ImportDataMetadata::from_import_info(import_info));
             }
         }
         let existing_asset_import_state = Arc::new(existing_asset_import_state);
@@ -348,35 +557,92 @@ impl ImportJobs {
             //
             for outcome in finished_import_task.result_rx.try_iter() {
                 match outcome {
-                    ImportThreadOutcome::Complete(msg) => match msg.result {
-                        Ok(result) => {
-                            for (name, imported_asset) in result {
-                                if let Some(requested_importable) =
-                                    msg.request.import_op.requested_importables.get(&name)
-                                {
-                                    editor_model.handle_import_complete(
-                                        requested_importable.asset_id,
-                                        requested_importable.asset_name.clone(),
-                                        requested_importable.asset_location.clone(),
-                                        &imported_asset.default_asset,
-                                        requested_importable.replace_with_default_asset,
-                                        imported_asset.import_info,
-                                        &requested_importable.canonical_path_references,
-                                        &requested_importable.path_references,
-                                    )?;
+                    ImportThreadOutcome::Complete(msg) => {
+                        if let Some(importer) =
+                            importer_registry.importer(msg.request.import_op.importer_id)
+                        {
+                            self.import_profile.record_import(
+                                importer.name(),
+                                msg.request
+                                    .import_op
+                                    .requested_importables
+                                    .values()
+                                    .map(|x| x.asset_id),
+                                msg.duration,
+                            );
+                        }
+
+                        for warning in &msg.warnings {
+                            // Attribute the warning to the asset produced by the importable if we can
+                            let asset_id = warning.importable_name.as_ref().and_then(|name| {
+                                msg.request
+                                    .import_op
+                                    .requested_importables
+                                    .get(name)
+                                    .map(|x| x.asset_id)
+                            });
+
+                            let message = if let Some(property_path) = &warning.property_path {
+                                format!("{}: {}", property_path, warning.message)
+                            } else {
+                                warning.message.clone()
+                            };
+
+                            finished_import_task
+                                .log_data
+                                .log_events
+                                .push(ImportLogEvent {
+                                    path: msg.request.import_op.path.clone(),
+                                    asset_id,
+                                    level: warning.severity.log_event_level(),
+                                    message,
+                                });
+                        }
+
+                        match msg.result {
+                            Ok(result) => {
+                                for (name, imported_asset) in result {
+                                    if let Some(requested_importable) =
+                                        msg.request.import_op.requested_importables.get(&name)
+                                    {
+                                        editor_model.handle_import_complete(
+                                            requested_importable.asset_id,
+                                            requested_importable.asset_name.clone(),
+                                            requested_importable.asset_location.clone(),
+                                            &imported_asset.default_asset,
+                                            requested_importable.replace_with_default_asset,
+                                            imported_asset.import_info,
+                                            &requested_importable.canonical_path_references,
+                                            &requested_importable.path_references,
+                                        )?;
+                                    }
                                 }
                             }
+                            Err(e) => {
+                                finished_import_task
+                                    .log_data
+                                    .log_events
+                                    .push(ImportLogEvent {
+                                        path: msg.request.import_op.path.clone(),
+                                        asset_id: None,
+                                        level: LogEventLevel::FatalError,
+                                        message: format!(
+                                            "Importer returned error: {}",
+                                            e.to_string()
+                                        ),
+                                    })
+                            }
                         }
-                        Err(e) => finished_import_task
-                            .log_data
-                            .log_events
-                            .push(ImportLogEvent {
-                                path: msg.request.import_op.path.clone(),
-                                asset_id: None,
-                                level: LogEventLevel::FatalError,
-                                message: format!("Importer returned error: {}", e.to_string()),
-                            }),
-                    },
+                    }
+                }
+            }
+
+            // Re-imported assets leave their previous packed import data behind as unused space
+            if self.project_config.pack_import_data {
+                if let Err(e) = PackedImportData::open(&self.import_data_root_path)
+                    .and_then(|packed_import_data| packed_import_data.compact_if_needed())
+                {
+                    log::warn!("Failed to compact packed import data: {}", e);
                 }
             }
 
@@ -410,6 +676,7 @@ impl ImportJobs {
     }
 
     fn find_all_jobs(
+        project_config: &HydrateProjectConfiguration,
         importer_registry: &ImporterRegistry,
         editor_model: &dyn DynEditorModel,
         import_data_root_path: &Path,
@@ -419,26 +686,48 @@ impl ImportJobs {
         //
         // Scan import dir for known import data
         //
-        let walker = globwalk::GlobWalkerBuilder::from_patterns(import_data_root_path, &["**.if"])
-            .file_type(globwalk::FileType::FILE)
-            .build()
-            .unwrap();
-
-        for file in walker {
-            if let Ok(file) = file {
-                let file = dunce::canonicalize(&file.path()).unwrap();
-                //println!("import file {:?}", file);
-                let import_file_uuid = path_to_uuid(import_data_root_path, &file).unwrap();
-                let asset_id = AssetId::from_uuid(import_file_uuid);
-                let job = import_jobs
-                    .entry(asset_id)
-                    .or_insert_with(|| ImportJob::new());
-
-                let file_metadata = file.metadata().unwrap();
-                let import_data_hash = hash_file_metadata(&file_metadata);
-
-                job.import_data_exists = true;
-                job.imported_data_hash = Some(import_data_hash);
+        if project_config.pack_import_data {
+            match PackedImportData::open(import_data_root_path) {
+                Ok(packed_import_data) => {
+                    for (asset_id, import_data_hash) in packed_import_data.metadata_hashes() {
+                        let job = import_jobs.entry(asset_id).or_insert_with(ImportJob::new);
+                        job.import_data_exists = true;
+                        job.imported_data_hash = Some(import_data_hash);
+                    }
+                }
+                Err(e) => log::error!(
+                    "Failed to read packed import data in {:?}: {}",
+                    import_data_root_path,
+                    e
+                ),
+            }
+        } else {
+            let walker =
+                globwalk::GlobWalkerBuilder::from_patterns(import_data_root_path, &["**.if"])
+                    .file_type(globwalk::FileType::FILE)
+                    .build()
+                    .unwrap();
+
+            for file in walker {
+                if let Ok(file) = file {
+                    let file = canonicalize_path(file.path(), project_config.path_canonicalization)
+                        .unwrap();
+                    //println!("import file {:?}", file);
+                    // Import data is stored at a path derived from the asset ID. Anything else
+                    // doesn't belong to an asset.
+                    let Some(import_file_uuid) = path_to_uuid(import_data_root_path, &file) else {
+                        log::warn!("Ignoring unrecognized import data file {:?}", file);
+                        continue;
+                    };
+                    let asset_id = AssetId::from_uuid(import_file_uuid);
+                    let job = import_jobs.entry(asset_id).or_insert_with(ImportJob::new);
+
+                    let file_metadata = file.metadata().unwrap();
+                    let import_data_hash = hash_file_metadata(&file_metadata);
+
+                    job.import_data_exists = true;
+                    job.imported_data_hash = Some(import_data_hash);
+                }
             }
         }
 
@@ -2,7 +2,7 @@ use crate::PipelineResult;
 use hydrate_base::b3f;
 use hydrate_base::b3f::B3FReader;
 use hydrate_data::json_storage::SingleObjectJson;
-use hydrate_data::{SchemaSet, SingleObject};
+use hydrate_data::{ImportInfo, SchemaSet, SingleObject};
 use serde::{Deserialize, Serialize};
 use std::hash::Hash;
 use std::sync::Arc;
@@ -17,6 +17,19 @@ pub struct ImportDataMetadata {
     pub source_file_modified_timestamp: u64,
     pub source_file_size: u64,
     pub import_data_contents_hash: u64,
+    // 0 if the source file contents were not hashed
+    pub source_file_contents_hash: u64,
+}
+
+impl ImportDataMetadata {
+    pub fn from_import_info(import_info: &ImportInfo) -> Self {
+        ImportDataMetadata {
+            source_file_modified_timestamp: import_info.source_file_modified_timestamp(),
+            source_file_size: import_info.source_file_size(),
+            import_data_contents_hash: import_info.import_data_contents_hash(),
+            source_file_contents_hash: import_info.source_file_contents_hash(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Hash)]
@@ -24,6 +37,14 @@ pub struct ImportDataHeader {
     metadata: ImportDataMetadata,
 }
 
+// Headers written before source file contents were hashed
+#[derive(Deserialize)]
+struct ImportDataHeaderWithoutSourceFileHash {
+    source_file_modified_timestamp: u64,
+    source_file_size: u64,
+    import_data_contents_hash: u64,
+}
+
 impl ImportDataHeader {
     pub fn write_header<T: std::io::Write>(
         &self,
@@ -33,7 +54,8 @@ impl ImportDataHeader {
         let bytes = serialized.len();
         // Just
         assert!(bytes <= MAX_HEADER_SIZE);
-        writer.write(&bytes.to_le_bytes())?;
+        // Fixed to 8 bytes so that the length can be read on hosts with a different pointer width
+        writer.write(&(bytes as u64).to_le_bytes())?;
         writer.write(&serialized)?;
 
         Ok(())
@@ -42,14 +64,26 @@ impl ImportDataHeader {
     pub fn read_header<T: std::io::Read>(reader: &mut T) -> std::io::Result<ImportDataHeader> {
         let mut length_bytes = [0u8; 8];
         reader.read(&mut length_bytes)?;
-        let length = usize::from_le_bytes(length_bytes);
+        let length = u64::from_le_bytes(length_bytes) as usize;
         assert!(length <= MAX_HEADER_SIZE);
 
         let mut read_buffer = vec![0u8; length];
         reader.read_exact(&mut read_buffer).unwrap();
 
-        let metadata = bincode::deserialize(&read_buffer).unwrap();
-        Ok(metadata)
+        if let Ok(header) = bincode::deserialize(&read_buffer) {
+            return Ok(header);
+        }
+
+        let old_header: ImportDataHeaderWithoutSourceFileHash =
+            bincode::deserialize(&read_buffer).unwrap();
+        Ok(ImportDataHeader {
+            metadata: ImportDataMetadata {
+                source_file_modified_timestamp: old_header.source_file_modified_timestamp,
+                source_file_size: old_header.source_file_size,
+                import_data_contents_hash: old_header.import_data_contents_hash,
+                source_file_contents_hash: 0,
+            },
+        })
     }
 }
 
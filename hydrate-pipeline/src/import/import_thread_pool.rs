@@ -1,18 +1,25 @@
 use crate::import::import_storage::ImportDataMetadata;
-use crate::import::{ImportContext, ImportOp, ImportType, ImportableAsset};
+use crate::import::packed_import_data::PackedImportData;
+use crate::import::source_file::SourceFileState;
+use crate::import::{
+    ImportContext, ImportOp, ImportSourceFile, ImportType, ImportWarning, ImportableAsset,
+};
 use crate::{HydrateProjectConfiguration, ImporterRegistry, PipelineResult};
 use crossbeam_channel::{Receiver, Sender};
 use hydrate_base::hashing::HashMap;
 use hydrate_base::uuid_path::uuid_to_path;
 use hydrate_base::AssetId;
-use hydrate_data::{ImportInfo, ImportableName, PathReference, SchemaSet, SingleObject};
-use std::hash::{Hash, Hasher};
+use hydrate_data::{
+    ImportDataStorage, ImportInfo, ImportableName, ImporterId, PathReference, SchemaSet,
+    SingleObject,
+};
+use std::collections::VecDeque;
+use std::hash::BuildHasher;
 use std::io::BufWriter;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
-use std::time::SystemTime;
 
 // Ask the thread to gather import data from the asset
 #[derive(Debug)]
@@ -38,6 +45,10 @@ pub struct ImportThreadImportedImportable {
 pub struct ImportThreadOutcomeComplete {
     pub request: ImportThreadRequestImport,
     pub result: PipelineResult<HashMap<ImportableName, ImportThreadImportedImportable>>,
+    // Non-fatal problems reported by the importer, these are returned even if the import failed
+    pub warnings: Vec<ImportWarning>,
+    // How long the import took, including checking if it could be skipped
+    pub duration: std::time::Duration,
     //asset: SingleObject,
     //import_data: SingleObject,
 }
@@ -46,12 +57,109 @@ pub enum ImportThreadOutcome {
     Complete(ImportThreadOutcomeComplete),
 }
 
+#[derive(Default)]
+struct ImportRequestDispatcherState {
+    pending_requests: VecDeque<ImportThreadRequestImport>,
+    in_flight_request_counts: HashMap<ImporterId, usize>,
+}
+
+// Holds requests back until their importer is below its Importer::max_concurrent() limit, then
+// sends them to the worker threads. Requests for other importers are not held up behind them.
+struct ImportRequestDispatcher {
+    importer_registry: ImporterRegistry,
+    request_tx: Sender<ImportThreadRequest>,
+    state: Mutex<ImportRequestDispatcherState>,
+}
+
+impl ImportRequestDispatcher {
+    fn add_request(
+        &self,
+        request: ImportThreadRequest,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        match request {
+            ImportThreadRequest::RequestImport(request) => {
+                state.pending_requests.push_back(request);
+            }
+        }
+        self.dispatch(&mut state);
+    }
+
+    // Called by a worker thread when it finishes a request
+    fn request_complete(
+        &self,
+        importer_id: ImporterId,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(count) = state.in_flight_request_counts.get_mut(&importer_id) {
+            *count -= 1;
+        }
+        self.dispatch(&mut state);
+    }
+
+    fn dispatch(
+        &self,
+        state: &mut ImportRequestDispatcherState,
+    ) {
+        // Send requests in the order they were added, skipping those whose importer is at its limit
+        let mut i = 0;
+        while i < state.pending_requests.len() {
+            let importer_id = state.pending_requests[i].import_op.importer_id;
+            let max_concurrent = self
+                .importer_registry
+                .importer(importer_id)
+                .and_then(|importer| importer.max_concurrent());
+            let in_flight_request_count = state
+                .in_flight_request_counts
+                .entry(importer_id)
+                .or_default();
+            if let Some(max_concurrent) = max_concurrent {
+                if *in_flight_request_count >= max_concurrent.max(1) {
+                    i += 1;
+                    continue;
+                }
+            }
+
+            *in_flight_request_count += 1;
+            let request = state.pending_requests.remove(i).unwrap();
+            self.request_tx
+                .send(ImportThreadRequest::RequestImport(request))
+                .unwrap();
+        }
+    }
+}
+
 // Thread that tries to take jobs out of the request channel and ends when the finish channel is signalled
 struct ImportWorkerThread {
     finish_tx: Sender<()>,
     join_handle: JoinHandle<()>,
 }
 
+trait ReadAndSeek: std::io::Read + std::io::Seek {}
+impl<T: std::io::Read + std::io::Seek> ReadAndSeek for T {}
+
+// Opens the asset's import data, wherever the project config says it is stored. Returns None if
+// the asset has no import data.
+fn open_import_data(
+    project_config: &HydrateProjectConfiguration,
+    import_data_root_path: &Path,
+    asset_id: AssetId,
+) -> PipelineResult<Option<Box<dyn ReadAndSeek>>> {
+    if project_config.pack_import_data {
+        let packed_import_data = PackedImportData::open(import_data_root_path)?;
+        Ok(packed_import_data
+            .read(asset_id)?
+            .map(|data| Box::new(std::io::Cursor::new(data)) as Box<dyn ReadAndSeek>))
+    } else {
+        let import_data_path = uuid_to_path(import_data_root_path, asset_id.as_uuid(), "if");
+        if !import_data_path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(Box::new(std::fs::File::open(import_data_path)?)))
+    }
+}
+
 fn do_import(
     project_config: &HydrateProjectConfiguration,
     importer_registry: &ImporterRegistry,
@@ -59,17 +167,19 @@ fn do_import(
     existing_asset_import_state: &HashMap<AssetId, ImportDataMetadata>,
     import_data_root_path: &Path,
     msg: &ImportThreadRequestImport,
+    warnings: &mut Vec<ImportWarning>,
 ) -> PipelineResult<HashMap<ImportableName, ImportThreadImportedImportable>> {
     //
     // Get metadata for the source file (i.e. length, last modified time)
     //
-    let source_file_metadata = msg.import_op.path.metadata()?;
-    let source_file_size = source_file_metadata.len();
-    let source_file_modified_timestamp = source_file_metadata
-        .modified()?
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .map_err(|e| format!("Error getting duration since unix epoch: {:?}", e))?
-        .as_secs();
+    let mut source_file_state = SourceFileState::new(
+        &msg.import_op.path,
+        project_config.source_file_change_detection,
+    )?;
+
+    let importer_id = msg.import_op.importer_id;
+    let importer = importer_registry.importer(importer_id).unwrap();
+    let import_data_storage = importer.import_data_storage();
 
     //
     // Compare the existing import data to the source file and see if we can skip importing this file
@@ -82,22 +192,38 @@ fn do_import(
         // Determine if any asset has stale asset or import data.
         //
         for (_, asset) in &msg.importable_assets {
-            let import_data_path = uuid_to_path(import_data_root_path, asset.id.as_uuid(), "if");
-            if !import_data_path.exists() {
+            if import_data_storage == ImportDataStorage::External {
+                //
+                // There is no import data file, so compare the source file to the asset's import
+                // info. If it's stale we have to run the full import to recover the asset.
+                //
+                let is_current = match existing_asset_import_state.get(&asset.id) {
+                    Some(asset_import_state) => source_file_state.matches(asset_import_state)?,
+                    None => false,
+                };
+                if !is_current {
+                    any_asset_has_stale_import_data = true;
+                    any_asset_has_stale_asset_data = true;
+                    break;
+                }
+
+                continue;
+            }
+
+            let Some(mut import_data_file) =
+                open_import_data(project_config, import_data_root_path, asset.id)?
+            else {
                 //
                 // Import data file is missing, we cannot reuse the data. We have to run the full import.
                 //
                 any_asset_has_stale_import_data = true;
                 any_asset_has_stale_asset_data = true;
                 break;
-            }
+            };
 
-            let mut import_data_file = std::fs::File::open(import_data_path)?;
             let metadata =
                 super::import_storage::load_import_metadata_from_b3f(&mut import_data_file)?;
-            if metadata.source_file_size != source_file_size
-                || metadata.source_file_modified_timestamp != source_file_modified_timestamp
-            {
+            if !source_file_state.matches(&metadata)? {
                 //
                 // Force re-import if the import data does not match the source file size/timestamp. We can stop
                 // as soon as we find stale import data because we will have to import.
@@ -121,6 +247,8 @@ fn do_import(
                 || asset_import_state.source_file_size != metadata.source_file_size
                 || asset_import_state.source_file_modified_timestamp
                     != metadata.source_file_modified_timestamp
+                || asset_import_state.source_file_contents_hash
+                    != metadata.source_file_contents_hash
             {
                 //
                 // The asset data does not match the source file size/timestamp. Even if import data is not
@@ -151,9 +279,9 @@ fn do_import(
                     //
                     // Load the metadata and default asset from disk
                     //
-                    let import_data_path =
-                        uuid_to_path(import_data_root_path, asset.id.as_uuid(), "if");
-                    let mut import_data_file = std::fs::File::open(import_data_path)?;
+                    let mut import_data_file =
+                        open_import_data(project_config, import_data_root_path, asset.id)?
+                            .ok_or("Import data is missing")?;
                     let metadata = super::import_storage::load_import_metadata_from_b3f(
                         &mut import_data_file,
                     )?;
@@ -162,12 +290,15 @@ fn do_import(
                         &mut import_data_file,
                     )?;
 
-                    let metadata = ImportDataMetadata {
-                        source_file_modified_timestamp,
-                        source_file_size,
-                        import_data_contents_hash: metadata.import_data_contents_hash,
-                    };
-                    let import_info = create_import_info(project_config, msg, &name, metadata);
+                    // The import data matches the source file, so its metadata describes the
+                    // source file too
+                    let import_info = create_import_info(
+                        project_config,
+                        msg,
+                        &name,
+                        metadata,
+                        ImportDataStorage::Copied,
+                    );
 
                     let old = cached_importables.insert(
                         name.clone(),
@@ -184,23 +315,29 @@ fn do_import(
         }
     }
 
-    let importer_id = msg.import_op.importer_id;
-    let importer = importer_registry.importer(importer_id).unwrap();
     let mut log_events = Vec::default();
     let mut imported_importables = HashMap::default();
 
+    // The source file may be inside an archive, so importers are given the bytes rather than
+    // reading the path themselves
+    let source_file_bytes = super::source_file::read_source_file(&msg.import_op.path)?;
+
     //
     // Do the import
     //
     {
         profiling::scope!("Importer::import_file");
         importer.import_file(ImportContext::new(
-            &msg.import_op.path,
+            ImportSourceFile {
+                path: &msg.import_op.path,
+                bytes: &source_file_bytes,
+            },
             &msg.importable_assets,
             schema_set,
             project_config,
             &mut imported_importables,
             &mut log_events,
+            warnings,
         ))?
     }
 
@@ -216,23 +353,59 @@ fn do_import(
 
             profiling::scope!(&format!("Importable {:?} {}", name, _type_name));
 
+            //
+            // Catch importer bugs here rather than when the asset is built or loaded
+            //
+            let objects_to_validate = std::iter::once(("default asset", default_asset)).chain(
+                imported_asset
+                    .import_data
+                    .as_ref()
+                    .map(|import_data| ("import data", import_data)),
+            );
+            for (object_kind, object) in objects_to_validate {
+                if let Err(e) = object.validate(schema_set) {
+                    Err(format!(
+                        "Importer produced {} for importable {:?} of file {:?} that does not match the schema: {:?}",
+                        object_kind, name, msg.import_op.path, e
+                    ))?;
+                }
+            }
+
             let mut import_data_metadata = ImportDataMetadata {
-                source_file_modified_timestamp,
-                source_file_size,
+                source_file_modified_timestamp: source_file_state.modified_timestamp,
+                source_file_size: source_file_state.size,
                 import_data_contents_hash: 0,
+                source_file_contents_hash: source_file_state
+                    .contents_hash_for_import(&source_file_bytes),
             };
 
-            //
-            // Write the import file to disk
-            //
-            {
-                let mut buf_writer = BufWriter::new(Vec::default());
+            if let Some(import_data) = &imported_asset.import_data {
+                import_data_metadata.import_data_contents_hash =
+                    project_config.content_hash_algorithm.hash_one(import_data);
+            }
 
-                if let Some(import_data) = &imported_asset.import_data {
-                    let mut contents_hasher = siphasher::sip::SipHasher::default();
-                    import_data.hash(&mut contents_hasher);
-                    import_data_metadata.import_data_contents_hash = contents_hasher.finish();
+            let path = uuid_to_path(
+                import_data_root_path,
+                requested_importable.asset_id.as_uuid(),
+                "if",
+            );
+
+            if import_data_storage == ImportDataStorage::External {
+                //
+                // Import data is not copied into the project, remove any that was copied by a
+                // previous import
+                //
+                if project_config.pack_import_data {
+                    PackedImportData::open(import_data_root_path)?
+                        .remove(requested_importable.asset_id)?;
+                } else if path.exists() {
+                    std::fs::remove_file(&path)?;
                 }
+            } else {
+                //
+                // Write the import file to disk
+                //
+                let mut buf_writer = BufWriter::new(Vec::default());
 
                 super::import_storage::save_single_object_to_b3f(
                     &mut buf_writer,
@@ -246,41 +419,47 @@ fn do_import(
                     .into_inner()
                     .map_err(|e| format!("Error converting bufwriter to Vec<u8>: {:?}", e))?;
 
-                let path = uuid_to_path(
-                    import_data_root_path,
-                    requested_importable.asset_id.as_uuid(),
-                    "if",
-                );
-
-                if let Some(parent) = path.parent() {
-                    std::fs::create_dir_all(parent).unwrap();
-                }
-
-                let mut file_needs_write = true;
-                if path.exists() {
-                    let data_on_disk = std::fs::read(&path).unwrap();
+                if project_config.pack_import_data {
+                    // Avoid unnecessary writes, they change the import data's metadata hash and
+                    // trigger unnecessary rebuilds
+                    let packed_import_data = PackedImportData::open(import_data_root_path)?;
+                    let existing_data = packed_import_data.read(requested_importable.asset_id)?;
+                    if existing_data.as_ref() != Some(&data_to_write) {
+                        packed_import_data.write(requested_importable.asset_id, &data_to_write)?;
+                    }
+                } else {
+                    if let Some(parent) = path.parent() {
+                        std::fs::create_dir_all(parent).unwrap();
+                    }
 
-                    let mut data_hasher = siphasher::sip::SipHasher::default();
-                    data_on_disk.hash(&mut data_hasher);
-                    let data_on_disk_hash = data_hasher.finish();
+                    let mut file_needs_write = true;
+                    if path.exists() {
+                        let data_on_disk = std::fs::read(&path).unwrap();
 
-                    let mut data_hasher = siphasher::sip::SipHasher::default();
-                    data_to_write.hash(&mut data_hasher);
-                    let data_hash = data_hasher.finish();
+                        let content_hash_algorithm = project_config.content_hash_algorithm;
+                        let data_on_disk_hash = content_hash_algorithm.hash_one(&data_on_disk);
+                        let data_hash = content_hash_algorithm.hash_one(&data_to_write);
 
-                    if data_on_disk_hash == data_hash {
-                        file_needs_write = false;
+                        if data_on_disk_hash == data_hash {
+                            file_needs_write = false;
+                        }
                     }
-                }
 
-                if file_needs_write {
-                    // Avoid unnecessary writes, they mutate the last modified date of the
-                    // file and trigger unnecessary rebuilds
-                    std::fs::write(&path, data_to_write).unwrap();
+                    if file_needs_write {
+                        // Avoid unnecessary writes, they mutate the last modified date of the
+                        // file and trigger unnecessary rebuilds
+                        std::fs::write(&path, data_to_write).unwrap();
+                    }
                 }
             }
 
-            let import_info = create_import_info(project_config, msg, &name, import_data_metadata);
+            let import_info = create_import_info(
+                project_config,
+                msg,
+                &name,
+                import_data_metadata,
+                import_data_storage,
+            );
             let old = written_importables.insert(
                 name,
                 ImportThreadImportedImportable {
@@ -302,6 +481,7 @@ fn create_import_info(
     msg: &ImportThreadRequestImport,
     name: &ImportableName,
     import_data_metadata: ImportDataMetadata,
+    import_data_storage: ImportDataStorage,
 ) -> ImportInfo {
     let source_file = PathReference::new(
         "".to_string(),
@@ -320,8 +500,10 @@ fn create_import_info(
             .collect(),
         import_data_metadata.source_file_modified_timestamp,
         import_data_metadata.source_file_size,
+        import_data_metadata.source_file_contents_hash,
         import_data_metadata.import_data_contents_hash,
-    );
+    )
+    .with_import_data_storage(import_data_storage);
     import_info
 }
 
@@ -334,6 +516,7 @@ impl ImportWorkerThread {
         import_data_root_path: Arc<PathBuf>,
         request_rx: Receiver<ImportThreadRequest>,
         outcome_tx: Sender<ImportThreadOutcome>,
+        dispatcher: Arc<ImportRequestDispatcher>,
         active_request_count: Arc<AtomicUsize>,
         _thread_index: usize,
     ) -> Self {
@@ -347,33 +530,44 @@ impl ImportWorkerThread {
                         recv(request_rx) -> msg => {
                             match msg.unwrap() {
                                 ImportThreadRequest::RequestImport(msg) => {
+                                    let importer_id = msg.import_op.importer_id;
+                                    let start_instant = std::time::Instant::now();
                                     let result = std::panic::catch_unwind(|| {
                                         profiling::scope!("ImportThreadRequest::RequestImport");
-                                        do_import(
+                                        let mut warnings = Vec::default();
+                                        let result = do_import(
                                             &project_config,
                                             &importer_registry,
                                             &schema_set,
                                             &*existing_asset_import_state,
                                             &*import_data_root_path,
                                             &msg,
-                                        )
+                                            &mut warnings,
+                                        );
+                                        (result, warnings)
                                     });
+                                    let duration = start_instant.elapsed();
 
                                     match result {
-                                        Ok(result) => {
+                                        Ok((result, warnings)) => {
                                             outcome_tx.send(ImportThreadOutcome::Complete(ImportThreadOutcomeComplete {
                                                 request: msg,
                                                 result,
+                                                warnings,
+                                                duration,
                                             })).unwrap();
                                         },
                                         Err(_) => {
                                             outcome_tx.send(ImportThreadOutcome::Complete(ImportThreadOutcomeComplete {
                                                 request: msg,
-                                                result: Err("Panic detected in importer.".into())
+                                                result: Err("Panic detected in importer.".into()),
+                                                warnings: Vec::default(),
+                                                duration,
                                             })).unwrap();
                                         }
                                     }
 
+                                    dispatcher.request_complete(importer_id);
                                     active_request_count.fetch_sub(1, Ordering::Release);
                                 },
                             }
@@ -396,7 +590,7 @@ impl ImportWorkerThread {
 // Spans N threads, proxies messages to/from them, and kills the threads when the pool is dropped
 pub struct ImportWorkerThreadPool {
     worker_threads: Vec<ImportWorkerThread>,
-    request_tx: Sender<ImportThreadRequest>,
+    dispatcher: Arc<ImportRequestDispatcher>,
     active_request_count: Arc<AtomicUsize>,
 }
 
@@ -412,6 +606,11 @@ impl ImportWorkerThreadPool {
     ) -> Self {
         let import_data_root_path = Arc::new(import_data_root_path.to_path_buf());
         let (request_tx, request_rx) = crossbeam_channel::unbounded::<ImportThreadRequest>();
+        let dispatcher = Arc::new(ImportRequestDispatcher {
+            importer_registry: importer_registry.clone(),
+            request_tx,
+            state: Default::default(),
+        });
         let active_request_count = Arc::new(AtomicUsize::new(0));
 
         let mut worker_threads = Vec::with_capacity(max_requests_in_flight);
@@ -424,6 +623,7 @@ impl ImportWorkerThreadPool {
                 import_data_root_path.clone(),
                 request_rx.clone(),
                 result_tx.clone(),
+                dispatcher.clone(),
                 active_request_count.clone(),
                 thread_index,
             );
@@ -431,7 +631,7 @@ impl ImportWorkerThreadPool {
         }
 
         ImportWorkerThreadPool {
-            request_tx,
+            dispatcher,
             worker_threads,
             active_request_count,
         }
@@ -450,7 +650,7 @@ impl ImportWorkerThreadPool {
         request: ImportThreadRequest,
     ) {
         self.active_request_count.fetch_add(1, Ordering::Release);
-        self.request_tx.send(request).unwrap();
+        self.dispatcher.add_request(request);
     }
 
     pub fn finish(self) {
@@ -463,3 +663,1075 @@ impl ImportWorkerThreadPool {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build::{JobExecutor, JobProcessorRegistryBuilder};
+    use crate::import::{ImportWarningSeverity, Importer, RequestedImportable, ScanContext};
+    use crate::{ImporterRegistryBuilder, PipelineError, SourceFileChangeDetection};
+    use hydrate_base::canonicalize_path;
+    use hydrate_data::{
+        canonicalize_source_file_path, join_archive_path, AssetLocation, AssetName, DataSet,
+        DataSetError, ImporterId, SchemaLinker, SchemaSetBuilder, Value,
+    };
+    use std::io::Write;
+    use type_uuid::TypeUuid;
+
+    #[derive(TypeUuid, Default)]
+    #[uuid = "0e6d0be0-6f51-4b0a-9b4c-0d5c8a0c3a21"]
+    struct WarningImporter;
+
+    impl Importer for WarningImporter {
+        fn supported_file_extensions(&self) -> &[&'static str] {
+            &["warning_test"]
+        }
+
+        fn scan_file(
+            &self,
+            _context: ScanContext,
+        ) -> PipelineResult<()> {
+            Ok(())
+        }
+
+        fn import_file(
+            &self,
+            context: ImportContext,
+        ) -> PipelineResult<()> {
+            context.add_importable_warning(
+                ImportableName::default(),
+                ImportWarningSeverity::Warning,
+                Some("tangents"),
+                "mesh has no tangents, generating defaults",
+            );
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn import_warnings_surface_in_outcome() {
+        let mut importer_registry = ImporterRegistryBuilder::default();
+        importer_registry.register_handler::<WarningImporter>();
+        let importer_registry = importer_registry.build();
+        let schema_set = SchemaSetBuilder::default().build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root_path).unwrap();
+        let source_file_path = root_path.join("test.warning_test");
+        std::fs::write(&source_file_path, "").unwrap();
+
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+
+        let request = ImportThreadRequestImport {
+            import_op: ImportOp {
+                requested_importables: Default::default(),
+                importer_id: ImporterId(uuid::Uuid::from_bytes(WarningImporter::UUID)),
+                path: source_file_path,
+                import_type: ImportType::ImportAlways,
+            },
+            importable_assets: Default::default(),
+        };
+
+        let mut warnings = Vec::default();
+        let result = do_import(
+            &project_config,
+            &importer_registry,
+            &schema_set,
+            &Default::default(),
+            &project_config.import_data_path,
+            &request,
+            &mut warnings,
+        );
+
+        std::fs::remove_dir_all(&root_path).unwrap();
+
+        // The import succeeds, and the warning is returned alongside the results
+        assert!(result.is_ok());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, ImportWarningSeverity::Warning);
+        assert_eq!(warnings[0].importable_name, Some(ImportableName::default()));
+        assert_eq!(warnings[0].property_path.as_deref(), Some("tangents"));
+    }
+
+    // A mesh file contains the relative path to its material, a material file contains its color
+    #[derive(TypeUuid, Default)]
+    #[uuid = "5c0f3b9e-8d55-4b8a-a2f4-2f0f9a8a6c11"]
+    struct TestMeshImporter;
+
+    impl Importer for TestMeshImporter {
+        fn supported_file_extensions(&self) -> &[&'static str] {
+            &["test_mesh"]
+        }
+
+        fn scan_file(
+            &self,
+            context: ScanContext,
+        ) -> PipelineResult<()> {
+            let material_path = context.read_source_file_to_string()?;
+            let mesh_record = context
+                .schema_set
+                .find_named_type("TestMesh")?
+                .as_record()?
+                .clone();
+            context
+                .add_importable_with_record(ImportableName::default(), mesh_record)?
+                .add_path_reference_with_importer::<TestMaterialImporter, _>(material_path)?;
+            Ok(())
+        }
+
+        fn import_file(
+            &self,
+            context: ImportContext,
+        ) -> PipelineResult<()> {
+            let mesh_record = context
+                .schema_set
+                .find_named_type("TestMesh")?
+                .as_record()?;
+            let mut mesh = SingleObject::new(mesh_record);
+            mesh.set_property_override(
+                context.schema_set,
+                "material",
+                Some(Value::String(Arc::new(
+                    context.source_file_string()?.to_string(),
+                ))),
+            )?;
+            context.add_default_importable(mesh, None);
+            Ok(())
+        }
+    }
+
+    #[derive(TypeUuid, Default)]
+    #[uuid = "a3f2b8c4-1b2e-4d8c-9c63-7a1e5d0b4f22"]
+    struct TestMaterialImporter;
+
+    impl Importer for TestMaterialImporter {
+        fn supported_file_extensions(&self) -> &[&'static str] {
+            &["test_material"]
+        }
+
+        fn scan_file(
+            &self,
+            context: ScanContext,
+        ) -> PipelineResult<()> {
+            let material_record = context
+                .schema_set
+                .find_named_type("TestMaterial")?
+                .as_record()?
+                .clone();
+            context.add_importable_with_record(ImportableName::default(), material_record)?;
+            Ok(())
+        }
+
+        fn import_file(
+            &self,
+            context: ImportContext,
+        ) -> PipelineResult<()> {
+            let material_record = context
+                .schema_set
+                .find_named_type("TestMaterial")?
+                .as_record()?;
+            let mut material = SingleObject::new(material_record);
+            material.set_property_override(
+                context.schema_set,
+                "color",
+                Some(Value::String(Arc::new(
+                    context.source_file_string()?.to_string(),
+                ))),
+            )?;
+            context.add_default_importable(material, None);
+            Ok(())
+        }
+    }
+
+    // Like TestMaterialImporter, but the color is import data that is read from the source file
+    // when building rather than copied into the project
+    #[derive(TypeUuid, Default)]
+    #[uuid = "d7e4a1c9-3b6f-4f2a-8e15-6c9b0a2d7f33"]
+    struct TestExternalMaterialImporter;
+
+    impl Importer for TestExternalMaterialImporter {
+        fn supported_file_extensions(&self) -> &[&'static str] {
+            &["test_external_material"]
+        }
+
+        fn scan_file(
+            &self,
+            context: ScanContext,
+        ) -> PipelineResult<()> {
+            let material_record = context
+                .schema_set
+                .find_named_type("TestMaterial")?
+                .as_record()?
+                .clone();
+            context.add_importable_with_record(ImportableName::default(), material_record)?;
+            Ok(())
+        }
+
+        fn import_file(
+            &self,
+            context: ImportContext,
+        ) -> PipelineResult<()> {
+            let material_record = context
+                .schema_set
+                .find_named_type("TestMaterial")?
+                .as_record()?;
+            let import_data_record = context
+                .schema_set
+                .find_named_type("TestMaterialImportData")?
+                .as_record()?;
+            let mut import_data = SingleObject::new(import_data_record);
+            import_data.set_property_override(
+                context.schema_set,
+                "color",
+                Some(Value::String(Arc::new(
+                    context.source_file_string()?.to_string(),
+                ))),
+            )?;
+            context.add_default_importable(SingleObject::new(material_record), Some(import_data));
+            Ok(())
+        }
+
+        fn import_data_storage(&self) -> ImportDataStorage {
+            ImportDataStorage::External
+        }
+    }
+
+    // Like TestExternalMaterialImporter, but the import data is copied into the project
+    #[derive(TypeUuid, Default)]
+    #[uuid = "4b8e2f71-6a3d-4c59-9f0e-2d7c1a5b8e44"]
+    struct TestCopiedMaterialImporter;
+
+    impl Importer for TestCopiedMaterialImporter {
+        fn supported_file_extensions(&self) -> &[&'static str] {
+            &["test_copied_material"]
+        }
+
+        fn scan_file(
+            &self,
+            context: ScanContext,
+        ) -> PipelineResult<()> {
+            let material_record = context
+                .schema_set
+                .find_named_type("TestMaterial")?
+                .as_record()?
+                .clone();
+            context.add_importable_with_record(ImportableName::default(), material_record)?;
+            Ok(())
+        }
+
+        fn import_file(
+            &self,
+            context: ImportContext,
+        ) -> PipelineResult<()> {
+            let material_record = context
+                .schema_set
+                .find_named_type("TestMaterial")?
+                .as_record()?;
+            let import_data_record = context
+                .schema_set
+                .find_named_type("TestMaterialImportData")?
+                .as_record()?;
+            let mut import_data = SingleObject::new(import_data_record);
+            import_data.set_property_override(
+                context.schema_set,
+                "color",
+                Some(Value::String(Arc::new(
+                    context.source_file_string()?.to_string(),
+                ))),
+            )?;
+            context.add_default_importable(SingleObject::new(material_record), Some(import_data));
+            Ok(())
+        }
+    }
+
+    fn default_importable_request(
+        project_config: &HydrateProjectConfiguration,
+        schema_set: &SchemaSet,
+        path: &Path,
+        asset_id: AssetId,
+        importer_id: ImporterId,
+        schema_name: &str,
+        import_type: ImportType,
+    ) -> ImportThreadRequestImport {
+        let requested_importable = RequestedImportable {
+            asset_id,
+            schema: schema_set
+                .find_named_type(schema_name)
+                .unwrap()
+                .as_record()
+                .unwrap()
+                .clone(),
+            asset_name: AssetName::new(schema_name.to_string()),
+            asset_location: AssetLocation::null(),
+            source_file: PathReference::new(
+                "".to_string(),
+                path.to_string_lossy().to_string(),
+                ImportableName::default(),
+            )
+            .simplify(project_config),
+            canonical_path_references: Default::default(),
+            path_references: Default::default(),
+            replace_with_default_asset: true,
+        };
+
+        let mut requested_importables = HashMap::default();
+        requested_importables.insert(ImportableName::default(), requested_importable);
+        let mut importable_assets = HashMap::default();
+        importable_assets.insert(
+            ImportableName::default(),
+            ImportableAsset {
+                id: asset_id,
+                canonical_path_references: Default::default(),
+                path_references: Default::default(),
+            },
+        );
+
+        ImportThreadRequestImport {
+            import_op: ImportOp {
+                requested_importables,
+                importer_id,
+                path: path.to_path_buf(),
+                import_type,
+            },
+            importable_assets,
+        }
+    }
+
+    fn import_default_importable(
+        project_config: &HydrateProjectConfiguration,
+        importer_registry: &ImporterRegistry,
+        schema_set: &SchemaSet,
+        path: &Path,
+        importer_id: ImporterId,
+        schema_name: &str,
+    ) -> (AssetId, ImportThreadImportedImportable) {
+        let asset_id = AssetId::from_uuid(uuid::Uuid::new_v4());
+        let request = default_importable_request(
+            project_config,
+            schema_set,
+            path,
+            asset_id,
+            importer_id,
+            schema_name,
+            ImportType::ImportAlways,
+        );
+
+        let mut importables = do_import(
+            project_config,
+            importer_registry,
+            schema_set,
+            &Default::default(),
+            &project_config.import_data_path,
+            &request,
+            &mut Vec::default(),
+        )
+        .unwrap();
+        (
+            asset_id,
+            importables.remove(&ImportableName::default()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn import_mesh_and_material_from_zip() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("TestMesh", uuid::Uuid::new_v4(), |builder| {
+                builder.add_string("material", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        linker
+            .register_record_type("TestMaterial", uuid::Uuid::new_v4(), |builder| {
+                builder.add_string("color", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let mut importer_registry = ImporterRegistryBuilder::default();
+        importer_registry.register_handler::<TestMeshImporter>();
+        importer_registry.register_handler::<TestMaterialImporter>();
+        let importer_registry = importer_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root_path).unwrap();
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+
+        // Package the mesh and material into one archive
+        let archive_path = root_path.join("art.zip");
+        {
+            let mut zip_writer = zip::ZipWriter::new(std::fs::File::create(&archive_path).unwrap());
+            let options = zip::write::FileOptions::default();
+            zip_writer
+                .start_file("meshes/cube.test_mesh", options)
+                .unwrap();
+            zip_writer
+                .write_all(b"../materials/red.test_material")
+                .unwrap();
+            zip_writer
+                .start_file("materials/red.test_material", options)
+                .unwrap();
+            zip_writer.write_all(b"red").unwrap();
+            zip_writer.finish().unwrap();
+        }
+        let archive_path =
+            canonicalize_path(&archive_path, project_config.path_canonicalization).unwrap();
+
+        // Scanning the mesh finds the material, and the relative reference resolves within the
+        // archive
+        let mesh_path = join_archive_path(&archive_path, "meshes/cube.test_mesh");
+        let mut scanned_importables = HashMap::default();
+        let mut log_events = Vec::default();
+        TestMeshImporter
+            .scan_file(ScanContext::new(
+                &mesh_path,
+                &schema_set,
+                &importer_registry,
+                &project_config,
+                &mut scanned_importables,
+                &mut log_events,
+            ))
+            .unwrap();
+        let scanned_mesh = &scanned_importables[&ImportableName::default()];
+        assert_eq!(scanned_mesh.referenced_source_file_info.len(), 1);
+        let (material_reference, material_importer_id) = scanned_mesh
+            .referenced_source_file_info
+            .iter()
+            .next()
+            .unwrap();
+        assert_eq!(
+            *material_importer_id,
+            ImporterId(uuid::Uuid::from_bytes(TestMaterialImporter::UUID))
+        );
+        let material_path = material_reference
+            .canonicalized_absolute_path(&project_config, &mesh_path)
+            .unwrap();
+        let material_path = PathBuf::from(material_path.path());
+        assert_eq!(
+            material_path,
+            join_archive_path(&archive_path, "materials/red.test_material")
+        );
+        assert_eq!(
+            canonicalize_source_file_path(&material_path, project_config.path_canonicalization)
+                .unwrap(),
+            material_path
+        );
+
+        // Importing reads the bytes out of the archive
+        let (_, mesh) = import_default_importable(
+            &project_config,
+            &importer_registry,
+            &schema_set,
+            &mesh_path,
+            ImporterId(uuid::Uuid::from_bytes(TestMeshImporter::UUID)),
+            "TestMesh",
+        );
+        let (_, material) = import_default_importable(
+            &project_config,
+            &importer_registry,
+            &schema_set,
+            &material_path,
+            ImporterId(uuid::Uuid::from_bytes(TestMaterialImporter::UUID)),
+            "TestMaterial",
+        );
+
+        std::fs::remove_dir_all(&root_path).unwrap();
+
+        assert_eq!(
+            mesh.default_asset
+                .get_property_override("material")
+                .unwrap()
+                .as_string()
+                .unwrap()
+                .as_str(),
+            "../materials/red.test_material"
+        );
+        assert_eq!(
+            material
+                .default_asset
+                .get_property_override("color")
+                .unwrap()
+                .as_string()
+                .unwrap()
+                .as_str(),
+            "red"
+        );
+        // The source file is stored relative to the project root
+        assert_eq!(
+            mesh.import_info.source_file().namespace(),
+            hydrate_data::PROJECT_ROOT_NAMESPACE
+        );
+        assert_eq!(
+            mesh.import_info.source_file().path(),
+            "art.zip!meshes/cube.test_mesh"
+        );
+        assert_eq!(
+            mesh.import_info.source_file_size(),
+            "../materials/red.test_material".len() as u64
+        );
+    }
+
+    #[test]
+    fn build_from_external_import_data() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("TestMaterial", uuid::Uuid::new_v4(), |builder| {
+                builder.add_string("color", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        linker
+            .register_record_type("TestMaterialImportData", uuid::Uuid::new_v4(), |builder| {
+                builder.add_string("color", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let mut importer_registry = ImporterRegistryBuilder::default();
+        importer_registry.register_handler::<TestExternalMaterialImporter>();
+        let importer_registry = importer_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root_path).unwrap();
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+
+        let material_path = root_path.join("red.test_external_material");
+        std::fs::write(&material_path, "red").unwrap();
+        let material_path =
+            canonicalize_path(&material_path, project_config.path_canonicalization).unwrap();
+
+        // The import data is not copied into the project
+        let (_, material) = import_default_importable(
+            &project_config,
+            &importer_registry,
+            &schema_set,
+            &material_path,
+            ImporterId(uuid::Uuid::from_bytes(TestExternalMaterialImporter::UUID)),
+            "TestMaterial",
+        );
+        assert_eq!(
+            material.import_info.import_data_storage(),
+            ImportDataStorage::External
+        );
+        assert!(!project_config.import_data_path.exists());
+
+        // Building reads the import data from the source file
+        let mut data_set = DataSet::default();
+        let asset_id = data_set.new_asset(
+            AssetName::new("material"),
+            AssetLocation::null(),
+            material.default_asset.schema(),
+        );
+        data_set
+            .set_import_info(asset_id, material.import_info)
+            .unwrap();
+        let job_executor = JobExecutor::new(
+            &schema_set,
+            &JobProcessorRegistryBuilder::default().build(),
+            &importer_registry,
+            &project_config,
+        );
+        let import_data = job_executor
+            .job_api()
+            .fetch_import_data(&data_set, asset_id)
+            .unwrap();
+        assert_eq!(
+            import_data
+                .import_data
+                .get_property_override("color")
+                .unwrap()
+                .as_string()
+                .unwrap()
+                .as_str(),
+            "red"
+        );
+
+        // A source file that changed since it was imported must be re-imported
+        std::fs::write(&material_path, "green").unwrap();
+        let error = job_executor
+            .job_api()
+            .fetch_import_data(&data_set, asset_id)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("changed since it was imported"));
+
+        std::fs::remove_file(&material_path).unwrap();
+        assert!(job_executor
+            .job_api()
+            .fetch_import_data(&data_set, asset_id)
+            .is_err());
+
+        drop(job_executor);
+        std::fs::remove_dir_all(&root_path).unwrap();
+    }
+
+    #[test]
+    fn touched_external_source_file_does_not_change_import_data_in_hash_mode() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("TestMaterial", uuid::Uuid::new_v4(), |builder| {
+                builder.add_string("color", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        linker
+            .register_record_type("TestMaterialImportData", uuid::Uuid::new_v4(), |builder| {
+                builder.add_string("color", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let mut importer_registry = ImporterRegistryBuilder::default();
+        importer_registry.register_handler::<TestExternalMaterialImporter>();
+        let importer_registry = importer_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root_path).unwrap();
+        let mut project_config = HydrateProjectConfiguration::for_test(&root_path);
+        project_config.source_file_change_detection = SourceFileChangeDetection::ContentHash;
+
+        let material_path = root_path.join("red.test_external_material");
+        std::fs::write(&material_path, "red").unwrap();
+        let material_path =
+            canonicalize_path(&material_path, project_config.path_canonicalization).unwrap();
+
+        let (_, material) = import_default_importable(
+            &project_config,
+            &importer_registry,
+            &schema_set,
+            &material_path,
+            ImporterId(uuid::Uuid::from_bytes(TestExternalMaterialImporter::UUID)),
+            "TestMaterial",
+        );
+        let mut data_set = DataSet::default();
+        let asset_id = data_set.new_asset(
+            AssetName::new("material"),
+            AssetLocation::null(),
+            material.default_asset.schema(),
+        );
+        data_set
+            .set_import_info(asset_id, material.import_info)
+            .unwrap();
+        let job_executor = JobExecutor::new(
+            &schema_set,
+            &JobProcessorRegistryBuilder::default().build(),
+            &importer_registry,
+            &project_config,
+        );
+        let metadata_hash = || {
+            job_executor
+                .job_api()
+                .fetch_import_data(&data_set, asset_id)
+                .unwrap()
+                .metadata_hash
+        };
+        let original_metadata_hash = metadata_hash();
+
+        // Only the contents are compared, so touching the file doesn't make builds that read the
+        // import data stale
+        std::fs::File::options()
+            .write(true)
+            .open(&material_path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(3600))
+            .unwrap();
+        assert_eq!(metadata_hash(), original_metadata_hash);
+
+        drop(job_executor);
+        std::fs::remove_dir_all(&root_path).unwrap();
+    }
+
+    #[test]
+    fn import_data_in_packed_mode() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("TestMaterial", uuid::Uuid::new_v4(), |builder| {
+                builder.add_string("color", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        linker
+            .register_record_type("TestMaterialImportData", uuid::Uuid::new_v4(), |builder| {
+                builder.add_string("color", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let mut importer_registry = ImporterRegistryBuilder::default();
+        importer_registry.register_handler::<TestCopiedMaterialImporter>();
+        let importer_registry = importer_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root_path).unwrap();
+        let mut project_config = HydrateProjectConfiguration::for_test(&root_path);
+        project_config.pack_import_data = true;
+
+        let mut asset_ids = Vec::default();
+        for color in ["red", "green", "blue"] {
+            let material_path = root_path.join(format!("{}.test_copied_material", color));
+            std::fs::write(&material_path, color).unwrap();
+            let material_path =
+                canonicalize_path(&material_path, project_config.path_canonicalization).unwrap();
+
+            let (asset_id, material) = import_default_importable(
+                &project_config,
+                &importer_registry,
+                &schema_set,
+                &material_path,
+                ImporterId(uuid::Uuid::from_bytes(TestCopiedMaterialImporter::UUID)),
+                "TestMaterial",
+            );
+            assert_eq!(
+                material.import_info.import_data_storage(),
+                ImportDataStorage::Copied
+            );
+            asset_ids.push(asset_id);
+        }
+
+        // Import data was not written as a file per asset
+        for asset_id in &asset_ids {
+            assert!(
+                !uuid_to_path(&project_config.import_data_path, asset_id.as_uuid(), "if").exists()
+            );
+        }
+
+        // Any one asset's import data can be read back by its ID
+        let import_data =
+            crate::import::load_import_data(&project_config, &schema_set, asset_ids[1]).unwrap();
+        assert_eq!(
+            import_data
+                .import_data
+                .get_property_override("color")
+                .unwrap()
+                .as_string()
+                .unwrap()
+                .as_str(),
+            "green"
+        );
+
+        std::fs::remove_dir_all(&root_path).unwrap();
+    }
+
+    #[test]
+    fn touched_source_file_is_not_reimported_in_hash_mode() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("TestMaterial", uuid::Uuid::new_v4(), |builder| {
+                builder.add_string("color", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        linker
+            .register_record_type("TestMaterialImportData", uuid::Uuid::new_v4(), |builder| {
+                builder.add_string("color", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let mut importer_registry = ImporterRegistryBuilder::default();
+        importer_registry.register_handler::<TestCopiedMaterialImporter>();
+        let importer_registry = importer_registry.build();
+        let importer_id = ImporterId(uuid::Uuid::from_bytes(TestCopiedMaterialImporter::UUID));
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root_path).unwrap();
+        let mut project_config = HydrateProjectConfiguration::for_test(&root_path);
+        project_config.source_file_change_detection =
+            SourceFileChangeDetection::HashIfTimestampChanged;
+
+        let material_path = root_path.join("red.test_copied_material");
+        std::fs::write(&material_path, "red").unwrap();
+        let material_path =
+            canonicalize_path(&material_path, project_config.path_canonicalization).unwrap();
+
+        let (asset_id, material) = import_default_importable(
+            &project_config,
+            &importer_registry,
+            &schema_set,
+            &material_path,
+            importer_id,
+            "TestMaterial",
+        );
+        assert_ne!(material.import_info.source_file_contents_hash(), 0);
+        let mut existing_asset_import_state = HashMap::default();
+        existing_asset_import_state.insert(
+            asset_id,
+            ImportDataMetadata::from_import_info(&material.import_info),
+        );
+
+        let import_if_stale = || {
+            let request = default_importable_request(
+                &project_config,
+                &schema_set,
+                &material_path,
+                asset_id,
+                importer_id,
+                "TestMaterial",
+                ImportType::ImportIfImportDataStale,
+            );
+            do_import(
+                &project_config,
+                &importer_registry,
+                &schema_set,
+                &existing_asset_import_state,
+                &project_config.import_data_path,
+                &request,
+                &mut Vec::default(),
+            )
+            .unwrap()
+        };
+        let set_modified_time = |seconds_from_now: u64| {
+            std::fs::File::options()
+                .write(true)
+                .open(&material_path)
+                .unwrap()
+                .set_modified(
+                    std::time::SystemTime::now() + std::time::Duration::from_secs(seconds_from_now),
+                )
+                .unwrap();
+        };
+
+        // Nothing changed
+        assert!(import_if_stale().is_empty());
+
+        // The modified time changed but the contents didn't, so it's not re-imported
+        set_modified_time(3600);
+        assert!(import_if_stale().is_empty());
+
+        // Changing the contents without changing the size is detected
+        std::fs::write(&material_path, "tan").unwrap();
+        set_modified_time(7200);
+        let importables = import_if_stale();
+        assert_ne!(
+            importables[&ImportableName::default()]
+                .import_info
+                .source_file_contents_hash(),
+            material.import_info.source_file_contents_hash()
+        );
+
+        std::fs::remove_dir_all(&root_path).unwrap();
+    }
+
+    // Records how many imports are running at once
+    static LIMITED_IMPORTS_RUNNING: AtomicUsize = AtomicUsize::new(0);
+    static LIMITED_IMPORTS_MAX_RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(TypeUuid, Default)]
+    #[uuid = "3f0b9c5a-2d8e-4c1f-9e6b-7a4d1c2e8f90"]
+    struct LimitedImporter;
+
+    impl Importer for LimitedImporter {
+        fn supported_file_extensions(&self) -> &[&'static str] {
+            &["limited_test"]
+        }
+
+        fn scan_file(
+            &self,
+            _context: ScanContext,
+        ) -> PipelineResult<()> {
+            Ok(())
+        }
+
+        fn import_file(
+            &self,
+            _context: ImportContext,
+        ) -> PipelineResult<()> {
+            let running = LIMITED_IMPORTS_RUNNING.fetch_add(1, Ordering::SeqCst) + 1;
+            LIMITED_IMPORTS_MAX_RUNNING.fetch_max(running, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            LIMITED_IMPORTS_RUNNING.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn max_concurrent(&self) -> Option<usize> {
+            Some(1)
+        }
+    }
+
+    #[test]
+    fn importer_concurrency_limit_is_respected() {
+        let mut importer_registry = ImporterRegistryBuilder::default();
+        importer_registry.register_handler::<LimitedImporter>();
+        importer_registry.register_handler::<WarningImporter>();
+        let importer_registry = importer_registry.build();
+        let schema_set = SchemaSetBuilder::default().build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root_path).unwrap();
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+
+        let (result_tx, result_rx) = crossbeam_channel::unbounded();
+        let thread_pool = ImportWorkerThreadPool::new(
+            &project_config,
+            &importer_registry,
+            &schema_set,
+            &Default::default(),
+            &project_config.import_data_path,
+            4,
+            result_tx,
+        );
+
+        // Interleave requests for the limited importer with requests for an unlimited one
+        let mut request_count = 0;
+        for i in 0..8 {
+            for (importer_id, extension) in [
+                (LimitedImporter::UUID, "limited_test"),
+                (WarningImporter::UUID, "warning_test"),
+            ] {
+                let source_file_path = root_path.join(format!("{}.{}", i, extension));
+                std::fs::write(&source_file_path, "").unwrap();
+                thread_pool.add_request(ImportThreadRequest::RequestImport(
+                    ImportThreadRequestImport {
+                        import_op: ImportOp {
+                            requested_importables: Default::default(),
+                            importer_id: ImporterId(uuid::Uuid::from_bytes(importer_id)),
+                            path: source_file_path,
+                            import_type: ImportType::ImportAlways,
+                        },
+                        importable_assets: Default::default(),
+                    },
+                ));
+                request_count += 1;
+            }
+        }
+
+        while !thread_pool.is_idle() {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        thread_pool.finish();
+
+        // Every request completed, and the limited importer never ran twice at the same time
+        let outcomes: Vec<_> = result_rx.try_iter().collect();
+        assert_eq!(outcomes.len(), request_count);
+        for outcome in outcomes {
+            let ImportThreadOutcome::Complete(outcome) = outcome;
+            assert!(outcome.result.is_ok());
+        }
+        assert_eq!(LIMITED_IMPORTS_MAX_RUNNING.load(Ordering::SeqCst), 1);
+
+        std::fs::remove_dir_all(&root_path).unwrap();
+    }
+
+    // A buggy importer that stores a number in the material's string color property
+    #[derive(TypeUuid, Default)]
+    #[uuid = "9c3d6e21-4f8a-4b17-b5e2-8a0f1c7d3e55"]
+    struct TestInvalidMaterialImporter;
+
+    impl Importer for TestInvalidMaterialImporter {
+        fn supported_file_extensions(&self) -> &[&'static str] {
+            &["test_invalid_material"]
+        }
+
+        fn scan_file(
+            &self,
+            _context: ScanContext,
+        ) -> PipelineResult<()> {
+            Ok(())
+        }
+
+        fn import_file(
+            &self,
+            context: ImportContext,
+        ) -> PipelineResult<()> {
+            let material_record = context
+                .schema_set
+                .find_named_type("TestMaterial")?
+                .as_record()?;
+            let mut properties = HashMap::default();
+            properties.insert("color".to_string(), Value::I32(5));
+            let material = SingleObject::restore(
+                context.schema_set,
+                material_record.fingerprint(),
+                properties,
+                Default::default(),
+                Default::default(),
+            );
+            context.add_default_importable(material, None);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn importer_output_not_matching_schema_is_rejected() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("TestMaterial", uuid::Uuid::new_v4(), |builder| {
+                builder.add_string("color", uuid::Uuid::new_v4());
+            })
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let mut importer_registry = ImporterRegistryBuilder::default();
+        importer_registry.register_handler::<TestInvalidMaterialImporter>();
+        let importer_registry = importer_registry.build();
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root_path).unwrap();
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+        let source_file_path = root_path.join("red.test_invalid_material");
+        std::fs::write(&source_file_path, "red").unwrap();
+
+        // The object itself reports which check failed
+        let material_record = schema_set
+            .find_named_type("TestMaterial")
+            .unwrap()
+            .as_record()
+            .unwrap();
+        let mut properties = HashMap::default();
+        properties.insert("color".to_string(), Value::I32(5));
+        let material = SingleObject::restore(
+            &schema_set,
+            material_record.fingerprint(),
+            properties,
+            Default::default(),
+            Default::default(),
+        );
+        assert!(matches!(
+            material.validate(&schema_set).unwrap_err().error,
+            DataSetError::ValueDoesNotMatchSchema
+        ));
+
+        // The import fails, naming the importable and source file, and nothing is persisted
+        let asset_id = AssetId::from_uuid(uuid::Uuid::new_v4());
+        let request = default_importable_request(
+            &project_config,
+            &schema_set,
+            &source_file_path,
+            asset_id,
+            ImporterId(uuid::Uuid::from_bytes(TestInvalidMaterialImporter::UUID)),
+            "TestMaterial",
+            ImportType::ImportAlways,
+        );
+        let result = do_import(
+            &project_config,
+            &importer_registry,
+            &schema_set,
+            &Default::default(),
+            &project_config.import_data_path,
+            &request,
+            &mut Vec::default(),
+        );
+        let import_data_path =
+            uuid_to_path(&project_config.import_data_path, asset_id.as_uuid(), "if");
+        let import_data_exists = import_data_path.exists();
+
+        std::fs::remove_dir_all(&root_path).unwrap();
+
+        match result.map_err(|e| e.error) {
+            Err(PipelineError::StringError(message)) => {
+                assert!(message.contains("default asset"));
+                assert!(message.contains("red.test_invalid_material"));
+                assert!(message.contains("ValueDoesNotMatchSchema"));
+            }
+            _ => panic!("Expected the import to fail validation"),
+        }
+        assert!(!import_data_exists);
+    }
+}
@@ -2,8 +2,8 @@ use crate::{
     HydrateProjectConfiguration, ImportLogEvent, ImporterRegistry, LogEventLevel, PipelineResult,
 };
 use hydrate_data::{
-    AssetId, CanonicalPathReference, HashMap, ImportableName, ImporterId, PathReference,
-    PathReferenceHash, Record, SchemaRecord, SchemaSet, SingleObject,
+    AssetId, CanonicalPathReference, HashMap, ImportDataStorage, ImportableName, ImporterId,
+    PathReference, PathReferenceHash, Record, SchemaRecord, SchemaSet, SingleObject,
 };
 use std::cell::RefCell;
 use std::panic::RefUnwindSafe;
@@ -35,6 +35,33 @@ pub struct ImportedImportable {
     pub import_data: Option<SingleObject>,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ImportWarningSeverity {
+    Info,
+    Warning,
+}
+
+impl ImportWarningSeverity {
+    pub fn log_event_level(&self) -> LogEventLevel {
+        match self {
+            ImportWarningSeverity::Info => LogEventLevel::Info,
+            ImportWarningSeverity::Warning => LogEventLevel::Warning,
+        }
+    }
+}
+
+// A non-fatal problem encountered by an importer. The import still completes, but the user should
+// be told about it. (i.e. "mesh has no tangents, generating defaults")
+#[derive(Debug, Clone)]
+pub struct ImportWarning {
+    pub severity: ImportWarningSeverity,
+    // The importable the warning applies to, or None if it applies to the whole file
+    pub importable_name: Option<ImportableName>,
+    // The property path the warning applies to, if any
+    pub property_path: Option<String>,
+    pub message: String,
+}
+
 pub trait ImporterStatic: TypeUuid {
     fn importer_id() -> ImporterId {
         ImporterId(Uuid::from_bytes(Self::UUID))
@@ -82,6 +109,29 @@ impl<'a> ScanContext<'a> {
         }
     }
 
+    // The path may point into an archive, so read the file with this rather than opening the path
+    pub fn read_source_file(&self) -> PipelineResult<Vec<u8>> {
+        super::source_file::read_source_file(self.path)
+    }
+
+    pub fn read_source_file_to_string(&self) -> PipelineResult<String> {
+        Ok(String::from_utf8(self.read_source_file()?)
+            .map_err(|e| format!("Source file {:?} is not valid UTF-8: {}", self.path, e))?)
+    }
+
+    pub fn info<T: Into<String>>(
+        &self,
+        message: T,
+    ) {
+        let mut log_events = self.log_events.borrow_mut();
+        log_events.push(ImportLogEvent {
+            path: self.path.to_path_buf(),
+            asset_id: None,
+            level: LogEventLevel::Info,
+            message: message.into(),
+        });
+    }
+
     pub fn warn<T: Into<String>>(
         &self,
         message: T,
@@ -265,35 +315,83 @@ impl<'a> ScanContextImportable<'a> {
     }
 }
 
+// The source file being imported. The path may point into an archive, so the contents are read
+// ahead of time rather than by the importer
+#[derive(Copy, Clone)]
+pub struct ImportSourceFile<'a> {
+    pub path: &'a Path,
+    pub bytes: &'a [u8],
+}
+
 #[derive(Clone)]
 pub struct ImportContext<'a> {
     pub path: &'a Path,
+    source_file_bytes: &'a [u8],
     importable_assets: &'a HashMap<ImportableName, ImportableAsset>,
     pub schema_set: &'a SchemaSet,
     project_config: &'a HydrateProjectConfiguration,
     imported_importables: Rc<RefCell<&'a mut HashMap<ImportableName, ImportedImportable>>>,
     pub(crate) log_events: Rc<RefCell<&'a mut Vec<ImportLogEvent>>>,
+    pub(crate) warnings: Rc<RefCell<&'a mut Vec<ImportWarning>>>,
 }
 
 impl<'a> ImportContext<'a> {
     pub fn new(
-        path: &'a Path,
+        source_file: ImportSourceFile<'a>,
         importable_assets: &'a HashMap<ImportableName, ImportableAsset>,
         schema_set: &'a SchemaSet,
         project_config: &'a HydrateProjectConfiguration,
         imported_importables: &'a mut HashMap<ImportableName, ImportedImportable>,
         log_events: &'a mut Vec<ImportLogEvent>,
+        warnings: &'a mut Vec<ImportWarning>,
     ) -> ImportContext<'a> {
         ImportContext {
-            path,
+            path: source_file.path,
+            source_file_bytes: source_file.bytes,
             importable_assets,
             schema_set,
             project_config,
             imported_importables: Rc::new(RefCell::new(imported_importables)),
             log_events: Rc::new(RefCell::new(log_events)),
+            warnings: Rc::new(RefCell::new(warnings)),
         }
     }
 
+    // Contents of the source file. The path may point into an archive, so importers should use this
+    // rather than opening the path
+    pub fn source_file_bytes(&self) -> &'a [u8] {
+        self.source_file_bytes
+    }
+
+    pub fn source_file_string(&self) -> PipelineResult<&'a str> {
+        Ok(std::str::from_utf8(self.source_file_bytes)
+            .map_err(|e| format!("Source file {:?} is not valid UTF-8: {}", self.path, e))?)
+    }
+
+    // Report a non-fatal problem. These are returned with the import results and shown to the user
+    // alongside the imported asset.
+    pub fn add_warning(
+        &self,
+        warning: ImportWarning,
+    ) {
+        self.warnings.borrow_mut().push(warning);
+    }
+
+    pub fn add_importable_warning<T: Into<String>>(
+        &self,
+        name: ImportableName,
+        severity: ImportWarningSeverity,
+        property_path: Option<&str>,
+        message: T,
+    ) {
+        self.add_warning(ImportWarning {
+            severity,
+            importable_name: Some(name),
+            property_path: property_path.map(|x| x.to_string()),
+            message: message.into(),
+        });
+    }
+
     pub fn warn<T: Into<String>>(
         &self,
         message: T,
@@ -393,6 +491,11 @@ pub trait Importer: TypeUuidDynamic + Sync + Send + RefUnwindSafe + 'static {
         ImporterId(Uuid::from_bytes(self.uuid()))
     }
 
+    // Used to identify the importer in reports like the build profile
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
     // Used to allow the importer registry to return all importers compatible with a given filename extension
     fn supported_file_extensions(&self) -> &[&'static str];
 
@@ -408,4 +511,18 @@ pub trait Importer: TypeUuidDynamic + Sync + Send + RefUnwindSafe + 'static {
         &self,
         context: ImportContext,
     ) -> PipelineResult<()>;
+
+    // Whether import data is copied into the project, or produced by importing the source file in
+    // place whenever it's needed. Not copying avoids duplicating very large source files, but the
+    // source file must stay where it is and not change.
+    fn import_data_storage(&self) -> ImportDataStorage {
+        ImportDataStorage::Copied
+    }
+
+    // The most files this importer may import at the same time, or None for no limit. Memory-hungry
+    // importers can set this to avoid running out of memory. Workers keep running other importers
+    // while requests for this one wait.
+    fn max_concurrent(&self) -> Option<usize> {
+        None
+    }
 }
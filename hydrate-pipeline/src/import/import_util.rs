@@ -1,9 +1,11 @@
+use crate::import::source_file;
 use crate::import::{ImportType, Importer, ScanContext, ScannedImportable};
 use crate::ImporterRegistry;
 use crate::{DynEditContext, HydrateProjectConfiguration, ImportLogData, PipelineResult};
+use hydrate_base::canonicalize_path;
 use hydrate_data::{
-    AssetId, AssetLocation, AssetName, CanonicalPathReference, HashMap, ImporterId,
-    PathReferenceHash,
+    canonicalize_source_file_path, AssetId, AssetLocation, AssetName, CanonicalPathReference,
+    HashMap, HashSet, ImporterId, PathReferenceHash,
 };
 use hydrate_data::{ImportableName, PathReference};
 use hydrate_schema::SchemaRecord;
@@ -35,6 +37,124 @@ impl ImportJobToQueue {
     }
 }
 
+// The files that will be imported when the user picks a set of files and directories, and the files
+// that were skipped because no importer handles them
+#[derive(Debug, Default)]
+pub struct ImportFileScan {
+    pub files_to_import: HashSet<PathBuf>,
+    // Keyed by lowercase extension without the leading dot, or "" for files with no extension
+    pub skipped_files: HashMap<String, Vec<PathBuf>>,
+}
+
+impl ImportFileScan {
+    pub fn skipped_file_count(&self) -> usize {
+        self.skipped_files.values().map(|x| x.len()).sum()
+    }
+
+    // Skipped extensions, sorted and with a leading dot, i.e. [".psd", ".txt"]
+    pub fn skipped_extensions(&self) -> Vec<String> {
+        let mut extensions: Vec<String> = self
+            .skipped_files
+            .keys()
+            .map(|extension| {
+                if extension.is_empty() {
+                    "no extension".to_string()
+                } else {
+                    format!(".{}", extension)
+                }
+            })
+            .collect();
+        extensions.sort();
+        extensions
+    }
+
+    // i.e. "12 files skipped (.psd, .txt)", or None if nothing was skipped
+    pub fn skipped_files_summary(&self) -> Option<String> {
+        let count = self.skipped_file_count();
+        if count == 0 {
+            return None;
+        }
+
+        Some(format!(
+            "{} {} skipped ({})",
+            count,
+            if count == 1 { "file" } else { "files" },
+            self.skipped_extensions().join(", ")
+        ))
+    }
+
+    fn add_file(
+        &mut self,
+        file: PathBuf,
+        importer_registry: &ImporterRegistry,
+    ) {
+        let extension = file
+            .extension()
+            .map(|x| x.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if extension.is_empty()
+            || importer_registry
+                .importers_for_file_extension(&extension)
+                .is_empty()
+        {
+            self.skipped_files.entry(extension).or_default().push(file);
+        } else {
+            self.files_to_import.insert(file);
+        }
+    }
+}
+
+// Expands directories and archives into the files within them and sorts out which of those can be
+// imported
+pub fn scan_files_to_import(
+    project_config: &HydrateProjectConfiguration,
+    files_to_import: &[PathBuf],
+    importer_registry: &ImporterRegistry,
+) -> ImportFileScan {
+    let mut found_files = Vec::default();
+    for file in files_to_import {
+        // Recursively look for files
+        if file.is_dir() {
+            let walker = globwalk::GlobWalkerBuilder::from_patterns(file, &["**/*"])
+                .file_type(globwalk::FileType::FILE)
+                .build()
+                .unwrap();
+
+            for file in walker.flatten() {
+                let file =
+                    canonicalize_path(file.path(), project_config.path_canonicalization).unwrap();
+                found_files.push(file);
+            }
+        } else {
+            found_files.push(file.to_path_buf());
+        }
+    }
+
+    let mut scan = ImportFileScan::default();
+    for file in found_files {
+        // Import the contents of archives rather than the archives themselves
+        if source_file::is_archive(&file) {
+            match source_file::archive_entries(&file) {
+                Ok(entries) => {
+                    for entry in entries {
+                        scan.add_file(entry, importer_registry);
+                    }
+                }
+                Err(e) => log::error!("Could not read archive {:?}: {}", file, e),
+            }
+        } else {
+            scan.add_file(file, importer_registry);
+        }
+    }
+
+    for skipped_files in scan.skipped_files.values_mut() {
+        skipped_files.sort();
+        skipped_files.dedup();
+    }
+
+    scan
+}
+
 #[derive(Debug)]
 pub struct ImportJobSourceFile {
     pub source_file_path: PathBuf,
@@ -50,9 +170,9 @@ pub fn create_asset_name(
     if let Some(file_name) = source_file_path.file_name() {
         let file_name = file_name.to_string_lossy();
         if let Some(importable_name) = &scanned_importable.name.name() {
-            AssetName::new(format!("{}.{}", file_name, importable_name))
+            AssetName::sanitize(&format!("{}.{}", file_name, importable_name))
         } else {
-            AssetName::new(file_name.to_string())
+            AssetName::sanitize(&file_name)
         }
     } else {
         AssetName::empty()
@@ -74,8 +194,53 @@ pub fn recursively_gather_import_operations_and_create_assets(
     // created. Pre-existing but referenced assets won't be in this list
     import_job_to_queue: &mut ImportJobToQueue,
 ) -> PipelineResult<HashMap<ImportableName, AssetId>> {
+    let mut gather_state = GatherImportOperationsState {
+        project_config,
+        editor_context,
+        importer_registry,
+        selected_import_location,
+        asset_id_assignments,
+        in_progress_source_files: Vec::default(),
+        max_depth: project_config.max_import_reference_depth,
+    };
+    gather_import_operations_and_create_assets(
+        &mut gather_state,
+        source_file_path,
+        importer,
+        import_job_to_queue,
+    )
+}
+
+// Carried through the recursion when gathering a source file and the files it references. The
+// inputs are the same for every file, the in-progress source files form the current call stack.
+struct GatherImportOperationsState<'a> {
+    project_config: &'a HydrateProjectConfiguration,
+    editor_context: &'a dyn DynEditContext,
+    importer_registry: &'a ImporterRegistry,
+    selected_import_location: &'a AssetLocation,
+    asset_id_assignments: Option<&'a HashMap<ImportableName, AssetId>>,
+    // Source files whose references are being resolved further up the call stack, and the assets
+    // that will be created for them
+    in_progress_source_files: Vec<(PathBuf, HashMap<ImportableName, AssetId>)>,
+    // Fail rather than nest more source files than this
+    max_depth: usize,
+}
+
+fn gather_import_operations_and_create_assets(
+    state: &mut GatherImportOperationsState,
+    source_file_path: &Path,
+    importer: &Arc<dyn Importer>,
+    import_job_to_queue: &mut ImportJobToQueue,
+) -> PipelineResult<HashMap<ImportableName, AssetId>> {
+    let project_config = state.project_config;
+    let editor_context = state.editor_context;
+    let importer_registry = state.importer_registry;
+    let asset_id_assignments = state.asset_id_assignments;
+
     assert!(source_file_path.is_absolute());
-    let source_file_path = dunce::canonicalize(source_file_path)?;
+    // The path may point into an archive, only the archive itself needs to exist
+    let source_file_path =
+        canonicalize_source_file_path(source_file_path, project_config.path_canonicalization)?;
 
     //
     // If we request to import a file we already processed, just return the name/id pairs again
@@ -90,6 +255,25 @@ pub fn recursively_gather_import_operations_and_create_assets(
         }
     }
 
+    //
+    // If the file references itself, directly or through other files, reuse the assets that will
+    // be created for it rather than recursing forever
+    //
+    if let Some((_, in_progress_asset_ids)) = state
+        .in_progress_source_files
+        .iter()
+        .find(|(path, _)| *path == source_file_path)
+    {
+        return Ok(in_progress_asset_ids.clone());
+    }
+
+    if state.in_progress_source_files.len() >= state.max_depth {
+        Err(format!(
+            "Importing {:?} requires importing a chain of more than {} source files that reference each other (see max_import_reference_depth)",
+            source_file_path, state.max_depth
+        ))?;
+    }
+
     log::info!(
         "recursively_gather_import_operations_and_create_assets {:?}",
         source_file_path
@@ -114,7 +298,32 @@ pub fn recursively_gather_import_operations_and_create_assets(
         &mut import_job_to_queue.log_data.log_events,
     ))?;
 
+    // We create a random asset ID now so that other imported files can reference this asset later,
+    // including files referenced by this one that reference it back
+    for (scanned_importable_name, scanned_importable) in &scanned_importables {
+        let asset_id = if let Some(asset_id_assignments) = asset_id_assignments {
+            let Some(asset_id) = asset_id_assignments.get(scanned_importable_name) else {
+                continue;
+            };
+
+            *asset_id
+        } else {
+            AssetId::from_uuid(Uuid::new_v4())
+        };
+
+        let old = imported_asset_ids.insert(scanned_importable.name.clone(), asset_id);
+        assert!(old.is_none());
+    }
+
+    state
+        .in_progress_source_files
+        .push((source_file_path.to_path_buf(), imported_asset_ids.clone()));
+
     for (scanned_importable_name, scanned_importable) in &scanned_importables {
+        let Some(&asset_id) = imported_asset_ids.get(&scanned_importable.name) else {
+            continue;
+        };
+
         log::info!(
             "iterating scanned importable {:?} {:?}",
             source_file_path,
@@ -151,13 +360,9 @@ pub fn recursively_gather_import_operations_and_create_assets(
 
             // Have we imported it previously?
             if found.is_none() {
-                for (asset_id, _) in editor_context.data_set().assets() {
-                    if let Some(import_info) = editor_context.data_set().import_info(*asset_id) {
-                        if *import_info.source_file() == referenced_file_canonical {
-                            found = Some(*asset_id);
-                        }
-                    }
-                }
+                found = editor_context
+                    .data_set()
+                    .asset_by_source_file(&referenced_file_canonical);
             }
 
             if asset_id_assignments.is_some() && found.is_none() {
@@ -167,14 +372,10 @@ pub fn recursively_gather_import_operations_and_create_assets(
                 // If we didn't find it, try to import it
                 if found.is_none() {
                     let importer = importer_registry.importer(*importer_id).unwrap();
-                    found = recursively_gather_import_operations_and_create_assets(
-                        project_config,
+                    found = gather_import_operations_and_create_assets(
+                        state,
                         Path::new(referenced_file_absolute.path()),
                         importer,
-                        editor_context,
-                        importer_registry,
-                        selected_import_location,
-                        asset_id_assignments,
                         import_job_to_queue,
                     )?
                     .get(referenced_source_file.importable_name())
@@ -189,17 +390,6 @@ pub fn recursively_gather_import_operations_and_create_assets(
 
         // At this point all referenced files have either been found or scanned
 
-        // We create a random asset ID now so that other imported files can reference this asset later
-        let asset_id = if let Some(asset_id_assignments) = asset_id_assignments {
-            let Some(asset_id) = asset_id_assignments.get(scanned_importable_name) else {
-                continue;
-            };
-
-            *asset_id
-        } else {
-            AssetId::from_uuid(Uuid::new_v4())
-        };
-
         let source_file = PathReference::new(
             "".to_string(),
             source_file_path.to_string_lossy().to_string(),
@@ -213,7 +403,7 @@ pub fn recursively_gather_import_operations_and_create_assets(
             asset_id,
             schema: scanned_importable.asset_type.clone(),
             asset_name: object_name,
-            asset_location: selected_import_location.clone(),
+            asset_location: state.selected_import_location.clone(),
             //importer_id: importer.importer_id(),
             source_file,
             canonical_path_references,
@@ -223,11 +413,10 @@ pub fn recursively_gather_import_operations_and_create_assets(
         };
 
         requested_importables.insert(scanned_importable.name.clone(), requested_importable);
-
-        let old = imported_asset_ids.insert(scanned_importable.name.clone(), asset_id);
-        assert!(old.is_none());
     }
 
+    state.in_progress_source_files.pop();
+
     //asset_engine.queue_import_operation(asset_ids, importer.importer_id(), file.to_path_buf());
     //(asset_ids, importer.importer_id(), file.to_path_buf())
     import_job_to_queue
@@ -241,3 +430,213 @@ pub fn recursively_gather_import_operations_and_create_assets(
 
     Ok(imported_asset_ids)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ImportContext, ImporterRegistryBuilder};
+    use hydrate_data::{DataSet, SchemaLinker, SchemaSet, SchemaSetBuilder};
+    use type_uuid::TypeUuid;
+
+    #[derive(TypeUuid, Default)]
+    #[uuid = "0f2ab4d4-6a0e-4d36-9a53-2b1f8c3e7a11"]
+    struct PngImporter;
+
+    impl Importer for PngImporter {
+        fn supported_file_extensions(&self) -> &[&'static str] {
+            &["png"]
+        }
+
+        fn scan_file(
+            &self,
+            _context: ScanContext,
+        ) -> PipelineResult<()> {
+            Ok(())
+        }
+
+        fn import_file(
+            &self,
+            _context: ImportContext,
+        ) -> PipelineResult<()> {
+            Ok(())
+        }
+    }
+
+    fn file_names(files: impl IntoIterator<Item = PathBuf>) -> Vec<String> {
+        let mut file_names: Vec<String> = files
+            .into_iter()
+            .map(|x| x.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        file_names.sort();
+        file_names
+    }
+
+    #[test]
+    fn scanning_a_directory_reports_skipped_files() {
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+        let directory = root_path.join("textures");
+        std::fs::create_dir_all(directory.join("nested")).unwrap();
+        for file_name in [
+            "albedo.png",
+            "nested/NORMAL.PNG",
+            "albedo.psd",
+            "nested/normal.psd",
+            "readme.txt",
+            "LICENSE",
+        ] {
+            std::fs::write(directory.join(file_name), "").unwrap();
+        }
+
+        let mut builder = ImporterRegistryBuilder::default();
+        builder.register_handler::<PngImporter>();
+        let importer_registry = builder.build();
+
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+        let scan = scan_files_to_import(&project_config, &[directory], &importer_registry);
+
+        assert_eq!(
+            file_names(scan.files_to_import.iter().cloned()),
+            vec!["NORMAL.PNG", "albedo.png"]
+        );
+        assert_eq!(scan.skipped_file_count(), 4);
+        assert_eq!(
+            file_names(scan.skipped_files["psd"].iter().cloned()),
+            vec!["albedo.psd", "normal.psd"]
+        );
+        assert_eq!(
+            file_names(scan.skipped_files["txt"].iter().cloned()),
+            vec!["readme.txt"]
+        );
+        assert_eq!(
+            file_names(scan.skipped_files[""].iter().cloned()),
+            vec!["LICENSE"]
+        );
+        assert_eq!(
+            scan.skipped_files_summary().unwrap(),
+            "4 files skipped (.psd, .txt, no extension)"
+        );
+
+        std::fs::remove_dir_all(&root_path).unwrap();
+    }
+
+    struct TestEditContext {
+        data_set: DataSet,
+        schema_set: SchemaSet,
+    }
+
+    impl DynEditContext for TestEditContext {
+        fn data_set(&self) -> &DataSet {
+            &self.data_set
+        }
+
+        fn schema_set(&self) -> &SchemaSet {
+            &self.schema_set
+        }
+    }
+
+    // The file contains the relative path of another file that it references
+    #[derive(TypeUuid, Default)]
+    #[uuid = "6b1e9f3a-2c47-4d8e-a0b5-3f7c9d2e1a66"]
+    struct ReferencingImporter;
+
+    impl Importer for ReferencingImporter {
+        fn supported_file_extensions(&self) -> &[&'static str] {
+            &["referencing_test"]
+        }
+
+        fn scan_file(
+            &self,
+            context: ScanContext,
+        ) -> PipelineResult<()> {
+            let referenced_path = context.read_source_file_to_string()?;
+            let record = context
+                .schema_set
+                .find_named_type("TestReferencing")?
+                .as_record()?
+                .clone();
+            context
+                .add_importable_with_record(ImportableName::default(), record)?
+                .add_path_reference_with_importer::<ReferencingImporter, _>(referenced_path)?;
+            Ok(())
+        }
+
+        fn import_file(
+            &self,
+            _context: ImportContext,
+        ) -> PipelineResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn source_files_referencing_each_other_are_imported_once() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("TestReferencing", Uuid::new_v4(), |builder| {
+                builder.add_string("name", Uuid::new_v4());
+            })
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let edit_context = TestEditContext {
+            data_set: DataSet::default(),
+            schema_set: schema_set.build(),
+        };
+
+        let mut builder = ImporterRegistryBuilder::default();
+        builder.register_handler::<ReferencingImporter>();
+        let importer_registry = builder.build();
+
+        // A mesh references its material, which references the mesh back
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root_path).unwrap();
+        let mut project_config = HydrateProjectConfiguration::for_test(&root_path);
+        let mesh_path = root_path.join("mesh.referencing_test");
+        let material_path = root_path.join("material.referencing_test");
+        std::fs::write(&mesh_path, "material.referencing_test").unwrap();
+        std::fs::write(&material_path, "mesh.referencing_test").unwrap();
+        let mesh_path =
+            canonicalize_path(&mesh_path, project_config.path_canonicalization).unwrap();
+
+        let importer = importer_registry
+            .importer(ImporterId(Uuid::from_bytes(ReferencingImporter::UUID)))
+            .unwrap();
+        let gather = |project_config: &HydrateProjectConfiguration| {
+            let mut import_job_to_queue = ImportJobToQueue::default();
+            recursively_gather_import_operations_and_create_assets(
+                project_config,
+                &mesh_path,
+                importer,
+                &edit_context,
+                &importer_registry,
+                &AssetLocation::null(),
+                None,
+                &mut import_job_to_queue,
+            )
+            .map(|_| import_job_to_queue)
+        };
+
+        // Each file is imported once, and each asset references the other
+        let import_job_to_queue = gather(&project_config).unwrap();
+        let source_files = &import_job_to_queue.import_job_source_files;
+        assert_eq!(source_files.len(), 2);
+        let importable =
+            |index: usize| &source_files[index].requested_importables[&ImportableName::default()];
+        let referenced_asset = |index: usize| {
+            let canonical_path_references = &importable(index).canonical_path_references;
+            assert_eq!(canonical_path_references.len(), 1);
+            *canonical_path_references.values().next().unwrap()
+        };
+        assert_eq!(referenced_asset(0), importable(1).asset_id);
+        assert_eq!(referenced_asset(1), importable(0).asset_id);
+        assert_ne!(importable(0).asset_id, importable(1).asset_id);
+
+        // A chain longer than the max depth fails cleanly
+        project_config.max_import_reference_depth = 1;
+        let result = gather(&project_config);
+
+        std::fs::remove_dir_all(&root_path).unwrap();
+
+        assert!(result.is_err());
+    }
+}
@@ -1,10 +1,16 @@
-use hydrate_data::{HashMap, ImporterId};
+use hydrate_data::{HashMap, HashSet, ImporterId};
 use std::sync::Arc;
 use type_uuid::TypeUuid;
 use uuid::Uuid;
 
 use super::import_types::*;
 
+// Extensions are stored and looked up lowercase without a leading dot, so "PNG", ".png" and "png"
+// are all treated the same
+fn normalize_file_extension(extension: &str) -> String {
+    extension.trim_start_matches('.').to_lowercase()
+}
+
 // Keeps track of all known importers
 pub struct ImporterRegistryInner {
     registered_importers: HashMap<ImporterId, Arc<dyn Importer>>,
@@ -24,11 +30,21 @@ impl ImporterRegistry {
         const EMPTY_LIST: &'static [ImporterId] = &[];
         self.inner
             .file_extension_associations
-            .get(extension)
+            .get(&normalize_file_extension(extension))
             .map(|x| x.as_slice())
             .unwrap_or(EMPTY_LIST)
     }
 
+    // All file extensions that at least one registered importer can handle (normalized to lowercase
+    // without a leading dot)
+    pub fn supported_extensions(&self) -> HashSet<&str> {
+        self.inner
+            .file_extension_associations
+            .keys()
+            .map(|x| x.as_str())
+            .collect()
+    }
+
     pub fn importer(
         &self,
         importer_id: ImporterId,
@@ -61,7 +77,7 @@ impl ImporterRegistryBuilder {
 
         for extension in self.registered_importers[&importer_id].supported_file_extensions() {
             self.file_extension_associations
-                .entry(extension.to_string())
+                .entry(normalize_file_extension(extension))
                 .or_default()
                 .push(importer_id);
         }
@@ -78,3 +94,88 @@ impl ImporterRegistryBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PipelineResult;
+
+    #[derive(TypeUuid, Default)]
+    #[uuid = "6b1f2a5e-1c43-4b52-8a52-5b6f0c1d9e01"]
+    struct ImageImporter;
+
+    impl Importer for ImageImporter {
+        fn supported_file_extensions(&self) -> &[&'static str] {
+            &["png", "JPG"]
+        }
+
+        fn scan_file(
+            &self,
+            _context: ScanContext,
+        ) -> PipelineResult<()> {
+            Ok(())
+        }
+
+        fn import_file(
+            &self,
+            _context: ImportContext,
+        ) -> PipelineResult<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(TypeUuid, Default)]
+    #[uuid = "6b1f2a5e-1c43-4b52-8a52-5b6f0c1d9e02"]
+    struct OtherImageImporter;
+
+    impl Importer for OtherImageImporter {
+        fn supported_file_extensions(&self) -> &[&'static str] {
+            &[".png", "tga"]
+        }
+
+        fn scan_file(
+            &self,
+            _context: ScanContext,
+        ) -> PipelineResult<()> {
+            Ok(())
+        }
+
+        fn import_file(
+            &self,
+            _context: ImportContext,
+        ) -> PipelineResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn supported_extensions_with_overlapping_importers() {
+        let mut builder = ImporterRegistryBuilder::default();
+        builder.register_handler::<ImageImporter>();
+        builder.register_handler::<OtherImageImporter>();
+        let importer_registry = builder.build();
+
+        let supported_extensions = importer_registry.supported_extensions();
+        assert_eq!(supported_extensions.len(), 3);
+        assert!(supported_extensions.contains("png"));
+        assert!(supported_extensions.contains("jpg"));
+        assert!(supported_extensions.contains("tga"));
+
+        // Lookup is normalized the same way as registration
+        assert_eq!(
+            importer_registry.importers_for_file_extension("PNG").len(),
+            2
+        );
+        assert_eq!(
+            importer_registry.importers_for_file_extension(".jpg").len(),
+            1
+        );
+        assert_eq!(
+            importer_registry.importers_for_file_extension("tga").len(),
+            1
+        );
+        assert!(importer_registry
+            .importers_for_file_extension("gltf")
+            .is_empty());
+    }
+}
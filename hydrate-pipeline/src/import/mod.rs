@@ -15,3 +15,7 @@ pub use import_util::ImportJobToQueue;
 pub use import_util::RequestedImportable;
 
 mod import_storage;
+
+mod packed_import_data;
+
+pub mod source_file;
@@ -0,0 +1,544 @@
+use crate::PipelineResult;
+use hydrate_base::hashing::HashMap;
+use hydrate_base::AssetId;
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+// Packed import data concatenates the import data of many assets into a few large segment files
+// rather than writing one .if file per asset. An index file next to the segments is an append-only
+// log of where each asset's import data is, the last record for an asset wins.
+//
+// Segments are only appended to. Replacing an asset's import data appends the new data and leaves
+// the old data in place as unused space. Once enough of the stored data is unused, compaction copies
+// the live data into new segments, rewrites the index and deletes the old segments.
+
+const INDEX_FILE_NAME: &str = "packed_import_data.index";
+const SEGMENT_FILE_EXTENSION: &str = "ifs";
+
+// A new segment is started once the current one is at least this large
+const MAX_SEGMENT_SIZE: u64 = 256 * 1024 * 1024;
+
+// Compaction is skipped until at least this much stored data is unused, and more of it is unused
+// than in use
+const MIN_COMPACTION_UNUSED_SIZE: u64 = 64 * 1024 * 1024;
+
+// Asset UUID, segment index, offset, length, metadata hash
+const INDEX_RECORD_SIZE: usize = 16 + 4 + 8 + 8 + 8;
+
+// Segment index of a record that removes an asset's import data
+const REMOVED_SEGMENT_INDEX: u32 = u32::MAX;
+
+lazy_static::lazy_static! {
+    // Import data is accessed by project config from free functions (load_import_data(),
+    // write_import_data_bytes()) called by import threads, build jobs, thumbnail jobs and tools
+    // that have no ImportJobs to borrow it from, the same way .if files are accessed by path.
+    // Appends must go through a single index per directory, otherwise two instances would each
+    // track their own end of segment and index over each other's data.
+    static ref OPEN_PACKED_IMPORT_DATA: Mutex<HashMap<PathBuf, Arc<PackedImportData>>> =
+        Mutex::new(HashMap::default());
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct PackedImportDataLocation {
+    segment_index: u32,
+    offset: u64,
+    length: u64,
+    // Changes every time the asset's import data is written, like the modified time of a .if
+    // file. Compaction keeps it, so moving the data isn't mistaken for it changing.
+    metadata_hash: u64,
+}
+
+struct PackedImportDataInner {
+    locations: HashMap<AssetId, PackedImportDataLocation>,
+    current_segment_index: u32,
+    current_segment_size: u64,
+}
+
+pub struct PackedImportData {
+    root_path: PathBuf,
+    inner: Mutex<PackedImportDataInner>,
+}
+
+impl PackedImportData {
+    // Returns the packed import data for the given directory, reading the index the first time the
+    // directory is opened
+    pub fn open(root_path: &Path) -> PipelineResult<Arc<PackedImportData>> {
+        let mut open_packed_import_data = OPEN_PACKED_IMPORT_DATA.lock().unwrap();
+        if let Some(packed_import_data) = open_packed_import_data.get(root_path) {
+            return Ok(packed_import_data.clone());
+        }
+
+        let packed_import_data = Arc::new(Self::read_index(root_path)?);
+        open_packed_import_data.insert(root_path.to_path_buf(), packed_import_data.clone());
+        Ok(packed_import_data)
+    }
+
+    // Forgets the packed import data for the given directory so that it is read from disk again the
+    // next time it is opened, i.e. because the directory was moved
+    pub fn close(root_path: &Path) {
+        OPEN_PACKED_IMPORT_DATA.lock().unwrap().remove(root_path);
+    }
+
+    fn read_index(root_path: &Path) -> PipelineResult<PackedImportData> {
+        let mut locations = HashMap::default();
+        let mut current_segment_index = 0;
+
+        let index_path = root_path.join(INDEX_FILE_NAME);
+        if index_path.exists() {
+            let index_bytes = std::fs::read(&index_path)?;
+
+            // A partially written record at the end of the index is ignored
+            for record in index_bytes.chunks_exact(INDEX_RECORD_SIZE) {
+                let asset_id = AssetId::from_uuid(uuid::Uuid::from_slice(&record[0..16]).unwrap());
+                let segment_index = u32::from_le_bytes(record[16..20].try_into().unwrap());
+                if segment_index == REMOVED_SEGMENT_INDEX {
+                    locations.remove(&asset_id);
+                    continue;
+                }
+
+                let location = PackedImportDataLocation {
+                    segment_index,
+                    offset: u64::from_le_bytes(record[20..28].try_into().unwrap()),
+                    length: u64::from_le_bytes(record[28..36].try_into().unwrap()),
+                    metadata_hash: u64::from_le_bytes(record[36..44].try_into().unwrap()),
+                };
+                current_segment_index = current_segment_index.max(segment_index);
+                locations.insert(asset_id, location);
+            }
+        }
+
+        // The segment may have data past the last indexed entry if a write was interrupted, so
+        // use the file size rather than the index to find where to append
+        let current_segment_path = Self::segment_path(root_path, current_segment_index);
+        let current_segment_size = if current_segment_path.exists() {
+            current_segment_path.metadata()?.len()
+        } else {
+            0
+        };
+
+        Ok(PackedImportData {
+            root_path: root_path.to_path_buf(),
+            inner: Mutex::new(PackedImportDataInner {
+                locations,
+                current_segment_index,
+                current_segment_size,
+            }),
+        })
+    }
+
+    fn segment_path(
+        root_path: &Path,
+        segment_index: u32,
+    ) -> PathBuf {
+        root_path.join(format!("{:05}.{}", segment_index, SEGMENT_FILE_EXTENSION))
+    }
+
+    fn encode_index_record(
+        asset_id: AssetId,
+        location: &PackedImportDataLocation,
+        record: &mut Vec<u8>,
+    ) {
+        record.extend_from_slice(asset_id.as_uuid().as_bytes());
+        record.extend_from_slice(&location.segment_index.to_le_bytes());
+        record.extend_from_slice(&location.offset.to_le_bytes());
+        record.extend_from_slice(&location.length.to_le_bytes());
+        record.extend_from_slice(&location.metadata_hash.to_le_bytes());
+    }
+
+    fn append_index_record(
+        &self,
+        asset_id: AssetId,
+        location: PackedImportDataLocation,
+    ) -> PipelineResult<()> {
+        let mut record = Vec::with_capacity(INDEX_RECORD_SIZE);
+        Self::encode_index_record(asset_id, &location, &mut record);
+
+        let mut index_file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.root_path.join(INDEX_FILE_NAME))?;
+        index_file.write_all(&record)?;
+        Ok(())
+    }
+
+    // Changes every time the asset's import data is written, like the modified time of a .if file
+    pub fn metadata_hash(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<u64> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .locations
+            .get(&asset_id)
+            .map(|location| location.metadata_hash)
+    }
+
+    pub fn metadata_hashes(&self) -> HashMap<AssetId, u64> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .locations
+            .iter()
+            .map(|(asset_id, location)| (*asset_id, location.metadata_hash))
+            .collect()
+    }
+
+    fn read_location(
+        &self,
+        location: &PackedImportDataLocation,
+    ) -> std::io::Result<Vec<u8>> {
+        let mut segment_file =
+            std::fs::File::open(Self::segment_path(&self.root_path, location.segment_index))?;
+        segment_file.seek(SeekFrom::Start(location.offset))?;
+        let mut data = vec![0u8; location.length as usize];
+        segment_file.read_exact(&mut data)?;
+        Ok(data)
+    }
+
+    pub fn read(
+        &self,
+        asset_id: AssetId,
+    ) -> PipelineResult<Option<Vec<u8>>> {
+        let Some(location) = self.inner.lock().unwrap().locations.get(&asset_id).copied() else {
+            return Ok(None);
+        };
+
+        // Written data is never modified, so it's safe to read without holding the lock. Compaction
+        // may delete the segment after the location was looked up, in which case the data has been
+        // moved and is read from its new location.
+        match self.read_location(&location) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let moved_location = self.inner.lock().unwrap().locations.get(&asset_id).copied();
+                match moved_location {
+                    Some(moved_location) if moved_location != location => {
+                        Ok(Some(self.read_location(&moved_location)?))
+                    }
+                    Some(_) => Err(e)?,
+                    None => Ok(None),
+                }
+            }
+            Err(e) => Err(e)?,
+        }
+    }
+
+    // Appends the data to the current segment, returning where it was written
+    fn append_to_segment(
+        root_path: &Path,
+        inner: &mut PackedImportDataInner,
+        data: &[u8],
+    ) -> PipelineResult<(u32, u64)> {
+        if inner.current_segment_size >= MAX_SEGMENT_SIZE {
+            inner.current_segment_index += 1;
+            inner.current_segment_size = 0;
+        }
+
+        std::fs::create_dir_all(root_path)?;
+        let segment_file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::segment_path(root_path, inner.current_segment_index))?;
+
+        // The file's length is where the data will actually be appended, even if an earlier write
+        // failed partway through
+        let offset = segment_file.metadata()?.len();
+        if let Err(e) = (&segment_file).write_all(data) {
+            // Don't leave part of the data behind as unused space
+            if let Err(truncate_error) = segment_file.set_len(offset) {
+                log::warn!(
+                    "Could not truncate packed import data segment {} after a failed write: {}",
+                    inner.current_segment_index,
+                    truncate_error
+                );
+            }
+            Err(e)?;
+        }
+
+        inner.current_segment_size = offset + data.len() as u64;
+        Ok((inner.current_segment_index, offset))
+    }
+
+    pub fn write(
+        &self,
+        asset_id: AssetId,
+        data: &[u8],
+    ) -> PipelineResult<()> {
+        let mut inner = self.inner.lock().unwrap();
+        let (segment_index, offset) = Self::append_to_segment(&self.root_path, &mut inner, data)?;
+
+        let mut hasher = siphasher::sip::SipHasher::default();
+        segment_index.hash(&mut hasher);
+        offset.hash(&mut hasher);
+        data.len().hash(&mut hasher);
+        let location = PackedImportDataLocation {
+            segment_index,
+            offset,
+            length: data.len() as u64,
+            metadata_hash: hasher.finish(),
+        };
+
+        self.append_index_record(asset_id, location)?;
+        inner.locations.insert(asset_id, location);
+        Ok(())
+    }
+
+    // Compacts the segments if enough of the stored data is unused. Returns true if they were
+    // compacted.
+    pub fn compact_if_needed(&self) -> PipelineResult<bool> {
+        let mut inner = self.inner.lock().unwrap();
+        let used_size: u64 = inner.locations.values().map(|x| x.length).sum();
+        let mut stored_size = 0;
+        for segment_index in 0..=inner.current_segment_index {
+            if let Ok(metadata) = Self::segment_path(&self.root_path, segment_index).metadata() {
+                stored_size += metadata.len();
+            }
+        }
+
+        let unused_size = stored_size.saturating_sub(used_size);
+        if unused_size < MIN_COMPACTION_UNUSED_SIZE || unused_size <= used_size {
+            return Ok(false);
+        }
+
+        self.compact_locked(&mut inner)?;
+        Ok(true)
+    }
+
+    fn compact_locked(
+        &self,
+        inner: &mut PackedImportDataInner,
+    ) -> PipelineResult<()> {
+        profiling::scope!("Compact packed import data");
+
+        // Live data is copied into new segments after the existing ones, so the existing ones stay
+        // valid until the new index replaces the old one
+        let old_segment_count = inner.current_segment_index + 1;
+        let mut compacted = PackedImportDataInner {
+            locations: HashMap::default(),
+            current_segment_index: old_segment_count,
+            current_segment_size: 0,
+        };
+
+        // Copy in the order the data is stored so that the old segments are read sequentially
+        let mut live_entries: Vec<_> = inner
+            .locations
+            .iter()
+            .map(|(asset_id, location)| (*asset_id, *location))
+            .collect();
+        live_entries.sort_by_key(|(_, location)| (location.segment_index, location.offset));
+
+        let mut index = Vec::with_capacity(live_entries.len() * INDEX_RECORD_SIZE);
+        for (asset_id, location) in live_entries {
+            let data = self.read_location(&location)?;
+            let (segment_index, offset) =
+                Self::append_to_segment(&self.root_path, &mut compacted, &data)?;
+            let compacted_location = PackedImportDataLocation {
+                segment_index,
+                offset,
+                length: location.length,
+                metadata_hash: location.metadata_hash,
+            };
+            Self::encode_index_record(asset_id, &compacted_location, &mut index);
+            compacted.locations.insert(asset_id, compacted_location);
+        }
+
+        for segment_index in old_segment_count..=compacted.current_segment_index {
+            let segment_path = Self::segment_path(&self.root_path, segment_index);
+            if segment_path.exists() {
+                std::fs::File::open(segment_path)?.sync_all()?;
+            }
+        }
+
+        // Replace the index in one step so that it never refers to a mix of old and new segments
+        let index_path = self.root_path.join(INDEX_FILE_NAME);
+        let mut tmp_index_path = index_path.clone().into_os_string();
+        tmp_index_path.push(".tmp");
+        {
+            let mut tmp_index_file = std::fs::File::create(&tmp_index_path)?;
+            tmp_index_file.write_all(&index)?;
+            tmp_index_file.sync_all()?;
+        }
+        std::fs::rename(&tmp_index_path, &index_path)?;
+
+        *inner = compacted;
+
+        for segment_index in 0..old_segment_count {
+            let segment_path = Self::segment_path(&self.root_path, segment_index);
+            if let Err(e) = std::fs::remove_file(&segment_path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    log::warn!(
+                        "Could not remove compacted packed import data segment {:?}: {}",
+                        segment_path,
+                        e
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn remove(
+        &self,
+        asset_id: AssetId,
+    ) -> PipelineResult<()> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.locations.remove(&asset_id).is_some() {
+            self.append_index_record(
+                asset_id,
+                PackedImportDataLocation {
+                    segment_index: REMOVED_SEGMENT_INDEX,
+                    offset: 0,
+                    length: 0,
+                    metadata_hash: 0,
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_and_read_packed_import_data() {
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+
+        let asset_ids: Vec<_> = (0..5)
+            .map(|_| AssetId::from_uuid(uuid::Uuid::new_v4()))
+            .collect();
+        {
+            let packed_import_data = PackedImportData::open(&root_path).unwrap();
+            for (i, asset_id) in asset_ids.iter().enumerate() {
+                packed_import_data
+                    .write(*asset_id, format!("import data {}", i).as_bytes())
+                    .unwrap();
+            }
+
+            // Replacing import data appends it, the latest write is returned
+            packed_import_data
+                .write(asset_ids[3], b"replaced import data")
+                .unwrap();
+            packed_import_data.remove(asset_ids[4]).unwrap();
+
+            assert_eq!(
+                packed_import_data.read(asset_ids[2]).unwrap().unwrap(),
+                b"import data 2"
+            );
+        }
+
+        // Reading the index from disk gives the same result as the index that was built in memory
+        let packed_import_data = PackedImportData::read_index(&root_path).unwrap();
+        assert_eq!(
+            packed_import_data.read(asset_ids[0]).unwrap().unwrap(),
+            b"import data 0"
+        );
+        assert_eq!(
+            packed_import_data.read(asset_ids[3]).unwrap().unwrap(),
+            b"replaced import data"
+        );
+        assert!(packed_import_data.read(asset_ids[4]).unwrap().is_none());
+        assert_eq!(packed_import_data.metadata_hashes().len(), 4);
+
+        // Everything is in a single segment and the index, not a file per asset
+        assert_eq!(std::fs::read_dir(&root_path).unwrap().count(), 2);
+
+        std::fs::remove_dir_all(&root_path).unwrap();
+    }
+
+    #[test]
+    fn write_appends_after_data_past_the_last_indexed_entry() {
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        let first_asset_id = AssetId::from_uuid(uuid::Uuid::new_v4());
+        let second_asset_id = AssetId::from_uuid(uuid::Uuid::new_v4());
+
+        let packed_import_data = PackedImportData::read_index(&root_path).unwrap();
+        packed_import_data
+            .write(first_asset_id, b"first import data")
+            .unwrap();
+
+        // Simulate a write that failed partway through without being rolled back
+        let mut segment_file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(PackedImportData::segment_path(&root_path, 0))
+            .unwrap();
+        segment_file.write_all(b"partial").unwrap();
+
+        packed_import_data
+            .write(second_asset_id, b"second import data")
+            .unwrap();
+        assert_eq!(
+            packed_import_data.read(second_asset_id).unwrap().unwrap(),
+            b"second import data"
+        );
+        assert_eq!(
+            PackedImportData::read_index(&root_path)
+                .unwrap()
+                .read(second_asset_id)
+                .unwrap()
+                .unwrap(),
+            b"second import data"
+        );
+
+        std::fs::remove_dir_all(&root_path).unwrap();
+    }
+
+    #[test]
+    fn compaction_removes_unused_packed_import_data() {
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+
+        let asset_ids: Vec<_> = (0..3)
+            .map(|_| AssetId::from_uuid(uuid::Uuid::new_v4()))
+            .collect();
+        let packed_import_data = PackedImportData::read_index(&root_path).unwrap();
+        for asset_id in &asset_ids {
+            packed_import_data.write(*asset_id, b"original").unwrap();
+        }
+        packed_import_data
+            .write(asset_ids[1], b"replaced import data")
+            .unwrap();
+        packed_import_data.remove(asset_ids[2]).unwrap();
+
+        // There isn't enough unused data to be worth compacting
+        assert!(!packed_import_data.compact_if_needed().unwrap());
+
+        let metadata_hashes = packed_import_data.metadata_hashes();
+        packed_import_data
+            .compact_locked(&mut packed_import_data.inner.lock().unwrap())
+            .unwrap();
+
+        // Only the live data is left, in a new segment, and moving it isn't a change
+        let segment_sizes: Vec<_> = std::fs::read_dir(&root_path)
+            .unwrap()
+            .map(|x| x.unwrap().path())
+            .filter(|x| x.extension().unwrap() == SEGMENT_FILE_EXTENSION)
+            .map(|x| x.metadata().unwrap().len())
+            .collect();
+        assert_eq!(
+            segment_sizes,
+            vec![("original".len() + "replaced import data".len()) as u64]
+        );
+        assert_eq!(packed_import_data.metadata_hashes(), metadata_hashes);
+
+        for packed_import_data in [
+            packed_import_data,
+            PackedImportData::read_index(&root_path).unwrap(),
+        ] {
+            assert_eq!(
+                packed_import_data.read(asset_ids[0]).unwrap().unwrap(),
+                b"original"
+            );
+            assert_eq!(
+                packed_import_data.read(asset_ids[1]).unwrap().unwrap(),
+                b"replaced import data"
+            );
+            assert!(packed_import_data.read(asset_ids[2]).unwrap().is_none());
+            assert_eq!(packed_import_data.metadata_hashes(), metadata_hashes);
+        }
+
+        std::fs::remove_dir_all(&root_path).unwrap();
+    }
+}
@@ -0,0 +1,203 @@
+use crate::import::import_storage::ImportDataMetadata;
+use crate::{PipelineResult, SourceFileChangeDetection};
+use hydrate_base::hashing::ContentHashAlgorithm;
+use hydrate_data::{join_archive_path, split_archive_path};
+use std::hash::{BuildHasher, Hasher};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+//
+// Source files are usually plain files on disk, but they may also be an entry in an archive, i.e.
+// "/path/to/archive.zip!inner/path.png". These functions handle either case so that importers
+// don't need to care where the bytes came from.
+//
+
+pub fn is_archive(path: &Path) -> bool {
+    path.extension()
+        .map(|extension| extension.eq_ignore_ascii_case("zip"))
+        .unwrap_or(false)
+}
+
+fn open_archive(archive_path: &Path) -> PipelineResult<zip::ZipArchive<std::fs::File>> {
+    let file = std::fs::File::open(archive_path)?;
+    Ok(zip::ZipArchive::new(file)
+        .map_err(|e| format!("Could not open archive {:?}: {}", archive_path, e))?)
+}
+
+// Returns paths to all files in the archive, suitable for passing to read_source_file()
+pub fn archive_entries(archive_path: &Path) -> PipelineResult<Vec<PathBuf>> {
+    let archive = open_archive(archive_path)?;
+    let mut entries: Vec<PathBuf> = archive
+        .file_names()
+        .filter(|file_name| !file_name.ends_with('/'))
+        .map(|file_name| join_archive_path(archive_path, file_name))
+        .collect();
+    entries.sort();
+    Ok(entries)
+}
+
+pub fn read_source_file(path: &Path) -> PipelineResult<Vec<u8>> {
+    let path_str = path.to_string_lossy();
+    if let Some((archive_path, entry_path)) = split_archive_path(&path_str) {
+        let mut archive = open_archive(Path::new(archive_path))?;
+        let mut entry = archive.by_name(entry_path).map_err(|e| {
+            format!(
+                "Could not read {:?} from archive {:?}: {}",
+                entry_path, archive_path, e
+            )
+        })?;
+        let mut data = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut data)?;
+        Ok(data)
+    } else {
+        Ok(std::fs::read(path)?)
+    }
+}
+
+// Returns the size and modified time (seconds since unix epoch) of the source file. This is used
+// to detect stale import data. Files in an archive use the archive's modified time, so changing
+// the archive re-imports everything in it.
+pub fn source_file_size_and_modified_timestamp(path: &Path) -> PipelineResult<(u64, u64)> {
+    let path_str = path.to_string_lossy();
+    let (file_system_path, size) =
+        if let Some((archive_path, entry_path)) = split_archive_path(&path_str) {
+            let mut archive = open_archive(Path::new(archive_path))?;
+            let entry = archive.by_name(entry_path).map_err(|e| {
+                format!(
+                    "Could not find {:?} in archive {:?}: {}",
+                    entry_path, archive_path, e
+                )
+            })?;
+            (PathBuf::from(archive_path), Some(entry.size()))
+        } else {
+            (path.to_path_buf(), None)
+        };
+
+    let metadata = file_system_path.metadata()?;
+    let modified_timestamp = metadata
+        .modified()?
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_err(|e| format!("Error getting duration since unix epoch: {:?}", e))?
+        .as_secs();
+
+    Ok((size.unwrap_or(metadata.len()), modified_timestamp))
+}
+
+// Source file contents are always hashed with xxh3, it's fast on large files and can be streamed
+// so the file doesn't need to be read into memory all at once
+fn hash_source_file_reader<T: Read>(reader: &mut T) -> PipelineResult<u64> {
+    let mut hasher = ContentHashAlgorithm::Xxh3.build_hasher();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let bytes_read = reader.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+
+        hasher.write(&buffer[0..bytes_read]);
+    }
+
+    Ok(hasher.finish())
+}
+
+// Hashes the contents of the source file, which may be in an archive
+pub fn hash_source_file(path: &Path) -> PipelineResult<u64> {
+    let path_str = path.to_string_lossy();
+    if let Some((archive_path, entry_path)) = split_archive_path(&path_str) {
+        let mut archive = open_archive(Path::new(archive_path))?;
+        let mut entry = archive.by_name(entry_path).map_err(|e| {
+            format!(
+                "Could not read {:?} from archive {:?}: {}",
+                entry_path, archive_path, e
+            )
+        })?;
+        hash_source_file_reader(&mut entry)
+    } else {
+        hash_source_file_reader(&mut std::fs::File::open(path)?)
+    }
+}
+
+// Same as hash_source_file(), for source file contents that were already read
+pub fn hash_source_file_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = ContentHashAlgorithm::Xxh3.build_hasher();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+// The current state of a source file, used to check if it changed since it was imported. The
+// contents are hashed at most once, and only if the size and modified time can't decide it.
+pub(crate) struct SourceFileState<'a> {
+    path: &'a Path,
+    change_detection: SourceFileChangeDetection,
+    pub size: u64,
+    pub modified_timestamp: u64,
+    contents_hash: Option<u64>,
+}
+
+impl<'a> SourceFileState<'a> {
+    pub fn new(
+        path: &'a Path,
+        change_detection: SourceFileChangeDetection,
+    ) -> PipelineResult<Self> {
+        let (size, modified_timestamp) = source_file_size_and_modified_timestamp(path)?;
+        Ok(SourceFileState {
+            path,
+            change_detection,
+            size,
+            modified_timestamp,
+            contents_hash: None,
+        })
+    }
+
+    // Returns the hash to record when importing the file, or 0 if the project doesn't hash source
+    // files. The bytes must be the contents of the file.
+    pub fn contents_hash_for_import(
+        &mut self,
+        bytes: &[u8],
+    ) -> u64 {
+        if self.change_detection == SourceFileChangeDetection::Timestamp {
+            return 0;
+        }
+
+        *self
+            .contents_hash
+            .get_or_insert_with(|| hash_source_file_bytes(bytes))
+    }
+
+    fn contents_hash(&mut self) -> PipelineResult<u64> {
+        if let Some(contents_hash) = self.contents_hash {
+            return Ok(contents_hash);
+        }
+
+        let contents_hash = hash_source_file(self.path)?;
+        self.contents_hash = Some(contents_hash);
+        Ok(contents_hash)
+    }
+
+    // Returns true if the source file is the same as when the metadata was recorded
+    pub fn matches(
+        &mut self,
+        metadata: &ImportDataMetadata,
+    ) -> PipelineResult<bool> {
+        if self.size != metadata.source_file_size {
+            return Ok(false);
+        }
+
+        let timestamp_matches = self.modified_timestamp == metadata.source_file_modified_timestamp;
+
+        // Files imported without hashing can only be compared by timestamp
+        let can_compare_hash = metadata.source_file_contents_hash != 0;
+        let compare_hash = match self.change_detection {
+            SourceFileChangeDetection::Timestamp => false,
+            SourceFileChangeDetection::HashIfTimestampChanged => !timestamp_matches,
+            SourceFileChangeDetection::ContentHash => true,
+        };
+
+        if compare_hash && can_compare_hash {
+            Ok(self.contents_hash()? == metadata.source_file_contents_hash)
+        } else {
+            Ok(timestamp_matches)
+        }
+    }
+}
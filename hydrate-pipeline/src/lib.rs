@@ -1,4 +1,6 @@
+use std::path::Path;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub use hydrate_schema::*;
 
@@ -14,19 +16,23 @@ pub use thumbnails::*;
 
 pub use import::{
     import_util::create_asset_name,
-    import_util::recursively_gather_import_operations_and_create_assets, ImportContext,
-    ImportJobSourceFile, ImportJobToQueue, ImportJobs, ImportStatus, ImportStatusImporting,
-    ImportType, Importer, ImporterRegistry, ImporterRegistryBuilder, RequestedImportable,
-    ScanContext, ScannedImportable,
+    import_util::recursively_gather_import_operations_and_create_assets,
+    import_util::scan_files_to_import, import_util::ImportFileScan, read_import_data_bytes,
+    source_file, write_import_data_bytes, ImportContext, ImportJobSourceFile, ImportJobToQueue,
+    ImportJobs, ImportStatus, ImportStatusImporting, ImportType, ImportWarning,
+    ImportWarningSeverity, Importer, ImporterRegistry, ImporterRegistryBuilder,
+    RequestedImportable, ScanContext, ScannedImportable,
 };
 
-pub use project::{HydrateProjectConfiguration, NamePathPair};
+pub use project::{HydrateProjectConfiguration, NamePathPair, SourceFileChangeDetection};
 
 pub use crate::build::{
-    AssetArtifactIdPair, BuildJobs, BuildStatus, BuildStatusBuilding, Builder, BuilderContext,
-    BuilderRegistry, BuilderRegistryBuilder, EnumerateDependenciesContext, HandleFactory,
-    JobEnumeratedDependencies, JobId, JobInput, JobOutput, JobProcessor, JobProcessorRegistry,
-    JobProcessorRegistryBuilder, RunContext,
+    hash_asset_build_inputs, AssetArtifactIdPair, AssetBuildOutputEstimate, BuildCacheProvider,
+    BuildJobs, BuildOutputEstimate, BuildOutputEstimateSource, BuildOutputSize, BuildProfile,
+    BuildProfileEntry, BuildStatus, BuildStatusBuilding, Builder, BuilderContext, BuilderRegistry,
+    BuilderRegistryBuilder, EnumerateDependenciesContext, FileSystemBuildCacheProvider,
+    HandleFactory, JobEnumeratedDependencies, JobId, JobInput, JobOutput, JobProcessor,
+    JobProcessorRegistry, JobProcessorRegistryBuilder, RunContext,
 };
 pub use pipeline_error::*;
 
@@ -123,6 +129,13 @@ pub trait DynEditorModel {
         &self,
         asset_id: AssetId,
     ) -> String;
+
+    // True while edits are being previewed (i.e. a slider is being dragged). Builds and thumbnails
+    // wait until the edits are committed.
+    fn has_preview_edit(&self) -> bool;
+
+    // Commits pending edits so that data_set() reflects them, without saving them
+    fn flush_pending_edits(&mut self);
 }
 
 pub trait DynEditContext {
@@ -145,6 +158,8 @@ pub struct AssetEngine {
     builder_registry: BuilderRegistry,
     build_jobs: BuildJobs,
     thumbnail_system: ThumbnailSystem,
+    last_build_log_data: Option<Arc<BuildLogData>>,
+    diagnostic_sinks: Vec<Box<dyn DiagnosticSink>>,
 }
 
 impl AssetEngine {
@@ -164,9 +179,8 @@ impl AssetEngine {
         let build_jobs = BuildJobs::new(
             schema_set,
             &registries.job_processor_registry,
-            project_configuration.import_data_path.clone(),
-            project_configuration.job_data_path.clone(),
-            project_configuration.build_data_path.clone(),
+            &registries.importer_registry,
+            project_configuration,
         );
 
         let thumbnail_system = ThumbnailSystem::new(
@@ -183,6 +197,28 @@ impl AssetEngine {
             builder_registry: registries.builder_registry,
             build_jobs,
             thumbnail_system,
+            last_build_log_data: None,
+            diagnostic_sinks: Vec::default(),
+        }
+    }
+
+    /// Registers a sink that receives every diagnostic logged by imports and builds. Diagnostics
+    /// are delivered when the import or build that logged them completes.
+    pub fn add_diagnostic_sink(
+        &mut self,
+        sink: Box<dyn DiagnosticSink>,
+    ) {
+        self.diagnostic_sinks.push(sink);
+    }
+
+    fn send_diagnostics(
+        &mut self,
+        diagnostics: impl Iterator<Item = Diagnostic>,
+    ) {
+        for diagnostic in diagnostics {
+            for sink in &mut self.diagnostic_sinks {
+                sink.on_diagnostic(diagnostic.clone());
+            }
         }
     }
 
@@ -196,6 +232,11 @@ impl AssetEngine {
         }
     }
 
+    // Timings from the most recently completed build
+    pub fn last_build_profile(&self) -> Option<&BuildProfile> {
+        self.last_build_log_data.as_ref().map(|x| x.profile())
+    }
+
     pub fn thumbnail_provider_registry(&self) -> &ThumbnailProviderRegistry {
         self.thumbnail_system.thumbnail_provider_registry()
     }
@@ -231,12 +272,15 @@ impl AssetEngine {
                     return Ok(AssetEngineState::Importing(importing_state))
                 }
                 ImportStatus::Completed(import_log_data) => {
-                    return Ok(AssetEngineState::ImportCompleted(import_log_data))
+                    self.send_diagnostics(
+                        import_log_data.log_events().iter().map(Diagnostic::from),
+                    );
+                    return Ok(AssetEngineState::ImportCompleted(import_log_data));
                 }
             }
         }
 
-        if !self.build_jobs.is_building() {
+        if !self.build_jobs.is_building() && !editor_model.has_preview_edit() {
             assert!(!self.import_jobs.is_importing());
             self.thumbnail_system
                 .update(editor_model.data_set(), editor_model.schema_set());
@@ -250,7 +294,7 @@ impl AssetEngine {
         // Check if our import state is consistent, if it is we save expected hashes and run builds
         let build_state =
             self.build_jobs
-                .update(&self.builder_registry, editor_model, &self.import_jobs)?;
+                .update(&self.builder_registry, editor_model, &mut self.import_jobs)?;
 
         match build_state {
             BuildStatus::Idle => Ok(AssetEngineState::Idle),
@@ -258,7 +302,9 @@ impl AssetEngine {
                 return Ok(AssetEngineState::Building(building_state))
             }
             BuildStatus::Completed(build_log_data) => {
-                return Ok(AssetEngineState::BuildCompleted(build_log_data))
+                self.last_build_log_data = Some(build_log_data.clone());
+                self.send_diagnostics(build_log_data.log_events().iter().map(Diagnostic::from));
+                return Ok(AssetEngineState::BuildCompleted(build_log_data));
             }
         }
     }
@@ -289,6 +335,18 @@ impl AssetEngine {
         self.builder_registry.builder_for_asset(fingerprint)
     }
 
+    // The TypeUuids of the artifacts that building an asset of the given schema produces
+    pub fn artifact_types_for_asset(
+        &self,
+        fingerprint: SchemaFingerprint,
+    ) -> &[Uuid] {
+        self.builder_registry.artifact_types_for_asset(fingerprint)
+    }
+
+    pub fn asset_type_to_artifact_types(&self) -> &HashMap<SchemaFingerprint, Vec<Uuid>> {
+        self.builder_registry.asset_type_to_artifact_types()
+    }
+
     pub fn queue_import_operation(
         &mut self,
         import_job_to_queue: ImportJobToQueue,
@@ -307,10 +365,88 @@ impl AssetEngine {
         self.build_jobs.needs_build()
     }
 
+    /// Returns the build jobs requested for the asset in the current build that haven't finished
+    pub fn active_build_jobs_for_asset(
+        &self,
+        asset_id: AssetId,
+    ) -> Vec<JobId> {
+        self.build_jobs.active_jobs_for_asset(asset_id)
+    }
+
+    /// Cancels the asset's build jobs in the current build. Jobs that are already running finish,
+    /// but the artifacts they produce are discarded.
+    pub fn cancel_jobs_for_asset(
+        &mut self,
+        asset_id: AssetId,
+    ) {
+        self.build_jobs.cancel_jobs_for_asset(asset_id);
+    }
+
     pub fn queue_build_all(&mut self) {
         self.build_jobs.build();
     }
 
+    /// Queues a build of every asset. If clear_cache is set, all existing build data is deleted
+    /// first, so every artifact is produced from scratch even if its inputs haven't changed. This
+    /// is for when build data is suspected to be stale, i.e. after a toolchain change. The number
+    /// of artifacts rebuilt is reported by BuildLogData::built_artifact_count() when the build
+    /// completes.
+    pub fn rebuild_all(
+        &mut self,
+        clear_cache: bool,
+    ) -> PipelineResult<()> {
+        self.build_jobs.rebuild_all(clear_cache)
+    }
+
+    /// Estimates the number and size of artifacts a build of every asset would produce, without
+    /// building anything. Assets whose builder implements Builder::estimate_output() use that.
+    /// Otherwise, the asset's output in the most recent build's manifest is used, and the estimate
+    /// notes whether the asset changed since then. Assets that can't be estimated either way are
+    /// listed by BuildOutputEstimate::unestimated_assets().
+    pub fn estimate_build_output(
+        &self,
+        editor_model: &dyn DynEditorModel,
+    ) -> PipelineResult<BuildOutputEstimate> {
+        self.build_jobs
+            .estimate_build_output(&self.builder_registry, editor_model)
+    }
+
+    /// Sets the cache that build jobs share results through, replacing the one configured by
+    /// HydrateProjectConfiguration::build_cache_path. Before a job runs, the cache is checked for a
+    /// result produced from the same inputs and its artifacts are fetched instead of running the
+    /// job. Jobs store their results in the cache when they complete. None disables the cache.
+    pub fn set_build_cache_provider(
+        &mut self,
+        build_cache: Option<Arc<dyn BuildCacheProvider>>,
+    ) {
+        self.build_jobs.set_build_cache_provider(build_cache);
+    }
+
+    /// Moves the import data and build data directories to new locations, i.e. to a faster or
+    /// larger drive. Everything already imported and built is kept, so the next build only
+    /// rebuilds what has changed. The project file is not modified, so the new paths will not be
+    /// used the next time the project is opened unless it is updated too.
+    pub fn relocate(
+        &mut self,
+        new_import_data_path: &Path,
+        new_build_data_path: &Path,
+    ) -> PipelineResult<()> {
+        if self.import_jobs.is_importing() || self.build_jobs.is_building() {
+            Err("Cannot relocate import and build data while importing or building")?;
+        }
+
+        let mut project_config = self.import_jobs.project_config().clone();
+
+        // Thumbnails read import data, so wait for in-flight requests to finish before moving it
+        self.thumbnail_system.relocate(&project_config);
+        project_config.relocate_data_paths(new_import_data_path, new_build_data_path)?;
+
+        self.import_jobs.relocate(&project_config);
+        self.build_jobs.relocate(&project_config);
+        self.thumbnail_system.relocate(&project_config);
+        Ok(())
+    }
+
     pub fn duplicate_import_data(
         &self,
         old_asset_id: AssetId,
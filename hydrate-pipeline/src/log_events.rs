@@ -1,4 +1,4 @@
-use crate::build::JobRequestor;
+use crate::build::{BuildProfile, JobRequestor};
 use crate::JobId;
 use hydrate_base::hashing::{HashMap, HashSet};
 use hydrate_base::AssetId;
@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 #[derive(Debug, Copy, Clone)]
 pub enum LogEventLevel {
+    Info,
     Warning,
     Error,
     FatalError,
@@ -20,6 +21,50 @@ pub struct ImportLogEvent {
     pub message: String,
 }
 
+// An import or build log event, as delivered to a DiagnosticSink
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub level: LogEventLevel,
+    pub message: String,
+    pub asset_id: Option<AssetId>,
+    pub job_id: Option<JobId>,
+    // The file being imported, for import diagnostics
+    pub path: Option<PathBuf>,
+}
+
+impl From<&ImportLogEvent> for Diagnostic {
+    fn from(log_event: &ImportLogEvent) -> Self {
+        Diagnostic {
+            level: log_event.level,
+            message: log_event.message.clone(),
+            asset_id: log_event.asset_id,
+            job_id: None,
+            path: Some(log_event.path.clone()),
+        }
+    }
+}
+
+impl From<&BuildLogEvent> for Diagnostic {
+    fn from(log_event: &BuildLogEvent) -> Self {
+        Diagnostic {
+            level: log_event.level,
+            message: log_event.message.clone(),
+            asset_id: log_event.asset_id,
+            job_id: log_event.job_id,
+            path: None,
+        }
+    }
+}
+
+// Receives import and build diagnostics from the AssetEngine, i.e. to show them in a console
+// rather than (or as well as) the process log
+pub trait DiagnosticSink {
+    fn on_diagnostic(
+        &mut self,
+        diagnostic: Diagnostic,
+    );
+}
+
 pub enum LogDataRef<'a> {
     Import(&'a ImportLogData),
     Build(&'a BuildLogData),
@@ -113,6 +158,8 @@ pub struct BuildLogData {
     pub(crate) start_time: std::time::SystemTime,
     pub(crate) log_events: Vec<BuildLogEvent>,
     pub(crate) requestors: HashMap<JobId, Vec<JobRequestor>>,
+    pub(crate) profile: BuildProfile,
+    pub(crate) built_artifact_count: usize,
 }
 
 impl Default for BuildLogData {
@@ -124,6 +171,8 @@ impl Default for BuildLogData {
             start_time: std::time::SystemTime::now(),
             log_events: vec![],
             requestors: Default::default(),
+            profile: Default::default(),
+            built_artifact_count: 0,
         }
     }
 }
@@ -133,6 +182,15 @@ impl BuildLogData {
         &self.log_events
     }
 
+    pub fn profile(&self) -> &BuildProfile {
+        &self.profile
+    }
+
+    // The number of artifacts written by the build, set when it completes
+    pub fn built_artifact_count(&self) -> usize {
+        self.built_artifact_count
+    }
+
     pub fn assets_relying_on_job(
         &self,
         job_id: JobId,
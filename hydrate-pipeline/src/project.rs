@@ -1,4 +1,7 @@
-use hydrate_data::PathReferenceNamespaceResolver;
+use crate::PipelineResult;
+use hydrate_base::hashing::ContentHashAlgorithm;
+use hydrate_base::{canonicalize_path, PathCanonicalization};
+use hydrate_data::{PathReferenceNamespaceResolver, PROJECT_ROOT_NAMESPACE};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::path::{Path, PathBuf};
@@ -27,6 +30,48 @@ pub struct HydrateProjectConfigurationJson {
     pub path_based_asset_sources: Vec<NamePathPairJson>,
     pub source_file_locations: Vec<NamePathPairJson>,
     pub schema_codegen_jobs: Vec<SchemaCodegenJobsJson>,
+    #[serde(default)]
+    pub fsync_on_save: bool,
+    #[serde(default)]
+    pub content_hash_algorithm: ContentHashAlgorithm,
+    #[serde(default)]
+    pub path_canonicalization: PathCanonicalization,
+    #[serde(default)]
+    pub build_thread_count: Option<usize>,
+    #[serde(default)]
+    pub pack_import_data: bool,
+    #[serde(default = "default_autosave_interval_seconds")]
+    pub autosave_interval_seconds: u64,
+    #[serde(default)]
+    pub source_file_change_detection: SourceFileChangeDetection,
+    #[serde(default)]
+    pub write_build_profile: bool,
+    #[serde(default)]
+    pub build_cache_path: Option<String>,
+    #[serde(default = "default_max_import_reference_depth")]
+    pub max_import_reference_depth: usize,
+}
+
+fn default_autosave_interval_seconds() -> u64 {
+    60
+}
+
+fn default_max_import_reference_depth() -> usize {
+    64
+}
+
+/// How source files are checked for changes to decide if they need to be re-imported
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum SourceFileChangeDetection {
+    /// A source file has changed if its size or modified time changed
+    #[default]
+    Timestamp,
+    /// Like Timestamp, but if only the modified time changed, the contents are hashed to check if
+    /// the file really changed. Avoids re-importing files touched by VCS checkouts or rsync.
+    HashIfTimestampChanged,
+    /// The contents are always hashed, so a change is detected even if the size and modified
+    /// time are the same. This reads every source file each time import data is checked.
+    ContentHash,
 }
 
 #[derive(Debug, Clone)]
@@ -66,6 +111,50 @@ pub struct HydrateProjectConfiguration {
     pub source_file_locations: Vec<NamePathPair>,
 
     pub schema_codegen_jobs: Vec<SchemaCodegenJobs>,
+
+    // If true, data sources will fsync written files and their containing directories when saving.
+    // Saves are slower but will survive power loss once they complete. Off by default.
+    pub fsync_on_save: bool,
+
+    // Hash function for import data and built artifact contents. Fingerprints and other identity
+    // hashes are always siphash and don't use this.
+    pub content_hash_algorithm: ContentHashAlgorithm,
+
+    // How paths to source files and asset files are canonicalized. All of the paths above are
+    // canonicalized with this as well so that paths can be compared against them.
+    pub path_canonicalization: PathCanonicalization,
+
+    // Number of worker threads used to run build jobs. If not set, one thread per CPU is used.
+    pub build_thread_count: Option<usize>,
+
+    // If true, import data is packed into a few large segment files in import_data_path rather
+    // than written as one file per asset. Off by default.
+    pub pack_import_data: bool,
+
+    // How often the editor writes unsaved edits to a recovery file in job_data_path, so they can be
+    // restored after a crash. 0 disables autosave.
+    pub autosave_interval_seconds: u64,
+
+    // How source files are checked for changes when deciding if they need to be re-imported
+    pub source_file_change_detection: SourceFileChangeDetection,
+
+    // If true, each build writes a JSON report of where build time went next to its manifest.
+    // Off by default.
+    pub write_build_profile: bool,
+
+    // If set, build jobs share their results through a cache in this directory, which may be on a
+    // network share. Jobs whose inputs match a cached result fetch its artifacts instead of running.
+    pub build_cache_path: Option<PathBuf>,
+
+    // How deep a chain of source files referencing each other can be when importing, i.e. a mesh
+    // referencing a material referencing a texture is 3. Importing a longer chain fails, which
+    // guards against buggy or malicious source files.
+    pub max_import_reference_depth: usize,
+
+    // The directory containing the project file. Source files under it that aren't in one of the
+    // locations above are tracked relative to it in the "project" namespace, so moving the project
+    // to another location or machine doesn't break them.
+    pub project_root_path: PathBuf,
 }
 
 impl PathReferenceNamespaceResolver for HydrateProjectConfiguration {
@@ -91,6 +180,10 @@ impl PathReferenceNamespaceResolver for HydrateProjectConfiguration {
             }
         }
 
+        if namespace == PROJECT_ROOT_NAMESPACE {
+            return Some(self.project_root_path.clone());
+        }
+
         None
     }
 
@@ -116,8 +209,19 @@ impl PathReferenceNamespaceResolver for HydrateProjectConfiguration {
             }
         }
 
+        // An empty root would be a prefix of every path
+        if !self.project_root_path.as_os_str().is_empty() {
+            if let Ok(path) = path.strip_prefix(&self.project_root_path) {
+                return Some((PROJECT_ROOT_NAMESPACE.to_string(), path.to_path_buf()));
+            }
+        }
+
         None
     }
+
+    fn path_canonicalization(&self) -> PathCanonicalization {
+        self.path_canonicalization
+    }
 }
 
 impl HydrateProjectConfiguration {
@@ -137,6 +241,7 @@ impl HydrateProjectConfiguration {
     pub fn parse_dir_path(
         root_path: &Path,
         json_path: &str,
+        path_canonicalization: PathCanonicalization,
     ) -> Result<PathBuf, Box<dyn Error>> {
         // If it's not an absolute path, join it onto the path containing the project file
         let joined_path = Self::unverified_absolute_path(root_path, json_path);
@@ -147,31 +252,49 @@ impl HydrateProjectConfiguration {
         }
 
         // Canonicalize the path
-        Ok(dunce::canonicalize(&joined_path).map_err(|e| e.to_string())?)
+        Ok(canonicalize_path(&joined_path, path_canonicalization).map_err(|e| e.to_string())?)
     }
 
     pub fn read_from_path(path: &Path) -> Result<Self, Box<dyn Error>> {
-        let root_path = dunce::canonicalize(
+        let file_contents = std::fs::read_to_string(path)?;
+        let project_file: HydrateProjectConfigurationJson = serde_json::from_str(&file_contents)?;
+        let path_canonicalization = project_file.path_canonicalization;
+        let root_path = canonicalize_path(
             path.parent()
                 .ok_or_else(|| "Parent of project file path could not be found".to_string())?,
+            path_canonicalization,
         )?;
-        let file_contents = std::fs::read_to_string(path)?;
-        let project_file: HydrateProjectConfigurationJson = serde_json::from_str(&file_contents)?;
 
-        let import_data_path = Self::parse_dir_path(&root_path, &project_file.import_data_path)?;
-        let build_data_path = Self::parse_dir_path(&root_path, &project_file.build_data_path)?;
-        let job_data_path = Self::parse_dir_path(&root_path, &project_file.job_data_path)?;
+        let import_data_path = Self::parse_dir_path(
+            &root_path,
+            &project_file.import_data_path,
+            path_canonicalization,
+        )?;
+        let build_data_path = Self::parse_dir_path(
+            &root_path,
+            &project_file.build_data_path,
+            path_canonicalization,
+        )?;
+        let job_data_path = Self::parse_dir_path(
+            &root_path,
+            &project_file.job_data_path,
+            path_canonicalization,
+        )?;
 
         let mut schema_def_paths = Vec::default();
         for path in &project_file.schema_def_paths {
-            schema_def_paths.push(Self::parse_dir_path(&root_path, path)?)
+            schema_def_paths.push(Self::parse_dir_path(
+                &root_path,
+                path,
+                path_canonicalization,
+            )?)
         }
 
         let mut id_based_asset_sources = Vec::default();
         for pair in project_file.id_based_asset_sources {
             id_based_asset_sources.push(NamePathPair {
                 name: pair.name,
-                path: Self::parse_dir_path(&root_path, &pair.path)?,
+                path: Self::parse_dir_path(&root_path, &pair.path, path_canonicalization)?,
             });
         }
 
@@ -179,7 +302,7 @@ impl HydrateProjectConfiguration {
         for pair in project_file.path_based_asset_sources {
             path_based_asset_sources.push(NamePathPair {
                 name: pair.name,
-                path: Self::parse_dir_path(&root_path, &pair.path)?,
+                path: Self::parse_dir_path(&root_path, &pair.path, path_canonicalization)?,
             });
         }
 
@@ -187,10 +310,16 @@ impl HydrateProjectConfiguration {
         for pair in project_file.source_file_locations {
             source_file_locations.push(NamePathPair {
                 name: pair.name,
-                path: Self::parse_dir_path(&root_path, &pair.path)?,
+                path: Self::parse_dir_path(&root_path, &pair.path, path_canonicalization)?,
             });
         }
 
+        let build_cache_path = project_file
+            .build_cache_path
+            .as_ref()
+            .map(|x| Self::parse_dir_path(&root_path, x, path_canonicalization))
+            .transpose()?;
+
         // We don't canonicalize/verify the codegen paths
         let mut schema_codegen_jobs = Vec::default();
         for schema_codegen_job in project_file.schema_codegen_jobs {
@@ -222,9 +351,40 @@ impl HydrateProjectConfiguration {
             path_based_asset_sources,
             source_file_locations,
             schema_codegen_jobs,
+            fsync_on_save: project_file.fsync_on_save,
+            content_hash_algorithm: project_file.content_hash_algorithm,
+            path_canonicalization,
+            build_thread_count: project_file.build_thread_count,
+            pack_import_data: project_file.pack_import_data,
+            autosave_interval_seconds: project_file.autosave_interval_seconds,
+            source_file_change_detection: project_file.source_file_change_detection,
+            write_build_profile: project_file.write_build_profile,
+            build_cache_path,
+            max_import_reference_depth: project_file.max_import_reference_depth,
+            project_root_path: root_path,
         })
     }
 
+    // Moves the import data and build data directories and updates the paths in this configuration
+    // to match. Files in these directories are named by ID rather than by absolute path, so they
+    // don't need to be rewritten. The project file is not modified.
+    pub fn relocate_data_paths(
+        &mut self,
+        new_import_data_path: &Path,
+        new_build_data_path: &Path,
+    ) -> PipelineResult<()> {
+        move_directory(&self.import_data_path, new_import_data_path)?;
+        if let Err(e) = move_directory(&self.build_data_path, new_build_data_path) {
+            // Put the import data back so the configuration still matches what is on disk
+            move_directory(new_import_data_path, &self.import_data_path)?;
+            return Err(e);
+        }
+        self.import_data_path =
+            canonicalize_path(new_import_data_path, self.path_canonicalization)?;
+        self.build_data_path = canonicalize_path(new_build_data_path, self.path_canonicalization)?;
+        Ok(())
+    }
+
     pub fn locate_project_file(search_location: &Path) -> Result<Self, Box<dyn Error>> {
         let mut path = Some(search_location.to_path_buf());
         while let Some(p) = path {
@@ -243,3 +403,120 @@ impl HydrateProjectConfiguration {
         ))?
     }
 }
+
+// Returns the number of files and total size of the files in the directory and its subdirectories
+fn directory_contents_size(path: &Path) -> PipelineResult<(u64, u64)> {
+    let mut file_count = 0;
+    let mut byte_count = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            let (dir_file_count, dir_byte_count) = directory_contents_size(&entry.path())?;
+            file_count += dir_file_count;
+            byte_count += dir_byte_count;
+        } else {
+            file_count += 1;
+            byte_count += entry.metadata()?.len();
+        }
+    }
+
+    Ok((file_count, byte_count))
+}
+
+fn copy_directory(
+    old_path: &Path,
+    new_path: &Path,
+) -> PipelineResult<()> {
+    std::fs::create_dir_all(new_path)?;
+    for entry in std::fs::read_dir(old_path)? {
+        let entry = entry?;
+        let new_entry_path = new_path.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_directory(&entry.path(), &new_entry_path)?;
+        } else {
+            std::fs::copy(entry.path(), new_entry_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Moves a directory, falling back to copying it if it can't be renamed (i.e. it's being moved to
+// another drive). The destination must not exist or be empty.
+fn move_directory(
+    old_path: &Path,
+    new_path: &Path,
+) -> PipelineResult<()> {
+    if new_path.exists() {
+        if std::fs::read_dir(new_path)?.next().is_some() {
+            Err(format!(
+                "Cannot move {:?} to {:?} because the destination is not empty",
+                old_path, new_path
+            ))?;
+        }
+
+        std::fs::remove_dir(new_path)?;
+    }
+
+    if !old_path.exists() {
+        std::fs::create_dir_all(new_path)?;
+        return Ok(());
+    }
+
+    if let Some(parent) = new_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let expected_size = directory_contents_size(old_path)?;
+    if std::fs::rename(old_path, new_path).is_err() {
+        copy_directory(old_path, new_path)?;
+
+        // Don't delete the original unless everything was copied
+        if directory_contents_size(new_path)? != expected_size {
+            Err(format!(
+                "Copying {:?} to {:?} did not copy every file, the original was left in place",
+                old_path, new_path
+            ))?;
+        }
+
+        std::fs::remove_dir_all(old_path)?;
+    }
+
+    if directory_contents_size(new_path)? != expected_size {
+        Err(format!(
+            "Moving {:?} to {:?} did not move every file",
+            old_path, new_path
+        ))?;
+    }
+
+    Ok(())
+}
+
+impl HydrateProjectConfiguration {
+    /// A project with no asset sources that keeps all of its data under root_path and uses default
+    /// settings. Intended for tests, which can override the fields they care about.
+    pub fn for_test(root_path: &Path) -> Self {
+        HydrateProjectConfiguration {
+            schema_def_paths: vec![],
+            import_data_path: root_path.join("import_data"),
+            build_data_path: root_path.join("build_data"),
+            job_data_path: root_path.join("job_data"),
+            id_based_asset_sources: vec![],
+            path_based_asset_sources: vec![],
+            source_file_locations: vec![],
+            schema_codegen_jobs: vec![],
+            fsync_on_save: false,
+            content_hash_algorithm: Default::default(),
+            path_canonicalization: Default::default(),
+            build_thread_count: None,
+            pack_import_data: false,
+            autosave_interval_seconds: 0,
+            source_file_change_detection: Default::default(),
+            write_build_profile: false,
+            build_cache_path: None,
+            max_import_reference_depth: default_max_import_reference_depth(),
+            project_root_path: root_path.to_path_buf(),
+        }
+    }
+}
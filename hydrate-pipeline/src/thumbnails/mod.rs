@@ -20,6 +20,7 @@ use hydrate_schema::HashSet;
 use siphasher::sip128::Hasher128;
 use std::cell::RefCell;
 use std::hash::Hash;
+use std::panic::RefUnwindSafe;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -34,7 +35,7 @@ pub struct ThumbnailEnumeratedDependencies {
     pub(crate) gathered_data: Arc<Vec<u8>>,
 }
 
-pub trait ThumbnailProviderAbstract: Send + Sync {
+pub trait ThumbnailProviderAbstract: Send + Sync + RefUnwindSafe {
     // The type of asset that this builder handles
     fn asset_type_inner(&self) -> &'static str;
 
@@ -58,7 +59,8 @@ pub trait ThumbnailProviderAbstract: Send + Sync {
 
 struct ThumbnailProviderWrapper<T: ThumbnailProvider>(T);
 
-impl<T: ThumbnailProvider + Send + Sync> ThumbnailProviderAbstract for ThumbnailProviderWrapper<T>
+impl<T: ThumbnailProvider + Send + Sync + RefUnwindSafe> ThumbnailProviderAbstract
+    for ThumbnailProviderWrapper<T>
 where
     <T as ThumbnailProvider>::GatheredDataT:
         Hash + for<'a> serde::Deserialize<'a> + serde::Serialize,
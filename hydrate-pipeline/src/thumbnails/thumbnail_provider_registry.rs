@@ -6,16 +6,20 @@ use hydrate_base::hashing::HashMap;
 use hydrate_data::SchemaSet;
 use hydrate_schema::SchemaFingerprint;
 use std::hash::Hash;
+use std::panic::RefUnwindSafe;
 use std::sync::Arc;
 
 #[derive(Default)]
 pub struct ThumbnailProviderRegistryBuilder {
     thumbnail_providers: Vec<Arc<dyn ThumbnailProviderAbstract>>,
     default_thumbnails: HashMap<String, Arc<ThumbnailImage>>,
+    icons: HashMap<String, Arc<ThumbnailImage>>,
 }
 
 impl ThumbnailProviderRegistryBuilder {
-    pub fn register_thumbnail_provider<T: ThumbnailProvider + Send + Sync + Default + 'static>(
+    pub fn register_thumbnail_provider<
+        T: ThumbnailProvider + Send + Sync + RefUnwindSafe + Default + 'static,
+    >(
         &mut self
     ) where
         T::GatheredDataT: Hash + for<'a> serde::Deserialize<'a> + serde::Serialize,
@@ -24,7 +28,9 @@ impl ThumbnailProviderRegistryBuilder {
             .push(Arc::new(ThumbnailProviderWrapper(T::default())));
     }
 
-    pub fn register_thumbnail_provider_instance<T: ThumbnailProvider + Send + Sync + 'static>(
+    pub fn register_thumbnail_provider_instance<
+        T: ThumbnailProvider + Send + Sync + RefUnwindSafe + 'static,
+    >(
         &mut self,
         thumbnail_provider: T,
     ) where
@@ -34,6 +40,15 @@ impl ThumbnailProviderRegistryBuilder {
             .push(Arc::new(ThumbnailProviderWrapper(thumbnail_provider)));
     }
 
+    // Registers an icon that schemas can refer to by name with the "icon" markup
+    pub fn register_icon(
+        &mut self,
+        icon: impl Into<String>,
+        image: ThumbnailImage,
+    ) {
+        self.icons.insert(icon.into(), Arc::new(image));
+    }
+
     pub fn build(
         self,
         schema_set: &SchemaSet,
@@ -66,8 +81,29 @@ impl ThumbnailProviderRegistryBuilder {
             default_thumbnails.insert(named_type, v);
         }
 
+        let mut asset_type_to_icon = HashMap::default();
+        for (fingerprint, named_type) in schema_set.schemas() {
+            let Some(icon) = named_type
+                .try_as_record()
+                .and_then(|record| record.markup().icon.as_ref())
+            else {
+                continue;
+            };
+
+            if let Some(image) = self.icons.get(icon) {
+                asset_type_to_icon.insert(*fingerprint, image.clone());
+            } else {
+                log::warn!(
+                    "Schema {} uses icon {} which is not registered",
+                    named_type.name(),
+                    icon
+                );
+            }
+        }
+
         let inner = ThumbnailProviderRegistryInner {
             asset_type_to_provider,
+            asset_type_to_icon,
             thumbnail_providers: self.thumbnail_providers,
         };
 
@@ -80,6 +116,7 @@ impl ThumbnailProviderRegistryBuilder {
 pub struct ThumbnailProviderRegistryInner {
     thumbnail_providers: Vec<Arc<dyn ThumbnailProviderAbstract>>,
     asset_type_to_provider: HashMap<SchemaFingerprint, ThumbnailProviderId>,
+    asset_type_to_icon: HashMap<SchemaFingerprint, Arc<ThumbnailImage>>,
 }
 
 #[derive(Clone)]
@@ -105,4 +142,12 @@ impl ThumbnailProviderRegistry {
             .copied()
             .map(|x| &self.inner.thumbnail_providers[x.0])
     }
+
+    // The icon named by the schema's markup, if it was registered
+    pub fn icon_for_asset(
+        &self,
+        fingerprint: SchemaFingerprint,
+    ) -> Option<&Arc<ThumbnailImage>> {
+        self.inner.asset_type_to_icon.get(&fingerprint)
+    }
 }
@@ -4,7 +4,7 @@ use crate::thumbnails::thumbnail_thread_pool::{
 };
 use crate::thumbnails::ThumbnailProviderRegistry;
 use crate::{HydrateProjectConfiguration, ThumbnailApi, ThumbnailInputHash};
-use crossbeam_channel::Receiver;
+use crossbeam_channel::{Receiver, Sender};
 use hydrate_base::lru_cache::LruCache;
 use hydrate_base::AssetId;
 use hydrate_data::{DataSet, SchemaSet};
@@ -23,6 +23,7 @@ use std::sync::{Arc, Mutex};
 
 const THUMBNAIL_CACHE_SIZE: u32 = 1024;
 const STALENESS_CHECK_TIME_MILLISECONDS: u128 = 1000;
+const THREAD_COUNT: usize = 4;
 
 pub struct ThumbnailImage {
     pub width: u32,
@@ -50,6 +51,25 @@ pub struct ThumbnailState {
     last_staleness_check: Option<std::time::Instant>,
 }
 
+impl ThumbnailState {
+    // Shows the default image in place of the thumbnail and stops retrying it. Returns true if the
+    // image changed.
+    fn set_failed(
+        &mut self,
+        default_image: &Arc<ThumbnailImage>,
+    ) -> bool {
+        let old_thumbnail_hash = self.image.as_ref().map(|x| x.hash);
+        let new_thumbnail_hash = ThumbnailInputHash::null();
+        self.failed_to_load = true;
+        self.queued_request_input_hash = None;
+        self.image = Some(ThumbnailImageWithHash {
+            image: default_image.clone(),
+            hash: new_thumbnail_hash,
+        });
+        old_thumbnail_hash != Some(new_thumbnail_hash)
+    }
+}
+
 struct ThumbnailSystemStateInner {
     cache: LruCache<AssetId, ThumbnailState>,
     refreshed_thumbnails: HashSet<AssetId>,
@@ -107,8 +127,10 @@ pub struct ThumbnailSystem {
     thumbnail_provider_registry: ThumbnailProviderRegistry,
     default_image: Arc<ThumbnailImage>,
     thread_pool: Option<ThumbnailThreadPool>,
+    thread_pool_result_tx: Sender<ThumbnailThreadPoolOutcome>,
     thread_pool_result_rx: Receiver<ThumbnailThreadPoolOutcome>,
     current_requests: HashSet<ThumbnailInputHash>,
+    schema_set: SchemaSet,
 }
 
 impl Drop for ThumbnailSystem {
@@ -145,8 +167,8 @@ impl ThumbnailSystem {
             thumbnail_provider_registry.clone(),
             schema_set.clone(),
             thumbnail_api.clone(),
-            4,
-            thread_pool_result_tx,
+            THREAD_COUNT,
+            thread_pool_result_tx.clone(),
         );
 
         ThumbnailSystem {
@@ -154,9 +176,33 @@ impl ThumbnailSystem {
             thumbnail_provider_registry,
             default_image: Arc::new(default_image),
             thread_pool: Some(thread_pool),
+            thread_pool_result_tx,
             thread_pool_result_rx,
             current_requests: Default::default(),
+            schema_set: schema_set.clone(),
+        }
+    }
+
+    // Thumbnail providers read import data using the paths in hydrate_config. The worker threads
+    // hold the old configuration, so they are replaced once in-flight requests are done. Their
+    // results are handled by the next update() as usual.
+    pub fn relocate(
+        &mut self,
+        hydrate_config: &HydrateProjectConfiguration,
+    ) {
+        let thread_pool = self.thread_pool.take().unwrap();
+        while !thread_pool.is_idle() {
+            std::thread::sleep(std::time::Duration::from_millis(1));
         }
+        thread_pool.finish();
+
+        self.thread_pool = Some(ThumbnailThreadPool::new(
+            self.thumbnail_provider_registry.clone(),
+            self.schema_set.clone(),
+            ThumbnailApi::new(hydrate_config, &self.schema_set),
+            THREAD_COUNT,
+            self.thread_pool_result_tx.clone(),
+        ));
     }
 
     pub fn update(
@@ -225,9 +271,25 @@ impl ThumbnailSystem {
             };
 
             // Calculate the current input hash
-            let dependencies = provider
-                .gather_inner(asset_id, data_set, schema_set)
-                .unwrap();
+            let dependencies = match std::panic::catch_unwind(|| {
+                provider.gather_inner(asset_id, data_set, schema_set)
+            }) {
+                Ok(Ok(dependencies)) => dependencies,
+                Ok(Err(e)) => {
+                    log::warn!("Thumbnail gather failed for {:?}: {:?}", asset_id, e);
+                    if thumbnail_state.set_failed(&self.default_image) {
+                        refreshed_thumbnails.push(asset_id);
+                    }
+                    continue;
+                }
+                Err(_) => {
+                    log::warn!("Panic detected in thumbnail provider for {:?}", asset_id);
+                    if thumbnail_state.set_failed(&self.default_image) {
+                        refreshed_thumbnails.push(asset_id);
+                    }
+                    continue;
+                }
+            };
             if self
                 .current_requests
                 .contains(&dependencies.thumbnail_input_hash)
@@ -285,7 +347,9 @@ impl ThumbnailSystem {
                             }
                             Err(e) => {
                                 log::warn!("Thumbnail creation failed: {:?}", e);
-                                thumbnail_state.failed_to_load = true;
+                                if thumbnail_state.set_failed(&self.default_image) {
+                                    refreshed_thumbnails.push(msg.request.asset_id);
+                                }
                             }
                         }
                     }
@@ -298,3 +362,127 @@ impl ThumbnailSystem {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::thumbnails::{
+        ThumbnailProvider, ThumbnailProviderGatherContext, ThumbnailProviderRegistryBuilder,
+        ThumbnailProviderRenderContext,
+    };
+    use crate::PipelineResult;
+    use hydrate_data::{AssetLocation, AssetName, SchemaLinker, SchemaSetBuilder};
+
+    // Renders a 2x2 image for every asset except the one it is told to panic on
+    struct PanickingThumbnailProvider {
+        panic_asset_id: AssetId,
+    }
+
+    impl ThumbnailProvider for PanickingThumbnailProvider {
+        type GatheredDataT = u128;
+
+        fn asset_type(&self) -> &'static str {
+            "ThumbnailTestAsset"
+        }
+
+        fn version(&self) -> u32 {
+            1
+        }
+
+        fn gather(
+            &self,
+            context: ThumbnailProviderGatherContext,
+        ) -> Self::GatheredDataT {
+            context.asset_id.as_uuid().as_u128()
+        }
+
+        fn render<'a>(
+            &'a self,
+            context: &'a ThumbnailProviderRenderContext<'a>,
+            _gathered_data: Self::GatheredDataT,
+        ) -> PipelineResult<ThumbnailImage> {
+            if context.asset_id == self.panic_asset_id {
+                panic!("Thumbnail provider panicked");
+            }
+
+            Ok(ThumbnailImage {
+                width: 2,
+                height: 2,
+                pixel_data: vec![255; 16],
+            })
+        }
+    }
+
+    fn update_until_idle(
+        thumbnail_system: &mut ThumbnailSystem,
+        data_set: &DataSet,
+        schema_set: &SchemaSet,
+    ) {
+        for _ in 0..1000 {
+            thumbnail_system.update(data_set, schema_set);
+            if thumbnail_system.current_requests.is_empty()
+                && thumbnail_system.thread_pool.as_ref().unwrap().is_idle()
+            {
+                return;
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        panic!("Thumbnail system did not become idle");
+    }
+
+    #[test]
+    fn panicking_provider_falls_back_to_default_image() {
+        let mut linker = SchemaLinker::default();
+        linker
+            .register_record_type("ThumbnailTestAsset", uuid::Uuid::new_v4(), |_| {})
+            .unwrap();
+        let mut schema_set = SchemaSetBuilder::default();
+        schema_set.add_linked_types(linker).unwrap();
+        let schema_set = schema_set.build();
+
+        let record = schema_set
+            .find_named_type("ThumbnailTestAsset")
+            .unwrap()
+            .as_record()
+            .unwrap()
+            .clone();
+        let mut data_set = DataSet::default();
+        let panic_asset_id =
+            data_set.new_asset(AssetName::new("panics"), AssetLocation::null(), &record);
+        let asset_id =
+            data_set.new_asset(AssetName::new("renders"), AssetLocation::null(), &record);
+        let later_asset_id =
+            data_set.new_asset(AssetName::new("later"), AssetLocation::null(), &record);
+
+        let mut registry = ThumbnailProviderRegistryBuilder::default();
+        registry
+            .register_thumbnail_provider_instance(PanickingThumbnailProvider { panic_asset_id });
+        let registry = registry.build(&schema_set);
+
+        let root_path = std::env::temp_dir().join(format!("hydrate-{}", uuid::Uuid::new_v4()));
+        let project_config = HydrateProjectConfiguration::for_test(&root_path);
+        let mut thumbnail_system = ThumbnailSystem::new(&project_config, registry, &schema_set);
+        let system_state = thumbnail_system.system_state().clone();
+
+        assert!(system_state.request(panic_asset_id).is_none());
+        assert!(system_state.request(asset_id).is_none());
+        update_until_idle(&mut thumbnail_system, &data_set, &schema_set);
+
+        // The asset whose provider panicked shows the default image, the other one still renders
+        let panic_image = system_state.request(panic_asset_id).unwrap();
+        assert_eq!(panic_image.image.width, 1);
+        assert!(panic_image.hash == ThumbnailInputHash::null());
+        assert_eq!(system_state.request(asset_id).unwrap().image.width, 2);
+
+        let refreshed_thumbnails = system_state.take_refreshed_thumbnails();
+        assert!(refreshed_thumbnails.contains(&panic_asset_id));
+        assert!(refreshed_thumbnails.contains(&asset_id));
+
+        // The system keeps generating thumbnails after the panic
+        assert!(system_state.request(later_asset_id).is_none());
+        update_until_idle(&mut thumbnail_system, &data_set, &schema_set);
+        assert_eq!(system_state.request(later_asset_id).unwrap().image.width, 2);
+    }
+}
@@ -40,28 +40,21 @@ fn do_build(
     thumbnail_provider_registry: &ThumbnailProviderRegistry,
     schema_set: &SchemaSet,
     thumbnail_api: &ThumbnailApi,
-    request: ThumbnailThreadPoolRequestRunJob,
-) -> ThumbnailThreadPoolOutcome {
+    request: &ThumbnailThreadPoolRequestRunJob,
+) -> PipelineResult<ThumbnailImage> {
     profiling::scope!(&format!("Build Thumbnail {}", request.asset_id));
 
     // Execute the job
     let thumbnail_provider = thumbnail_provider_registry
         .provider_for_asset(request.asset_type)
         .unwrap();
-    let result = {
-        profiling::scope!(&format!("JobProcessor::run_inner"));
-        thumbnail_provider.render_inner(
-            request.asset_id,
-            &*request.dependencies.gathered_data,
-            schema_set,
-            thumbnail_api,
-        )
-    };
-
-    ThumbnailThreadPoolOutcome::RunJobComplete(ThumbnailThreadPoolOutcomeRunJobComplete {
-        request,
-        result,
-    })
+    profiling::scope!(&format!("JobProcessor::run_inner"));
+    thumbnail_provider.render_inner(
+        request.asset_id,
+        &*request.dependencies.gathered_data,
+        schema_set,
+        thumbnail_api,
+    )
 
     //TODO: Write to file
     //hydrate_base::uuid_path::uuid_to_path()
@@ -90,14 +83,25 @@ impl ThumbnailWorkerThread {
                                 ThumbnailThreadPoolRequest::RunJob(msg) => {
                                     profiling::scope!("ThumbnailThreadPoolRequest::RequestBuild");
 
-                                    let result = do_build(
-                                        &thumbnail_provider_registry,
-                                        &schema_set,
-                                        &thumbnail_api,
-                                        msg
-                                    );
-
-                                    outcome_tx.send(result).unwrap();
+                                    // A panicking provider must not take the worker thread down with it
+                                    let result = std::panic::catch_unwind(|| {
+                                        do_build(
+                                            &thumbnail_provider_registry,
+                                            &schema_set,
+                                            &thumbnail_api,
+                                            &msg
+                                        )
+                                    });
+
+                                    let result = match result {
+                                        Ok(result) => result,
+                                        Err(_) => Err("Panic detected in thumbnail provider.".into()),
+                                    };
+
+                                    outcome_tx.send(ThumbnailThreadPoolOutcome::RunJobComplete(ThumbnailThreadPoolOutcomeRunJobComplete {
+                                        request: msg,
+                                        result,
+                                    })).unwrap();
                                     active_request_count.fetch_sub(1, Ordering::Release);
                                 },
                             }
@@ -159,6 +163,10 @@ impl ThumbnailThreadPool {
         }
     }
 
+    pub fn is_idle(&self) -> bool {
+        self.active_request_count.load(Ordering::Acquire) == 0
+    }
+
     pub(crate) fn add_request(
         &self,
         request: ThumbnailThreadPoolRequest,
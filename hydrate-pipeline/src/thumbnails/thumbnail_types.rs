@@ -41,7 +41,7 @@ impl ThumbnailApi {
         asset_id: AssetId,
     ) -> PipelineResult<ImportData> {
         crate::import::load_import_data(
-            &self.inner.hydrate_config.import_data_path,
+            &self.inner.hydrate_config,
             &self.inner.schema_set,
             asset_id,
         )
@@ -103,9 +103,11 @@ impl<'a> ThumbnailProviderRenderContext<'a> {
             Err(DataSetError::InvalidSchema)?;
         }
 
+        // The reader shares the fetched import data through the Arc rather than cloning it. It
+        // can't borrow it because the cache is behind a RefCell.
         return Ok(<T as Record>::Reader::new(
             PropertyPath::default(),
-            DataContainerRef::from_single_object_arc(import_data.clone(), self.schema_set),
+            DataContainerRef::from_single_object_arc(import_data, self.schema_set),
         ));
     }
 }
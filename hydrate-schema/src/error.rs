@@ -10,6 +10,7 @@ pub enum DataSetError {
     DuplicateAssetId,
     DuplicateEntryKey,
     EntryKeyNotFound,
+    EntryIndexOutOfRange,
     AssetNotFound,
     ImportDataNotFound,
     SingleObjectDoesNotMatchSchema,
@@ -22,6 +23,9 @@ pub enum DataSetError {
     NewLocationIsChildOfCurrentAsset,
     UnknownPathNamespace,
     InvalidPath,
+    InvalidAssetName,
+    FieldReadOnly,
+    EditContextsOverlap,
 
     // the data was in a container, but moved out of it (i.e. Option::take())
     DataTaken,
@@ -7,7 +7,9 @@ use std::fmt::{Debug, Formatter};
 use uuid::Uuid;
 
 mod property_path;
-pub use property_path::PropertyPath;
+pub use property_path::{
+    escape_property_path_segment, split_property_path, unescape_property_path_segment, PropertyPath,
+};
 
 mod schema;
 pub use schema::*;
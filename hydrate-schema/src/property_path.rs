@@ -1,3 +1,57 @@
+use std::borrow::Cow;
+
+// Property paths are segments joined by '.'. Field names, array indices and entry UUIDs never
+// contain '.', but a segment derived from an arbitrary string might, so those are added with
+// push_raw. It percent-encodes '.' (and '%' itself) so the path splits back into the same segments.
+const PROPERTY_PATH_SEPARATOR: char = '.';
+const ESCAPED_SEPARATOR: &str = "%2E";
+const ESCAPED_PERCENT: &str = "%25";
+
+pub fn escape_property_path_segment(segment: &str) -> Cow<'_, str> {
+    if segment.contains([PROPERTY_PATH_SEPARATOR, '%']) {
+        Cow::Owned(
+            segment
+                .replace('%', ESCAPED_PERCENT)
+                .replace(PROPERTY_PATH_SEPARATOR, ESCAPED_SEPARATOR),
+        )
+    } else {
+        Cow::Borrowed(segment)
+    }
+}
+
+pub fn unescape_property_path_segment(segment: &str) -> Cow<'_, str> {
+    if !segment.contains('%') {
+        return Cow::Borrowed(segment);
+    }
+
+    let mut unescaped = String::with_capacity(segment.len());
+    let mut remaining = segment;
+    while let Some(position) = remaining.find('%') {
+        unescaped.push_str(&remaining[0..position]);
+        remaining = &remaining[position..];
+        if remaining.starts_with(ESCAPED_SEPARATOR) {
+            unescaped.push(PROPERTY_PATH_SEPARATOR);
+            remaining = &remaining[ESCAPED_SEPARATOR.len()..];
+        } else if remaining.starts_with(ESCAPED_PERCENT) {
+            unescaped.push('%');
+            remaining = &remaining[ESCAPED_PERCENT.len()..];
+        } else {
+            // Not something we escaped, keep it as is
+            unescaped.push('%');
+            remaining = &remaining[1..];
+        }
+    }
+    unescaped.push_str(remaining);
+
+    Cow::Owned(unescaped)
+}
+
+// Splits a path into its segments without unescaping them. Escaped segments never contain the
+// separator, so this is safe for any path built with push/push_raw.
+pub fn split_property_path(path: &str) -> std::str::Split<'_, char> {
+    path.split(PROPERTY_PATH_SEPARATOR)
+}
+
 #[derive(Default, Clone)]
 pub struct PropertyPath(String);
 
@@ -11,10 +65,34 @@ impl PropertyPath {
         } else if str.is_empty() {
             PropertyPath(self.0.to_string())
         } else {
-            PropertyPath(format!("{}.{}", self.0, str))
+            PropertyPath(format!("{}{}{}", self.0, PROPERTY_PATH_SEPARATOR, str))
         }
     }
 
+    // Appends a single segment that may contain any characters, escaping it as needed
+    pub fn push_raw(
+        &self,
+        segment: &str,
+    ) -> PropertyPath {
+        self.push(&escape_property_path_segment(segment))
+    }
+
+    // Parses a path string, i.e. one produced by path()
+    pub fn parse(path: &str) -> PropertyPath {
+        PropertyPath(path.to_string())
+    }
+
+    // The unescaped segments of the path
+    pub fn segments(&self) -> Vec<String> {
+        if self.0.is_empty() {
+            return vec![];
+        }
+
+        split_property_path(&self.0)
+            .map(|x| unescape_property_path_segment(x).into_owned())
+            .collect()
+    }
+
     pub fn path(&self) -> &str {
         &self.0
     }
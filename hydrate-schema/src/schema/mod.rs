@@ -19,8 +19,8 @@ pub use record::*;
 mod static_array;
 pub use static_array::*;
 
+use crate::{split_property_path, HashSet, PropertyPath, SchemaFingerprint};
 use crate::{DataSetError, DataSetResult, HashMap};
-use crate::{HashSet, PropertyPath, SchemaFingerprint};
 use std::hash::Hash;
 use std::str::FromStr;
 use uuid::Uuid;
@@ -96,7 +96,7 @@ impl SchemaNamedType {
         let mut new_schema = Schema::Record(new_root_named_type.fingerprint());
 
         log::trace!("migrate property name {:?}", old_path.as_ref());
-        let old_split_path = old_path.as_ref().split(".");
+        let old_split_path = split_property_path(old_path.as_ref());
         let mut new_path = PropertyPath::default();
 
         // Iterate the path segments to find
@@ -142,7 +142,7 @@ impl SchemaNamedType {
     ) -> Option<Schema> {
         let mut schema = Schema::Record(self.fingerprint());
 
-        let split_path = path.as_ref().split(".");
+        let split_path = split_property_path(path.as_ref());
 
         // Iterate the path segments to find
         for path_segment in split_path {
@@ -307,6 +307,48 @@ impl Schema {
         }
     }
 
+    /// Produces a readable name for the type, i.e. "DynamicArray<Vec3>" or "Nullable<String>".
+    /// Named types are referred to by name.
+    pub fn type_name(
+        &self,
+        named_types: &HashMap<SchemaFingerprint, SchemaNamedType>,
+    ) -> String {
+        let named_type_name = |fingerprint: &SchemaFingerprint| {
+            named_types
+                .get(fingerprint)
+                .map(|x| x.name().to_string())
+                .unwrap_or_else(|| format!("<unknown type {:?}>", fingerprint))
+        };
+
+        match self {
+            Schema::Nullable(inner) => format!("Nullable<{}>", inner.type_name(named_types)),
+            Schema::Boolean => "Boolean".to_string(),
+            Schema::I32 => "I32".to_string(),
+            Schema::I64 => "I64".to_string(),
+            Schema::U32 => "U32".to_string(),
+            Schema::U64 => "U64".to_string(),
+            Schema::F32 => "F32".to_string(),
+            Schema::F64 => "F64".to_string(),
+            Schema::Bytes => "Bytes".to_string(),
+            Schema::String => "String".to_string(),
+            Schema::StaticArray(x) => format!(
+                "StaticArray<{}; {}>",
+                x.item_type().type_name(named_types),
+                x.length()
+            ),
+            Schema::DynamicArray(x) => {
+                format!("DynamicArray<{}>", x.item_type().type_name(named_types))
+            }
+            Schema::Map(x) => format!(
+                "Map<{}, {}>",
+                x.key_type().type_name(named_types),
+                x.value_type().type_name(named_types)
+            ),
+            Schema::AssetRef(fingerprint) => format!("AssetRef<{}>", named_type_name(fingerprint)),
+            Schema::Record(fingerprint) | Schema::Enum(fingerprint) => named_type_name(fingerprint),
+        }
+    }
+
     pub fn types_are_interchangeable(
         old_parent_schema: &Schema,
         new_parent_schema: &Schema,
@@ -1,6 +1,7 @@
 use super::Schema;
 use crate::{
-    HashMap, SchemaDefRecordFieldMarkup, SchemaDefRecordMarkup, SchemaFingerprint, SchemaNamedType,
+    split_property_path, HashMap, SchemaDefRecordFieldMarkup, SchemaDefRecordMarkup,
+    SchemaFingerprint, SchemaNamedType,
 };
 use std::ops::Deref;
 use std::sync::Arc;
@@ -156,6 +157,88 @@ impl SchemaRecord {
         self.fields.iter().find(|x| x.name == field_name)
     }
 
+    // Finds the field the property belongs to, which may be a field of a nested record. For
+    // example "a.b.<uuid>" returns field "b" of the record stored in field "a".
+    pub fn find_property_field<'a>(
+        &'a self,
+        path: impl AsRef<str>,
+        named_types: &'a HashMap<SchemaFingerprint, SchemaNamedType>,
+    ) -> Option<&'a SchemaRecordField> {
+        let mut split_path = split_property_path(path.as_ref());
+        let mut field = self.find_field_from_name(split_path.next()?)?;
+        let mut schema = field.field_schema();
+
+        for path_segment in split_path {
+            if let Schema::Record(fingerprint) = schema {
+                field = named_types
+                    .get(fingerprint)?
+                    .try_as_record()?
+                    .find_field_from_name(path_segment)?;
+                schema = field.field_schema();
+            } else {
+                schema = schema.find_field_schema(path_segment, named_types)?;
+            }
+        }
+
+        Some(field)
+    }
+
+    // True if the property belongs to a field marked build_ignore, or is nested within one
+    pub fn is_property_build_ignored(
+        &self,
+        path: impl AsRef<str>,
+        named_types: &HashMap<SchemaFingerprint, SchemaNamedType>,
+    ) -> bool {
+        self.any_field_on_property_path(path, named_types, |x| x.build_ignore)
+    }
+
+    // True if the property belongs to a field marked readonly, or is nested within one
+    pub fn is_property_readonly(
+        &self,
+        path: impl AsRef<str>,
+        named_types: &HashMap<SchemaFingerprint, SchemaNamedType>,
+    ) -> bool {
+        self.any_field_on_property_path(path, named_types, |x| x.readonly)
+    }
+
+    fn any_field_on_property_path<F: Fn(&SchemaDefRecordFieldMarkup) -> bool>(
+        &self,
+        path: impl AsRef<str>,
+        named_types: &HashMap<SchemaFingerprint, SchemaNamedType>,
+        f: F,
+    ) -> bool {
+        let mut split_path = split_property_path(path.as_ref());
+        let Some(mut field) = split_path.next().and_then(|x| self.find_field_from_name(x)) else {
+            return false;
+        };
+        let mut schema = field.field_schema();
+
+        for path_segment in split_path {
+            if f(field.markup()) {
+                return true;
+            }
+
+            if let Schema::Record(fingerprint) = schema {
+                let Some(nested_field) = named_types
+                    .get(fingerprint)
+                    .and_then(|x| x.try_as_record())
+                    .and_then(|x| x.find_field_from_name(path_segment))
+                else {
+                    return false;
+                };
+                field = nested_field;
+                schema = field.field_schema();
+            } else if let Some(nested_schema) = schema.find_field_schema(path_segment, named_types)
+            {
+                schema = nested_schema;
+            } else {
+                return false;
+            }
+        }
+
+        f(field.markup())
+    }
+
     pub fn find_field_from_field_uuid(
         &self,
         field_uuid: Uuid,
@@ -294,6 +294,30 @@ fn parse_json_schema_def_record_field(
         })?);
     }
 
+    if let Some(owned) = object.get("owned") {
+        markup.owned = owned
+            .as_bool()
+            .ok_or_else(|| SchemaDefParserError::String("owned must be a bool".to_string()))?;
+    }
+
+    if let Some(build_ignore) = object.get("build_ignore") {
+        markup.build_ignore = build_ignore.as_bool().ok_or_else(|| {
+            SchemaDefParserError::String("build_ignore must be a bool".to_string())
+        })?;
+    }
+
+    if let Some(hidden) = object.get("hidden") {
+        markup.hidden = hidden
+            .as_bool()
+            .ok_or_else(|| SchemaDefParserError::String("hidden must be a bool".to_string()))?;
+    }
+
+    if let Some(readonly) = object.get("readonly") {
+        markup.readonly = readonly
+            .as_bool()
+            .ok_or_else(|| SchemaDefParserError::String("readonly must be a bool".to_string()))?;
+    }
+
     if markup.clamp_min.unwrap_or(f64::MIN) > markup.ui_min.unwrap_or(f64::MIN) {
         Err(SchemaDefParserError::String(
             "clamp_min must be <= ui_min".to_string(),
@@ -422,6 +446,14 @@ fn parse_json_schema_def_record(
         }
     }
 
+    if let Some(icon) = json_object.get("icon") {
+        markup.icon = Some(
+            icon.as_str()
+                .ok_or_else(|| SchemaDefParserError::String("icon must be a string".to_string()))?
+                .to_string(),
+        );
+    }
+
     if let Some(tags) = json_object.get("tags") {
         let tags = tags.as_array().ok_or_else(|| {
             SchemaDefParserError::String("tags must be an array of strings".to_string())
@@ -437,13 +469,13 @@ fn parse_json_schema_def_record(
         }
     }
 
-    Ok(SchemaDefRecord {
-        type_name: name_str.to_string(),
+    Ok(SchemaDefRecord::new(
+        name_str.to_string(),
         type_uuid,
         aliases,
         fields,
         markup,
-    })
+    )?)
 }
 
 fn parse_json_schema_def_enum_symbol(
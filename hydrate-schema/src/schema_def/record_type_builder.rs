@@ -1,4 +1,4 @@
-use super::schema_def::{SchemaDefDynamicArray, SchemaDefType};
+use super::schema_def::{SchemaDefDynamicArray, SchemaDefMap, SchemaDefType};
 use crate::{SchemaDefRecordFieldMarkup, SchemaDefRecordMarkup};
 use uuid::Uuid;
 
@@ -17,6 +17,38 @@ impl RecordTypeFieldBuilder {
     ) {
         self.aliases.push(alias.into());
     }
+
+    // Marks the referenced assets as part of the referencing asset, see SchemaDefRecordFieldMarkup
+    pub fn set_owned(
+        &mut self,
+        owned: bool,
+    ) {
+        self.markup.owned = owned;
+    }
+
+    // Excludes the field from build input hashing, see SchemaDefRecordFieldMarkup
+    pub fn set_build_ignore(
+        &mut self,
+        build_ignore: bool,
+    ) {
+        self.markup.build_ignore = build_ignore;
+    }
+
+    // Hides the field in the inspector, see SchemaDefRecordFieldMarkup
+    pub fn set_hidden(
+        &mut self,
+        hidden: bool,
+    ) {
+        self.markup.hidden = hidden;
+    }
+
+    // Prevents editing the field through an EditContext, see SchemaDefRecordFieldMarkup
+    pub fn set_readonly(
+        &mut self,
+        readonly: bool,
+    ) {
+        self.markup.readonly = readonly;
+    }
 }
 
 #[derive(Default)]
@@ -34,6 +66,14 @@ impl RecordTypeBuilder {
         self.aliases.push(alias.into())
     }
 
+    // Sets the icon shown for assets of this type, see SchemaDefRecordMarkup
+    pub fn set_icon(
+        &mut self,
+        icon: impl Into<String>,
+    ) {
+        self.markup.icon = Some(icon.into());
+    }
+
     pub fn add_nullable(
         &mut self,
         name: impl Into<String>,
@@ -216,6 +256,25 @@ impl RecordTypeBuilder {
         });
     }
 
+    pub fn add_map(
+        &mut self,
+        name: impl Into<String>,
+        field_uuid: Uuid,
+        key_schema: SchemaDefType,
+        value_schema: SchemaDefType,
+    ) {
+        self.fields.push(RecordTypeFieldBuilder {
+            field_type: SchemaDefType::Map(SchemaDefMap::new(
+                Box::new(key_schema),
+                Box::new(value_schema),
+            )),
+            field_uuid,
+            aliases: Default::default(),
+            name: name.into(),
+            markup: Default::default(),
+        });
+    }
+
     pub fn add_named_type(
         &mut self,
         name: impl Into<String>,
@@ -15,6 +15,8 @@ pub enum SchemaDefValidationError {
     InvalidMapKeyType(String, String),
     // AssetRef can only reference named types that are records
     InvalidAssetRefInnerType(String, String),
+    // Only fields that contain AssetRefs can be marked as owned
+    InvalidOwnedFieldType(String, String),
 }
 
 impl std::fmt::Display for SchemaDefValidationError {
@@ -53,6 +55,11 @@ impl std::fmt::Display for SchemaDefValidationError {
                 "Schema {} references an AssetRef that references {} but it is not a record",
                 schema_name, invalid_asset_ref_inner_type
             ),
+            SchemaDefValidationError::InvalidOwnedFieldType(schema_name, field_name) => write!(
+                f,
+                "Schema {} has field {} marked as owned, but it does not contain an AssetRef",
+                schema_name, field_name
+            ),
         }
     }
 }
@@ -164,6 +171,16 @@ pub struct SchemaDefMap {
 }
 
 impl SchemaDefMap {
+    pub fn new(
+        key_type: Box<SchemaDefType>,
+        value_type: Box<SchemaDefType>,
+    ) -> Self {
+        SchemaDefMap {
+            key_type,
+            value_type,
+        }
+    }
+
     fn apply_type_aliases(
         &mut self,
         aliases: &HashMap<String, String>,
@@ -218,7 +235,6 @@ impl SchemaDefMap {
 //code generation
 //if it is an asset that can be created in ui
 //if it is import data
-//hide in inspector?
 
 #[derive(Default, Debug, Clone)]
 pub struct SchemaDefRecordFieldMarkup {
@@ -238,6 +254,21 @@ pub struct SchemaDefRecordFieldMarkup {
     // this range
     pub ui_min: Option<f64>,
     pub ui_max: Option<f64>,
+
+    // Assets referenced by an owned field are part of the asset that references them, and are
+    // deleted along with it. Only valid for fields that contain AssetRefs.
+    pub owned: bool,
+
+    // Editor-only data (i.e. notes) that does not affect build output. Changing the field does not
+    // change the asset's build input hash, so it does not cause a rebuild.
+    pub build_ignore: bool,
+
+    // Internal or computed data (i.e. a cached hash) that is not shown in the inspector
+    pub hidden: bool,
+
+    // Shown in the inspector but can't be edited through an EditContext. Importers and builders
+    // can still set it through the DataSet.
+    pub readonly: bool,
 }
 
 impl SchemaDefRecordFieldMarkup {
@@ -343,6 +374,10 @@ pub struct SchemaDefRecordMarkup {
 
     pub default_thumbnail: Option<PathBuf>,
 
+    // Identifies a small icon registered with the editor, shown for assets of this type when there
+    // is no thumbnail for them. Unlike default_thumbnail, this is a name rather than an image file.
+    pub icon: Option<String>,
+
     // Tags can be used to query for a list of records that meet some criteria
     pub tags: HashSet<String>,
     //description: String,
@@ -378,6 +413,15 @@ impl SchemaDefRecord {
             }
         }
 
+        for field in &fields {
+            if field.markup.owned && !field.field_type.contains_asset_ref() {
+                Err(SchemaDefValidationError::InvalidOwnedFieldType(
+                    type_name.clone(),
+                    field.field_name.to_string(),
+                ))?;
+            }
+        }
+
         Ok(SchemaDefRecord {
             type_name,
             type_uuid,
@@ -581,6 +625,18 @@ pub enum SchemaDefType {
 }
 
 impl SchemaDefType {
+    // True for AssetRefs, and for containers/nullables whose values are AssetRefs
+    fn contains_asset_ref(&self) -> bool {
+        match self {
+            SchemaDefType::AssetRef(_) => true,
+            SchemaDefType::Nullable(x) => x.contains_asset_ref(),
+            SchemaDefType::StaticArray(x) => x.item_type.contains_asset_ref(),
+            SchemaDefType::DynamicArray(x) => x.item_type.contains_asset_ref(),
+            SchemaDefType::Map(x) => x.value_type.contains_asset_ref(),
+            _ => false,
+        }
+    }
+
     fn apply_type_aliases(
         &mut self,
         aliases: &HashMap<String, String>,
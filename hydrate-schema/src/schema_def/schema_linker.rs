@@ -66,7 +66,8 @@ impl SchemaLinker {
         self.types.keys().cloned().collect()
     }
 
-    fn add_named_type(
+    // For types defined in code that don't go through register_record_type/register_enum_type
+    pub fn add_named_type(
         &mut self,
         named_type: SchemaDefNamedType,
     ) -> SchemaLinkerResult<()> {